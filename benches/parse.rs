@@ -0,0 +1,74 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use oox::{
+    docx::wml::document::{BorderType, Document, Jc, NumberFormat, PPr, RPr, ShdType, UnderlineType},
+    xml::XmlNode,
+};
+use std::{fs::File, io::Read, path::PathBuf, str::FromStr};
+use zip::ZipArchive;
+
+fn read_sample_part(file_name: &str, part_name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join(file_name);
+    let mut zipper = ZipArchive::new(File::open(path).unwrap()).unwrap();
+    let mut xml = String::new();
+    zipper.by_name(part_name).unwrap().read_to_string(&mut xml).unwrap();
+    xml
+}
+
+fn parse_benchmark(c: &mut Criterion) {
+    let document_xml = read_sample_part("sample.docx", "word/document.xml");
+    let styles_xml = read_sample_part("sample.docx", "word/styles.xml");
+
+    c.bench_function("xml_node_document", |b| {
+        b.iter(|| XmlNode::from_str(&document_xml).unwrap())
+    });
+    c.bench_function("xml_node_styles", |b| {
+        b.iter(|| XmlNode::from_str(&styles_xml).unwrap())
+    });
+
+    let document_node = XmlNode::from_str(&document_xml).unwrap();
+    c.bench_function("docx_document", |b| {
+        b.iter(|| Document::from_xml_element(&document_node).unwrap())
+    });
+
+    let styles_node = XmlNode::from_str(&styles_xml).unwrap();
+    let run_properties: Vec<&XmlNode> = [&document_node, &styles_node]
+        .iter()
+        .flat_map(|xml_node| xml_node.select("//w:rPr").unwrap())
+        .collect();
+    let paragraph_properties: Vec<&XmlNode> = [&document_node, &styles_node]
+        .iter()
+        .flat_map(|xml_node| xml_node.select("//w:pPr").unwrap())
+        .collect();
+    c.bench_function("docx_run_properties", |b| {
+        b.iter(|| {
+            for xml_node in &run_properties {
+                RPr::from_xml_element(xml_node).unwrap();
+            }
+        })
+    });
+    c.bench_function("docx_paragraph_properties", |b| {
+        b.iter(|| {
+            for xml_node in &paragraph_properties {
+                PPr::from_xml_element(xml_node).unwrap();
+            }
+        })
+    });
+}
+
+fn enum_benchmark(c: &mut Criterion) {
+    c.bench_function("enum_from_str", |b| {
+        b.iter(|| {
+            black_box("single".parse::<BorderType>().unwrap());
+            black_box("thinThickSmallGap".parse::<BorderType>().unwrap());
+            black_box("clear".parse::<ShdType>().unwrap());
+            black_box("pct25".parse::<ShdType>().unwrap());
+            black_box("both".parse::<Jc>().unwrap());
+            black_box("single".parse::<UnderlineType>().unwrap());
+            black_box("decimal".parse::<NumberFormat>().unwrap());
+            black_box("lowerRoman".parse::<NumberFormat>().unwrap());
+        })
+    });
+}
+
+criterion_group!(benches, parse_benchmark, enum_benchmark);
+criterion_main!(benches);
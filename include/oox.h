@@ -0,0 +1,58 @@
+/*
+ * C API of the oox crate, built with `cargo rustc --release --features ffi --crate-type cdylib`.
+ *
+ * Keep in sync with src/ffi.rs, see the documentation of that module for the ownership and error handling rules.
+ */
+
+#ifndef OOX_H
+#define OOX_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* An opened WordprocessingML document. */
+typedef struct OoxDocument OoxDocument;
+
+/* The core properties of a document, see oox_document_core_property. */
+typedef enum OoxCoreProperty {
+    OOX_CORE_PROPERTY_TITLE = 0,
+    OOX_CORE_PROPERTY_CREATOR = 1,
+    OOX_CORE_PROPERTY_LAST_MODIFIED_BY = 2,
+    OOX_CORE_PROPERTY_CREATED = 3,
+    OOX_CORE_PROPERTY_MODIFIED = 4,
+} OoxCoreProperty;
+
+/* Returns the message of the error of the last call that could fail on the calling thread, or NULL if it succeeded.
+ * The message is owned by the library and is valid until the next such call on the same thread. */
+const char *oox_last_error(void);
+
+/* Opens and parses a document. Returns NULL on failure. */
+OoxDocument *oox_document_open(const char *path);
+
+/* Releases a document. Does nothing if document is NULL. */
+void oox_document_free(OoxDocument *document);
+
+/* Extracts the plain text of the main document. Returns NULL on failure. */
+char *oox_document_extract_text(const OoxDocument *document);
+
+/* Returns the number of paragraphs of the body of the main document, including the paragraphs of tables. Returns -1
+ * on failure. */
+long long oox_document_paragraph_count(const OoxDocument *document);
+
+/* Returns a core property of the document. property is one of the values of OoxCoreProperty. Returns NULL if the
+ * property isn't set, in which case the last error is cleared, or on failure, in which case the last error is set. */
+char *oox_document_core_property(const OoxDocument *document, int property);
+
+/* Returns the revision number of the document. Returns -1 if it isn't set, in which case the last error is cleared,
+ * or on failure, in which case the last error is set. */
+int oox_document_revision(const OoxDocument *document);
+
+/* Releases a string returned by the library. Does nothing if value is NULL. */
+void oox_string_free(char *value);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* OOX_H */
@@ -0,0 +1,554 @@
+use super::{
+    package::Package,
+    text::RevisionView,
+    textmap::{RunPath, TextMap},
+    wml::{
+        comments::{Comment, Comments},
+        document::{
+            BlockLevelElts, ContentBlockContent, ContentRunContent, HighlightColor, Markup, MarkupRange, PContent, RPr,
+            RPrBase, RangeMarkupElements, RunInnerContent, RunLevelElts, RunTrackChangeChoice, Text, P, R,
+        },
+        simpletypes::DecimalNumber,
+    },
+};
+use crate::error::AnnotateRangeError;
+use std::ops::Range;
+
+/// The markup added to a range of text by [`Package::annotate_range`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation {
+    Highlight(HighlightColor),
+    /// A comment anchored to the range, added to `word/comments.xml`.
+    Comment {
+        author: String,
+        text: String,
+    },
+}
+
+impl Package {
+    /// Annotates a range of character offsets of the text extracted by
+    /// [`Document::text_map`](super::wml::document::Document::text_map) with the accepted revision view, which is
+    /// the text returned by [`Package::extract_text`] without notes.
+    ///
+    /// Runs partially covered by the range are split, so only the characters of the range are annotated. Characters
+    /// inserted by the extraction, like line separators, are skipped. Returns the id of the inserted comment, if any.
+    ///
+    /// ```
+    /// use oox::docx::{
+    ///     annotation::Annotation,
+    ///     package::Package,
+    ///     text::TextOptions,
+    ///     wml::document::{Document, HighlightColor, P},
+    /// };
+    ///
+    /// let document = Document::builder().paragraph(P::builder().text("Hello world").build()).build();
+    /// let mut package = Package {
+    ///     main_document: Some(Box::new(document)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// package.annotate_range(6..11, Annotation::Highlight(HighlightColor::Yellow)).unwrap();
+    /// let comment = Annotation::Comment { author: String::from("Bot"), text: String::from("Typo?") };
+    /// assert_eq!(package.annotate_range(0..5, comment).unwrap(), Some(0));
+    ///
+    /// assert_eq!(package.extract_text(&TextOptions::default()), "Hello world");
+    /// assert_eq!(package.comments.unwrap().0[0].author, "Bot");
+    /// ```
+    pub fn annotate_range(
+        &mut self,
+        range: Range<usize>,
+        annotation: Annotation,
+    ) -> Result<Option<DecimalNumber>, AnnotateRangeError> {
+        let document = self
+            .main_document
+            .as_deref_mut()
+            .ok_or(AnnotateRangeError::MissingMainDocument)?;
+
+        let text_map = document.text_map(RevisionView::Accepted);
+        let len = text_map.text.chars().count();
+        if range.end > len {
+            return Err(AnnotateRangeError::OutOfBounds { end: range.end, len });
+        }
+
+        let segments = RunSegment::collect(&text_map, &range);
+        if segments.is_empty() {
+            return Err(AnnotateRangeError::EmptyRange);
+        }
+
+        let comment_id = match &annotation {
+            Annotation::Highlight(_) => None,
+            Annotation::Comment { .. } => Some(self.next_comment_id()),
+        };
+
+        // Runs are split from the end of the range, so splitting a run doesn't invalidate the paths of the preceding
+        // runs
+        let document = self.main_document.as_deref_mut().unwrap();
+        for (index, segment) in segments.iter().enumerate().rev() {
+            let paragraph = match document.paragraph_at_mut(&segment.path.paragraph) {
+                Some(paragraph) => paragraph,
+                None => continue,
+            };
+
+            let (mut container, run_index) = match segment.isolate(paragraph) {
+                Some(isolated) => isolated,
+                None => continue,
+            };
+
+            if let Annotation::Highlight(color) = &annotation {
+                if let Some(run) = container.run_mut(run_index) {
+                    set_highlight(run, *color);
+                }
+            }
+
+            if let Some(id) = comment_id {
+                if index == segments.len() - 1 {
+                    container.insert(run_index + 1, comment_reference_run(id));
+                    container.insert(
+                        run_index + 1,
+                        comment_range_marker(id, RangeMarkupElements::CommentRangeEnd),
+                    );
+                }
+
+                if index == 0 {
+                    container.insert(
+                        run_index,
+                        comment_range_marker(id, RangeMarkupElements::CommentRangeStart),
+                    );
+                }
+            }
+        }
+
+        if let (Some(id), Annotation::Comment { author, text }) = (comment_id, annotation) {
+            self.comments.get_or_insert_with(Comments::default).0.push(Comment {
+                id,
+                author,
+                date: None,
+                initials: None,
+                block_level_elements: vec![BlockLevelElts::Chunk(ContentBlockContent::Paragraph(Box::new(
+                    comment_paragraph(text),
+                )))],
+            });
+        }
+
+        Ok(comment_id)
+    }
+
+    fn next_comment_id(&self) -> DecimalNumber {
+        self.comments
+            .iter()
+            .flat_map(|comments| comments.0.iter())
+            .map(|comment| comment.id + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// The annotated part of a single run. Positions are `(content_index, char_index)` pairs, the end is exclusive.
+#[derive(Debug, Clone)]
+struct RunSegment {
+    path: RunPath,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl RunSegment {
+    fn collect(text_map: &TextMap, range: &Range<usize>) -> Vec<RunSegment> {
+        let mut segments: Vec<RunSegment> = Vec::new();
+        for span in text_map.spans_between(range.start, range.end) {
+            let start = (span.content_index, range.start.max(span.start) - span.start);
+            let end = (span.content_index, range.end.min(span.end) - span.start);
+            match segments.last_mut() {
+                Some(segment) if segment.path == span.path => segment.end = end,
+                _ => segments.push(RunSegment {
+                    path: span.path.clone(),
+                    start,
+                    end,
+                }),
+            }
+        }
+
+        segments
+    }
+
+    /// Splits the run of the segment so the segment is covered by a run of its own. Returns the container of the
+    /// run with the index of the run covering the segment.
+    fn isolate<'a>(&self, paragraph: &'a mut P) -> Option<(RunContainer<'a>, usize)> {
+        let (mut container, index) = container_in_contents(&mut paragraph.contents, &self.path.run)?;
+
+        let run = container.run_mut(index)?;
+        let tail = split_run(run, self.end);
+        let middle = split_run(run, self.start);
+
+        let mut index = index;
+        if run.run_inner_contents.is_empty() {
+            container.remove(index);
+        } else {
+            index += 1;
+        }
+
+        if !tail.run_inner_contents.is_empty() {
+            container.insert(index, ContentRunContent::Run(tail));
+        }
+        container.insert(index, ContentRunContent::Run(middle));
+
+        Some((container, index))
+    }
+}
+
+/// The list containing a run, which is either the contents of a paragraph (or of an element nested in it, like a
+/// hyperlink) or the choices of a tracked change.
+enum RunContainer<'a> {
+    Paragraph(&'a mut Vec<PContent>),
+    TrackChange(&'a mut Vec<RunTrackChangeChoice>),
+}
+
+impl RunContainer<'_> {
+    fn run_mut(&mut self, index: usize) -> Option<&mut R> {
+        let content = match self {
+            RunContainer::Paragraph(contents) => match contents.get_mut(index)? {
+                PContent::ContentRunContent(content) => content.as_mut(),
+                _ => return None,
+            },
+            RunContainer::TrackChange(choices) => match choices.get_mut(index)? {
+                RunTrackChangeChoice::ContentRunContent(content) => content,
+            },
+        };
+
+        match content {
+            ContentRunContent::Run(run) => Some(run),
+            _ => None,
+        }
+    }
+
+    fn insert(&mut self, index: usize, content: ContentRunContent) {
+        match self {
+            RunContainer::Paragraph(contents) => contents.insert(index, PContent::ContentRunContent(Box::new(content))),
+            RunContainer::TrackChange(choices) => {
+                choices.insert(index, RunTrackChangeChoice::ContentRunContent(content))
+            }
+        }
+    }
+
+    fn remove(&mut self, index: usize) {
+        match self {
+            RunContainer::Paragraph(contents) => {
+                contents.remove(index);
+            }
+            RunContainer::TrackChange(choices) => {
+                choices.remove(index);
+            }
+        }
+    }
+}
+
+fn container_in_contents<'a>(contents: &'a mut Vec<PContent>, path: &[usize]) -> Option<(RunContainer<'a>, usize)> {
+    let (index, rest) = path.split_first()?;
+    if rest.is_empty() {
+        return match contents.get(*index)? {
+            PContent::ContentRunContent(content) if matches!(content.as_ref(), ContentRunContent::Run(_)) => {
+                Some((RunContainer::Paragraph(contents), *index))
+            }
+            _ => None,
+        };
+    }
+
+    match contents.get_mut(*index)? {
+        PContent::ContentRunContent(content) => container_in_content_run(content, rest),
+        PContent::SimpleField(field) => container_in_contents(&mut field.paragraph_contents, rest),
+        PContent::Hyperlink(hyperlink) => container_in_contents(&mut hyperlink.paragraph_contents, rest),
+        PContent::SubDocument(_) => None,
+    }
+}
+
+fn container_in_content_run<'a>(
+    content: &'a mut ContentRunContent,
+    path: &[usize],
+) -> Option<(RunContainer<'a>, usize)> {
+    match content {
+        ContentRunContent::CustomXml(custom_xml) => container_in_contents(&mut custom_xml.paragraph_contents, path),
+        ContentRunContent::SmartTag(smart_tag) => container_in_contents(&mut smart_tag.paragraph_contents, path),
+        ContentRunContent::Sdt(sdt) => container_in_contents(&mut sdt.sdt_content.as_mut()?.p_contents, path),
+        ContentRunContent::Bidirectional(dir) => container_in_contents(&mut dir.p_contents, path),
+        ContentRunContent::BidirectionalOverride(bdo) => container_in_contents(&mut bdo.p_contents, path),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            let (index, rest) = path.split_first()?;
+            if rest.is_empty() {
+                return match track_change.choices.get(*index)? {
+                    RunTrackChangeChoice::ContentRunContent(ContentRunContent::Run(_)) => {
+                        Some((RunContainer::TrackChange(&mut track_change.choices), *index))
+                    }
+                    _ => None,
+                };
+            }
+
+            match track_change.choices.get_mut(*index)? {
+                RunTrackChangeChoice::ContentRunContent(content) => container_in_content_run(content, rest),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Splits the run before the `(content_index, char_index)` position. The run keeps the contents preceding the
+/// position, the returned run with the same properties contains the rest.
+fn split_run(run: &mut R, (content_index, char_index): (usize, usize)) -> R {
+    let content_index = content_index.min(run.run_inner_contents.len());
+    let mut tail = run.run_inner_contents.split_off(content_index);
+    if char_index > 0 && !tail.is_empty() {
+        let (head, rest) = split_run_content(tail.remove(0), char_index);
+        run.run_inner_contents.push(head);
+        if let Some(rest) = rest {
+            tail.insert(0, rest);
+        }
+    }
+
+    R {
+        run_properties: run.run_properties.clone(),
+        run_inner_contents: tail,
+        run_properties_revision_id: run.run_properties_revision_id,
+        deletion_revision_id: run.deletion_revision_id,
+        run_revision_id: run.run_revision_id,
+    }
+}
+
+fn split_run_content(content: RunInnerContent, char_index: usize) -> (RunInnerContent, Option<RunInnerContent>) {
+    match content {
+        RunInnerContent::Text(text) => {
+            let (head, rest) = split_text(text, char_index);
+            (RunInnerContent::Text(head), rest.map(RunInnerContent::Text))
+        }
+        RunInnerContent::DeletedText(text) => {
+            let (head, rest) = split_text(text, char_index);
+            (
+                RunInnerContent::DeletedText(head),
+                rest.map(RunInnerContent::DeletedText),
+            )
+        }
+        // Other contents are extracted as a single character
+        content => (content, None),
+    }
+}
+
+fn split_text(mut text: Text, char_index: usize) -> (Text, Option<Text>) {
+    match text.text.char_indices().nth(char_index) {
+        Some((byte_index, _)) => {
+            let rest = text.text.split_off(byte_index);
+            // The split may leave whitespaces at the ends of either part
+            let xml_space = Some(String::from("preserve"));
+            (
+                Text {
+                    text: text.text,
+                    xml_space: xml_space.clone(),
+                },
+                Some(Text { text: rest, xml_space }),
+            )
+        }
+        None => (text, None),
+    }
+}
+
+fn set_highlight(run: &mut R, color: HighlightColor) {
+    let properties = run.run_properties.get_or_insert_with(RPr::default);
+    properties
+        .r_pr_bases
+        .retain(|property| !matches!(property, RPrBase::Highlight(_)));
+    properties.r_pr_bases.push(RPrBase::Highlight(color));
+}
+
+fn comment_range_marker(id: DecimalNumber, marker: fn(MarkupRange) -> RangeMarkupElements) -> ContentRunContent {
+    ContentRunContent::RunLevelElements(RunLevelElts::RangeMarkupElements(marker(MarkupRange {
+        base: Markup { id },
+        displaced_by_custom_xml: None,
+    })))
+}
+
+fn comment_reference_run(id: DecimalNumber) -> ContentRunContent {
+    let mut run = R::builder().style("CommentReference").build();
+    run.run_inner_contents
+        .push(RunInnerContent::CommentReference(Markup { id }));
+    ContentRunContent::Run(run)
+}
+
+fn comment_paragraph(text: String) -> P {
+    let mut reference_mark = R::builder().style("CommentReference").build();
+    reference_mark
+        .run_inner_contents
+        .push(RunInnerContent::AnnorationReferenceMark);
+
+    P::builder()
+        .style("CommentText")
+        .run(reference_mark)
+        .run(R::builder().text(text).build())
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{text::TextOptions, wml::document::Document},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn test_package() -> Package {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:rPr><w:b /></w:rPr><w:t xml:space="preserve">Grüße </w:t></w:r>
+                <w:ins w:id="1" w:author="Jane"><w:r><w:t>an</w:t><w:tab /></w:r></w:ins>
+                <w:hyperlink r:id="rId1"><w:r><w:t>alle</w:t></w:r></w:hyperlink>
+            </w:p>
+            <w:p><w:r><w:t>Zweite Zeile</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        }
+    }
+
+    fn run_text(run: &R) -> String {
+        run.run_inner_contents
+            .iter()
+            .filter_map(|content| match content {
+                RunInnerContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn content_run(content: &PContent) -> Option<&ContentRunContent> {
+        match content {
+            PContent::ContentRunContent(content) => Some(content),
+            _ => None,
+        }
+    }
+
+    fn is_highlighted(run: &R) -> bool {
+        matches!(&run.run_properties, Some(properties) if properties
+            .r_pr_bases
+            .iter()
+            .any(|property| matches!(property, RPrBase::Highlight(HighlightColor::Yellow))))
+    }
+
+    #[test]
+    pub fn test_annotate_range_highlight() {
+        let mut package = test_package();
+        let text = package.extract_text(&TextOptions::default());
+        assert_eq!(text, "Grüße an\talle\nZweite Zeile");
+
+        package
+            .annotate_range(3..11, Annotation::Highlight(HighlightColor::Yellow))
+            .unwrap();
+        assert_eq!(package.extract_text(&TextOptions::default()), text);
+
+        let paragraph = package.main_document.as_ref().unwrap().paragraph_at(&[0]).unwrap();
+        let head = paragraph.run_at(&[0]).unwrap();
+        assert_eq!(run_text(head), "Grü");
+        assert!(!is_highlighted(head));
+        // Splitting a run keeps its formatting
+        assert!(head
+            .run_properties
+            .as_ref()
+            .unwrap()
+            .r_pr_bases
+            .contains(&RPrBase::Bold(true)));
+
+        let middle = paragraph.run_at(&[1]).unwrap();
+        assert_eq!(run_text(middle), "ße ");
+        assert!(is_highlighted(middle));
+        assert!(middle
+            .run_properties
+            .as_ref()
+            .unwrap()
+            .r_pr_bases
+            .contains(&RPrBase::Bold(true)));
+
+        let inserted = paragraph.run_at(&[2, 0]).unwrap();
+        assert_eq!(inserted.run_inner_contents.len(), 2);
+        assert!(is_highlighted(inserted));
+
+        let linked = paragraph.run_at(&[3, 0]).unwrap();
+        assert_eq!(run_text(linked), "al");
+        assert!(is_highlighted(linked));
+        assert_eq!(run_text(paragraph.run_at(&[3, 1]).unwrap()), "le");
+        assert!(!is_highlighted(paragraph.run_at(&[3, 1]).unwrap()));
+    }
+
+    #[test]
+    pub fn test_annotate_range_comment() {
+        let mut package = test_package();
+        let comment = Annotation::Comment {
+            author: String::from("Reviewer"),
+            text: String::from("Check this"),
+        };
+        assert_eq!(package.annotate_range(10..20, comment.clone()).unwrap(), Some(0));
+        assert_eq!(package.annotate_range(0..1, comment).unwrap(), Some(1));
+        assert_eq!(
+            package.extract_text(&TextOptions::default()),
+            "Grüße an\talle\nZweite Zeile"
+        );
+
+        let comments = &package.comments.as_ref().unwrap().0;
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].author, "Reviewer");
+
+        let document = package.main_document.as_ref().unwrap();
+        let first = document.paragraph_at(&[0]).unwrap();
+        // The second comment added markers and split the first run
+        let hyperlink = match &first.contents[6] {
+            PContent::Hyperlink(hyperlink) => hyperlink,
+            _ => panic!("expected a hyperlink"),
+        };
+        assert!(matches!(
+            content_run(&hyperlink.paragraph_contents[1]),
+            Some(ContentRunContent::RunLevelElements(RunLevelElts::RangeMarkupElements(
+                RangeMarkupElements::CommentRangeStart(MarkupRange {
+                    base: Markup { id: 0 },
+                    ..
+                })
+            )))
+        ));
+
+        let second = document.paragraph_at(&[1]).unwrap();
+        assert_eq!(run_text(second.run_at(&[0]).unwrap()), "Zweite");
+        assert!(matches!(
+            content_run(&second.contents[1]),
+            Some(ContentRunContent::RunLevelElements(RunLevelElts::RangeMarkupElements(
+                RangeMarkupElements::CommentRangeEnd(MarkupRange {
+                    base: Markup { id: 0 },
+                    ..
+                })
+            )))
+        ));
+        let reference = second.run_at(&[2]).unwrap();
+        assert!(matches!(
+            reference.run_inner_contents[0],
+            RunInnerContent::CommentReference(Markup { id: 0 })
+        ));
+        assert_eq!(run_text(second.run_at(&[3]).unwrap()), " Zeile");
+    }
+
+    #[test]
+    pub fn test_annotate_range_errors() {
+        let mut package = test_package();
+        let highlight = Annotation::Highlight(HighlightColor::Yellow);
+        assert_eq!(
+            package.annotate_range(13..14, highlight.clone()),
+            Err(AnnotateRangeError::EmptyRange)
+        );
+        assert_eq!(
+            package.annotate_range(0..100, highlight.clone()),
+            Err(AnnotateRangeError::OutOfBounds { end: 100, len: 26 })
+        );
+        assert_eq!(
+            Package::default().annotate_range(0..1, highlight),
+            Err(AnnotateRangeError::MissingMainDocument)
+        );
+    }
+}
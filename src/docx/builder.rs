@@ -0,0 +1,631 @@
+use super::wml::{
+    document::{
+        BlockLevelElts, Body, Br, BrType, Color, ContentBlockContent, ContentRunContent, DecimalNumberOrPercent,
+        Document, HexColor, HpsMeasure, Jc, MeasurementOrPercent, NumPr, PContent, PPr, PPrBase, RPr, RPrBase,
+        RunInnerContent, SectPr, Text, Underline, UnderlineType, P, R,
+    },
+    simpletypes::DecimalNumber,
+    table::{
+        ContentCellContent, ContentRowContent, Row, Tbl, TblGrid, TblGridBase, TblGridCol, TblPr, TblPrBase, TblWidth,
+        TblWidthType, Tc, TcPr, TcPrBase, TcPrInner,
+    },
+};
+use crate::shared::{drawingml::simpletypes::HexColorRGB, sharedtypes::TwipsMeasure};
+
+/// Builds a run of text with direct formatting.
+///
+/// ```
+/// use oox::docx::wml::document::{RunInnerContent, R};
+///
+/// let run = R::builder().text("Hello").tab().text("world").bold(true).font_size(24).build();
+/// assert_eq!(run.run_inner_contents.len(), 3);
+/// assert!(matches!(run.run_inner_contents[1], RunInnerContent::Tab));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RunBuilder {
+    properties: Vec<RPrBase>,
+    contents: Vec<RunInnerContent>,
+}
+
+impl RunBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Appends text to the run. Leading and trailing whitespaces are preserved.
+    pub fn text<T: Into<String>>(mut self, text: T) -> Self {
+        self.contents.push(RunInnerContent::Text(Text {
+            text: text.into(),
+            xml_space: Some(String::from("preserve")),
+        }));
+        self
+    }
+
+    pub fn tab(mut self) -> Self {
+        self.contents.push(RunInnerContent::Tab);
+        self
+    }
+
+    pub fn line_break(self) -> Self {
+        self.break_of_type(None)
+    }
+
+    pub fn page_break(self) -> Self {
+        self.break_of_type(Some(BrType::Page))
+    }
+
+    pub fn style<T: Into<String>>(self, style_id: T) -> Self {
+        self.property(RPrBase::RunStyle(style_id.into()))
+    }
+
+    pub fn bold(self, bold: bool) -> Self {
+        self.property(RPrBase::Bold(bold))
+    }
+
+    pub fn italic(self, italic: bool) -> Self {
+        self.property(RPrBase::Italic(italic))
+    }
+
+    pub fn underline(self, underline_type: UnderlineType) -> Self {
+        self.property(RPrBase::Underline(Underline {
+            value: Some(underline_type),
+            ..Default::default()
+        }))
+    }
+
+    /// Sets the font size in half-points.
+    pub fn font_size(self, half_points: u64) -> Self {
+        self.property(RPrBase::FontSize(HpsMeasure::Decimal(half_points)))
+    }
+
+    pub fn color(self, color: HexColorRGB) -> Self {
+        self.property(RPrBase::Color(Color {
+            value: HexColor::RGB(color),
+            theme_color: None,
+            theme_tint: None,
+            theme_shade: None,
+        }))
+    }
+
+    /// Adds an arbitrary run property.
+    pub fn property(mut self, property: RPrBase) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    pub fn build(self) -> R {
+        let run_properties = if self.properties.is_empty() {
+            None
+        } else {
+            Some(RPr {
+                r_pr_bases: self.properties,
+                run_properties_change: None,
+                extras: Default::default(),
+            })
+        };
+
+        R {
+            run_properties,
+            run_inner_contents: self.contents,
+            ..Default::default()
+        }
+    }
+
+    fn break_of_type(mut self, break_type: Option<BrType>) -> Self {
+        self.contents.push(RunInnerContent::Break(Br {
+            break_type,
+            clear: None,
+        }));
+        self
+    }
+}
+
+/// Builds a paragraph.
+///
+/// ```
+/// use oox::docx::wml::document::{P, R};
+///
+/// let paragraph = P::builder().style("Heading1").run(R::text("Introduction")).build();
+/// assert_eq!(paragraph.properties.unwrap().base.style.as_deref(), Some("Heading1"));
+/// assert_eq!(paragraph.contents.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ParagraphBuilder {
+    properties: PPrBase,
+    contents: Vec<PContent>,
+}
+
+impl ParagraphBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn style<T: Into<String>>(mut self, style_id: T) -> Self {
+        self.properties.style = Some(style_id.into());
+        self
+    }
+
+    pub fn alignment(mut self, alignment: Jc) -> Self {
+        self.properties.alignment = Some(alignment);
+        self
+    }
+
+    pub fn keep_with_next(mut self, keep_with_next: bool) -> Self {
+        self.properties.keep_with_next = Some(keep_with_next);
+        self
+    }
+
+    pub fn outline_level(mut self, level: DecimalNumber) -> Self {
+        self.properties.outline_level = Some(level);
+        self
+    }
+
+    /// Makes the paragraph a list item of a numbering definition instance at the given level.
+    pub fn numbering(mut self, numbering_id: DecimalNumber, level: DecimalNumber) -> Self {
+        self.properties.numbering_properties = Some(NumPr {
+            indent_level: Some(level),
+            numbering_id: Some(numbering_id),
+            inserted: None,
+        });
+        self
+    }
+
+    pub fn run(self, run: R) -> Self {
+        self.content(PContent::ContentRunContent(Box::new(ContentRunContent::Run(run))))
+    }
+
+    /// Appends a run of unformatted text.
+    pub fn text<T: Into<String>>(self, text: T) -> Self {
+        self.run(R::text(text))
+    }
+
+    /// Appends arbitrary paragraph content, e.g. a hyperlink or a field.
+    pub fn content(mut self, content: PContent) -> Self {
+        self.contents.push(content);
+        self
+    }
+
+    pub fn build(self) -> P {
+        let properties = if self.properties == Default::default() {
+            None
+        } else {
+            Some(PPr {
+                base: self.properties,
+                ..Default::default()
+            })
+        };
+
+        P {
+            properties,
+            contents: self.contents,
+            ..Default::default()
+        }
+    }
+}
+
+/// Builds a table cell. Cells without content get an empty paragraph, as required by the schema.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CellBuilder {
+    properties: TcPrBase,
+    contents: Vec<BlockLevelElts>,
+}
+
+impl CellBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn paragraph(self, paragraph: P) -> Self {
+        self.block(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(Box::new(
+            paragraph,
+        ))))
+    }
+
+    /// Appends a paragraph of unformatted text.
+    pub fn text<T: Into<String>>(self, text: T) -> Self {
+        self.paragraph(P::builder().text(text).build())
+    }
+
+    /// Appends a nested table.
+    pub fn table(self, table: Tbl) -> Self {
+        self.block(BlockLevelElts::Chunk(ContentBlockContent::Table(Box::new(table))))
+    }
+
+    pub fn block(mut self, block: BlockLevelElts) -> Self {
+        self.contents.push(block);
+        self
+    }
+
+    /// Sets the preferred width of the cell in twips.
+    pub fn width(mut self, width: u64) -> Self {
+        self.properties.width = Some(twips_width(width));
+        self
+    }
+
+    pub fn grid_span(mut self, grid_span: DecimalNumber) -> Self {
+        self.properties.grid_span = Some(grid_span);
+        self
+    }
+
+    pub fn build(mut self) -> Tc {
+        if !self.contents.iter().any(|block| {
+            matches!(
+                block,
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(_))
+                    | BlockLevelElts::Chunk(ContentBlockContent::Table(_))
+            )
+        }) {
+            self = self.paragraph(Default::default());
+        }
+
+        let properties = if self.properties == Default::default() {
+            None
+        } else {
+            Some(TcPr {
+                base: TcPrInner {
+                    base: self.properties,
+                    markup_element: None,
+                },
+                change: None,
+            })
+        };
+
+        Tc {
+            properties,
+            block_level_elements: self.contents,
+            id: None,
+        }
+    }
+}
+
+/// Builds a table row.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RowBuilder {
+    cells: Vec<Tc>,
+}
+
+impl RowBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn cell(mut self, cell: Tc) -> Self {
+        self.cells.push(cell);
+        self
+    }
+
+    /// Appends a cell with a paragraph of unformatted text.
+    pub fn text_cell<T: Into<String>>(self, text: T) -> Self {
+        self.cell(Tc::builder().text(text).build())
+    }
+
+    pub fn build(self) -> Row {
+        Row {
+            contents: self
+                .cells
+                .into_iter()
+                .map(|cell| ContentCellContent::Cell(Box::new(cell)))
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Builds a table.
+///
+/// ```
+/// use oox::docx::wml::{document::P, table::{Row, Tbl}};
+///
+/// let table = Tbl::builder()
+///     .style("TableGrid")
+///     .column_widths(vec![2000, 4000])
+///     .row(Row::builder().text_cell("Name").text_cell("Value").build())
+///     .build();
+///
+/// assert_eq!(table.grid.base.columns.len(), 2);
+/// assert_eq!(table.row_contents.len(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableBuilder {
+    properties: TblPrBase,
+    column_widths: Vec<u64>,
+    rows: Vec<Row>,
+}
+
+impl TableBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn style<T: Into<String>>(mut self, style_id: T) -> Self {
+        self.properties.style = Some(style_id.into());
+        self
+    }
+
+    /// Sets the preferred width of the table in twips. The table is sized automatically by default.
+    pub fn width(mut self, width: u64) -> Self {
+        self.properties.width = Some(twips_width(width));
+        self
+    }
+
+    /// Sets the widths of the grid columns in twips. Cells without a preferred width get the width of their column.
+    pub fn column_widths(mut self, column_widths: Vec<u64>) -> Self {
+        self.column_widths = column_widths;
+        self
+    }
+
+    pub fn row(mut self, row: Row) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    pub fn build(mut self) -> Tbl {
+        if self.properties.width.is_none() {
+            self.properties.width = Some(TblWidth {
+                width: None,
+                width_type: Some(TblWidthType::Auto),
+            });
+        }
+
+        let column_count = self
+            .rows
+            .iter()
+            .map(|row| row.contents.len())
+            .max()
+            .unwrap_or_default()
+            .max(self.column_widths.len());
+
+        let columns = (0..column_count)
+            .map(|idx| TblGridCol {
+                width: self.column_widths.get(idx).copied().map(TwipsMeasure::Decimal),
+            })
+            .collect();
+
+        for row in &mut self.rows {
+            for (cell, width) in row.contents.iter_mut().zip(&self.column_widths) {
+                if let ContentCellContent::Cell(cell) = cell {
+                    let properties = cell.properties.get_or_insert_with(Default::default);
+                    if properties.base.base.width.is_none() {
+                        properties.base.base.width = Some(twips_width(*width));
+                    }
+                }
+            }
+        }
+
+        Tbl {
+            range_markup_elements: Vec::new(),
+            properties: TblPr {
+                base: self.properties,
+                change: None,
+            },
+            grid: TblGrid {
+                base: TblGridBase { columns },
+                change: None,
+            },
+            row_contents: self
+                .rows
+                .into_iter()
+                .map(|row| ContentRowContent::Table(Box::new(row)))
+                .collect(),
+        }
+    }
+}
+
+/// Builds the main document.
+///
+/// ```
+/// use oox::docx::wml::document::{Document, P};
+///
+/// let document = Document::builder()
+///     .paragraph(P::builder().style("Title").text("Report").build())
+///     .paragraph(P::builder().text("Lorem ipsum").build())
+///     .build();
+///
+/// assert_eq!(document.body.unwrap().block_level_elements.len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocumentBuilder {
+    body: Body,
+}
+
+impl DocumentBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn paragraph(self, paragraph: P) -> Self {
+        self.block(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(Box::new(
+            paragraph,
+        ))))
+    }
+
+    pub fn table(self, table: Tbl) -> Self {
+        self.block(BlockLevelElts::Chunk(ContentBlockContent::Table(Box::new(table))))
+    }
+
+    pub fn block(mut self, block: BlockLevelElts) -> Self {
+        self.body.block_level_elements.push(block);
+        self
+    }
+
+    /// Sets the properties of the last section of the document.
+    pub fn section(mut self, section_properties: SectPr) -> Self {
+        self.body.section_properties = Some(section_properties);
+        self
+    }
+
+    pub fn build(self) -> Document {
+        Document {
+            body: Some(self.body),
+            ..Default::default()
+        }
+    }
+}
+
+impl R {
+    pub fn builder() -> RunBuilder {
+        RunBuilder::new()
+    }
+
+    /// Creates a run of unformatted text.
+    pub fn text<T: Into<String>>(text: T) -> Self {
+        RunBuilder::new().text(text).build()
+    }
+}
+
+impl P {
+    pub fn builder() -> ParagraphBuilder {
+        ParagraphBuilder::new()
+    }
+}
+
+impl Tc {
+    pub fn builder() -> CellBuilder {
+        CellBuilder::new()
+    }
+}
+
+impl Row {
+    pub fn builder() -> RowBuilder {
+        RowBuilder::new()
+    }
+}
+
+impl Tbl {
+    pub fn builder() -> TableBuilder {
+        TableBuilder::new()
+    }
+}
+
+impl Document {
+    pub fn builder() -> DocumentBuilder {
+        DocumentBuilder::new()
+    }
+}
+
+fn twips_width(width: u64) -> TblWidth {
+    TblWidth {
+        width: Some(MeasurementOrPercent::DecimalOrPercent(DecimalNumberOrPercent::Decimal(
+            width as i32,
+        ))),
+        width_type: Some(TblWidthType::TwentiethsOfPoint),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_builder() {
+        let run = R::builder()
+            .style("Strong")
+            .bold(true)
+            .color([0xff, 0x00, 0x00])
+            .text(" spaced ")
+            .page_break()
+            .build();
+
+        assert_eq!(
+            run.run_properties.unwrap().r_pr_bases,
+            vec![
+                RPrBase::RunStyle(String::from("Strong")),
+                RPrBase::Bold(true),
+                RPrBase::Color(Color {
+                    value: HexColor::RGB([0xff, 0x00, 0x00]),
+                    theme_color: None,
+                    theme_tint: None,
+                    theme_shade: None,
+                }),
+            ]
+        );
+        assert_eq!(
+            run.run_inner_contents,
+            vec![
+                RunInnerContent::Text(Text {
+                    text: String::from(" spaced "),
+                    xml_space: Some(String::from("preserve")),
+                }),
+                RunInnerContent::Break(Br {
+                    break_type: Some(BrType::Page),
+                    clear: None,
+                }),
+            ]
+        );
+
+        assert_eq!(R::text("plain").run_properties, None);
+    }
+
+    #[test]
+    pub fn test_paragraph_builder() {
+        assert_eq!(P::builder().build(), P::default());
+
+        let paragraph = P::builder()
+            .style("ListParagraph")
+            .alignment(Jc::Center)
+            .numbering(1, 0)
+            .text("Item")
+            .build();
+
+        let properties = paragraph.properties.unwrap().base;
+        assert_eq!(properties.style.as_deref(), Some("ListParagraph"));
+        assert_eq!(properties.alignment, Some(Jc::Center));
+        assert_eq!(properties.numbering_properties.unwrap().numbering_id, Some(1));
+        assert_eq!(paragraph.contents.len(), 1);
+    }
+
+    #[test]
+    pub fn test_table_builder() {
+        let table = Tbl::builder()
+            .column_widths(vec![1000, 2000])
+            .row(
+                Row::builder()
+                    .cell(Tc::builder().width(1500).build())
+                    .text_cell("b")
+                    .build(),
+            )
+            .row(Row::builder().text_cell("c").build())
+            .build();
+
+        assert_eq!(
+            table.grid.base.columns,
+            vec![
+                TblGridCol {
+                    width: Some(TwipsMeasure::Decimal(1000)),
+                },
+                TblGridCol {
+                    width: Some(TwipsMeasure::Decimal(2000)),
+                },
+            ]
+        );
+        assert_eq!(
+            table.properties.base.width.unwrap().width_type,
+            Some(TblWidthType::Auto)
+        );
+
+        let cells: Vec<&Tc> = table
+            .row_contents
+            .iter()
+            .filter_map(|row| match row {
+                ContentRowContent::Table(row) => Some(row),
+                _ => None,
+            })
+            .flat_map(|row| row.contents.iter())
+            .filter_map(|cell| match cell {
+                ContentCellContent::Cell(cell) => Some(cell.as_ref()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0].block_level_elements.len(), 1);
+        assert_eq!(
+            cells[0].properties.as_ref().unwrap().base.base.width,
+            Some(twips_width(1500))
+        );
+        assert_eq!(
+            cells[1].properties.as_ref().unwrap().base.base.width,
+            Some(twips_width(2000))
+        );
+    }
+}
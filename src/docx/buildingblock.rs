@@ -0,0 +1,426 @@
+use super::{
+    merge::{remap_block_ids, AppendMapping, IdRemapper},
+    package::Package,
+    wml::{
+        numbering::Numbering,
+        simpletypes::DecimalNumber,
+        styles::{Style, Styles},
+    },
+};
+use crate::{
+    error::InsertBuildingBlockError,
+    shared::{
+        partname::RelationshipTarget,
+        relationship::{Relationship, RelationshipId},
+    },
+};
+use std::collections::HashSet;
+
+/// The name of the glossary document part, which the targets of its relationships are relative to.
+const GLOSSARY_DOCUMENT_PART_NAME: &str = "word/glossary/document.xml";
+
+impl Package {
+    /// Inserts the contents of the named building block from the glossary document into the main document's body
+    /// before the block level element at `position`.
+    ///
+    /// The dependencies of the inserted content are copied from the glossary document:
+    ///
+    /// * styles (including the styles they are based on, linked to or followed by) are copied to the main document's
+    ///   styles if the main document doesn't define them already,
+    /// * numbering definitions are added to the main document's numbering, renumbered when their id is already used,
+    /// * relationships, e.g. of images and hyperlinks, are added to the main document's relationships with new ids.
+    ///   Internal targets are rewritten to absolute part names, since they're relative to the glossary document.
+    ///
+    /// The inserted content is updated with the new numbering and relationship ids.
+    pub fn insert_building_block<T: AsRef<str>>(
+        &mut self,
+        name: T,
+        position: usize,
+    ) -> Result<(), InsertBuildingBlockError> {
+        let doc_part = self
+            .find_building_block(name.as_ref())
+            .ok_or_else(|| InsertBuildingBlockError::NotFound(String::from(name.as_ref())))?;
+
+        let mut block_level_elements = doc_part
+            .body
+            .as_ref()
+            .ok_or(InsertBuildingBlockError::MissingBody)?
+            .block_level_elements
+            .clone();
+
+        let len = self
+            .main_document
+            .as_ref()
+            .and_then(|document| document.body.as_ref())
+            .ok_or(InsertBuildingBlockError::MissingBody)?
+            .block_level_elements
+            .len();
+        if position > len {
+            return Err(InsertBuildingBlockError::PositionOutOfBounds { position, len });
+        }
+
+        let mut used_ids = UsedIds::default();
+        remap_block_ids(&mut block_level_elements, &mut used_ids);
+
+        let mut mapping = AppendMapping::default();
+        let relationships = self.used_glossary_relationships(&used_ids.relationship_ids);
+        self.import_relationships(relationships, &mut mapping);
+        if let Some(numbering) = self.used_glossary_numbering(&used_ids.numbering_ids) {
+            self.import_numbering(&numbering, &mut mapping);
+        }
+        self.copy_glossary_styles(used_ids.style_ids);
+
+        remap_block_ids(&mut block_level_elements, &mut mapping);
+        if let Some(body) = self.main_document.as_mut().and_then(|document| document.body.as_mut()) {
+            body.block_level_elements
+                .splice(position..position, block_level_elements);
+        }
+
+        Ok(())
+    }
+
+    fn used_glossary_relationships(&self, relationship_ids: &HashSet<RelationshipId>) -> Vec<Relationship> {
+        self.glossary_document_relationships
+            .iter()
+            .filter(|relationship| relationship_ids.contains(&relationship.id))
+            .map(|relationship| match relationship.resolve(GLOSSARY_DOCUMENT_PART_NAME) {
+                Ok(RelationshipTarget::Internal(part_name)) => Relationship {
+                    target: String::from(part_name.as_str()),
+                    ..relationship.clone()
+                },
+                _ => relationship.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the numbering instances of the glossary document with the given ids and the abstract numberings they
+    /// refer to, or None if there are none.
+    fn used_glossary_numbering(&self, numbering_ids: &HashSet<DecimalNumber>) -> Option<Numbering> {
+        let glossary_numbering = self.glossary_numbering.as_ref()?;
+        let numberings: Vec<_> = glossary_numbering
+            .numberings
+            .iter()
+            .filter(|numbering| numbering_ids.contains(&numbering.numbering_id))
+            .cloned()
+            .collect();
+        if numberings.is_empty() {
+            return None;
+        }
+
+        let abstract_numberings = glossary_numbering
+            .abstract_numberings
+            .iter()
+            .filter(|abstract_numbering| {
+                numberings
+                    .iter()
+                    .any(|numbering| numbering.abstract_num_id == abstract_numbering.abstract_num_id)
+            })
+            .cloned()
+            .collect();
+
+        Some(Numbering {
+            abstract_numberings,
+            numberings,
+            ..Default::default()
+        })
+    }
+
+    fn copy_glossary_styles(&mut self, mut style_ids: HashSet<String>) {
+        let glossary_styles = match &self.glossary_styles {
+            Some(glossary_styles) => &glossary_styles.styles,
+            None => return,
+        };
+
+        let styles = self.styles.get_or_insert_with(|| Box::new(Styles::default()));
+
+        let mut pending: Vec<String> = style_ids.iter().cloned().collect();
+        while let Some(style_id) = pending.pop() {
+            if find_style(&styles.styles, &style_id).is_some() {
+                continue;
+            }
+
+            let style = match find_style(glossary_styles, &style_id) {
+                Some(style) => style,
+                None => continue,
+            };
+
+            for dependency in [&style.based_on, &style.link, &style.next].iter().copied().flatten() {
+                if style_ids.insert(dependency.clone()) {
+                    pending.push(dependency.clone());
+                }
+            }
+
+            styles.styles.push(style.clone());
+        }
+    }
+}
+
+fn find_style<'a>(styles: &'a [Style], style_id: &str) -> Option<&'a Style> {
+    styles.iter().find(|style| style.style_id.as_deref() == Some(style_id))
+}
+
+/// Records the ids referenced by the content of a building block, keeping them unchanged.
+#[derive(Debug, Default)]
+struct UsedIds {
+    relationship_ids: HashSet<RelationshipId>,
+    style_ids: HashSet<String>,
+    numbering_ids: HashSet<DecimalNumber>,
+}
+
+impl IdRemapper for UsedIds {
+    fn relationship_id(&mut self, rel_id: &str) -> RelationshipId {
+        self.relationship_ids.insert(String::from(rel_id));
+        String::from(rel_id)
+    }
+
+    fn style_id(&mut self, style_id: &str) -> String {
+        self.style_ids.insert(String::from(style_id));
+        String::from(style_id)
+    }
+
+    fn numbering_id(&mut self, numbering_id: DecimalNumber) -> DecimalNumber {
+        self.numbering_ids.insert(numbering_id);
+        numbering_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            document::{
+                BlockLevelElts, Body, ContentBlockContent, ContentRunContent, Document, DrawingChoice, PContent,
+                RunInnerContent,
+            },
+            glossary::GlossaryDocument,
+        },
+        shared::relationship::TargetMode,
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn package_for_test() -> Package {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p><w:r><w:t>First</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Second</w:t></w:r></w:p>
+            </w:body>
+        </w:document>"#;
+
+        let glossary_xml = r#"<w:glossaryDocument>
+            <w:docParts>
+                <w:docPart>
+                    <w:docPartPr>
+                        <w:name w:val="Cover Page" />
+                        <w:category>
+                            <w:name w:val="Built-In" />
+                            <w:gallery w:val="coverPg" />
+                        </w:category>
+                    </w:docPartPr>
+                    <w:docPartBody>
+                        <w:p>
+                            <w:pPr><w:pStyle w:val="CoverTitle" /></w:pPr>
+                            <w:r>
+                                <w:rPr><w:rStyle w:val="Emphasis" /></w:rPr>
+                                <w:t>Cover</w:t>
+                            </w:r>
+                        </w:p>
+                    </w:docPartBody>
+                </w:docPart>
+                <w:docPart>
+                    <w:docPartPr><w:name w:val="Logo List" /></w:docPartPr>
+                    <w:docPartBody>
+                        <w:p>
+                            <w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr>
+                            <w:r>
+                                <w:drawing>
+                                    <wp:inline>
+                                        <wp:extent cx="100" cy="100" />
+                                        <wp:docPr id="1" name="Logo" />
+                                        <a:graphic>
+                                            <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/picture">
+                                                <pic:pic>
+                                                    <pic:nvPicPr><pic:cNvPr id="0" name="logo.png" /><pic:cNvPicPr /></pic:nvPicPr>
+                                                    <pic:blipFill><a:blip r:embed="rId1" /></pic:blipFill>
+                                                    <pic:spPr />
+                                                </pic:pic>
+                                            </a:graphicData>
+                                        </a:graphic>
+                                    </wp:inline>
+                                </w:drawing>
+                            </w:r>
+                            <w:hyperlink r:id="rId2"><w:r><w:t>Example</w:t></w:r></w:hyperlink>
+                        </w:p>
+                    </w:docPartBody>
+                </w:docPart>
+            </w:docParts>
+        </w:glossaryDocument>"#;
+
+        let glossary_styles_xml = r#"<w:styles>
+            <w:style w:type="paragraph" w:styleId="Normal"><w:name w:val="Normal" /></w:style>
+            <w:style w:type="paragraph" w:styleId="Title">
+                <w:name w:val="Title" />
+                <w:basedOn w:val="Normal" />
+            </w:style>
+            <w:style w:type="paragraph" w:styleId="CoverTitle">
+                <w:name w:val="Cover Title" />
+                <w:basedOn w:val="Title" />
+            </w:style>
+            <w:style w:type="character" w:styleId="Emphasis"><w:name w:val="Emphasis" /></w:style>
+            <w:style w:type="character" w:styleId="Unused"><w:name w:val="Unused" /></w:style>
+        </w:styles>"#;
+
+        let main_styles_xml = r#"<w:styles>
+            <w:style w:type="paragraph" w:styleId="Normal"><w:name w:val="Main Normal" /></w:style>
+        </w:styles>"#;
+
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:abstractNum w:abstractNumId="1"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="1" /></w:num>
+            <w:num w:numId="2"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+
+        let relationship = |id: &str, rel_type: &str, target: &str, target_mode| Relationship {
+            id: String::from(id),
+            rel_type: format!(
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}",
+                rel_type
+            ),
+            target: String::from(target),
+            target_mode,
+        };
+
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            glossary_document: Some(Box::new(
+                GlossaryDocument::from_xml_element(&XmlNode::from_str(glossary_xml).unwrap()).unwrap(),
+            )),
+            glossary_styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(glossary_styles_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(main_styles_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            glossary_numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            main_document_relationships: vec![relationship("rId1", "styles", "styles.xml", None)],
+            glossary_document_relationships: vec![
+                relationship("rId1", "image", "media/image1.png", None),
+                relationship("rId2", "hyperlink", "https://example.com", Some(TargetMode::External)),
+                relationship("rId3", "image", "media/unused.png", None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn body_of(package: &Package) -> &Body {
+        package.main_document.as_ref().unwrap().body.as_ref().unwrap()
+    }
+
+    #[test]
+    pub fn test_insert_building_block() {
+        let mut package = package_for_test();
+        package.insert_building_block("Cover Page", 1).unwrap();
+
+        let body = body_of(&package);
+        assert_eq!(body.block_level_elements.len(), 3);
+        match &body.block_level_elements[1] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => assert_eq!(
+                paragraph.properties.as_ref().unwrap().base.style,
+                Some(String::from("CoverTitle"))
+            ),
+            _ => panic!("inserted block level element is not a paragraph"),
+        }
+
+        let mut style_ids: Vec<&str> = package
+            .styles
+            .as_ref()
+            .unwrap()
+            .styles
+            .iter()
+            .filter_map(|style| style.style_id.as_deref())
+            .collect();
+        style_ids.sort();
+        assert_eq!(style_ids, vec!["CoverTitle", "Emphasis", "Normal", "Title"]);
+
+        let normal_style = find_style(&package.styles.as_ref().unwrap().styles, "Normal").unwrap();
+        assert_eq!(normal_style.name, Some(String::from("Main Normal")));
+    }
+
+    #[test]
+    pub fn test_insert_building_block_dependencies() {
+        let mut package = package_for_test();
+        package.insert_building_block("Logo List", 0).unwrap();
+
+        let paragraph = match &body_of(&package).block_level_elements[0] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph,
+            _ => panic!("inserted block level element is not a paragraph"),
+        };
+        let numbering_properties = paragraph
+            .properties
+            .as_ref()
+            .unwrap()
+            .base
+            .numbering_properties
+            .as_ref()
+            .unwrap();
+        assert_eq!(numbering_properties.numbering_id, Some(3));
+
+        let numbering = package.numbering.as_ref().unwrap();
+        assert_eq!(numbering.numberings.len(), 3);
+        assert_eq!(numbering.abstract_numberings.len(), 3);
+        let numbering_instance = numbering.numberings.iter().find(|num| num.numbering_id == 3).unwrap();
+        assert_eq!(numbering_instance.abstract_num_id, 2);
+
+        let embed_rel_id = match &paragraph.contents[0] {
+            PContent::ContentRunContent(content) => match &**content {
+                ContentRunContent::Run(run) => match &run.run_inner_contents[0] {
+                    RunInnerContent::Drawing(drawing) => match &drawing.0[0] {
+                        DrawingChoice::Inline(inline) => inline.graphic.graphic_data.pictures[0]
+                            .blip_fill_props
+                            .blip
+                            .as_ref()
+                            .unwrap()
+                            .embed_rel_id
+                            .clone(),
+                        _ => panic!("expected an inline drawing"),
+                    },
+                    _ => panic!("expected a drawing"),
+                },
+                _ => panic!("expected a run"),
+            },
+            content => panic!("expected a run, got {:?}", content),
+        };
+        assert_eq!(embed_rel_id.as_deref(), Some("rId2"));
+        match &paragraph.contents[1] {
+            PContent::Hyperlink(hyperlink) => assert_eq!(hyperlink.rel_id.as_deref(), Some("rId3")),
+            content => panic!("expected a hyperlink, got {:?}", content),
+        }
+
+        let relationships = &package.main_document_relationships;
+        assert_eq!(relationships.len(), 3);
+        assert_eq!(relationships[1].id, "rId2");
+        assert_eq!(relationships[1].target, "/word/glossary/media/image1.png");
+        assert_eq!(relationships[2].id, "rId3");
+        assert_eq!(relationships[2].target, "https://example.com");
+    }
+
+    #[test]
+    pub fn test_insert_building_block_errors() {
+        let mut package = package_for_test();
+
+        assert_eq!(
+            package.insert_building_block("Missing", 0),
+            Err(InsertBuildingBlockError::NotFound(String::from("Missing")))
+        );
+        assert_eq!(
+            package.insert_building_block("Cover Page", 3),
+            Err(InsertBuildingBlockError::PositionOutOfBounds { position: 3, len: 2 })
+        );
+        assert_eq!(body_of(&package).block_level_elements.len(), 2);
+    }
+}
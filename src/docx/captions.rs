@@ -0,0 +1,557 @@
+use super::wml::{
+    document::{
+        BlockLevelElts, ContentBlockContent, ContentRunContent, Document, FldChar, FldCharType, PContent, PPr, PPrBase,
+        RunInnerContent, RunLevelElts, RunTrackChangeChoice, SimpleField, Text, P, R,
+    },
+    fields::{Field, SeqField},
+    table::{ContentCellContent, ContentRowContent},
+};
+use std::collections::HashMap;
+
+pub const CAPTION_STYLE: &str = "Caption";
+pub const TABLE_OF_FIGURES_STYLE: &str = "TableofFigures";
+
+/// A caption paragraph of the document, e.g. "Figure 1: Architecture overview".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caption {
+    /// The identifier of the first SEQ field of the paragraph, e.g. "Figure" or "Table". None if the paragraph only
+    /// has the caption style.
+    pub label: Option<String>,
+    /// The number the first SEQ field of the paragraph evaluates to.
+    pub number: Option<u32>,
+    /// The text of the caption paragraph with the SEQ fields replaced by their computed numbers.
+    pub text: String,
+    /// The index of the top level block element of the body containing the caption paragraph.
+    pub block_index: usize,
+}
+
+impl Document {
+    /// Lists the caption paragraphs of the document in document order. A paragraph is considered to be a caption
+    /// if it has the `Caption` paragraph style or contains a SEQ field.
+    ///
+    /// SEQ fields are numbered in document order, honoring the `\c`, `\h`, `\r` and `\*` switches. Cached field
+    /// results are ignored.
+    pub fn captions(&self) -> Vec<Caption> {
+        let mut collector = CaptionCollector::default();
+        if let Some(body) = &self.body {
+            for (block_index, block_level_element) in body.block_level_elements.iter().enumerate() {
+                collector.block_index = block_index;
+                collector.block_level_element(block_level_element);
+            }
+        }
+
+        collector.captions
+    }
+
+    /// Lists the captions that have the given SEQ identifier, e.g. "Figure".
+    pub fn captions_with_label<T: AsRef<str>>(&self, label: T) -> Vec<Caption> {
+        self.captions()
+            .into_iter()
+            .filter(|caption| caption.label.as_deref() == Some(label.as_ref()))
+            .collect()
+    }
+
+    /// Generates the paragraphs of a Table of Figures field (`TOC \h \z \c "label"`) with an up to date field result.
+    /// Every entry is a paragraph with the `TableofFigures` style containing the caption's text. Page numbers are
+    /// not generated.
+    pub fn table_of_figures<T: AsRef<str>>(&self, label: T) -> Vec<P> {
+        let entries: Vec<String> = self
+            .captions_with_label(label.as_ref())
+            .into_iter()
+            .map(|caption| caption.text)
+            .collect();
+
+        let mut paragraphs: Vec<P> = if entries.is_empty() {
+            vec![table_of_figures_paragraph(String::from(
+                "No table of figures entries found.",
+            ))]
+        } else {
+            entries.into_iter().map(table_of_figures_paragraph).collect()
+        };
+
+        let field_start = vec![
+            field_char_run(FldCharType::Begin),
+            run_with_content(RunInnerContent::InstructionText(preserved_text(format!(
+                r#" TOC \h \z \c "{}" "#,
+                label.as_ref()
+            )))),
+            field_char_run(FldCharType::Separate),
+        ];
+
+        if let Some(first) = paragraphs.first_mut() {
+            first.contents.splice(0..0, field_start);
+        }
+
+        if let Some(last) = paragraphs.last_mut() {
+            last.contents.push(field_char_run(FldCharType::End));
+        }
+
+        paragraphs
+    }
+}
+
+/// Parses a field instruction if it's a SEQ field.
+fn parse_seq(instruction: &str) -> Option<SeqField> {
+    match Field::parse(instruction) {
+        Ok(Field::Seq(seq)) => Some(seq),
+        _ => None,
+    }
+}
+
+fn evaluate_seq(seq: &SeqField, counters: &mut HashMap<String, u32>) -> u32 {
+    let counter = counters.entry(seq.identifier.clone()).or_insert(0);
+    match (seq.reset, seq.repeat) {
+        (Some(reset), _) => *counter = reset,
+        (None, true) => (),
+        (None, false) => *counter += 1,
+    }
+
+    *counter
+}
+
+fn format_number(number: u32, format: Option<&str>) -> String {
+    match format {
+        Some("ALPHABETIC") => alphabetic(number),
+        Some("alphabetic") => alphabetic(number).to_lowercase(),
+        Some("ROMAN") => roman(number),
+        Some("roman") => roman(number).to_lowercase(),
+        _ => number.to_string(),
+    }
+}
+
+fn alphabetic(number: u32) -> String {
+    if number == 0 {
+        return String::new();
+    }
+
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+    letter.to_string().repeat(((number - 1) / 26 + 1) as usize)
+}
+
+fn roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut result = String::new();
+    for &(value, numeral) in NUMERALS.iter() {
+        while number >= value {
+            result.push_str(numeral);
+            number -= value;
+        }
+    }
+
+    result
+}
+
+enum Inline<'a> {
+    Text(&'a str),
+    Tab,
+    FieldBegin,
+    Instruction(&'a str),
+    FieldSeparate,
+    FieldEnd,
+    SimpleField(&'a SimpleField),
+}
+
+fn collect_paragraph_contents<'a>(contents: &'a [PContent], inlines: &mut Vec<Inline<'a>>) {
+    for content in contents {
+        match content {
+            PContent::ContentRunContent(content) => collect_content_run_content(content, inlines),
+            PContent::SimpleField(field) => inlines.push(Inline::SimpleField(field)),
+            PContent::Hyperlink(hyperlink) => collect_paragraph_contents(&hyperlink.paragraph_contents, inlines),
+            PContent::SubDocument(_) => (),
+        }
+    }
+}
+
+fn collect_content_run_content<'a>(content: &'a ContentRunContent, inlines: &mut Vec<Inline<'a>>) {
+    match content {
+        ContentRunContent::Run(run) => collect_run(run, inlines),
+        ContentRunContent::CustomXml(custom_xml) => collect_paragraph_contents(&custom_xml.paragraph_contents, inlines),
+        ContentRunContent::SmartTag(smart_tag) => collect_paragraph_contents(&smart_tag.paragraph_contents, inlines),
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                collect_paragraph_contents(&sdt_content.p_contents, inlines);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => collect_paragraph_contents(&dir.p_contents, inlines),
+        ContentRunContent::BidirectionalOverride(bdo) => collect_paragraph_contents(&bdo.p_contents, inlines),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            for choice in &track_change.choices {
+                match choice {
+                    RunTrackChangeChoice::ContentRunContent(content) => collect_content_run_content(content, inlines),
+                }
+            }
+        }
+        ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+fn collect_run<'a>(run: &'a R, inlines: &mut Vec<Inline<'a>>) {
+    for inner_content in &run.run_inner_contents {
+        match inner_content {
+            RunInnerContent::Text(text) => inlines.push(Inline::Text(&text.text)),
+            RunInnerContent::InstructionText(text) => inlines.push(Inline::Instruction(&text.text)),
+            RunInnerContent::Tab | RunInnerContent::PositionTab(_) => inlines.push(Inline::Tab),
+            RunInnerContent::FieldCharacter(fld_char) => inlines.push(match fld_char.field_char_type {
+                FldCharType::Begin => Inline::FieldBegin,
+                FldCharType::Separate => Inline::FieldSeparate,
+                FldCharType::End => Inline::FieldEnd,
+            }),
+            _ => (),
+        }
+    }
+}
+
+struct FieldFrame {
+    instruction: String,
+    in_result: bool,
+    show_result: bool,
+}
+
+#[derive(Default)]
+struct CaptionCollector {
+    counters: HashMap<String, u32>,
+    captions: Vec<Caption>,
+    block_index: usize,
+}
+
+impl CaptionCollector {
+    fn block_level_element(&mut self, element: &BlockLevelElts) {
+        if let BlockLevelElts::Chunk(content) = element {
+            self.content_block_content(content);
+        }
+    }
+
+    fn content_block_content(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph),
+            ContentBlockContent::Table(table) => {
+                for row_content in &table.row_contents {
+                    self.row_content(row_content);
+                }
+            }
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn row_content(&mut self, content: &ContentRowContent) {
+        match content {
+            ContentRowContent::Table(row) => {
+                for cell_content in &row.contents {
+                    self.cell_content(cell_content);
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn cell_content(&mut self, content: &ContentCellContent) {
+        match content {
+            ContentCellContent::Cell(cell) => {
+                for block_level_element in &cell.block_level_elements {
+                    self.block_level_element(block_level_element);
+                }
+            }
+            ContentCellContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P) {
+        let mut inlines = Vec::new();
+        collect_paragraph_contents(&paragraph.contents, &mut inlines);
+
+        let mut text = String::new();
+        let mut first_seq = None;
+        self.render(&inlines, &mut text, &mut first_seq);
+
+        let has_caption_style = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.base.style.as_deref())
+            == Some(CAPTION_STYLE);
+
+        if has_caption_style || first_seq.is_some() {
+            let (label, number) = match first_seq {
+                Some((label, number)) => (Some(label), Some(number)),
+                None => (None, None),
+            };
+
+            self.captions.push(Caption {
+                label,
+                number,
+                text: String::from(text.trim()),
+                block_index: self.block_index,
+            });
+        }
+    }
+
+    fn render(&mut self, inlines: &[Inline<'_>], text: &mut String, first_seq: &mut Option<(String, u32)>) {
+        let mut stack: Vec<FieldFrame> = Vec::new();
+        let is_visible = |stack: &[FieldFrame]| stack.iter().all(|frame| frame.in_result && frame.show_result);
+
+        for inline in inlines {
+            match inline {
+                Inline::Text(value) if is_visible(&stack) => text.push_str(value),
+                Inline::Tab if is_visible(&stack) => text.push('\t'),
+                Inline::Text(_) | Inline::Tab => (),
+                Inline::FieldBegin => stack.push(FieldFrame {
+                    instruction: String::new(),
+                    in_result: false,
+                    show_result: true,
+                }),
+                Inline::Instruction(value) => {
+                    if let Some(frame) = stack.last_mut().filter(|frame| !frame.in_result) {
+                        frame.instruction.push_str(value);
+                    }
+                }
+                Inline::FieldSeparate => {
+                    let outer_visible = stack.len() < 2 || is_visible(&stack[..stack.len() - 1]);
+                    if let Some(frame) = stack.last_mut().filter(|frame| !frame.in_result) {
+                        frame.in_result = true;
+                        if let Some(seq) = parse_seq(&frame.instruction) {
+                            frame.show_result = false;
+                            self.seq_field(&seq, outer_visible, text, first_seq);
+                        }
+                    }
+                }
+                Inline::FieldEnd => {
+                    if let Some(frame) = stack.pop() {
+                        if !frame.in_result {
+                            if let Some(seq) = parse_seq(&frame.instruction) {
+                                let visible = is_visible(&stack);
+                                self.seq_field(&seq, visible, text, first_seq);
+                            }
+                        }
+                    }
+                }
+                Inline::SimpleField(field) => {
+                    let visible = is_visible(&stack);
+                    match parse_seq(&field.field_codes) {
+                        Some(seq) => self.seq_field(&seq, visible, text, first_seq),
+                        None if visible => {
+                            let mut field_inlines = Vec::new();
+                            collect_paragraph_contents(&field.paragraph_contents, &mut field_inlines);
+                            self.render(&field_inlines, text, first_seq);
+                        }
+                        None => (),
+                    }
+                }
+            }
+        }
+    }
+
+    fn seq_field(&mut self, seq: &SeqField, visible: bool, text: &mut String, first_seq: &mut Option<(String, u32)>) {
+        let number = evaluate_seq(seq, &mut self.counters);
+        if first_seq.is_none() {
+            *first_seq = Some((seq.identifier.clone(), number));
+        }
+
+        if visible && !seq.hidden {
+            text.push_str(&format_number(number, seq.format.number_format()));
+        }
+    }
+}
+
+pub(crate) fn preserved_text(text: String) -> Text {
+    Text {
+        text,
+        xml_space: Some(String::from("preserve")),
+    }
+}
+
+pub(crate) fn run_with_content(content: RunInnerContent) -> PContent {
+    PContent::ContentRunContent(Box::new(ContentRunContent::Run(R {
+        run_inner_contents: vec![content],
+        ..Default::default()
+    })))
+}
+
+pub(crate) fn field_char_run(field_char_type: FldCharType) -> PContent {
+    run_with_content(RunInnerContent::FieldCharacter(FldChar {
+        form_field_properties: Vec::new(),
+        field_char_type,
+        field_lock: None,
+        dirty: None,
+    }))
+}
+
+fn table_of_figures_paragraph(text: String) -> P {
+    P {
+        properties: Some(PPr {
+            base: PPrBase {
+                style: Some(String::from(TABLE_OF_FIGURES_STYLE)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        contents: vec![run_with_content(RunInnerContent::Text(preserved_text(text)))],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn document_for_test() -> Document {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p><w:r><w:t>Introduction</w:t></w:r></w:p>
+                <w:p>
+                    <w:pPr><w:pStyle w:val="Caption" /></w:pPr>
+                    <w:r><w:t xml:space="preserve">Figure </w:t></w:r>
+                    <w:fldSimple w:instr=" SEQ Figure \* ARABIC "><w:r><w:t>7</w:t></w:r></w:fldSimple>
+                    <w:r><w:t>: Overview</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:pPr><w:pStyle w:val="Caption" /></w:pPr>
+                    <w:r><w:t xml:space="preserve">Table </w:t></w:r>
+                    <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+                    <w:r><w:instrText xml:space="preserve"> SEQ Table \* ROMAN </w:instrText></w:r>
+                    <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+                    <w:r><w:t>9</w:t></w:r>
+                    <w:r><w:fldChar w:fldCharType="end" /></w:r>
+                    <w:r><w:t>: Results</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Figure </w:t></w:r>
+                    <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+                    <w:r><w:instrText xml:space="preserve"> SEQ Figure </w:instrText></w:r>
+                    <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+                    <w:r><w:t>1</w:t></w:r>
+                    <w:r><w:fldChar w:fldCharType="end" /></w:r>
+                    <w:r><w:t>: Details</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:pPr><w:pStyle w:val="Caption" /></w:pPr>
+                    <w:r><w:t>Unnumbered caption</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Figure </w:t></w:r>
+                    <w:fldSimple w:instr="SEQ Figure \r 10"><w:r><w:t>3</w:t></w:r></w:fldSimple>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+
+        Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_seq_field_parse() {
+        let seq = parse_seq(r#" SEQ Figure \* ARABIC \r 3 \h "#).unwrap();
+        assert_eq!(seq.identifier, "Figure");
+        assert_eq!(seq.format.number_format(), Some("ARABIC"));
+        assert!(!seq.repeat);
+        assert!(seq.hidden);
+        assert_eq!(seq.reset, Some(3));
+        assert_eq!(parse_seq(" PAGE "), None);
+    }
+
+    #[test]
+    pub fn test_format_number() {
+        assert_eq!(format_number(4, None), "4");
+        assert_eq!(format_number(4, Some("ROMAN")), "IV");
+        assert_eq!(format_number(1994, Some("roman")), "mcmxciv");
+        assert_eq!(format_number(3, Some("ALPHABETIC")), "C");
+        assert_eq!(format_number(28, Some("alphabetic")), "bb");
+    }
+
+    #[test]
+    pub fn test_captions() {
+        let captions = document_for_test().captions();
+        let summary: Vec<(Option<&str>, Option<u32>, &str, usize)> = captions
+            .iter()
+            .map(|caption| {
+                (
+                    caption.label.as_deref(),
+                    caption.number,
+                    caption.text.as_str(),
+                    caption.block_index,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (Some("Figure"), Some(1), "Figure 1: Overview", 1),
+                (Some("Table"), Some(1), "Table I: Results", 2),
+                (Some("Figure"), Some(2), "Figure 2: Details", 3),
+                (None, None, "Unnumbered caption", 4),
+                (Some("Figure"), Some(10), "Figure 10", 5),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_table_of_figures() {
+        let document = document_for_test();
+        let paragraphs = document.table_of_figures("Figure");
+        assert_eq!(paragraphs.len(), 3);
+
+        let mut inlines = Vec::new();
+        collect_paragraph_contents(&paragraphs[0].contents, &mut inlines);
+        let mut text = String::new();
+        CaptionCollector::default().render(&inlines, &mut text, &mut None);
+        assert_eq!(text, "Figure 1: Overview");
+
+        assert_eq!(paragraphs[0].contents.len(), 4);
+        assert_eq!(paragraphs[1].contents.len(), 1);
+        assert_eq!(paragraphs[2].contents.len(), 2);
+        assert_eq!(
+            paragraphs[1].properties.as_ref().unwrap().base.style.as_deref(),
+            Some(TABLE_OF_FIGURES_STYLE)
+        );
+
+        let empty = document.table_of_figures("Equation");
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0].contents.len(), 5);
+    }
+}
@@ -0,0 +1,141 @@
+use super::{
+    package::Package,
+    wml::{
+        comments::{Comment, CommentEx},
+        document::{BlockLevelElts, ContentBlockContent},
+        simpletypes::LongHexNumber,
+    },
+};
+
+/// A comment with its replies, as displayed by the comment pane of Word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentThread<'a> {
+    pub comment: &'a Comment,
+    /// Whether the comment has been marked as resolved.
+    pub done: bool,
+    pub replies: Vec<CommentThread<'a>>,
+}
+
+impl Comment {
+    /// Returns the `w14:paraId` of the last paragraph of the comment, which identifies it in `commentsExtended.xml`.
+    pub fn paragraph_id(&self) -> Option<LongHexNumber> {
+        self.block_level_elements
+            .iter()
+            .rev()
+            .find_map(|element| match element {
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => Some(paragraph.paragraph_id),
+                _ => None,
+            })
+            .flatten()
+    }
+}
+
+impl Package {
+    pub fn find_comment_with_id(&self, id: i64) -> Option<&Comment> {
+        self.comments.as_ref()?.0.iter().find(|comment| comment.id == id)
+    }
+
+    pub fn find_comment_with_paragraph_id(&self, paragraph_id: LongHexNumber) -> Option<&Comment> {
+        self.comments
+            .as_ref()?
+            .0
+            .iter()
+            .find(|comment| comment.paragraph_id() == Some(paragraph_id))
+    }
+
+    /// Returns the extended information of a comment, keyed by its paragraph id.
+    pub fn find_comment_extension(&self, comment: &Comment) -> Option<&CommentEx> {
+        let paragraph_id = comment.paragraph_id()?;
+        self.comments_extended
+            .as_ref()?
+            .0
+            .iter()
+            .find(|comment_ex| comment_ex.paragraph_id == paragraph_id)
+    }
+
+    /// Builds the comment threads of the document. Comments without a parent in `commentsExtended.xml` start a new
+    /// thread, replies are attached to their parent in document order.
+    pub fn comment_threads(&self) -> Vec<CommentThread<'_>> {
+        let comments = match &self.comments {
+            Some(comments) => &comments.0,
+            None => return Vec::new(),
+        };
+
+        comments
+            .iter()
+            .filter(|comment| self.parent_paragraph_id(comment).is_none())
+            .map(|comment| self.comment_thread(comment, comments))
+            .collect()
+    }
+
+    fn parent_paragraph_id(&self, comment: &Comment) -> Option<LongHexNumber> {
+        let parent_paragraph_id = self.find_comment_extension(comment)?.parent_paragraph_id?;
+        // Orphaned replies are treated as top level comments
+        self.find_comment_with_paragraph_id(parent_paragraph_id)
+            .map(|_| parent_paragraph_id)
+    }
+
+    fn comment_thread<'a>(&'a self, comment: &'a Comment, comments: &'a [Comment]) -> CommentThread<'a> {
+        let done = self
+            .find_comment_extension(comment)
+            .and_then(|comment_ex| comment_ex.done)
+            .unwrap_or(false);
+
+        let replies = match comment.paragraph_id() {
+            Some(paragraph_id) => comments
+                .iter()
+                .filter(|reply| self.parent_paragraph_id(reply) == Some(paragraph_id))
+                .map(|reply| self.comment_thread(reply, comments))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        CommentThread { comment, done, replies }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::comments::{Comments, CommentsEx},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_comment_threads() {
+        let comments_xml = r#"<w:comments>
+            <w:comment w:id="0" w:author="Alice"><w:p w14:paraId="00000001" /></w:comment>
+            <w:comment w:id="1" w:author="Bob"><w:p /><w:p w14:paraId="00000002" /></w:comment>
+            <w:comment w:id="2" w:author="Alice"><w:p w14:paraId="00000003" /></w:comment>
+            <w:comment w:id="3" w:author="Carol"><w:p w14:paraId="00000004" /></w:comment>
+        </w:comments>"#;
+        let comments_ex_xml = r#"<w15:commentsEx>
+            <w15:commentEx w15:paraId="00000001" w15:done="1" />
+            <w15:commentEx w15:paraId="00000002" w15:paraIdParent="00000001" />
+            <w15:commentEx w15:paraId="00000003" w15:paraIdParent="00000002" />
+            <w15:commentEx w15:paraId="00000004" w15:paraIdParent="000000FF" />
+        </w15:commentsEx>"#;
+
+        let package = Package {
+            comments: Some(Comments::from_xml_element(&XmlNode::from_str(comments_xml).unwrap()).unwrap()),
+            comments_extended: Some(
+                CommentsEx::from_xml_element(&XmlNode::from_str(comments_ex_xml).unwrap()).unwrap(),
+            ),
+            ..Default::default()
+        };
+
+        assert_eq!(package.find_comment_with_paragraph_id(0x2).unwrap().id, 1);
+
+        let threads = package.comment_threads();
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].comment.id, 0);
+        assert!(threads[0].done);
+        assert_eq!(threads[0].replies.len(), 1);
+        assert_eq!(threads[0].replies[0].comment.author, "Bob");
+        assert_eq!(threads[0].replies[0].replies[0].comment.id, 2);
+        assert_eq!(threads[1].comment.id, 3);
+        assert!(threads[1].replies.is_empty());
+    }
+}
@@ -0,0 +1,502 @@
+use super::{
+    package::Package,
+    sdtdate::CalendarDate,
+    text::{cell_texts, content_block_lines, for_each_inline, row_lines, InlineItem, NoteCollector, NotePlacement},
+    wml::{
+        document::{
+            BlockLevelElts, ContentBlockContent, ContentRunContent, Document, PContent, RunLevelElts,
+            RunTrackChangeChoice, SdtListItem, SdtPr, SdtPrChoice,
+        },
+        simpletypes::DecimalNumber,
+        table::{ContentCellContent, ContentRowContent},
+    },
+};
+
+/// The level of the document a content control is placed at.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentControlLevel {
+    /// `w:sdt` containing paragraphs and tables
+    Block,
+    /// `w:sdt` inside a paragraph
+    Run,
+    /// `w:sdt` containing table rows
+    Row,
+    /// `w:sdt` containing table cells
+    Cell,
+}
+
+/// The typed value of a content control.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContentControlValue {
+    /// The text of plain text, rich text and untyped controls, and of any other control without a typed value.
+    Text(String),
+    /// The date stored by a date picker. `None` if no date is selected or the stored date is invalid.
+    Date(Option<CalendarDate>),
+    /// The selected item of a drop-down list or combo box. `value` is `None` if nothing is selected or the text of a
+    /// combo box doesn't match any of its items.
+    Selection {
+        value: Option<String>,
+        display_text: String,
+    },
+    /// A picture control, whose value is the drawing in its content.
+    Picture,
+}
+
+/// A content control of a document, see [`Document::content_controls`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentControl<'a> {
+    pub level: ContentControlLevel,
+    pub properties: Option<&'a SdtPr>,
+    /// The path of the control following the convention of [`RunPath::paragraph`]. For run level controls it's the
+    /// path of the paragraph containing the control.
+    ///
+    /// [`RunPath::paragraph`]: super::textmap::RunPath::paragraph
+    pub path: Vec<usize>,
+    /// The path of run level controls in the contents of their paragraph, following the convention of
+    /// [`RunPath::run`].
+    ///
+    /// [`RunPath::run`]: super::textmap::RunPath::run
+    pub run_path: Option<Vec<usize>>,
+    /// The index of the innermost control containing this one in the controls returned by
+    /// [`Document::content_controls`].
+    pub parent: Option<usize>,
+    /// The text of the content of the control. Paragraphs and rows are separated by new lines, cells by tabs.
+    pub text: String,
+}
+
+impl ContentControl<'_> {
+    pub fn tag(&self) -> Option<&str> {
+        self.properties?.tag.as_deref()
+    }
+
+    pub fn alias(&self) -> Option<&str> {
+        self.properties?.alias.as_deref()
+    }
+
+    pub fn id(&self) -> Option<DecimalNumber> {
+        self.properties?.id
+    }
+
+    /// Returns whether the control displays its placeholder text instead of a value.
+    pub fn is_showing_placeholder(&self) -> bool {
+        matches!(self.properties, Some(properties) if properties.showing_placeholder_header == Some(true))
+    }
+
+    /// Returns the typed value of the control. Controls showing their placeholder have an empty value.
+    pub fn value(&self) -> ContentControlValue {
+        let text = if self.is_showing_placeholder() {
+            String::new()
+        } else {
+            self.text.clone()
+        };
+
+        match self
+            .properties
+            .and_then(|properties| properties.control_choice.as_ref())
+        {
+            Some(SdtPrChoice::Date(date)) if !self.is_showing_placeholder() => {
+                ContentControlValue::Date(date.date().ok().flatten())
+            }
+            Some(SdtPrChoice::Date(_)) => ContentControlValue::Date(None),
+            Some(SdtPrChoice::DropDownList(list)) => selection(&list.list_items, text),
+            Some(SdtPrChoice::ComboBox(combo_box)) => selection(&combo_box.list_items, text),
+            Some(SdtPrChoice::Picture) => ContentControlValue::Picture,
+            _ => ContentControlValue::Text(text),
+        }
+    }
+}
+
+/// Finds the selected item by its display text. Items without a display text display their value.
+fn selection(list_items: &[SdtListItem], display_text: String) -> ContentControlValue {
+    let value = list_items
+        .iter()
+        .find(|item| {
+            let item_text = if item.display_text.is_empty() {
+                &item.value
+            } else {
+                &item.display_text
+            };
+            !display_text.is_empty() && *item_text == display_text
+        })
+        .map(|item| item.value.clone());
+
+    ContentControlValue::Selection { value, display_text }
+}
+
+impl Document {
+    /// Enumerates the content controls of the document in document order, outer controls before the controls nested
+    /// in them.
+    ///
+    /// ```
+    /// use oox::docx::{
+    ///     contentcontrols::ContentControlValue,
+    ///     wml::document::Document,
+    /// };
+    /// # use oox::xml::XmlNode;
+    /// # use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p>
+    ///         <w:r><w:t xml:space="preserve">Name: </w:t></w:r>
+    ///         <w:sdt>
+    ///             <w:sdtPr><w:tag w:val="name" /><w:text w:multiLine="0" /></w:sdtPr>
+    ///             <w:sdtContent><w:r><w:t>John</w:t></w:r></w:sdtContent>
+    ///         </w:sdt>
+    ///     </w:p>
+    /// </w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// let control = document.content_controls().find(|control| control.tag() == Some("name")).unwrap();
+    /// assert_eq!(control.value(), ContentControlValue::Text(String::from("John")));
+    /// assert_eq!(control.run_path, Some(vec![1]));
+    /// ```
+    pub fn content_controls(&self) -> impl Iterator<Item = ContentControl<'_>> {
+        let mut collector = Collector::default();
+        if let Some(body) = &self.body {
+            collector.blocks(&body.block_level_elements, &mut Vec::new());
+        }
+
+        collector.controls.into_iter()
+    }
+}
+
+#[derive(Default)]
+struct Collector<'a> {
+    controls: Vec<ContentControl<'a>>,
+    parents: Vec<usize>,
+}
+
+impl<'a> Collector<'a> {
+    fn enter(
+        &mut self,
+        level: ContentControlLevel,
+        properties: Option<&'a SdtPr>,
+        path: &[usize],
+        run_path: Option<&[usize]>,
+        text: String,
+    ) {
+        self.controls.push(ContentControl {
+            level,
+            properties,
+            path: path.to_vec(),
+            run_path: run_path.map(<[usize]>::to_vec),
+            parent: self.parents.last().copied(),
+            text,
+        });
+        self.parents.push(self.controls.len() - 1);
+    }
+
+    fn leave(&mut self) {
+        self.parents.pop();
+    }
+
+    fn blocks(&mut self, elements: &'a [BlockLevelElts], path: &mut Vec<usize>) {
+        for (index, element) in elements.iter().enumerate() {
+            if let BlockLevelElts::Chunk(content) = element {
+                path.push(index);
+                self.content_block(content, path);
+                path.pop();
+            }
+        }
+    }
+
+    fn content_blocks(&mut self, contents: &'a [ContentBlockContent], path: &mut Vec<usize>) {
+        for (index, content) in contents.iter().enumerate() {
+            path.push(index);
+            self.content_block(content, path);
+            path.pop();
+        }
+    }
+
+    fn content_block(&mut self, content: &'a ContentBlockContent, path: &mut Vec<usize>) {
+        match content {
+            ContentBlockContent::Paragraph(paragraph) => {
+                self.paragraph_contents(&paragraph.contents, path, &mut Vec::new())
+            }
+            ContentBlockContent::Table(table) => {
+                for (index, row_content) in table.row_contents.iter().enumerate() {
+                    path.push(index);
+                    self.row(row_content, path);
+                    path.pop();
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                let contents = sdt
+                    .sdt_content
+                    .as_ref()
+                    .map(|content| content.block_contents.as_slice())
+                    .unwrap_or_default();
+
+                let package = Package::default();
+                let mut notes = NoteCollector::new(&package, NotePlacement::Omit);
+                let mut lines = Vec::new();
+                for content in contents {
+                    content_block_lines(content, &mut notes, &mut lines);
+                }
+
+                self.enter(
+                    ContentControlLevel::Block,
+                    sdt.sdt_properties.as_ref(),
+                    path,
+                    None,
+                    lines.join("\n"),
+                );
+                self.content_blocks(contents, path);
+                self.leave();
+            }
+            ContentBlockContent::CustomXml(custom_xml) => self.content_blocks(&custom_xml.block_contents, path),
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn row(&mut self, content: &'a ContentRowContent, path: &mut Vec<usize>) {
+        match content {
+            ContentRowContent::Table(row) => {
+                for (index, cell_content) in row.contents.iter().enumerate() {
+                    path.push(index);
+                    self.cell(cell_content, path);
+                    path.pop();
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => self.rows(&custom_xml.contents, path),
+            ContentRowContent::Sdt(sdt) => {
+                let contents = sdt
+                    .content
+                    .as_ref()
+                    .map(|content| content.contents.as_slice())
+                    .unwrap_or_default();
+
+                let package = Package::default();
+                let mut notes = NoteCollector::new(&package, NotePlacement::Omit);
+                let mut lines = Vec::new();
+                for content in contents {
+                    row_lines(content, &mut notes, &mut lines);
+                }
+
+                self.enter(
+                    ContentControlLevel::Row,
+                    sdt.properties.as_ref(),
+                    path,
+                    None,
+                    lines.join("\n"),
+                );
+                self.rows(contents, path);
+                self.leave();
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn rows(&mut self, contents: &'a [ContentRowContent], path: &mut Vec<usize>) {
+        for (index, content) in contents.iter().enumerate() {
+            path.push(index);
+            self.row(content, path);
+            path.pop();
+        }
+    }
+
+    fn cell(&mut self, content: &'a ContentCellContent, path: &mut Vec<usize>) {
+        match content {
+            ContentCellContent::Cell(cell) => self.blocks(&cell.block_level_elements, path),
+            ContentCellContent::CustomXml(custom_xml) => self.cells(&custom_xml.contents, path),
+            ContentCellContent::Sdt(sdt) => {
+                let contents = sdt
+                    .content
+                    .as_ref()
+                    .map(|content| content.contents.as_slice())
+                    .unwrap_or_default();
+
+                let package = Package::default();
+                let mut notes = NoteCollector::new(&package, NotePlacement::Omit);
+                let mut cells = Vec::new();
+                for content in contents {
+                    cell_texts(content, &mut notes, &mut cells);
+                }
+
+                self.enter(
+                    ContentControlLevel::Cell,
+                    sdt.properties.as_ref(),
+                    path,
+                    None,
+                    cells.join("\t"),
+                );
+                self.cells(contents, path);
+                self.leave();
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn cells(&mut self, contents: &'a [ContentCellContent], path: &mut Vec<usize>) {
+        for (index, content) in contents.iter().enumerate() {
+            path.push(index);
+            self.cell(content, path);
+            path.pop();
+        }
+    }
+
+    fn paragraph_contents(&mut self, contents: &'a [PContent], path: &[usize], run_path: &mut Vec<usize>) {
+        for (index, content) in contents.iter().enumerate() {
+            run_path.push(index);
+            match content {
+                PContent::ContentRunContent(content) => self.content_run(content, path, run_path),
+                PContent::SimpleField(field) => self.paragraph_contents(&field.paragraph_contents, path, run_path),
+                PContent::Hyperlink(hyperlink) => {
+                    self.paragraph_contents(&hyperlink.paragraph_contents, path, run_path)
+                }
+                PContent::SubDocument(_) => (),
+            }
+            run_path.pop();
+        }
+    }
+
+    fn content_run(&mut self, content: &'a ContentRunContent, path: &[usize], run_path: &mut Vec<usize>) {
+        match content {
+            ContentRunContent::Run(_) => (),
+            ContentRunContent::CustomXml(custom_xml) => {
+                self.paragraph_contents(&custom_xml.paragraph_contents, path, run_path)
+            }
+            ContentRunContent::SmartTag(smart_tag) => {
+                self.paragraph_contents(&smart_tag.paragraph_contents, path, run_path)
+            }
+            ContentRunContent::Sdt(sdt) => {
+                let contents = sdt
+                    .sdt_content
+                    .as_ref()
+                    .map(|content| content.p_contents.as_slice())
+                    .unwrap_or_default();
+
+                let mut text = String::new();
+                for_each_inline(contents, &mut |item| match item {
+                    InlineItem::Text(value) => text.push_str(value),
+                    InlineItem::Tab => text.push('\t'),
+                    InlineItem::Break(_) => text.push('\n'),
+                    InlineItem::NoteReference(..) => (),
+                });
+
+                self.enter(
+                    ContentControlLevel::Run,
+                    sdt.sdt_properties.as_ref(),
+                    path,
+                    Some(run_path),
+                    text,
+                );
+                self.paragraph_contents(contents, path, run_path);
+                self.leave();
+            }
+            ContentRunContent::Bidirectional(dir) => self.paragraph_contents(&dir.p_contents, path, run_path),
+            ContentRunContent::BidirectionalOverride(bdo) => self.paragraph_contents(&bdo.p_contents, path, run_path),
+            ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+                for (index, choice) in track_change.choices.iter().enumerate() {
+                    run_path.push(index);
+                    match choice {
+                        RunTrackChangeChoice::ContentRunContent(content) => self.content_run(content, path, run_path),
+                    }
+                    run_path.pop();
+                }
+            }
+            ContentRunContent::RunLevelElements(_) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_content_controls() {
+        let xml = r#"<w:document><w:body>
+            <w:sdt>
+                <w:sdtPr><w:alias w:val="Section" /><w:id w:val="1" /><w:group /></w:sdtPr>
+                <w:sdtContent>
+                    <w:p>
+                        <w:sdt>
+                            <w:sdtPr>
+                                <w:tag w:val="color" />
+                                <w:dropDownList>
+                                    <w:listItem w:displayText="Red" w:value="r" />
+                                    <w:listItem w:displayText="Blue" w:value="b" />
+                                </w:dropDownList>
+                            </w:sdtPr>
+                            <w:sdtContent><w:r><w:t>Blue</w:t></w:r></w:sdtContent>
+                        </w:sdt>
+                    </w:p>
+                    <w:p>
+                        <w:sdt>
+                            <w:sdtPr>
+                                <w:tag w:val="due" />
+                                <w:date w:fullDate="2021-03-04T00:00:00Z"><w:dateFormat w:val="d/M/yyyy" /></w:date>
+                            </w:sdtPr>
+                            <w:sdtContent><w:r><w:t>4/3/2021</w:t></w:r></w:sdtContent>
+                        </w:sdt>
+                        <w:sdt>
+                            <w:sdtPr><w:tag w:val="note" /><w:showingPlcHdr /><w:text w:multiLine="0" /></w:sdtPr>
+                            <w:sdtContent><w:r><w:t>Click here</w:t></w:r></w:sdtContent>
+                        </w:sdt>
+                    </w:p>
+                </w:sdtContent>
+            </w:sdt>
+            <w:tbl>
+                <w:tblPr /><w:tblGrid />
+                <w:tr>
+                    <w:sdt>
+                        <w:sdtPr><w:tag w:val="cell" /></w:sdtPr>
+                        <w:sdtContent><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc></w:sdtContent>
+                    </w:sdt>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let controls: Vec<ContentControl<'_>> = document.content_controls().collect();
+        assert_eq!(controls.len(), 5);
+
+        let section = &controls[0];
+        assert_eq!(
+            (section.level, section.alias(), section.id()),
+            (ContentControlLevel::Block, Some("Section"), Some(1))
+        );
+        assert_eq!(section.path, vec![0]);
+        assert_eq!(section.parent, None);
+        assert_eq!(section.text, "Blue\n4/3/2021Click here");
+
+        let color = &controls[1];
+        assert_eq!(color.level, ContentControlLevel::Run);
+        assert_eq!(
+            (color.path.as_slice(), color.run_path.as_deref()),
+            (&[0, 0][..], Some(&[0][..]))
+        );
+        assert_eq!(color.parent, Some(0));
+        assert_eq!(
+            color.value(),
+            ContentControlValue::Selection {
+                value: Some(String::from("b")),
+                display_text: String::from("Blue"),
+            }
+        );
+
+        let due = &controls[2];
+        assert_eq!(due.tag(), Some("due"));
+        assert_eq!(
+            due.value(),
+            ContentControlValue::Date(CalendarDate::parse("2021-03-04").ok())
+        );
+
+        let note = &controls[3];
+        assert!(note.is_showing_placeholder());
+        assert_eq!(note.value(), ContentControlValue::Text(String::new()));
+        assert_eq!(note.run_path, Some(vec![1]));
+
+        let cell = &controls[4];
+        assert_eq!(
+            (cell.level, cell.path.as_slice()),
+            (ContentControlLevel::Cell, &[1, 0, 0][..])
+        );
+        assert_eq!(cell.value(), ContentControlValue::Text(String::from("A")));
+    }
+}
@@ -0,0 +1,377 @@
+use super::{
+    package::Package,
+    text::{cell_texts, content_block_lines, for_each_inline, row_lines, InlineItem, NoteCollector, NotePlacement},
+    wml::{
+        document::{
+            Attr, BlockLevelElts, ContentBlockContent, ContentRunContent, CustomXmlPr, PContent, RunLevelElts,
+            RunTrackChangeChoice,
+        },
+        table::{ContentCellContent, ContentRowContent},
+    },
+};
+use crate::shared::customxml::CustomXmlPart;
+
+/// The level of the document content a custom XML element wraps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CustomXmlLevel {
+    Block,
+    Row,
+    Cell,
+    Run,
+}
+
+/// A custom XML element of the document (`w:customXml`) along with the content it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomXmlElement {
+    pub level: CustomXmlLevel,
+    /// The namespace URI of the element (`w:uri`).
+    pub uri: Option<String>,
+    /// The local name of the element (`w:element`).
+    pub element: String,
+    /// The attributes of the element (`w:customXmlPr/w:attr`).
+    pub attributes: Vec<Attr>,
+    /// The placeholder text shown by Word if the element has no content (`w:customXmlPr/w:placeholder`).
+    pub placeholder: Option<String>,
+    /// The plain text of the covered content. Paragraphs and table rows are separated by newlines and table cells
+    /// by tabs, the same way as `Package::extract_text` does.
+    pub text: String,
+    /// The part name of the custom XML data part whose schema references contain the namespace of the element.
+    pub schema_part: Option<String>,
+    /// The custom XML elements nested in the covered content.
+    pub children: Vec<CustomXmlElement>,
+}
+
+impl CustomXmlElement {
+    /// Returns the qualified name of the element in Clark notation, e.g. `{urn:invoice}total`. Elements without a
+    /// namespace return their local name.
+    pub fn qualified_name(&self) -> String {
+        match &self.uri {
+            Some(uri) if !uri.is_empty() => format!("{{{}}}{}", uri, self.element),
+            _ => self.element.clone(),
+        }
+    }
+
+    /// Iterates over this element and all of its descendants in document order.
+    pub fn descendants(&self) -> Box<dyn Iterator<Item = &CustomXmlElement> + '_> {
+        Box::new(std::iter::once(self).chain(self.children.iter().flat_map(|child| child.descendants())))
+    }
+}
+
+impl Package {
+    /// Returns the custom XML data part that declares a schema with the given namespace URI.
+    pub fn find_custom_xml_part_for_schema<T: AsRef<str>>(&self, uri: T) -> Option<&CustomXmlPart> {
+        self.custom_xml_parts.iter().find(|part| {
+            part.properties
+                .iter()
+                .flat_map(|properties| properties.schema_refs.iter())
+                .any(|schema_ref| schema_ref == uri.as_ref())
+        })
+    }
+
+    /// Builds a structured view of the custom XML markup of the main document. The top level custom XML elements
+    /// are returned in document order with the nested elements as their children. Elements are paired with the
+    /// custom XML data part that references their schema, if any.
+    pub fn custom_xml_elements(&self) -> Vec<CustomXmlElement> {
+        let mut mapper = CustomXmlMapper { package: self };
+        let mut elements = Vec::new();
+
+        let body = self.main_document.as_ref().and_then(|document| document.body.as_ref());
+        for block_level_element in body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            if let BlockLevelElts::Chunk(content) = block_level_element {
+                mapper.content_block(content, &mut elements);
+            }
+        }
+
+        elements
+    }
+
+    /// Returns the covered text of every custom XML element of the main document keyed by its qualified name, in
+    /// document order. Nested elements are included.
+    pub fn custom_xml_values(&self) -> Vec<(String, String)> {
+        self.custom_xml_elements()
+            .iter()
+            .flat_map(CustomXmlElement::descendants)
+            .map(|element| (element.qualified_name(), element.text.clone()))
+            .collect()
+    }
+}
+
+struct CustomXmlMapper<'a> {
+    package: &'a Package,
+}
+
+impl<'a> CustomXmlMapper<'a> {
+    fn element(
+        &self,
+        level: CustomXmlLevel,
+        uri: Option<&String>,
+        element: &str,
+        properties: Option<&CustomXmlPr>,
+        text: String,
+        children: Vec<CustomXmlElement>,
+    ) -> CustomXmlElement {
+        let schema_part = uri
+            .and_then(|uri| self.package.find_custom_xml_part_for_schema(uri))
+            .map(|part| part.part_name.clone());
+
+        CustomXmlElement {
+            level,
+            uri: uri.cloned(),
+            element: String::from(element),
+            attributes: properties
+                .map(|properties| properties.attributes.clone())
+                .unwrap_or_default(),
+            placeholder: properties.and_then(|properties| properties.placeholder.clone()),
+            text,
+            schema_part,
+            children,
+        }
+    }
+
+    fn content_block(&mut self, content: &ContentBlockContent, elements: &mut Vec<CustomXmlElement>) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                let mut children = Vec::new();
+                let mut notes = NoteCollector::new(self.package, NotePlacement::Omit);
+                let mut lines = Vec::new();
+                for content in &custom_xml.block_contents {
+                    self.content_block(content, &mut children);
+                    content_block_lines(content, &mut notes, &mut lines);
+                }
+
+                elements.push(self.element(
+                    CustomXmlLevel::Block,
+                    custom_xml.uri.as_ref(),
+                    &custom_xml.element,
+                    custom_xml.custom_xml_properties.as_ref(),
+                    lines.join("\n"),
+                    children,
+                ));
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.content_block(content, elements);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph_contents(&paragraph.contents, elements),
+            ContentBlockContent::Table(table) => {
+                for row_content in &table.row_contents {
+                    self.row(row_content, elements);
+                }
+            }
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn row(&mut self, content: &ContentRowContent, elements: &mut Vec<CustomXmlElement>) {
+        match content {
+            ContentRowContent::Table(row) => {
+                for cell_content in &row.contents {
+                    self.cell(cell_content, elements);
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                let mut children = Vec::new();
+                let mut notes = NoteCollector::new(self.package, NotePlacement::Omit);
+                let mut lines = Vec::new();
+                for content in &custom_xml.contents {
+                    self.row(content, &mut children);
+                    row_lines(content, &mut notes, &mut lines);
+                }
+
+                elements.push(self.element(
+                    CustomXmlLevel::Row,
+                    custom_xml.uri.as_ref(),
+                    &custom_xml.element,
+                    custom_xml.custom_xml_properties.as_ref(),
+                    lines.join("\n"),
+                    children,
+                ));
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.row(content, elements);
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn cell(&mut self, content: &ContentCellContent, elements: &mut Vec<CustomXmlElement>) {
+        match content {
+            ContentCellContent::Cell(cell) => {
+                for block_level_element in &cell.block_level_elements {
+                    if let BlockLevelElts::Chunk(content) = block_level_element {
+                        self.content_block(content, elements);
+                    }
+                }
+            }
+            ContentCellContent::CustomXml(custom_xml) => {
+                let mut children = Vec::new();
+                let mut notes = NoteCollector::new(self.package, NotePlacement::Omit);
+                let mut cells = Vec::new();
+                for content in &custom_xml.contents {
+                    self.cell(content, &mut children);
+                    cell_texts(content, &mut notes, &mut cells);
+                }
+
+                elements.push(self.element(
+                    CustomXmlLevel::Cell,
+                    custom_xml.uri.as_ref(),
+                    &custom_xml.element,
+                    custom_xml.custom_xml_properties.as_ref(),
+                    cells.join("\t"),
+                    children,
+                ));
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.cell(content, elements);
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn paragraph_contents(&mut self, contents: &[PContent], elements: &mut Vec<CustomXmlElement>) {
+        for content in contents {
+            match content {
+                PContent::ContentRunContent(content) => self.content_run(content, elements),
+                PContent::SimpleField(field) => self.paragraph_contents(&field.paragraph_contents, elements),
+                PContent::Hyperlink(hyperlink) => self.paragraph_contents(&hyperlink.paragraph_contents, elements),
+                PContent::SubDocument(_) => (),
+            }
+        }
+    }
+
+    fn content_run(&mut self, content: &ContentRunContent, elements: &mut Vec<CustomXmlElement>) {
+        match content {
+            ContentRunContent::CustomXml(custom_xml) => {
+                let mut children = Vec::new();
+                self.paragraph_contents(&custom_xml.paragraph_contents, &mut children);
+
+                let mut text = String::new();
+                for_each_inline(&custom_xml.paragraph_contents, &mut |item| match item {
+                    InlineItem::Text(value) => text.push_str(value),
+                    InlineItem::Tab => text.push('\t'),
+                    InlineItem::Break(_) => text.push('\n'),
+                    InlineItem::NoteReference(..) => (),
+                });
+
+                elements.push(self.element(
+                    CustomXmlLevel::Run,
+                    Some(&custom_xml.uri),
+                    &custom_xml.element,
+                    custom_xml.custom_xml_properties.as_ref(),
+                    text,
+                    children,
+                ));
+            }
+            ContentRunContent::SmartTag(smart_tag) => self.paragraph_contents(&smart_tag.paragraph_contents, elements),
+            ContentRunContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    self.paragraph_contents(&sdt_content.p_contents, elements);
+                }
+            }
+            ContentRunContent::Bidirectional(dir) => self.paragraph_contents(&dir.p_contents, elements),
+            ContentRunContent::BidirectionalOverride(bdo) => self.paragraph_contents(&bdo.p_contents, elements),
+            ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+                for choice in &track_change.choices {
+                    match choice {
+                        RunTrackChangeChoice::ContentRunContent(content) => self.content_run(content, elements),
+                    }
+                }
+            }
+            ContentRunContent::Run(_) | ContentRunContent::RunLevelElements(_) => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::wml::document::Document, shared::customxml::DataStoreItem, xml::XmlNode};
+    use std::str::FromStr;
+
+    fn package_for_test() -> Package {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:customXml w:uri="urn:invoice" w:element="invoice">
+                    <w:p>
+                        <w:r><w:t xml:space="preserve">Customer: </w:t></w:r>
+                        <w:customXml w:uri="urn:invoice" w:element="customer">
+                            <w:customXmlPr>
+                                <w:placeholder w:val="Customer name" />
+                                <w:attr w:uri="urn:invoice" w:name="id" w:val="42" />
+                            </w:customXmlPr>
+                            <w:r><w:t>Contoso</w:t></w:r>
+                        </w:customXml>
+                    </w:p>
+                    <w:p>
+                        <w:r><w:t>Total: 100</w:t></w:r>
+                    </w:p>
+                </w:customXml>
+                <w:customXml w:element="note">
+                    <w:p>
+                        <w:r><w:t>Unmapped</w:t></w:r>
+                    </w:p>
+                </w:customXml>
+            </w:body>
+        </w:document>"#;
+
+        let item_xml = r#"<inv:invoice xmlns:inv="urn:invoice"><inv:customer>Contoso</inv:customer></inv:invoice>"#;
+
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            custom_xml_parts: vec![CustomXmlPart {
+                part_name: String::from("customXml/item1.xml"),
+                xml: XmlNode::from_str(item_xml).unwrap(),
+                properties: Some(DataStoreItem {
+                    item_id: String::from("{6E3B1A2C-0000-4000-8000-000000000001}"),
+                    schema_refs: vec![String::from("urn:invoice")],
+                }),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_custom_xml_elements() {
+        let elements = package_for_test().custom_xml_elements();
+        assert_eq!(elements.len(), 2);
+
+        let invoice = &elements[0];
+        assert_eq!(invoice.level, CustomXmlLevel::Block);
+        assert_eq!(invoice.qualified_name(), "{urn:invoice}invoice");
+        assert_eq!(invoice.text, "Customer: Contoso\nTotal: 100");
+        assert_eq!(invoice.schema_part.as_deref(), Some("customXml/item1.xml"));
+        assert_eq!(invoice.children.len(), 1);
+
+        let customer = &invoice.children[0];
+        assert_eq!(customer.level, CustomXmlLevel::Run);
+        assert_eq!(customer.text, "Contoso");
+        assert_eq!(customer.placeholder.as_deref(), Some("Customer name"));
+        assert_eq!(customer.attributes[0].value, "42");
+
+        assert_eq!(elements[1].qualified_name(), "note");
+        assert_eq!(elements[1].schema_part, None);
+    }
+
+    #[test]
+    pub fn test_custom_xml_values() {
+        assert_eq!(
+            package_for_test().custom_xml_values(),
+            vec![
+                (
+                    String::from("{urn:invoice}invoice"),
+                    String::from("Customer: Contoso\nTotal: 100")
+                ),
+                (String::from("{urn:invoice}customer"), String::from("Contoso")),
+                (String::from("note"), String::from("Unmapped")),
+            ]
+        );
+    }
+}
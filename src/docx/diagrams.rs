@@ -0,0 +1,56 @@
+use super::{package::Package, wml::document::DrawingChoice};
+use crate::shared::drawingml::diagrams::{DataModel, DiagramRelationshipIds, DIAGRAM_DATA_RELATION_TYPE};
+use std::{error::Error, fs::File};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+impl DrawingChoice {
+    /// Returns the relationships to the parts of the diagram (SmartArt) displayed by the drawing, or `None` if the
+    /// drawing doesn't display a diagram.
+    pub fn diagram_relationship_ids(&self) -> Option<&DiagramRelationshipIds> {
+        let graphic = match self {
+            DrawingChoice::Inline(inline) => &inline.graphic,
+            DrawingChoice::Anchor(anchor) => &anchor.graphic,
+        };
+
+        graphic.graphic_data.diagram_relationship_ids.as_ref()
+    }
+}
+
+impl Package {
+    /// Reads the diagram data model part targeted by a relationship of the main document from the package file.
+    /// Returns `None` if there's no such relationship or it targets an external resource.
+    pub fn diagram_data_for<T: AsRef<str>>(&self, rel_id: T) -> Result<Option<DataModel>> {
+        let part_name = match self.main_document_part_name(rel_id) {
+            Some(part_name) => part_name,
+            None => return Ok(None),
+        };
+
+        let file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&file)?;
+        let mut zip_file = zipper.by_name(&part_name)?;
+        Ok(Some(DataModel::from_zip_file(&mut zip_file)?))
+    }
+
+    /// Reads the data model of the diagram displayed by a drawing. Returns `None` if the drawing doesn't display a diagram.
+    pub fn drawing_diagram_data(&self, drawing: &DrawingChoice) -> Result<Option<DataModel>> {
+        match drawing.diagram_relationship_ids() {
+            Some(relationship_ids) => self.diagram_data_for(&relationship_ids.data),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the data models of all diagrams of the main document in the order of the relationships of the main
+    /// document, e.g. to extract the text of every SmartArt with [`DataModel::text`].
+    pub fn diagram_data(&self) -> Result<Vec<DataModel>> {
+        let mut data_models = Vec::new();
+        for relationship in &self.main_document_relationships {
+            if relationship.rel_type == DIAGRAM_DATA_RELATION_TYPE {
+                data_models.extend(self.diagram_data_for(&relationship.id)?);
+            }
+        }
+
+        Ok(data_models)
+    }
+}
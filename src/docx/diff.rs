@@ -0,0 +1,641 @@
+use super::{
+    textmap::RunPath,
+    wml::{
+        document::{BlockLevelElts, ContentBlockContent, Document, RunInnerContent, P, R},
+        table::{ContentCellContent, ContentRowContent, Row, Tbl},
+    },
+};
+use std::mem;
+
+/// The kind of an element reported by a [`Difference`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElementKind {
+    Paragraph,
+    Table,
+    Row,
+    Cell,
+    ContentControl,
+    CustomXml,
+    /// Any other block, row or cell level element, e.g. an `w:altChunk` or a bookmark between paragraphs
+    Other,
+}
+
+/// A difference between two documents.
+///
+/// Paths of block level elements, rows and cells follow the convention of [`RunPath::paragraph`], paths of runs are
+/// [`RunPath`]s. Paths of removed elements point into the old document, paths of inserted elements point into the new
+/// one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+    Inserted {
+        kind: ElementKind,
+        path: Vec<usize>,
+    },
+    Removed {
+        kind: ElementKind,
+        path: Vec<usize>,
+    },
+    /// The properties of a paragraph (`w:pPr`), table (`w:tblPr` or `w:tblGrid`), row, cell, content control or
+    /// custom XML element changed.
+    PropertiesChanged {
+        kind: ElementKind,
+        old: Vec<usize>,
+        new: Vec<usize>,
+    },
+    /// The element changed in a way not covered by the other differences, e.g. a bookmark was added to a paragraph
+    /// or the target of a hyperlink changed.
+    Modified {
+        kind: ElementKind,
+        old: Vec<usize>,
+        new: Vec<usize>,
+    },
+    RunInserted(RunPath),
+    RunRemoved(RunPath),
+    RunTextChanged {
+        old: RunPath,
+        new: RunPath,
+        old_text: String,
+        new_text: String,
+    },
+    RunPropertiesChanged {
+        old: RunPath,
+        new: RunPath,
+    },
+    /// The properties of the last section of the document changed.
+    SectionPropertiesChanged,
+}
+
+impl Document {
+    /// Compares the document with a newer version of it and returns the differences in document order.
+    ///
+    /// Elements are matched by equality first. The unmatched elements between two matches are paired in order if
+    /// they're of the same kind and compared recursively, the rest of them are reported as removed or inserted.
+    /// Differences in revision save ids (`w:rsid*`) alone aren't reported.
+    ///
+    /// ```
+    /// use oox::docx::{
+    ///     diff::{Difference, ElementKind},
+    ///     wml::document::{Document, P},
+    /// };
+    ///
+    /// let old = Document::builder().paragraph(P::builder().text("Hello").build()).build();
+    /// let new = Document::builder()
+    ///     .paragraph(P::builder().text("Hello world").build())
+    ///     .paragraph(P::builder().text("Bye").build())
+    ///     .build();
+    ///
+    /// let differences = old.diff(&new);
+    /// assert_eq!(differences.len(), 2);
+    /// assert!(matches!(&differences[0], Difference::RunTextChanged { new_text, .. } if new_text == "Hello world"));
+    /// assert_eq!(differences[1], Difference::Inserted { kind: ElementKind::Paragraph, path: vec![1] });
+    /// ```
+    pub fn diff(&self, new: &Document) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        let old_blocks = self.body.as_ref().map(|body| body.block_level_elements.as_slice());
+        let new_blocks = new.body.as_ref().map(|body| body.block_level_elements.as_slice());
+        diff_blocks(
+            old_blocks.unwrap_or_default(),
+            new_blocks.unwrap_or_default(),
+            block_content,
+            &[],
+            &[],
+            &mut differences,
+        );
+
+        let old_section = self.body.as_ref().and_then(|body| body.section_properties.as_ref());
+        let new_section = new.body.as_ref().and_then(|body| body.section_properties.as_ref());
+        if old_section != new_section {
+            differences.push(Difference::SectionPropertiesChanged);
+        }
+
+        differences
+    }
+}
+
+/// A change between two sequences of elements
+enum Change {
+    Removed(usize),
+    Inserted(usize),
+    /// Unequal elements at the same position of a change, compared recursively
+    Paired(usize, usize),
+}
+
+/// Compares two sequences using their longest common subsequence.
+fn diff_sequence<T>(
+    old: &[T],
+    new: &[T],
+    eq: impl Fn(&T, &T) -> bool,
+    can_pair: impl Fn(&T, &T) -> bool,
+) -> Vec<Change> {
+    let prefix = old.iter().zip(new).take_while(|(old, new)| eq(old, new)).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| eq(old, new))
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    // lengths[i][j] is the length of the longest common subsequence of old_middle[i..] and new_middle[j..]
+    let (n, m) = (old_middle.len(), new_middle.len());
+    let mut lengths = vec![0usize; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i * (m + 1) + j] = if eq(&old_middle[i], &new_middle[j]) {
+                lengths[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                lengths[(i + 1) * (m + 1) + j].max(lengths[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut inserted = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && eq(&old_middle[i], &new_middle[j]) {
+            flush_hunk(&mut removed, &mut inserted, old, new, &can_pair, &mut changes);
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lengths[(i + 1) * (m + 1) + j] >= lengths[i * (m + 1) + j + 1]) {
+            removed.push(prefix + i);
+            i += 1;
+        } else {
+            inserted.push(prefix + j);
+            j += 1;
+        }
+    }
+
+    flush_hunk(&mut removed, &mut inserted, old, new, &can_pair, &mut changes);
+    changes
+}
+
+fn flush_hunk<T>(
+    removed: &mut Vec<usize>,
+    inserted: &mut Vec<usize>,
+    old: &[T],
+    new: &[T],
+    can_pair: &impl Fn(&T, &T) -> bool,
+    changes: &mut Vec<Change>,
+) {
+    let removed = mem::take(removed);
+    let inserted = mem::take(inserted);
+    for index in 0..removed.len().max(inserted.len()) {
+        match (removed.get(index), inserted.get(index)) {
+            (Some(&old_index), Some(&new_index)) if can_pair(&old[old_index], &new[new_index]) => {
+                changes.push(Change::Paired(old_index, new_index))
+            }
+            (old_index, new_index) => {
+                changes.extend(old_index.map(|index| Change::Removed(*index)));
+                changes.extend(new_index.map(|index| Change::Inserted(*index)));
+            }
+        }
+    }
+}
+
+fn child_path(path: &[usize], index: usize) -> Vec<usize> {
+    let mut path = path.to_vec();
+    path.push(index);
+    path
+}
+
+fn block_content(element: &BlockLevelElts) -> Option<&ContentBlockContent> {
+    match element {
+        BlockLevelElts::Chunk(content) => Some(content),
+        BlockLevelElts::AltChunk(_) => None,
+    }
+}
+
+fn nested_block_content(content: &ContentBlockContent) -> Option<&ContentBlockContent> {
+    Some(content)
+}
+
+fn content_block_kind(content: Option<&ContentBlockContent>) -> ElementKind {
+    match content {
+        Some(ContentBlockContent::Paragraph(_)) => ElementKind::Paragraph,
+        Some(ContentBlockContent::Table(_)) => ElementKind::Table,
+        Some(ContentBlockContent::Sdt(_)) => ElementKind::ContentControl,
+        Some(ContentBlockContent::CustomXml(_)) => ElementKind::CustomXml,
+        Some(ContentBlockContent::RunLevelElement(_)) | None => ElementKind::Other,
+    }
+}
+
+fn diff_blocks<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+    content: fn(&T) -> Option<&ContentBlockContent>,
+    old_path: &[usize],
+    new_path: &[usize],
+    differences: &mut Vec<Difference>,
+) {
+    let kind = |element: &T| content_block_kind(content(element));
+    // Paragraphs are compared without revision save ids, otherwise an unchanged paragraph could be paired with an
+    // edited one
+    let normalized = |elements: &[T]| -> Vec<Option<P>> {
+        elements
+            .iter()
+            .map(|element| match content(element) {
+                Some(ContentBlockContent::Paragraph(paragraph)) => Some(normalized_paragraph(paragraph)),
+                _ => None,
+            })
+            .collect()
+    };
+    let old_normalized = normalized(old);
+    let new_normalized = normalized(new);
+    let indices = |elements: &[T]| (0..elements.len()).collect::<Vec<usize>>();
+
+    let changes = diff_sequence(
+        &indices(old),
+        &indices(new),
+        |&old_index, &new_index| match (&old_normalized[old_index], &new_normalized[new_index]) {
+            (Some(old_paragraph), Some(new_paragraph)) => old_paragraph == new_paragraph,
+            _ => old[old_index] == new[new_index],
+        },
+        |&old_index, &new_index| {
+            kind(&old[old_index]) == kind(&new[new_index]) && kind(&old[old_index]) != ElementKind::Other
+        },
+    );
+
+    for change in changes {
+        match change {
+            Change::Removed(index) => differences.push(Difference::Removed {
+                kind: kind(&old[index]),
+                path: child_path(old_path, index),
+            }),
+            Change::Inserted(index) => differences.push(Difference::Inserted {
+                kind: kind(&new[index]),
+                path: child_path(new_path, index),
+            }),
+            Change::Paired(old_index, new_index) => {
+                if let (Some(old_content), Some(new_content)) = (content(&old[old_index]), content(&new[new_index])) {
+                    diff_content_block(
+                        old_content,
+                        new_content,
+                        &child_path(old_path, old_index),
+                        &child_path(new_path, new_index),
+                        differences,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn diff_content_block(
+    old: &ContentBlockContent,
+    new: &ContentBlockContent,
+    old_path: &[usize],
+    new_path: &[usize],
+    differences: &mut Vec<Difference>,
+) {
+    match (old, new) {
+        (ContentBlockContent::Paragraph(old), ContentBlockContent::Paragraph(new)) => {
+            diff_paragraph(old, new, old_path, new_path, differences)
+        }
+        (ContentBlockContent::Table(old), ContentBlockContent::Table(new)) => {
+            diff_table(old, new, old_path, new_path, differences)
+        }
+        (ContentBlockContent::Sdt(old), ContentBlockContent::Sdt(new)) => {
+            if old.sdt_properties != new.sdt_properties || old.sdt_end_properties != new.sdt_end_properties {
+                differences.push(properties_changed(ElementKind::ContentControl, old_path, new_path));
+            }
+
+            let old_contents = old
+                .sdt_content
+                .as_ref()
+                .map(|content| content.block_contents.as_slice());
+            let new_contents = new
+                .sdt_content
+                .as_ref()
+                .map(|content| content.block_contents.as_slice());
+            diff_blocks(
+                old_contents.unwrap_or_default(),
+                new_contents.unwrap_or_default(),
+                nested_block_content,
+                old_path,
+                new_path,
+                differences,
+            );
+        }
+        (ContentBlockContent::CustomXml(old), ContentBlockContent::CustomXml(new)) => {
+            if old.custom_xml_properties != new.custom_xml_properties
+                || old.uri != new.uri
+                || old.element != new.element
+            {
+                differences.push(properties_changed(ElementKind::CustomXml, old_path, new_path));
+            }
+
+            diff_blocks(
+                &old.block_contents,
+                &new.block_contents,
+                nested_block_content,
+                old_path,
+                new_path,
+                differences,
+            );
+        }
+        _ => differences.push(Difference::Modified {
+            kind: content_block_kind(Some(old)),
+            old: old_path.to_vec(),
+            new: new_path.to_vec(),
+        }),
+    }
+}
+
+fn properties_changed(kind: ElementKind, old_path: &[usize], new_path: &[usize]) -> Difference {
+    Difference::PropertiesChanged {
+        kind,
+        old: old_path.to_vec(),
+        new: new_path.to_vec(),
+    }
+}
+
+fn diff_table(old: &Tbl, new: &Tbl, old_path: &[usize], new_path: &[usize], differences: &mut Vec<Difference>) {
+    if old.properties != new.properties || old.grid != new.grid {
+        differences.push(properties_changed(ElementKind::Table, old_path, new_path));
+    }
+
+    let kind = |content: &ContentRowContent| match content {
+        ContentRowContent::Table(_) => ElementKind::Row,
+        ContentRowContent::CustomXml(_) => ElementKind::CustomXml,
+        ContentRowContent::Sdt(_) => ElementKind::ContentControl,
+        ContentRowContent::RunLevelElements(_) => ElementKind::Other,
+    };
+
+    let changes = diff_sequence(&old.row_contents, &new.row_contents, PartialEq::eq, |old, new| {
+        kind(old) == ElementKind::Row && kind(new) == ElementKind::Row
+    });
+    for change in changes {
+        match change {
+            Change::Removed(index) => differences.push(Difference::Removed {
+                kind: kind(&old.row_contents[index]),
+                path: child_path(old_path, index),
+            }),
+            Change::Inserted(index) => differences.push(Difference::Inserted {
+                kind: kind(&new.row_contents[index]),
+                path: child_path(new_path, index),
+            }),
+            Change::Paired(old_index, new_index) => {
+                if let (ContentRowContent::Table(old_row), ContentRowContent::Table(new_row)) =
+                    (&old.row_contents[old_index], &new.row_contents[new_index])
+                {
+                    diff_row(
+                        old_row,
+                        new_row,
+                        &child_path(old_path, old_index),
+                        &child_path(new_path, new_index),
+                        differences,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn diff_row(old: &Row, new: &Row, old_path: &[usize], new_path: &[usize], differences: &mut Vec<Difference>) {
+    if old.properties != new.properties || old.property_exceptions != new.property_exceptions {
+        differences.push(properties_changed(ElementKind::Row, old_path, new_path));
+    }
+
+    let kind = |content: &ContentCellContent| match content {
+        ContentCellContent::Cell(_) => ElementKind::Cell,
+        ContentCellContent::CustomXml(_) => ElementKind::CustomXml,
+        ContentCellContent::Sdt(_) => ElementKind::ContentControl,
+        ContentCellContent::RunLevelElement(_) => ElementKind::Other,
+    };
+
+    let changes = diff_sequence(&old.contents, &new.contents, PartialEq::eq, |old, new| {
+        kind(old) == ElementKind::Cell && kind(new) == ElementKind::Cell
+    });
+    for change in changes {
+        match change {
+            Change::Removed(index) => differences.push(Difference::Removed {
+                kind: kind(&old.contents[index]),
+                path: child_path(old_path, index),
+            }),
+            Change::Inserted(index) => differences.push(Difference::Inserted {
+                kind: kind(&new.contents[index]),
+                path: child_path(new_path, index),
+            }),
+            Change::Paired(old_index, new_index) => {
+                if let (ContentCellContent::Cell(old_cell), ContentCellContent::Cell(new_cell)) =
+                    (&old.contents[old_index], &new.contents[new_index])
+                {
+                    let old_cell_path = child_path(old_path, old_index);
+                    let new_cell_path = child_path(new_path, new_index);
+                    if old_cell.properties != new_cell.properties {
+                        differences.push(properties_changed(ElementKind::Cell, &old_cell_path, &new_cell_path));
+                    }
+
+                    diff_blocks(
+                        &old_cell.block_level_elements,
+                        &new_cell.block_level_elements,
+                        block_content,
+                        &old_cell_path,
+                        &new_cell_path,
+                        differences,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn diff_paragraph(old: &P, new: &P, old_path: &[usize], new_path: &[usize], differences: &mut Vec<Difference>) {
+    let difference_count = differences.len();
+    if old.properties != new.properties {
+        differences.push(properties_changed(ElementKind::Paragraph, old_path, new_path));
+    }
+
+    let old_runs = old.runs_with_paths();
+    let new_runs = new.runs_with_paths();
+    let run_path = |paragraph: &[usize], run: &[usize]| RunPath {
+        paragraph: paragraph.to_vec(),
+        run: run.to_vec(),
+    };
+
+    let changes = diff_sequence(&old_runs, &new_runs, |old, new| runs_equal(old.1, new.1), |_, _| true);
+    for change in changes {
+        match change {
+            Change::Removed(index) => differences.push(Difference::RunRemoved(run_path(old_path, &old_runs[index].0))),
+            Change::Inserted(index) => {
+                differences.push(Difference::RunInserted(run_path(new_path, &new_runs[index].0)))
+            }
+            Change::Paired(old_index, new_index) => {
+                let (old_run_path, old_run) = &old_runs[old_index];
+                let (new_run_path, new_run) = &new_runs[new_index];
+                let old_text = run_text(old_run);
+                let new_text = run_text(new_run);
+                let text_changed = old_text != new_text;
+                let properties_changed = old_run.run_properties != new_run.run_properties;
+
+                if text_changed {
+                    differences.push(Difference::RunTextChanged {
+                        old: run_path(old_path, old_run_path),
+                        new: run_path(new_path, new_run_path),
+                        old_text,
+                        new_text,
+                    });
+                }
+
+                if properties_changed {
+                    differences.push(Difference::RunPropertiesChanged {
+                        old: run_path(old_path, old_run_path),
+                        new: run_path(new_path, new_run_path),
+                    });
+                }
+
+                if !text_changed && !properties_changed {
+                    // Contents without text, e.g. a field character or a drawing, changed
+                    differences.push(Difference::RunRemoved(run_path(old_path, old_run_path)));
+                    differences.push(Difference::RunInserted(run_path(new_path, new_run_path)));
+                }
+            }
+        }
+    }
+
+    if differences.len() == difference_count && normalized_paragraph(old) != normalized_paragraph(new) {
+        differences.push(Difference::Modified {
+            kind: ElementKind::Paragraph,
+            old: old_path.to_vec(),
+            new: new_path.to_vec(),
+        });
+    }
+}
+
+fn runs_equal(old: &R, new: &R) -> bool {
+    old.run_properties == new.run_properties && old.run_inner_contents == new.run_inner_contents
+}
+
+fn run_text(run: &R) -> String {
+    let mut text = String::new();
+    for content in &run.run_inner_contents {
+        match content {
+            RunInnerContent::Text(t) | RunInnerContent::DeletedText(t) => text.push_str(&t.text),
+            RunInnerContent::Tab | RunInnerContent::PositionTab(_) => text.push('\t'),
+            RunInnerContent::Break(_) | RunInnerContent::CarriageReturn => text.push('\n'),
+            RunInnerContent::NonBreakingHyphen => text.push('\u{2011}'),
+            _ => (),
+        }
+    }
+
+    text
+}
+
+/// Returns a copy of the paragraph with the revision save ids of it and its runs and its text id cleared.
+fn normalized_paragraph(paragraph: &P) -> P {
+    let paths: Vec<Vec<usize>> = paragraph.runs_with_paths().into_iter().map(|(path, _)| path).collect();
+    let mut paragraph = P {
+        run_properties_revision_id: None,
+        run_revision_id: None,
+        deletion_revision_id: None,
+        paragraph_revision_id: None,
+        run_default_revision_id: None,
+        text_id: None,
+        ..paragraph.clone()
+    };
+    for path in paths {
+        if let Some(run) = paragraph.run_at_mut(&path) {
+            run.run_properties_revision_id = None;
+            run.deletion_revision_id = None;
+            run.run_revision_id = None;
+        }
+    }
+
+    paragraph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn document(body: &str) -> Document {
+        let xml = format!("<w:document><w:body>{}</w:body></w:document>", body);
+        Document::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_diff_paragraphs() {
+        let old = document(
+            r#"<w:p><w:r><w:t>Unchanged</w:t></w:r></w:p>
+            <w:p><w:pPr><w:jc w:val="left" /></w:pPr><w:r><w:t xml:space="preserve">Old </w:t></w:r><w:r><w:t>text</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Removed</w:t></w:r></w:p>
+            <w:p w:rsidR="00000001"><w:r w:rsidR="00000001"><w:t>Same</w:t></w:r></w:p>"#,
+        );
+        let new = document(
+            r#"<w:p><w:r><w:t>Unchanged</w:t></w:r></w:p>
+            <w:p><w:pPr><w:jc w:val="center" /></w:pPr><w:r><w:t xml:space="preserve">New </w:t></w:r><w:r><w:rPr><w:b /></w:rPr><w:t>text</w:t></w:r></w:p>
+            <w:p w:rsidR="00000002"><w:r w:rsidR="00000002"><w:t>Same</w:t></w:r></w:p>
+            <w:tbl><w:tblPr /><w:tblGrid /><w:tr><w:tc><w:p /></w:tc></w:tr></w:tbl>"#,
+        );
+
+        let paragraph_path = |run: usize| RunPath {
+            paragraph: vec![1],
+            run: vec![run],
+        };
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                Difference::PropertiesChanged {
+                    kind: ElementKind::Paragraph,
+                    old: vec![1],
+                    new: vec![1],
+                },
+                Difference::RunTextChanged {
+                    old: paragraph_path(0),
+                    new: paragraph_path(0),
+                    old_text: String::from("Old "),
+                    new_text: String::from("New "),
+                },
+                Difference::RunPropertiesChanged {
+                    old: paragraph_path(1),
+                    new: paragraph_path(1),
+                },
+                Difference::Removed {
+                    kind: ElementKind::Paragraph,
+                    path: vec![2],
+                },
+                Difference::Inserted {
+                    kind: ElementKind::Table,
+                    path: vec![3],
+                },
+            ]
+        );
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    pub fn test_diff_tables() {
+        let old = document(
+            r#"<w:tbl><w:tblPr /><w:tblGrid />
+                <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p /></w:tc></w:tr>
+            </w:tbl>"#,
+        );
+        let new = document(
+            r#"<w:tbl><w:tblPr /><w:tblGrid />
+                <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc></w:tr>
+                <w:tr><w:tc><w:p /></w:tc></w:tr>
+            </w:tbl>"#,
+        );
+
+        let differences = old.diff(&new);
+        assert_eq!(
+            differences,
+            vec![
+                Difference::RunInserted(RunPath {
+                    paragraph: vec![0, 0, 1, 0],
+                    run: vec![0],
+                }),
+                Difference::Inserted {
+                    kind: ElementKind::Row,
+                    path: vec![0, 1],
+                },
+            ]
+        );
+        assert!(new.paragraph_at(&[0, 0, 1, 0]).is_some());
+    }
+}
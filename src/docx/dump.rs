@@ -0,0 +1,559 @@
+use super::wml::{
+    document::{
+        BlockLevelElts, Body, ContentBlockContent, ContentRunContent, Document, PContent, RPrBase, RangeMarkupElements,
+        RunInnerContent, RunLevelElts, RunTrackChange, RunTrackChangeChoice, SdtPr, Text, TrackChange, P, R,
+    },
+    table::{ContentCellContent, ContentRowContent, Tbl},
+};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpOptions {
+    /// Nodes deeper than this are not printed. `None` prints the whole tree.
+    pub max_depth: Option<usize>,
+    /// Text content longer than this many characters is truncated.
+    pub max_text_length: usize,
+    /// Only nodes with one of these kinds (e.g. "p", "r", "t") are printed. Children of filtered out nodes are still
+    /// visited. An empty list prints every node.
+    pub kinds: Vec<String>,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            max_text_length: 40,
+            kinds: Vec::new(),
+        }
+    }
+}
+
+impl Document {
+    /// Dumps the parsed tree in a compact, indented format. Every line contains the kind of the node, which is the
+    /// local name of the xml element it was parsed from, some key attributes and truncated text content.
+    pub fn dump(&self, options: &DumpOptions) -> String {
+        let mut dumper = Dumper::new(options);
+        dumper.document(self);
+        dumper.output
+    }
+}
+
+struct Dumper<'a> {
+    options: &'a DumpOptions,
+    output: String,
+}
+
+impl<'a> Dumper<'a> {
+    fn new(options: &'a DumpOptions) -> Self {
+        Self {
+            options,
+            output: String::new(),
+        }
+    }
+
+    fn is_too_deep(&self, depth: usize) -> bool {
+        matches!(self.options.max_depth, Some(max_depth) if depth > max_depth)
+    }
+
+    fn line(&mut self, depth: usize, kind: &str, details: &[(&str, String)]) {
+        if !self.options.kinds.is_empty() && !self.options.kinds.iter().any(|filter| filter == kind) {
+            return;
+        }
+
+        for _ in 0..depth {
+            self.output.push_str("  ");
+        }
+
+        self.output.push_str(kind);
+        for (name, value) in details {
+            let _ = write!(self.output, " {}={}", name, value);
+        }
+
+        self.output.push('\n');
+    }
+
+    fn truncate(&self, text: &str) -> String {
+        let mut truncated: String = text.chars().take(self.options.max_text_length).collect();
+        if text.chars().count() > self.options.max_text_length {
+            truncated.push('…');
+        }
+
+        format!("{:?}", truncated)
+    }
+
+    fn document(&mut self, document: &Document) {
+        let mut details = Vec::new();
+        if let Some(conformance) = document.conformance {
+            details.push(("conformance", format!("{:?}", conformance)));
+        }
+
+        self.line(0, "document", &details);
+        if let Some(body) = &document.body {
+            self.body(body, 1);
+        }
+    }
+
+    fn body(&mut self, body: &Body, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        self.line(depth, "body", &[]);
+        for block_level_element in &body.block_level_elements {
+            self.block_level_element(block_level_element, depth + 1);
+        }
+
+        if body.section_properties.is_some() && !self.is_too_deep(depth + 1) {
+            self.line(depth + 1, "sectPr", &[]);
+        }
+    }
+
+    fn block_level_element(&mut self, element: &BlockLevelElts, depth: usize) {
+        match element {
+            BlockLevelElts::Chunk(content) => self.content_block_content(content, depth),
+            BlockLevelElts::AltChunk(alt_chunk) => {
+                if !self.is_too_deep(depth) {
+                    self.line(depth, "altChunk", &optional_detail("r:id", &alt_chunk.rel_id));
+                }
+            }
+        }
+    }
+
+    fn content_block_content(&mut self, content: &ContentBlockContent, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                self.line(depth, "customXml", &[("element", custom_xml.element.clone())]);
+                for content in &custom_xml.block_contents {
+                    self.content_block_content(content, depth + 1);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                self.line(depth, "sdt", &sdt_details(&sdt.sdt_properties));
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    for content in &sdt_content.block_contents {
+                        self.content_block_content(content, depth + 1);
+                    }
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph, depth),
+            ContentBlockContent::Table(table) => self.table(table, depth),
+            ContentBlockContent::RunLevelElement(element) => self.run_level_element(element, depth),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P, depth: usize) {
+        let style = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.base.style.clone());
+
+        let mut details = optional_detail("style", &style);
+        if let Some(rsid) = paragraph.paragraph_revision_id {
+            details.push(("rsidP", format!("{:08X}", rsid)));
+        }
+
+        self.line(depth, "p", &details);
+        for content in &paragraph.contents {
+            self.paragraph_content(content, depth + 1);
+        }
+    }
+
+    fn paragraph_content(&mut self, content: &PContent, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        match content {
+            PContent::ContentRunContent(content) => self.content_run_content(content, depth),
+            PContent::SimpleField(field) => {
+                self.line(depth, "fldSimple", &[("instr", self.truncate(&field.field_codes))]);
+                for content in &field.paragraph_contents {
+                    self.paragraph_content(content, depth + 1);
+                }
+            }
+            PContent::Hyperlink(hyperlink) => {
+                let mut details = optional_detail("r:id", &hyperlink.rel_id);
+                details.extend(optional_detail("anchor", &hyperlink.anchor));
+                self.line(depth, "hyperlink", &details);
+                for content in &hyperlink.paragraph_contents {
+                    self.paragraph_content(content, depth + 1);
+                }
+            }
+            PContent::SubDocument(rel) => self.line(depth, "subDoc", &[("r:id", rel.rel_id.clone())]),
+        }
+    }
+
+    fn content_run_content(&mut self, content: &ContentRunContent, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        let (kind, details, paragraph_contents) = match content {
+            ContentRunContent::Run(run) => return self.run(run, depth),
+            ContentRunContent::RunLevelElements(element) => return self.run_level_element(element, depth),
+            ContentRunContent::CustomXml(custom_xml) => (
+                "customXml",
+                vec![("element", custom_xml.element.clone())],
+                &custom_xml.paragraph_contents,
+            ),
+            ContentRunContent::SmartTag(smart_tag) => (
+                "smartTag",
+                vec![("element", smart_tag.element.clone())],
+                &smart_tag.paragraph_contents,
+            ),
+            ContentRunContent::Sdt(sdt) => match &sdt.sdt_content {
+                Some(sdt_content) => ("sdt", sdt_details(&sdt.sdt_properties), &sdt_content.p_contents),
+                None => return self.line(depth, "sdt", &sdt_details(&sdt.sdt_properties)),
+            },
+            ContentRunContent::Bidirectional(dir) => (
+                "dir",
+                optional_detail("val", &dir.value.map(|value| format!("{:?}", value))),
+                &dir.p_contents,
+            ),
+            ContentRunContent::BidirectionalOverride(bdo) => (
+                "bdo",
+                optional_detail("val", &bdo.value.map(|value| format!("{:?}", value))),
+                &bdo.p_contents,
+            ),
+        };
+
+        self.line(depth, kind, &details);
+        for content in paragraph_contents {
+            self.paragraph_content(content, depth + 1);
+        }
+    }
+
+    fn run(&mut self, run: &R, depth: usize) {
+        let style = run.run_properties.as_ref().and_then(|properties| {
+            properties.r_pr_bases.iter().find_map(|base| match base {
+                RPrBase::RunStyle(style) => Some(style.clone()),
+                _ => None,
+            })
+        });
+
+        self.line(depth, "r", &optional_detail("style", &style));
+        if self.is_too_deep(depth + 1) {
+            return;
+        }
+
+        for inner_content in &run.run_inner_contents {
+            self.run_inner_content(inner_content, depth + 1);
+        }
+    }
+
+    fn run_inner_content(&mut self, content: &RunInnerContent, depth: usize) {
+        let text_details = |dumper: &Self, text: &Text| vec![("text", dumper.truncate(&text.text))];
+
+        let (kind, details) = match content {
+            RunInnerContent::Break(br) => (
+                "br",
+                optional_detail("type", &br.break_type.map(|break_type| format!("{:?}", break_type))),
+            ),
+            RunInnerContent::Text(text) => ("t", text_details(self, text)),
+            RunInnerContent::ContentPart(rel) => ("contentPart", vec![("r:id", rel.rel_id.clone())]),
+            RunInnerContent::DeletedText(text) => ("delText", text_details(self, text)),
+            RunInnerContent::InstructionText(text) => ("instrText", text_details(self, text)),
+            RunInnerContent::DeletedInstructionText(text) => ("delInstrText", text_details(self, text)),
+            RunInnerContent::NonBreakingHyphen => ("noBreakHyphen", Vec::new()),
+            RunInnerContent::OptionalHypen => ("softHyphen", Vec::new()),
+            RunInnerContent::ShortDayFormat => ("dayShort", Vec::new()),
+            RunInnerContent::ShortMonthFormat => ("monthShort", Vec::new()),
+            RunInnerContent::ShortYearFormat => ("yearShort", Vec::new()),
+            RunInnerContent::LongDayFormat => ("dayLong", Vec::new()),
+            RunInnerContent::LongMonthFormat => ("monthLong", Vec::new()),
+            RunInnerContent::LongYearFormat => ("yearLong", Vec::new()),
+            RunInnerContent::AnnorationReferenceMark => ("annotationRef", Vec::new()),
+            RunInnerContent::FootnoteReferenceMark => ("footnoteRef", Vec::new()),
+            RunInnerContent::EndnoteReferenceMark => ("endnoteRef", Vec::new()),
+            RunInnerContent::Separator => ("separator", Vec::new()),
+            RunInnerContent::ContinuationSeparator => ("continuationSeparator", Vec::new()),
+            RunInnerContent::Symbol(sym) => (
+                "sym",
+                optional_detail("char", &sym.character.map(|character| format!("{:04X}", character))),
+            ),
+            RunInnerContent::PageNum => ("pgNum", Vec::new()),
+            RunInnerContent::CarriageReturn => ("cr", Vec::new()),
+            RunInnerContent::Tab => ("tab", Vec::new()),
+            RunInnerContent::Object(_) => ("object", Vec::new()),
+            RunInnerContent::Picture(_) => ("pict", Vec::new()),
+            RunInnerContent::FieldCharacter(fld_char) => {
+                ("fldChar", vec![("type", format!("{:?}", fld_char.field_char_type))])
+            }
+            RunInnerContent::Ruby(_) => ("ruby", Vec::new()),
+            RunInnerContent::FootnoteReference(reference) => {
+                ("footnoteReference", vec![("id", reference.id.to_string())])
+            }
+            RunInnerContent::EndnoteReference(reference) => {
+                ("endnoteReference", vec![("id", reference.id.to_string())])
+            }
+            RunInnerContent::CommentReference(markup) => ("commentReference", vec![("id", markup.id.to_string())]),
+            RunInnerContent::Drawing(_) => ("drawing", Vec::new()),
+            RunInnerContent::PositionTab(_) => ("ptab", Vec::new()),
+            RunInnerContent::LastRenderedPageBreak => ("lastRenderedPageBreak", Vec::new()),
+        };
+
+        self.line(depth, kind, &details);
+    }
+
+    fn run_level_element(&mut self, element: &RunLevelElts, depth: usize) {
+        match element {
+            RunLevelElts::ProofError(proof_err) => {
+                self.line(depth, "proofErr", &[("type", format!("{:?}", proof_err.error_type))])
+            }
+            RunLevelElts::PermissionStart(perm_start) => {
+                self.line(depth, "permStart", &[("id", perm_start.permission.id.clone())])
+            }
+            RunLevelElts::PermissionEnd(perm) => self.line(depth, "permEnd", &[("id", perm.id.clone())]),
+            RunLevelElts::RangeMarkupElements(element) => self.range_markup_element(element, depth),
+            RunLevelElts::Insert(track_change) => self.run_track_change("ins", track_change, depth),
+            RunLevelElts::Delete(track_change) => self.run_track_change("del", track_change, depth),
+            RunLevelElts::MoveFrom(track_change) => self.run_track_change("moveFrom", track_change, depth),
+            RunLevelElts::MoveTo(track_change) => self.run_track_change("moveTo", track_change, depth),
+            RunLevelElts::MathContent(math_content) => match *math_content {},
+        }
+    }
+
+    fn range_markup_element(&mut self, element: &RangeMarkupElements, depth: usize) {
+        let (kind, details) = match element {
+            RangeMarkupElements::BookmarkStart(bookmark) => (
+                "bookmarkStart",
+                vec![
+                    ("id", bookmark.base.base.base.id.to_string()),
+                    ("name", bookmark.name.clone()),
+                ],
+            ),
+            RangeMarkupElements::BookmarkEnd(range) => ("bookmarkEnd", vec![("id", range.base.id.to_string())]),
+            RangeMarkupElements::MoveFromRangeStart(bookmark) => (
+                "moveFromRangeStart",
+                vec![("id", bookmark.base.base.base.base.id.to_string())],
+            ),
+            RangeMarkupElements::MoveFromRangeEnd(range) => {
+                ("moveFromRangeEnd", vec![("id", range.base.id.to_string())])
+            }
+            RangeMarkupElements::MoveToRangeStart(bookmark) => (
+                "moveToRangeStart",
+                vec![("id", bookmark.base.base.base.base.id.to_string())],
+            ),
+            RangeMarkupElements::MoveToRangeEnd(range) => ("moveToRangeEnd", vec![("id", range.base.id.to_string())]),
+            RangeMarkupElements::CommentRangeStart(range) => {
+                ("commentRangeStart", vec![("id", range.base.id.to_string())])
+            }
+            RangeMarkupElements::CommentRangeEnd(range) => ("commentRangeEnd", vec![("id", range.base.id.to_string())]),
+            RangeMarkupElements::CustomXmlInsertRangeStart(track_change) => {
+                ("customXmlInsRangeStart", track_change_details(track_change))
+            }
+            RangeMarkupElements::CustomXmlInsertRangeEnd(markup) => {
+                ("customXmlInsRangeEnd", vec![("id", markup.id.to_string())])
+            }
+            RangeMarkupElements::CustomXmlDeleteRangeStart(track_change) => {
+                ("customXmlDelRangeStart", track_change_details(track_change))
+            }
+            RangeMarkupElements::CustomXmlDeleteRangeEnd(markup) => {
+                ("customXmlDelRangeEnd", vec![("id", markup.id.to_string())])
+            }
+            RangeMarkupElements::CustomXmlMoveFromRangeStart(track_change) => {
+                ("customXmlMoveFromRangeStart", track_change_details(track_change))
+            }
+            RangeMarkupElements::CustomXmlMoveFromRangeEnd(markup) => {
+                ("customXmlMoveFromRangeEnd", vec![("id", markup.id.to_string())])
+            }
+            RangeMarkupElements::CustomXmlMoveToRangeStart(track_change) => {
+                ("customXmlMoveToRangeStart", track_change_details(track_change))
+            }
+            RangeMarkupElements::CustomXmlMoveToRangeEnd(markup) => {
+                ("customXmlMoveToRangeEnd", vec![("id", markup.id.to_string())])
+            }
+        };
+
+        self.line(depth, kind, &details);
+    }
+
+    fn run_track_change(&mut self, kind: &str, track_change: &RunTrackChange, depth: usize) {
+        self.line(depth, kind, &track_change_details(&track_change.base));
+        for choice in &track_change.choices {
+            match choice {
+                RunTrackChangeChoice::ContentRunContent(content) => self.content_run_content(content, depth + 1),
+            }
+        }
+    }
+
+    fn table(&mut self, table: &Tbl, depth: usize) {
+        let style = table.properties.base.style.clone();
+        self.line(depth, "tbl", &optional_detail("style", &style));
+        for row_content in &table.row_contents {
+            self.row_content(row_content, depth + 1);
+        }
+    }
+
+    fn row_content(&mut self, content: &ContentRowContent, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        match content {
+            ContentRowContent::Table(row) => {
+                self.line(depth, "tr", &[]);
+                for cell_content in &row.contents {
+                    self.cell_content(cell_content, depth + 1);
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                self.line(depth, "customXml", &[("element", custom_xml.element.clone())]);
+                for content in &custom_xml.contents {
+                    self.row_content(content, depth + 1);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                self.line(depth, "sdt", &sdt_details(&sdt.properties));
+                if let Some(sdt_content) = &sdt.content {
+                    for content in &sdt_content.contents {
+                        self.row_content(content, depth + 1);
+                    }
+                }
+            }
+            ContentRowContent::RunLevelElements(element) => self.run_level_element(element, depth),
+        }
+    }
+
+    fn cell_content(&mut self, content: &ContentCellContent, depth: usize) {
+        if self.is_too_deep(depth) {
+            return;
+        }
+
+        match content {
+            ContentCellContent::Cell(cell) => {
+                self.line(depth, "tc", &[]);
+                for block_level_element in &cell.block_level_elements {
+                    self.block_level_element(block_level_element, depth + 1);
+                }
+            }
+            ContentCellContent::CustomXml(custom_xml) => {
+                self.line(depth, "customXml", &[("element", custom_xml.element.clone())]);
+                for content in &custom_xml.contents {
+                    self.cell_content(content, depth + 1);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                self.line(depth, "sdt", &sdt_details(&sdt.properties));
+                if let Some(sdt_content) = &sdt.content {
+                    for content in &sdt_content.contents {
+                        self.cell_content(content, depth + 1);
+                    }
+                }
+            }
+            ContentCellContent::RunLevelElement(element) => self.run_level_element(element, depth),
+        }
+    }
+}
+
+fn optional_detail(name: &'static str, value: &Option<String>) -> Vec<(&'static str, String)> {
+    value.iter().map(|value| (name, value.clone())).collect()
+}
+
+fn sdt_details(properties: &Option<SdtPr>) -> Vec<(&'static str, String)> {
+    properties
+        .as_ref()
+        .map(|properties| {
+            let mut details = optional_detail("alias", &properties.alias);
+            details.extend(optional_detail("tag", &properties.tag));
+            details
+        })
+        .unwrap_or_default()
+}
+
+fn track_change_details(track_change: &TrackChange) -> Vec<(&'static str, String)> {
+    let mut details = vec![
+        ("id", track_change.base.id.to_string()),
+        ("author", track_change.author.clone()),
+    ];
+
+    details.extend(optional_detail("date", &track_change.date.clone()));
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn test_document() -> Document {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p w:rsidP="00AB12CD">
+                    <w:pPr><w:pStyle w:val="Heading1" /></w:pPr>
+                    <w:r>
+                        <w:rPr><w:rStyle w:val="Strong" /></w:rPr>
+                        <w:t>The quick brown fox jumps over the lazy dog</w:t>
+                    </w:r>
+                    <w:ins w:id="1" w:author="John Doe">
+                        <w:r><w:t>inserted</w:t></w:r>
+                    </w:ins>
+                </w:p>
+                <w:p>
+                    <w:r><w:tab /><w:t>second</w:t></w:r>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+
+        Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_document_dump() {
+        let options = DumpOptions {
+            max_text_length: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            test_document().dump(&options),
+            r#"document
+  body
+    p style=Heading1 rsidP=00AB12CD
+      r style=Strong
+        t text="The quick …"
+      ins id=1 author=John Doe
+        r
+          t text="inserted"
+    p
+      r
+        tab
+        t text="second"
+"#
+        );
+    }
+
+    #[test]
+    pub fn test_document_dump_max_depth() {
+        let options = DumpOptions {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            test_document().dump(&options),
+            "document\n  body\n    p style=Heading1 rsidP=00AB12CD\n    p\n"
+        );
+    }
+
+    #[test]
+    pub fn test_document_dump_kind_filter() {
+        let options = DumpOptions {
+            kinds: vec![String::from("t")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            test_document().dump(&options),
+            r#"        t text="The quick brown fox jumps over the lazy …"
+          t text="inserted"
+        t text="second"
+"#
+        );
+    }
+}
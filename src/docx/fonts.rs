@@ -0,0 +1,151 @@
+use super::{
+    package::Package,
+    wml::{
+        document::Fonts,
+        fonttable::{Font, FontRel},
+    },
+};
+use crate::{error::InvalidFontKeyError, shared::relationship::join_part_path};
+use std::{error::Error, fs::File, io::Read};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// The part name of the font table, which the embedded font relationships are relative to.
+pub const FONT_TABLE_PART_NAME: &str = "word/fontTable.xml";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbeddedFontStyle {
+    Regular,
+    Bold,
+    Italic,
+    BoldItalic,
+}
+
+/// A font file embedded in the package.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedFont {
+    pub font_name: String,
+    pub style: EmbeddedFontStyle,
+    pub part_name: String,
+    /// Whether only the characters used by the document are embedded.
+    pub is_subsetted: bool,
+    /// The TrueType or OpenType font file with the obfuscation removed.
+    pub bytes: Vec<u8>,
+}
+
+impl Font {
+    /// Returns the references to the embedded faces of the font.
+    pub fn embedded_faces(&self) -> Vec<(EmbeddedFontStyle, &FontRel)> {
+        let faces = [
+            (EmbeddedFontStyle::Regular, &self.embed_regular),
+            (EmbeddedFontStyle::Bold, &self.embed_bold),
+            (EmbeddedFontStyle::Italic, &self.embed_italic),
+            (EmbeddedFontStyle::BoldItalic, &self.embed_bold_italic),
+        ];
+
+        faces
+            .iter()
+            .filter_map(|(style, font_rel)| font_rel.as_ref().map(|font_rel| (*style, font_rel)))
+            .collect()
+    }
+}
+
+/// Removes the obfuscation of an embedded font in place. The first 32 bytes of the font are XORed with the bytes of
+/// the font key GUID in reverse order.
+///
+/// ```
+/// use oox::docx::fonts::deobfuscate_font;
+///
+/// let mut bytes = vec![0u8; 40];
+/// deobfuscate_font(&mut bytes, "{00010203-0405-0607-0809-0A0B0C0D0E0F}").unwrap();
+/// assert_eq!(bytes[0], 0x0F);
+/// assert_eq!(bytes[16], 0x0F);
+/// assert_eq!(bytes[31], 0x00);
+/// assert_eq!(bytes[32], 0x00);
+/// ```
+pub fn deobfuscate_font(bytes: &mut [u8], font_key: &str) -> ::std::result::Result<(), InvalidFontKeyError> {
+    let invalid_key = || InvalidFontKeyError {
+        font_key: String::from(font_key),
+    };
+
+    let digits: Vec<u8> = font_key
+        .chars()
+        .filter(|c| !matches!(c, '{' | '}' | '-'))
+        .map(|c| c.to_digit(16).map(|digit| digit as u8))
+        .collect::<Option<_>>()
+        .ok_or_else(invalid_key)?;
+    if digits.len() != 32 {
+        return Err(invalid_key());
+    }
+
+    let key: Vec<u8> = digits.chunks(2).rev().map(|pair| pair[0] << 4 | pair[1]).collect();
+    for (index, byte) in bytes.iter_mut().take(32).enumerate() {
+        *byte ^= key[index % 16];
+    }
+
+    Ok(())
+}
+
+impl Package {
+    /// Returns the font of the font table with the given name or alternate name.
+    pub fn font<T: AsRef<str>>(&self, name: T) -> Option<&Font> {
+        self.font_table.as_ref()?.font(name)
+    }
+
+    /// Returns the font of the font table used for ASCII characters by the run fonts, e.g. to pick a substitute
+    /// with the same family and pitch when the font isn't installed. Theme fonts aren't resolved.
+    pub fn ascii_font(&self, fonts: &Fonts) -> Option<&Font> {
+        self.font(fonts.ascii.as_ref()?)
+    }
+
+    /// Reads the embedded faces of a font from the package file.
+    pub fn font_faces(&self, font: &Font) -> Result<Vec<EmbeddedFont>> {
+        let faces = font.embedded_faces();
+        if faces.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&file)?;
+        let mut embedded_fonts = Vec::new();
+        for (style, font_rel) in faces {
+            let relationship = match self
+                .font_table_relationships
+                .iter()
+                .find(|rel| rel.id == font_rel.rel_id && !rel.is_external())
+            {
+                Some(relationship) => relationship,
+                None => continue,
+            };
+
+            let part_name = join_part_path(FONT_TABLE_PART_NAME, &relationship.target);
+            let mut zip_file = zipper.by_name(&part_name)?;
+            let mut bytes = Vec::with_capacity(zip_file.size() as usize);
+            zip_file.read_to_end(&mut bytes)?;
+            if let Some(font_key) = &font_rel.font_key {
+                deobfuscate_font(&mut bytes, font_key)?;
+            }
+
+            embedded_fonts.push(EmbeddedFont {
+                font_name: font.name.clone(),
+                style,
+                part_name,
+                is_subsetted: font_rel.subsetted.unwrap_or(false),
+                bytes,
+            });
+        }
+
+        Ok(embedded_fonts)
+    }
+
+    /// Reads every font embedded in the package in the order of the font table.
+    pub fn embedded_fonts(&self) -> Result<Vec<EmbeddedFont>> {
+        let mut embedded_fonts = Vec::new();
+        for font in self.font_table.iter().flat_map(|font_table| font_table.0.iter()) {
+            embedded_fonts.extend(self.font_faces(font)?);
+        }
+
+        Ok(embedded_fonts)
+    }
+}
@@ -0,0 +1,372 @@
+use super::{
+    textmap::RunPath,
+    wml::document::{Document, FFCheckBox, FFDDList, FFData, FFTextInput, FldCharType, RunInnerContent, Text},
+};
+use crate::error::FormFieldError;
+use std::convert::TryFrom;
+
+pub type Result<T> = ::std::result::Result<T, FormFieldError>;
+
+/// The value of a legacy form field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormFieldValue {
+    CheckBox(bool),
+    /// The text of a text input, i.e. the result of the field.
+    Text(String),
+    /// The index of the selected entry of a drop-down list.
+    DropDown(Option<usize>),
+}
+
+/// A legacy form field (`FORMTEXT`, `FORMCHECKBOX` or `FORMDROPDOWN`), i.e. a complex field whose `w:fldChar begin`
+/// has form field properties.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormField<'a> {
+    /// The children of the `w:ffData` element of the field.
+    pub properties: &'a [FFData],
+    /// The run containing the `w:fldChar begin` of the field.
+    pub begin: RunPath,
+    /// The run containing the `w:fldChar separate` of the field, if the field has a result.
+    pub separate: Option<RunPath>,
+    /// The run containing the `w:fldChar end` of the field.
+    pub end: RunPath,
+    /// The text between the `separate` and `end` field characters.
+    pub result: String,
+    begin_content_index: usize,
+    separate_content_index: Option<usize>,
+    result_texts: Vec<(RunPath, usize)>,
+}
+
+impl FormField<'_> {
+    pub fn name(&self) -> Option<&str> {
+        self.properties.iter().find_map(|property| match property {
+            FFData::Name(name) => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Returns whether the field can be edited by the user. Fields are enabled by default.
+    pub fn is_enabled(&self) -> bool {
+        self.properties
+            .iter()
+            .find_map(|property| match property {
+                FFData::Enabled(enabled) => Some(*enabled),
+                _ => None,
+            })
+            .unwrap_or(true)
+    }
+
+    pub fn check_box(&self) -> Option<&FFCheckBox> {
+        self.properties.iter().find_map(|property| match property {
+            FFData::CheckBox(check_box) => Some(check_box),
+            _ => None,
+        })
+    }
+
+    pub fn drop_down_list(&self) -> Option<&FFDDList> {
+        self.properties.iter().find_map(|property| match property {
+            FFData::DropDownList(list) => Some(list),
+            _ => None,
+        })
+    }
+
+    pub fn text_input(&self) -> Option<&FFTextInput> {
+        self.properties.iter().find_map(|property| match property {
+            FFData::TextInput(text_input) => Some(text_input),
+            _ => None,
+        })
+    }
+
+    /// Returns the current value of the field. Check boxes fall back to their default state and drop-down lists to
+    /// their default entry, which is the first entry if it's not specified. Returns `None` for fields without a
+    /// check box, drop-down list or text input.
+    pub fn value(&self) -> Option<FormFieldValue> {
+        if let Some(check_box) = self.check_box() {
+            let checked = check_box.is_checked.or(check_box.is_default).unwrap_or(false);
+            Some(FormFieldValue::CheckBox(checked))
+        } else if let Some(list) = self.drop_down_list() {
+            let selected = list.result.or(list.default).unwrap_or(0);
+            let selected = usize::try_from(selected)
+                .ok()
+                .filter(|selected| *selected < list.list_entries.len());
+            Some(FormFieldValue::DropDown(selected))
+        } else if self.text_input().is_some() {
+            Some(FormFieldValue::Text(self.result.clone()))
+        } else {
+            None
+        }
+    }
+}
+
+/// A field whose `end` field character wasn't reached yet
+struct FieldFrame<'a> {
+    properties: &'a [FFData],
+    begin: (RunPath, usize),
+    separate: Option<(RunPath, usize)>,
+    result: String,
+    result_texts: Vec<(RunPath, usize)>,
+}
+
+impl Document {
+    /// Lists the legacy form fields of the document in document order.
+    ///
+    /// ```
+    /// use oox::docx::{forms::FormFieldValue, wml::document::Document};
+    /// # use oox::xml::XmlNode;
+    /// # use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r><w:fldChar w:fldCharType="begin">
+    ///         <w:ffData><w:name w:val="Agree" /><w:checkBox><w:sizeAuto /><w:default w:val="1" /></w:checkBox></w:ffData>
+    ///     </w:fldChar></w:r>
+    ///     <w:r><w:instrText xml:space="preserve"> FORMCHECKBOX </w:instrText></w:r>
+    ///     <w:r><w:fldChar w:fldCharType="end" /></w:r>
+    /// </w:p></w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// let fields = document.form_fields();
+    /// assert_eq!(fields[0].name(), Some("Agree"));
+    /// assert_eq!(fields[0].value(), Some(FormFieldValue::CheckBox(true)));
+    /// ```
+    pub fn form_fields(&self) -> Vec<FormField<'_>> {
+        let mut fields = Vec::new();
+        let mut stack: Vec<FieldFrame<'_>> = Vec::new();
+        for (path, run) in self.runs_with_paths() {
+            for (index, content) in run.run_inner_contents.iter().enumerate() {
+                match content {
+                    RunInnerContent::FieldCharacter(fld_char) => match fld_char.field_char_type {
+                        FldCharType::Begin => stack.push(FieldFrame {
+                            properties: &fld_char.form_field_properties,
+                            begin: (path.clone(), index),
+                            separate: None,
+                            result: String::new(),
+                            result_texts: Vec::new(),
+                        }),
+                        FldCharType::Separate => {
+                            if let Some(frame) = stack.last_mut() {
+                                frame.separate = Some((path.clone(), index));
+                            }
+                        }
+                        FldCharType::End => match stack.pop() {
+                            Some(frame) if !frame.properties.is_empty() => {
+                                let (begin, begin_content_index) = frame.begin;
+                                let (separate, separate_content_index) = frame.separate.unzip();
+                                fields.push(FormField {
+                                    properties: frame.properties,
+                                    begin,
+                                    separate,
+                                    end: path.clone(),
+                                    result: frame.result,
+                                    begin_content_index,
+                                    separate_content_index,
+                                    result_texts: frame.result_texts,
+                                });
+                            }
+                            _ => (),
+                        },
+                    },
+                    RunInnerContent::Text(text) => {
+                        // The result of a field includes the results of the fields nested in it
+                        for frame in stack.iter_mut().filter(|frame| frame.separate.is_some()) {
+                            frame.result.push_str(&text.text);
+                            frame.result_texts.push((path.clone(), index));
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        fields
+    }
+
+    /// Sets the value of the first form field with the given name. The value must match the type of the field.
+    ///
+    /// Check boxes and drop-down lists store their value in their form field properties. Text inputs store it as the
+    /// result of the field, which replaces the text of the first result run and clears the text of the others, so
+    /// the formatting of the result is kept.
+    pub fn set_form_field_value(&mut self, name: &str, value: FormFieldValue) -> Result<()> {
+        let field = self
+            .form_fields()
+            .into_iter()
+            .find(|field| field.name() == Some(name))
+            .ok_or_else(|| FormFieldError::NotFound(String::from(name)))?;
+
+        let begin = field.begin.clone();
+        let begin_content_index = field.begin_content_index;
+        let separate = field.separate.clone().zip(field.separate_content_index);
+        let result_texts = field.result_texts.clone();
+        match (&value, field.value()) {
+            (FormFieldValue::CheckBox(_), Some(FormFieldValue::CheckBox(_))) => (),
+            (FormFieldValue::Text(text), Some(FormFieldValue::Text(_))) => {
+                let max_length = field
+                    .text_input()
+                    .and_then(|text_input| text_input.max_length)
+                    .filter(|max_length| *max_length > 0);
+                if let Some(max_length) = max_length {
+                    if text.chars().count() as i64 > max_length {
+                        return Err(FormFieldError::TextTooLong { max_length });
+                    }
+                }
+            }
+            (FormFieldValue::DropDown(Some(index)), Some(FormFieldValue::DropDown(_))) => {
+                let len = field.drop_down_list().map_or(0, |list| list.list_entries.len());
+                if *index >= len {
+                    return Err(FormFieldError::EntryOutOfBounds { index: *index, len });
+                }
+            }
+            (FormFieldValue::DropDown(None), Some(FormFieldValue::DropDown(_))) => (),
+            _ => return Err(FormFieldError::ValueMismatch),
+        }
+
+        match value {
+            FormFieldValue::CheckBox(checked) => {
+                self.update_form_field_properties(&begin, begin_content_index, |property| {
+                    if let FFData::CheckBox(check_box) = property {
+                        check_box.is_checked = Some(checked);
+                    }
+                });
+            }
+            FormFieldValue::DropDown(index) => {
+                self.update_form_field_properties(&begin, begin_content_index, |property| {
+                    if let FFData::DropDownList(list) = property {
+                        list.result = index.map(|index| index as i64);
+                    }
+                });
+            }
+            FormFieldValue::Text(text) => {
+                let new_text = |text: String| {
+                    RunInnerContent::Text(Text {
+                        text,
+                        xml_space: Some(String::from("preserve")),
+                    })
+                };
+
+                match result_texts.split_first() {
+                    Some(((first_path, first_index), rest)) => {
+                        if let Some(run) = self.run_at_mut(first_path) {
+                            run.run_inner_contents[*first_index] = new_text(text);
+                        }
+
+                        for (path, index) in rest {
+                            if let Some(run) = self.run_at_mut(path) {
+                                run.run_inner_contents[*index] = new_text(String::new());
+                            }
+                        }
+                    }
+                    None => {
+                        let (separate, separate_index) = separate.ok_or(FormFieldError::MissingResult)?;
+                        let run = self.run_at_mut(&separate).ok_or(FormFieldError::MissingResult)?;
+                        run.run_inner_contents.insert(separate_index + 1, new_text(text));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn update_form_field_properties(&mut self, begin: &RunPath, content_index: usize, mut f: impl FnMut(&mut FFData)) {
+        let content = self
+            .run_at_mut(begin)
+            .and_then(|run| run.run_inner_contents.get_mut(content_index));
+        if let Some(RunInnerContent::FieldCharacter(fld_char)) = content {
+            fld_char.form_field_properties.iter_mut().for_each(&mut f);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    const DOCUMENT_XML: &str = r#"<w:document><w:body>
+        <w:p>
+            <w:r><w:fldChar w:fldCharType="begin">
+                <w:ffData>
+                    <w:name w:val="Name" />
+                    <w:enabled />
+                    <w:textInput><w:default w:val="Your name" /><w:maxLength w:val="10" /></w:textInput>
+                </w:ffData>
+            </w:fldChar></w:r>
+            <w:r><w:instrText xml:space="preserve"> FORMTEXT </w:instrText></w:r>
+            <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+            <w:r><w:rPr><w:b /></w:rPr><w:t>John</w:t></w:r>
+            <w:r><w:t xml:space="preserve"> Doe</w:t></w:r>
+            <w:r><w:fldChar w:fldCharType="end" /></w:r>
+        </w:p>
+        <w:p>
+            <w:r>
+                <w:fldChar w:fldCharType="begin">
+                    <w:ffData>
+                        <w:name w:val="Color" />
+                        <w:ddList><w:listEntry w:val="Red" /><w:listEntry w:val="Blue" /></w:ddList>
+                    </w:ffData>
+                </w:fldChar>
+                <w:instrText xml:space="preserve"> FORMDROPDOWN </w:instrText>
+                <w:fldChar w:fldCharType="end" />
+            </w:r>
+            <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+            <w:r><w:instrText xml:space="preserve"> PAGE </w:instrText></w:r>
+            <w:r><w:fldChar w:fldCharType="end" /></w:r>
+        </w:p>
+    </w:body></w:document>"#;
+
+    #[test]
+    pub fn test_form_fields() {
+        let document = Document::from_xml_element(&XmlNode::from_str(DOCUMENT_XML).unwrap()).unwrap();
+        let fields = document.form_fields();
+        assert_eq!(fields.len(), 2);
+
+        let name = &fields[0];
+        assert_eq!(name.name(), Some("Name"));
+        assert!(name.is_enabled());
+        assert_eq!(name.text_input().unwrap().default.as_deref(), Some("Your name"));
+        assert_eq!(name.value(), Some(FormFieldValue::Text(String::from("John Doe"))));
+        assert_eq!(name.separate.as_ref().unwrap().run, vec![2]);
+        assert_eq!(name.end.run, vec![5]);
+
+        let color = &fields[1];
+        assert_eq!(color.begin, color.end);
+        assert_eq!(color.value(), Some(FormFieldValue::DropDown(Some(0))));
+    }
+
+    #[test]
+    pub fn test_set_form_field_value() {
+        let mut document = Document::from_xml_element(&XmlNode::from_str(DOCUMENT_XML).unwrap()).unwrap();
+
+        document
+            .set_form_field_value("Name", FormFieldValue::Text(String::from("Jane")))
+            .unwrap();
+        document
+            .set_form_field_value("Color", FormFieldValue::DropDown(Some(1)))
+            .unwrap();
+
+        let fields = document.form_fields();
+        assert_eq!(fields[0].value(), Some(FormFieldValue::Text(String::from("Jane"))));
+        assert_eq!(fields[1].value(), Some(FormFieldValue::DropDown(Some(1))));
+        let bold_run = document.run_at(&RunPath {
+            paragraph: vec![0],
+            run: vec![3],
+        });
+        assert!(bold_run.unwrap().run_properties.is_some());
+
+        assert_eq!(
+            document.set_form_field_value("Name", FormFieldValue::Text(String::from("Far too long name"))),
+            Err(FormFieldError::TextTooLong { max_length: 10 })
+        );
+        assert_eq!(
+            document.set_form_field_value("Color", FormFieldValue::DropDown(Some(2))),
+            Err(FormFieldError::EntryOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(
+            document.set_form_field_value("Color", FormFieldValue::CheckBox(true)),
+            Err(FormFieldError::ValueMismatch)
+        );
+        assert_eq!(
+            document.set_form_field_value("Missing", FormFieldValue::CheckBox(true)),
+            Err(FormFieldError::NotFound(String::from("Missing")))
+        );
+    }
+}
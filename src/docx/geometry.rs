@@ -0,0 +1,268 @@
+use super::wml::document::{PageOrientation, SectPr, SectPrContents};
+pub use crate::shared::sharedtypes::EMUS_PER_TWIP;
+use crate::shared::sharedtypes::{Measure, TwipsMeasure};
+
+/// The spacing between columns when the section doesn't specify one, in twips.
+pub const DEFAULT_COLUMN_SPACING: f64 = 720.0;
+
+/// A rectangle on the page measured from the top left corner of the page.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PageRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl PageRect {
+    /// Converts a rectangle measured in twips to EMUs.
+    pub fn to_emus(self) -> Self {
+        Self {
+            x: self.x * EMUS_PER_TWIP,
+            y: self.y * EMUS_PER_TWIP,
+            width: self.width * EMUS_PER_TWIP,
+            height: self.height * EMUS_PER_TWIP,
+        }
+    }
+}
+
+/// The margins of a page in twips. The gutter is added to the inner edge of the page.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PageMargins {
+    pub top: f64,
+    pub right: f64,
+    pub bottom: f64,
+    pub left: f64,
+}
+
+impl SectPrContents {
+    /// Returns the orientation of the page. Falls back to comparing the width and height of the page when the
+    /// orientation isn't given explicitly.
+    pub fn orientation(&self) -> PageOrientation {
+        let page_size = self.page_size.as_ref();
+        match page_size.and_then(|page_size| page_size.orientation) {
+            Some(orientation) => orientation,
+            None => match (self.page_width(), self.page_height()) {
+                (Some(width), Some(height)) if width > height => PageOrientation::Landscape,
+                _ => PageOrientation::Portrait,
+            },
+        }
+    }
+
+    /// Returns the width of the page in twips.
+    pub fn page_width(&self) -> Option<f64> {
+        self.page_size?.width.as_ref().map(TwipsMeasure::to_twips)
+    }
+
+    /// Returns the height of the page in twips.
+    pub fn page_height(&self) -> Option<f64> {
+        self.page_size?.height.as_ref().map(TwipsMeasure::to_twips)
+    }
+
+    /// Returns the margins of the page in twips with the gutter added to the left margin, or to the right margin for
+    /// sections with a right to left gutter. Negative top and bottom margins, which let the text overlap the header or
+    /// footer, are taken by their absolute value. A gutter placed at the top of the page by the document settings isn't
+    /// taken into account.
+    pub fn margins(&self) -> Option<PageMargins> {
+        let page_margin = self.page_margin.as_ref()?;
+        let gutter = page_margin.gutter.to_twips();
+        let (left_gutter, right_gutter) = if self.rtl_gutter == Some(true) {
+            (0.0, gutter)
+        } else {
+            (gutter, 0.0)
+        };
+
+        Some(PageMargins {
+            top: page_margin.top.to_twips().abs(),
+            right: page_margin.right.to_twips() + right_gutter,
+            bottom: page_margin.bottom.to_twips().abs(),
+            left: page_margin.left.to_twips() + left_gutter,
+        })
+    }
+
+    /// Returns the rectangle between the page margins, in twips.
+    pub fn content_rect(&self) -> Option<PageRect> {
+        let margins = self.margins()?;
+        Some(PageRect {
+            x: margins.left,
+            y: margins.top,
+            width: (self.page_width()? - margins.left - margins.right).max(0.0),
+            height: (self.page_height()? - margins.top - margins.bottom).max(0.0),
+        })
+    }
+
+    /// Returns the width available for text between the left and right margins, in twips.
+    pub fn content_width(&self) -> Option<f64> {
+        self.content_rect().map(|rect| rect.width)
+    }
+
+    /// Returns the height available for text between the top and bottom margins, in twips.
+    pub fn content_height(&self) -> Option<f64> {
+        self.content_rect().map(|rect| rect.height)
+    }
+
+    /// Returns the rectangles of the text columns in twips, in reading order. Columns of right to left sections are
+    /// laid out from the right edge of the page.
+    ///
+    /// ```
+    /// use oox::docx::{pagesetup::PageSetup, wml::document::SectPr};
+    ///
+    /// let mut section = SectPr::default().with_page_setup(PageSetup::LETTER_PORTRAIT);
+    /// let contents = section.contents.as_mut().unwrap();
+    /// contents.columns.as_mut().unwrap().number = Some(2);
+    ///
+    /// let columns = contents.column_rects();
+    /// assert_eq!(columns.len(), 2);
+    /// assert_eq!(columns[0].width, 4320.0);
+    /// assert_eq!(columns[1].x, 1440.0 + 4320.0 + 720.0);
+    /// ```
+    pub fn column_rects(&self) -> Vec<PageRect> {
+        let content_rect = match self.content_rect() {
+            Some(content_rect) => content_rect,
+            None => return Vec::new(),
+        };
+
+        let columns = self.columns.as_ref();
+        let default_spacing = columns
+            .and_then(|columns| columns.spacing.as_ref())
+            .map(TwipsMeasure::to_twips)
+            .unwrap_or(DEFAULT_COLUMN_SPACING);
+
+        // (width, spacing after the column)
+        let column_extents: Vec<(f64, f64)> = match columns {
+            Some(columns) if columns.equal_width == Some(false) && !columns.columns.is_empty() => columns
+                .columns
+                .iter()
+                .map(|column| {
+                    (
+                        column.width.as_ref().map(TwipsMeasure::to_twips).unwrap_or(0.0),
+                        column
+                            .spacing
+                            .as_ref()
+                            .map(TwipsMeasure::to_twips)
+                            .unwrap_or(default_spacing),
+                    )
+                })
+                .collect(),
+            _ => {
+                let count = columns.and_then(|columns| columns.number).unwrap_or(1).max(1) as usize;
+                let width = ((content_rect.width - default_spacing * (count - 1) as f64) / count as f64).max(0.0);
+                vec![(width, default_spacing); count]
+            }
+        };
+
+        let right_to_left = self.bidirectional == Some(true);
+        let mut offset = 0.0;
+        column_extents
+            .iter()
+            .map(|(width, spacing)| {
+                let x = if right_to_left {
+                    content_rect.x + content_rect.width - offset - width
+                } else {
+                    content_rect.x + offset
+                };
+                offset += width + spacing;
+
+                PageRect {
+                    x,
+                    y: content_rect.y,
+                    width: *width,
+                    height: content_rect.height,
+                }
+            })
+            .collect()
+    }
+}
+
+impl SectPr {
+    pub fn orientation(&self) -> PageOrientation {
+        self.contents
+            .as_ref()
+            .map(SectPrContents::orientation)
+            .unwrap_or(PageOrientation::Portrait)
+    }
+
+    pub fn content_rect(&self) -> Option<PageRect> {
+        self.contents.as_ref()?.content_rect()
+    }
+
+    /// Returns the width available for text between the left and right margins, in twips.
+    pub fn content_width(&self) -> Option<f64> {
+        self.contents.as_ref()?.content_width()
+    }
+
+    /// Returns the rectangles of the text columns in twips, in reading order.
+    pub fn column_rects(&self) -> Vec<PageRect> {
+        self.contents
+            .as_ref()
+            .map(SectPrContents::column_rects)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::{
+        pagesetup::PageSetup,
+        wml::document::{Column, Columns, PageSz},
+    };
+
+    #[test]
+    pub fn test_section_geometry() {
+        let mut section = SectPr::default().with_page_setup(PageSetup::A4_LANDSCAPE);
+        assert_eq!(section.orientation(), PageOrientation::Landscape);
+        assert_eq!(section.content_width(), Some(16_838.0 - 2880.0));
+
+        let contents = section.contents.as_mut().unwrap();
+        contents.page_margin.as_mut().unwrap().gutter = TwipsMeasure::Decimal(360);
+        contents.page_size = Some(PageSz {
+            orientation: None,
+            ..contents.page_size.unwrap()
+        });
+        assert_eq!(contents.orientation(), PageOrientation::Landscape);
+        assert_eq!(contents.margins().unwrap().left, 1800.0);
+        assert_eq!(
+            contents.content_rect().unwrap().to_emus(),
+            PageRect {
+                x: 1800.0 * EMUS_PER_TWIP,
+                y: 1440.0 * EMUS_PER_TWIP,
+                width: (16_838.0 - 3240.0) * EMUS_PER_TWIP,
+                height: (11_906.0 - 2880.0) * EMUS_PER_TWIP,
+            }
+        );
+
+        contents.rtl_gutter = Some(true);
+        assert_eq!(contents.margins().unwrap().right, 1800.0);
+    }
+
+    #[test]
+    pub fn test_column_rects() {
+        let mut section = SectPr::default().with_page_setup(PageSetup::LETTER_PORTRAIT);
+        let contents = section.contents.as_mut().unwrap();
+        contents.columns = Some(Columns {
+            columns: vec![
+                Column {
+                    width: Some(TwipsMeasure::Decimal(3000)),
+                    spacing: Some(TwipsMeasure::Decimal(360)),
+                },
+                Column {
+                    width: Some(TwipsMeasure::Decimal(6000)),
+                    spacing: None,
+                },
+            ],
+            equal_width: Some(false),
+            ..Default::default()
+        });
+
+        let columns = contents.column_rects();
+        assert_eq!(columns.len(), 2);
+        assert_eq!((columns[0].x, columns[0].width), (1440.0, 3000.0));
+        assert_eq!((columns[1].x, columns[1].width), (4800.0, 6000.0));
+
+        contents.bidirectional = Some(true);
+        let columns = contents.column_rects();
+        assert_eq!(columns[0].x, 12_240.0 - 1440.0 - 3000.0);
+        assert_eq!(columns[1].x, 12_240.0 - 1440.0 - 3360.0 - 6000.0);
+    }
+}
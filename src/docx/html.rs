@@ -0,0 +1,799 @@
+use super::{
+    media::emus_to_pixels,
+    package::Package,
+    resolvedstyle::RunProperties,
+    text::{for_each_run_item, InlineItem, NoteAnchor, NoteCollector, NotePlacement, RevisionView, RunItem},
+    wml::{
+        document::{
+            BlockLevelElts, BrType, ContentBlockContent, DrawingChoice, HexColor, Hyperlink, SectPr, SectPrContents,
+            SectionMark, UnderlineType, P, R,
+        },
+        styles::StyleType,
+        table::{ContentCellContent, ContentRowContent, Tbl},
+    },
+};
+use crate::{
+    shared::sharedtypes::{TwipsMeasure, VerticalAlignRun},
+    xml::{escape_xml, unescape_xml},
+};
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlOptions {
+    pub notes: NotePlacement,
+    pub images: ImageSource,
+}
+
+/// Controls the `src` attribute of the images exported to HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ImageSource {
+    /// The image is embedded as a base64 encoded data URI. The image is read from the package file, images that can't
+    /// be read fall back to their part name.
+    #[default]
+    DataUri,
+    /// The part name of the image, e.g. `word/media/image1.png`, for exports that extract the media of the package
+    /// next to the HTML file.
+    PartName,
+}
+
+impl Package {
+    /// Exports the main document as an HTML fragment wrapped in a `div` with the `document` class.
+    ///
+    /// Every section of the document is rendered as a `section` element with the `section` class, a
+    /// `section-{type}` class derived from the section's type (e.g. `section-next-page`) and the `data-section-index`,
+    /// `data-section-type` and `data-columns` attributes. Multi-column sections also get a `column-count` style.
+    /// Sections and paragraphs starting on a new page get the `page-break-before` class, manual page and column breaks
+    /// are rendered as `br.page-break` and `br.column-break`.
+    ///
+    /// Runs are rendered with their resolved formatting: bold, italic, underline, strikethrough, superscript and
+    /// subscript become the matching HTML elements and colors a `span` with a `color` style. Hyperlinks are rendered as
+    /// `a` elements, pictures as `img` elements sized to their extent at 96 DPI, see [`ImageSource`]. Consecutive
+    /// numbered and bulleted paragraphs are grouped into nested `ol` and `ul` lists by their list level.
+    ///
+    /// With `NotePlacement::Appendix`, note references are rendered as `sup.note-ref` links pointing to the items of
+    /// an ordered list inside `section.notes`. With `NotePlacement::Inline`, the note texts are rendered as
+    /// `span.note` elements at the reference point.
+    pub fn to_html(&self, options: &HtmlOptions) -> String {
+        let mut writer = HtmlWriter {
+            package: self,
+            notes: NoteCollector::new(self, options.notes),
+            images: options.images,
+            lists: Vec::new(),
+            output: String::new(),
+        };
+
+        writer.output.push_str("<div class=\"document\">\n");
+        if let Some(body) = self.main_document.as_ref().and_then(|document| document.body.as_ref()) {
+            let mut section_index = 0;
+            let mut section_start = 0;
+            for (idx, block_level_element) in body.block_level_elements.iter().enumerate() {
+                if let Some(section_properties) = section_break_of(block_level_element) {
+                    writer.section(
+                        section_index,
+                        section_properties.contents.as_ref(),
+                        &body.block_level_elements[section_start..=idx],
+                    );
+                    section_index += 1;
+                    section_start = idx + 1;
+                }
+            }
+
+            let last_section_properties = body
+                .section_properties
+                .as_ref()
+                .and_then(|section_properties| section_properties.contents.as_ref());
+
+            if section_start < body.block_level_elements.len() || last_section_properties.is_some() {
+                writer.section(
+                    section_index,
+                    last_section_properties,
+                    &body.block_level_elements[section_start..],
+                );
+            }
+        }
+
+        writer.notes_section();
+        writer.output.push_str("</div>\n");
+        writer.output
+    }
+}
+
+struct HtmlWriter<'a> {
+    package: &'a Package,
+    notes: NoteCollector<'a>,
+    images: ImageSource,
+    /// The lists currently open, outermost first. True for ordered lists.
+    lists: Vec<bool>,
+    output: String,
+}
+
+/// The formatting of a run as rendered by the HTML exporter.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct RunFormat {
+    color: Option<String>,
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strikethrough: bool,
+    vertical_alignment: Option<VerticalAlignRun>,
+}
+
+impl RunFormat {
+    fn from_run_properties(properties: &RunProperties) -> Self {
+        let color = match properties.color.as_ref().map(|color| &color.value) {
+            Some(HexColor::RGB(rgb)) => Some(format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])),
+            _ => None,
+        };
+
+        Self {
+            color,
+            bold: properties.bold.unwrap_or(false),
+            italic: properties.italic.unwrap_or(false),
+            underline: matches!(&properties.underline, Some(underline) if underline.value != Some(UnderlineType::None)),
+            strikethrough: properties.strikethrough.unwrap_or(false)
+                || properties.double_strikethrough.unwrap_or(false),
+            vertical_alignment: properties
+                .vertical_alignment
+                .filter(|alignment| *alignment != VerticalAlignRun::Baseline),
+        }
+    }
+
+    fn tags(&self) -> Vec<&'static str> {
+        let mut tags = Vec::new();
+        if self.bold {
+            tags.push("strong");
+        }
+        if self.italic {
+            tags.push("em");
+        }
+        if self.underline {
+            tags.push("u");
+        }
+        if self.strikethrough {
+            tags.push("s");
+        }
+        match self.vertical_alignment {
+            Some(VerticalAlignRun::Superscript) => tags.push("sup"),
+            Some(VerticalAlignRun::Subscript) => tags.push("sub"),
+            _ => (),
+        }
+
+        tags
+    }
+
+    fn open(&self, output: &mut String) {
+        if let Some(color) = &self.color {
+            let _ = write!(output, "<span style=\"color: {}\">", color);
+        }
+
+        for tag in self.tags() {
+            let _ = write!(output, "<{}>", tag);
+        }
+    }
+
+    fn close(&self, output: &mut String) {
+        for tag in self.tags().iter().rev() {
+            let _ = write!(output, "</{}>", tag);
+        }
+
+        if self.color.is_some() {
+            output.push_str("</span>");
+        }
+    }
+}
+
+impl<'a> HtmlWriter<'a> {
+    fn section(&mut self, index: usize, properties: Option<&SectPrContents>, block_level_elements: &[BlockLevelElts]) {
+        // The section type defaults to nextPage if omitted
+        let section_type = properties
+            .and_then(|properties| properties.section_type)
+            .unwrap_or(SectionMark::NextPage);
+
+        let mut classes = vec![
+            String::from("section"),
+            format!("section-{}", section_type_class(section_type)),
+        ];
+        let starts_new_page = match section_type {
+            SectionMark::NextPage | SectionMark::EvenPage | SectionMark::OddPage => true,
+            SectionMark::NextColumn | SectionMark::Continuous => false,
+        };
+
+        if index > 0 && starts_new_page {
+            classes.push(String::from("page-break-before"));
+        }
+
+        let columns = properties.and_then(|properties| properties.columns.as_ref());
+        let column_count = columns
+            .and_then(|columns| columns.number)
+            .filter(|number| *number > 0)
+            .unwrap_or(1);
+
+        let _ = write!(
+            self.output,
+            "<section class=\"{}\" data-section-index=\"{}\" data-section-type=\"{}\" data-columns=\"{}\"",
+            classes.join(" "),
+            index,
+            section_type_value(section_type),
+            column_count,
+        );
+
+        if column_count > 1 {
+            let _ = write!(self.output, " style=\"column-count: {}", column_count);
+            if let Some(TwipsMeasure::Decimal(spacing)) = columns.and_then(|columns| columns.spacing.as_ref()) {
+                let _ = write!(self.output, "; column-gap: {}pt", *spacing as f64 / 20.0);
+            }
+
+            self.output.push('"');
+        }
+
+        self.output.push_str(">\n");
+        for block_level_element in block_level_elements {
+            self.block_level_element(block_level_element);
+        }
+        self.close_lists();
+        self.output.push_str("</section>\n");
+    }
+
+    fn block_level_element(&mut self, element: &BlockLevelElts) {
+        if let BlockLevelElts::Chunk(content) = element {
+            self.content_block_content(content);
+        }
+    }
+
+    fn content_block_content(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph),
+            ContentBlockContent::Table(table) => self.table(table),
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P) {
+        let (open_tag, close_tag) = match self.package.resolve_list_level(paragraph) {
+            Some(list_level) => {
+                self.list_item(list_level.level.max(0) as usize, !list_level.is_bulleted());
+                ("li", "")
+            }
+            None => {
+                self.close_lists();
+                ("p", "</p>\n")
+            }
+        };
+
+        if self.has_page_break_before(paragraph) {
+            let _ = write!(self.output, "<{} class=\"page-break-before\">", open_tag);
+        } else {
+            let _ = write!(self.output, "<{}>", open_tag);
+        }
+
+        let mut items = Vec::new();
+        for_each_run_item(&paragraph.contents, RevisionView::Accepted, &mut |item| {
+            items.push(item)
+        });
+
+        let mut current_run: Option<&R> = None;
+        let mut format = RunFormat::default();
+        let mut hyperlinks = Vec::new();
+        for item in items {
+            let run = match &item {
+                RunItem::Inline(run, _) | RunItem::Drawing(run, _) => Some(*run),
+                RunItem::HyperlinkStart(_) | RunItem::HyperlinkEnd => None,
+            };
+
+            match run {
+                Some(run) if !matches!(current_run, Some(current_run) if std::ptr::eq(current_run, run)) => {
+                    current_run = Some(run);
+                    let new_format = self.run_format(paragraph, run);
+                    if new_format != format {
+                        format.close(&mut self.output);
+                        new_format.open(&mut self.output);
+                        format = new_format;
+                    }
+                }
+                Some(_) => (),
+                None => {
+                    // Formatting elements are closed at hyperlink boundaries, so the elements stay properly nested
+                    format.close(&mut self.output);
+                    format = RunFormat::default();
+                    current_run = None;
+                }
+            }
+
+            match item {
+                RunItem::Inline(_, inline) => self.inline(inline),
+                RunItem::Drawing(_, drawing) => self.image(drawing),
+                RunItem::HyperlinkStart(hyperlink) => {
+                    let href = self.hyperlink_href(hyperlink);
+                    if let Some(href) = &href {
+                        let _ = write!(self.output, "<a href=\"{}\">", escape_xml(href));
+                    }
+                    hyperlinks.push(href.is_some());
+                }
+                RunItem::HyperlinkEnd => {
+                    if hyperlinks.pop() == Some(true) {
+                        self.output.push_str("</a>");
+                    }
+                }
+            }
+        }
+
+        format.close(&mut self.output);
+        self.output.push_str(close_tag);
+    }
+
+    fn inline(&mut self, item: InlineItem<'_>) {
+        let output = &mut self.output;
+        match item {
+            InlineItem::Text(value) => output.push_str(&escape_xml(value)),
+            InlineItem::Tab => output.push('\t'),
+            InlineItem::Break(Some(BrType::Page)) => output.push_str("<br class=\"page-break\"/>"),
+            InlineItem::Break(Some(BrType::Column)) => output.push_str("<br class=\"column-break\"/>"),
+            InlineItem::Break(_) => output.push_str("<br/>"),
+            InlineItem::NoteReference(kind, id) => match self.notes.reference(kind, id) {
+                Some(NoteAnchor::Marker(number)) => {
+                    let _ = write!(
+                        output,
+                        "<sup class=\"note-ref\"><a href=\"#note-{0}\">[{0}]</a></sup>",
+                        number
+                    );
+                }
+                Some(NoteAnchor::Inline(text)) => {
+                    let _ = write!(output, " <span class=\"note\">({})</span>", escape_xml(&text));
+                }
+                None => (),
+            },
+        }
+    }
+
+    fn run_format(&self, paragraph: &P, run: &R) -> RunFormat {
+        RunFormat::from_run_properties(&self.package.resolve_run_properties(paragraph, run))
+    }
+
+    /// Returns the `href` of a hyperlink. Targets that aren't safe to link, e.g. `javascript:` URLs, return `None`, so
+    /// only the text of the hyperlink is exported.
+    fn hyperlink_href(&self, hyperlink: &Hyperlink) -> Option<String> {
+        self.package
+            .resolve_hyperlink(hyperlink)
+            .ok()
+            .flatten()
+            .and_then(|target| target.to_export_url())
+    }
+
+    fn image(&mut self, drawing: &DrawingChoice) {
+        let extent = drawing.extent();
+        let description = match drawing {
+            DrawingChoice::Inline(inline) => inline.doc_properties.description.as_deref(),
+            DrawingChoice::Anchor(anchor) => anchor.document_properties.description.as_deref(),
+        };
+
+        for rel_id in drawing.image_rel_ids() {
+            let part_name = match self.package.main_document_part_name(rel_id) {
+                Some(part_name) => part_name,
+                None => continue,
+            };
+
+            let media = match self.images {
+                ImageSource::DataUri => self.package.media_for(rel_id).ok().flatten(),
+                ImageSource::PartName => None,
+            };
+            let src = match media {
+                Some(media) => format!(
+                    "data:{};base64,{}",
+                    media.content_type.as_deref().unwrap_or("application/octet-stream"),
+                    base64(&media.bytes)
+                ),
+                None => part_name,
+            };
+
+            let _ = write!(
+                self.output,
+                "<img src=\"{}\" alt=\"{}\" width=\"{}\" height=\"{}\"/>",
+                escape_xml(&src),
+                escape_xml(&unescape_xml(description.unwrap_or_default())),
+                emus_to_pixels(extent.width, 96.0).round(),
+                emus_to_pixels(extent.height, 96.0).round(),
+            );
+        }
+    }
+
+    /// Starts an item of a list at the given level, opening and closing lists as needed.
+    fn list_item(&mut self, level: usize, ordered: bool) {
+        while self.lists.len() > level + 1 {
+            self.close_list();
+        }
+
+        if self.lists.len() == level + 1 {
+            if self.lists[level] == ordered {
+                self.output.push_str("</li>\n");
+            } else {
+                self.close_list();
+            }
+        }
+
+        while self.lists.len() < level + 1 {
+            self.output.push_str(if ordered { "<ol>\n" } else { "<ul>\n" });
+            self.lists.push(ordered);
+        }
+    }
+
+    fn close_list(&mut self) {
+        if let Some(ordered) = self.lists.pop() {
+            self.output
+                .push_str(if ordered { "</li>\n</ol>\n" } else { "</li>\n</ul>\n" });
+        }
+    }
+
+    fn close_lists(&mut self) {
+        while !self.lists.is_empty() {
+            self.close_list();
+        }
+    }
+
+    fn has_page_break_before(&self, paragraph: &P) -> bool {
+        let direct = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.base.start_on_next_page);
+
+        let resolved_style = match &paragraph.properties {
+            Some(properties) if properties.base.style.is_some() => self.package.resolve_paragraph_style(properties),
+            _ => self.package.resolve_default_style(StyleType::Paragraph),
+        };
+
+        direct
+            .or_else(|| resolved_style.and_then(|style| style.paragraph_properties.start_on_next_page))
+            .unwrap_or(false)
+    }
+
+    fn table(&mut self, table: &Tbl) {
+        self.close_lists();
+        self.output.push_str("<table>\n");
+        for row_content in &table.row_contents {
+            self.row_content(row_content);
+        }
+        self.output.push_str("</table>\n");
+    }
+
+    fn row_content(&mut self, content: &ContentRowContent) {
+        match content {
+            ContentRowContent::Table(row) => {
+                self.output.push_str("<tr>\n");
+                for cell_content in &row.contents {
+                    self.cell_content(cell_content);
+                }
+                self.output.push_str("</tr>\n");
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn cell_content(&mut self, content: &ContentCellContent) {
+        match content {
+            ContentCellContent::Cell(cell) => {
+                self.output.push_str("<td>\n");
+                for block_level_element in &cell.block_level_elements {
+                    self.block_level_element(block_level_element);
+                }
+                self.close_lists();
+                self.output.push_str("</td>\n");
+            }
+            ContentCellContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn notes_section(&mut self) {
+        if self.notes.appendix.is_empty() {
+            return;
+        }
+
+        self.output.push_str("<section class=\"notes\">\n<ol>\n");
+        for (number, text) in &self.notes.appendix {
+            let _ = writeln!(self.output, "<li id=\"note-{}\">{}</li>", number, escape_xml(text));
+        }
+        self.output.push_str("</ol>\n</section>\n");
+    }
+}
+
+/// Returns the section properties of the paragraph that ends a section, if the block level element contains one.
+fn section_break_of(element: &BlockLevelElts) -> Option<&SectPr> {
+    match element {
+        BlockLevelElts::Chunk(content) => section_break_of_content(content),
+        BlockLevelElts::AltChunk(_) => None,
+    }
+}
+
+fn section_break_of_content(content: &ContentBlockContent) -> Option<&SectPr> {
+    match content {
+        ContentBlockContent::Paragraph(paragraph) => paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.section_properties.as_ref()),
+        ContentBlockContent::CustomXml(custom_xml) => custom_xml
+            .block_contents
+            .iter()
+            .rev()
+            .find_map(section_break_of_content),
+        ContentBlockContent::Sdt(sdt) => sdt
+            .sdt_content
+            .iter()
+            .flat_map(|content| content.block_contents.iter().rev())
+            .find_map(section_break_of_content),
+        ContentBlockContent::Table(_) | ContentBlockContent::RunLevelElement(_) => None,
+    }
+}
+
+fn section_type_value(section_type: SectionMark) -> &'static str {
+    match section_type {
+        SectionMark::NextPage => "nextPage",
+        SectionMark::NextColumn => "nextColumn",
+        SectionMark::Continuous => "continuous",
+        SectionMark::EvenPage => "evenPage",
+        SectionMark::OddPage => "oddPage",
+    }
+}
+
+fn section_type_class(section_type: SectionMark) -> &'static str {
+    match section_type {
+        SectionMark::NextPage => "next-page",
+        SectionMark::NextColumn => "next-column",
+        SectionMark::Continuous => "continuous",
+        SectionMark::EvenPage => "even-page",
+        SectionMark::OddPage => "odd-page",
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (idx, byte)| group | u32::from(*byte) << (16 - idx * 8));
+
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                encoded.push(char::from(ALPHABET[(group >> (18 - idx * 6)) as usize & 0x3F]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{
+            text::tests::package_with_notes,
+            wml::{document::Document, numbering::Numbering},
+        },
+        shared::relationship::Relationship,
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_to_html_with_appendix_notes() {
+        let html = package_with_notes().to_html(&HtmlOptions {
+            notes: NotePlacement::Appendix,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            html,
+            r##"<div class="document">
+<section class="section section-next-page" data-section-index="0" data-section-type="nextPage" data-columns="1">
+<p>Some claim<sup class="note-ref"><a href="#note-1">[1]</a></sup> and more<sup class="note-ref"><a href="#note-2">[2]</a></sup></p>
+<table>
+<tr>
+<td>
+<p>A &amp; B</p>
+</td>
+<td>
+<p>C</p>
+</td>
+</tr>
+</table>
+</section>
+<section class="notes">
+<ol>
+<li id="note-1">See the source.</li>
+<li id="note-2">First line. Second line.</li>
+</ol>
+</section>
+</div>
+"##
+        );
+    }
+
+    #[test]
+    pub fn test_to_html_with_inline_notes() {
+        let html = package_with_notes().to_html(&HtmlOptions {
+            notes: NotePlacement::Inline,
+            ..Default::default()
+        });
+
+        assert!(html.starts_with(
+            r#"<div class="document">
+<section class="section section-next-page" data-section-index="0" data-section-type="nextPage" data-columns="1">
+<p>Some claim <span class="note">(See the source.)</span> and more <span class="note">(First line. Second line.)</span></p>"#
+        ));
+        assert!(!html.contains("class=\"notes\""));
+    }
+
+    #[test]
+    pub fn test_to_html_sections() {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p><w:r><w:t>Title</w:t></w:r></w:p>
+                <w:p>
+                    <w:pPr>
+                        <w:sectPr><w:type w:val="continuous" /></w:sectPr>
+                    </w:pPr>
+                </w:p>
+                <w:p>
+                    <w:pPr><w:pageBreakBefore /></w:pPr>
+                    <w:r><w:t>Left</w:t><w:br w:type="column" /><w:t>Right</w:t></w:r>
+                </w:p>
+                <w:sectPr>
+                    <w:cols w:num="2" w:space="720" />
+                </w:sectPr>
+            </w:body>
+        </w:document>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.to_html(&Default::default()),
+            r#"<div class="document">
+<section class="section section-continuous" data-section-index="0" data-section-type="continuous" data-columns="1">
+<p>Title</p>
+<p></p>
+</section>
+<section class="section section-next-page page-break-before" data-section-index="1" data-section-type="nextPage" data-columns="2" style="column-count: 2; column-gap: 36pt">
+<p class="page-break-before">Left<br class="column-break"/>Right</p>
+</section>
+</div>
+"#
+        );
+    }
+
+    #[test]
+    pub fn test_to_html_formatting_and_lists() {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Plain </w:t></w:r>
+                    <w:r><w:rPr><w:b /><w:i /></w:rPr><w:t>bold</w:t></w:r>
+                    <w:r><w:rPr><w:b /><w:i /></w:rPr><w:t xml:space="preserve"> italic</w:t></w:r>
+                    <w:r><w:rPr><w:color w:val="FF0000" /><w:u w:val="single" /></w:rPr><w:t>red</w:t></w:r>
+                    <w:hyperlink w:anchor="top"><w:r><w:rPr><w:b /></w:rPr><w:t>link</w:t></w:r></w:hyperlink>
+                </w:p>
+                <w:p><w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr><w:r><w:t>One</w:t></w:r></w:p>
+                <w:p><w:pPr><w:numPr><w:ilvl w:val="1" /><w:numId w:val="1" /></w:numPr></w:pPr><w:r><w:t>Nested</w:t></w:r></w:p>
+                <w:p><w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr><w:r><w:t>Two</w:t></w:r></w:p>
+                <w:p><w:r><w:t>After</w:t></w:r></w:p>
+            </w:body>
+        </w:document>"#;
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0">
+                <w:lvl w:ilvl="0"><w:numFmt w:val="decimal" /></w:lvl>
+                <w:lvl w:ilvl="1"><w:numFmt w:val="bullet" /></w:lvl>
+            </w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.to_html(&Default::default()),
+            r##"<div class="document">
+<section class="section section-next-page" data-section-index="0" data-section-type="nextPage" data-columns="1">
+<p>Plain <strong><em>bold italic</em></strong><span style="color: #FF0000"><u>red</u></span><a href="#top"><strong>link</strong></a></p>
+<ol>
+<li>One<ul>
+<li>Nested</li>
+</ul>
+</li>
+<li>Two</li>
+</ol>
+<p>After</p>
+</section>
+</div>
+"##
+        );
+    }
+
+    #[test]
+    pub fn test_to_html_hyperlink_targets() {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:hyperlink r:id="rId1"><w:r><w:t>query</w:t></w:r></w:hyperlink>
+                    <w:hyperlink r:id="rId2"><w:r><w:t>script</w:t></w:r></w:hyperlink>
+                    <w:hyperlink r:id="rId3"><w:r><w:t>data</w:t></w:r></w:hyperlink>
+                    <w:hyperlink r:id="rId4"><w:r><w:t>mail</w:t></w:r></w:hyperlink>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+        let relationships_xml = r#"<Relationships>
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="https://example.com/?a=1&amp;b=2" TargetMode="External"/>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="javascript:alert(1)" TargetMode="External"/>
+            <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="data:text/html,&lt;script&gt;alert(1)&lt;/script&gt;" TargetMode="External"/>
+            <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="MAILTO:someone@example.com" TargetMode="External"/>
+        </Relationships>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            main_document_relationships: XmlNode::from_str(relationships_xml)
+                .unwrap()
+                .child_nodes
+                .iter()
+                .map(|node| Relationship::from_xml_element(node).unwrap())
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.to_html(&Default::default()),
+            r#"<div class="document">
+<section class="section section-next-page" data-section-index="0" data-section-type="nextPage" data-columns="1">
+<p><a href="https://example.com/?a=1&amp;b=2">query</a>scriptdata<a href="MAILTO:someone@example.com">mail</a></p>
+</section>
+</div>
+"#
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use super::{
+    package::Package,
+    wml::document::{EmbeddedObjectInfo, Hyperlink, Rel},
+};
+use crate::{
+    shared::{
+        partname::{PartName, RelationshipTarget},
+        relationship::Relationship,
+    },
+    xml::unescape_xml,
+};
+use std::error::Error;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+const MAIN_DOCUMENT_PART_NAME: &str = "word/document.xml";
+
+/// The URL schemes of the external targets exported as links by [`LinkTarget::to_export_url`].
+const EXPORTED_URL_SCHEMES: [&str; 3] = ["http", "https", "mailto"];
+
+/// The resolved target of a hyperlink or of a relationship reference.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkTarget {
+    /// A resource outside of the package, e.g. a web page. The anchor of a hyperlink is appended as a fragment.
+    External(String),
+    /// A bookmark in the document
+    Internal(String),
+    /// A part of the package
+    Part(PartName),
+}
+
+impl LinkTarget {
+    /// Returns the target as a URL reference for exports: bookmarks become fragments and parts their part names.
+    pub fn to_url(&self) -> String {
+        match self {
+            LinkTarget::External(url) => url.clone(),
+            LinkTarget::Internal(anchor) => format!("#{}", anchor),
+            LinkTarget::Part(part_name) => part_name.to_string(),
+        }
+    }
+
+    /// Returns the target as a URL that's safe to export as a link of untrusted content: the unescaped URL of an
+    /// `http`, `https` or `mailto` resource, or the fragment of a bookmark. Other targets, e.g. `javascript:` URLs or
+    /// parts, return `None`.
+    pub(crate) fn to_export_url(&self) -> Option<String> {
+        match self {
+            LinkTarget::External(url) => {
+                let url = unescape_xml(url);
+                let scheme = &url[..url.find(':')?];
+                if EXPORTED_URL_SCHEMES
+                    .iter()
+                    .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+                {
+                    Some(url.into_owned())
+                } else {
+                    None
+                }
+            }
+            LinkTarget::Internal(anchor) => Some(format!("#{}", unescape_xml(anchor))),
+            LinkTarget::Part(_) => None,
+        }
+    }
+}
+
+impl From<RelationshipTarget> for LinkTarget {
+    fn from(target: RelationshipTarget) -> Self {
+        match target {
+            RelationshipTarget::Internal(part_name) => LinkTarget::Part(part_name),
+            RelationshipTarget::External(url) => LinkTarget::External(url),
+        }
+    }
+}
+
+/// Resolves the target of the relationship with the given id. `source_part` is the name of the part the
+/// relationships belong to, e.g. `word/document.xml` or `word/header1.xml`. Returns `None` if there's no such
+/// relationship.
+pub fn resolve_relationship(
+    relationships: &[Relationship],
+    source_part: &str,
+    rel_id: &str,
+) -> Result<Option<LinkTarget>> {
+    relationships
+        .iter()
+        .find(|rel| rel.id == rel_id)
+        .map(|rel| Ok(rel.resolve(source_part)?.into()))
+        .transpose()
+}
+
+impl Hyperlink {
+    /// Resolves the target of the hyperlink using the relationships of the part containing it.
+    ///
+    /// Hyperlinks with a relationship id point to an external resource, or rarely to a part of the package. An anchor
+    /// given along with a relationship id is appended to the URL as a fragment. Hyperlinks with an anchor only point
+    /// to a bookmark in the document. Returns `None` if the hyperlink has no target or its relationship is missing.
+    ///
+    /// ```
+    /// use oox::{
+    ///     docx::{hyperlink::LinkTarget, wml::document::Hyperlink},
+    ///     shared::relationship::{Relationship, TargetMode, HYPERLINK_RELATION_TYPE},
+    /// };
+    ///
+    /// let relationships = vec![Relationship {
+    ///     id: String::from("rId1"),
+    ///     rel_type: String::from(HYPERLINK_RELATION_TYPE),
+    ///     target: String::from("https://example.com/page"),
+    ///     target_mode: Some(TargetMode::External),
+    /// }];
+    ///
+    /// let hyperlink = Hyperlink {
+    ///     rel_id: Some(String::from("rId1")),
+    ///     anchor: Some(String::from("section")),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     hyperlink.resolve_target(&relationships, "word/document.xml").unwrap(),
+    ///     Some(LinkTarget::External(String::from("https://example.com/page#section")))
+    /// );
+    ///
+    /// let bookmark_link = Hyperlink {
+    ///     anchor: Some(String::from("_Toc1")),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     bookmark_link.resolve_target(&relationships, "word/document.xml").unwrap(),
+    ///     Some(LinkTarget::Internal(String::from("_Toc1")))
+    /// );
+    /// ```
+    pub fn resolve_target(&self, relationships: &[Relationship], source_part: &str) -> Result<Option<LinkTarget>> {
+        let rel_id = match &self.rel_id {
+            Some(rel_id) => rel_id,
+            None => return Ok(self.anchor.clone().map(LinkTarget::Internal)),
+        };
+
+        let target = resolve_relationship(relationships, source_part, rel_id)?;
+        Ok(match (target, &self.anchor) {
+            (Some(LinkTarget::External(url)), Some(anchor)) if !url.contains('#') => {
+                Some(LinkTarget::External(format!("{}#{}", url, anchor)))
+            }
+            (target, _) => target,
+        })
+    }
+}
+
+impl Rel {
+    /// Resolves the target of a subdocument, movie, content part or other relationship reference using the
+    /// relationships of the part containing it.
+    pub fn resolve_target(&self, relationships: &[Relationship], source_part: &str) -> Result<Option<LinkTarget>> {
+        resolve_relationship(relationships, source_part, &self.rel_id)
+    }
+}
+
+impl EmbeddedObjectInfo {
+    /// Resolves the target of the object's data, linked file or movie using the relationships of the part
+    /// containing it.
+    pub fn resolve_target(&self, relationships: &[Relationship], source_part: &str) -> Result<Option<LinkTarget>> {
+        match &self.rel_id {
+            Some(rel_id) => resolve_relationship(relationships, source_part, rel_id),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Package {
+    /// Resolves the target of a hyperlink of the main document.
+    pub fn resolve_hyperlink(&self, hyperlink: &Hyperlink) -> Result<Option<LinkTarget>> {
+        hyperlink.resolve_target(&self.main_document_relationships, MAIN_DOCUMENT_PART_NAME)
+    }
+
+    /// Resolves the target of a relationship reference of the main document, e.g. a subdocument or a movie.
+    pub fn resolve_rel(&self, rel: &Rel) -> Result<Option<LinkTarget>> {
+        rel.resolve_target(&self.main_document_relationships, MAIN_DOCUMENT_PART_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::relationship::{TargetMode, HYPERLINK_RELATION_TYPE};
+
+    fn relationships_for_test() -> Vec<Relationship> {
+        vec![
+            Relationship {
+                id: String::from("rId1"),
+                rel_type: String::from(HYPERLINK_RELATION_TYPE),
+                target: String::from("https://example.com/#top"),
+                target_mode: Some(TargetMode::External),
+            },
+            Relationship {
+                id: String::from("rId2"),
+                rel_type: String::from(
+                    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/subDocument",
+                ),
+                target: String::from("../chapters/chapter1.docx"),
+                target_mode: None,
+            },
+            Relationship {
+                id: String::from("rId3"),
+                rel_type: String::from(
+                    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/subDocument",
+                ),
+                target: String::from("file:///C:/chapters/chapter2.docx"),
+                target_mode: Some(TargetMode::External),
+            },
+        ]
+    }
+
+    #[test]
+    pub fn test_hyperlink_resolve_target() {
+        let relationships = relationships_for_test();
+
+        let hyperlink = Hyperlink {
+            rel_id: Some(String::from("rId1")),
+            anchor: Some(String::from("ignored")),
+            ..Default::default()
+        };
+        assert_eq!(
+            hyperlink.resolve_target(&relationships, "word/document.xml").unwrap(),
+            Some(LinkTarget::External(String::from("https://example.com/#top")))
+        );
+
+        let missing = Hyperlink {
+            rel_id: Some(String::from("rId9")),
+            ..Default::default()
+        };
+        assert_eq!(
+            missing.resolve_target(&relationships, "word/document.xml").unwrap(),
+            None
+        );
+        assert_eq!(
+            Hyperlink::default()
+                .resolve_target(&relationships, "word/document.xml")
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_rel_resolve_target() {
+        let relationships = relationships_for_test();
+
+        let internal = Rel {
+            rel_id: String::from("rId2"),
+        };
+        assert_eq!(
+            internal.resolve_target(&relationships, "word/document.xml").unwrap(),
+            Some(LinkTarget::Part(PartName::new("/chapters/chapter1.docx").unwrap()))
+        );
+
+        let external = Rel {
+            rel_id: String::from("rId3"),
+        };
+        assert_eq!(
+            external.resolve_target(&relationships, "word/document.xml").unwrap(),
+            Some(LinkTarget::External(String::from("file:///C:/chapters/chapter2.docx")))
+        );
+    }
+}
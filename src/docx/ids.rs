@@ -0,0 +1,692 @@
+use super::{
+    package::Package,
+    visit::{walk_paragraph_mut, walk_paragraph_properties_mut, walk_row_mut, walk_run_mut, VisitMut},
+    wml::{
+        document::{
+            BlockLevelElts, ContentBlockContent, ContentRunContent, Document, PContent, PPr, PPrBase,
+            RangeMarkupElements, RunInnerContent, RunLevelElts, RunTrackChangeChoice, SectPr, P, R,
+        },
+        simpletypes::{DecimalNumber, LongHexNumber},
+        table::{ContentCellContent, ContentRowContent, Row, Tbl},
+    },
+};
+use std::{
+    collections::{BTreeSet, HashMap},
+    convert::TryFrom,
+};
+
+/// The kinds of ids that tie the parts of a package together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IdKind {
+    /// The ids of bookmarks and move ranges, which share the same id space.
+    Bookmark,
+    /// The ids of comments, referenced by comment ranges and comment references of the stories.
+    Comment,
+    /// The ids of numbering instances, referenced by the numbering properties of paragraphs and styles.
+    Numbering,
+    /// The ids of abstract numberings, referenced by numbering instances.
+    AbstractNumbering,
+    /// Revision save ids (rsids), listed in the settings and referenced by paragraphs, runs, rows, sections and
+    /// styles.
+    RevisionSave,
+}
+
+/// Whether an id is defined or only referenced at the place it's found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdUsage {
+    Definition,
+    Reference,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum IdConflictReason {
+    /// The id is defined more than once.
+    Duplicate,
+    /// The id is referenced but never defined.
+    Undefined,
+}
+
+/// An inconsistent id of a package, see [`IdRegistry::conflicts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IdConflict {
+    pub kind: IdKind,
+    pub id: DecimalNumber,
+    pub reason: IdConflictReason,
+}
+
+/// The ids used across the parts of a package: the main document, footnotes, endnotes, comments, numbering, styles
+/// and settings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdRegistry {
+    /// The defined ids of each kind in document order, including duplicates.
+    definitions: HashMap<IdKind, Vec<DecimalNumber>>,
+    references: HashMap<IdKind, BTreeSet<DecimalNumber>>,
+}
+
+impl IdRegistry {
+    fn record(&mut self, kind: IdKind, usage: IdUsage, id: DecimalNumber) {
+        match usage {
+            IdUsage::Definition => self.definitions.entry(kind).or_default().push(id),
+            IdUsage::Reference => {
+                self.references.entry(kind).or_default().insert(id);
+            }
+        }
+    }
+
+    /// Returns every id of the kind that's either defined or referenced.
+    pub fn ids(&self, kind: IdKind) -> BTreeSet<DecimalNumber> {
+        let mut ids: BTreeSet<_> = self.definitions.get(&kind).into_iter().flatten().copied().collect();
+        ids.extend(self.references.get(&kind).into_iter().flatten());
+        ids
+    }
+
+    pub fn is_used(&self, kind: IdKind, id: DecimalNumber) -> bool {
+        self.definitions.get(&kind).is_some_and(|ids| ids.contains(&id))
+            || self.references.get(&kind).is_some_and(|ids| ids.contains(&id))
+    }
+
+    /// Returns the ids defined more than once and the ids referenced without a definition. Revision save ids are
+    /// only checked for duplicates, as the list in the settings is optional.
+    pub fn conflicts(&self) -> Vec<IdConflict> {
+        let mut conflicts = BTreeSet::new();
+        for (kind, ids) in &self.definitions {
+            let mut seen = BTreeSet::new();
+            for id in ids.iter().filter(|id| !seen.insert(**id)) {
+                conflicts.insert((*kind, *id, IdConflictReason::Duplicate));
+            }
+        }
+
+        for (kind, ids) in self
+            .references
+            .iter()
+            .filter(|(kind, _)| **kind != IdKind::RevisionSave)
+        {
+            let definitions = self.definitions.get(kind);
+            for id in ids
+                .iter()
+                .filter(|id| !definitions.is_some_and(|definitions| definitions.contains(id)))
+            {
+                conflicts.insert((*kind, *id, IdConflictReason::Undefined));
+            }
+        }
+
+        conflicts
+            .into_iter()
+            .map(|(kind, id, reason)| IdConflict { kind, id, reason })
+            .collect()
+    }
+
+    /// Returns the ids used by both registries, e.g. the ids that clash when the content of two packages is merged.
+    pub fn conflicts_with(&self, other: &IdRegistry) -> Vec<(IdKind, DecimalNumber)> {
+        let mut kinds: Vec<_> = self.definitions.keys().chain(self.references.keys()).copied().collect();
+        kinds.sort();
+        kinds.dedup();
+
+        kinds
+            .into_iter()
+            .flat_map(|kind| {
+                let other_ids = other.ids(kind);
+                self.ids(kind)
+                    .into_iter()
+                    .filter(move |id| other_ids.contains(id))
+                    .map(move |id| (kind, id))
+            })
+            .collect()
+    }
+
+    /// Returns a map assigning a new id to every id clashing with the other registry. The new ids are unused by both
+    /// registries, so applying the map with [`Package::renumber_ids`] makes the ids of the two packages disjoint.
+    pub fn renumbering_against(&self, other: &IdRegistry) -> IdMap {
+        let mut map = IdMap::default();
+        let mut next_ids = HashMap::new();
+        for (kind, id) in self.conflicts_with(other) {
+            let next_id = next_ids.entry(kind).or_insert_with(|| {
+                let last_id = self.ids(kind).into_iter().chain(other.ids(kind)).max().unwrap_or(0);
+                last_id + 1
+            });
+            map.insert(kind, id, *next_id);
+            *next_id += 1;
+        }
+
+        map
+    }
+}
+
+/// A mapping of old ids to new ones, see [`Package::renumber_ids`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdMap(HashMap<(IdKind, DecimalNumber), DecimalNumber>);
+
+impl IdMap {
+    pub fn insert(&mut self, kind: IdKind, old_id: DecimalNumber, new_id: DecimalNumber) {
+        self.0.insert((kind, old_id), new_id);
+    }
+
+    pub fn get(&self, kind: IdKind, id: DecimalNumber) -> Option<DecimalNumber> {
+        self.0.get(&(kind, id)).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Document {
+    /// Collects the ids used by the body of the document. Only references are recorded for the ids defined by other
+    /// parts, like comments and numberings.
+    pub fn id_registry(&self) -> IdRegistry {
+        let mut scanner = IdScanner::default();
+        scanner.document(self);
+        scanner.registry
+    }
+}
+
+impl Package {
+    /// Collects the ids used by the parts of the package.
+    ///
+    /// A numbering id of 0 removes the numbering of a paragraph, so it isn't recorded as a reference.
+    pub fn id_registry(&self) -> IdRegistry {
+        let mut scanner = IdScanner::default();
+        if let Some(document) = &self.main_document {
+            scanner.document(document);
+        }
+
+        let notes = self.footnotes.iter().flat_map(|footnotes| footnotes.0.iter());
+        for note in notes.chain(self.endnotes.iter().flat_map(|endnotes| endnotes.0.iter())) {
+            scanner.blocks(&note.block_level_elements);
+        }
+
+        for comment in self.comments.iter().flat_map(|comments| comments.0.iter()) {
+            scanner.record(IdKind::Comment, IdUsage::Definition, comment.id);
+            scanner.blocks(&comment.block_level_elements);
+        }
+
+        if let Some(numbering) = &self.numbering {
+            for abstract_numbering in &numbering.abstract_numberings {
+                scanner.record(
+                    IdKind::AbstractNumbering,
+                    IdUsage::Definition,
+                    abstract_numbering.abstract_num_id,
+                );
+            }
+            for numbering in &numbering.numberings {
+                scanner.record(IdKind::Numbering, IdUsage::Definition, numbering.numbering_id);
+                scanner.record(IdKind::AbstractNumbering, IdUsage::Reference, numbering.abstract_num_id);
+            }
+        }
+
+        for style in self.styles.iter().flat_map(|styles| styles.styles.iter()) {
+            scanner.revision_id(&style.revision_id);
+            if let Some(properties) = &style.paragraph_properties {
+                scanner.paragraph_properties(&properties.base);
+            }
+        }
+
+        if let Some(revision_ids) = self
+            .settings
+            .as_ref()
+            .and_then(|settings| settings.revision_ids.as_ref())
+        {
+            for revision_id in revision_ids.revision_id_root.iter().chain(&revision_ids.revision_ids) {
+                scanner.record(
+                    IdKind::RevisionSave,
+                    IdUsage::Definition,
+                    DecimalNumber::from(*revision_id),
+                );
+            }
+        }
+
+        scanner.registry
+    }
+
+    /// Replaces the ids found in the map everywhere they're defined or referenced, keeping the parts consistent with
+    /// each other. Returns the number of replaced ids.
+    pub fn renumber_ids(&mut self, map: &IdMap) -> usize {
+        let mut renumberer = IdRenumberer { map, replaced: 0 };
+        self.visit_stories_mut(&mut renumberer);
+
+        for comment in self.comments.iter_mut().flat_map(|comments| comments.0.iter_mut()) {
+            renumberer.id(IdKind::Comment, &mut comment.id);
+        }
+
+        if let Some(numbering) = &mut self.numbering {
+            for abstract_numbering in &mut numbering.abstract_numberings {
+                renumberer.id(IdKind::AbstractNumbering, &mut abstract_numbering.abstract_num_id);
+            }
+            for numbering in &mut numbering.numberings {
+                renumberer.id(IdKind::Numbering, &mut numbering.numbering_id);
+                renumberer.id(IdKind::AbstractNumbering, &mut numbering.abstract_num_id);
+            }
+        }
+
+        for style in self.styles.iter_mut().flat_map(|styles| styles.styles.iter_mut()) {
+            renumberer.revision_id(&mut style.revision_id);
+            if let Some(properties) = &mut style.paragraph_properties {
+                renumberer.numbering_properties(&mut properties.base);
+            }
+        }
+
+        if let Some(revision_ids) = self
+            .settings
+            .as_mut()
+            .and_then(|settings| settings.revision_ids.as_mut())
+        {
+            renumberer.revision_id(&mut revision_ids.revision_id_root);
+            for revision_id in &mut revision_ids.revision_ids {
+                renumberer.long_hex_id(revision_id);
+            }
+        }
+
+        renumberer.replaced
+    }
+}
+
+fn range_markup_id(element: &RangeMarkupElements) -> Option<(IdKind, IdUsage, DecimalNumber)> {
+    match element {
+        RangeMarkupElements::BookmarkStart(bookmark) => {
+            Some((IdKind::Bookmark, IdUsage::Definition, bookmark.base.base.base.id))
+        }
+        RangeMarkupElements::MoveFromRangeStart(bookmark) | RangeMarkupElements::MoveToRangeStart(bookmark) => {
+            Some((IdKind::Bookmark, IdUsage::Definition, bookmark.base.base.base.base.id))
+        }
+        RangeMarkupElements::BookmarkEnd(range)
+        | RangeMarkupElements::MoveFromRangeEnd(range)
+        | RangeMarkupElements::MoveToRangeEnd(range) => Some((IdKind::Bookmark, IdUsage::Reference, range.base.id)),
+        RangeMarkupElements::CommentRangeStart(range) | RangeMarkupElements::CommentRangeEnd(range) => {
+            Some((IdKind::Comment, IdUsage::Reference, range.base.id))
+        }
+        _ => None,
+    }
+}
+
+fn range_markup_id_mut(element: &mut RangeMarkupElements) -> Option<(IdKind, &mut DecimalNumber)> {
+    match element {
+        RangeMarkupElements::BookmarkStart(bookmark) => Some((IdKind::Bookmark, &mut bookmark.base.base.base.id)),
+        RangeMarkupElements::MoveFromRangeStart(bookmark) | RangeMarkupElements::MoveToRangeStart(bookmark) => {
+            Some((IdKind::Bookmark, &mut bookmark.base.base.base.base.id))
+        }
+        RangeMarkupElements::BookmarkEnd(range)
+        | RangeMarkupElements::MoveFromRangeEnd(range)
+        | RangeMarkupElements::MoveToRangeEnd(range) => Some((IdKind::Bookmark, &mut range.base.id)),
+        RangeMarkupElements::CommentRangeStart(range) | RangeMarkupElements::CommentRangeEnd(range) => {
+            Some((IdKind::Comment, &mut range.base.id))
+        }
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct IdScanner {
+    registry: IdRegistry,
+}
+
+impl IdScanner {
+    fn record(&mut self, kind: IdKind, usage: IdUsage, id: DecimalNumber) {
+        self.registry.record(kind, usage, id);
+    }
+
+    fn revision_id(&mut self, revision_id: &Option<LongHexNumber>) {
+        if let Some(revision_id) = revision_id {
+            self.record(
+                IdKind::RevisionSave,
+                IdUsage::Reference,
+                DecimalNumber::from(*revision_id),
+            );
+        }
+    }
+
+    fn document(&mut self, document: &Document) {
+        if let Some(body) = &document.body {
+            self.blocks(&body.block_level_elements);
+            if let Some(section_properties) = &body.section_properties {
+                self.section_properties(section_properties);
+            }
+        }
+    }
+
+    fn blocks(&mut self, elements: &[BlockLevelElts]) {
+        for element in elements {
+            if let BlockLevelElts::Chunk(content) = element {
+                self.content_block(content);
+            }
+        }
+    }
+
+    fn content_block(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.content_block(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt
+                    .sdt_content
+                    .iter()
+                    .flat_map(|sdt_content| &sdt_content.block_contents)
+                {
+                    self.content_block(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph),
+            ContentBlockContent::Table(table) => self.table(table),
+            ContentBlockContent::RunLevelElement(element) => self.run_level_element(element),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P) {
+        self.revision_id(&paragraph.run_properties_revision_id);
+        self.revision_id(&paragraph.run_revision_id);
+        self.revision_id(&paragraph.deletion_revision_id);
+        self.revision_id(&paragraph.paragraph_revision_id);
+        self.revision_id(&paragraph.run_default_revision_id);
+
+        if let Some(properties) = &paragraph.properties {
+            self.paragraph_properties(&properties.base);
+            if let Some(section_properties) = &properties.section_properties {
+                self.section_properties(section_properties);
+            }
+        }
+
+        self.paragraph_contents(&paragraph.contents);
+    }
+
+    fn paragraph_properties(&mut self, properties: &PPrBase) {
+        let numbering_id = properties
+            .numbering_properties
+            .as_ref()
+            .and_then(|numbering_properties| numbering_properties.numbering_id);
+        if let Some(numbering_id) = numbering_id.filter(|numbering_id| *numbering_id != 0) {
+            self.record(IdKind::Numbering, IdUsage::Reference, numbering_id);
+        }
+    }
+
+    fn paragraph_contents(&mut self, contents: &[PContent]) {
+        for content in contents {
+            match content {
+                PContent::ContentRunContent(content) => self.run_content(content),
+                PContent::SimpleField(field) => self.paragraph_contents(&field.paragraph_contents),
+                PContent::Hyperlink(hyperlink) => self.paragraph_contents(&hyperlink.paragraph_contents),
+                PContent::SubDocument(_) => (),
+            }
+        }
+    }
+
+    fn run_content(&mut self, content: &ContentRunContent) {
+        match content {
+            ContentRunContent::Run(run) => self.run(run),
+            ContentRunContent::CustomXml(custom_xml) => self.paragraph_contents(&custom_xml.paragraph_contents),
+            ContentRunContent::SmartTag(smart_tag) => self.paragraph_contents(&smart_tag.paragraph_contents),
+            ContentRunContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    self.paragraph_contents(&sdt_content.p_contents);
+                }
+            }
+            ContentRunContent::Bidirectional(dir) => self.paragraph_contents(&dir.p_contents),
+            ContentRunContent::BidirectionalOverride(bdo) => self.paragraph_contents(&bdo.p_contents),
+            ContentRunContent::RunLevelElements(element) => self.run_level_element(element),
+        }
+    }
+
+    fn run_level_element(&mut self, element: &RunLevelElts) {
+        match element {
+            RunLevelElts::Insert(track_change)
+            | RunLevelElts::Delete(track_change)
+            | RunLevelElts::MoveFrom(track_change)
+            | RunLevelElts::MoveTo(track_change) => {
+                for RunTrackChangeChoice::ContentRunContent(content) in &track_change.choices {
+                    self.run_content(content);
+                }
+            }
+            RunLevelElts::RangeMarkupElements(element) => {
+                if let Some((kind, usage, id)) = range_markup_id(element) {
+                    self.record(kind, usage, id);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn run(&mut self, run: &R) {
+        self.revision_id(&run.run_properties_revision_id);
+        self.revision_id(&run.deletion_revision_id);
+        self.revision_id(&run.run_revision_id);
+
+        for content in &run.run_inner_contents {
+            if let RunInnerContent::CommentReference(markup) = content {
+                self.record(IdKind::Comment, IdUsage::Reference, markup.id);
+            }
+        }
+    }
+
+    fn table(&mut self, table: &Tbl) {
+        for element in &table.range_markup_elements {
+            if let Some((kind, usage, id)) = range_markup_id(element) {
+                self.record(kind, usage, id);
+            }
+        }
+
+        for content in &table.row_contents {
+            self.row_content(content);
+        }
+    }
+
+    fn row_content(&mut self, content: &ContentRowContent) {
+        match content {
+            ContentRowContent::Table(row) => self.row(row),
+            ContentRowContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| &content.contents) {
+                    self.row_content(content);
+                }
+            }
+            ContentRowContent::RunLevelElements(element) => self.run_level_element(element),
+        }
+    }
+
+    fn row(&mut self, row: &Row) {
+        self.revision_id(&row.run_properties_revision_id);
+        self.revision_id(&row.run_revision_id);
+        self.revision_id(&row.deletion_revision_id);
+        self.revision_id(&row.row_revision_id);
+
+        for content in &row.contents {
+            self.cell_content(content);
+        }
+    }
+
+    fn cell_content(&mut self, content: &ContentCellContent) {
+        match content {
+            ContentCellContent::Cell(cell) => self.blocks(&cell.block_level_elements),
+            ContentCellContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| &content.contents) {
+                    self.cell_content(content);
+                }
+            }
+            ContentCellContent::RunLevelElement(element) => self.run_level_element(element),
+        }
+    }
+
+    fn section_properties(&mut self, section_properties: &SectPr) {
+        let attributes = &section_properties.attributes;
+        self.revision_id(&attributes.run_properties_revision_id);
+        self.revision_id(&attributes.deletion_revision_id);
+        self.revision_id(&attributes.run_revision_id);
+        self.revision_id(&attributes.section_revision_id);
+    }
+}
+
+struct IdRenumberer<'a> {
+    map: &'a IdMap,
+    replaced: usize,
+}
+
+impl<'a> IdRenumberer<'a> {
+    fn id(&mut self, kind: IdKind, id: &mut DecimalNumber) {
+        if let Some(new_id) = self.map.get(kind, *id) {
+            *id = new_id;
+            self.replaced += 1;
+        }
+    }
+
+    fn long_hex_id(&mut self, revision_id: &mut LongHexNumber) {
+        let new_id = self.map.get(IdKind::RevisionSave, DecimalNumber::from(*revision_id));
+        if let Some(new_id) = new_id.and_then(|new_id| LongHexNumber::try_from(new_id).ok()) {
+            *revision_id = new_id;
+            self.replaced += 1;
+        }
+    }
+
+    fn revision_id(&mut self, revision_id: &mut Option<LongHexNumber>) {
+        if let Some(revision_id) = revision_id {
+            self.long_hex_id(revision_id);
+        }
+    }
+
+    fn numbering_properties(&mut self, properties: &mut PPrBase) {
+        if let Some(numbering_id) = properties
+            .numbering_properties
+            .as_mut()
+            .and_then(|numbering_properties| numbering_properties.numbering_id.as_mut())
+        {
+            self.id(IdKind::Numbering, numbering_id);
+        }
+    }
+}
+
+impl<'a> VisitMut for IdRenumberer<'a> {
+    fn visit_paragraph_mut(&mut self, paragraph: &mut P) {
+        self.revision_id(&mut paragraph.run_properties_revision_id);
+        self.revision_id(&mut paragraph.run_revision_id);
+        self.revision_id(&mut paragraph.deletion_revision_id);
+        self.revision_id(&mut paragraph.paragraph_revision_id);
+        self.revision_id(&mut paragraph.run_default_revision_id);
+        walk_paragraph_mut(self, paragraph);
+    }
+
+    fn visit_paragraph_properties_mut(&mut self, properties: &mut PPr) {
+        self.numbering_properties(&mut properties.base);
+        walk_paragraph_properties_mut(self, properties);
+    }
+
+    fn visit_range_markup_mut(&mut self, element: &mut RangeMarkupElements) {
+        if let Some((kind, id)) = range_markup_id_mut(element) {
+            self.id(kind, id);
+        }
+    }
+
+    fn visit_run_mut(&mut self, run: &mut R) {
+        self.revision_id(&mut run.run_properties_revision_id);
+        self.revision_id(&mut run.deletion_revision_id);
+        self.revision_id(&mut run.run_revision_id);
+        walk_run_mut(self, run);
+    }
+
+    fn visit_run_inner_content_mut(&mut self, content: &mut RunInnerContent) {
+        if let RunInnerContent::CommentReference(markup) = content {
+            self.id(IdKind::Comment, &mut markup.id);
+        }
+    }
+
+    fn visit_row_mut(&mut self, row: &mut Row) {
+        self.revision_id(&mut row.run_properties_revision_id);
+        self.revision_id(&mut row.run_revision_id);
+        self.revision_id(&mut row.deletion_revision_id);
+        self.revision_id(&mut row.row_revision_id);
+        walk_row_mut(self, row);
+    }
+
+    fn visit_section_properties_mut(&mut self, section_properties: &mut SectPr) {
+        let attributes = &mut section_properties.attributes;
+        self.revision_id(&mut attributes.run_properties_revision_id);
+        self.revision_id(&mut attributes.deletion_revision_id);
+        self.revision_id(&mut attributes.run_revision_id);
+        self.revision_id(&mut attributes.section_revision_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{comments::Comments, document::Document, numbering::Numbering},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn package(document_xml: &str, comments_xml: &str, numbering_xml: &str) -> Package {
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            comments: Some(Comments::from_xml_element(&XmlNode::from_str(comments_xml).unwrap()).unwrap()),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_id_registry_renumbering() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p w:rsidR="00A1B2C3">
+                <w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr>
+                <w:bookmarkStart w:id="0" w:name="first" />
+                <w:commentRangeStart w:id="0" />
+                <w:r><w:t>Text</w:t></w:r>
+                <w:commentRangeEnd w:id="0" />
+                <w:bookmarkEnd w:id="0" />
+                <w:r><w:commentReference w:id="0" /></w:r>
+            </w:p>
+            <w:p><w:bookmarkStart w:id="1" w:name="second" /><w:bookmarkStart w:id="1" w:name="third" /></w:p>
+        </w:body></w:document>"#;
+        let comments_xml = r#"<w:comments><w:comment w:id="0" w:author="Jane Doe" /></w:comments>"#;
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+
+        let mut first = package(document_xml, comments_xml, numbering_xml);
+        let registry = first.id_registry();
+        assert_eq!(
+            registry.ids(IdKind::Bookmark).into_iter().collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert!(registry.is_used(IdKind::RevisionSave, 0x00A1_B2C3));
+        assert_eq!(
+            registry.conflicts(),
+            vec![IdConflict {
+                kind: IdKind::Bookmark,
+                id: 1,
+                reason: IdConflictReason::Duplicate,
+            }]
+        );
+
+        let second = package(
+            r#"<w:document><w:body><w:p><w:bookmarkStart w:id="0" w:name="other" /><w:bookmarkEnd w:id="0" /></w:p></w:body></w:document>"#,
+            r#"<w:comments><w:comment w:id="0" w:author="John Doe" /><w:comment w:id="3" w:author="John Doe" /></w:comments>"#,
+            r#"<w:numbering><w:abstractNum w:abstractNumId="5" /><w:num w:numId="1"><w:abstractNumId w:val="5" /></w:num></w:numbering>"#,
+        );
+        let map = registry.renumbering_against(&second.id_registry());
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(IdKind::Bookmark, 0), Some(2));
+        assert_eq!(map.get(IdKind::Comment, 0), Some(4));
+        assert_eq!(map.get(IdKind::Numbering, 1), Some(2));
+
+        assert_eq!(first.renumber_ids(&map), 8);
+        assert!(first.id_registry().conflicts_with(&second.id_registry()).is_empty());
+        assert_eq!(first.comments.as_ref().unwrap().0[0].id, 4);
+        assert_eq!(first.numbering.as_ref().unwrap().numberings[0].numbering_id, 2);
+    }
+}
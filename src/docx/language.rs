@@ -0,0 +1,528 @@
+use super::{
+    package::Package,
+    resolvedstyle::RunProperties,
+    text::RevisionView,
+    textmap::RunPath,
+    wml::{
+        document::{Document, Language, RPr, RPrBase, P, R},
+        settings::Proof,
+    },
+};
+use crate::shared::sharedtypes::Lang;
+use std::{cmp::Reverse, collections::HashMap};
+
+/// Detects the language of a piece of text. Implement this trait to plug a language detection library into
+/// [`Package::fix_run_languages`].
+///
+/// Closures taking the text and returning the detected language are detectors as well.
+pub trait LanguageDetector {
+    /// Returns the language of the text as a BCP 47 tag (e.g. `de-DE`), or `None` if the language can't be detected
+    /// reliably.
+    fn detect(&self, text: &str) -> Option<Lang>;
+}
+
+impl<F: Fn(&str) -> Option<Lang>> LanguageDetector for F {
+    fn detect(&self, text: &str) -> Option<Lang> {
+        self(text)
+    }
+}
+
+/// Selects the runs whose language is set by [`Package::fix_run_languages`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LanguageFixMode {
+    /// Only set the language of runs without a language, including the ones inherited from styles and the document
+    /// defaults.
+    MissingOnly,
+    /// Also replace languages that differ from the detected one. Languages are compared by their primary subtag, so
+    /// `en-GB` isn't replaced by a detected `en`.
+    Incorrect,
+}
+
+/// The attribute of `w:lang` used by the characters of a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// `w:val`, used by Latin and other scripts
+    Default,
+    /// `w:eastAsia`
+    EastAsian,
+    /// `w:bidi`, used by complex scripts
+    Complex,
+}
+
+impl Script {
+    /// Classifies the text by its first letter.
+    fn of_text(text: &str) -> Option<Script> {
+        let c = text.chars().find(|c| c.is_alphabetic())?;
+        let script = match c {
+            '\u{1100}'..='\u{11ff}'
+            | '\u{2e80}'..='\u{2fdf}'
+            | '\u{3040}'..='\u{30ff}'
+            | '\u{3130}'..='\u{318f}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{ac00}'..='\u{d7af}'
+            | '\u{f900}'..='\u{faff}'
+            | '\u{ff66}'..='\u{ffdc}' => Script::EastAsian,
+            // Hebrew, Arabic, Syriac, Thaana and the Indic and South East Asian scripts up to Khmer
+            '\u{0590}'..='\u{07bf}' | '\u{0900}'..='\u{0dff}' | '\u{0e00}'..='\u{0eff}' | '\u{1780}'..='\u{17ff}' => {
+                Script::Complex
+            }
+            _ => Script::Default,
+        };
+
+        Some(script)
+    }
+
+    fn language(self, language: &Language) -> Option<&Lang> {
+        match self {
+            Script::Default => language.value.as_ref(),
+            Script::EastAsian => language.east_asia.as_ref(),
+            Script::Complex => language.bidirectional.as_ref(),
+        }
+    }
+
+    fn set_language(self, language: &mut Language, lang: Lang) {
+        match self {
+            Script::Default => language.value = Some(lang),
+            Script::EastAsian => language.east_asia = Some(lang),
+            Script::Complex => language.bidirectional = Some(lang),
+        }
+    }
+}
+
+struct LanguageChange {
+    path: RunPath,
+    script: Script,
+    language: Lang,
+}
+
+impl Package {
+    /// Sets the language of the runs of the main document to the language detected by the detector, which improves
+    /// spell checking, hyphenation and the behavior of screen readers. Returns the number of runs changed.
+    ///
+    /// The text of each paragraph is grouped by script, so e.g. the Japanese text of a paragraph is detected
+    /// separately from its English text and sets `w:eastAsia` instead of `w:val`. Deleted text is ignored.
+    ///
+    /// ```
+    /// use oox::docx::{
+    ///     language::LanguageFixMode,
+    ///     package::Package,
+    ///     wml::document::{Document, P},
+    /// };
+    ///
+    /// let document = Document::builder().paragraph(P::builder().text("Guten Tag").build()).build();
+    /// let mut package = Package {
+    ///     main_document: Some(Box::new(document)),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let detector = |text: &str| if text.contains("Tag") { Some(String::from("de-DE")) } else { None };
+    /// assert_eq!(package.fix_run_languages(&detector, LanguageFixMode::MissingOnly), 1);
+    /// assert_eq!(package.fix_run_languages(&detector, LanguageFixMode::MissingOnly), 0);
+    /// ```
+    pub fn fix_run_languages(&mut self, detector: &dyn LanguageDetector, mode: LanguageFixMode) -> usize {
+        let changes = match &self.main_document {
+            Some(document) => self.language_changes(document, detector, mode),
+            None => return 0,
+        };
+
+        let document = match self.main_document.as_deref_mut() {
+            Some(document) => document,
+            None => return 0,
+        };
+
+        changes
+            .into_iter()
+            .filter_map(|change| {
+                let run = document.run_at_mut(&change.path)?;
+                set_run_language(run, change.script, change.language);
+                Some(())
+            })
+            .count()
+    }
+
+    fn language_changes(
+        &self,
+        document: &Document,
+        detector: &dyn LanguageDetector,
+        mode: LanguageFixMode,
+    ) -> Vec<LanguageChange> {
+        let text_map = document.text_map(RevisionView::Accepted);
+        let chars: Vec<char> = text_map.text.chars().collect();
+
+        // The text of every run, grouped by paragraph in document order
+        let mut paragraphs: Vec<Vec<(&RunPath, String)>> = Vec::new();
+        for span in &text_map.spans {
+            let text = chars[span.start..span.end].iter().collect::<String>();
+            match paragraphs.last_mut() {
+                Some(runs) if runs[0].0.paragraph == span.path.paragraph => match runs.last_mut() {
+                    Some((path, run_text)) if *path == &span.path => run_text.push_str(&text),
+                    _ => runs.push((&span.path, text)),
+                },
+                _ => paragraphs.push(vec![(&span.path, text)]),
+            }
+        }
+
+        let mut changes = Vec::new();
+        for runs in paragraphs {
+            let paragraph = match document.paragraph_at(&runs[0].0.paragraph) {
+                Some(paragraph) => paragraph,
+                None => continue,
+            };
+
+            let runs: Vec<(&RunPath, Script, String)> = runs
+                .into_iter()
+                .filter_map(|(path, text)| Some((path, Script::of_text(&text)?, text)))
+                .collect();
+
+            let mut script_texts: HashMap<Script, String> = HashMap::new();
+            for (_, script, text) in &runs {
+                script_texts.entry(*script).or_default().push_str(text);
+            }
+
+            let detected: HashMap<Script, Lang> = script_texts
+                .into_iter()
+                .filter_map(|(script, text)| Some((script, detector.detect(&text)?)))
+                .collect();
+
+            for (path, script, _) in runs {
+                let language = match detected.get(&script) {
+                    Some(language) => language,
+                    None => continue,
+                };
+
+                let run = match document.run_at(path) {
+                    Some(run) => run,
+                    None => continue,
+                };
+
+                let needs_change = match self.effective_language(paragraph, run, script) {
+                    None => true,
+                    Some(current) => {
+                        mode == LanguageFixMode::Incorrect && primary_subtag(&current) != primary_subtag(language)
+                    }
+                };
+
+                if needs_change {
+                    changes.push(LanguageChange {
+                        path: path.clone(),
+                        script,
+                        language: language.clone(),
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+
+    fn effective_language(&self, paragraph: &P, run: &R, script: Script) -> Option<Lang> {
+        let run_properties = match self.resolve_style_inheritance(paragraph, run) {
+            Some(style) => *style.run_properties,
+            None => RunProperties::from_vec(&run.run_properties.as_ref()?.r_pr_bases),
+        };
+
+        script.language(run_properties.language.as_ref()?).cloned()
+    }
+}
+
+fn primary_subtag(language: &str) -> String {
+    language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn set_run_language(run: &mut R, script: Script, lang: Lang) {
+    let properties = run.run_properties.get_or_insert_with(RPr::default);
+    let language = properties.r_pr_bases.iter_mut().find_map(|property| match property {
+        RPrBase::Language(language) => Some(language),
+        _ => None,
+    });
+
+    match language {
+        Some(language) => script.set_language(language, lang),
+        None => {
+            let mut language = Language::default();
+            script.set_language(&mut language, lang);
+            properties.r_pr_bases.push(RPrBase::Language(language));
+        }
+    }
+}
+
+/// The languages of the runs of one script with one language, see [`LanguageReport::run_languages`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageUsage {
+    pub script: Script,
+    /// The effective language of the runs, `None` if neither the runs nor their styles set one.
+    pub language: Option<Lang>,
+    pub runs: usize,
+    pub characters: usize,
+}
+
+/// The languages and proofing state of a document, see [`Package::language_report`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LanguageReport {
+    /// The effective languages of the runs of the main document, the most used first. Every run is counted by the
+    /// script of its first letter.
+    pub run_languages: Vec<LanguageUsage>,
+    /// The language of the document defaults (`w:docDefaults`).
+    pub default_language: Option<Language>,
+    /// The languages set by the styles, by style id.
+    pub style_languages: Vec<(String, Language)>,
+    /// The languages used to pick the theme fonts (`w:themeFontLang` of the settings).
+    pub theme_font_language: Option<Language>,
+    /// The runs and characters excluded from proofing by `w:noProof`, directly or through their style.
+    pub no_proof_runs: usize,
+    pub no_proof_characters: usize,
+    /// Whether the spelling and grammar of the document were checked when it was saved (`w:proofState`).
+    pub proof_state: Option<Proof>,
+}
+
+impl LanguageReport {
+    /// Returns the language used by the most characters of the given script.
+    pub fn dominant_language(&self, script: Script) -> Option<&Lang> {
+        self.run_languages
+            .iter()
+            .filter(|usage| usage.script == script)
+            .find_map(|usage| usage.language.as_ref())
+    }
+}
+
+impl Package {
+    /// Collects the languages used by the document and its proofing state, e.g. to route documents in a localization
+    /// pipeline. Deleted text is ignored.
+    pub fn language_report(&self) -> LanguageReport {
+        let mut report = LanguageReport {
+            theme_font_language: self
+                .settings
+                .as_ref()
+                .and_then(|settings| settings.theme_font_lang.clone()),
+            proof_state: self.settings.as_ref().and_then(|settings| settings.proof_state),
+            ..Default::default()
+        };
+
+        if let Some(styles) = &self.styles {
+            report.default_language = styles
+                .document_defaults
+                .as_ref()
+                .and_then(|defaults| defaults.run_properties_default.as_ref())
+                .and_then(|default| default.0.as_ref())
+                .and_then(r_pr_language);
+            report.style_languages = styles
+                .styles
+                .iter()
+                .filter_map(|style| {
+                    let language = style.run_properties.as_ref().and_then(r_pr_language)?;
+                    Some((style.style_id.clone()?, language))
+                })
+                .collect();
+        }
+
+        let document = match &self.main_document {
+            Some(document) => document,
+            None => return report,
+        };
+
+        let text_map = document.text_map(RevisionView::Accepted);
+        let chars: Vec<char> = text_map.text.chars().collect();
+        let mut runs: Vec<(&RunPath, String)> = Vec::new();
+        for span in &text_map.spans {
+            let text: String = chars[span.start..span.end].iter().collect();
+            match runs.last_mut() {
+                Some((path, run_text)) if *path == &span.path => run_text.push_str(&text),
+                _ => runs.push((&span.path, text)),
+            }
+        }
+
+        let mut usages: Vec<LanguageUsage> = Vec::new();
+        for (path, text) in runs {
+            let (paragraph, run) = match (document.paragraph_at(&path.paragraph), document.run_at(path)) {
+                (Some(paragraph), Some(run)) => (paragraph, run),
+                _ => continue,
+            };
+            let characters = text.chars().filter(|c| !c.is_whitespace()).count();
+            let properties = self.resolve_run_properties(paragraph, run);
+            if properties.no_proofing == Some(true) {
+                report.no_proof_runs += 1;
+                report.no_proof_characters += characters;
+            }
+
+            let script = match Script::of_text(&text) {
+                Some(script) => script,
+                None => continue,
+            };
+            let language = properties
+                .language
+                .as_ref()
+                .and_then(|language| script.language(language))
+                .cloned();
+            match usages
+                .iter_mut()
+                .find(|usage| usage.script == script && usage.language == language)
+            {
+                Some(usage) => {
+                    usage.runs += 1;
+                    usage.characters += characters;
+                }
+                None => usages.push(LanguageUsage {
+                    script,
+                    language,
+                    runs: 1,
+                    characters,
+                }),
+            }
+        }
+
+        usages.sort_by_key(|usage| Reverse(usage.characters));
+        report.run_languages = usages;
+        report
+    }
+}
+
+fn r_pr_language(r_pr: &RPr) -> Option<Language> {
+    r_pr.r_pr_bases.iter().find_map(|property| match property {
+        RPrBase::Language(language) => Some(language.clone()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            settings::{ProofType, Settings},
+            styles::Styles,
+        },
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn detect(text: &str) -> Option<Lang> {
+        if text.contains('日') {
+            Some(String::from("ja-JP"))
+        } else if text.contains("Tag") {
+            Some(String::from("de-DE"))
+        } else if text.contains("day") {
+            Some(String::from("en"))
+        } else {
+            None
+        }
+    }
+
+    fn run_language(document: &Document, paragraph: usize, run: usize) -> Option<Language> {
+        let path = RunPath {
+            paragraph: vec![paragraph],
+            run: vec![run],
+        };
+        document
+            .run_at(&path)?
+            .run_properties
+            .as_ref()?
+            .r_pr_bases
+            .iter()
+            .find_map(|property| match property {
+                RPrBase::Language(language) => Some(language.clone()),
+                _ => None,
+            })
+    }
+
+    #[test]
+    pub fn test_fix_run_languages() {
+        let styles_xml = r#"<w:styles>
+            <w:docDefaults><w:rPrDefault><w:rPr><w:lang w:val="en-US" /></w:rPr></w:rPrDefault></w:docDefaults>
+        </w:styles>"#;
+        let document_xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Guten Tag </w:t></w:r>
+                <w:r><w:rPr><w:b /></w:rPr><w:t>日本語</w:t></w:r>
+                <w:r><w:t>, </w:t></w:r>
+            </w:p>
+            <w:p><w:r><w:t>Good day</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+
+        let mut package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        // The document defaults set the Latin language, only the East Asian one is missing
+        assert_eq!(package.fix_run_languages(&detect, LanguageFixMode::MissingOnly), 1);
+        let document = package.main_document.as_ref().unwrap();
+        let japanese = run_language(document, 0, 1).unwrap();
+        assert_eq!(japanese.east_asia.as_deref(), Some("ja-JP"));
+        assert_eq!(japanese.value, None);
+        assert_eq!(run_language(document, 0, 0), None);
+
+        // The German text is fixed, the English one already has a matching language
+        assert_eq!(package.fix_run_languages(&detect, LanguageFixMode::Incorrect), 1);
+        let document = package.main_document.as_ref().unwrap();
+        assert_eq!(run_language(document, 0, 0).unwrap().value.as_deref(), Some("de-DE"));
+        assert_eq!(run_language(document, 0, 2), None);
+        assert_eq!(run_language(document, 1, 0), None);
+        assert_eq!(package.fix_run_languages(&detect, LanguageFixMode::Incorrect), 0);
+    }
+
+    #[test]
+    pub fn test_language_report() {
+        let styles_xml = r#"<w:styles>
+            <w:docDefaults><w:rPrDefault><w:rPr><w:lang w:val="en-US" /></w:rPr></w:rPrDefault></w:docDefaults>
+            <w:style w:type="character" w:styleId="Code"><w:rPr><w:noProof /><w:lang w:val="en-GB" /></w:rPr></w:style>
+        </w:styles>"#;
+        let document_xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Hello world </w:t></w:r>
+                <w:r><w:rPr><w:lang w:val="de-DE" w:eastAsia="ja-JP" /></w:rPr><w:t>日本語</w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Code" /></w:rPr><w:t>main()</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let settings_xml = r#"<w:settings>
+            <w:proofState w:spelling="clean" />
+            <w:themeFontLang w:val="en-US" w:eastAsia="zh-CN" />
+        </w:settings>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            settings: Some(Box::new(
+                Settings::from_xml_element(&XmlNode::from_str(settings_xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        let report = package.language_report();
+        let usages: Vec<_> = report
+            .run_languages
+            .iter()
+            .map(|usage| (usage.script, usage.language.as_deref(), usage.characters))
+            .collect();
+        assert_eq!(
+            usages,
+            vec![
+                (Script::Default, Some("en-US"), 10),
+                (Script::Default, Some("en-GB"), 6),
+                (Script::EastAsian, Some("ja-JP"), 3),
+            ]
+        );
+        assert_eq!(
+            report.dominant_language(Script::EastAsian).map(String::as_str),
+            Some("ja-JP")
+        );
+        assert_eq!(report.default_language.unwrap().value.as_deref(), Some("en-US"));
+        assert_eq!(report.style_languages[0].0, "Code");
+        assert_eq!(report.theme_font_language.unwrap().east_asia.as_deref(), Some("zh-CN"));
+        assert_eq!((report.no_proof_runs, report.no_proof_characters), (1, 6));
+        assert_eq!(report.proof_state.unwrap().spelling, Some(ProofType::Clean));
+    }
+}
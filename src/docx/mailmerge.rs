@@ -0,0 +1,549 @@
+use super::{
+    visit::VisitMut,
+    wml::{
+        document::{
+            ContentRunContent, Document, FldCharType, PContent, RPr, RunInnerContent, RunLevelElts,
+            RunTrackChangeChoice, Text, R,
+        },
+        fields::{Field, FieldFormat, FieldInstruction},
+    },
+};
+use std::{cmp::Ordering, collections::HashMap, mem};
+
+/// The values of the merge fields of a mail merge record keyed by the field name.
+pub type MergeRecord = HashMap<String, String>;
+
+impl Document {
+    /// Returns the distinct names of the `MERGEFIELD` fields of the document in order of appearance, including the
+    /// fields nested in other fields, e.g. in the condition of an `IF` field.
+    pub fn merge_field_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for path in self.paragraph_paths() {
+            let mut instructions = Vec::new();
+            if let Some(paragraph) = self.paragraph_at(&path) {
+                collect_instructions(&paragraph.contents, &mut Vec::new(), &mut instructions);
+            }
+
+            for instruction in instructions {
+                if let Ok(Field::MergeField(field)) = Field::parse(&instruction) {
+                    if !names.contains(&field.name) {
+                        names.push(field.name);
+                    }
+                }
+            }
+        }
+
+        names
+    }
+
+    /// Creates a copy of the document with the `MERGEFIELD` and `IF` fields replaced by their result for the record.
+    ///
+    /// Field names are matched case insensitively like Word does, missing values are merged as empty text. A merged
+    /// field is replaced by a single run, which has the formatting of the previous field result if the field has the
+    /// `\* MERGEFORMAT` switch and the formatting of the field instruction otherwise. The `Upper`, `Lower`, `FirstCap`
+    /// and `Caps` text formats are applied to the result. `IF` fields compare numbers numerically and text
+    /// lexicographically, the `*` and `?` wildcards can be used in the right hand side of the `=` and `<>` operators.
+    ///
+    /// Only fields starting and ending in the same paragraph are merged, other fields are left unchanged.
+    ///
+    /// ```
+    /// use oox::{docx::{mailmerge::MergeRecord, text::RevisionView, wml::document::Document}, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r><w:t xml:space="preserve">Dear </w:t></w:r>
+    ///     <w:fldSimple w:instr=" MERGEFIELD Name "><w:r><w:t>«Name»</w:t></w:r></w:fldSimple>
+    /// </w:p></w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// assert_eq!(document.merge_field_names(), vec!["Name"]);
+    ///
+    /// let mut record = MergeRecord::new();
+    /// record.insert(String::from("name"), String::from("Jane"));
+    /// let merged = document.merge_record(&record);
+    /// assert_eq!(merged.text_map(RevisionView::Accepted).text, "Dear Jane");
+    /// ```
+    pub fn merge_record(&self, record: &MergeRecord) -> Document {
+        let mut document = self.clone();
+        FieldMerger { record }.visit_document_mut(&mut document);
+        document
+    }
+
+    /// Merges every record into a copy of the document, see [`Document::merge_record`].
+    pub fn mail_merge<'a, I>(&self, records: I) -> Vec<Document>
+    where
+        I: IntoIterator<Item = &'a MergeRecord>,
+    {
+        records.into_iter().map(|record| self.merge_record(record)).collect()
+    }
+}
+
+/// Collects the instruction of every field in the contents. The instruction of a complex field doesn't contain the
+/// instructions of the fields nested in it.
+fn collect_instructions(contents: &[PContent], stack: &mut Vec<String>, instructions: &mut Vec<String>) {
+    for content in contents {
+        match content {
+            PContent::ContentRunContent(content) => collect_run_content_instructions(content, stack, instructions),
+            PContent::SimpleField(field) => {
+                instructions.push(field.field_codes.clone());
+                collect_instructions(&field.paragraph_contents, stack, instructions);
+            }
+            PContent::Hyperlink(hyperlink) => collect_instructions(&hyperlink.paragraph_contents, stack, instructions),
+            PContent::SubDocument(_) => (),
+        }
+    }
+}
+
+fn collect_run_content_instructions(
+    content: &ContentRunContent,
+    stack: &mut Vec<String>,
+    instructions: &mut Vec<String>,
+) {
+    match content {
+        ContentRunContent::Run(run) => {
+            for inner_content in &run.run_inner_contents {
+                match inner_content {
+                    RunInnerContent::FieldCharacter(field_char) => match field_char.field_char_type {
+                        FldCharType::Begin => stack.push(String::new()),
+                        FldCharType::End => instructions.extend(stack.pop()),
+                        FldCharType::Separate => (),
+                    },
+                    RunInnerContent::InstructionText(text) => {
+                        if let Some(instruction) = stack.last_mut() {
+                            instruction.push_str(&text.text);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        ContentRunContent::CustomXml(custom_xml) => {
+            collect_instructions(&custom_xml.paragraph_contents, stack, instructions)
+        }
+        ContentRunContent::SmartTag(smart_tag) => {
+            collect_instructions(&smart_tag.paragraph_contents, stack, instructions)
+        }
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                collect_instructions(&sdt_content.p_contents, stack, instructions);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => collect_instructions(&dir.p_contents, stack, instructions),
+        ContentRunContent::BidirectionalOverride(bdo) => collect_instructions(&bdo.p_contents, stack, instructions),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            for choice in &track_change.choices {
+                let RunTrackChangeChoice::ContentRunContent(content) = choice;
+                collect_run_content_instructions(content, stack, instructions);
+            }
+        }
+        ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+/// Replaces the fields of every paragraph by their result for a record.
+struct FieldMerger<'a> {
+    record: &'a MergeRecord,
+}
+
+impl VisitMut for FieldMerger<'_> {
+    fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+        let mut result = Vec::with_capacity(contents.len());
+        let mut field = Vec::new();
+        let mut depth = 0;
+        for mut content in isolate_field_characters(mem::take(contents)) {
+            let field_char_type = field_character(&content);
+            if depth > 0 || field_char_type == Some(FldCharType::Begin) {
+                match field_char_type {
+                    Some(FldCharType::Begin) => depth += 1,
+                    Some(FldCharType::End) => depth -= 1,
+                    _ => (),
+                }
+
+                field.push(content);
+                if depth == 0 {
+                    let field = mem::take(&mut field);
+                    match merge_complex_field(&field, self.record) {
+                        Some(runs) => result.extend(runs),
+                        None => result.extend(field),
+                    }
+                }
+                continue;
+            }
+
+            if let PContent::SimpleField(field) = &content {
+                if let Some((text, _)) = evaluate_instruction(&field.field_codes, self.record) {
+                    let properties = first_run(&field.paragraph_contents).and_then(|run| run.run_properties.clone());
+                    result.extend(result_run(text, properties));
+                    continue;
+                }
+            }
+
+            self.visit_paragraph_content_mut(&mut content);
+            result.push(content);
+        }
+
+        // The end of the field is not in these contents
+        result.extend(field);
+        *contents = result;
+    }
+}
+
+/// Splits the runs of the contents, so every field character is in a run of its own.
+fn isolate_field_characters(contents: Vec<PContent>) -> Vec<PContent> {
+    let mut result = Vec::with_capacity(contents.len());
+    for content in contents {
+        match content {
+            PContent::ContentRunContent(run_content) => match *run_content {
+                ContentRunContent::Run(mut run) => {
+                    while let Some(index) = split_index(&run) {
+                        let rest = run.split_off(index, 0);
+                        result.push(run_item(run));
+                        run = rest;
+                    }
+                    result.push(run_item(run));
+                }
+                run_content => result.push(PContent::ContentRunContent(Box::new(run_content))),
+            },
+            content => result.push(content),
+        }
+    }
+
+    result
+}
+
+/// Returns the index where the run has to be split to separate its first field character from the other contents.
+fn split_index(run: &R) -> Option<usize> {
+    let index = run
+        .run_inner_contents
+        .iter()
+        .position(|content| matches!(content, RunInnerContent::FieldCharacter(_)))?;
+
+    match index {
+        0 if run.run_inner_contents.len() > 1 => Some(1),
+        0 => None,
+        index => Some(index),
+    }
+}
+
+fn run_item(run: R) -> PContent {
+    PContent::ContentRunContent(Box::new(ContentRunContent::Run(run)))
+}
+
+fn as_run(content: &PContent) -> Option<&R> {
+    match content {
+        PContent::ContentRunContent(run_content) => match &**run_content {
+            ContentRunContent::Run(run) => Some(run),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn first_run(contents: &[PContent]) -> Option<&R> {
+    contents.iter().find_map(as_run)
+}
+
+/// Returns the type of the field character if the content is a run containing only a field character.
+fn field_character(content: &PContent) -> Option<FldCharType> {
+    match &as_run(content)?.run_inner_contents[..] {
+        [RunInnerContent::FieldCharacter(field_char)] => Some(field_char.field_char_type),
+        _ => None,
+    }
+}
+
+/// A complex field with the results of its nested fields substituted into its instruction.
+struct ComplexField<'a> {
+    instruction: String,
+    result_text: String,
+    /// The first run of the instruction
+    instruction_run: Option<&'a R>,
+    /// The first run of the result
+    result_run: Option<&'a R>,
+}
+
+impl<'a> ComplexField<'a> {
+    /// Parses a field from its begin field character to its end field character.
+    fn parse(field: &'a [PContent], record: &MergeRecord) -> Self {
+        let mut complex_field = ComplexField {
+            instruction: String::new(),
+            result_text: String::new(),
+            instruction_run: None,
+            result_run: None,
+        };
+
+        let mut in_result = false;
+        let mut index = 1;
+        while index + 1 < field.len() {
+            match field_character(&field[index]) {
+                Some(FldCharType::Begin) => {
+                    let end = match nested_field_end(field, index) {
+                        Some(end) => end,
+                        None => break,
+                    };
+
+                    let nested_field = ComplexField::parse(&field[index..=end], record);
+                    let text = match evaluate_instruction(&nested_field.instruction, record) {
+                        Some((text, _)) => text,
+                        None => nested_field.result_text,
+                    };
+
+                    match in_result {
+                        true => complex_field.result_text.push_str(&text),
+                        false => complex_field.instruction.push_str(&text),
+                    }
+                    index = end;
+                }
+                Some(FldCharType::Separate) => in_result = true,
+                _ => {
+                    if let Some(run) = as_run(&field[index]) {
+                        complex_field.push_run(run, in_result);
+                    }
+                }
+            }
+
+            index += 1;
+        }
+
+        complex_field
+    }
+
+    fn push_run(&mut self, run: &'a R, in_result: bool) {
+        for content in &run.run_inner_contents {
+            match content {
+                RunInnerContent::InstructionText(text) if !in_result => {
+                    self.instruction_run.get_or_insert(run);
+                    self.instruction.push_str(&text.text);
+                }
+                RunInnerContent::Text(text) if in_result => {
+                    self.result_run.get_or_insert(run);
+                    self.result_text.push_str(&text.text);
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+fn nested_field_end(field: &[PContent], begin: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, content) in field.iter().enumerate().skip(begin) {
+        match field_character(content) {
+            Some(FldCharType::Begin) => depth += 1,
+            Some(FldCharType::End) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Returns the runs replacing a complex field, or `None` if the field is not merged.
+fn merge_complex_field(field: &[PContent], record: &MergeRecord) -> Option<Vec<PContent>> {
+    let complex_field = ComplexField::parse(field, record);
+    let (text, format) = evaluate_instruction(&complex_field.instruction, record)?;
+    let run = match format.merge_format() {
+        true => complex_field.result_run.or(complex_field.instruction_run),
+        false => complex_field.instruction_run.or(complex_field.result_run),
+    };
+
+    Some(result_run(text, run.and_then(|run| run.run_properties.clone())).collect())
+}
+
+fn result_run(text: String, run_properties: Option<RPr>) -> impl Iterator<Item = PContent> {
+    Some(text).filter(|text| !text.is_empty()).into_iter().map(move |text| {
+        run_item(R {
+            run_properties: run_properties.clone(),
+            run_inner_contents: vec![RunInnerContent::Text(Text {
+                text,
+                xml_space: Some(String::from("preserve")),
+            })],
+            ..Default::default()
+        })
+    })
+}
+
+/// Evaluates a merged field. Returns the result with the formatting switches of the field, or `None` if the field
+/// is not merged.
+fn evaluate_instruction(instruction: &str, record: &MergeRecord) -> Option<(String, FieldFormat)> {
+    let instruction = FieldInstruction::parse(instruction).ok()?;
+    let format = instruction.format();
+    let text = match Field::from_instruction(instruction).ok()? {
+        Field::MergeField(field) => match record_value(record, &field.name) {
+            Some(value) if !value.is_empty() => format!(
+                "{}{}{}",
+                field.text_before.as_deref().unwrap_or_default(),
+                value,
+                field.text_after.as_deref().unwrap_or_default(),
+            ),
+            _ => String::new(),
+        },
+        Field::Other(instruction) if instruction.field_type == "IF" => evaluate_if(&instruction)?,
+        _ => return None,
+    };
+
+    let text = apply_text_formats(text, &format);
+    Some((text, format))
+}
+
+fn record_value<'a>(record: &'a MergeRecord, name: &str) -> Option<&'a str> {
+    record
+        .get(name)
+        .or_else(|| {
+            record
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        })
+        .map(String::as_str)
+}
+
+const COMPARISON_OPERATORS: &[&str] = &["=", "<>", "<", "<=", ">", ">="];
+
+/// Evaluates an `IF Expression1 Operator Expression2 TrueText FalseText` field.
+fn evaluate_if(instruction: &FieldInstruction) -> Option<String> {
+    let mut arguments: Vec<&str> = instruction.arguments.iter().map(String::as_str).collect();
+    // A nested field merged as empty text leaves no argument behind when it's not quoted
+    if matches!(arguments.first(), Some(argument) if COMPARISON_OPERATORS.contains(argument)) {
+        arguments.insert(0, "");
+    }
+
+    let is_true = compare(arguments.first()?, arguments.get(1)?, arguments.get(2)?)?;
+    let text = match is_true {
+        true => arguments.get(3),
+        false => arguments.get(4),
+    };
+
+    Some(text.copied().map(String::from).unwrap_or_default())
+}
+
+fn compare(left: &str, operator: &str, right: &str) -> Option<bool> {
+    let ordering = match (left.trim().parse::<f64>(), right.trim().parse::<f64>()) {
+        (Ok(left), Ok(right)) => left.partial_cmp(&right)?,
+        _ if operator == "=" || operator == "<>" => {
+            let left: Vec<char> = left.chars().collect();
+            let right: Vec<char> = right.chars().collect();
+            match wildcard_match(&right, &left) {
+                true => Ordering::Equal,
+                false => Ordering::Less,
+            }
+        }
+        _ => left.cmp(right),
+    };
+
+    let result = match operator {
+        "=" => ordering == Ordering::Equal,
+        "<>" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+/// Matches the text against a pattern, where `*` matches any number of characters and `?` matches a single one.
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    match (pattern.split_first(), text.split_first()) {
+        (None, _) => text.is_empty(),
+        (Some(('*', pattern_rest)), _) => {
+            wildcard_match(pattern_rest, text) || (!text.is_empty() && wildcard_match(pattern, &text[1..]))
+        }
+        (Some(('?', pattern_rest)), Some((_, text_rest))) => wildcard_match(pattern_rest, text_rest),
+        (Some((expected, pattern_rest)), Some((c, text_rest))) => {
+            expected == c && wildcard_match(pattern_rest, text_rest)
+        }
+        (Some(_), None) => false,
+    }
+}
+
+/// Applies the `Upper`, `Lower`, `FirstCap` and `Caps` text formats of the field.
+fn apply_text_formats(text: String, format: &FieldFormat) -> String {
+    format.formats.iter().fold(text, |text, text_format| {
+        if text_format.eq_ignore_ascii_case("Upper") {
+            text.to_uppercase()
+        } else if text_format.eq_ignore_ascii_case("Lower") {
+            text.to_lowercase()
+        } else if text_format.eq_ignore_ascii_case("FirstCap") {
+            capitalize(&text)
+        } else if text_format.eq_ignore_ascii_case("Caps") {
+            text.split(' ').map(capitalize).collect::<Vec<_>>().join(" ")
+        } else {
+            text
+        }
+    })
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::text::RevisionView, xml::XmlNode};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_mail_merge() {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+                <w:r><w:instrText xml:space="preserve"> IF "</w:instrText></w:r>
+                <w:r><w:fldChar w:fldCharType="begin" /><w:instrText> MERGEFIELD Gender </w:instrText></w:r>
+                <w:r><w:fldChar w:fldCharType="separate" /><w:t>«Gender»</w:t><w:fldChar w:fldCharType="end" /></w:r>
+                <w:r><w:instrText xml:space="preserve">" = "F*" "Ms." "Mr." </w:instrText></w:r>
+                <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+                <w:r><w:t>Mr.</w:t></w:r>
+                <w:r><w:fldChar w:fldCharType="end" /></w:r>
+                <w:r><w:t xml:space="preserve"> </w:t></w:r>
+                <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+                <w:r><w:instrText xml:space="preserve"> MERGEFIELD LastName \* Upper \* MERGEFORMAT </w:instrText></w:r>
+                <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+                <w:r><w:rPr><w:b /></w:rPr><w:t>«LastName»</w:t></w:r>
+                <w:r><w:fldChar w:fldCharType="end" /></w:r>
+                <w:fldSimple w:instr=' MERGEFIELD Title \b ", " '><w:r><w:t>«Title»</w:t></w:r></w:fldSimple>
+            </w:p>
+            <w:p>
+                <w:fldSimple w:instr=" PAGE "><w:r><w:t>1</w:t></w:r></w:fldSimple>
+            </w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(document.merge_field_names(), vec!["Gender", "LastName", "Title"]);
+
+        let records: Vec<MergeRecord> = vec![
+            vec![("Gender", "Female"), ("LastName", "Smith"), ("Title", "PhD")],
+            vec![("Gender", "Male"), ("LastName", "Doe")],
+        ]
+        .into_iter()
+        .map(|record| {
+            record
+                .into_iter()
+                .map(|(name, value)| (String::from(name), String::from(value)))
+                .collect()
+        })
+        .collect();
+
+        let merged = document.mail_merge(&records);
+        let texts: Vec<String> = merged
+            .iter()
+            .map(|document| document.text_map(RevisionView::Accepted).text)
+            .collect();
+        assert_eq!(texts, vec!["Ms. SMITH, PhD\n1", "Mr. DOE\n1"]);
+
+        let last_name_run = merged[0].runs_with_paths()[2].1;
+        assert!(last_name_run.run_properties.is_some());
+        assert_eq!(merged[0].merge_field_names(), Vec::<String>::new());
+    }
+}
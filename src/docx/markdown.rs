@@ -0,0 +1,545 @@
+use super::{
+    package::Package,
+    text::{for_each_run_item, InlineItem, NoteAnchor, NoteCollector, NotePlacement, RevisionView, RunItem},
+    wml::{
+        document::{BlockLevelElts, ContentBlockContent, DrawingChoice, P, R},
+        table::{ContentCellContent, ContentRowContent, Tbl},
+    },
+};
+use crate::xml::unescape_xml;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarkdownOptions {
+    /// With `NotePlacement::Appendix`, notes are exported as Markdown footnotes (`[^1]`).
+    pub notes: NotePlacement,
+}
+
+impl Package {
+    /// Exports the main document as Markdown.
+    ///
+    /// Paragraphs with an outline level become ATX headings, paragraphs of a list become bulleted or numbered list
+    /// items indented by their list level and tables become GFM pipe tables with the first row used as the header.
+    /// Bold, italic and strikethrough runs are rendered as emphasis, hyperlinks as links and pictures as image links
+    /// pointing to the part name of the image, e.g. `word/media/image1.png`.
+    ///
+    /// ```
+    /// use oox::docx::{package::Package, wml::document::Document};
+    /// # use oox::xml::XmlNode;
+    /// # use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p><w:pPr><w:outlineLvl w:val="0" /></w:pPr><w:r><w:t>Title</w:t></w:r></w:p>
+    ///     <w:p><w:r><w:t xml:space="preserve">Some </w:t></w:r><w:r><w:rPr><w:b /></w:rPr><w:t>bold</w:t></w:r></w:p>
+    /// </w:body></w:document>"#;
+    /// let package = Package {
+    ///     main_document: Some(Box::new(Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap())),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(package.to_markdown(&Default::default()), "# Title\n\nSome **bold**\n");
+    /// ```
+    pub fn to_markdown(&self, options: &MarkdownOptions) -> String {
+        let mut writer = MarkdownWriter {
+            package: self,
+            notes: NoteCollector::new(self, options.notes),
+            in_list: false,
+            output: String::new(),
+        };
+
+        let body = self.main_document.as_ref().and_then(|document| document.body.as_ref());
+        for block_level_element in body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            writer.block_level_element(block_level_element);
+        }
+
+        writer.end_list();
+        for (number, text) in &writer.notes.appendix {
+            let _ = writeln!(writer.output, "[^{}]: {}", number, escape_markdown(text));
+        }
+
+        let mut output = writer.output;
+        output.truncate(output.trim_end().len());
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+struct MarkdownWriter<'a> {
+    package: &'a Package,
+    notes: NoteCollector<'a>,
+    /// Whether the previous block was a list item. List items aren't separated by blank lines.
+    in_list: bool,
+    output: String,
+}
+
+impl MarkdownWriter<'_> {
+    fn block_level_element(&mut self, element: &BlockLevelElts) {
+        if let BlockLevelElts::Chunk(content) = element {
+            self.content_block_content(content);
+        }
+    }
+
+    fn content_block_content(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.content_block_content(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph),
+            ContentBlockContent::Table(table) => self.table(table),
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P) {
+        let text = self.paragraph_text(paragraph, false);
+        if text.trim().is_empty() {
+            return;
+        }
+
+        if let Some(level) = self.package.resolve_outline_level(paragraph) {
+            self.end_list();
+            let _ = write!(
+                self.output,
+                "{} {}\n\n",
+                "#".repeat(level.min(5) as usize + 1),
+                text.trim()
+            );
+        } else if let Some(list_level) = self.package.resolve_list_level(paragraph) {
+            let marker = if list_level.is_bulleted() { "-" } else { "1." };
+            let indent = "    ".repeat(list_level.level.max(0) as usize);
+            let _ = writeln!(self.output, "{}{} {}", indent, marker, text.trim());
+            self.in_list = true;
+        } else {
+            self.end_list();
+            let _ = write!(self.output, "{}\n\n", text.trim());
+        }
+    }
+
+    fn end_list(&mut self) {
+        if self.in_list {
+            self.output.push('\n');
+            self.in_list = false;
+        }
+    }
+
+    fn table(&mut self, table: &Tbl) {
+        let mut rows = Vec::new();
+        for row_content in &table.row_contents {
+            self.row_content(row_content, &mut rows);
+        }
+
+        let column_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if column_count == 0 {
+            return;
+        }
+
+        self.end_list();
+        for (idx, row) in rows.iter().enumerate() {
+            self.output.push('|');
+            for column in 0..column_count {
+                let _ = write!(
+                    self.output,
+                    " {} |",
+                    row.get(column).map(String::as_str).unwrap_or_default()
+                );
+            }
+            self.output.push('\n');
+
+            if idx == 0 {
+                self.output.push('|');
+                self.output.push_str(&" --- |".repeat(column_count));
+                self.output.push('\n');
+            }
+        }
+        self.output.push('\n');
+    }
+
+    fn row_content(&mut self, content: &ContentRowContent, rows: &mut Vec<Vec<String>>) {
+        match content {
+            ContentRowContent::Table(row) => {
+                let mut cells = Vec::new();
+                for cell_content in &row.contents {
+                    self.cell_content(cell_content, &mut cells);
+                }
+                rows.push(cells);
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.row_content(content, rows);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.row_content(content, rows);
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn cell_content(&mut self, content: &ContentCellContent, cells: &mut Vec<String>) {
+        match content {
+            ContentCellContent::Cell(cell) => {
+                let mut paragraphs = Vec::new();
+                for block_level_element in &cell.block_level_elements {
+                    if let BlockLevelElts::Chunk(content) = block_level_element {
+                        self.cell_paragraphs(content, &mut paragraphs);
+                    }
+                }
+                cells.push(paragraphs.join("<br>"));
+            }
+            ContentCellContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    self.cell_content(content, cells);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    self.cell_content(content, cells);
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    /// Collects the texts of the paragraphs of a cell. Pipe tables can't contain block elements, so nested tables are
+    /// flattened into their paragraphs.
+    fn cell_paragraphs(&mut self, content: &ContentBlockContent, paragraphs: &mut Vec<String>) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.cell_paragraphs(content, paragraphs);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.cell_paragraphs(content, paragraphs);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => {
+                let text = self.paragraph_text(paragraph, true);
+                if !text.trim().is_empty() {
+                    paragraphs.push(String::from(text.trim()));
+                }
+            }
+            ContentBlockContent::Table(table) => {
+                let mut rows = Vec::new();
+                for row_content in &table.row_contents {
+                    self.row_content(row_content, &mut rows);
+                }
+                paragraphs.extend(rows.into_iter().flatten().filter(|text| !text.is_empty()));
+            }
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    /// Renders the inline content of a paragraph. Line breaks are rendered as `<br>` in table cells, where Markdown
+    /// doesn't allow new lines.
+    fn paragraph_text(&mut self, paragraph: &P, in_table: bool) -> String {
+        let mut items = Vec::new();
+        for_each_run_item(&paragraph.contents, RevisionView::Accepted, &mut |item| {
+            items.push(item)
+        });
+
+        let mut text = EmphasizedText::default();
+        let mut current_run: Option<&R> = None;
+        let mut hyperlinks = Vec::new();
+        for item in items {
+            if let RunItem::Inline(run, _) | RunItem::Drawing(run, _) = &item {
+                if !matches!(current_run, Some(current_run) if std::ptr::eq(current_run, *run)) {
+                    current_run = Some(run);
+                    let properties = self.package.resolve_run_properties(paragraph, run);
+                    text.set_emphasis(Emphasis {
+                        bold: properties.bold.unwrap_or(false),
+                        italic: properties.italic.unwrap_or(false),
+                        strikethrough: properties.strikethrough.unwrap_or(false)
+                            || properties.double_strikethrough.unwrap_or(false),
+                    });
+                }
+            }
+
+            match item {
+                RunItem::Inline(_, InlineItem::Text(value)) => text.pending.push_str(&escape_markdown(value)),
+                RunItem::Inline(_, InlineItem::Tab) => text.pending.push(' '),
+                RunItem::Inline(_, InlineItem::Break(_)) => {
+                    text.flush();
+                    text.output.push_str(if in_table { "<br>" } else { "\\\n" });
+                }
+                RunItem::Inline(_, InlineItem::NoteReference(kind, id)) => match self.notes.reference(kind, id) {
+                    Some(NoteAnchor::Marker(number)) => {
+                        text.flush();
+                        let _ = write!(text.output, "[^{}]", number);
+                    }
+                    Some(NoteAnchor::Inline(note_text)) => {
+                        let _ = write!(text.pending, " ({})", escape_markdown(&note_text));
+                    }
+                    None => (),
+                },
+                RunItem::Drawing(_, drawing) => {
+                    text.flush();
+                    self.image(drawing, &mut text.output);
+                }
+                RunItem::HyperlinkStart(hyperlink) => {
+                    let url = self
+                        .package
+                        .resolve_hyperlink(hyperlink)
+                        .ok()
+                        .flatten()
+                        .and_then(|target| target.to_export_url());
+                    text.set_emphasis(Emphasis::default());
+                    text.flush();
+                    current_run = None;
+                    if url.is_some() {
+                        text.output.push('[');
+                    }
+                    hyperlinks.push(url);
+                }
+                RunItem::HyperlinkEnd => {
+                    text.set_emphasis(Emphasis::default());
+                    text.flush();
+                    current_run = None;
+                    if let Some(Some(url)) = hyperlinks.pop() {
+                        let _ = write!(text.output, "]({})", url.replace(' ', "%20"));
+                    }
+                }
+            }
+        }
+
+        text.flush();
+        text.output
+    }
+
+    fn image(&self, drawing: &DrawingChoice, output: &mut String) {
+        let description = match drawing {
+            DrawingChoice::Inline(inline) => inline.doc_properties.description.as_deref(),
+            DrawingChoice::Anchor(anchor) => anchor.document_properties.description.as_deref(),
+        };
+
+        for rel_id in drawing.image_rel_ids() {
+            if let Some(part_name) = self.package.main_document_part_name(rel_id) {
+                let _ = write!(
+                    output,
+                    "![{}]({})",
+                    escape_markdown(&unescape_xml(description.unwrap_or_default())),
+                    part_name.replace(' ', "%20")
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Emphasis {
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+}
+
+impl Emphasis {
+    fn delimiter(self) -> String {
+        let mut delimiter = String::new();
+        if self.strikethrough {
+            delimiter.push_str("~~");
+        }
+        if self.bold {
+            delimiter.push_str("**");
+        }
+        if self.italic {
+            delimiter.push('*');
+        }
+
+        delimiter
+    }
+}
+
+/// Inline text with emphasis. Text of the same emphasis is collected until the emphasis changes, so adjacent runs
+/// with the same formatting share their delimiters.
+#[derive(Default)]
+struct EmphasizedText {
+    emphasis: Emphasis,
+    pending: String,
+    output: String,
+}
+
+impl EmphasizedText {
+    fn set_emphasis(&mut self, emphasis: Emphasis) {
+        if emphasis != self.emphasis {
+            self.flush();
+            self.emphasis = emphasis;
+        }
+    }
+
+    /// Writes the pending text to the output. Emphasis delimiters can't be next to whitespace on their inner side, so
+    /// leading and trailing whitespace is moved outside of them.
+    fn flush(&mut self) {
+        let delimiter = self.emphasis.delimiter();
+        let trimmed = self.pending.trim();
+        if delimiter.is_empty() || trimmed.is_empty() {
+            self.output.push_str(&self.pending);
+        } else {
+            let leading = &self.pending[..self.pending.len() - self.pending.trim_start().len()];
+            let trailing = &self.pending[self.pending.trim_end().len()..];
+            let closing: String = delimiter.chars().rev().collect();
+            let _ = write!(
+                self.output,
+                "{}{}{}{}{}",
+                leading, delimiter, trimmed, closing, trailing
+            );
+        }
+
+        self.pending.clear();
+    }
+}
+
+fn escape_markdown(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '~' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{
+            text::tests::package_with_notes,
+            wml::{document::Document, numbering::Numbering, styles::Styles},
+        },
+        shared::relationship::Relationship,
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_to_markdown() {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p><w:pPr><w:pStyle w:val="Heading2" /></w:pPr><w:r><w:t>Results</w:t></w:r></w:p>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Plain </w:t></w:r>
+                    <w:r><w:rPr><w:b /></w:rPr><w:t xml:space="preserve">bold </w:t></w:r>
+                    <w:r><w:rPr><w:i /></w:rPr><w:t>italic</w:t></w:r>
+                    <w:r><w:t xml:space="preserve"> and 2*3 </w:t></w:r>
+                    <w:hyperlink w:anchor="top"><w:r><w:t>a link</w:t></w:r></w:hyperlink>
+                </w:p>
+                <w:p><w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr><w:r><w:t>One</w:t></w:r></w:p>
+                <w:p><w:pPr><w:numPr><w:ilvl w:val="1" /><w:numId w:val="1" /></w:numPr></w:pPr><w:r><w:t>Nested</w:t></w:r></w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid><w:gridCol w:w="100" /><w:gridCol w:w="100" /></w:tblGrid>
+                    <w:tr>
+                        <w:tc><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc>
+                        <w:tc><w:p><w:r><w:t>Value</w:t></w:r></w:p></w:tc>
+                    </w:tr>
+                    <w:tr>
+                        <w:tc><w:p><w:r><w:t>a|b</w:t></w:r></w:p></w:tc>
+                        <w:tc><w:p><w:r><w:t>1</w:t></w:r></w:p><w:p><w:r><w:t>2</w:t></w:r></w:p></w:tc>
+                    </w:tr>
+                </w:tbl>
+                <w:p/>
+                <w:p><w:r><w:t>End</w:t></w:r></w:p>
+            </w:body>
+        </w:document>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="paragraph" w:styleId="Heading2"><w:pPr><w:outlineLvl w:val="1" /></w:pPr></w:style>
+        </w:styles>"#;
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0">
+                <w:lvl w:ilvl="0"><w:numFmt w:val="decimal" /></w:lvl>
+                <w:lvl w:ilvl="1"><w:numFmt w:val="bullet" /></w:lvl>
+            </w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.to_markdown(&Default::default()),
+            r"## Results
+
+Plain **bold** *italic* and 2\*3 [a link](#top)
+
+1. One
+    - Nested
+
+| Name | Value |
+| --- | --- |
+| a\|b | 1<br>2 |
+
+End
+"
+        );
+    }
+
+    #[test]
+    pub fn test_to_markdown_with_footnotes() {
+        let markdown = package_with_notes().to_markdown(&MarkdownOptions {
+            notes: NotePlacement::Appendix,
+        });
+
+        assert!(markdown.starts_with("Some claim[^1] and more[^2]\n"));
+        assert!(markdown.ends_with("[^1]: See the source.\n[^2]: First line. Second line.\n"));
+    }
+
+    #[test]
+    pub fn test_to_markdown_hyperlink_targets() {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:hyperlink r:id="rId1"><w:r><w:t>a</w:t></w:r></w:hyperlink>
+                    <w:r><w:t xml:space="preserve"> </w:t></w:r>
+                    <w:hyperlink r:id="rId2"><w:r><w:t>b</w:t></w:r></w:hyperlink>
+                </w:p>
+            </w:body>
+        </w:document>"#;
+        let relationships_xml = r#"<Relationships>
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="https://example.com/?a=1&amp;b=2" TargetMode="External"/>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="javascript:alert(1)" TargetMode="External"/>
+        </Relationships>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            main_document_relationships: XmlNode::from_str(relationships_xml)
+                .unwrap()
+                .child_nodes
+                .iter()
+                .map(|node| Relationship::from_xml_element(node).unwrap())
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.to_markdown(&Default::default()),
+            "[a](https://example.com/?a=1&b=2) b\n"
+        );
+    }
+}
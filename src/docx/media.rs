@@ -0,0 +1,129 @@
+use super::{package::Package, wml::document::DrawingChoice};
+pub use crate::shared::sharedtypes::EMUS_PER_INCH;
+use crate::shared::{
+    drawingml::coordsys::PositiveSize2D,
+    relationship::{join_part_path, RelationshipId},
+};
+use std::{error::Error, fs::File, io::Read};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// Converts a length in EMUs to pixels at the given resolution.
+///
+/// ```
+/// use oox::docx::media::emus_to_pixels;
+///
+/// assert_eq!(emus_to_pixels(914_400, 96.0), 96.0);
+/// assert_eq!(emus_to_pixels(457_200, 72.0), 36.0);
+/// ```
+pub fn emus_to_pixels(emus: u64, dpi: f64) -> f64 {
+    emus as f64 / EMUS_PER_INCH * dpi
+}
+
+/// An image or other media part of the package along with the size it is displayed with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Media {
+    pub part_name: String,
+    /// The content type of the part as declared by `[Content_Types].xml`.
+    pub content_type: Option<String>,
+    pub bytes: Vec<u8>,
+    /// The extent of the drawing displaying the media in EMUs. None if the media wasn't looked up through a drawing.
+    pub extent: Option<PositiveSize2D>,
+}
+
+impl Media {
+    /// Returns the displayed width and height in pixels at the given resolution, rounded to the nearest pixel.
+    pub fn size_in_pixels(&self, dpi: f64) -> Option<(u32, u32)> {
+        self.extent.map(|extent| {
+            (
+                emus_to_pixels(extent.width, dpi).round() as u32,
+                emus_to_pixels(extent.height, dpi).round() as u32,
+            )
+        })
+    }
+}
+
+impl DrawingChoice {
+    /// Returns the extent of the inline or floating drawing.
+    pub fn extent(&self) -> PositiveSize2D {
+        match self {
+            DrawingChoice::Inline(inline) => inline.extent,
+            DrawingChoice::Anchor(anchor) => anchor.extent,
+        }
+    }
+
+    /// Returns the relationship ids of the images embedded by the pictures of the drawing.
+    pub fn image_rel_ids(&self) -> Vec<&RelationshipId> {
+        let graphic = match self {
+            DrawingChoice::Inline(inline) => &inline.graphic,
+            DrawingChoice::Anchor(anchor) => &anchor.graphic,
+        };
+
+        graphic
+            .graphic_data
+            .pictures
+            .iter()
+            .filter_map(|picture| picture.blip_fill_props.blip.as_ref())
+            .filter_map(|blip| blip.embed_rel_id.as_ref())
+            .collect()
+    }
+}
+
+impl Package {
+    /// Returns the part name of the internal part targeted by a relationship of the main document.
+    pub fn main_document_part_name<T: AsRef<str>>(&self, rel_id: T) -> Option<String> {
+        self.main_document_relationships
+            .iter()
+            .find(|rel| rel.id == rel_id.as_ref() && !rel.is_external())
+            .map(|rel| join_part_path("word/document.xml", &rel.target))
+    }
+
+    /// Reads the media part targeted by a relationship of the main document from [`Package::media_bytes`] or the
+    /// package file. Returns `None` if there's no such relationship or it targets an external resource.
+    pub fn media_for<T: AsRef<str>>(&self, rel_id: T) -> Result<Option<Media>> {
+        let part_name = match self.main_document_part_name(rel_id) {
+            Some(part_name) => part_name,
+            None => return Ok(None),
+        };
+
+        let bytes = match self.media_bytes.get(&part_name) {
+            Some(bytes) => bytes.clone(),
+            None => {
+                let file = File::open(&self.file_path)?;
+                let mut zipper = ZipArchive::new(&file)?;
+                let mut zip_file = zipper.by_name(&part_name)?;
+
+                let mut bytes = Vec::with_capacity(zip_file.size() as usize);
+                zip_file.read_to_end(&mut bytes)?;
+                bytes
+            }
+        };
+
+        let content_type = self
+            .content_types
+            .as_ref()
+            .and_then(|content_types| content_types.content_type_for(&part_name))
+            .map(String::from);
+
+        Ok(Some(Media {
+            part_name,
+            content_type,
+            bytes,
+            extent: None,
+        }))
+    }
+
+    /// Reads the images displayed by a drawing with the extent of the drawing.
+    pub fn drawing_media(&self, drawing: &DrawingChoice) -> Result<Vec<Media>> {
+        let mut medias = Vec::new();
+        for rel_id in drawing.image_rel_ids() {
+            if let Some(mut media) = self.media_for(rel_id)? {
+                media.extent = Some(drawing.extent());
+                medias.push(media);
+            }
+        }
+
+        Ok(medias)
+    }
+}
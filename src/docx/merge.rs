@@ -0,0 +1,492 @@
+use super::{
+    ids::{IdKind, IdMap},
+    package::Package,
+    visit::{walk_hyperlink_mut, walk_paragraph_content_mut, walk_paragraph_properties_mut, walk_table_mut, VisitMut},
+    wml::{
+        document::{
+            AltChunk, BlockLevelElts, ContentBlockContent, Document, DrawingChoice, HdrFtrReferences, Hyperlink,
+            PContent, PPr, RPr, RPrBase, RunInnerContent, SectPr, P,
+        },
+        numbering::Numbering,
+        simpletypes::DecimalNumber,
+        styles::Styles,
+        table::Tbl,
+    },
+};
+use crate::shared::relationship::{Relationship, RelationshipId};
+use std::collections::{BTreeSet, HashMap};
+
+/// The relationship types of the main document that target the parts shared by the whole document, so they aren't
+/// imported along with the content of another package.
+const DOCUMENT_PART_RELATION_TYPE_SUFFIXES: [&str; 15] = [
+    "/styles",
+    "/stylesWithEffects",
+    "/numbering",
+    "/settings",
+    "/webSettings",
+    "/fontTable",
+    "/theme",
+    "/footnotes",
+    "/endnotes",
+    "/comments",
+    "/commentsExtended",
+    "/commentsIds",
+    "/people",
+    "/customXml",
+    "/glossaryDocument",
+];
+
+/// Rewrites the ids of a document that's appended to another one, see [`Document::append_document`].
+pub trait IdRemapper {
+    fn relationship_id(&mut self, rel_id: &str) -> RelationshipId;
+
+    fn style_id(&mut self, style_id: &str) -> String {
+        String::from(style_id)
+    }
+
+    fn numbering_id(&mut self, numbering_id: DecimalNumber) -> DecimalNumber {
+        numbering_id
+    }
+}
+
+/// Only the relationship ids are rewritten by a closure, style and numbering ids are kept.
+impl<F: FnMut(&str) -> RelationshipId> IdRemapper for F {
+    fn relationship_id(&mut self, rel_id: &str) -> RelationshipId {
+        self(rel_id)
+    }
+}
+
+/// The ids of a package renamed when its definitions are imported into another package, see
+/// [`Package::import_definitions`]. Ids missing from the maps are kept.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AppendMapping {
+    pub relationship_ids: HashMap<RelationshipId, RelationshipId>,
+    pub style_ids: HashMap<String, String>,
+    /// The new numbering and abstract numbering ids.
+    pub ids: IdMap,
+}
+
+impl IdRemapper for AppendMapping {
+    fn relationship_id(&mut self, rel_id: &str) -> RelationshipId {
+        self.relationship_ids
+            .get(rel_id)
+            .cloned()
+            .unwrap_or_else(|| String::from(rel_id))
+    }
+
+    fn style_id(&mut self, style_id: &str) -> String {
+        self.style_ids
+            .get(style_id)
+            .cloned()
+            .unwrap_or_else(|| String::from(style_id))
+    }
+
+    fn numbering_id(&mut self, numbering_id: DecimalNumber) -> DecimalNumber {
+        self.ids.get(IdKind::Numbering, numbering_id).unwrap_or(numbering_id)
+    }
+}
+
+impl Document {
+    /// Appends the body of another document after the body of this one. The relationship, style and numbering ids of
+    /// the other document are rewritten by the remapper.
+    ///
+    /// The sources are separated by a section break: the last section properties of this document are moved to an
+    /// empty paragraph and the section properties of the other document become the last ones.
+    ///
+    /// ```
+    /// use oox::docx::wml::document::{Body, Document};
+    ///
+    /// let mut document = Document {
+    ///     body: Some(Body::default()),
+    ///     ..Default::default()
+    /// };
+    /// document.append_document(Document::default(), &mut |rel_id: &str| format!("{}_appended", rel_id));
+    /// assert!(document.body.unwrap().block_level_elements.is_empty());
+    /// ```
+    pub fn append_document<M: IdRemapper>(&mut self, other: Document, remapper: &mut M) {
+        let mut other_body = match other.body {
+            Some(body) => body,
+            None => return,
+        };
+
+        IdRewriter { remapper }.visit_body_mut(&mut other_body);
+
+        let body = self.body.get_or_insert_with(Default::default);
+        if !body.block_level_elements.is_empty() {
+            let section_break = P {
+                properties: Some(PPr {
+                    section_properties: Some(body.section_properties.take().unwrap_or_default()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            body.block_level_elements
+                .push(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(Box::new(
+                    section_break,
+                ))));
+        }
+
+        body.block_level_elements.append(&mut other_body.block_level_elements);
+        body.section_properties = other_body.section_properties;
+    }
+}
+
+impl Package {
+    /// Imports the relationships of the main document, styles and numbering definitions of another package, so its
+    /// main document can be appended to this one with the returned mapping:
+    ///
+    /// * relationships used by the content, like hyperlinks, images, headers and footers, are added with new ids. The
+    ///   targets are kept, the parts they point to have to be copied from the other package by the caller,
+    /// * styles missing from this package are added. Styles with the same id but a different definition are renamed
+    ///   by appending a number to their id,
+    /// * every numbering definition is added, renumbered when its id is already used by this package.
+    ///
+    /// Comments, footnotes and endnotes aren't imported.
+    pub fn import_definitions(&mut self, other: &Package) -> AppendMapping {
+        let mut mapping = AppendMapping::default();
+        let relationships = other.main_document_relationships.iter().filter(|relationship| {
+            !DOCUMENT_PART_RELATION_TYPE_SUFFIXES
+                .iter()
+                .any(|suffix| relationship.rel_type.ends_with(suffix))
+        });
+        self.import_relationships(relationships.cloned(), &mut mapping);
+        if let Some(other_styles) = &other.styles {
+            import_styles(
+                self.styles.get_or_insert_with(Default::default),
+                other_styles,
+                &mut mapping,
+            );
+        }
+        if let Some(other_numbering) = &other.numbering {
+            self.import_numbering(other_numbering, &mut mapping);
+        }
+
+        mapping
+    }
+
+    /// Adds the relationships to the main document with new ids, recording the renames in the mapping.
+    pub(crate) fn import_relationships<I: IntoIterator<Item = Relationship>>(
+        &mut self,
+        relationships: I,
+        mapping: &mut AppendMapping,
+    ) {
+        let first_id = self
+            .main_document_relationships
+            .iter()
+            .filter_map(|relationship| relationship.id.strip_prefix("rId")?.parse::<u32>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+
+        for (next_id, relationship) in (first_id..).zip(relationships) {
+            let id = format!("rId{}", next_id);
+            mapping.relationship_ids.insert(relationship.id.clone(), id.clone());
+            self.main_document_relationships
+                .push(Relationship { id, ..relationship });
+        }
+    }
+
+    /// Adds every numbering definition to the numbering of this package, renumbering the ones whose id is already
+    /// used and recording the renames in the mapping.
+    pub(crate) fn import_numbering(&mut self, other_numbering: &Numbering, mapping: &mut AppendMapping) {
+        let numbering = self.numbering.get_or_insert_with(Default::default);
+        let mut abstract_num_ids: BTreeSet<_> = numbering
+            .abstract_numberings
+            .iter()
+            .map(|abstract_numbering| abstract_numbering.abstract_num_id)
+            .collect();
+        let mut numbering_ids: BTreeSet<_> = numbering
+            .numberings
+            .iter()
+            .map(|numbering| numbering.numbering_id)
+            .collect();
+
+        for abstract_numbering in &other_numbering.abstract_numberings {
+            let mut abstract_numbering = abstract_numbering.clone();
+            let id = unused_id(&abstract_num_ids, abstract_numbering.abstract_num_id);
+            if id != abstract_numbering.abstract_num_id {
+                mapping
+                    .ids
+                    .insert(IdKind::AbstractNumbering, abstract_numbering.abstract_num_id, id);
+                abstract_numbering.abstract_num_id = id;
+            }
+            abstract_num_ids.insert(id);
+            numbering.abstract_numberings.push(abstract_numbering);
+        }
+
+        for other_instance in &other_numbering.numberings {
+            let mut instance = other_instance.clone();
+            let id = unused_id(&numbering_ids, instance.numbering_id);
+            if id != instance.numbering_id {
+                mapping.ids.insert(IdKind::Numbering, instance.numbering_id, id);
+                instance.numbering_id = id;
+            }
+            if let Some(abstract_num_id) = mapping.ids.get(IdKind::AbstractNumbering, instance.abstract_num_id) {
+                instance.abstract_num_id = abstract_num_id;
+            }
+            numbering_ids.insert(id);
+            numbering.numberings.push(instance);
+        }
+    }
+}
+
+/// Returns the id if it's unused, or the smallest id after the used ones.
+fn unused_id(used_ids: &BTreeSet<DecimalNumber>, id: DecimalNumber) -> DecimalNumber {
+    if used_ids.contains(&id) {
+        used_ids.iter().next_back().map_or(id, |last_id| last_id + 1)
+    } else {
+        id
+    }
+}
+
+fn import_styles(styles: &mut Styles, other_styles: &Styles, mapping: &mut AppendMapping) {
+    let mut imported = Vec::new();
+    for style in &other_styles.styles {
+        let style_id = match &style.style_id {
+            Some(style_id) => style_id,
+            None => continue,
+        };
+
+        match styles
+            .styles
+            .iter()
+            .find(|existing| existing.style_id.as_ref() == Some(style_id))
+        {
+            Some(existing) if existing == style => (),
+            Some(_) => {
+                let new_id = (1..)
+                    .map(|suffix| format!("{}{}", style_id, suffix))
+                    .find(|new_id| {
+                        !styles
+                            .styles
+                            .iter()
+                            .any(|style| style.style_id.as_ref() == Some(new_id))
+                            && !other_styles
+                                .styles
+                                .iter()
+                                .any(|style| style.style_id.as_ref() == Some(new_id))
+                    })
+                    .unwrap_or_default();
+                mapping.style_ids.insert(style_id.clone(), new_id);
+                imported.push(style.clone());
+            }
+            None => imported.push(style.clone()),
+        }
+    }
+
+    // The links between the imported styles are updated once every renamed style is known
+    for mut style in imported {
+        for style_id in [
+            &mut style.style_id,
+            &mut style.based_on,
+            &mut style.next,
+            &mut style.link,
+        ]
+        .iter_mut()
+        .filter_map(|style_id| style_id.as_mut())
+        {
+            if let Some(new_id) = mapping.style_ids.get(style_id.as_str()) {
+                *style_id = new_id.clone();
+            }
+        }
+        styles.styles.push(style);
+    }
+}
+
+/// Rewrites the relationship, style and numbering ids of block level elements with the remapper.
+pub(crate) fn remap_block_ids<M: IdRemapper>(blocks: &mut Vec<BlockLevelElts>, remapper: &mut M) {
+    IdRewriter { remapper }.visit_blocks_mut(blocks);
+}
+
+struct IdRewriter<'a, M: IdRemapper> {
+    remapper: &'a mut M,
+}
+
+impl<'a, M: IdRemapper> IdRewriter<'a, M> {
+    fn relationship_id(&mut self, rel_id: &mut RelationshipId) {
+        *rel_id = self.remapper.relationship_id(rel_id);
+    }
+
+    fn style_id(&mut self, style_id: &mut Option<String>) {
+        if let Some(style_id) = style_id {
+            *style_id = self.remapper.style_id(style_id);
+        }
+    }
+}
+
+impl<'a, M: IdRemapper> VisitMut for IdRewriter<'a, M> {
+    fn visit_alt_chunk_mut(&mut self, alt_chunk: &mut AltChunk) {
+        if let Some(rel_id) = &mut alt_chunk.rel_id {
+            self.relationship_id(rel_id);
+        }
+    }
+
+    fn visit_paragraph_properties_mut(&mut self, properties: &mut PPr) {
+        self.style_id(&mut properties.base.style);
+        if let Some(numbering_id) = properties
+            .base
+            .numbering_properties
+            .as_mut()
+            .and_then(|numbering_properties| numbering_properties.numbering_id.as_mut())
+        {
+            *numbering_id = self.remapper.numbering_id(*numbering_id);
+        }
+        walk_paragraph_properties_mut(self, properties);
+    }
+
+    fn visit_paragraph_content_mut(&mut self, content: &mut PContent) {
+        if let PContent::SubDocument(rel) = content {
+            self.relationship_id(&mut rel.rel_id);
+        }
+        walk_paragraph_content_mut(self, content);
+    }
+
+    fn visit_hyperlink_mut(&mut self, hyperlink: &mut Hyperlink) {
+        if let Some(rel_id) = &mut hyperlink.rel_id {
+            self.relationship_id(rel_id);
+        }
+        walk_hyperlink_mut(self, hyperlink);
+    }
+
+    fn visit_run_properties_mut(&mut self, run_properties: &mut RPr) {
+        for base in &mut run_properties.r_pr_bases {
+            if let RPrBase::RunStyle(style_id) = base {
+                *style_id = self.remapper.style_id(style_id);
+            }
+        }
+    }
+
+    fn visit_run_inner_content_mut(&mut self, content: &mut RunInnerContent) {
+        let drawing = match content {
+            RunInnerContent::Drawing(drawing) => drawing,
+            _ => return,
+        };
+
+        for choice in &mut drawing.0 {
+            let graphic = match choice {
+                DrawingChoice::Inline(inline) => &mut inline.graphic,
+                DrawingChoice::Anchor(anchor) => &mut anchor.graphic,
+            };
+            for rel_id in graphic
+                .graphic_data
+                .pictures
+                .iter_mut()
+                .filter_map(|picture| picture.blip_fill_props.blip.as_mut())
+                .filter_map(|blip| blip.embed_rel_id.as_mut())
+            {
+                self.relationship_id(rel_id);
+            }
+        }
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Tbl) {
+        self.style_id(&mut table.properties.base.style);
+        walk_table_mut(self, table);
+    }
+
+    fn visit_section_properties_mut(&mut self, section_properties: &mut SectPr) {
+        for reference in &mut section_properties.header_footer_references {
+            match reference {
+                HdrFtrReferences::Header(reference) | HdrFtrReferences::Footer(reference) => {
+                    self.relationship_id(&mut reference.base.rel_id)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shared::sharedtypes::TwipsMeasure, xml::XmlNode};
+    use std::str::FromStr;
+
+    fn package(document_xml: &str, styles_xml: &str, numbering_xml: &str) -> Package {
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            main_document_relationships: vec![Relationship {
+                id: String::from("rId1"),
+                rel_type: String::from("http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"),
+                target: String::from("https://example.com"),
+                target_mode: None,
+            }],
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_append_document() {
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+        let mut first = package(
+            r#"<w:document><w:body>
+                <w:p><w:r><w:t>First</w:t></w:r></w:p>
+                <w:sectPr><w:pgSz w:w="11906" w:h="16838" /></w:sectPr>
+            </w:body></w:document>"#,
+            r#"<w:styles><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1" /></w:style></w:styles>"#,
+            numbering_xml,
+        );
+        let second = package(
+            r#"<w:document><w:body>
+                <w:p>
+                    <w:pPr><w:pStyle w:val="Heading1" /><w:numPr><w:ilvl w:val="0" /><w:numId w:val="1" /></w:numPr></w:pPr>
+                    <w:hyperlink r:id="rId1"><w:r><w:t>Second</w:t></w:r></w:hyperlink>
+                </w:p>
+                <w:sectPr><w:pgSz w:w="12240" w:h="15840" /></w:sectPr>
+            </w:body></w:document>"#,
+            r#"<w:styles><w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="Title" /></w:style></w:styles>"#,
+            numbering_xml,
+        );
+
+        let mut mapping = first.import_definitions(&second);
+        assert_eq!(mapping.relationship_ids.get("rId1").map(String::as_str), Some("rId2"));
+        assert_eq!(mapping.style_ids.get("Heading1").map(String::as_str), Some("Heading11"));
+        assert_eq!(mapping.ids.get(IdKind::Numbering, 1), Some(2));
+        assert_eq!(first.main_document_relationships.len(), 2);
+        assert_eq!(first.styles.as_ref().unwrap().styles.len(), 2);
+        let numbering = first.numbering.as_ref().unwrap();
+        assert_eq!(numbering.numberings[1].abstract_num_id, 1);
+
+        let second_document = *second.main_document.unwrap();
+        let document = first.main_document.as_mut().unwrap();
+        document.append_document(second_document, &mut mapping);
+
+        let body = document.body.as_ref().unwrap();
+        assert_eq!(body.block_level_elements.len(), 3);
+        let section_break = match &body.block_level_elements[1] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph,
+            _ => panic!("expected a paragraph"),
+        };
+        let page_width =
+            |section_properties: Option<&SectPr>| section_properties?.contents.as_ref()?.page_size.as_ref()?.width;
+        let properties = section_break.properties.as_ref().unwrap();
+        assert_eq!(
+            page_width(properties.section_properties.as_ref()),
+            Some(TwipsMeasure::Decimal(11906))
+        );
+        assert_eq!(
+            page_width(body.section_properties.as_ref()),
+            Some(TwipsMeasure::Decimal(12240))
+        );
+
+        let paragraph = match &body.block_level_elements[2] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph,
+            _ => panic!("expected a paragraph"),
+        };
+        let properties = &paragraph.properties.as_ref().unwrap().base;
+        assert_eq!(properties.style.as_deref(), Some("Heading11"));
+        assert_eq!(properties.numbering_properties.as_ref().unwrap().numbering_id, Some(2));
+        match &paragraph.contents[0] {
+            PContent::Hyperlink(hyperlink) => assert_eq!(hyperlink.rel_id.as_deref(), Some("rId2")),
+            content => panic!("expected a hyperlink, got {:?}", content),
+        }
+    }
+}
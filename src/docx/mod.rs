@@ -1,3 +1,49 @@
+pub mod annotation;
+pub mod builder;
+pub mod buildingblock;
+pub mod captions;
+pub mod comments;
+pub mod contentcontrols;
+pub mod customxml;
+pub mod diagrams;
+pub mod diff;
+pub mod dump;
+pub mod fonts;
+pub mod forms;
+pub mod geometry;
+pub mod html;
+pub mod hyperlink;
+pub mod ids;
+pub mod language;
+pub mod mailmerge;
+pub mod markdown;
+pub mod media;
+pub mod merge;
+pub mod normalize;
+pub mod outline;
 pub mod package;
+pub mod pagesetup;
+pub mod paraid;
+pub mod partial;
+pub mod positioning;
+pub mod preview;
+pub mod producer;
+pub mod protection;
+pub mod replace;
 pub mod resolvedstyle;
+pub mod revisions;
+pub mod rsids;
+pub mod sanitize;
+pub mod sdtdate;
+pub mod smarttags;
+pub mod split;
+pub mod statistics;
+pub mod tablelayout;
+pub mod text;
+pub mod textmap;
+pub mod textmetrics;
+pub mod validation;
+pub mod visit;
 pub mod wml;
+pub mod writer;
+pub mod zorder;
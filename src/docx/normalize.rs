@@ -0,0 +1,185 @@
+use super::{
+    package::Package,
+    visit::VisitMut,
+    wml::document::{
+        ContentRunContent, Document, PContent, RPr, RunInnerContent, RunLevelElts, RunTrackChange,
+        RunTrackChangeChoice, Text, R,
+    },
+};
+use std::mem;
+
+impl Document {
+    /// Merges the adjacent runs of the document that have the same run properties and removes empty runs and proofing
+    /// error markers. Returns the number of runs and markers removed.
+    ///
+    /// Word splits text into many runs, because every editing session and spell check marks the text it touched. The
+    /// properties of merged runs are compared without their revision save ids, the merged run keeps the ids of the
+    /// first one. Adjacent text of merged runs is concatenated into a single text element.
+    ///
+    /// ```
+    /// use oox::{docx::wml::document::Document, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r w:rsidR="00A1"><w:t xml:space="preserve">Hello </w:t></w:r>
+    ///     <w:proofErr w:type="spellStart" />
+    ///     <w:r w:rsidR="00B2"><w:t>wrold</w:t></w:r>
+    ///     <w:proofErr w:type="spellEnd" />
+    /// </w:p></w:body></w:document>"#;
+    ///
+    /// let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// assert_eq!(document.normalize_runs(), 3);
+    /// ```
+    pub fn normalize_runs(&mut self) -> usize {
+        let mut normalizer = RunNormalizer::default();
+        normalizer.visit_document_mut(self);
+        normalizer.removed
+    }
+}
+
+impl Package {
+    /// Normalizes the runs of the main document, the footnotes, the endnotes and the comments, see
+    /// [`Document::normalize_runs`]. Returns the number of runs and markers removed.
+    pub fn normalize_runs(&mut self) -> usize {
+        let mut normalizer = RunNormalizer::default();
+        self.visit_stories_mut(&mut normalizer);
+        normalizer.removed
+    }
+}
+
+#[derive(Default)]
+struct RunNormalizer {
+    removed: usize,
+}
+
+impl VisitMut for RunNormalizer {
+    /// Normalizes the contents of a paragraph or of an element nested in a paragraph, e.g. a hyperlink.
+    fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+        let mut result: Vec<PContent> = Vec::with_capacity(contents.len());
+        for mut content in mem::take(contents) {
+            if let PContent::ContentRunContent(run_content) = &mut content {
+                match &mut **run_content {
+                    ContentRunContent::Run(run) => {
+                        if run.run_inner_contents.is_empty() {
+                            self.removed += 1;
+                            continue;
+                        }
+
+                        if let Some(PContent::ContentRunContent(previous)) = result.last_mut() {
+                            if let ContentRunContent::Run(previous) = &mut **previous {
+                                if same_properties(previous, run) {
+                                    append_run(previous, mem::take(run));
+                                    self.removed += 1;
+                                    continue;
+                                }
+                            }
+                        }
+                    }
+                    ContentRunContent::RunLevelElements(RunLevelElts::ProofError(_)) => {
+                        self.removed += 1;
+                        continue;
+                    }
+                    _ => (),
+                }
+            }
+
+            self.visit_paragraph_content_mut(&mut content);
+            result.push(content);
+        }
+
+        *contents = result;
+    }
+
+    /// Normalizes the runs of a revision. Revisions hold run content instead of paragraph content, so they're
+    /// normalized as paragraph content and converted back.
+    fn visit_run_track_change_mut(&mut self, track_change: &mut RunTrackChange) {
+        let mut contents = track_change
+            .choices
+            .drain(..)
+            .map(|RunTrackChangeChoice::ContentRunContent(content)| PContent::ContentRunContent(Box::new(content)))
+            .collect();
+        self.visit_paragraph_contents_mut(&mut contents);
+        track_change.choices = contents
+            .into_iter()
+            .filter_map(|content| match content {
+                PContent::ContentRunContent(content) => Some(RunTrackChangeChoice::ContentRunContent(*content)),
+                _ => None,
+            })
+            .collect();
+    }
+}
+
+/// Appends the contents of a run to another one with the same properties. Adjacent text elements of the same kind are
+/// concatenated.
+fn append_run(run: &mut R, other: R) {
+    for content in other.run_inner_contents {
+        match (run.run_inner_contents.last_mut(), content) {
+            (Some(RunInnerContent::Text(text)), RunInnerContent::Text(other_text))
+            | (Some(RunInnerContent::DeletedText(text)), RunInnerContent::DeletedText(other_text))
+            | (Some(RunInnerContent::InstructionText(text)), RunInnerContent::InstructionText(other_text))
+            | (
+                Some(RunInnerContent::DeletedInstructionText(text)),
+                RunInnerContent::DeletedInstructionText(other_text),
+            ) => append_text(text, other_text),
+            (_, content) => run.run_inner_contents.push(content),
+        }
+    }
+}
+
+/// Compares the properties of two runs. A missing and an empty run properties element are considered equal.
+fn same_properties(run: &R, other: &R) -> bool {
+    let is_empty = |properties: &RPr| properties.r_pr_bases.is_empty() && properties.run_properties_change.is_none();
+    match (&run.run_properties, &other.run_properties) {
+        (Some(properties), Some(other_properties)) => properties == other_properties,
+        (Some(properties), None) | (None, Some(properties)) => is_empty(properties),
+        (None, None) => true,
+    }
+}
+
+fn append_text(text: &mut Text, other: Text) {
+    text.text.push_str(&other.text);
+    if text.xml_space.is_none() {
+        text.xml_space = other.xml_space;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_normalize_runs() {
+        let xml = r#"<w:document><w:body><w:p>
+            <w:r w:rsidR="00A1"><w:rPr><w:b /></w:rPr><w:t xml:space="preserve">Bold </w:t></w:r>
+            <w:proofErr w:type="spellStart" />
+            <w:r w:rsidR="00B2"><w:rPr><w:b /></w:rPr><w:t>txet</w:t><w:tab /></w:r>
+            <w:proofErr w:type="spellEnd" />
+            <w:r w:rsidR="00B2"><w:rPr><w:b /></w:rPr><w:t>tail</w:t></w:r>
+            <w:r><w:rPr><w:b /></w:rPr></w:r>
+            <w:r><w:t xml:space="preserve"> plain</w:t></w:r>
+            <w:hyperlink w:anchor="top">
+                <w:r><w:t>one</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> two</w:t></w:r>
+            </w:hyperlink>
+            <w:ins w:id="1" w:author="Jane">
+                <w:r><w:t>in</w:t></w:r>
+                <w:r><w:t>serted</w:t></w:r>
+            </w:ins>
+        </w:p></w:body></w:document>"#;
+        let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(document.normalize_runs(), 7);
+
+        let expected_xml = r#"<w:document><w:body><w:p>
+            <w:r w:rsidR="00A1"><w:rPr><w:b /></w:rPr><w:t xml:space="preserve">Bold txet</w:t><w:tab /><w:t>tail</w:t></w:r>
+            <w:r><w:t xml:space="preserve"> plain</w:t></w:r>
+            <w:hyperlink w:anchor="top"><w:r><w:t xml:space="preserve">one two</w:t></w:r></w:hyperlink>
+            <w:ins w:id="1" w:author="Jane"><w:r><w:t>inserted</w:t></w:r></w:ins>
+        </w:p></w:body></w:document>"#;
+        let expected = Document::from_xml_element(&XmlNode::from_str(expected_xml).unwrap()).unwrap();
+        assert_eq!(document, expected);
+        assert_eq!(document.normalize_runs(), 0);
+    }
+}
@@ -0,0 +1,261 @@
+use super::{
+    captions::{field_char_run, preserved_text, run_with_content},
+    package::Package,
+    text::{for_each_inline, InlineItem},
+    wml::document::{FldCharType, PContent, PPr, PPrBase, RunInnerContent, P},
+};
+use std::iter::Peekable;
+
+/// A heading of the document outline, see [`Package::outline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineEntry {
+    /// The 0-based outline level of the heading, e.g. 0 for `Heading 1`.
+    pub level: i64,
+    pub text: String,
+    /// The path of the heading paragraph following the convention of [`RunPath::paragraph`].
+    ///
+    /// [`RunPath::paragraph`]: super::textmap::RunPath::paragraph
+    pub path: Vec<usize>,
+    /// The headings following this one until the next heading of the same or a higher level.
+    pub children: Vec<OutlineEntry>,
+}
+
+/// The options of [`Package::table_of_contents`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocOptions {
+    /// The lowest and highest heading levels listed, 1-based as in the `\o` switch of the TOC field.
+    pub levels: (i64, i64),
+    /// The text written in place of the page numbers, which are only known after the document is laid out.
+    pub page_number_placeholder: String,
+}
+
+impl Default for TocOptions {
+    fn default() -> Self {
+        Self {
+            levels: (1, 3),
+            page_number_placeholder: String::from("#"),
+        }
+    }
+}
+
+impl Package {
+    /// Builds the outline of the main document from the paragraphs that have an outline level, either directly or
+    /// through their paragraph style. Headings in tables and content controls are included, empty headings are
+    /// skipped.
+    ///
+    /// ```
+    /// use oox::docx::{package::Package, wml::document::Document};
+    /// # use oox::xml::XmlNode;
+    /// # use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p><w:pPr><w:outlineLvl w:val="0" /></w:pPr><w:r><w:t>Introduction</w:t></w:r></w:p>
+    ///     <w:p><w:pPr><w:outlineLvl w:val="1" /></w:pPr><w:r><w:t>Scope</w:t></w:r></w:p>
+    ///     <w:p><w:pPr><w:outlineLvl w:val="0" /></w:pPr><w:r><w:t>Design</w:t></w:r></w:p>
+    /// </w:body></w:document>"#;
+    /// let package = Package {
+    ///     main_document: Some(Box::new(Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap())),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let outline = package.outline();
+    /// assert_eq!(outline.len(), 2);
+    /// assert_eq!(outline[0].children[0].text, "Scope");
+    /// ```
+    pub fn outline(&self) -> Vec<OutlineEntry> {
+        nest_entries(&mut self.headings().into_iter().peekable(), None)
+    }
+
+    /// Generates the paragraphs of a table of contents field (`TOC \o "1-3" \h \z \u`) with a field result listing
+    /// the headings of the given levels. Every entry is a paragraph with the `TOC1` to `TOC9` style of its level,
+    /// containing the heading text, a tab and the page number placeholder.
+    pub fn table_of_contents(&self, options: &TocOptions) -> Vec<P> {
+        let (min_level, max_level) = options.levels;
+        let mut paragraphs: Vec<P> = self
+            .headings()
+            .into_iter()
+            .filter(|entry| (min_level..=max_level).contains(&(entry.level + 1)))
+            .map(|entry| {
+                toc_paragraph(
+                    entry.level + 1,
+                    vec![
+                        run_with_content(RunInnerContent::Text(preserved_text(entry.text))),
+                        run_with_content(RunInnerContent::Tab),
+                        run_with_content(RunInnerContent::Text(preserved_text(
+                            options.page_number_placeholder.clone(),
+                        ))),
+                    ],
+                )
+            })
+            .collect();
+
+        if paragraphs.is_empty() {
+            paragraphs.push(toc_paragraph(
+                min_level,
+                vec![run_with_content(RunInnerContent::Text(preserved_text(String::from(
+                    "No table of contents entries found.",
+                ))))],
+            ));
+        }
+
+        let field_start = vec![
+            field_char_run(FldCharType::Begin),
+            run_with_content(RunInnerContent::InstructionText(preserved_text(format!(
+                r#" TOC \o "{}-{}" \h \z \u "#,
+                min_level, max_level
+            )))),
+            field_char_run(FldCharType::Separate),
+        ];
+
+        if let Some(first) = paragraphs.first_mut() {
+            first.contents.splice(0..0, field_start);
+        }
+
+        if let Some(last) = paragraphs.last_mut() {
+            last.contents.push(field_char_run(FldCharType::End));
+        }
+
+        paragraphs
+    }
+
+    /// Returns the non-empty headings of the main document in document order, without children.
+    fn headings(&self) -> Vec<OutlineEntry> {
+        let document = match &self.main_document {
+            Some(document) => document,
+            None => return Vec::new(),
+        };
+
+        document
+            .paragraphs()
+            .filter_map(|(path, paragraph)| {
+                let level = self.resolve_outline_level(paragraph)?;
+                let text = heading_text(paragraph);
+                if text.is_empty() {
+                    return None;
+                }
+
+                Some(OutlineEntry {
+                    level,
+                    text,
+                    path,
+                    children: Vec::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the visible text of a heading with tabs and breaks replaced by spaces.
+fn heading_text(paragraph: &P) -> String {
+    let mut text = String::new();
+    for_each_inline(&paragraph.contents, &mut |item| match item {
+        InlineItem::Text(value) => text.push_str(value),
+        InlineItem::Tab | InlineItem::Break(_) => text.push(' '),
+        InlineItem::NoteReference(..) => (),
+    });
+
+    String::from(text.trim())
+}
+
+/// Takes the entries that belong under a heading of the given level, nesting each of them recursively.
+fn nest_entries<I>(entries: &mut Peekable<I>, parent_level: Option<i64>) -> Vec<OutlineEntry>
+where
+    I: Iterator<Item = OutlineEntry>,
+{
+    let mut nested = Vec::new();
+    while let Some(mut entry) = entries.next_if(|entry| parent_level.is_none_or(|level| entry.level > level)) {
+        entry.children = nest_entries(entries, Some(entry.level));
+        nested.push(entry);
+    }
+
+    nested
+}
+
+fn toc_paragraph(level: i64, contents: Vec<PContent>) -> P {
+    P {
+        properties: Some(PPr {
+            base: PPrBase {
+                style: Some(format!("TOC{}", level)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+        contents,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{document::Document, styles::Styles},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn package_for_test() -> Package {
+        let document = r#"<w:document><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Introduction</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Body text</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading2" /></w:pPr><w:r><w:t>Scope</w:t></w:r></w:p>
+            <w:p><w:pPr><w:outlineLvl w:val="3" /></w:pPr><w:r><w:t>Details</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading2" /></w:pPr></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Design</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let styles = r#"<w:styles>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:pPr><w:outlineLvl w:val="0" /></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading2"><w:pPr><w:outlineLvl w:val="1" /></w:pPr></w:style>
+        </w:styles>"#;
+
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_outline() {
+        let outline = package_for_test().outline();
+        assert_eq!(outline.len(), 2);
+        assert_eq!(outline[0].text, "Introduction");
+        assert_eq!(outline[0].path, vec![0]);
+        assert_eq!(outline[0].children.len(), 1);
+
+        let scope = &outline[0].children[0];
+        assert_eq!(
+            (scope.level, scope.text.as_str(), scope.path.clone()),
+            (1, "Scope", vec![2])
+        );
+        assert_eq!(scope.children[0].text, "Details");
+        assert_eq!(scope.children[0].level, 3);
+        assert_eq!(outline[1].text, "Design");
+        assert!(outline[1].children.is_empty());
+    }
+
+    #[test]
+    pub fn test_table_of_contents() {
+        let package = package_for_test();
+        let paragraphs = package.table_of_contents(&Default::default());
+        assert_eq!(paragraphs.len(), 3);
+        assert_eq!(
+            paragraphs[1].properties.as_ref().unwrap().base.style.as_deref(),
+            Some("TOC2")
+        );
+        assert_eq!(paragraphs[0].contents.len(), 6);
+        assert_eq!(paragraphs[1].contents.len(), 3);
+        assert_eq!(paragraphs[2].contents.len(), 4);
+
+        let empty = package.table_of_contents(&TocOptions {
+            levels: (5, 9),
+            ..Default::default()
+        });
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0].contents.len(), 5);
+    }
+}
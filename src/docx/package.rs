@@ -1,24 +1,42 @@
 use super::{
+    partial::DocumentRange,
     resolvedstyle::{ResolvedStyle, RunProperties},
+    validation::{repair_wml_part, ParseDiagnostic},
     wml::{
+        comments::{Comments, CommentsEx},
         document::{
-            BlockLevelElts, ContentBlockContent, ContentRunContent, Document, PContent, PPr, RPr, RPrBase,
-            SectPrContents, P, R,
+            BlockLevelElts, ContentBlockContent, ContentRunContent, DataBinding, Document, EmbeddedObjectInfo,
+            NumberFormat, PContent, PPr, RPr, RPrBase, SectPrContents, ThemeColor, P, R,
         },
-        footnotes::{Footnotes, FtnEdn, FtnEdnType},
+        fonttable::FontTable,
+        footnotes::{Endnotes, Footnotes, FtnEdn, FtnEdnType},
+        glossary::{DocPart, GlossaryDocument},
         numbering::{Lvl, Numbering},
         settings::Settings,
         styles::{Style, StyleType, Styles},
+        websettings::{Div, WebSettings},
     },
 };
+#[cfg(feature = "tokio")]
 use crate::{
+    error::AsyncLoadError,
+    shared::partsource::{read_parts, PartEntry, PartSource},
+};
+use crate::{
+    error::PartParseError,
     shared::{
+        contenttypes::{ContentTypes, CONTENT_TYPES_PART_NAME},
+        customxml::{pair_custom_xml_parts, CustomXmlPart, DataStoreItem},
         docprops::{AppInfo, Core},
-        drawingml::sharedstylesheet::OfficeStyleSheet,
-        relationship::{Relationship, THEME_RELATION_TYPE},
+        drawingml::{sharedstylesheet::OfficeStyleSheet, simpletypes::HexColorRGB},
+        filekind::ensure_zip_package,
+        flatopc::{flat_opc_content_types, read_flat_opc_parts},
+        partcache::PartParsers,
+        partkind::PartKind,
+        relationship::{source_part_of_rels_path, Relationship, Relationships, THEME_RELATION_TYPE},
     },
     update::Update,
-    xml::zip_file_to_xml_node,
+    xml::{read_xml_node, zip_file_to_xml_node, XmlNode},
 };
 use log::error;
 use std::{
@@ -26,80 +44,599 @@ use std::{
     error::Error,
     ffi::OsStr,
     fs::File,
+    io::Read,
     path::{Path, PathBuf},
 };
-use zip::ZipArchive;
+use zip::{read::ZipFile, ZipArchive};
 
 #[derive(Debug, Default)]
 pub struct Package {
+    pub file_path: PathBuf,
+    pub content_types: Option<ContentTypes>,
     pub app_info: Option<AppInfo>,
     pub core: Option<Core>,
     pub main_document: Option<Box<Document>>,
     pub main_document_relationships: Vec<Relationship>,
     pub styles: Option<Box<Styles>>,
     pub footnotes: Option<Footnotes>,
+    pub endnotes: Option<Endnotes>,
+    pub comments: Option<Comments>,
+    pub comments_extended: Option<CommentsEx>,
     pub numbering: Option<Numbering>,
     pub settings: Option<Box<Settings>>,
+    pub web_settings: Option<WebSettings>,
+    pub font_table: Option<FontTable>,
+    /// The relationships of the font table, targeting the embedded fonts.
+    pub font_table_relationships: Vec<Relationship>,
+    pub glossary_document: Option<Box<GlossaryDocument>>,
+    /// The relationships of the glossary document, targeting e.g. the images of the building blocks.
+    pub glossary_document_relationships: Vec<Relationship>,
+    pub glossary_styles: Option<Box<Styles>>,
+    pub glossary_numbering: Option<Numbering>,
+    pub custom_xml_parts: Vec<CustomXmlPart>,
     pub medias: Vec<PathBuf>,
+    /// The content of the media parts that can't be read from [`Package::file_path`], keyed by part name, e.g. the
+    /// binary data of a Flat OPC document. See [`Package::media_for`].
+    pub media_bytes: HashMap<String, Vec<u8>>,
     pub themes: HashMap<String, OfficeStyleSheet>,
+    /// The parts this library has no parser for, see [`Package::unsupported_parts`].
+    pub unsupported_parts: Vec<UnsupportedPart>,
+}
+
+/// A part of a package that this library can't parse, so its content isn't available in the [`Package`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedPart {
+    pub part_name: String,
+    /// The content type of the part, or None if `[Content_Types].xml` doesn't declare one for it.
+    pub content_type: Option<String>,
+    pub kind: PartKind,
+    /// The uncompressed size of the part in bytes.
+    pub size: u64,
+}
+
+/// The numbering of a paragraph that's part of a list, see [`Package::resolve_list_level`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListLevel<'a> {
+    pub numbering_id: i64,
+    /// The zero based level of the paragraph in the list.
+    pub level: i64,
+    pub definition: &'a Lvl,
+}
+
+impl ListLevel<'_> {
+    /// Returns whether the level is marked with bullets instead of numbers.
+    pub fn is_bulleted(&self) -> bool {
+        matches!(
+            self.definition.numbering_format.as_ref().map(|format| format.value),
+            Some(NumberFormat::Bullet)
+        )
+    }
+}
+
+/// The result of opening a package with per-part error isolation. Parts that failed to parse are left out of the
+/// package and their errors are collected, so the rest of the content can still be used.
+#[derive(Debug)]
+pub struct PackageParseReport {
+    pub package: Package,
+    /// The names of the parts that were parsed successfully, in the order of the zip archive.
+    pub parsed_parts: Vec<String>,
+    pub errors: Vec<PartParseError>,
+    /// The repairs and skipped elements of a lenient parse, see [`Package::from_file_lenient`]. Always empty
+    /// otherwise.
+    pub diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl PackageParseReport {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Returns the error of a part, or None if the part was parsed successfully or wasn't present.
+    pub fn error_for<T: AsRef<str>>(&self, part_name: T) -> Option<&PartParseError> {
+        self.errors.iter().find(|error| error.part_name == part_name.as_ref())
+    }
+}
+
+/// The WordprocessingML parts parsed from their xml tree, see [`PackageLoader::load_wml_part`].
+const WML_PART_NAMES: &[&str] = &[
+    "word/document.xml",
+    "word/styles.xml",
+    "word/settings.xml",
+    "word/webSettings.xml",
+    "word/fontTable.xml",
+    "word/footnotes.xml",
+    "word/endnotes.xml",
+    "word/comments.xml",
+    "word/commentsExtended.xml",
+    "word/numbering.xml",
+    "word/glossary/document.xml",
+    "word/glossary/styles.xml",
+    "word/glossary/numbering.xml",
+];
+
+/// The prefix of the content types of the WordprocessingML parts.
+const WML_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.";
+
+/// Registers the parsers of the WordprocessingML parts for the [`PartCache`](crate::shared::partcache::PartCache).
+pub(crate) fn register_part_parsers(parsers: &mut PartParsers) {
+    let content_type = |subtype: &str| format!("{}{}", WML_CONTENT_TYPE, subtype);
+    for main_content_type in [
+        content_type("document.main+xml"),
+        content_type("template.main+xml"),
+        String::from("application/vnd.ms-word.document.macroEnabled.main+xml"),
+        String::from("application/vnd.ms-word.template.macroEnabledTemplate.main+xml"),
+    ] {
+        parsers.register(&main_content_type, Document::from_xml_element);
+    }
+    parsers.register(
+        &content_type("document.glossary+xml"),
+        GlossaryDocument::from_xml_element,
+    );
+    parsers.register(&content_type("styles+xml"), Styles::from_xml_element);
+    parsers.register(&content_type("numbering+xml"), Numbering::from_xml_element);
+    parsers.register(&content_type("settings+xml"), Settings::from_xml_element);
+    parsers.register(&content_type("webSettings+xml"), WebSettings::from_xml_element);
+    parsers.register(&content_type("fontTable+xml"), FontTable::from_xml_element);
+    parsers.register(&content_type("footnotes+xml"), Footnotes::from_xml_element);
+    parsers.register(&content_type("endnotes+xml"), Endnotes::from_xml_element);
+    parsers.register(&content_type("comments+xml"), Comments::from_xml_element);
+    parsers.register(&content_type("commentsExtended+xml"), CommentsEx::from_xml_element);
+}
+
+#[derive(Default)]
+struct PackageLoader {
+    package: Package,
+    custom_xml_items: HashMap<String, XmlNode>,
+    custom_xml_properties: HashMap<String, DataStoreItem>,
+    custom_xml_relationships: HashMap<String, Relationships>,
+    unsupported_parts: Vec<(String, u64)>,
+    document_range: Option<DocumentRange>,
+}
+
+impl PackageLoader {
+    /// Parses a single part into the package. Returns false if the part isn't known by the package.
+    fn load_part<R: Read>(&mut self, path: &str, reader: &mut R, size: u64) -> Result<bool, Box<dyn Error>> {
+        let instance = &mut self.package;
+        match path {
+            CONTENT_TYPES_PART_NAME => {
+                instance.content_types = Some(ContentTypes::from_xml_element(&read_xml_node(reader, size)?)?)
+            }
+            "docProps/app.xml" => instance.app_info = Some(AppInfo::from_xml_element(&read_xml_node(reader, size)?)?),
+            "docProps/core.xml" => instance.core = Some(Core::from_xml_element(&read_xml_node(reader, size)?)?),
+            "word/document.xml" => match &self.document_range {
+                Some(range) => {
+                    let mut xml_string = String::new();
+                    reader.read_to_string(&mut xml_string)?;
+                    instance.main_document = Some(Box::new(Document::from_xml_str_in_range(&xml_string, range)?));
+                }
+                None => {
+                    let xml_node = read_xml_node(reader, size)?;
+                    instance.main_document = Some(Box::new(Document::from_xml_element(&xml_node)?));
+                }
+            },
+            "word/_rels/document.xml.rels" => {
+                instance.main_document_relationships = read_xml_node(reader, size)?
+                    .child_nodes
+                    .iter()
+                    .map(Relationship::from_xml_element)
+                    .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            }
+            "word/_rels/fontTable.xml.rels" => {
+                instance.font_table_relationships = read_xml_node(reader, size)?
+                    .child_nodes
+                    .iter()
+                    .map(Relationship::from_xml_element)
+                    .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            }
+            "word/glossary/_rels/document.xml.rels" => {
+                instance.glossary_document_relationships = read_xml_node(reader, size)?
+                    .child_nodes
+                    .iter()
+                    .map(Relationship::from_xml_element)
+                    .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+            }
+            path if WML_PART_NAMES.contains(&path) => self.load_wml_part(path, &read_xml_node(reader, size)?)?,
+            path if path.starts_with("customXml/_rels/") => {
+                let source_part = source_part_of_rels_path(path).unwrap_or_default();
+                let relationships = Relationships::from_xml_element(source_part, &read_xml_node(reader, size)?)?;
+                self.custom_xml_relationships
+                    .insert(relationships.source_part.clone(), relationships);
+            }
+            path if path.starts_with("customXml/itemProps") => {
+                let xml_node = read_xml_node(reader, size)?;
+                self.custom_xml_properties
+                    .insert(String::from(path), DataStoreItem::from_xml_element(&xml_node)?);
+            }
+            path if path.starts_with("customXml/item") => {
+                self.custom_xml_items
+                    .insert(String::from(path), read_xml_node(reader, size)?);
+            }
+            path if path.starts_with("word/media/") => instance.medias.push(PathBuf::from(path)),
+            path if path.starts_with("word/theme/") => {
+                let file_stem = match Path::new(path).file_stem().and_then(OsStr::to_str).map(String::from) {
+                    Some(name) => name,
+                    None => {
+                        error!("Couldn't get file name of theme");
+                        return Ok(false);
+                    }
+                };
+                let style_sheet = OfficeStyleSheet::from_xml_element(&read_xml_node(reader, size)?)?;
+                instance.themes.insert(file_stem, style_sheet);
+            }
+            _ => {
+                if !path.ends_with('/') {
+                    self.unsupported_parts.push((String::from(path), size));
+                }
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns whether [`PackageLoader::load_part`] reads the content of the part, e.g. to avoid fetching media and
+    /// unsupported parts from a remote storage.
+    #[cfg(feature = "tokio")]
+    fn reads_part(path: &str) -> bool {
+        matches!(
+            path,
+            CONTENT_TYPES_PART_NAME
+                | "docProps/app.xml"
+                | "docProps/core.xml"
+                | "word/_rels/document.xml.rels"
+                | "word/_rels/fontTable.xml.rels"
+                | "word/glossary/_rels/document.xml.rels"
+        ) || WML_PART_NAMES.contains(&path)
+            || path.starts_with("customXml/")
+            || path.starts_with("word/theme/")
+    }
+
+    /// Parses one of [`WML_PART_NAMES`], repairing its xml tree with [`repair_wml_part`] if it fails to parse.
+    fn load_wml_part_leniently(
+        &mut self,
+        path: &str,
+        zip_file: &mut ZipFile<'_>,
+        diagnostics: &mut Vec<ParseDiagnostic>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut xml_node = zip_file_to_xml_node(zip_file)?;
+        if self.load_wml_part(path, &xml_node).is_ok() {
+            return Ok(());
+        }
+
+        diagnostics.extend(repair_wml_part(path, &mut xml_node, |xml_node| {
+            PackageLoader::default().load_wml_part(path, xml_node)
+        }));
+        self.load_wml_part(path, &xml_node)
+    }
+
+    /// Parses one of [`WML_PART_NAMES`] from its xml tree into the package.
+    fn load_wml_part(&mut self, path: &str, xml_node: &XmlNode) -> Result<(), Box<dyn Error>> {
+        let instance = &mut self.package;
+        match path {
+            "word/document.xml" => instance.main_document = Some(Box::new(Document::from_xml_element(xml_node)?)),
+            "word/styles.xml" => instance.styles = Some(Box::new(Styles::from_xml_element(xml_node)?)),
+            "word/settings.xml" => instance.settings = Some(Box::new(Settings::from_xml_element(xml_node)?)),
+            "word/webSettings.xml" => instance.web_settings = Some(WebSettings::from_xml_element(xml_node)?),
+            "word/fontTable.xml" => instance.font_table = Some(FontTable::from_xml_element(xml_node)?),
+            "word/footnotes.xml" => instance.footnotes = Some(Footnotes::from_xml_element(xml_node)?),
+            "word/endnotes.xml" => instance.endnotes = Some(Endnotes::from_xml_element(xml_node)?),
+            "word/comments.xml" => instance.comments = Some(Comments::from_xml_element(xml_node)?),
+            "word/commentsExtended.xml" => instance.comments_extended = Some(CommentsEx::from_xml_element(xml_node)?),
+            "word/numbering.xml" => instance.numbering = Some(Numbering::from_xml_element(xml_node)?),
+            "word/glossary/document.xml" => {
+                instance.glossary_document = Some(Box::new(GlossaryDocument::from_xml_element(xml_node)?))
+            }
+            "word/glossary/styles.xml" => {
+                instance.glossary_styles = Some(Box::new(Styles::from_xml_element(xml_node)?))
+            }
+            "word/glossary/numbering.xml" => instance.glossary_numbering = Some(Numbering::from_xml_element(xml_node)?),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Package {
+        let mut package = self.package;
+        package.custom_xml_parts = pair_custom_xml_parts(
+            self.custom_xml_items,
+            self.custom_xml_properties,
+            &self.custom_xml_relationships,
+        );
+
+        // The content types part isn't necessarily the first one in the archive, so content types are resolved last
+        let content_types = package.content_types.clone().unwrap_or_default();
+        package.unsupported_parts = self
+            .unsupported_parts
+            .into_iter()
+            .map(|(part_name, size)| {
+                let content_type = content_types.content_type_for(&part_name).map(String::from);
+                let kind = content_type
+                    .as_deref()
+                    .map(PartKind::from_content_type)
+                    .unwrap_or(PartKind::Unknown);
+                UnsupportedPart {
+                    part_name,
+                    content_type,
+                    kind,
+                    size,
+                }
+            })
+            .collect();
+        package
+    }
+}
+
+/// The number of parts fetched at a time by [`Package::from_part_source`].
+#[cfg(feature = "tokio")]
+const CONCURRENT_PART_FETCHES: usize = 8;
+
+/// Where the parts fetched by [`Package::from_part_source`] are parsed.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseExecutor {
+    /// Parses the parts on the task awaiting the package, which blocks the executor while parsing.
+    Current,
+    /// Parses the parts on the blocking thread pool of tokio with `spawn_blocking`.
+    Blocking,
+}
+
+#[cfg(feature = "tokio")]
+impl Package {
+    /// Loads a package from an async source of parts, e.g. an object storage. The parts are fetched concurrently and
+    /// parsed on the given executor once all of them are fetched. Media and unsupported parts are only listed, their
+    /// content isn't fetched.
+    ///
+    /// ```no_run
+    /// use oox::{
+    ///     docx::package::{Package, ParseExecutor},
+    ///     shared::partsource::InMemoryPartSource,
+    /// };
+    ///
+    /// # async fn load(data: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let source = InMemoryPartSource::from_zip_bytes(&data)?;
+    /// let package = Package::from_part_source(&source, ParseExecutor::Blocking).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn from_part_source<S: PartSource + ?Sized>(
+        source: &S,
+        executor: ParseExecutor,
+    ) -> Result<Self, AsyncLoadError> {
+        let entries = source
+            .list_parts()
+            .await
+            .map_err(|error| AsyncLoadError::Fetch { part_name: None, error })?;
+        let names: Vec<_> = entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .filter(|name| PackageLoader::reads_part(name))
+            .collect();
+        let mut contents = names
+            .iter()
+            .zip(read_parts(source, &names, CONCURRENT_PART_FETCHES).await);
+
+        let mut parts = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let content = match PackageLoader::reads_part(&entry.name) {
+                true => match contents.next() {
+                    Some((_, Ok(content))) => content,
+                    Some((name, Err(error))) => {
+                        return Err(AsyncLoadError::Fetch {
+                            part_name: Some(String::from(*name)),
+                            error,
+                        })
+                    }
+                    None => Vec::new(),
+                },
+                false => Vec::new(),
+            };
+            parts.push((entry.clone(), content));
+        }
+
+        match executor {
+            ParseExecutor::Current => Self::from_fetched_parts(parts),
+            ParseExecutor::Blocking => tokio::task::spawn_blocking(move || Self::from_fetched_parts(parts))
+                .await
+                .map_err(|error| AsyncLoadError::Join(error.to_string()))?,
+        }
+    }
+
+    fn from_fetched_parts(parts: Vec<(PartEntry, Vec<u8>)>) -> Result<Self, AsyncLoadError> {
+        let mut loader = PackageLoader::default();
+        for (entry, content) in parts {
+            loader
+                .load_part(&entry.name, &mut content.as_slice(), entry.size)
+                .map_err(|error| AsyncLoadError::Parse {
+                    part_name: entry.name,
+                    message: error.to_string(),
+                })?;
+        }
+
+        Ok(loader.finish())
+    }
 }
 
 impl Package {
     pub fn from_file(file_path: &Path) -> Result<Self, Box<dyn Error>> {
-        let file = File::open(file_path)?;
+        Package::from_file_with_document_range(file_path, None)
+    }
+
+    pub(crate) fn from_file_with_document_range(
+        file_path: &Path,
+        document_range: Option<DocumentRange>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut file = File::open(file_path)?;
+        ensure_zip_package(&mut file)?;
+        let mut zipper = ZipArchive::new(&file)?;
+
+        let mut loader = PackageLoader {
+            document_range,
+            ..Default::default()
+        };
+        loader.package.file_path = PathBuf::from(file_path);
+        for idx in 0..zipper.len() {
+            let mut zip_file = zipper.by_index(idx)?;
+            let path = String::from(zip_file.name());
+            let size = zip_file.size();
+            loader.load_part(&path, &mut zip_file, size)?;
+        }
+
+        Ok(loader.finish())
+    }
+
+    /// Opens a Flat OPC document, which stores the parts of the package in a single xml file, e.g. a document saved as
+    /// "Word XML Document" by Word. See [`Package::from_flat_opc`].
+    pub fn from_flat_opc_file(file_path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut package = Self::from_flat_opc(&std::fs::read(file_path)?)?;
+        package.file_path = PathBuf::from(file_path);
+        Ok(package)
+    }
+
+    /// Parses a Flat OPC document into the same model as a zip package. The content types are taken from the parts,
+    /// as Flat OPC documents have no content types part. Media parts are listed in [`Package::medias`] and their
+    /// decoded content is kept in [`Package::media_bytes`].
+    ///
+    /// ```
+    /// use oox::docx::package::Package;
+    ///
+    /// let xml = r#"<pkg:package xmlns:pkg="http://schemas.microsoft.com/office/2006/xmlPackage">
+    ///     <pkg:part pkg:name="/word/document.xml"
+    ///         pkg:contentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml">
+    ///         <pkg:xmlData>
+    ///             <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+    ///                 <w:body><w:p><w:r><w:t>Hello</w:t></w:r></w:p></w:body>
+    ///             </w:document>
+    ///         </pkg:xmlData>
+    ///     </pkg:part>
+    /// </pkg:package>"#;
+    /// let package = Package::from_flat_opc(xml.as_bytes()).unwrap();
+    /// assert_eq!(package.main_document.unwrap().paragraphs().count(), 1);
+    /// ```
+    pub fn from_flat_opc(xml: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let parts = read_flat_opc_parts(&XmlNode::from_bytes(xml)?)?;
+
+        let mut loader = PackageLoader::default();
+        for part in &parts {
+            loader.load_part(&part.name, &mut part.content.as_slice(), part.content.len() as u64)?;
+        }
+        loader.package.content_types = Some(flat_opc_content_types(&parts));
+
+        let mut package = loader.finish();
+        for part in parts {
+            if package.medias.iter().any(|media| media.as_os_str() == part.name.as_str()) {
+                package.media_bytes.insert(part.name, part.content);
+            }
+        }
+
+        Ok(package)
+    }
+
+    /// Opens a package, isolating the failures of the individual parts. A part that can't be parsed is skipped and
+    /// its error is recorded in the report, e.g. a malformed `word/footnotes.xml` only leaves the footnotes empty.
+    /// Errors that make the whole archive unreadable are still returned as errors.
+    ///
+    /// ```no_run
+    /// use oox::docx::package::Package;
+    /// use std::path::Path;
+    ///
+    /// let report = Package::from_file_with_report(Path::new("document.docx")).unwrap();
+    /// for error in &report.errors {
+    ///     eprintln!("{}", error);
+    /// }
+    /// let package = report.package;
+    /// ```
+    pub fn from_file_with_report(file_path: &Path) -> Result<PackageParseReport, Box<dyn Error>> {
+        Self::from_file_isolated(file_path, false)
+    }
+
+    /// Opens a package like [`Package::from_file_with_report`], but tries to recover the WordprocessingML parts that
+    /// fail to parse, e.g. because of an invalid enum value written by a third-party tool. The invalid attributes and
+    /// elements are dropped according to the schema rules and the innermost elements that still fail to parse are
+    /// skipped. Every repair is listed in the diagnostics of the report.
+    ///
+    /// Lenient parsing is only supported for WordprocessingML packages, presentations opened with
+    /// `pptx::package::Package::from_file` still fail on the first part that doesn't parse.
+    ///
+    /// ```no_run
+    /// use oox::docx::package::Package;
+    /// use std::path::Path;
+    ///
+    /// let report = Package::from_file_lenient(Path::new("document.docx")).unwrap();
+    /// for diagnostic in &report.diagnostics {
+    ///     eprintln!("warning: {}", diagnostic);
+    /// }
+    /// ```
+    pub fn from_file_lenient(file_path: &Path) -> Result<PackageParseReport, Box<dyn Error>> {
+        Self::from_file_isolated(file_path, true)
+    }
+
+    fn from_file_isolated(file_path: &Path, lenient: bool) -> Result<PackageParseReport, Box<dyn Error>> {
+        let mut file = File::open(file_path)?;
+        ensure_zip_package(&mut file)?;
         let mut zipper = ZipArchive::new(&file)?;
 
-        let mut instance: Self = Default::default();
+        let mut loader = PackageLoader::default();
+        loader.package.file_path = PathBuf::from(file_path);
+        let mut parsed_parts = Vec::new();
+        let mut errors = Vec::new();
+        let mut diagnostics = Vec::new();
         for idx in 0..zipper.len() {
             let mut zip_file = zipper.by_index(idx)?;
+            let path = String::from(zip_file.name());
+            let result = if lenient && WML_PART_NAMES.contains(&path.as_str()) {
+                loader
+                    .load_wml_part_leniently(&path, &mut zip_file, &mut diagnostics)
+                    .map(|_| true)
+            } else {
+                let size = zip_file.size();
+                loader.load_part(&path, &mut zip_file, size)
+            };
 
-            match zip_file.name() {
-                "docProps/app.xml" => instance.app_info = Some(AppInfo::from_zip_file(&mut zip_file)?),
-                "docProps/core.xml" => instance.core = Some(Core::from_zip_file(&mut zip_file)?),
-                "word/document.xml" => {
-                    let xml_node = zip_file_to_xml_node(&mut zip_file)?;
-                    instance.main_document = Some(Box::new(Document::from_xml_element(&xml_node)?));
-                }
-                "word/_rels/document.xml.rels" => {
-                    instance.main_document_relationships = zip_file_to_xml_node(&mut zip_file)?
-                        .child_nodes
-                        .iter()
-                        .map(Relationship::from_xml_element)
-                        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
-                }
-                "word/styles.xml" => {
-                    let xml_node = zip_file_to_xml_node(&mut zip_file)?;
-                    instance.styles = Some(Box::new(Styles::from_xml_element(&xml_node)?));
-                }
-                "word/settings.xml" => {
-                    let xml_node = zip_file_to_xml_node(&mut zip_file)?;
-                    instance.settings = Some(Box::new(Settings::from_xml_element(&xml_node)?));
-                }
-                "word/footnotes.xml" => {
-                    let xml_node = zip_file_to_xml_node(&mut zip_file)?;
-                    instance.footnotes = Some(Footnotes::from_xml_element(&xml_node)?);
-                }
-                "word/numbering.xml" => {
-                    let xml_node = zip_file_to_xml_node(&mut zip_file)?;
-                    instance.numbering = Some(Numbering::from_xml_element(&xml_node)?);
+            match result {
+                Ok(true) => parsed_parts.push(path),
+                Ok(false) => (),
+                Err(error) => {
+                    error!("Failed to parse part {}: {}", path, error);
+                    errors.push(PartParseError { part_name: path, error });
                 }
-                path if path.starts_with("word/media/") => instance.medias.push(PathBuf::from(file_path)),
-                path if path.starts_with("word/theme/") => {
-                    let file_stem = match Path::new(path).file_stem().and_then(OsStr::to_str).map(String::from) {
-                        Some(name) => name,
-                        None => {
-                            error!("Couldn't get file name of theme");
-                            continue;
-                        }
-                    };
-                    let style_sheet = OfficeStyleSheet::from_xml_element(&zip_file_to_xml_node(&mut zip_file)?)?;
-                    instance.themes.insert(file_stem, style_sheet);
-                }
-                _ => (),
             }
         }
 
-        Ok(instance)
+        Ok(PackageParseReport {
+            package: loader.finish(),
+            parsed_parts,
+            errors,
+            diagnostics,
+        })
+    }
+
+    /// Returns the parts of the package that have no parser in this library, with their content type and size, in
+    /// the order of the zip archive. Relationship parts of unsupported parts are listed as well.
+    ///
+    /// ```no_run
+    /// use oox::docx::package::Package;
+    /// use std::path::Path;
+    ///
+    /// let package = Package::from_file(Path::new("document.docx")).unwrap();
+    /// for part in package.unsupported_parts() {
+    ///     println!("{} ({:?}, {} bytes)", part.part_name, part.content_type, part.size);
+    /// }
+    /// ```
+    pub fn unsupported_parts(&self) -> &[UnsupportedPart] {
+        &self.unsupported_parts
+    }
+
+    pub fn find_custom_xml_part<T: AsRef<str>>(&self, store_item_id: T) -> Option<&CustomXmlPart> {
+        self.custom_xml_parts
+            .iter()
+            .find(|part| part.has_item_id(store_item_id.as_ref()))
+    }
+
+    /// Returns the value of the custom XML data node the data binding of a content control points to.
+    pub fn resolve_data_binding(&self, data_binding: &DataBinding) -> Option<String> {
+        self.find_custom_xml_part(&data_binding.store_item_id)?
+            .evaluate_xpath(&data_binding.xpath)
     }
 
     pub fn resolve_document_default_style(&self) -> Option<ResolvedStyle> {
@@ -161,7 +698,7 @@ impl Package {
         })
     }
 
-    fn resolve_style_with_id<T: AsRef<str>>(&self, style_id: T) -> Option<ResolvedStyle> {
+    pub(crate) fn resolve_style_with_id<T: AsRef<str>>(&self, style_id: T) -> Option<ResolvedStyle> {
         // TODO(kalmar.robert) Use caching
         let styles = &self.styles.as_ref()?.styles;
 
@@ -239,6 +776,19 @@ impl Package {
         })
     }
 
+    /// Returns the effective run properties of a run, see [`Package::resolve_style_inheritance`]. Falls back to the
+    /// direct formatting of the run if the package has no styles.
+    pub fn resolve_run_properties(&self, paragraph: &P, run: &R) -> RunProperties {
+        match self.resolve_style_inheritance(paragraph, run) {
+            Some(style) => *style.run_properties,
+            None => run
+                .run_properties
+                .as_ref()
+                .map(|r_pr| RunProperties::from_vec(&r_pr.r_pr_bases))
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn get_main_document_theme(&self) -> Option<&OfficeStyleSheet> {
         let theme_relation = self
             .main_document_relationships
@@ -252,6 +802,18 @@ impl Package {
         self.themes.get(rel_target_file)
     }
 
+    /// Resolves a theme color with the optional `w:themeTint` and `w:themeShade` modifiers using the main document's
+    /// theme.
+    pub fn resolve_theme_color(
+        &self,
+        theme_color: ThemeColor,
+        tint: Option<u8>,
+        shade: Option<u8>,
+    ) -> Option<HexColorRGB> {
+        self.get_main_document_theme()?
+            .resolve_color(theme_color.to_scheme_color_val()?, tint, shade)
+    }
+
     pub fn get_main_document_section_properties(&self) -> Option<&SectPrContents> {
         self.main_document
             .as_ref()?
@@ -263,8 +825,22 @@ impl Package {
             .as_ref()
     }
 
+    /// Returns the target of the relationship of an embedded object in the main document, e.g. the embedded package
+    /// part or the path of a linked file.
+    pub fn resolve_embedded_object_target(&self, info: &EmbeddedObjectInfo) -> Option<&str> {
+        let rel_id = info.rel_id.as_ref()?;
+        self.main_document_relationships
+            .iter()
+            .find(|relationship| &relationship.id == rel_id)
+            .map(|relationship| relationship.target.as_str())
+    }
+
     pub fn find_footnote_with_id(&self, id: i64) -> Option<&FtnEdn> {
-        self.footnotes.as_ref()?.0.iter().find(|ftn_edn| ftn_edn.id == id)
+        self.footnotes.as_ref()?.note(id)
+    }
+
+    pub fn find_endnote_with_id(&self, id: i64) -> Option<&FtnEdn> {
+        self.endnotes.as_ref()?.note(id)
     }
 
     pub fn resolve_footnote_style(&self, footnote_type: FtnEdnType) -> Option<ResolvedStyle> {
@@ -313,6 +889,10 @@ impl Package {
             })
     }
 
+    pub fn find_building_block<T: AsRef<str>>(&self, name: T) -> Option<&DocPart> {
+        self.glossary_document.as_ref()?.find_doc_part_by_name(name)
+    }
+
     pub fn find_numbering_level(&self, numbering_id: i64, level: i64) -> Option<&Lvl> {
         if !(0..=8).contains(&level) {
             return None;
@@ -331,6 +911,63 @@ impl Package {
         abstract_num.levels.iter().find(|lvl| lvl.level == level)
     }
 
+    /// Resolves the list level of a paragraph from its direct numbering properties and the ones of its paragraph
+    /// style. Returns `None` if the paragraph isn't part of a list, e.g. when its numbering is removed with the `0`
+    /// numbering id.
+    pub fn resolve_list_level(&self, paragraph: &P) -> Option<ListLevel<'_>> {
+        let direct = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.base.numbering_properties.as_ref());
+        let style = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| self.resolve_paragraph_style(properties))
+            .and_then(|style| style.paragraph_properties.numbering_properties);
+
+        let numbering_id = direct
+            .and_then(|numbering| numbering.numbering_id)
+            .or_else(|| style.as_ref().and_then(|numbering| numbering.numbering_id))
+            .filter(|numbering_id| *numbering_id != 0)?;
+        let level = direct
+            .and_then(|numbering| numbering.indent_level)
+            .or_else(|| style.as_ref().and_then(|numbering| numbering.indent_level))
+            .unwrap_or(0);
+
+        Some(ListLevel {
+            numbering_id,
+            level,
+            definition: self.find_numbering_level(numbering_id, level)?,
+        })
+    }
+
+    /// Resolves the outline level of a paragraph from its direct properties or its paragraph style. Returns the zero
+    /// based level of headings, `None` for body text, i.e. paragraphs without an outline level or with level 9.
+    pub fn resolve_outline_level(&self, paragraph: &P) -> Option<i64> {
+        let properties = paragraph.properties.as_ref();
+        properties
+            .and_then(|properties| properties.base.outline_level)
+            .or_else(|| {
+                properties
+                    .and_then(|properties| self.resolve_paragraph_style(properties))
+                    .and_then(|style| style.paragraph_properties.outline_level)
+            })
+            .filter(|level| (0..9).contains(level))
+    }
+
+    /// Resolves the HTML div of a paragraph from its direct properties or its paragraph style, see
+    /// [`WebSettings::div`].
+    pub fn resolve_div(&self, paragraph: &P) -> Option<&Div> {
+        let properties = paragraph.properties.as_ref();
+        let div_id = properties.and_then(|properties| properties.base.div_id).or_else(|| {
+            properties
+                .and_then(|properties| self.resolve_paragraph_style(properties))
+                .and_then(|style| style.paragraph_properties.div_id)
+        })?;
+
+        self.web_settings.as_ref()?.div(div_id)
+    }
+
     pub fn resolve_numbering_level_style(numbering_level: &Lvl) -> ResolvedStyle {
         let paragraph_properties = Box::new(
             numbering_level
@@ -648,4 +1285,124 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    pub fn test_from_flat_opc() {
+        let xml = r#"<?xml version="1.0" standalone="yes"?>
+        <?mso-application progid="Word.Document"?>
+        <pkg:package xmlns:pkg="http://schemas.microsoft.com/office/2006/xmlPackage">
+            <pkg:part pkg:name="/_rels/.rels"
+                pkg:contentType="application/vnd.openxmlformats-package.relationships+xml">
+                <pkg:xmlData>
+                    <Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                        <Relationship Id="rId1" Target="word/document.xml"
+                            Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" />
+                    </Relationships>
+                </pkg:xmlData>
+            </pkg:part>
+            <pkg:part pkg:name="/word/document.xml"
+                pkg:contentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml">
+                <pkg:xmlData>
+                    <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                        <w:body>
+                            <w:p><w:pPr><w:pStyle w:val="Title" /></w:pPr><w:r><w:t>A &amp; B</w:t></w:r></w:p>
+                        </w:body>
+                    </w:document>
+                </pkg:xmlData>
+            </pkg:part>
+            <pkg:part pkg:name="/word/_rels/document.xml.rels"
+                pkg:contentType="application/vnd.openxmlformats-package.relationships+xml">
+                <pkg:xmlData>
+                    <Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                        <Relationship Id="rId1" Target="media/image1.png"
+                            Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" />
+                    </Relationships>
+                </pkg:xmlData>
+            </pkg:part>
+            <pkg:part pkg:name="/word/styles.xml"
+                pkg:contentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml">
+                <pkg:xmlData>
+                    <w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                        <w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title" /></w:style>
+                    </w:styles>
+                </pkg:xmlData>
+            </pkg:part>
+            <pkg:part pkg:name="/word/media/image1.png" pkg:contentType="image/png" pkg:compression="store">
+                <pkg:binaryData>iVBORw0KGgo=</pkg:binaryData>
+            </pkg:part>
+            <pkg:part pkg:name="/word/vbaData.xml" pkg:contentType="application/vnd.ms-word.vbaData+xml">
+                <pkg:xmlData>
+                    <wne:vbaSuppData xmlns:wne="http://schemas.microsoft.com/office/word/2006/wordml" />
+                </pkg:xmlData>
+            </pkg:part>
+        </pkg:package>"#;
+
+        let package = Package::from_flat_opc(xml.as_bytes()).unwrap();
+        let (_, paragraph) = package.main_document.as_ref().unwrap().paragraphs().next().unwrap();
+        assert!(package
+            .resolve_paragraph_style(paragraph.properties.as_ref().unwrap())
+            .is_some());
+        assert_eq!(package.medias, vec![std::path::PathBuf::from("word/media/image1.png")]);
+        let media = package.media_for("rId1").unwrap().unwrap();
+        assert_eq!(media.bytes, b"\x89PNG\r\n\x1a\n");
+        assert_eq!(media.content_type.as_deref(), Some("image/png"));
+
+        let unsupported_parts: Vec<_> = package
+            .unsupported_parts()
+            .iter()
+            .map(|part| (part.part_name.as_str(), part.content_type.as_deref()))
+            .collect();
+        assert_eq!(
+            unsupported_parts,
+            vec![
+                (
+                    "_rels/.rels",
+                    Some("application/vnd.openxmlformats-package.relationships+xml")
+                ),
+                ("word/vbaData.xml", Some("application/vnd.ms-word.vbaData+xml")),
+            ]
+        );
+
+        assert!(Package::from_flat_opc(b"<w:document />").is_err());
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    pub fn test_from_part_source() {
+        use super::ParseExecutor;
+        use crate::{error::AsyncLoadError, shared::partsource::InMemoryPartSource};
+        use std::path::PathBuf;
+
+        let part = |name: &str, content: &str| (String::from(name), Vec::from(content.as_bytes()));
+        let mut source = InMemoryPartSource {
+            parts: vec![
+                part(
+                    "word/document.xml",
+                    r#"<w:document><w:body><w:p><w:r><w:t>Hello</w:t></w:r></w:p></w:body></w:document>"#,
+                ),
+                part(
+                    "docProps/app.xml",
+                    "<Properties><Application>Word</Application></Properties>",
+                ),
+                part("word/media/image1.png", "not an image"),
+            ],
+        };
+
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        for executor in [ParseExecutor::Current, ParseExecutor::Blocking] {
+            let package = runtime.block_on(Package::from_part_source(&source, executor)).unwrap();
+            assert!(package.main_document.is_some());
+            assert_eq!(package.app_info.unwrap().app_name.as_deref(), Some("Word"));
+            assert_eq!(package.medias, vec![PathBuf::from("word/media/image1.png")]);
+        }
+
+        source.parts.push(part(
+            "word/styles.xml",
+            "<w:styles><w:style w:type=\"invalid\" /></w:styles>",
+        ));
+        match runtime.block_on(Package::from_part_source(&source, ParseExecutor::Blocking)) {
+            Err(AsyncLoadError::Parse { part_name, .. }) => assert_eq!(part_name, "word/styles.xml"),
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        }
+    }
 }
@@ -0,0 +1,195 @@
+use super::wml::document::{Columns, PageMar, PageOrientation, PageSz, SectPr, SignedTwipsMeasure};
+use crate::shared::sharedtypes::TwipsMeasure;
+
+/// The distance between the page edge and the header or footer used by every preset, in twips.
+pub const HEADER_FOOTER_DISTANCE: u64 = 720;
+/// The spacing between columns used by every preset, in twips.
+pub const COLUMN_SPACING: u64 = 720;
+
+/// Paper sizes supported by the page setup presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaperSize {
+    /// 210 × 297 mm
+    A4,
+    /// 8.5 × 11 in
+    Letter,
+}
+
+impl PaperSize {
+    /// Returns the width and height of the paper in portrait orientation, in twips.
+    pub fn portrait_dimensions(self) -> (u64, u64) {
+        match self {
+            PaperSize::A4 => (11_906, 16_838),
+            PaperSize::Letter => (12_240, 15_840),
+        }
+    }
+}
+
+/// Page margin presets matching the ones offered by Word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarginPreset {
+    /// 1 inch on every side.
+    Normal,
+    /// 0.5 inch on every side.
+    Narrow,
+}
+
+impl MarginPreset {
+    /// Returns the margin of every side of the page, in twips.
+    pub fn margin(self) -> u64 {
+        match self {
+            MarginPreset::Normal => 1440,
+            MarginPreset::Narrow => 720,
+        }
+    }
+}
+
+/// A combination of paper size, orientation and margins producing the page size, margin and column properties of a
+/// section.
+///
+/// ```
+/// use oox::docx::{pagesetup::PageSetup, wml::document::SectPr};
+/// use oox::shared::sharedtypes::TwipsMeasure;
+///
+/// let section = SectPr::default().with_page_setup(PageSetup::A4_LANDSCAPE_NARROW);
+/// let page_size = section.contents.unwrap().page_size.unwrap();
+/// assert_eq!(page_size.width, Some(TwipsMeasure::Decimal(16_838)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageSetup {
+    pub paper_size: PaperSize,
+    pub orientation: PageOrientation,
+    pub margins: MarginPreset,
+}
+
+impl PageSetup {
+    pub const A4_PORTRAIT: Self = Self::new(PaperSize::A4, PageOrientation::Portrait, MarginPreset::Normal);
+    pub const A4_PORTRAIT_NARROW: Self = Self::new(PaperSize::A4, PageOrientation::Portrait, MarginPreset::Narrow);
+    pub const A4_LANDSCAPE: Self = Self::new(PaperSize::A4, PageOrientation::Landscape, MarginPreset::Normal);
+    pub const A4_LANDSCAPE_NARROW: Self = Self::new(PaperSize::A4, PageOrientation::Landscape, MarginPreset::Narrow);
+    pub const LETTER_PORTRAIT: Self = Self::new(PaperSize::Letter, PageOrientation::Portrait, MarginPreset::Normal);
+    pub const LETTER_PORTRAIT_NARROW: Self =
+        Self::new(PaperSize::Letter, PageOrientation::Portrait, MarginPreset::Narrow);
+    pub const LETTER_LANDSCAPE: Self = Self::new(PaperSize::Letter, PageOrientation::Landscape, MarginPreset::Normal);
+    pub const LETTER_LANDSCAPE_NARROW: Self =
+        Self::new(PaperSize::Letter, PageOrientation::Landscape, MarginPreset::Narrow);
+
+    pub const fn new(paper_size: PaperSize, orientation: PageOrientation, margins: MarginPreset) -> Self {
+        Self {
+            paper_size,
+            orientation,
+            margins,
+        }
+    }
+
+    /// Returns the page size. The width and height are swapped for landscape orientation and, like Word, the
+    /// orientation is only written for landscape pages.
+    pub fn page_size(&self) -> PageSz {
+        let (width, height) = self.paper_size.portrait_dimensions();
+        let (width, height, orientation) = match self.orientation {
+            PageOrientation::Portrait => (width, height, None),
+            PageOrientation::Landscape => (height, width, Some(PageOrientation::Landscape)),
+        };
+
+        PageSz {
+            width: Some(TwipsMeasure::Decimal(width)),
+            height: Some(TwipsMeasure::Decimal(height)),
+            orientation,
+            code: None,
+        }
+    }
+
+    pub fn page_margin(&self) -> PageMar {
+        let margin = self.margins.margin();
+
+        PageMar {
+            top: SignedTwipsMeasure::Decimal(margin as i32),
+            right: TwipsMeasure::Decimal(margin),
+            bottom: SignedTwipsMeasure::Decimal(margin as i32),
+            left: TwipsMeasure::Decimal(margin),
+            header: TwipsMeasure::Decimal(HEADER_FOOTER_DISTANCE),
+            footer: TwipsMeasure::Decimal(HEADER_FOOTER_DISTANCE),
+            gutter: TwipsMeasure::Decimal(0),
+        }
+    }
+
+    /// Returns a single column layout.
+    pub fn columns(&self) -> Columns {
+        Columns {
+            spacing: Some(TwipsMeasure::Decimal(COLUMN_SPACING)),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the width available for text between the left and right margins, in twips.
+    pub fn text_width(&self) -> u64 {
+        let (width, height) = self.paper_size.portrait_dimensions();
+        let page_width = match self.orientation {
+            PageOrientation::Portrait => width,
+            PageOrientation::Landscape => height,
+        };
+
+        page_width - 2 * self.margins.margin()
+    }
+}
+
+impl SectPr {
+    /// Replaces the page size, page margins and columns of the section with the ones of a page setup preset. Other
+    /// section properties are kept.
+    pub fn with_page_setup(mut self, page_setup: PageSetup) -> Self {
+        let contents = self.contents.get_or_insert_with(Default::default);
+        contents.page_size = Some(page_setup.page_size());
+        contents.page_margin = Some(page_setup.page_margin());
+        contents.columns = Some(page_setup.columns());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::wml::document::SectPrContents;
+
+    #[test]
+    pub fn test_page_setup_presets() {
+        let page_size = PageSetup::LETTER_PORTRAIT.page_size();
+        assert_eq!(page_size.width, Some(TwipsMeasure::Decimal(12_240)));
+        assert_eq!(page_size.height, Some(TwipsMeasure::Decimal(15_840)));
+        assert_eq!(page_size.orientation, None);
+
+        let page_size = PageSetup::LETTER_LANDSCAPE.page_size();
+        assert_eq!(page_size.width, Some(TwipsMeasure::Decimal(15_840)));
+        assert_eq!(page_size.height, Some(TwipsMeasure::Decimal(12_240)));
+        assert_eq!(page_size.orientation, Some(PageOrientation::Landscape));
+
+        let page_margin = PageSetup::A4_PORTRAIT_NARROW.page_margin();
+        assert_eq!(page_margin.top, SignedTwipsMeasure::Decimal(720));
+        assert_eq!(page_margin.left, TwipsMeasure::Decimal(720));
+        assert_eq!(page_margin.header, TwipsMeasure::Decimal(720));
+        assert_eq!(page_margin.gutter, TwipsMeasure::Decimal(0));
+
+        assert_eq!(PageSetup::A4_PORTRAIT.text_width(), 9026);
+        assert_eq!(PageSetup::LETTER_LANDSCAPE_NARROW.text_width(), 14_400);
+    }
+
+    #[test]
+    pub fn test_sect_pr_with_page_setup() {
+        let section = SectPr {
+            contents: Some(SectPrContents {
+                title_page: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+        .with_page_setup(PageSetup::A4_LANDSCAPE);
+
+        let contents = section.contents.unwrap();
+        assert_eq!(contents.title_page, Some(true));
+        assert_eq!(contents.page_size, Some(PageSetup::A4_LANDSCAPE.page_size()));
+        assert_eq!(contents.page_margin, Some(PageSetup::A4_LANDSCAPE.page_margin()));
+        assert_eq!(
+            contents.columns.unwrap().spacing,
+            Some(TwipsMeasure::Decimal(COLUMN_SPACING))
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use super::wml::{
+    document::{BlockLevelElts, ContentBlockContent, Document, P},
+    simpletypes::LongHexNumber,
+    table::{ContentCellContent, ContentRowContent, Tbl},
+};
+use std::collections::HashSet;
+
+/// Paragraph and text ids must be less than this value.
+pub const MAX_PARAGRAPH_ID: LongHexNumber = 0x8000_0000;
+
+/// Generates `w14:paraId` and `w14:textId` values that don't collide with the ones already used by a document.
+///
+/// ```
+/// use oox::docx::{paraid::ParagraphIdGenerator, wml::document::P};
+///
+/// let mut generator = ParagraphIdGenerator::default();
+/// let mut paragraph = P::default();
+/// generator.assign(&mut paragraph);
+///
+/// let paragraph_id = paragraph.paragraph_id.unwrap();
+/// assert!(paragraph_id > 0 && paragraph_id < 0x8000_0000);
+/// assert_ne!(generator.next_id(), paragraph_id);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParagraphIdGenerator {
+    used: HashSet<LongHexNumber>,
+    state: u32,
+}
+
+impl Default for ParagraphIdGenerator {
+    fn default() -> Self {
+        Self {
+            used: HashSet::new(),
+            state: 0x2545_f491,
+        }
+    }
+}
+
+impl ParagraphIdGenerator {
+    /// Creates a generator avoiding the paragraph ids of every paragraph and table row of the document.
+    pub fn from_document(document: &Document) -> Self {
+        let mut instance: Self = Default::default();
+        instance.used.extend(document.paragraph_ids());
+        instance
+    }
+
+    /// Marks an id as used so it's never generated.
+    pub fn reserve(&mut self, id: LongHexNumber) {
+        self.used.insert(id);
+    }
+
+    /// Returns a new unique id in `1..MAX_PARAGRAPH_ID`.
+    pub fn next_id(&mut self) -> LongHexNumber {
+        loop {
+            // xorshift32
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 17;
+            self.state ^= self.state << 5;
+
+            let id = self.state % MAX_PARAGRAPH_ID;
+            if id != 0 && self.used.insert(id) {
+                return id;
+            }
+        }
+    }
+
+    /// Assigns new ids to a paragraph that doesn't have them yet.
+    pub fn assign(&mut self, paragraph: &mut P) {
+        assign_ids(self, &mut paragraph.paragraph_id, &mut paragraph.text_id);
+    }
+}
+
+impl Document {
+    /// Returns the `w14:paraId` of every paragraph and table row of the body in document order.
+    pub fn paragraph_ids(&self) -> Vec<LongHexNumber> {
+        let mut ids = Vec::new();
+        for element in self.body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            if let BlockLevelElts::Chunk(content) = element {
+                content_block_ids(content, &mut ids);
+            }
+        }
+
+        ids
+    }
+
+    /// Assigns unique ids to every paragraph and table row of the body that doesn't have one. Returns the number of
+    /// elements that got a new paragraph id.
+    pub fn assign_paragraph_ids(&mut self) -> usize {
+        let mut generator = ParagraphIdGenerator::from_document(self);
+        let previous_count = self.paragraph_ids().len();
+
+        for element in self
+            .body
+            .iter_mut()
+            .flat_map(|body| body.block_level_elements.iter_mut())
+        {
+            if let BlockLevelElts::Chunk(content) = element {
+                assign_content_block_ids(content, &mut generator);
+            }
+        }
+
+        self.paragraph_ids().len() - previous_count
+    }
+}
+
+fn assign_ids(
+    generator: &mut ParagraphIdGenerator,
+    paragraph_id: &mut Option<LongHexNumber>,
+    text_id: &mut Option<LongHexNumber>,
+) {
+    if paragraph_id.is_none() {
+        *paragraph_id = Some(generator.next_id());
+    }
+
+    if text_id.is_none() {
+        *text_id = Some(generator.next_id());
+    }
+}
+
+fn content_block_ids(content: &ContentBlockContent, ids: &mut Vec<LongHexNumber>) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.block_contents {
+                content_block_ids(content, ids);
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                content_block_ids(content, ids);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => ids.extend(paragraph.paragraph_id),
+        ContentBlockContent::Table(table) => table_ids(table, ids),
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn table_ids(table: &Tbl, ids: &mut Vec<LongHexNumber>) {
+    for row_content in &table.row_contents {
+        row_ids(row_content, ids);
+    }
+}
+
+fn row_ids(content: &ContentRowContent, ids: &mut Vec<LongHexNumber>) {
+    match content {
+        ContentRowContent::Table(row) => {
+            ids.extend(row.paragraph_id);
+            for cell_content in &row.contents {
+                cell_ids(cell_content, ids);
+            }
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                row_ids(content, ids);
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                row_ids(content, ids);
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+fn cell_ids(content: &ContentCellContent, ids: &mut Vec<LongHexNumber>) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            for element in &cell.block_level_elements {
+                if let BlockLevelElts::Chunk(content) = element {
+                    content_block_ids(content, ids);
+                }
+            }
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                cell_ids(content, ids);
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                cell_ids(content, ids);
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+fn assign_content_block_ids(content: &mut ContentBlockContent, generator: &mut ParagraphIdGenerator) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.block_contents {
+                assign_content_block_ids(content, generator);
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            for content in sdt
+                .sdt_content
+                .iter_mut()
+                .flat_map(|content| content.block_contents.iter_mut())
+            {
+                assign_content_block_ids(content, generator);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => generator.assign(paragraph),
+        ContentBlockContent::Table(table) => {
+            for row_content in &mut table.row_contents {
+                assign_row_ids(row_content, generator);
+            }
+        }
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn assign_row_ids(content: &mut ContentRowContent, generator: &mut ParagraphIdGenerator) {
+    match content {
+        ContentRowContent::Table(row) => {
+            assign_ids(generator, &mut row.paragraph_id, &mut row.text_id);
+            for cell_content in &mut row.contents {
+                assign_cell_ids(cell_content, generator);
+            }
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.contents {
+                assign_row_ids(content, generator);
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            for content in sdt.content.iter_mut().flat_map(|content| content.contents.iter_mut()) {
+                assign_row_ids(content, generator);
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+fn assign_cell_ids(content: &mut ContentCellContent, generator: &mut ParagraphIdGenerator) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            for element in &mut cell.block_level_elements {
+                if let BlockLevelElts::Chunk(content) = element {
+                    assign_content_block_ids(content, generator);
+                }
+            }
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.contents {
+                assign_cell_ids(content, generator);
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            for content in sdt.content.iter_mut().flat_map(|content| content.contents.iter_mut()) {
+                assign_cell_ids(content, generator);
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_assign_paragraph_ids() {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p w14:paraId="00000001" w14:textId="77777777" />
+                <w:p />
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid />
+                    <w:tr>
+                        <w:tc><w:p /></w:tc>
+                    </w:tr>
+                </w:tbl>
+            </w:body>
+        </w:document>"#;
+
+        let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(document.paragraph_ids(), vec![0x1]);
+
+        assert_eq!(document.assign_paragraph_ids(), 3);
+        let ids = document.paragraph_ids();
+        assert_eq!(ids.len(), 4);
+        assert_eq!(ids[0], 0x1);
+        assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 4);
+        assert!(ids.iter().all(|id| *id > 0 && *id < MAX_PARAGRAPH_ID));
+
+        assert_eq!(document.assign_paragraph_ids(), 0);
+    }
+}
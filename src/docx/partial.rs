@@ -0,0 +1,119 @@
+use super::{package::Package, wml::document::Document};
+use crate::xml::{ElementContext, XmlNode};
+use std::{error::Error, ops::Range, path::Path};
+
+/// Selects the body content parsed by [`Document::from_xml_str_in_range`]. Content outside the range is skipped by
+/// the xml parser, so neither xml nodes nor model objects are built for it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentRange {
+    /// The paragraphs of the body with their index in the range. Only the paragraphs directly in the body are
+    /// counted, other block level elements like tables are parsed if the number of paragraphs preceding them is in
+    /// the range.
+    Paragraphs(Range<usize>),
+    /// The block level elements of the body starting in the byte range of the document part.
+    Bytes(Range<usize>),
+}
+
+impl DocumentRange {
+    fn filter(&self) -> impl FnMut(&ElementContext<'_>) -> bool + '_ {
+        let mut paragraph_count = 0;
+        move |context| {
+            let is_body_child = context.ancestors.len() == 2 && context.ancestors[1] == "w:body";
+            // The section properties of the last section are always parsed, like the page setup of a preview.
+            if !is_body_child || &*context.node.name == "w:sectPr" {
+                return true;
+            }
+
+            match self {
+                DocumentRange::Paragraphs(range) => {
+                    let index = paragraph_count;
+                    if &*context.node.name == "w:p" {
+                        paragraph_count += 1;
+                    }
+                    range.contains(&index)
+                }
+                DocumentRange::Bytes(range) => range.contains(&context.offset),
+            }
+        }
+    }
+}
+
+impl Document {
+    /// Parses the body content of a main document part in the range only, e.g. to preview the first paragraphs of a
+    /// huge document.
+    ///
+    /// ```
+    /// use oox::docx::{partial::DocumentRange, wml::document::Document};
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p><w:r><w:t>First</w:t></w:r></w:p>
+    ///     <w:p><w:r><w:t>Second</w:t></w:r></w:p>
+    ///     <w:p><w:r><w:t>Third</w:t></w:r></w:p>
+    /// </w:body></w:document>"#;
+    ///
+    /// let document = Document::from_xml_str_in_range(xml, &DocumentRange::Paragraphs(1..2)).unwrap();
+    /// assert_eq!(document.body.unwrap().block_level_elements.len(), 1);
+    /// ```
+    pub fn from_xml_str_in_range(xml_string: &str, range: &DocumentRange) -> Result<Self, Box<dyn Error>> {
+        let mut filter = range.filter();
+        let xml_node = XmlNode::from_str_filtered(xml_string, &mut filter)?;
+        Document::from_xml_element(&xml_node)
+    }
+}
+
+impl Package {
+    /// Opens a package parsing only the range of the main document body, see [`Document::from_xml_str_in_range`].
+    /// The other parts are parsed completely.
+    ///
+    /// ```no_run
+    /// use oox::docx::{package::Package, partial::DocumentRange};
+    /// use std::path::Path;
+    ///
+    /// let package = Package::from_file_in_range(Path::new("document.docx"), DocumentRange::Paragraphs(100..200));
+    /// ```
+    pub fn from_file_in_range(file_path: &Path, range: DocumentRange) -> Result<Self, Box<dyn Error>> {
+        Package::from_file_with_document_range(file_path, Some(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::{text::RevisionView, wml::document::BlockLevelElts};
+
+    const DOCUMENT_XML: &str = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+        <w:body>
+            <w:p><w:r><w:t>Zero</w:t></w:r></w:p>
+            <w:p><w:r><w:t>One</w:t></w:r></w:p>
+            <w:tbl>
+                <w:tblPr />
+                <w:tblGrid><w:gridCol w:w="100" /></w:tblGrid>
+                <w:tr><w:tc><w:p><w:r><w:t>Cell</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+            <w:p><w:r><w:t>Two</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Three</w:t></w:r></w:p>
+            <w:sectPr><w:pgSz w:w="11906" w:h="16838" /></w:sectPr>
+        </w:body>
+    </w:document>"#;
+
+    #[test]
+    pub fn test_from_xml_str_in_range() {
+        let document = Document::from_xml_str_in_range(DOCUMENT_XML, &DocumentRange::Paragraphs(1..3)).unwrap();
+        assert_eq!(document.text_map(RevisionView::Accepted).text, "One\nCell\nTwo");
+        let body = document.body.unwrap();
+        assert_eq!(body.block_level_elements.len(), 3);
+        assert!(body.section_properties.is_some());
+
+        let start = DOCUMENT_XML.find("<w:tbl>").unwrap();
+        let end = DOCUMENT_XML.find("<w:p><w:r><w:t>Three").unwrap();
+        let document = Document::from_xml_str_in_range(DOCUMENT_XML, &DocumentRange::Bytes(start..end)).unwrap();
+        assert_eq!(document.text_map(RevisionView::Accepted).text, "Cell\nTwo");
+        assert!(matches!(
+            document.body.unwrap().block_level_elements.first(),
+            Some(BlockLevelElts::Chunk(_))
+        ));
+
+        let document = Document::from_xml_str_in_range(DOCUMENT_XML, &DocumentRange::Paragraphs(10..20)).unwrap();
+        assert!(document.body.unwrap().block_level_elements.is_empty());
+    }
+}
@@ -0,0 +1,335 @@
+use super::wml::{
+    document::{HAnchor, VAnchor},
+    drawing::{AlignH, AlignV, Anchor, PosHChoice, PosVChoice, RelFromH, RelFromV, WrapPath, WrapText, WrapType},
+    table::{Tbl, TblPPr},
+};
+use crate::shared::{
+    drawingml::coordsys::{Point2D, PositiveSize2D},
+    sharedtypes::{Length, Measure, XAlign, YAlign},
+};
+
+/// The size of the coordinate space of wrap polygons. The polygon is scaled from this space to the extent of the
+/// drawing.
+pub const WRAP_POLYGON_SIZE: i64 = 21600;
+
+/// The horizontal position of a floating object relative to its anchor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HorizontalPosition {
+    Aligned(AlignH),
+    /// The offset of the left edge of the object from the left edge of the anchor.
+    Offset(Length),
+}
+
+/// The vertical position of a floating object relative to its anchor.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerticalPosition {
+    Aligned(AlignV),
+    /// The offset of the top edge of the object from the top edge of the anchor.
+    Offset(Length),
+}
+
+/// The minimum distances between the edges of a floating object and the text wrapping around it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct WrapDistances {
+    pub top: Length,
+    pub bottom: Length,
+    pub left: Length,
+    pub right: Length,
+}
+
+/// The position of a floating object, i.e. an anchored drawing or a floating table, independent of the markup used to
+/// describe it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionedObject {
+    pub horizontal_anchor: RelFromH,
+    pub horizontal_position: HorizontalPosition,
+    pub vertical_anchor: RelFromV,
+    pub vertical_position: VerticalPosition,
+    pub wrap_distances: WrapDistances,
+}
+
+/// How text wraps around an anchored drawing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextWrapping {
+    /// The drawing is in front of or behind the text, which doesn't wrap around it.
+    None,
+    /// Text wraps around the bounding box of the drawing.
+    Square,
+    /// Text wraps around the wrap polygon.
+    Tight,
+    /// Text wraps around the wrap polygon and fills its concave parts.
+    Through,
+    /// Text is placed above and below the drawing only.
+    TopAndBottom,
+}
+
+/// The text wrapping of an anchored drawing in a form ready for layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextWrap {
+    pub wrapping: TextWrapping,
+    /// The sides of the drawing text may wrap around. `None` for wrapping types placing text on both sides or on
+    /// neither of them.
+    pub sides: Option<WrapText>,
+    /// The distances from the text, taken from the wrapping element if present and from the anchor otherwise.
+    pub distances: WrapDistances,
+    /// The points of the wrap polygon of tight and through wrapping in EMUs, relative to the top left corner of the
+    /// extent of the drawing.
+    pub polygon: Vec<Point2D>,
+}
+
+impl WrapPath {
+    /// Returns the points of the polygon, starting point included, scaled from the [`WRAP_POLYGON_SIZE`] coordinate
+    /// space to EMUs of the given extent.
+    pub fn to_emu_points(&self, extent: &PositiveSize2D) -> Vec<Point2D> {
+        let scale =
+            |value: i64, size: u64| (i128::from(value) * i128::from(size) / i128::from(WRAP_POLYGON_SIZE)) as i64;
+        std::iter::once(&self.start)
+            .chain(&self.line_to)
+            .map(|point| Point2D::new(scale(point.x, extent.width), scale(point.y, extent.height)))
+            .collect()
+    }
+}
+
+impl Anchor {
+    /// Resolves the position of the drawing. Drawings using a simple position are positioned relative to the page.
+    pub fn positioned_object(&self) -> PositionedObject {
+        let wrap_distances = WrapDistances {
+            top: Length::from_emu(f64::from(self.distance_top.unwrap_or(0))),
+            bottom: Length::from_emu(f64::from(self.distance_bottom.unwrap_or(0))),
+            left: Length::from_emu(f64::from(self.distance_left.unwrap_or(0))),
+            right: Length::from_emu(f64::from(self.distance_right.unwrap_or(0))),
+        };
+
+        if self.use_simple_position == Some(true) {
+            return PositionedObject {
+                horizontal_anchor: RelFromH::Page,
+                horizontal_position: HorizontalPosition::Offset(Length::from_emu(self.simple_position.x as f64)),
+                vertical_anchor: RelFromV::Page,
+                vertical_position: VerticalPosition::Offset(Length::from_emu(self.simple_position.y as f64)),
+                wrap_distances,
+            };
+        }
+
+        let horizontal_position = match &self.horizontal_position.align_or_offset {
+            PosHChoice::Align(align) => HorizontalPosition::Aligned(align.clone()),
+            PosHChoice::PositionOffset(offset) => HorizontalPosition::Offset(Length::from_emu(f64::from(*offset))),
+        };
+
+        let vertical_position = match &self.vertical_position.align_or_offset {
+            PosVChoice::Align(align) => VerticalPosition::Aligned(align.clone()),
+            PosVChoice::PositionOffset(offset) => VerticalPosition::Offset(Length::from_emu(f64::from(*offset))),
+        };
+
+        PositionedObject {
+            horizontal_anchor: self.horizontal_position.relative_from.clone(),
+            horizontal_position,
+            vertical_anchor: self.vertical_position.relative_from.clone(),
+            vertical_position,
+            wrap_distances,
+        }
+    }
+
+    /// Resolves the text wrapping of the drawing.
+    pub fn text_wrap(&self) -> TextWrap {
+        let emus = |distance: Option<u32>| distance.map(|distance| Length::from_emu(f64::from(distance)));
+        let anchor_distances = self.positioned_object().wrap_distances;
+        let distances = |top: Option<u32>, bottom: Option<u32>, left: Option<u32>, right: Option<u32>| WrapDistances {
+            top: emus(top).unwrap_or(anchor_distances.top),
+            bottom: emus(bottom).unwrap_or(anchor_distances.bottom),
+            left: emus(left).unwrap_or(anchor_distances.left),
+            right: emus(right).unwrap_or(anchor_distances.right),
+        };
+
+        let (wrapping, sides, distances, polygon) = match &self.wrap_type {
+            WrapType::None => (TextWrapping::None, None, anchor_distances, Vec::new()),
+            WrapType::Square(square) => (
+                TextWrapping::Square,
+                Some(square.wrap_text.clone()),
+                distances(
+                    square.distance_top,
+                    square.distance_bottom,
+                    square.distance_left,
+                    square.distance_right,
+                ),
+                Vec::new(),
+            ),
+            WrapType::Tight(tight) => (
+                TextWrapping::Tight,
+                Some(tight.wrap_text.clone()),
+                distances(None, None, tight.distance_left, tight.distance_right),
+                tight.wrap_polygon.to_emu_points(&self.extent),
+            ),
+            WrapType::Through(through) => (
+                TextWrapping::Through,
+                Some(through.wrap_text.clone()),
+                distances(None, None, through.distance_left, through.distance_right),
+                through.wrap_polygon.to_emu_points(&self.extent),
+            ),
+            WrapType::TopAndBottom(top_and_bottom) => (
+                TextWrapping::TopAndBottom,
+                None,
+                distances(top_and_bottom.distance_top, top_and_bottom.distance_bottom, None, None),
+                Vec::new(),
+            ),
+        };
+
+        TextWrap {
+            wrapping,
+            sides,
+            distances,
+            polygon,
+        }
+    }
+}
+
+impl TblPPr {
+    /// Resolves the position of the floating table. Tables anchored to the text are positioned relative to the column
+    /// horizontally and to the paragraph following them vertically. Tables aligned inline vertically are placed at the
+    /// top of the paragraph.
+    pub fn positioned_object(&self) -> PositionedObject {
+        let horizontal_anchor = match self.horizontal_anchor.unwrap_or(HAnchor::Text) {
+            HAnchor::Text => RelFromH::Column,
+            HAnchor::Margin => RelFromH::Margin,
+            HAnchor::Page => RelFromH::Page,
+        };
+
+        let vertical_anchor = match self.vertical_anchor.unwrap_or(VAnchor::Text) {
+            VAnchor::Text => RelFromV::Paragraph,
+            VAnchor::Margin => RelFromV::Margin,
+            VAnchor::Page => RelFromV::Page,
+        };
+
+        let offset = |distance: Option<Length>| distance.unwrap_or_default();
+        let horizontal_position = match self.horizontal_alignment {
+            Some(XAlign::Left) => HorizontalPosition::Aligned(AlignH::Left),
+            Some(XAlign::Center) => HorizontalPosition::Aligned(AlignH::Center),
+            Some(XAlign::Right) => HorizontalPosition::Aligned(AlignH::Right),
+            Some(XAlign::Inside) => HorizontalPosition::Aligned(AlignH::Inside),
+            Some(XAlign::Outside) => HorizontalPosition::Aligned(AlignH::Outside),
+            None => HorizontalPosition::Offset(offset(self.horizontal_distance.map(|distance| distance.to_length()))),
+        };
+
+        let vertical_position = match self.vertical_alignment {
+            Some(YAlign::Top) => VerticalPosition::Aligned(AlignV::Top),
+            Some(YAlign::Center) => VerticalPosition::Aligned(AlignV::Center),
+            Some(YAlign::Bottom) => VerticalPosition::Aligned(AlignV::Bottom),
+            Some(YAlign::Inside) => VerticalPosition::Aligned(AlignV::Inside),
+            Some(YAlign::Outside) => VerticalPosition::Aligned(AlignV::Outside),
+            Some(YAlign::Inline) => VerticalPosition::Offset(Length::default()),
+            None => VerticalPosition::Offset(offset(self.vertical_distance.map(|distance| distance.to_length()))),
+        };
+
+        let distance = |measure: &Option<_>| measure.as_ref().map(Measure::to_length).unwrap_or_default();
+        PositionedObject {
+            horizontal_anchor,
+            horizontal_position,
+            vertical_anchor,
+            vertical_position,
+            wrap_distances: WrapDistances {
+                top: distance(&self.top_from_text),
+                bottom: distance(&self.bottom_from_text),
+                left: distance(&self.left_from_text),
+                right: distance(&self.right_from_text),
+            },
+        }
+    }
+}
+
+impl Tbl {
+    /// Returns the position of the table if it's a floating table, i.e. it has `tblpPr`.
+    pub fn positioned_object(&self) -> Option<PositionedObject> {
+        self.properties
+            .base
+            .paragraph_properties
+            .as_ref()
+            .map(TblPPr::positioned_object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{tablelayout::TableLayout, wml::drawing::WrapTight},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_floating_table_position() {
+        let xml = r#"<w:tbl>
+            <w:tblPr>
+                <w:tblpPr w:leftFromText="180" w:rightFromText="180" w:vertAnchor="page" w:horzAnchor="margin"
+                    w:tblpXSpec="center" w:tblpY="1440" />
+            </w:tblPr>
+            <w:tblGrid><w:gridCol w:w="1000" /></w:tblGrid>
+            <w:tr><w:tc><w:p /></w:tc></w:tr>
+        </w:tbl>"#;
+        let table = Tbl::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let position = TableLayout::new(&table).position.unwrap();
+        assert_eq!(position.horizontal_anchor, RelFromH::Margin);
+        assert_eq!(
+            position.horizontal_position,
+            HorizontalPosition::Aligned(AlignH::Center)
+        );
+        assert_eq!(position.vertical_anchor, RelFromV::Page);
+        assert_eq!(
+            position.vertical_position,
+            VerticalPosition::Offset(Length::from_inches(1.0))
+        );
+        assert_eq!(position.wrap_distances.left, Length::from_twips(180.0));
+        assert_eq!(position.wrap_distances.top, Length::default());
+
+        let xml = r#"<w:tbl><w:tblPr /><w:tblGrid /><w:tr><w:tc><w:p /></w:tc></w:tr></w:tbl>"#;
+        let inline = Tbl::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(inline.positioned_object(), None);
+    }
+
+    #[test]
+    pub fn test_anchor_position() {
+        let mut anchor = Anchor::test_instance();
+        let position = anchor.positioned_object();
+        assert_eq!(position.horizontal_anchor, anchor.horizontal_position.relative_from);
+        assert_eq!(position.wrap_distances.bottom, Length::from_emu(100.0));
+
+        anchor.use_simple_position = Some(true);
+        anchor.simple_position.x = 914400;
+        let position = anchor.positioned_object();
+        assert_eq!(position.horizontal_anchor, RelFromH::Page);
+        assert_eq!(
+            position.horizontal_position,
+            HorizontalPosition::Offset(Length::from_inches(1.0))
+        );
+    }
+
+    #[test]
+    pub fn test_anchor_text_wrap() {
+        let mut anchor = Anchor::test_instance();
+        let wrap = anchor.text_wrap();
+        assert_eq!(wrap.wrapping, TextWrapping::Square);
+        assert_eq!(wrap.sides, Some(WrapText::BothSides));
+        assert!(wrap.polygon.is_empty());
+
+        anchor.extent = PositiveSize2D::new(43200, 10800);
+        anchor.distance_left = Some(50);
+        anchor.wrap_type = WrapType::Tight(WrapTight {
+            wrap_polygon: WrapPath {
+                start: Point2D::new(0, 0),
+                line_to: vec![Point2D::new(21600, 0), Point2D::new(10800, 21600)],
+                edited: None,
+            },
+            wrap_text: WrapText::Largest,
+            distance_left: None,
+            distance_right: Some(10),
+        });
+        let wrap = anchor.text_wrap();
+        assert_eq!(wrap.wrapping, TextWrapping::Tight);
+        assert_eq!(
+            wrap.polygon,
+            vec![Point2D::new(0, 0), Point2D::new(43200, 0), Point2D::new(21600, 10800)]
+        );
+        assert_eq!(wrap.distances.left, Length::from_emu(50.0));
+        assert_eq!(wrap.distances.right, Length::from_emu(10.0));
+    }
+}
@@ -0,0 +1,500 @@
+use super::{
+    package::Package,
+    text::{content_block_lines, NoteCollector, NotePlacement},
+    wml::{
+        document::{
+            BlockLevelElts, Body, ContentBlockContent, ContentRunContent, Document, PContent, RPrBase, RunLevelElts,
+            RunTrackChangeChoice, P,
+        },
+        numbering::Numbering,
+        simpletypes::DecimalNumber,
+        styles::Styles,
+        table::{ContentCellContent, ContentRowContent, Tbl},
+    },
+};
+use std::collections::HashSet;
+
+impl Document {
+    /// Returns a copy of the document truncated after its first `paragraph_count` paragraphs. Paragraphs in tables,
+    /// content controls and custom XML elements are counted too, but these block containers are never split, so the
+    /// preview may contain a few more paragraphs than requested. The properties of the last section are kept.
+    ///
+    /// ```
+    /// use oox::docx::wml::document::{BlockLevelElts, Body, ContentBlockContent, Document, P};
+    ///
+    /// let paragraph = BlockLevelElts::Chunk(ContentBlockContent::Paragraph(Box::new(P::default())));
+    /// let document = Document {
+    ///     body: Some(Body {
+    ///         block_level_elements: vec![paragraph; 10],
+    ///         section_properties: None,
+    ///         ..Default::default()
+    ///     }),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let preview = document.preview(3);
+    /// assert_eq!(preview.body.unwrap().block_level_elements.len(), 3);
+    /// ```
+    pub fn preview(&self, paragraph_count: usize) -> Document {
+        let body = self.body.as_ref().map(|body| {
+            let mut block_level_elements = Vec::new();
+            let mut retained_paragraphs = 0;
+            for element in &body.block_level_elements {
+                if retained_paragraphs >= paragraph_count {
+                    break;
+                }
+
+                retained_paragraphs += match element {
+                    BlockLevelElts::Chunk(content) => content_block_paragraph_count(content),
+                    BlockLevelElts::AltChunk(_) => 1,
+                };
+                block_level_elements.push(element.clone());
+            }
+
+            Body {
+                block_level_elements,
+                section_properties: body.section_properties.clone(),
+                extras: body.extras.clone(),
+            }
+        });
+
+        Document {
+            base: self.base.clone(),
+            body,
+            conformance: self.conformance,
+        }
+    }
+}
+
+/// A truncated copy of the main document along with the styles and numbering definitions it uses.
+#[derive(Debug)]
+pub struct PackagePreview {
+    pub document: Document,
+    /// The styles used by the retained content, the styles they are based on or linked to and the default styles.
+    pub styles: Option<Styles>,
+    /// The numbering instances used by the retained content and their abstract numbering definitions. Picture bullets
+    /// aren't copied, since their pictures can't be cloned.
+    pub numbering: Option<Numbering>,
+    /// The ids of the picture bullets used by the retained numbering definitions. They can be looked up in the
+    /// numbering of the original package.
+    pub picture_bullet_ids: Vec<DecimalNumber>,
+}
+
+impl Package {
+    /// Creates a preview of the main document with its first `paragraph_count` paragraphs. See
+    /// [`Document::preview`] for how the document is truncated. Returns `None` if the package has no main document.
+    pub fn preview(&self, paragraph_count: usize) -> Option<PackagePreview> {
+        let document = self.main_document.as_ref()?.preview(paragraph_count);
+
+        let mut used = UsedDefinitions::default();
+        for element in document.body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            used.collect_block_level_element(element);
+        }
+        used.close_over(self.styles.as_deref(), self.numbering.as_ref());
+
+        let styles = self.styles.as_ref().map(|styles| Styles {
+            document_defaults: styles.document_defaults.clone(),
+            latent_styles: styles.latent_styles.clone(),
+            styles: styles
+                .styles
+                .iter()
+                .filter(|style| {
+                    style.is_default == Some(true)
+                        || matches!(&style.style_id, Some(style_id) if used.style_ids.contains(style_id))
+                })
+                .cloned()
+                .collect(),
+        });
+
+        let mut picture_bullet_ids = Vec::new();
+        let numbering = self.numbering.as_ref().map(|numbering| {
+            let abstract_numberings: Vec<_> = numbering
+                .abstract_numberings
+                .iter()
+                .filter(|abstract_num| used.abstract_numbering_ids.contains(&abstract_num.abstract_num_id))
+                .cloned()
+                .collect();
+            for level in abstract_numberings
+                .iter()
+                .flat_map(|abstract_num| abstract_num.levels.iter())
+            {
+                if let Some(id) = level.level_picture_bullet_id {
+                    if !picture_bullet_ids.contains(&id) {
+                        picture_bullet_ids.push(id);
+                    }
+                }
+            }
+
+            Numbering {
+                picture_numbering_symbols: Vec::new(),
+                abstract_numberings,
+                numberings: numbering
+                    .numberings
+                    .iter()
+                    .filter(|num| used.numbering_ids.contains(&num.numbering_id))
+                    .cloned()
+                    .collect(),
+                numbering_id_mac_at_cleanup: numbering.numbering_id_mac_at_cleanup,
+            }
+        });
+
+        Some(PackagePreview {
+            document,
+            styles,
+            numbering,
+            picture_bullet_ids,
+        })
+    }
+
+    /// Extracts the text of the first `paragraph_count` paragraphs of the main document, one line per paragraph and
+    /// table row. Note references are omitted.
+    pub fn preview_text(&self, paragraph_count: usize) -> String {
+        let document = match &self.main_document {
+            Some(document) => document.preview(paragraph_count),
+            None => return String::new(),
+        };
+
+        let mut notes = NoteCollector::new(self, NotePlacement::Omit);
+        let mut lines = Vec::new();
+        for element in document.body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            if let BlockLevelElts::Chunk(content) = element {
+                content_block_lines(content, &mut notes, &mut lines);
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+fn content_block_paragraph_count(content: &ContentBlockContent) -> usize {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => custom_xml
+            .block_contents
+            .iter()
+            .map(content_block_paragraph_count)
+            .sum(),
+        ContentBlockContent::Sdt(sdt) => sdt
+            .sdt_content
+            .iter()
+            .flat_map(|sdt_content| sdt_content.block_contents.iter())
+            .map(content_block_paragraph_count)
+            .sum(),
+        ContentBlockContent::Paragraph(_) => 1,
+        ContentBlockContent::Table(table) => {
+            let mut count = 0;
+            for_each_table_block(table, &mut |element| {
+                if let BlockLevelElts::Chunk(content) = element {
+                    count += content_block_paragraph_count(content);
+                }
+            });
+            count
+        }
+        ContentBlockContent::RunLevelElement(_) => 0,
+    }
+}
+
+/// Calls `f` for every block level element of the cells of a table, looking through custom XML elements and content
+/// controls around rows and cells.
+pub(crate) fn for_each_table_block<'a>(table: &'a Tbl, f: &mut dyn FnMut(&'a BlockLevelElts)) {
+    fn row_blocks<'a>(content: &'a ContentRowContent, f: &mut dyn FnMut(&'a BlockLevelElts)) {
+        match content {
+            ContentRowContent::Table(row) => {
+                for cell_content in &row.contents {
+                    cell_blocks(cell_content, f);
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    row_blocks(content, f);
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    row_blocks(content, f);
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn cell_blocks<'a>(content: &'a ContentCellContent, f: &mut dyn FnMut(&'a BlockLevelElts)) {
+        match content {
+            ContentCellContent::Cell(cell) => cell.block_level_elements.iter().for_each(f),
+            ContentCellContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.contents {
+                    cell_blocks(content, f);
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                    cell_blocks(content, f);
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    for row_content in &table.row_contents {
+        row_blocks(row_content, f);
+    }
+}
+
+/// The style and numbering definitions referenced by a part of a document.
+#[derive(Default)]
+struct UsedDefinitions {
+    style_ids: HashSet<String>,
+    numbering_ids: HashSet<DecimalNumber>,
+    abstract_numbering_ids: HashSet<DecimalNumber>,
+}
+
+impl UsedDefinitions {
+    fn collect_block_level_element(&mut self, element: &BlockLevelElts) {
+        if let BlockLevelElts::Chunk(content) = element {
+            self.collect_content_block(content);
+        }
+    }
+
+    fn collect_content_block(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.collect_content_block(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.collect_content_block(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.collect_paragraph(paragraph),
+            ContentBlockContent::Table(table) => {
+                if let Some(style) = &table.properties.base.style {
+                    self.style_ids.insert(style.clone());
+                }
+
+                let mut elements = Vec::new();
+                for_each_table_block(table, &mut |element| elements.push(element));
+                for element in elements {
+                    self.collect_block_level_element(element);
+                }
+            }
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn collect_paragraph(&mut self, paragraph: &P) {
+        if let Some(properties) = &paragraph.properties {
+            if let Some(style) = &properties.base.style {
+                self.style_ids.insert(style.clone());
+            }
+
+            if let Some(numbering_id) = properties
+                .base
+                .numbering_properties
+                .as_ref()
+                .and_then(|num_pr| num_pr.numbering_id)
+            {
+                self.numbering_ids.insert(numbering_id);
+            }
+
+            if let Some(run_properties) = &properties.run_properties {
+                self.collect_run_properties(&run_properties.bases);
+            }
+        }
+
+        self.collect_paragraph_contents(&paragraph.contents);
+    }
+
+    fn collect_paragraph_contents(&mut self, contents: &[PContent]) {
+        for content in contents {
+            match content {
+                PContent::ContentRunContent(content) => self.collect_content_run(content),
+                PContent::SimpleField(field) => self.collect_paragraph_contents(&field.paragraph_contents),
+                PContent::Hyperlink(hyperlink) => self.collect_paragraph_contents(&hyperlink.paragraph_contents),
+                PContent::SubDocument(_) => (),
+            }
+        }
+    }
+
+    fn collect_content_run(&mut self, content: &ContentRunContent) {
+        match content {
+            ContentRunContent::Run(run) => {
+                if let Some(run_properties) = &run.run_properties {
+                    self.collect_run_properties(&run_properties.r_pr_bases);
+                }
+            }
+            ContentRunContent::CustomXml(custom_xml) => self.collect_paragraph_contents(&custom_xml.paragraph_contents),
+            ContentRunContent::SmartTag(smart_tag) => self.collect_paragraph_contents(&smart_tag.paragraph_contents),
+            ContentRunContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    self.collect_paragraph_contents(&sdt_content.p_contents);
+                }
+            }
+            ContentRunContent::Bidirectional(dir) => self.collect_paragraph_contents(&dir.p_contents),
+            ContentRunContent::BidirectionalOverride(bdo) => self.collect_paragraph_contents(&bdo.p_contents),
+            ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+                for choice in &track_change.choices {
+                    match choice {
+                        RunTrackChangeChoice::ContentRunContent(content) => self.collect_content_run(content),
+                    }
+                }
+            }
+            ContentRunContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn collect_run_properties(&mut self, r_pr_bases: &[RPrBase]) {
+        for r_pr_base in r_pr_bases {
+            if let RPrBase::RunStyle(style) = r_pr_base {
+                self.style_ids.insert(style.clone());
+            }
+        }
+    }
+
+    /// Adds the definitions the used definitions depend on: the styles they are based on or linked to, the numbering
+    /// of the styles, the abstract numbering definitions and the styles linked from them.
+    fn close_over(&mut self, styles: Option<&Styles>, numbering: Option<&Numbering>) {
+        loop {
+            let (style_count, numbering_count, abstract_numbering_count) = (
+                self.style_ids.len(),
+                self.numbering_ids.len(),
+                self.abstract_numbering_ids.len(),
+            );
+
+            for style in styles.iter().flat_map(|styles| styles.styles.iter()) {
+                let is_used = style.is_default == Some(true)
+                    || matches!(&style.style_id, Some(style_id) if self.style_ids.contains(style_id));
+                if !is_used {
+                    continue;
+                }
+
+                for style_id in style.based_on.iter().chain(style.link.iter()) {
+                    self.style_ids.insert(style_id.clone());
+                }
+
+                if let Some(numbering_id) = style
+                    .paragraph_properties
+                    .as_ref()
+                    .and_then(|p_pr| p_pr.base.numbering_properties.as_ref())
+                    .and_then(|num_pr| num_pr.numbering_id)
+                {
+                    self.numbering_ids.insert(numbering_id);
+                }
+            }
+
+            if let Some(numbering) = numbering {
+                for num in &numbering.numberings {
+                    if self.numbering_ids.contains(&num.numbering_id) {
+                        self.abstract_numbering_ids.insert(num.abstract_num_id);
+                    }
+                }
+
+                for abstract_num in &numbering.abstract_numberings {
+                    if !self.abstract_numbering_ids.contains(&abstract_num.abstract_num_id) {
+                        continue;
+                    }
+
+                    let linked_styles = abstract_num
+                        .style_link
+                        .iter()
+                        .chain(abstract_num.numbering_style_link.iter())
+                        .chain(
+                            abstract_num
+                                .levels
+                                .iter()
+                                .filter_map(|level| level.paragraph_style.as_ref()),
+                        );
+                    for style_id in linked_styles {
+                        self.style_ids.insert(style_id.clone());
+                    }
+                }
+            }
+
+            if (style_count, numbering_count, abstract_numbering_count)
+                == (
+                    self.style_ids.len(),
+                    self.numbering_ids.len(),
+                    self.abstract_numbering_ids.len(),
+                )
+            {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_package_preview() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Title</w:t></w:r></w:p>
+            <w:tbl>
+                <w:tblPr><w:tblStyle w:val="Grid" /></w:tblPr>
+                <w:tblGrid><w:gridCol w:w="1000" /></w:tblGrid>
+                <w:tr><w:tc><w:p><w:pPr><w:numPr><w:ilvl w:val="0" /><w:numId w:val="2" /></w:numPr></w:pPr>
+                    <w:r><w:t>Item</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+            <w:p><w:r><w:rPr><w:rStyle w:val="Emphasis" /></w:rPr><w:t>Dropped</w:t></w:r></w:p>
+            <w:sectPr />
+        </w:body></w:document>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal" /></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:basedOn w:val="Normal" /><w:link w:val="Heading1Char" /></w:style>
+            <w:style w:type="character" w:styleId="Heading1Char" />
+            <w:style w:type="table" w:styleId="Grid" />
+            <w:style w:type="paragraph" w:styleId="ListParagraph" />
+            <w:style w:type="character" w:styleId="Emphasis" />
+        </w:styles>"#;
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0"><w:pStyle w:val="ListParagraph" /></w:lvl></w:abstractNum>
+            <w:abstractNum w:abstractNumId="1"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="1" /></w:num>
+            <w:num w:numId="2"><w:abstractNumId w:val="0" /></w:num>
+        </w:numbering>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        };
+
+        let preview = package.preview(2).unwrap();
+        let body = preview.document.body.unwrap();
+        assert_eq!(body.block_level_elements.len(), 2);
+        assert!(body.section_properties.is_some());
+
+        let mut style_ids: Vec<_> = preview
+            .styles
+            .unwrap()
+            .styles
+            .iter()
+            .filter_map(|style| style.style_id.clone())
+            .collect();
+        style_ids.sort();
+        assert_eq!(
+            style_ids,
+            vec!["Grid", "Heading1", "Heading1Char", "ListParagraph", "Normal"]
+        );
+
+        let numbering = preview.numbering.unwrap();
+        assert_eq!(numbering.numberings.len(), 1);
+        assert_eq!(numbering.numberings[0].numbering_id, 2);
+        assert_eq!(numbering.abstract_numberings.len(), 1);
+        assert_eq!(numbering.abstract_numberings[0].abstract_num_id, 0);
+
+        assert_eq!(package.preview_text(2), "Title\nItem");
+    }
+}
@@ -0,0 +1,241 @@
+use super::{package::Package, wml::settings::Settings};
+use crate::{
+    shared::docprops::{AppInfo, Core},
+    xml::read_root_element,
+};
+use std::{error::Error, fs::File, io::BufReader};
+use zip::{result::ZipError, ZipArchive};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+const WORD_EXTENSION_NAMESPACE_PREFIX: &str = "http://schemas.microsoft.com/office/word/";
+
+/// The application that generated a document, as guessed by [`ProducerInfo::detect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Producer {
+    Word2007,
+    Word2010,
+    Word2013,
+    /// Word 2016, or a later perpetual version that doesn't use any newer extension namespace
+    Word2016,
+    /// Word 2019 and later, including the Microsoft 365 subscription
+    Word365,
+    /// Microsoft Word with a version that can't be determined
+    Word,
+    LibreOffice,
+    GoogleDocs,
+    PythonDocx,
+    Unknown,
+}
+
+impl Producer {
+    pub fn is_word(self) -> bool {
+        matches!(
+            self,
+            Producer::Word2007
+                | Producer::Word2010
+                | Producer::Word2013
+                | Producer::Word2016
+                | Producer::Word365
+                | Producer::Word
+        )
+    }
+}
+
+/// The result of fingerprinting the application that generated a document. The detection is heuristic and is meant
+/// for triaging compatibility issues, not for making decisions on the content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProducerInfo {
+    pub producer: Producer,
+    /// The `Application` value of `docProps/app.xml`
+    pub application: Option<String>,
+    /// The `AppVersion` value of `docProps/app.xml`
+    pub app_version: Option<String>,
+    /// The Microsoft extension namespaces declared by the main document, e.g.
+    /// `http://schemas.microsoft.com/office/word/2010/wordml`
+    pub extension_namespaces: Vec<String>,
+    /// Whether the settings contain revision save ids, which Word always writes
+    pub has_rsids: bool,
+}
+
+impl ProducerInfo {
+    /// Guesses the producer from the application properties, the core properties, the settings and the namespaces
+    /// declared by the root element of the main document.
+    ///
+    /// The application properties are trusted first. Word versions are told apart by the major version of
+    /// `AppVersion`, with the newest declared Word extension namespace separating Word 2016 from later versions.
+    /// Documents without application properties are attributed to Word if they contain revision save ids, otherwise to
+    /// Google Docs, which omits both.
+    ///
+    /// ```
+    /// use oox::{
+    ///     docx::producer::{Producer, ProducerInfo},
+    ///     shared::docprops::AppInfo,
+    /// };
+    ///
+    /// let app_info = AppInfo {
+    ///     app_name: Some(String::from("LibreOffice/7.3.7.2$Linux_X86_64 LibreOffice_project/30$Build-2")),
+    ///     app_version: Some(String::from("15.0000")),
+    /// };
+    /// let info = ProducerInfo::detect(Some(&app_info), None, None, &[]);
+    /// assert_eq!(info.producer, Producer::LibreOffice);
+    /// ```
+    pub fn detect(
+        app_info: Option<&AppInfo>,
+        core: Option<&Core>,
+        settings: Option<&Settings>,
+        namespaces: &[&str],
+    ) -> Self {
+        let application = app_info.and_then(|app_info| app_info.app_name.clone());
+        let app_version = app_info.and_then(|app_info| app_info.app_version.clone());
+        let extension_namespaces: Vec<String> = namespaces
+            .iter()
+            .filter(|namespace| namespace.starts_with("http://schemas.microsoft.com/"))
+            .map(|namespace| String::from(*namespace))
+            .collect();
+        let has_rsids = settings
+            .and_then(|settings| settings.revision_ids.as_ref())
+            .map(|rsids| rsids.revision_id_root.is_some() || !rsids.revision_ids.is_empty())
+            .unwrap_or(false);
+
+        let creator = core.and_then(|core| core.creator.as_deref());
+        let producer = match application.as_deref() {
+            _ if creator == Some("python-docx") => Producer::PythonDocx,
+            Some(app_name) if app_name.contains("LibreOffice") => Producer::LibreOffice,
+            Some(app_name) if app_name.contains("Google") => Producer::GoogleDocs,
+            Some(app_name) if app_name.contains("Word") => word_version(
+                app_version.as_deref(),
+                latest_word_extension_year(&extension_namespaces),
+            ),
+            Some(_) => Producer::Unknown,
+            None if has_rsids => word_version(None, latest_word_extension_year(&extension_namespaces)),
+            None => Producer::GoogleDocs,
+        };
+
+        Self {
+            producer,
+            application,
+            app_version,
+            extension_namespaces,
+            has_rsids,
+        }
+    }
+}
+
+fn latest_word_extension_year(namespaces: &[String]) -> Option<u32> {
+    namespaces
+        .iter()
+        .filter_map(|namespace| namespace.strip_prefix(WORD_EXTENSION_NAMESPACE_PREFIX))
+        .filter_map(|rest| rest.split('/').next()?.parse::<u32>().ok())
+        .max()
+}
+
+fn word_version(app_version: Option<&str>, latest_extension_year: Option<u32>) -> Producer {
+    let major_version = app_version
+        .and_then(|version| version.split('.').next())
+        .and_then(|major| major.parse::<u32>().ok());
+
+    match (major_version, latest_extension_year) {
+        (Some(12), _) => Producer::Word2007,
+        (Some(14), _) => Producer::Word2010,
+        (Some(15), _) => Producer::Word2013,
+        (Some(16), Some(year)) if year >= 2018 => Producer::Word365,
+        (Some(16), _) => Producer::Word2016,
+        (None, Some(year)) if year >= 2018 => Producer::Word365,
+        _ => Producer::Word,
+    }
+}
+
+impl Package {
+    /// Fingerprints the application that generated the package. The namespace declarations of the main document are
+    /// read from the package file, since they aren't kept by the parsed document.
+    pub fn producer_info(&self) -> Result<ProducerInfo> {
+        let file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&file)?;
+        let namespaces: Vec<String> = match zipper.by_name("word/document.xml") {
+            Ok(zip_file) => read_root_element(BufReader::new(zip_file))?
+                .attributes
+                .into_iter()
+                .filter(|(attr, _)| attr.starts_with("xmlns"))
+                .map(|(_, namespace)| namespace)
+                .collect(),
+            Err(ZipError::FileNotFound) => Vec::new(),
+            Err(err) => return Err(Box::new(err)),
+        };
+
+        Ok(ProducerInfo::detect(
+            self.app_info.as_ref(),
+            self.core.as_ref(),
+            self.settings.as_deref(),
+            &namespaces.iter().map(String::as_str).collect::<Vec<_>>(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::wml::settings::DocRsids;
+
+    fn word_app_info(app_version: &str) -> AppInfo {
+        AppInfo {
+            app_name: Some(String::from("Microsoft Office Word")),
+            app_version: Some(String::from(app_version)),
+        }
+    }
+
+    #[test]
+    pub fn test_detect_word_versions() {
+        let detect = |app_version, namespaces| {
+            ProducerInfo::detect(Some(&word_app_info(app_version)), None, None, namespaces).producer
+        };
+
+        assert_eq!(detect("12.0000", &[]), Producer::Word2007);
+        assert_eq!(detect("14.0000", &[]), Producer::Word2010);
+        assert_eq!(detect("15.0000", &[]), Producer::Word2013);
+        assert_eq!(
+            detect(
+                "16.0000",
+                &[
+                    "http://schemas.microsoft.com/office/word/2012/wordml",
+                    "http://schemas.microsoft.com/office/word/2015/wordml/symex",
+                ]
+            ),
+            Producer::Word2016
+        );
+        assert_eq!(
+            detect(
+                "16.0000",
+                &[
+                    "http://schemas.openxmlformats.org/wordprocessingml/2006/main",
+                    "http://schemas.microsoft.com/office/word/2018/wordml/cex",
+                ]
+            ),
+            Producer::Word365
+        );
+    }
+
+    #[test]
+    pub fn test_detect_other_producers() {
+        let core = Core {
+            creator: Some(String::from("python-docx")),
+            ..Default::default()
+        };
+        let info = ProducerInfo::detect(Some(&word_app_info("14.0000")), Some(&core), None, &[]);
+        assert_eq!(info.producer, Producer::PythonDocx);
+
+        let settings = Settings {
+            revision_ids: Some(DocRsids {
+                revision_id_root: Some(0x00AB_12CD),
+                revision_ids: Vec::new(),
+            }),
+            ..Default::default()
+        };
+        let info = ProducerInfo::detect(None, None, Some(&settings), &[]);
+        assert_eq!(info.producer, Producer::Word);
+        assert!(info.has_rsids);
+
+        let info = ProducerInfo::detect(None, None, None, &[]);
+        assert_eq!(info.producer, Producer::GoogleDocs);
+    }
+}
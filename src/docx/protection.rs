@@ -0,0 +1,395 @@
+use super::{
+    package::Package,
+    wml::{
+        document::{
+            BlockLevelElts, ContentBlockContent, ContentRunContent, Document, EdGrp, PContent, PermStart,
+            RunInnerContent, RunLevelElts, RunTrackChangeChoice,
+        },
+        settings::{DocProtectType, Password, Settings},
+        table::{ContentCellContent, ContentRowContent},
+    },
+};
+
+/// The editing restrictions of a document set in the settings part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Protection<'a> {
+    /// The kind of editing allowed in the document outside of the editable ranges.
+    pub edit: DocProtectType,
+    /// Whether the restrictions are enforced by the application. Documents may store restrictions without enforcing
+    /// them.
+    pub is_enforced: bool,
+    /// Whether formatting is limited to a set of styles.
+    pub is_formatting_restricted: bool,
+    /// The password required to remove the enforcement.
+    pub password: Option<&'a Password>,
+    /// Whether the document should be opened read-only, see [`Protection::write_password`].
+    pub is_read_only_recommended: bool,
+    /// The password required to save changes to the document.
+    pub write_password: Option<&'a Password>,
+}
+
+impl Default for Protection<'_> {
+    fn default() -> Self {
+        Self {
+            edit: DocProtectType::None,
+            is_enforced: false,
+            is_formatting_restricted: false,
+            password: None,
+            is_read_only_recommended: false,
+            write_password: None,
+        }
+    }
+}
+
+impl Protection<'_> {
+    /// Returns whether editing of the document is restricted to the editable ranges, possibly allowing comments,
+    /// tracked changes or form fields outside of them.
+    pub fn is_editing_restricted(&self) -> bool {
+        self.is_enforced && self.edit != DocProtectType::None
+    }
+}
+
+/// A range of the main document which can be edited in a protected document, delimited by `w:permStart` and
+/// `w:permEnd`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EditableRange<'a> {
+    pub permission: &'a PermStart,
+    /// The paths of the paragraphs overlapping the range, see [`Document::paragraph_at`]. Column restrictions of
+    /// ranges in tables aren't applied.
+    pub paragraphs: Vec<Vec<usize>>,
+    /// The text of the range with paragraphs separated by new lines, as it looks with revisions accepted.
+    pub text: String,
+    /// Whether the matching `w:permEnd` was found. Unclosed ranges extend to the end of the document.
+    pub is_closed: bool,
+}
+
+impl EditableRange<'_> {
+    /// Returns whether a user in the given editor group can edit the range. Ranges granted to everyone are editable
+    /// by any group.
+    pub fn is_editable_by_group(&self, group: EdGrp) -> bool {
+        match self.permission.editor_group {
+            Some(EdGrp::Everyone) => true,
+            Some(editor_group) => editor_group == group,
+            None => false,
+        }
+    }
+
+    /// Returns whether the user with the given name or email address can edit the range, either granted to the user
+    /// or to everyone.
+    pub fn is_editable_by(&self, editor: &str) -> bool {
+        let is_editor =
+            matches!(&self.permission.editor, Some(range_editor) if range_editor.eq_ignore_ascii_case(editor));
+        is_editor || self.permission.editor_group == Some(EdGrp::Everyone)
+    }
+}
+
+impl Settings {
+    pub fn protection(&self) -> Protection<'_> {
+        let mut protection = Protection::default();
+        if let Some(document_protection) = &self.document_protection {
+            protection.edit = document_protection.edit.unwrap_or(DocProtectType::None);
+            protection.is_enforced = document_protection.enforcement.unwrap_or(false);
+            protection.is_formatting_restricted = document_protection.formatting.unwrap_or(false);
+            protection.password = Some(&document_protection.password).filter(|password| password.is_set());
+        }
+
+        if let Some(write_protection) = &self.write_protection {
+            protection.is_read_only_recommended = write_protection.recommended.unwrap_or(false);
+            protection.write_password = Some(&write_protection.password).filter(|password| password.is_set());
+        }
+
+        protection
+    }
+}
+
+impl Document {
+    /// Returns the ranges of the document marked editable in the order of their start.
+    ///
+    /// ```
+    /// use oox::{docx::wml::document::{Document, EdGrp}, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p><w:r><w:t>Locked</w:t></w:r></w:p>
+    ///     <w:p><w:permStart w:id="1" w:edGrp="everyone" /><w:r><w:t>Editable</w:t></w:r><w:permEnd w:id="1" /></w:p>
+    /// </w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// let ranges = document.editable_ranges();
+    /// assert_eq!(ranges[0].text, "Editable");
+    /// assert!(ranges[0].is_editable_by_group(EdGrp::Editors));
+    /// ```
+    pub fn editable_ranges(&self) -> Vec<EditableRange<'_>> {
+        let mut collector = RangeCollector::default();
+        if let Some(body) = &self.body {
+            collector.blocks(&body.block_level_elements, &mut Vec::new());
+        }
+
+        collector.ranges
+    }
+}
+
+impl Package {
+    /// Returns the editing restrictions of the document. Documents without settings aren't restricted.
+    pub fn protection(&self) -> Protection<'_> {
+        self.settings
+            .as_ref()
+            .map(|settings| settings.protection())
+            .unwrap_or_default()
+    }
+
+    /// Returns the editable ranges of the main document, see [`Document::editable_ranges`].
+    pub fn editable_ranges(&self) -> Vec<EditableRange<'_>> {
+        self.main_document
+            .as_ref()
+            .map(|document| document.editable_ranges())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Default)]
+struct RangeCollector<'a> {
+    ranges: Vec<EditableRange<'a>>,
+    /// The indexes of the ranges started but not ended yet
+    open_ranges: Vec<usize>,
+    paragraph_path: Option<Vec<usize>>,
+}
+
+impl<'a> RangeCollector<'a> {
+    fn blocks(&mut self, elements: &'a [BlockLevelElts], path: &mut Vec<usize>) {
+        for (index, element) in elements.iter().enumerate() {
+            if let BlockLevelElts::Chunk(content) = element {
+                path.push(index);
+                self.content_block(content, path);
+                path.pop();
+            }
+        }
+    }
+
+    fn content_block(&mut self, content: &'a ContentBlockContent, path: &mut Vec<usize>) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for (index, content) in custom_xml.block_contents.iter().enumerate() {
+                    path.push(index);
+                    self.content_block(content, path);
+                    path.pop();
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                let contents = sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter());
+                for (index, content) in contents.enumerate() {
+                    path.push(index);
+                    self.content_block(content, path);
+                    path.pop();
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => {
+                for &range_index in &self.open_ranges {
+                    let range = &mut self.ranges[range_index];
+                    if !range.paragraphs.is_empty() {
+                        range.text.push('\n');
+                    }
+                    range.paragraphs.push(path.clone());
+                }
+
+                self.paragraph_path = Some(path.clone());
+                self.paragraph_contents(&paragraph.contents);
+                self.paragraph_path = None;
+            }
+            ContentBlockContent::Table(table) => {
+                for (index, row_content) in table.row_contents.iter().enumerate() {
+                    path.push(index);
+                    self.row(row_content, path);
+                    path.pop();
+                }
+            }
+            ContentBlockContent::RunLevelElement(element) => self.run_level_element(element),
+        }
+    }
+
+    fn row(&mut self, content: &'a ContentRowContent, path: &mut Vec<usize>) {
+        match content {
+            ContentRowContent::Table(row) => {
+                for (index, cell_content) in row.contents.iter().enumerate() {
+                    path.push(index);
+                    self.cell(cell_content, path);
+                    path.pop();
+                }
+            }
+            ContentRowContent::CustomXml(custom_xml) => {
+                for (index, content) in custom_xml.contents.iter().enumerate() {
+                    path.push(index);
+                    self.row(content, path);
+                    path.pop();
+                }
+            }
+            ContentRowContent::Sdt(sdt) => {
+                let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+                for (index, content) in contents.enumerate() {
+                    path.push(index);
+                    self.row(content, path);
+                    path.pop();
+                }
+            }
+            ContentRowContent::RunLevelElements(element) => self.run_level_element(element),
+        }
+    }
+
+    fn cell(&mut self, content: &'a ContentCellContent, path: &mut Vec<usize>) {
+        match content {
+            ContentCellContent::Cell(cell) => self.blocks(&cell.block_level_elements, path),
+            ContentCellContent::CustomXml(custom_xml) => {
+                for (index, content) in custom_xml.contents.iter().enumerate() {
+                    path.push(index);
+                    self.cell(content, path);
+                    path.pop();
+                }
+            }
+            ContentCellContent::Sdt(sdt) => {
+                let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+                for (index, content) in contents.enumerate() {
+                    path.push(index);
+                    self.cell(content, path);
+                    path.pop();
+                }
+            }
+            ContentCellContent::RunLevelElement(element) => self.run_level_element(element),
+        }
+    }
+
+    fn paragraph_contents(&mut self, contents: &'a [PContent]) {
+        for content in contents {
+            match content {
+                PContent::ContentRunContent(content) => self.content_run(content),
+                PContent::SimpleField(field) => self.paragraph_contents(&field.paragraph_contents),
+                PContent::Hyperlink(hyperlink) => self.paragraph_contents(&hyperlink.paragraph_contents),
+                PContent::SubDocument(_) => (),
+            }
+        }
+    }
+
+    fn content_run(&mut self, content: &'a ContentRunContent) {
+        match content {
+            ContentRunContent::Run(run) => {
+                for inner_content in &run.run_inner_contents {
+                    match inner_content {
+                        RunInnerContent::Text(text) => self.push_text(&text.text),
+                        RunInnerContent::Tab | RunInnerContent::PositionTab(_) => self.push_text("\t"),
+                        RunInnerContent::Break(_) | RunInnerContent::CarriageReturn => self.push_text("\n"),
+                        _ => (),
+                    }
+                }
+            }
+            ContentRunContent::CustomXml(custom_xml) => self.paragraph_contents(&custom_xml.paragraph_contents),
+            ContentRunContent::SmartTag(smart_tag) => self.paragraph_contents(&smart_tag.paragraph_contents),
+            ContentRunContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    self.paragraph_contents(&sdt_content.p_contents);
+                }
+            }
+            ContentRunContent::Bidirectional(dir) => self.paragraph_contents(&dir.p_contents),
+            ContentRunContent::BidirectionalOverride(bdo) => self.paragraph_contents(&bdo.p_contents),
+            ContentRunContent::RunLevelElements(element) => self.run_level_element(element),
+        }
+    }
+
+    fn run_level_element(&mut self, element: &'a RunLevelElts) {
+        match element {
+            RunLevelElts::PermissionStart(permission) => {
+                self.open_ranges.push(self.ranges.len());
+                self.ranges.push(EditableRange {
+                    permission,
+                    paragraphs: self.paragraph_path.iter().cloned().collect(),
+                    text: String::new(),
+                    is_closed: false,
+                });
+            }
+            RunLevelElts::PermissionEnd(permission) => {
+                let ranges = &mut self.ranges;
+                let position = self
+                    .open_ranges
+                    .iter()
+                    .position(|&range_index| ranges[range_index].permission.permission.id == permission.id);
+                if let Some(position) = position {
+                    let range_index = self.open_ranges.remove(position);
+                    ranges[range_index].is_closed = true;
+                }
+            }
+            RunLevelElts::Insert(track_change) | RunLevelElts::MoveTo(track_change) => {
+                for choice in &track_change.choices {
+                    match choice {
+                        RunTrackChangeChoice::ContentRunContent(content) => self.content_run(content),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn push_text(&mut self, text: &str) {
+        for &range_index in &self.open_ranges {
+            self.ranges[range_index].text.push_str(text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_editable_ranges() {
+        let xml = r#"<w:document><w:body>
+            <w:permStart w:id='1' w:ed='reviewer@example.com' />
+            <w:p><w:r><w:t>First</w:t></w:r></w:p>
+            <w:p>
+                <w:r><w:t>Second</w:t></w:r>
+                <w:permEnd w:id='1' />
+                <w:permStart w:id='2' w:edGrp='editors' />
+                <w:r><w:t> third</w:t></w:r>
+            </w:p>
+            <w:p><w:r><w:t>Fourth</w:t></w:r><w:permEnd w:id='2' /></w:p>
+            <w:p><w:permStart w:id='3' w:edGrp='everyone' /><w:r><w:t>Open</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let ranges = document.editable_ranges();
+        assert_eq!(ranges.len(), 3);
+
+        assert_eq!(ranges[0].text, "First\nSecond");
+        assert_eq!(ranges[0].paragraphs, vec![vec![1], vec![2]]);
+        assert!(ranges[0].is_closed);
+        assert!(ranges[0].is_editable_by("Reviewer@example.com"));
+        assert!(!ranges[0].is_editable_by_group(EdGrp::Editors));
+
+        assert_eq!(ranges[1].text, " third\nFourth");
+        assert_eq!(ranges[1].paragraphs, vec![vec![2], vec![3]]);
+        assert!(ranges[1].is_editable_by_group(EdGrp::Editors));
+        assert!(!ranges[1].is_editable_by_group(EdGrp::Owners));
+
+        assert_eq!(ranges[2].text, "Open");
+        assert!(!ranges[2].is_closed);
+        assert!(ranges[2].is_editable_by_group(EdGrp::Owners));
+    }
+
+    #[test]
+    pub fn test_settings_protection() {
+        let xml = r#"<w:settings>
+            <w:writeProtection w:recommended='1' />
+            <w:documentProtection w:edit='readOnly' w:enforcement='1' w:cryptProviderType='rsaAES'
+                w:cryptAlgorithmClass='hash' w:cryptAlgorithmType='typeAny' w:cryptAlgorithmSid='14'
+                w:cryptSpinCount='100000' w:hash='aGFzaA==' w:salt='c2FsdA==' />
+        </w:settings>"#;
+        let settings = Settings::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let protection = settings.protection();
+        assert_eq!(protection.edit, DocProtectType::ReadOnly);
+        assert!(protection.is_editing_restricted());
+        assert!(protection.is_read_only_recommended);
+        assert!(protection.write_password.is_none());
+
+        let password = protection.password.unwrap();
+        assert_eq!(password.legacy.crypt_algorithm_sid, Some(14));
+        assert_eq!(password.legacy.crypt_spin_count, Some(100_000));
+        assert_eq!(password.legacy.salt.as_deref(), Some("c2FsdA=="));
+    }
+}
@@ -0,0 +1,299 @@
+use super::{
+    text::RevisionView,
+    textmap::{RunPath, TextSpan},
+    wml::document::{Document, RunInnerContent, Text, R},
+};
+
+impl Text {
+    /// Splits the text at the character index. The text keeps the characters before the index, the returned text
+    /// contains the rest. Whitespaces at the new boundaries are preserved.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is greater than the number of characters of the text.
+    pub fn split_off(&mut self, at: usize) -> Text {
+        let rest = self.text.split_off(byte_offset(&self.text, at));
+        self.preserve_whitespaces();
+        let mut rest = Text {
+            text: rest,
+            xml_space: self.xml_space.clone(),
+        };
+        rest.preserve_whitespaces();
+        rest
+    }
+
+    /// Replaces the characters in the range `start..end` with the replacement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of the bounds of the text.
+    pub fn replace_range(&mut self, start: usize, end: usize, replacement: &str) {
+        let start = byte_offset(&self.text, start);
+        let end = start + byte_offset(&self.text[start..], end - start);
+        self.text.replace_range(start..end, replacement);
+        self.preserve_whitespaces();
+    }
+
+    fn preserve_whitespaces(&mut self) {
+        if self.text.starts_with(char::is_whitespace) || self.text.ends_with(char::is_whitespace) {
+            self.xml_space = Some(String::from("preserve"));
+        }
+    }
+}
+
+impl R {
+    /// Splits the run at a position of its contents. The run keeps the contents before the position, the returned run
+    /// has the same properties and contains the rest. If the position is inside a text content, the text is split in
+    /// two, otherwise the contents are split before the content at `content_index`.
+    ///
+    /// ```
+    /// use oox::docx::wml::document::{R, RunInnerContent};
+    ///
+    /// let mut run = R::builder().text("Hello world").tab().build();
+    /// let rest = run.split_off(0, 5);
+    /// assert!(matches!(&run.run_inner_contents[..], [RunInnerContent::Text(text)] if text.text == "Hello"));
+    /// assert!(matches!(&rest.run_inner_contents[0], RunInnerContent::Text(text) if text.text == " world"));
+    /// assert_eq!(rest.run_inner_contents.len(), 2);
+    /// ```
+    pub fn split_off(&mut self, content_index: usize, char_index: usize) -> R {
+        let mut split_index = content_index.min(self.run_inner_contents.len());
+        let split_text = match self.run_inner_contents.get_mut(content_index) {
+            Some(RunInnerContent::Text(text)) if char_index > 0 => {
+                Some(RunInnerContent::Text(text.split_off(char_index)))
+            }
+            Some(RunInnerContent::DeletedText(text)) if char_index > 0 => {
+                Some(RunInnerContent::DeletedText(text.split_off(char_index)))
+            }
+            Some(RunInnerContent::InstructionText(text)) if char_index > 0 => {
+                Some(RunInnerContent::InstructionText(text.split_off(char_index)))
+            }
+            Some(RunInnerContent::DeletedInstructionText(text)) if char_index > 0 => {
+                Some(RunInnerContent::DeletedInstructionText(text.split_off(char_index)))
+            }
+            _ => None,
+        };
+
+        if split_text.is_some() {
+            split_index += 1;
+        }
+
+        let mut run_inner_contents: Vec<_> = split_text.into_iter().collect();
+        run_inner_contents.extend(self.run_inner_contents.drain(split_index..));
+        R {
+            run_properties: self.run_properties.clone(),
+            run_inner_contents,
+            run_properties_revision_id: self.run_properties_revision_id,
+            deletion_revision_id: self.deletion_revision_id,
+            run_revision_id: self.run_revision_id,
+        }
+    }
+}
+
+/// A replacement of characters of a single run content.
+struct ContentEdit {
+    path: RunPath,
+    content_index: usize,
+    start: usize,
+    end: usize,
+    replacement: String,
+    /// Whether every character of the run content is replaced
+    is_whole: bool,
+}
+
+impl Document {
+    /// Replaces every occurrence of the pattern in the text of the document with the replacement and returns the
+    /// number of replaced occurrences. Matches are found in the text with tracked changes accepted, see
+    /// [`Document::text_map`], so they can span several runs, e.g. when only a part of a word is bold.
+    ///
+    /// The characters of the replacement are distributed to the runs of the match in order, so every replaced
+    /// character keeps the formatting of the character it replaces. When the replacement is longer than the match,
+    /// the extra characters get the formatting of the last character of the match. Tabs and breaks in the match are
+    /// replaced with text. Matches spanning several paragraphs or table cells are skipped.
+    ///
+    /// ```
+    /// use oox::{docx::wml::document::Document, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r><w:t xml:space="preserve">Dear </w:t></w:r>
+    ///     <w:r><w:rPr><w:b /></w:rPr><w:t>{na</w:t></w:r>
+    ///     <w:r><w:t>me}!</w:t></w:r>
+    /// </w:p></w:body></w:document>"#;
+    /// let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// assert_eq!(document.replace_text("{name}", "John"), 1);
+    /// ```
+    pub fn replace_text(&mut self, pattern: &str, replacement: &str) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+
+        let text_map = self.text_map(RevisionView::Accepted);
+        let mut edits = Vec::new();
+        let mut count = 0;
+        let mut char_offset = 0;
+        let mut byte_position = 0;
+        for (byte_start, _) in text_map.text.match_indices(pattern) {
+            char_offset += text_map.text[byte_position..byte_start].chars().count();
+            byte_position = byte_start;
+            let start = char_offset;
+            let end = start + pattern.chars().count();
+            if let Some(match_edits) = match_edits(text_map.spans_between(start, end), start, end, replacement) {
+                edits.extend(match_edits);
+                count += 1;
+            }
+        }
+
+        // Edits are applied backwards, so the indexes of the remaining edits stay valid.
+        for edit in edits.into_iter().rev() {
+            if let Some(run) = self.run_at_mut(&edit.path) {
+                apply_edit(run, edit);
+            }
+        }
+
+        count
+    }
+}
+
+/// Distributes the replacement to the spans of a match. Returns `None` if the match contains characters not mapped to
+/// any run.
+fn match_edits(spans: &[TextSpan], start: usize, end: usize, replacement: &str) -> Option<Vec<ContentEdit>> {
+    let mut position = start;
+    let mut replacement_chars = replacement.chars();
+    let mut edits = Vec::with_capacity(spans.len());
+    for (index, span) in spans.iter().enumerate() {
+        if span.start > position {
+            return None;
+        }
+
+        let span_end = span.end.min(end);
+        let replaced_count = span_end - position;
+        let replacement = if index + 1 == spans.len() {
+            replacement_chars.by_ref().collect()
+        } else {
+            replacement_chars.by_ref().take(replaced_count).collect()
+        };
+
+        edits.push(ContentEdit {
+            path: span.path.clone(),
+            content_index: span.content_index,
+            start: position - span.start,
+            end: span_end - span.start,
+            replacement,
+            is_whole: position == span.start && span_end == span.end,
+        });
+        position = span_end;
+    }
+
+    if position == end {
+        Some(edits)
+    } else {
+        None
+    }
+}
+
+fn apply_edit(run: &mut R, edit: ContentEdit) {
+    let content_index = edit.content_index;
+    match run.run_inner_contents.get_mut(content_index) {
+        Some(_) if edit.is_whole && edit.replacement.is_empty() => {
+            run.run_inner_contents.remove(content_index);
+        }
+        Some(RunInnerContent::Text(text)) => text.replace_range(edit.start, edit.end, &edit.replacement),
+        Some(content) => {
+            *content = RunInnerContent::Text(Text {
+                text: edit.replacement,
+                xml_space: Some(String::from("preserve")),
+            })
+        }
+        None => (),
+    }
+}
+
+fn byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(offset, _)| offset)
+        .unwrap_or_else(|| {
+            assert_eq!(char_index, text.chars().count(), "character index out of bounds");
+            text.len()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{text::RevisionView, wml::document::RunInnerContent},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn run_texts(document: &Document) -> Vec<(bool, String)> {
+        document
+            .runs_with_paths()
+            .into_iter()
+            .map(|(_, run)| {
+                let text = run
+                    .run_inner_contents
+                    .iter()
+                    .filter_map(|content| match content {
+                        RunInnerContent::Text(text) => Some(text.text.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                (run.run_properties.is_some(), text)
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn test_text_split_off() {
+        let mut text = Text {
+            text: String::from("Héllo world"),
+            xml_space: None,
+        };
+        let rest = text.split_off(5);
+        assert_eq!(text.text, "Héllo");
+        assert_eq!(text.xml_space, None);
+        assert_eq!(rest.text, " world");
+        assert_eq!(rest.xml_space, Some(String::from("preserve")));
+
+        text.replace_range(1, 2, "e");
+        assert_eq!(text.text, "Hello");
+    }
+
+    #[test]
+    pub fn test_replace_text() {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Dear </w:t></w:r>
+                <w:r><w:rPr><w:b /></w:rPr><w:t>{na</w:t></w:r>
+                <w:r><w:t>me}, {name}!</w:t></w:r>
+            </w:p>
+            <w:p><w:r><w:t>{name</w:t></w:r></w:p>
+            <w:p><w:r><w:t>}</w:t></w:r></w:p>
+            <w:p><w:r><w:t>a</w:t><w:tab /><w:t>b</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(document.replace_text("{name}", "Alexandra"), 2);
+        assert_eq!(
+            run_texts(&document),
+            vec![
+                (false, String::from("Dear ")),
+                (true, String::from("Ale")),
+                (false, String::from("xandra, Alexandra!")),
+                (false, String::from("{name")),
+                (false, String::from("}")),
+                (false, String::from("ab")),
+            ]
+        );
+
+        assert_eq!(document.replace_text("a\tb", "a b"), 1);
+        assert_eq!(document.replace_text("Alexandra", ""), 2);
+        assert_eq!(
+            document.text_map(RevisionView::Accepted).text,
+            "Dear , !\n{name\n}\na b"
+        );
+    }
+}
@@ -3,13 +3,15 @@ use super::wml::{
         Border, Color, EastAsianLayout, Em, FitText, Fonts, HighlightColor, HpsMeasure, Language, PPrBase, RPrBase,
         Shd, SignedHpsMeasure, SignedTwipsMeasure, TextEffect, Underline,
     },
+    extensions::RunPropertyExtension,
     simpletypes::TextScale,
     styles::Style,
 };
 use crate::{
     shared::sharedtypes::{OnOff, VerticalAlignRun},
-    update::{update_options, Update},
+    update::{update_options, update_toggle, ToggleMode, Update},
 };
+use std::mem::discriminant;
 
 pub type ParagraphProperties = PPrBase;
 
@@ -54,6 +56,8 @@ pub struct RunProperties {
     pub east_asian_layout: Option<EastAsianLayout>,
     pub special_vanish: Option<OnOff>,
     pub o_math: Option<OnOff>,
+    /// The run properties of the Word 2010 extensions. Contains at most one extension of each kind.
+    pub extensions: Vec<RunPropertyExtension>,
 }
 
 impl RunProperties {
@@ -107,6 +111,9 @@ impl RunProperties {
                     RPrBase::EastAsianLayout(ea_layout) => instance.east_asian_layout = Some(*ea_layout),
                     RPrBase::SpecialVanish(vanish) => instance.special_vanish = Some(*vanish),
                     RPrBase::OMath(o_math) => instance.o_math = Some(*o_math),
+                    RPrBase::Extension(extension) => {
+                        instance.extensions = update_extensions(instance.extensions, vec![extension.clone()])
+                    }
                 }
 
                 instance
@@ -114,24 +121,37 @@ impl RunProperties {
     }
 
     pub fn update_with(self, other: Self) -> Self {
+        self.update_with_mode(other, ToggleMode::Override)
+    }
+
+    /// Updates the properties with the properties of a character style applied on another level of the style
+    /// hierarchy, e.g. a run style over a paragraph style. Toggle properties are toggled, see [`ToggleMode::Toggle`].
+    pub fn update_with_style_on_another_level(self, other: Self) -> Self {
+        self.update_with_mode(other, ToggleMode::Toggle)
+    }
+
+    /// Updates the properties with `other`, combining the toggle properties (`b`, `bCs`, `caps`, `emboss`, `i`,
+    /// `iCs`, `imprint`, `outline`, `shadow`, `smallCaps`, `strike` and `vanish`) according to `mode`.
+    pub fn update_with_mode(self, other: Self, mode: ToggleMode) -> Self {
+        let toggle = |lhs, rhs| update_toggle(lhs, rhs, mode);
         Self {
             style: other.style.or(self.style),
             fonts: update_options(self.fonts, other.fonts),
-            bold: other.bold.or(self.bold),
-            complex_script_bold: other.complex_script_bold.or(self.complex_script_bold),
-            italic: other.italic.or(self.italic),
-            complex_script_italic: other.complex_script_italic.or(self.complex_script_italic),
-            all_capitals: other.all_capitals.or(self.all_capitals),
-            all_small_capitals: other.all_small_capitals.or(self.all_small_capitals),
-            strikethrough: other.strikethrough.or(self.strikethrough),
+            bold: toggle(self.bold, other.bold),
+            complex_script_bold: toggle(self.complex_script_bold, other.complex_script_bold),
+            italic: toggle(self.italic, other.italic),
+            complex_script_italic: toggle(self.complex_script_italic, other.complex_script_italic),
+            all_capitals: toggle(self.all_capitals, other.all_capitals),
+            all_small_capitals: toggle(self.all_small_capitals, other.all_small_capitals),
+            strikethrough: toggle(self.strikethrough, other.strikethrough),
             double_strikethrough: other.double_strikethrough.or(self.double_strikethrough),
-            outline: other.outline.or(self.outline),
-            shadow: other.shadow.or(self.shadow),
-            emboss: other.emboss.or(self.emboss),
-            imprint: other.imprint.or(self.imprint),
+            outline: toggle(self.outline, other.outline),
+            shadow: toggle(self.shadow, other.shadow),
+            emboss: toggle(self.emboss, other.emboss),
+            imprint: toggle(self.imprint, other.imprint),
             no_proofing: other.no_proofing.or(self.no_proofing),
             snap_to_grid: other.snap_to_grid.or(self.snap_to_grid),
-            vanish: other.vanish.or(self.vanish),
+            vanish: toggle(self.vanish, other.vanish),
             web_hidden: other.web_hidden.or(self.web_hidden),
             color: update_options(self.color, other.color),
             spacing: other.spacing.or(self.spacing),
@@ -154,35 +174,28 @@ impl RunProperties {
             east_asian_layout: update_options(self.east_asian_layout, other.east_asian_layout),
             special_vanish: other.special_vanish.or(self.special_vanish),
             o_math: other.o_math.or(self.o_math),
+            extensions: update_extensions(self.extensions, other.extensions),
         }
     }
+}
 
-    pub fn update_with_style_on_another_level(self, other: Self) -> Self {
-        Self {
-            bold: update_or_toggle_on_off(self.bold, other.bold),
-            complex_script_bold: update_or_toggle_on_off(self.complex_script_bold, other.complex_script_bold),
-            italic: update_or_toggle_on_off(self.italic, other.italic),
-            complex_script_italic: update_or_toggle_on_off(self.complex_script_italic, other.complex_script_italic),
-            all_capitals: update_or_toggle_on_off(self.all_capitals, other.all_capitals),
-            all_small_capitals: update_or_toggle_on_off(self.all_small_capitals, other.all_small_capitals),
-            strikethrough: update_or_toggle_on_off(self.strikethrough, other.strikethrough),
-            double_strikethrough: update_or_toggle_on_off(self.double_strikethrough, other.double_strikethrough),
-            outline: update_or_toggle_on_off(self.outline, other.outline),
-            shadow: update_or_toggle_on_off(self.shadow, other.shadow),
-            emboss: update_or_toggle_on_off(self.emboss, other.emboss),
-            imprint: update_or_toggle_on_off(self.imprint, other.imprint),
-            no_proofing: update_or_toggle_on_off(self.no_proofing, other.no_proofing),
-            snap_to_grid: update_or_toggle_on_off(self.snap_to_grid, other.snap_to_grid),
-            vanish: update_or_toggle_on_off(self.vanish, other.vanish),
-            web_hidden: update_or_toggle_on_off(self.web_hidden, other.web_hidden),
-            rtl: update_or_toggle_on_off(self.rtl, other.rtl),
-            complex_script: update_or_toggle_on_off(self.complex_script, self.complex_script),
-            special_vanish: update_or_toggle_on_off(self.special_vanish, other.special_vanish),
-            o_math: update_or_toggle_on_off(self.o_math, other.o_math),
-            ..self.update_with(other)
-        }
-    }
+/// Overrides the extensions with the extensions of the same kind from `other`.
+fn update_extensions(
+    extensions: Vec<RunPropertyExtension>,
+    other: Vec<RunPropertyExtension>,
+) -> Vec<RunPropertyExtension> {
+    let mut extensions: Vec<_> = extensions
+        .into_iter()
+        .filter(|extension| {
+            !other
+                .iter()
+                .any(|other_extension| discriminant(other_extension) == discriminant(extension))
+        })
+        .collect();
+    extensions.extend(other);
+    extensions
 }
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ResolvedStyle {
     pub paragraph_properties: Box<ParagraphProperties>,
@@ -252,9 +265,36 @@ impl ResolvedStyle {
     }
 }
 
-fn update_or_toggle_on_off(lhs: Option<OnOff>, rhs: Option<OnOff>) -> Option<OnOff> {
-    match (lhs, rhs) {
-        (Some(lhs), Some(rhs)) => Some(lhs ^ rhs),
-        (lhs, rhs) => rhs.or(lhs),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_update_with_toggle_mode() {
+        let paragraph_style = RunProperties {
+            bold: Some(true),
+            italic: Some(true),
+            no_proofing: Some(true),
+            ..Default::default()
+        };
+        let run_style = RunProperties {
+            bold: Some(true),
+            italic: Some(false),
+            no_proofing: Some(true),
+            all_capitals: Some(true),
+            ..Default::default()
+        };
+
+        let toggled = paragraph_style
+            .clone()
+            .update_with_mode(run_style.clone(), ToggleMode::Toggle);
+        assert_eq!(toggled.bold, Some(false));
+        assert_eq!(toggled.italic, Some(true));
+        assert_eq!(toggled.all_capitals, Some(true));
+        assert_eq!(toggled.no_proofing, Some(true));
+
+        let overridden = paragraph_style.update_with(run_style);
+        assert_eq!(overridden.bold, Some(true));
+        assert_eq!(overridden.italic, Some(false));
     }
 }
@@ -0,0 +1,1218 @@
+use super::{
+    text::{for_each_inline_in_view, track_change_items, InlineItem, RevisionView, RunItem},
+    textmap::{collect_block_paragraphs, collect_cell_paragraphs},
+    visit::{walk_cell, walk_paragraph, walk_row, walk_run_level_element, walk_table, walk_table_mut, Visit, VisitMut},
+    wml::{
+        document::{
+            BlockLevelElts, ContentBlockContent, ContentRunContent, Document, PContent, PPr, PPrBase,
+            ParaRPrTrackChanges, RPrBase, RunInnerContent, RunLevelElts, RunTrackChange, RunTrackChangeChoice, SectPr,
+            SectPrContents, TrackChange, P, R,
+        },
+        simpletypes::{DateTime, DecimalNumber},
+        table::{CellMarkupElements, ContentCellContent, ContentRowContent, Row, Tbl, Tc},
+    },
+};
+use std::mem;
+
+/// Lists the names of the fields that differ between two property structs as [`PropertyDelta::Field`] deltas.
+macro_rules! changed_fields {
+    ($original:expr, $current:expr, [$($field:ident),* $(,)?]) => {{
+        let (original, current) = ($original, $current);
+        let mut deltas = Vec::new();
+        $(
+            if original.$field != current.$field {
+                deltas.push(PropertyDelta::Field(stringify!($field)));
+            }
+        )*
+        deltas
+    }};
+}
+
+/// Whether revisions are accepted or rejected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevisionAction {
+    Accept,
+    Reject,
+}
+
+/// Selects the revisions to accept or reject.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevisionFilter<'a> {
+    All,
+    /// Revisions made by the given author. Table grid changes don't record an author, so they are only selected by
+    /// [`RevisionFilter::All`].
+    Author(&'a str),
+}
+
+impl RevisionFilter<'_> {
+    fn matches(&self, change: &TrackChange) -> bool {
+        match self {
+            RevisionFilter::All => true,
+            RevisionFilter::Author(author) => *author == change.author,
+        }
+    }
+}
+
+/// The kind of a tracked change, see [`Revision`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RevisionKind {
+    Insertion,
+    Deletion,
+    MoveFrom,
+    MoveTo,
+    ParagraphMarkInsertion,
+    ParagraphMarkDeletion,
+    ParagraphMarkMoveFrom,
+    ParagraphMarkMoveTo,
+    RunProperties,
+    ParagraphMarkProperties,
+    ParagraphProperties,
+    SectionProperties,
+    TableProperties,
+    RowPropertyExceptions,
+    RowProperties,
+    RowInsertion,
+    RowDeletion,
+    CellProperties,
+    CellInsertion,
+    CellDeletion,
+    CellMerge,
+}
+
+/// A property changed by a property revision.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyDelta {
+    /// A run property of a run or a paragraph mark. `original` is `None` if the property was added, `current` is
+    /// `None` if it was removed.
+    Run {
+        original: Option<RPrBase>,
+        current: Option<RPrBase>,
+    },
+    /// The name of a changed field of the paragraph, section, table, row or cell properties, e.g. `alignment`.
+    Field(&'static str),
+}
+
+/// A tracked change of a document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    pub kind: RevisionKind,
+    pub id: DecimalNumber,
+    pub author: String,
+    pub date: Option<DateTime>,
+    /// The affected text with both the inserted and the deleted text included. For paragraph level changes it's the
+    /// text of the paragraph, for table level changes the text of the row or cell with one line per paragraph.
+    pub text: String,
+    /// The changed properties of a property revision, empty for the other kinds.
+    pub deltas: Vec<PropertyDelta>,
+}
+
+/// The revisions made by an author at the same date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionGroup {
+    pub author: String,
+    pub date: Option<DateTime>,
+    pub revisions: Vec<Revision>,
+}
+
+/// The tracked changes of a document grouped by author and date, see [`Document::revision_report`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RevisionReport {
+    /// The groups in order of their first revision in the document.
+    pub groups: Vec<RevisionGroup>,
+}
+
+impl RevisionReport {
+    /// Returns every revision of the report, group by group.
+    pub fn revisions(&self) -> impl Iterator<Item = &Revision> {
+        self.groups.iter().flat_map(|group| group.revisions.iter())
+    }
+
+    /// Returns the authors of the revisions in order of their first revision.
+    pub fn authors(&self) -> Vec<&str> {
+        let mut authors: Vec<&str> = Vec::new();
+        for group in &self.groups {
+            if !authors.contains(&group.author.as_str()) {
+                authors.push(&group.author);
+            }
+        }
+
+        authors
+    }
+}
+
+impl Document {
+    /// Accepts every tracked change of the main document. Returns the number of revisions accepted.
+    ///
+    /// ```
+    /// use oox::{docx::wml::document::Document, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:ins w:id="1" w:author="Jane"><w:r><w:t>new</w:t></w:r></w:ins>
+    ///     <w:del w:id="2" w:author="Jane"><w:r><w:delText>old</w:delText></w:r></w:del>
+    /// </w:p></w:body></w:document>"#;
+    ///
+    /// let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// assert_eq!(document.accept_all_revisions(), 2);
+    /// assert_eq!(document.accept_all_revisions(), 0);
+    /// ```
+    pub fn accept_all_revisions(&mut self) -> usize {
+        self.apply_revisions(RevisionAction::Accept, RevisionFilter::All)
+    }
+
+    /// Rejects every tracked change of the main document. Returns the number of revisions rejected.
+    pub fn reject_all_revisions(&mut self) -> usize {
+        self.apply_revisions(RevisionAction::Reject, RevisionFilter::All)
+    }
+
+    pub fn accept_revisions_by(&mut self, author: &str) -> usize {
+        self.apply_revisions(RevisionAction::Accept, RevisionFilter::Author(author))
+    }
+
+    pub fn reject_revisions_by(&mut self, author: &str) -> usize {
+        self.apply_revisions(RevisionAction::Reject, RevisionFilter::Author(author))
+    }
+
+    /// Accepts or rejects the selected revisions of the main document. Returns the number of revisions applied.
+    ///
+    /// Inserted and moved-to content is unwrapped on accept and removed on reject, deleted and moved-from content is
+    /// removed on accept and restored on reject. Property changes are dropped on accept and replaced by the recorded
+    /// original properties on reject. Removing the mark of a paragraph merges it with the following paragraph.
+    pub fn apply_revisions(&mut self, action: RevisionAction, filter: RevisionFilter<'_>) -> usize {
+        let mut transform = RevisionTransform {
+            action,
+            filter,
+            count: 0,
+        };
+
+        transform.visit_document_mut(self);
+        transform.count
+    }
+
+    /// Collects every tracked change of the main document into a report grouped by author and date. Table grid changes
+    /// don't record an author, so they aren't reported.
+    ///
+    /// ```
+    /// use oox::{
+    ///     docx::{revisions::RevisionKind, wml::document::Document},
+    ///     xml::XmlNode,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:ins w:id="1" w:author="Jane" w:date="2020-01-01T10:00:00Z"><w:r><w:t>new</w:t></w:r></w:ins>
+    ///     <w:del w:id="2" w:author="John"><w:r><w:delText>old</w:delText></w:r></w:del>
+    /// </w:p></w:body></w:document>"#;
+    ///
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// let report = document.revision_report();
+    /// assert_eq!(report.authors(), vec!["Jane", "John"]);
+    /// assert_eq!(report.groups[0].revisions[0].kind, RevisionKind::Insertion);
+    /// assert_eq!(report.groups[1].revisions[0].text, "old");
+    /// ```
+    pub fn revision_report(&self) -> RevisionReport {
+        let mut collector = RevisionCollector::default();
+        collector.visit_document(self);
+
+        let mut report = RevisionReport::default();
+        for revision in collector.revisions {
+            let group = report
+                .groups
+                .iter_mut()
+                .find(|group| group.author == revision.author && group.date == revision.date);
+            match group {
+                Some(group) => group.revisions.push(revision),
+                None => report.groups.push(RevisionGroup {
+                    author: revision.author.clone(),
+                    date: revision.date.clone(),
+                    revisions: vec![revision],
+                }),
+            }
+        }
+
+        report
+    }
+}
+
+/// What happens to a run level element once the revisions are applied.
+enum Resolution {
+    Keep(RunLevelElts),
+    Remove,
+    Unwrap(Vec<RunTrackChangeChoice>),
+}
+
+struct RevisionTransform<'a> {
+    action: RevisionAction,
+    filter: RevisionFilter<'a>,
+    count: usize,
+}
+
+impl RevisionTransform<'_> {
+    fn is_accept(&self) -> bool {
+        self.action == RevisionAction::Accept
+    }
+
+    /// Takes the change out of the slot if it's selected by the filter.
+    fn take_selected<T>(&mut self, slot: &mut Option<T>, track_change: impl Fn(&T) -> &TrackChange) -> Option<T> {
+        if matches!(slot, Some(value) if self.filter.matches(track_change(value))) {
+            self.count += 1;
+            slot.take()
+        } else {
+            None
+        }
+    }
+
+    /// Applies the revisions of a paragraph. Returns whether the paragraph mark has been removed.
+    fn paragraph(&mut self, paragraph: &mut P) -> bool {
+        let mark_removed = match &mut paragraph.properties {
+            Some(properties) => self.paragraph_properties(properties),
+            None => false,
+        };
+
+        self.visit_paragraph_contents_mut(&mut paragraph.contents);
+        mark_removed
+    }
+
+    fn paragraph_properties(&mut self, properties: &mut PPr) -> bool {
+        if let Some(change) = self.take_selected(&mut properties.properties_change, |change| &change.base) {
+            if !self.is_accept() {
+                properties.base = change.properties;
+            }
+        }
+
+        if let Some(section_properties) = &mut properties.section_properties {
+            self.visit_section_properties_mut(section_properties);
+        }
+
+        match &mut properties.run_properties {
+            Some(run_properties) => {
+                if let Some(change) = self.take_selected(&mut run_properties.change, |change| &change.base) {
+                    if !self.is_accept() {
+                        run_properties.bases = change.run_properties.bases;
+                    }
+                }
+
+                self.paragraph_mark(&mut run_properties.track_changes)
+            }
+            None => false,
+        }
+    }
+
+    /// Applies the insertion, deletion and move of a paragraph mark. Returns whether the mark has been removed.
+    fn paragraph_mark(&mut self, track_changes: &mut Option<ParaRPrTrackChanges>) -> bool {
+        let changes = match track_changes {
+            Some(changes) => changes,
+            None => return false,
+        };
+
+        let accept = self.is_accept();
+        let mut removed = false;
+        for slot in &mut [&mut changes.inserted, &mut changes.move_to] {
+            removed |= self.take_selected(slot, |change| change).is_some() && !accept;
+        }
+        for slot in &mut [&mut changes.deleted, &mut changes.move_from] {
+            removed |= self.take_selected(slot, |change| change).is_some() && accept;
+        }
+
+        if *changes == Default::default() {
+            *track_changes = None;
+        }
+
+        removed
+    }
+
+    fn run_content(&mut self, mut content: ContentRunContent, result: &mut Vec<ContentRunContent>) {
+        match content {
+            ContentRunContent::RunLevelElements(element) => match self.resolve(element) {
+                Resolution::Keep(element) => result.push(ContentRunContent::RunLevelElements(element)),
+                Resolution::Remove => (),
+                Resolution::Unwrap(choices) => {
+                    for RunTrackChangeChoice::ContentRunContent(content) in choices {
+                        self.run_content(content, result);
+                    }
+                }
+            },
+            _ => {
+                self.visit_run_content_mut(&mut content);
+                result.push(content);
+            }
+        }
+    }
+
+    /// Applies the revisions of a run level element outside of a paragraph, keeping only the run level elements of
+    /// unwrapped revisions.
+    fn run_level_element(&mut self, element: RunLevelElts) -> Vec<RunLevelElts> {
+        let mut contents = Vec::new();
+        self.run_content(ContentRunContent::RunLevelElements(element), &mut contents);
+        contents
+            .into_iter()
+            .filter_map(|content| match content {
+                ContentRunContent::RunLevelElements(element) => Some(element),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn resolve(&mut self, element: RunLevelElts) -> Resolution {
+        match element {
+            RunLevelElts::Insert(track_change) | RunLevelElts::MoveTo(track_change)
+                if self.filter.matches(&track_change.base) =>
+            {
+                self.count += 1;
+                if self.is_accept() {
+                    Resolution::Unwrap(track_change.choices)
+                } else {
+                    Resolution::Remove
+                }
+            }
+            RunLevelElts::Delete(mut track_change) | RunLevelElts::MoveFrom(mut track_change)
+                if self.filter.matches(&track_change.base) =>
+            {
+                self.count += 1;
+                if self.is_accept() {
+                    Resolution::Remove
+                } else {
+                    for RunTrackChangeChoice::ContentRunContent(content) in &mut track_change.choices {
+                        RestoreDeletedText.visit_run_content_mut(content);
+                    }
+                    Resolution::Unwrap(track_change.choices)
+                }
+            }
+            mut element => {
+                // The revisions nested in a revision that's not selected by the filter are still applied
+                self.visit_run_level_element_mut(&mut element);
+                Resolution::Keep(element)
+            }
+        }
+    }
+
+    /// Applies the revisions of a table row. Returns whether the row is kept.
+    fn row(&mut self, row: &mut Row) -> bool {
+        if let Some(property_exceptions) = &mut row.property_exceptions {
+            if let Some(change) = self.take_selected(&mut property_exceptions.change, |change| &change.base) {
+                if !self.is_accept() {
+                    property_exceptions.base = change.properties_ex;
+                }
+            }
+        }
+
+        let mut keep = true;
+        if let Some(properties) = &mut row.properties {
+            if let Some(change) = self.take_selected(&mut properties.change, |change| &change.base) {
+                if !self.is_accept() {
+                    properties.base = change.properties;
+                }
+            }
+
+            if self.take_selected(&mut properties.inserted, |change| change).is_some() {
+                keep &= self.is_accept();
+            }
+            if self.take_selected(&mut properties.deleted, |change| change).is_some() {
+                keep &= !self.is_accept();
+            }
+        }
+
+        if keep {
+            self.visit_cell_contents_mut(&mut row.contents);
+        }
+
+        keep
+    }
+
+    /// Applies the revisions of a table cell. Returns whether the cell is kept.
+    fn cell(&mut self, cell: &mut Tc) -> bool {
+        if let Some(properties) = &mut cell.properties {
+            if let Some(change) = self.take_selected(&mut properties.change, |change| &change.base) {
+                if !self.is_accept() {
+                    properties.base = change.properties;
+                }
+            }
+
+            let markup_element = &mut properties.base.markup_element;
+            let removed_on_accept = match markup_element {
+                Some(CellMarkupElements::Insertion(change)) if self.filter.matches(change) => Some(false),
+                Some(CellMarkupElements::Deletion(change)) if self.filter.matches(change) => Some(true),
+                _ => None,
+            };
+
+            if let Some(removed_on_accept) = removed_on_accept {
+                self.count += 1;
+                *markup_element = None;
+                if removed_on_accept == self.is_accept() {
+                    return false;
+                }
+            }
+        }
+
+        self.visit_blocks_mut(&mut cell.block_level_elements);
+        true
+    }
+}
+
+impl VisitMut for RevisionTransform<'_> {
+    fn visit_blocks_mut(&mut self, elements: &mut Vec<BlockLevelElts>) {
+        let mut result = Vec::with_capacity(elements.len());
+        let mut merge_into_next: Option<Box<P>> = None;
+
+        for element in mem::take(elements) {
+            match element {
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(mut paragraph)) => {
+                    let mark_removed = self.paragraph(&mut paragraph);
+                    if let Some(previous) = merge_into_next.take() {
+                        paragraph.contents.splice(0..0, previous.contents);
+                    }
+
+                    if mark_removed {
+                        merge_into_next = Some(paragraph);
+                    } else {
+                        result.push(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)));
+                    }
+                }
+                mut element => {
+                    // Paragraphs are only merged with a directly following paragraph
+                    if let Some(previous) = merge_into_next.take() {
+                        result.push(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(previous)));
+                    }
+
+                    match element {
+                        BlockLevelElts::Chunk(ContentBlockContent::RunLevelElement(element)) => {
+                            // Runs can't appear outside of paragraphs, only the run level elements of a revision are
+                            // kept
+                            result.extend(
+                                self.run_level_element(element).into_iter().map(|element| {
+                                    BlockLevelElts::Chunk(ContentBlockContent::RunLevelElement(element))
+                                }),
+                            );
+                        }
+                        _ => {
+                            self.visit_block_mut(&mut element);
+                            result.push(element);
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(previous) = merge_into_next {
+            result.push(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(previous)));
+        }
+
+        *elements = result;
+    }
+
+    fn visit_content_blocks_mut(&mut self, contents: &mut Vec<ContentBlockContent>) {
+        let mut elements = mem::take(contents).into_iter().map(BlockLevelElts::Chunk).collect();
+        self.visit_blocks_mut(&mut elements);
+        *contents = elements
+            .into_iter()
+            .filter_map(|element| match element {
+                BlockLevelElts::Chunk(content) => Some(content),
+                _ => None,
+            })
+            .collect();
+    }
+
+    fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+        let mut result = Vec::with_capacity(contents.len());
+        for mut content in mem::take(contents) {
+            match content {
+                PContent::ContentRunContent(content) => {
+                    let mut run_contents = Vec::new();
+                    self.run_content(*content, &mut run_contents);
+                    result.extend(
+                        run_contents
+                            .into_iter()
+                            .map(|content| PContent::ContentRunContent(Box::new(content))),
+                    );
+                }
+                _ => {
+                    self.visit_paragraph_content_mut(&mut content);
+                    result.push(content);
+                }
+            }
+        }
+
+        *contents = result;
+    }
+
+    /// Applies the revisions nested in a revision that's not selected by the filter.
+    fn visit_run_track_change_mut(&mut self, track_change: &mut RunTrackChange) {
+        let mut contents = Vec::new();
+        for RunTrackChangeChoice::ContentRunContent(content) in track_change.choices.drain(..) {
+            self.run_content(content, &mut contents);
+        }
+
+        track_change.choices = contents
+            .into_iter()
+            .map(RunTrackChangeChoice::ContentRunContent)
+            .collect();
+    }
+
+    fn visit_run_mut(&mut self, run: &mut R) {
+        if let Some(run_properties) = &mut run.run_properties {
+            if let Some(change) = self.take_selected(&mut run_properties.run_properties_change, |change| &change.base) {
+                if !self.is_accept() {
+                    run_properties.r_pr_bases = change.run_properties.r_pr_bases;
+                }
+            }
+        }
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Tbl) {
+        if let Some(change) = self.take_selected(&mut table.properties.change, |change| &change.base) {
+            if !self.is_accept() {
+                table.properties.base = change.properties;
+            }
+        }
+
+        if self.filter == RevisionFilter::All {
+            if let Some(change) = table.grid.change.take() {
+                self.count += 1;
+                if !self.is_accept() {
+                    table.grid.base = change.grid;
+                }
+            }
+        }
+
+        walk_table_mut(self, table);
+    }
+
+    fn visit_row_contents_mut(&mut self, contents: &mut Vec<ContentRowContent>) {
+        let mut result = Vec::with_capacity(contents.len());
+        for mut content in mem::take(contents) {
+            match content {
+                ContentRowContent::Table(mut row) => {
+                    if self.row(&mut row) {
+                        result.push(ContentRowContent::Table(row));
+                    }
+                }
+                ContentRowContent::RunLevelElements(element) => result.extend(
+                    self.run_level_element(element)
+                        .into_iter()
+                        .map(ContentRowContent::RunLevelElements),
+                ),
+                _ => {
+                    self.visit_row_content_mut(&mut content);
+                    result.push(content);
+                }
+            }
+        }
+
+        *contents = result;
+    }
+
+    fn visit_cell_contents_mut(&mut self, contents: &mut Vec<ContentCellContent>) {
+        let mut result = Vec::with_capacity(contents.len());
+        for mut content in mem::take(contents) {
+            match content {
+                ContentCellContent::Cell(mut cell) => {
+                    if self.cell(&mut cell) {
+                        result.push(ContentCellContent::Cell(cell));
+                    }
+                }
+                ContentCellContent::RunLevelElement(element) => result.extend(
+                    self.run_level_element(element)
+                        .into_iter()
+                        .map(ContentCellContent::RunLevelElement),
+                ),
+                _ => {
+                    self.visit_cell_content_mut(&mut content);
+                    result.push(content);
+                }
+            }
+        }
+
+        *contents = result;
+    }
+
+    fn visit_section_properties_mut(&mut self, section_properties: &mut SectPr) {
+        if let Some(change) = self.take_selected(&mut section_properties.change, |change| &change.base) {
+            if let (false, Some(original)) = (self.is_accept(), change.section_properties) {
+                section_properties.contents = original.contents;
+                section_properties.attributes = original.attributes;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct RevisionCollector {
+    revisions: Vec<Revision>,
+}
+
+impl RevisionCollector {
+    fn push(&mut self, kind: RevisionKind, change: &TrackChange, text: String, deltas: Vec<PropertyDelta>) {
+        self.revisions.push(Revision {
+            kind,
+            id: change.base.id,
+            author: change.author.clone(),
+            date: change.date.clone(),
+            text,
+            deltas,
+        });
+    }
+}
+
+impl Visit for RevisionCollector {
+    fn visit_paragraph(&mut self, paragraph: &P) {
+        if let Some(properties) = &paragraph.properties {
+            let text = paragraph_text(paragraph);
+            if let Some(change) = &properties.properties_change {
+                let deltas = paragraph_property_deltas(&change.properties, &properties.base);
+                self.push(RevisionKind::ParagraphProperties, &change.base, text.clone(), deltas);
+            }
+
+            if let Some(run_properties) = &properties.run_properties {
+                if let Some(changes) = &run_properties.track_changes {
+                    let marks = [
+                        (RevisionKind::ParagraphMarkInsertion, &changes.inserted),
+                        (RevisionKind::ParagraphMarkDeletion, &changes.deleted),
+                        (RevisionKind::ParagraphMarkMoveFrom, &changes.move_from),
+                        (RevisionKind::ParagraphMarkMoveTo, &changes.move_to),
+                    ];
+                    for (kind, change) in marks {
+                        if let Some(change) = change {
+                            self.push(kind, change, text.clone(), Vec::new());
+                        }
+                    }
+                }
+
+                if let Some(change) = &run_properties.change {
+                    let deltas = run_property_deltas(&change.run_properties.bases, &run_properties.bases);
+                    self.push(
+                        RevisionKind::ParagraphMarkProperties,
+                        &change.base,
+                        text.clone(),
+                        deltas,
+                    );
+                }
+            }
+        }
+
+        walk_paragraph(self, paragraph);
+    }
+
+    fn visit_run(&mut self, run: &R) {
+        if let Some(run_properties) = &run.run_properties {
+            if let Some(change) = &run_properties.run_properties_change {
+                let deltas = run_property_deltas(&change.run_properties.r_pr_bases, &run_properties.r_pr_bases);
+                self.push(RevisionKind::RunProperties, &change.base, run_text(run), deltas);
+            }
+        }
+    }
+
+    fn visit_run_level_element(&mut self, element: &RunLevelElts) {
+        let (kind, track_change) = match element {
+            RunLevelElts::Insert(track_change) => (RevisionKind::Insertion, track_change),
+            RunLevelElts::Delete(track_change) => (RevisionKind::Deletion, track_change),
+            RunLevelElts::MoveFrom(track_change) => (RevisionKind::MoveFrom, track_change),
+            RunLevelElts::MoveTo(track_change) => (RevisionKind::MoveTo, track_change),
+            _ => return,
+        };
+
+        self.push(kind, &track_change.base, track_change_text(track_change), Vec::new());
+        walk_run_level_element(self, element);
+    }
+
+    fn visit_table(&mut self, table: &Tbl) {
+        if let Some(change) = &table.properties.change {
+            let deltas = changed_fields!(
+                &change.properties,
+                &table.properties.base,
+                [
+                    style,
+                    paragraph_properties,
+                    overlap,
+                    bidirectional_visual,
+                    style_row_band_size,
+                    style_column_band_size,
+                    width,
+                    alignment,
+                    cell_spacing,
+                    indent,
+                    borders,
+                    shading,
+                    layout,
+                    cell_margin,
+                    look,
+                    caption,
+                    description,
+                ]
+            );
+            self.push(RevisionKind::TableProperties, &change.base, String::new(), deltas);
+        }
+
+        walk_table(self, table);
+    }
+
+    fn visit_row(&mut self, row: &Row) {
+        let mut paragraphs = Vec::new();
+        for content in &row.contents {
+            collect_cell_paragraphs(content, &mut Vec::new(), &mut paragraphs);
+        }
+        let text = paragraphs_text(paragraphs);
+
+        if let Some(property_exceptions) = &row.property_exceptions {
+            if let Some(change) = &property_exceptions.change {
+                let deltas = changed_fields!(
+                    &change.properties_ex,
+                    &property_exceptions.base,
+                    [
+                        width,
+                        alignment,
+                        cell_spacing,
+                        indent,
+                        borders,
+                        shading,
+                        layout,
+                        cell_margin,
+                        look
+                    ]
+                );
+                self.push(RevisionKind::RowPropertyExceptions, &change.base, text.clone(), deltas);
+            }
+        }
+
+        if let Some(properties) = &row.properties {
+            if let Some(change) = &properties.change {
+                let deltas = changed_fields!(
+                    &change.properties,
+                    &properties.base,
+                    [
+                        conditional_formatting,
+                        div_id,
+                        grid_column_before_first_cell,
+                        grid_column_after_last_cell,
+                        width_before_row,
+                        width_after_row,
+                        cant_split,
+                        row_height,
+                        header,
+                        cell_spacing,
+                        alignment,
+                        hidden,
+                    ]
+                );
+                self.push(RevisionKind::RowProperties, &change.base, text.clone(), deltas);
+            }
+
+            if let Some(change) = &properties.inserted {
+                self.push(RevisionKind::RowInsertion, change, text.clone(), Vec::new());
+            }
+
+            if let Some(change) = &properties.deleted {
+                self.push(RevisionKind::RowDeletion, change, text, Vec::new());
+            }
+        }
+
+        walk_row(self, row);
+    }
+
+    fn visit_cell(&mut self, cell: &Tc) {
+        if let Some(properties) = &cell.properties {
+            let mut paragraphs = Vec::new();
+            collect_block_paragraphs(&cell.block_level_elements, &mut Vec::new(), &mut paragraphs);
+            let text = paragraphs_text(paragraphs);
+
+            if let Some(change) = &properties.change {
+                let deltas = changed_fields!(
+                    &change.properties.base,
+                    &properties.base.base,
+                    [
+                        conditional_formatting,
+                        width,
+                        grid_span,
+                        vertical_merge,
+                        borders,
+                        shading,
+                        no_wrapping,
+                        margin,
+                        text_direction,
+                        fit_text,
+                        vertical_alignment,
+                        hide_marker,
+                        headers,
+                    ]
+                );
+                self.push(RevisionKind::CellProperties, &change.base, text.clone(), deltas);
+            }
+
+            match &properties.base.markup_element {
+                Some(CellMarkupElements::Insertion(change)) => {
+                    self.push(RevisionKind::CellInsertion, change, text, Vec::new())
+                }
+                Some(CellMarkupElements::Deletion(change)) => {
+                    self.push(RevisionKind::CellDeletion, change, text, Vec::new())
+                }
+                Some(CellMarkupElements::Merge(change)) => {
+                    self.push(RevisionKind::CellMerge, &change.base, text, Vec::new())
+                }
+                None => (),
+            }
+        }
+
+        walk_cell(self, cell);
+    }
+
+    fn visit_section_properties(&mut self, section_properties: &SectPr) {
+        if let Some(change) = &section_properties.change {
+            let default_contents = SectPrContents::default();
+            let original = change
+                .section_properties
+                .as_ref()
+                .and_then(|original| original.contents.as_ref())
+                .unwrap_or(&default_contents);
+            let current = section_properties.contents.as_ref().unwrap_or(&default_contents);
+            let deltas = changed_fields!(
+                original,
+                current,
+                [
+                    footnote_properties,
+                    endnote_properties,
+                    section_type,
+                    page_size,
+                    page_margin,
+                    paper_source,
+                    page_borders,
+                    line_number_type,
+                    page_number_type,
+                    columns,
+                    protect_form_fields,
+                    vertical_align,
+                    no_endnote,
+                    title_page,
+                    text_direction,
+                    bidirectional,
+                    rtl_gutter,
+                    document_grid,
+                    printer_settings,
+                ]
+            );
+            self.push(RevisionKind::SectionProperties, &change.base, String::new(), deltas);
+        }
+    }
+}
+
+/// Returns the text of a paragraph with both the inserted and the deleted text included.
+fn paragraph_text(paragraph: &P) -> String {
+    let mut text = String::new();
+    for_each_inline_in_view(&paragraph.contents, RevisionView::Original, &mut |item| match item {
+        InlineItem::Text(value) => text.push_str(value),
+        InlineItem::Tab => text.push('\t'),
+        InlineItem::Break(_) | InlineItem::NoteReference(..) => (),
+    });
+
+    text
+}
+
+fn paragraphs_text(paragraphs: Vec<(Vec<usize>, &P)>) -> String {
+    paragraphs
+        .into_iter()
+        .map(|(_, paragraph)| paragraph_text(paragraph))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn run_text(run: &R) -> String {
+    run.run_inner_contents
+        .iter()
+        .filter_map(|content| match content {
+            RunInnerContent::Text(text) | RunInnerContent::DeletedText(text) => Some(text.text.as_str()),
+            RunInnerContent::Tab => Some("\t"),
+            _ => None,
+        })
+        .collect()
+}
+
+fn track_change_text(track_change: &RunTrackChange) -> String {
+    let mut text = String::new();
+    track_change_items(&track_change.choices, RevisionView::Original, &mut |item| match item {
+        RunItem::Inline(_, InlineItem::Text(value)) => text.push_str(value),
+        RunItem::Inline(_, InlineItem::Tab) => text.push('\t'),
+        _ => (),
+    });
+
+    text
+}
+
+/// Compares the run properties by their kind, so a changed value is reported as a single delta.
+fn run_property_deltas(original: &[RPrBase], current: &[RPrBase]) -> Vec<PropertyDelta> {
+    let find = |properties: &[RPrBase], property: &RPrBase| {
+        properties
+            .iter()
+            .find(|other| mem::discriminant(*other) == mem::discriminant(property))
+            .cloned()
+    };
+
+    let mut deltas = Vec::new();
+    for property in current {
+        let original_property = find(original, property);
+        if original_property.as_ref() != Some(property) {
+            deltas.push(PropertyDelta::Run {
+                original: original_property,
+                current: Some(property.clone()),
+            });
+        }
+    }
+
+    for property in original {
+        if find(current, property).is_none() {
+            deltas.push(PropertyDelta::Run {
+                original: Some(property.clone()),
+                current: None,
+            });
+        }
+    }
+
+    deltas
+}
+
+fn paragraph_property_deltas(original: &PPrBase, current: &PPrBase) -> Vec<PropertyDelta> {
+    changed_fields!(
+        original,
+        current,
+        [
+            style,
+            keep_with_next,
+            keep_lines_on_one_page,
+            start_on_next_page,
+            frame_properties,
+            widow_control,
+            numbering_properties,
+            suppress_line_numbers,
+            borders,
+            shading,
+            tabs,
+            suppress_auto_hyphens,
+            kinsoku,
+            word_wrapping,
+            overflow_punctuations,
+            top_line_punctuations,
+            auto_space_latin_and_east_asian,
+            auto_space_east_asian_and_numbers,
+            bidirectional,
+            adjust_right_indent,
+            snap_to_grid,
+            spacing,
+            indent,
+            contextual_spacing,
+            mirror_indents,
+            suppress_overlapping,
+            alignment,
+            text_direction,
+            text_alignment,
+            textbox_tight_wrap,
+            outline_level,
+            div_id,
+            conditional_formatting,
+        ]
+    )
+}
+
+/// Turns the deleted text of restored content back into regular text.
+struct RestoreDeletedText;
+
+impl VisitMut for RestoreDeletedText {
+    // The revisions nested in restored content are resolved on their own
+    fn visit_run_level_element_mut(&mut self, _element: &mut RunLevelElts) {}
+
+    fn visit_run_inner_content_mut(&mut self, content: &mut RunInnerContent) {
+        let restored = match mem::replace(content, RunInnerContent::Tab) {
+            RunInnerContent::DeletedText(text) => RunInnerContent::Text(text),
+            RunInnerContent::DeletedInstructionText(text) => RunInnerContent::InstructionText(text),
+            other => other,
+        };
+        *content = restored;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::document::{RPrBase, Text},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn document_for_test() -> Document {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:pPr>
+                        <w:jc w:val="center" />
+                        <w:rPr><w:del w:id="1" w:author="Bob" /></w:rPr>
+                        <w:pPrChange w:id="2" w:author="Alice"><w:pPr><w:jc w:val="left" /></w:pPr></w:pPrChange>
+                    </w:pPr>
+                    <w:r><w:t>Keep </w:t></w:r>
+                    <w:ins w:id="3" w:author="Alice"><w:r><w:t>inserted </w:t></w:r></w:ins>
+                    <w:del w:id="4" w:author="Bob"><w:r><w:delText>deleted</w:delText></w:r></w:del>
+                </w:p>
+                <w:p>
+                    <w:r>
+                        <w:rPr>
+                            <w:b />
+                            <w:rPrChange w:id="5" w:author="Alice"><w:rPr><w:i /></w:rPr></w:rPrChange>
+                        </w:rPr>
+                        <w:t>next</w:t>
+                    </w:r>
+                </w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid />
+                    <w:tr><w:trPr><w:ins w:id="6" w:author="Alice" /></w:trPr><w:tc><w:p /></w:tc></w:tr>
+                    <w:tr><w:trPr><w:del w:id="7" w:author="Bob" /></w:trPr><w:tc><w:p /></w:tc></w:tr>
+                </w:tbl>
+            </w:body>
+        </w:document>"#;
+
+        Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    fn paragraph_texts(document: &Document) -> Vec<String> {
+        document
+            .body
+            .as_ref()
+            .unwrap()
+            .block_level_elements
+            .iter()
+            .filter_map(|element| match element {
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => Some(paragraph),
+                _ => None,
+            })
+            .map(|paragraph| {
+                let mut text = String::new();
+                for content in &paragraph.contents {
+                    match content {
+                        PContent::ContentRunContent(content) => match content.as_ref() {
+                            ContentRunContent::Run(run) => {
+                                for inner_content in &run.run_inner_contents {
+                                    if let RunInnerContent::Text(Text { text: t, .. }) = inner_content {
+                                        text.push_str(t);
+                                    }
+                                }
+                            }
+                            _ => text.push('?'),
+                        },
+                        _ => text.push('?'),
+                    }
+                }
+                text
+            })
+            .collect()
+    }
+
+    fn row_count(document: &Document) -> usize {
+        document
+            .body
+            .as_ref()
+            .unwrap()
+            .block_level_elements
+            .iter()
+            .find_map(|element| match element {
+                BlockLevelElts::Chunk(ContentBlockContent::Table(table)) => Some(table.row_contents.len()),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn first_run_properties(document: &Document) -> Vec<RPrBase> {
+        document
+            .body
+            .as_ref()
+            .unwrap()
+            .block_level_elements
+            .iter()
+            .filter_map(|element| match element {
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => Some(paragraph),
+                _ => None,
+            })
+            .next_back()
+            .and_then(|paragraph| match paragraph.contents.last()? {
+                PContent::ContentRunContent(content) => match content.as_ref() {
+                    ContentRunContent::Run(run) => Some(run.run_properties.as_ref()?.r_pr_bases.clone()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_accept_all_revisions() {
+        let mut document = document_for_test();
+        assert_eq!(document.accept_all_revisions(), 7);
+        assert_eq!(paragraph_texts(&document), vec!["Keep inserted next"]);
+        assert_eq!(first_run_properties(&document), vec![RPrBase::Bold(true)]);
+        assert_eq!(row_count(&document), 1);
+        assert_eq!(document.accept_all_revisions(), 0);
+    }
+
+    #[test]
+    pub fn test_reject_all_revisions() {
+        let mut document = document_for_test();
+        assert_eq!(document.reject_all_revisions(), 7);
+        assert_eq!(paragraph_texts(&document), vec!["Keep deleted", "next"]);
+        assert_eq!(first_run_properties(&document), vec![RPrBase::Italic(true)]);
+        assert_eq!(row_count(&document), 1);
+    }
+
+    #[test]
+    pub fn test_apply_revisions_by_author() {
+        let mut document = document_for_test();
+        assert_eq!(document.accept_revisions_by("Bob"), 3);
+        assert_eq!(paragraph_texts(&document), vec!["Keep ?next"]);
+        assert_eq!(row_count(&document), 1);
+
+        assert_eq!(document.reject_revisions_by("Alice"), 3);
+        assert_eq!(paragraph_texts(&document), vec!["Keep next"]);
+        assert_eq!(first_run_properties(&document), vec![RPrBase::Italic(true)]);
+        assert_eq!(row_count(&document), 0);
+    }
+
+    #[test]
+    pub fn test_revision_report() {
+        let report = document_for_test().revision_report();
+        assert_eq!(report.authors(), vec!["Alice", "Bob"]);
+        assert_eq!(report.groups.len(), 2);
+        assert_eq!(report.revisions().count(), 7);
+
+        let kinds: Vec<_> = report.groups[1]
+            .revisions
+            .iter()
+            .map(|revision| revision.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RevisionKind::ParagraphMarkDeletion,
+                RevisionKind::Deletion,
+                RevisionKind::RowDeletion
+            ]
+        );
+
+        let alice = &report.groups[0].revisions;
+        assert_eq!(alice[0].kind, RevisionKind::ParagraphProperties);
+        assert_eq!(alice[0].text, "Keep inserted deleted");
+        assert_eq!(alice[0].deltas, vec![PropertyDelta::Field("alignment")]);
+        assert_eq!(
+            (alice[1].kind, alice[1].text.as_str()),
+            (RevisionKind::Insertion, "inserted ")
+        );
+        assert_eq!(alice[2].kind, RevisionKind::RunProperties);
+        assert_eq!(
+            alice[2].deltas,
+            vec![
+                PropertyDelta::Run {
+                    original: None,
+                    current: Some(RPrBase::Bold(true)),
+                },
+                PropertyDelta::Run {
+                    original: Some(RPrBase::Italic(true)),
+                    current: None,
+                },
+            ]
+        );
+        assert_eq!(alice[3].kind, RevisionKind::RowInsertion);
+    }
+}
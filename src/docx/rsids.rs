@@ -0,0 +1,210 @@
+use super::{
+    package::Package,
+    textmap::RunPath,
+    wml::{document::RunInnerContent, simpletypes::LongHexNumber},
+};
+
+/// The content attributed to an editing session by the revision save ids (`w:rsid*`) of the main document, see
+/// [`Package::save_sessions`].
+///
+/// Word assigns a new revision save id to every editing session ended by a save and stamps the paragraphs and runs
+/// created in the session with it. Later edits don't replace the stamps of untouched content, so the sessions only
+/// approximate the history of the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSession {
+    pub revision_id: LongHexNumber,
+    /// The 0-based position of the revision save id in the rsid table of the settings, which lists the sessions in the
+    /// order they were saved. `None` if the table doesn't list it.
+    pub generation: Option<usize>,
+    /// Whether this is the session that created the document (`w:rsidRoot`).
+    pub is_root: bool,
+    /// The paths of the paragraphs created in the session, following the convention of [`RunPath::paragraph`].
+    pub paragraphs: Vec<Vec<usize>>,
+    /// The runs created in the session. Runs without a revision save id are attributed to the default run session of
+    /// their paragraph (`w:rsidRDefault`).
+    pub runs: Vec<RunPath>,
+    /// The number of characters of the text of [`SaveSession::runs`], including deleted text.
+    pub characters: usize,
+    /// The runs deleted as a tracked change in the session.
+    pub deleted_runs: Vec<RunPath>,
+}
+
+impl SaveSession {
+    fn new(revision_id: LongHexNumber) -> Self {
+        Self {
+            revision_id,
+            generation: None,
+            is_root: false,
+            paragraphs: Vec::new(),
+            runs: Vec::new(),
+            characters: 0,
+            deleted_runs: Vec::new(),
+        }
+    }
+}
+
+impl Package {
+    /// Groups the paragraphs and runs of the main document by the editing session that introduced them.
+    ///
+    /// The sessions listed in the rsid table of the settings come first in save order, followed by the unlisted ones
+    /// in order of their first appearance in the document.
+    ///
+    /// ```
+    /// use oox::docx::{
+    ///     package::Package,
+    ///     wml::{document::Document, settings::{DocRsids, Settings}},
+    /// };
+    /// # use oox::xml::XmlNode;
+    /// # use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body>
+    ///     <w:p w:rsidR="00000002" w:rsidRDefault="00000002"><w:r><w:t>Second</w:t></w:r></w:p>
+    ///     <w:p w:rsidR="00000001" w:rsidRDefault="00000001"><w:r><w:t>First</w:t></w:r></w:p>
+    /// </w:body></w:document>"#;
+    /// let package = Package {
+    ///     main_document: Some(Box::new(Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap())),
+    ///     settings: Some(Box::new(Settings {
+    ///         revision_ids: Some(DocRsids {
+    ///             revision_id_root: Some(1),
+    ///             revision_ids: vec![1, 2],
+    ///         }),
+    ///         ..Default::default()
+    ///     })),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let sessions = package.save_sessions();
+    /// assert_eq!(sessions[0].revision_id, 1);
+    /// assert_eq!(sessions[0].paragraphs, vec![vec![1]]);
+    /// assert_eq!(sessions[1].characters, 6);
+    /// ```
+    pub fn save_sessions(&self) -> Vec<SaveSession> {
+        let rsids = self
+            .settings
+            .as_ref()
+            .and_then(|settings| settings.revision_ids.as_ref());
+        let mut sessions: Vec<SaveSession> = rsids
+            .iter()
+            .flat_map(|rsids| rsids.revision_ids.iter())
+            .map(|revision_id| SaveSession::new(*revision_id))
+            .collect();
+
+        if let Some(document) = &self.main_document {
+            for (path, paragraph) in document.paragraphs() {
+                if let Some(revision_id) = paragraph.run_revision_id {
+                    session_mut(&mut sessions, revision_id).paragraphs.push(path);
+                }
+            }
+
+            for (path, run) in document.iter_runs() {
+                let revision_id = run.run_revision_id.or_else(|| {
+                    document
+                        .paragraph_at(&path.paragraph)
+                        .and_then(|paragraph| paragraph.run_default_revision_id)
+                });
+
+                if let Some(revision_id) = run.deletion_revision_id {
+                    session_mut(&mut sessions, revision_id).deleted_runs.push(path.clone());
+                }
+
+                if let Some(revision_id) = revision_id {
+                    let session = session_mut(&mut sessions, revision_id);
+                    session.characters += run_characters(&run.run_inner_contents);
+                    session.runs.push(path);
+                }
+            }
+        }
+
+        let listed_ids: Vec<LongHexNumber> = rsids.map_or_else(Vec::new, |rsids| rsids.revision_ids.clone());
+        for session in &mut sessions {
+            session.generation = listed_ids
+                .iter()
+                .position(|revision_id| *revision_id == session.revision_id);
+            session.is_root = rsids.and_then(|rsids| rsids.revision_id_root) == Some(session.revision_id);
+        }
+
+        // Sessions which are listed in the table but left no trace in the document are dropped
+        sessions.retain(|session| {
+            !(session.paragraphs.is_empty() && session.runs.is_empty() && session.deleted_runs.is_empty())
+        });
+        sessions
+    }
+}
+
+fn session_mut(sessions: &mut Vec<SaveSession>, revision_id: LongHexNumber) -> &mut SaveSession {
+    match sessions.iter().position(|session| session.revision_id == revision_id) {
+        Some(index) => &mut sessions[index],
+        None => {
+            sessions.push(SaveSession::new(revision_id));
+            sessions.last_mut().unwrap()
+        }
+    }
+}
+
+fn run_characters(contents: &[RunInnerContent]) -> usize {
+    contents
+        .iter()
+        .map(|content| match content {
+            RunInnerContent::Text(text) | RunInnerContent::DeletedText(text) => text.text.chars().count(),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            document::Document,
+            settings::{DocRsids, Settings},
+        },
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_save_sessions() {
+        let xml = r#"<w:document><w:body>
+            <w:p w:rsidR="00000001" w:rsidRDefault="00000001">
+                <w:r><w:t>Original</w:t></w:r>
+                <w:r w:rsidR="00000003"><w:t> text</w:t></w:r>
+                <w:del w:id="1" w:author="Jane">
+                    <w:r w:rsidR="00000001" w:rsidDel="00000002"><w:delText>old</w:delText></w:r>
+                </w:del>
+            </w:p>
+            <w:p w:rsidR="00000003" w:rsidRDefault="00000003"><w:r><w:t>Added</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap(),
+            )),
+            settings: Some(Box::new(Settings {
+                revision_ids: Some(DocRsids {
+                    revision_id_root: Some(1),
+                    revision_ids: vec![1, 2, 4],
+                }),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        let sessions = package.save_sessions();
+        let ids: Vec<_> = sessions
+            .iter()
+            .map(|session| (session.revision_id, session.generation))
+            .collect();
+        assert_eq!(ids, vec![(1, Some(0)), (2, Some(1)), (3, None)]);
+
+        assert!(sessions[0].is_root);
+        assert_eq!(sessions[0].paragraphs, vec![vec![0]]);
+        assert_eq!(sessions[0].runs.len(), 2);
+        assert_eq!(sessions[0].characters, 11);
+
+        assert!(sessions[1].runs.is_empty());
+        assert_eq!(sessions[1].deleted_runs[0].run, vec![2, 0]);
+
+        assert_eq!(sessions[2].paragraphs, vec![vec![1]]);
+        assert_eq!(sessions[2].characters, 10);
+    }
+}
@@ -0,0 +1,383 @@
+use super::{
+    package::Package,
+    visit::{
+        walk_cell_mut, walk_paragraph_contents_mut, walk_paragraph_mut, walk_paragraph_properties_mut, walk_row_mut,
+        walk_run_track_change_mut, walk_table_mut, VisitMut,
+    },
+    wml::{
+        document::{
+            ContentRunContent, PContent, PPr, RangeMarkupElements, RunInnerContent, RunLevelElts, RunTrackChange,
+            RunTrackChangeChoice, SectPr, TrackChange, P, R,
+        },
+        table::{CellMarkupElements, Row, Tbl, Tc},
+    },
+};
+
+/// The author tracked changes are attributed to after sanitizing. This is the name Word uses when personal information
+/// is removed from a document.
+pub const ANONYMOUS_AUTHOR: &str = "Author";
+
+/// The relationship types of the main document that point to comments or the identities of their authors.
+const COMMENT_RELATION_TYPE_SUFFIXES: [&str; 5] = [
+    "/comments",
+    "/commentsExtended",
+    "/commentsIds",
+    "/commentsExtensible",
+    "/people",
+];
+
+/// The content types of the parts removed by sanitizing that the package doesn't parse.
+const REMOVED_CONTENT_TYPE_SUFFIXES: [&str; 5] = [
+    "printerSettings",
+    "commentsIds+xml",
+    "commentsExtensible+xml",
+    "people+xml",
+    "custom-properties+xml",
+];
+
+/// Statistics of a sanitize pass.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SanitizeSummary {
+    /// The number of comments removed.
+    pub comments: usize,
+    /// The number of tracked changes whose author and date have been removed.
+    pub revisions: usize,
+    /// The number of revision save ids (rsids) removed from paragraphs, runs, table rows and sections.
+    pub revision_ids: usize,
+    /// The names of the unparsed parts removed from the package, e.g. printer settings.
+    pub removed_parts: Vec<String>,
+}
+
+impl Package {
+    /// Removes personally identifying information from the package:
+    ///
+    /// * comments, their anchors in the document and the parts describing their authors,
+    /// * the author and date of tracked changes; authors are replaced with [`ANONYMOUS_AUTHOR`] as the attribute is
+    ///   required,
+    /// * revision save ids of paragraphs, runs, table rows and sections, and the list of them in the settings,
+    /// * core, extended and custom document properties,
+    /// * embedded printer settings and the attached template.
+    ///
+    /// The main document, footnotes and endnotes are sanitized. `w:removePersonalInformation` is set in the settings,
+    /// so applications keep the document anonymous when it's edited later on.
+    ///
+    /// The content of tracked changes is kept, use [`Document::apply_revisions`](crate::docx::wml::document::Document::apply_revisions) to
+    /// resolve them.
+    pub fn sanitize(&mut self) -> SanitizeSummary {
+        // Comments are removed before visiting the stories, so they aren't sanitized in vain
+        let comments = self.comments.take();
+        let mut sanitizer = Sanitizer::default();
+        self.visit_stories_mut(&mut sanitizer);
+
+        let mut summary = sanitizer.summary;
+        summary.comments = comments.map_or(0, |comments| comments.0.len());
+        self.comments_extended = None;
+        self.main_document_relationships.retain(|relationship| {
+            !COMMENT_RELATION_TYPE_SUFFIXES
+                .iter()
+                .any(|suffix| relationship.rel_type.ends_with(suffix))
+        });
+
+        self.core = None;
+        self.app_info = None;
+
+        if let Some(settings) = &mut self.settings {
+            if let Some(revision_ids) = settings.revision_ids.take() {
+                summary.revision_ids += revision_ids.revision_ids.len();
+            }
+            settings.remove_personal_information = Some(true);
+            settings.attached_template = None;
+        }
+
+        let (removed, kept) = self.unsupported_parts.drain(..).partition(|part| {
+            matches!(&part.content_type, Some(content_type) if REMOVED_CONTENT_TYPE_SUFFIXES
+                .iter()
+                .any(|suffix| content_type.ends_with(suffix)))
+        });
+        self.unsupported_parts = kept;
+
+        let removed: Vec<_> = removed.into_iter().map(|part| part.part_name).collect();
+        if let Some(content_types) = &mut self.content_types {
+            for part_name in &removed {
+                content_types.overrides.remove(&part_name.to_lowercase());
+            }
+        }
+        summary.removed_parts = removed;
+
+        summary
+    }
+}
+
+#[derive(Default)]
+struct Sanitizer {
+    summary: SanitizeSummary,
+}
+
+impl Sanitizer {
+    fn track_change(&mut self, track_change: &mut TrackChange) {
+        track_change.author = String::from(ANONYMOUS_AUTHOR);
+        track_change.date = None;
+        self.summary.revisions += 1;
+    }
+
+    fn revision_id<T>(&mut self, revision_id: &mut Option<T>) {
+        if revision_id.take().is_some() {
+            self.summary.revision_ids += 1;
+        }
+    }
+}
+
+impl VisitMut for Sanitizer {
+    fn visit_paragraph_mut(&mut self, paragraph: &mut P) {
+        self.revision_id(&mut paragraph.run_properties_revision_id);
+        self.revision_id(&mut paragraph.run_revision_id);
+        self.revision_id(&mut paragraph.deletion_revision_id);
+        self.revision_id(&mut paragraph.paragraph_revision_id);
+        self.revision_id(&mut paragraph.run_default_revision_id);
+
+        walk_paragraph_mut(self, paragraph);
+    }
+
+    fn visit_paragraph_properties_mut(&mut self, properties: &mut PPr) {
+        if let Some(change) = &mut properties.properties_change {
+            self.track_change(&mut change.base);
+        }
+
+        if let Some(inserted) = properties
+            .base
+            .numbering_properties
+            .as_mut()
+            .and_then(|numbering_properties| numbering_properties.inserted.as_mut())
+        {
+            self.track_change(inserted);
+        }
+
+        if let Some(run_properties) = &mut properties.run_properties {
+            if let Some(change) = &mut run_properties.change {
+                self.track_change(&mut change.base);
+            }
+
+            if let Some(track_changes) = &mut run_properties.track_changes {
+                for track_change in [
+                    &mut track_changes.inserted,
+                    &mut track_changes.deleted,
+                    &mut track_changes.move_from,
+                    &mut track_changes.move_to,
+                ]
+                .iter_mut()
+                .filter_map(|track_change| track_change.as_mut())
+                {
+                    self.track_change(track_change);
+                }
+            }
+        }
+
+        walk_paragraph_properties_mut(self, properties);
+    }
+
+    /// Sanitizes the contents of a paragraph, removing the anchors of comments.
+    fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+        contents.retain(|content| !is_comment_anchor(content));
+        walk_paragraph_contents_mut(self, contents);
+    }
+
+    fn visit_run_track_change_mut(&mut self, track_change: &mut RunTrackChange) {
+        self.track_change(&mut track_change.base);
+        track_change
+            .choices
+            .retain(|RunTrackChangeChoice::ContentRunContent(content)| !is_comment_anchor_run_content(content));
+        walk_run_track_change_mut(self, track_change);
+    }
+
+    fn visit_range_markup_mut(&mut self, element: &mut RangeMarkupElements) {
+        match element {
+            RangeMarkupElements::CustomXmlInsertRangeStart(track_change)
+            | RangeMarkupElements::CustomXmlDeleteRangeStart(track_change)
+            | RangeMarkupElements::CustomXmlMoveFromRangeStart(track_change)
+            | RangeMarkupElements::CustomXmlMoveToRangeStart(track_change) => self.track_change(track_change),
+            // The date of move ranges is required, only the author is removed
+            RangeMarkupElements::MoveFromRangeStart(bookmark) | RangeMarkupElements::MoveToRangeStart(bookmark) => {
+                bookmark.author = String::from(ANONYMOUS_AUTHOR);
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_run_mut(&mut self, run: &mut R) {
+        self.revision_id(&mut run.run_properties_revision_id);
+        self.revision_id(&mut run.deletion_revision_id);
+        self.revision_id(&mut run.run_revision_id);
+
+        if let Some(change) = run
+            .run_properties
+            .as_mut()
+            .and_then(|run_properties| run_properties.run_properties_change.as_mut())
+        {
+            self.track_change(&mut change.base);
+        }
+
+        run.run_inner_contents
+            .retain(|content| !matches!(content, RunInnerContent::CommentReference(_)));
+    }
+
+    fn visit_table_mut(&mut self, table: &mut Tbl) {
+        if let Some(change) = &mut table.properties.change {
+            self.track_change(&mut change.base);
+        }
+
+        walk_table_mut(self, table);
+    }
+
+    fn visit_row_mut(&mut self, row: &mut Row) {
+        self.revision_id(&mut row.run_properties_revision_id);
+        self.revision_id(&mut row.run_revision_id);
+        self.revision_id(&mut row.deletion_revision_id);
+        self.revision_id(&mut row.row_revision_id);
+
+        if let Some(change) = row
+            .property_exceptions
+            .as_mut()
+            .and_then(|property_exceptions| property_exceptions.change.as_mut())
+        {
+            self.track_change(&mut change.base);
+        }
+
+        if let Some(properties) = &mut row.properties {
+            if let Some(change) = &mut properties.change {
+                self.track_change(&mut change.base);
+            }
+            for track_change in [&mut properties.inserted, &mut properties.deleted]
+                .iter_mut()
+                .filter_map(|track_change| track_change.as_mut())
+            {
+                self.track_change(track_change);
+            }
+        }
+
+        walk_row_mut(self, row);
+    }
+
+    fn visit_cell_mut(&mut self, cell: &mut Tc) {
+        if let Some(properties) = &mut cell.properties {
+            if let Some(change) = &mut properties.change {
+                self.track_change(&mut change.base);
+            }
+
+            match &mut properties.base.markup_element {
+                Some(CellMarkupElements::Insertion(track_change))
+                | Some(CellMarkupElements::Deletion(track_change)) => self.track_change(track_change),
+                Some(CellMarkupElements::Merge(merge)) => self.track_change(&mut merge.base),
+                None => (),
+            }
+        }
+
+        walk_cell_mut(self, cell);
+    }
+
+    fn visit_section_properties_mut(&mut self, section_properties: &mut SectPr) {
+        let attributes = &mut section_properties.attributes;
+        self.revision_id(&mut attributes.run_properties_revision_id);
+        self.revision_id(&mut attributes.deletion_revision_id);
+        self.revision_id(&mut attributes.run_revision_id);
+        self.revision_id(&mut attributes.section_revision_id);
+
+        if let Some(change) = &mut section_properties.change {
+            self.track_change(&mut change.base);
+        }
+    }
+}
+
+fn is_comment_anchor(content: &PContent) -> bool {
+    match content {
+        PContent::ContentRunContent(content) => is_comment_anchor_run_content(content),
+        _ => false,
+    }
+}
+
+fn is_comment_anchor_run_content(content: &ContentRunContent) -> bool {
+    matches!(
+        content,
+        ContentRunContent::RunLevelElements(RunLevelElts::RangeMarkupElements(
+            RangeMarkupElements::CommentRangeStart(_) | RangeMarkupElements::CommentRangeEnd(_)
+        ))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            comments::Comments,
+            document::{BlockLevelElts, ContentBlockContent, Document},
+            settings::Settings,
+        },
+        shared::docprops::Core,
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_sanitize() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p w:rsidR="00A1B2C3" w:rsidRDefault="00A1B2C3">
+                <w:commentRangeStart w:id="0" />
+                <w:r w:rsidR="00D4E5F6"><w:t>Text</w:t></w:r>
+                <w:commentRangeEnd w:id="0" />
+                <w:r><w:commentReference w:id="0" /></w:r>
+                <w:ins w:id="1" w:author="Jane Doe" w:date="2020-01-01T00:00:00Z">
+                    <w:r><w:rPr><w:rPrChange w:id="2" w:author="John Doe"><w:rPr /></w:rPrChange></w:rPr><w:t>new</w:t></w:r>
+                </w:ins>
+            </w:p>
+            <w:sectPr w:rsidR="00A1B2C3" />
+        </w:body></w:document>"#;
+        let comments_xml = r#"<w:comments>
+            <w:comment w:id="0" w:author="Jane Doe"><w:p><w:r><w:t>Comment</w:t></w:r></w:p></w:comment>
+        </w:comments>"#;
+        let settings_xml = r#"<w:settings><w:rsids><w:rsidRoot w:val="00A1B2C3" /><w:rsid w:val="00A1B2C3" /><w:rsid w:val="00D4E5F6" /></w:rsids></w:settings>"#;
+
+        let mut package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            comments: Some(Comments::from_xml_element(&XmlNode::from_str(comments_xml).unwrap()).unwrap()),
+            settings: Some(Box::new(
+                Settings::from_xml_element(&XmlNode::from_str(settings_xml).unwrap()).unwrap(),
+            )),
+            core: Some(Core {
+                creator: Some(String::from("Jane Doe")),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let summary = package.sanitize();
+        assert_eq!(summary.comments, 1);
+        assert_eq!(summary.revisions, 2);
+        assert_eq!(summary.revision_ids, 6);
+        assert_eq!(package.comments, None);
+        assert_eq!(package.core, None);
+
+        let settings = package.settings.as_ref().unwrap();
+        assert_eq!(settings.revision_ids, None);
+        assert_eq!(settings.remove_personal_information, Some(true));
+
+        let body = package.main_document.as_ref().unwrap().body.as_ref().unwrap();
+        let paragraph = match &body.block_level_elements[0] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph,
+            _ => panic!("expected a paragraph"),
+        };
+        assert_eq!(paragraph.contents.len(), 3);
+        assert_eq!(paragraph.run_revision_id, None);
+        match &paragraph.contents[2] {
+            PContent::ContentRunContent(content) => match &**content {
+                ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change)) => {
+                    assert_eq!(track_change.base.author, ANONYMOUS_AUTHOR);
+                    assert_eq!(track_change.base.date, None);
+                }
+                content => panic!("expected an insertion, got {:?}", content),
+            },
+            content => panic!("expected an insertion, got {:?}", content),
+        }
+    }
+}
@@ -0,0 +1,609 @@
+use super::wml::document::{
+    BlockLevelElts, ContentBlockContent, ContentRunContent, Lock, PContent, RunInnerContent, SdtBlock, SdtDate, SdtPr,
+    SdtPrChoice, SdtRun, Text, P, R,
+};
+use crate::{error::SdtDateError, shared::sharedtypes::CalendarType};
+
+pub type Result<T> = ::std::result::Result<T, SdtDateError>;
+
+/// The date format used by Word if a date content control doesn't specify one.
+pub const DEFAULT_DATE_FORMAT: &str = "M/d/yyyy";
+
+/// A Gregorian date and time as stored in the `w:fullDate` attribute of a date content control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+}
+
+impl CalendarDate {
+    /// Parses an ISO 8601 date with an optional time part, e.g. `2021-03-04` or `2021-03-04T13:30:00Z`. Time zone
+    /// designators are accepted but ignored, fractional seconds are truncated.
+    ///
+    /// ```
+    /// use oox::docx::sdtdate::CalendarDate;
+    ///
+    /// let date = CalendarDate::parse("2020-02-29T10:15:00Z").unwrap();
+    /// assert_eq!((date.year, date.month, date.day, date.hour), (2020, 2, 29, 10));
+    /// assert!(CalendarDate::parse("2021-02-29").is_err());
+    /// ```
+    pub fn parse<T: AsRef<str>>(value: T) -> Result<Self> {
+        let value = value.as_ref().trim();
+        let invalid = || SdtDateError::InvalidDate(String::from(value));
+
+        let (date_part, time_part) = match value.find('T') {
+            Some(idx) => (&value[..idx], Some(&value[idx + 1..])),
+            None => (value, None),
+        };
+
+        let mut date_fields = date_part.split('-');
+        let year = parse_field(date_fields.next(), 4).ok_or_else(invalid)? as i32;
+        let month = parse_field(date_fields.next(), 2).ok_or_else(invalid)?;
+        let day = parse_field(date_fields.next(), 2).ok_or_else(invalid)?;
+        if date_fields.next().is_some() || !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(invalid());
+        }
+
+        let (hour, minute, second) = match time_part {
+            Some(time_part) => {
+                let time_part = time_part
+                    .trim_end_matches('Z')
+                    .split(['+', '-'])
+                    .next()
+                    .unwrap_or_default();
+                let time_part = time_part.split('.').next().unwrap_or_default();
+
+                let mut time_fields = time_part.split(':');
+                let hour = parse_field(time_fields.next(), 2).ok_or_else(invalid)?;
+                let minute = parse_field(time_fields.next(), 2).ok_or_else(invalid)?;
+                let second = match time_fields.next() {
+                    Some(second) => parse_field(Some(second), 2).ok_or_else(invalid)?,
+                    None => 0,
+                };
+
+                if time_fields.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+                    return Err(invalid());
+                }
+
+                (hour, minute, second)
+            }
+            None => (0, 0, 0),
+        };
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        })
+    }
+
+    /// Returns the date in the format Word uses for the `w:fullDate` attribute, e.g. `2021-03-04T00:00:00Z`.
+    pub fn to_full_date(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+
+    /// Returns the day of the week with 0 being Sunday.
+    pub fn weekday(&self) -> usize {
+        // Sakamoto's method
+        const OFFSETS: [i32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+        let year = if self.month < 3 { self.year - 1 } else { self.year };
+        let weekday = year + year / 4 - year / 100 + year / 400 + OFFSETS[self.month as usize - 1] + self.day as i32;
+        weekday.rem_euclid(7) as usize
+    }
+
+    /// Returns the year of the date in the given calendar. Only calendars that share the months and days of the
+    /// Gregorian calendar are supported.
+    pub fn calendar_year(&self, calendar: CalendarType) -> Result<i32> {
+        match calendar {
+            CalendarType::Taiwan => Ok(self.year - 1911),
+            CalendarType::Thai => Ok(self.year + 543),
+            CalendarType::Korea => Ok(self.year + 2333),
+            CalendarType::Japan => {
+                const ERAS: [((i32, u32, u32), i32); 5] = [
+                    ((2019, 5, 1), 2018),
+                    ((1989, 1, 8), 1988),
+                    ((1926, 12, 25), 1925),
+                    ((1912, 7, 30), 1911),
+                    ((1868, 10, 23), 1867),
+                ];
+
+                let date = (self.year, self.month, self.day);
+                Ok(ERAS
+                    .iter()
+                    .find(|(start, _)| date >= *start)
+                    .map(|(_, offset)| self.year - offset)
+                    .unwrap_or(self.year))
+            }
+            CalendarType::Hijri | CalendarType::Hebrew | CalendarType::Saka => {
+                Err(SdtDateError::UnsupportedCalendar(calendar))
+            }
+            _ => Ok(self.year),
+        }
+    }
+
+    /// Formats the date with a Word date picture, e.g. `dddd, MMMM d, yyyy`.
+    ///
+    /// The supported tokens are `d`, `dd`, `ddd`, `dddd`, `M`, `MM`, `MMM`, `MMMM`, `yy`, `yyyy`, `h`, `hh`, `H`,
+    /// `HH`, `m`, `mm`, `s`, `ss` and `AM/PM` or `am/pm`. Text between apostrophes is copied literally. Month and
+    /// day names are localized for a handful of languages and fall back to English.
+    ///
+    /// ```
+    /// use oox::{docx::sdtdate::CalendarDate, shared::sharedtypes::CalendarType};
+    ///
+    /// let date = CalendarDate::parse("2021-03-04T13:05:00Z").unwrap();
+    /// assert_eq!(
+    ///     date.format("dddd, MMMM d, yyyy h:mm AM/PM", "en-US", CalendarType::Gregorian).unwrap(),
+    ///     "Thursday, March 4, 2021 1:05 PM"
+    /// );
+    /// assert_eq!(date.format("d. MMMM yyyy", "de-DE", CalendarType::Gregorian).unwrap(), "4. März 2021");
+    /// ```
+    pub fn format(&self, date_format: &str, language_id: &str, calendar: CalendarType) -> Result<String> {
+        let names = DateNames::for_language(language_id);
+        let year = self.calendar_year(calendar)?;
+
+        let chars: Vec<char> = date_format.chars().collect();
+        let mut output = String::new();
+        let mut idx = 0;
+
+        while idx < chars.len() {
+            let current = chars[idx];
+
+            if current == '\'' {
+                let end = chars[idx + 1..]
+                    .iter()
+                    .position(|c| *c == '\'')
+                    .map_or(chars.len(), |pos| idx + 1 + pos);
+                output.extend(&chars[idx + 1..end]);
+                idx = end + 1;
+                continue;
+            }
+
+            let rest: String = chars[idx..].iter().take(5).collect();
+            if rest.eq_ignore_ascii_case("am/pm") {
+                let am_pm = if self.hour < 12 { "AM" } else { "PM" };
+                if rest.starts_with('a') {
+                    output.push_str(&am_pm.to_lowercase());
+                } else {
+                    output.push_str(am_pm);
+                }
+
+                idx += 5;
+                continue;
+            }
+
+            let count = chars[idx..].iter().take_while(|c| **c == current).count();
+            match (current, count) {
+                ('d', 1) => output.push_str(&self.day.to_string()),
+                ('d', 2) => output.push_str(&format!("{:02}", self.day)),
+                ('d', 3) => output.push_str(names.abbreviated_days[self.weekday()]),
+                ('d', _) => output.push_str(names.days[self.weekday()]),
+                ('M', 1) => output.push_str(&self.month.to_string()),
+                ('M', 2) => output.push_str(&format!("{:02}", self.month)),
+                ('M', 3) => output.push_str(names.abbreviated_months[self.month as usize - 1]),
+                ('M', _) => output.push_str(names.months[self.month as usize - 1]),
+                ('y', 1) | ('y', 2) => output.push_str(&format!("{:02}", year.rem_euclid(100))),
+                ('y', _) => output.push_str(&year.to_string()),
+                ('h', 1) => output.push_str(&twelve_hour(self.hour).to_string()),
+                ('h', _) => output.push_str(&format!("{:02}", twelve_hour(self.hour))),
+                ('H', 1) => output.push_str(&self.hour.to_string()),
+                ('H', _) => output.push_str(&format!("{:02}", self.hour)),
+                ('m', 1) => output.push_str(&self.minute.to_string()),
+                ('m', _) => output.push_str(&format!("{:02}", self.minute)),
+                ('s', 1) => output.push_str(&self.second.to_string()),
+                ('s', _) => output.push_str(&format!("{:02}", self.second)),
+                _ => output.extend(&chars[idx..idx + count]),
+            }
+
+            idx += count;
+        }
+
+        Ok(output)
+    }
+}
+
+impl SdtDate {
+    /// Parses the stored `w:fullDate` value. Returns `Ok(None)` if the control doesn't have a value.
+    pub fn date(&self) -> Result<Option<CalendarDate>> {
+        self.full_date.as_ref().map(CalendarDate::parse).transpose()
+    }
+
+    /// Renders the stored date the way Word displays it, using the date format, language and calendar of the
+    /// control. Returns `Ok(None)` if the control doesn't have a value.
+    pub fn display_text(&self) -> Result<Option<String>> {
+        match self.date()? {
+            Some(date) => self.format_date(&date).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Validates a user supplied date and stores it as the value of the control. Returns the text Word would display
+    /// for the new value.
+    pub fn set_date<T: AsRef<str>>(&mut self, value: T) -> Result<String> {
+        let date = CalendarDate::parse(value)?;
+        let display_text = self.format_date(&date)?;
+        self.full_date = Some(date.to_full_date());
+        Ok(display_text)
+    }
+
+    fn format_date(&self, date: &CalendarDate) -> Result<String> {
+        date.format(
+            self.date_format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT),
+            self.language_id.as_deref().unwrap_or("en-US"),
+            self.calendar.unwrap_or(CalendarType::Gregorian),
+        )
+    }
+}
+
+impl SdtPr {
+    /// Returns the date properties if this is a date content control.
+    pub fn date(&self) -> Option<&SdtDate> {
+        match &self.control_choice {
+            Some(SdtPrChoice::Date(date)) => Some(date),
+            _ => None,
+        }
+    }
+
+    fn set_date<T: AsRef<str>>(&mut self, value: T) -> Result<String> {
+        if matches!(self.lock, Some(Lock::ContentLocked) | Some(Lock::SdtContentLocked)) {
+            return Err(SdtDateError::ContentLocked);
+        }
+
+        let display_text = match &mut self.control_choice {
+            Some(SdtPrChoice::Date(date)) => date.set_date(value)?,
+            _ => return Err(SdtDateError::NotDateControl),
+        };
+
+        self.showing_placeholder_header = None;
+        Ok(display_text)
+    }
+}
+
+impl SdtRun {
+    /// Sets the value of an inline date content control. The value is validated, stored in `w:fullDate` and the
+    /// content of the control is replaced with a single run displaying the formatted date. The properties of the
+    /// first run of the previous content are kept.
+    pub fn set_date<T: AsRef<str>>(&mut self, value: T) -> Result<()> {
+        let display_text = self
+            .sdt_properties
+            .as_mut()
+            .ok_or(SdtDateError::NotDateControl)?
+            .set_date(value)?;
+
+        let sdt_content = self.sdt_content.get_or_insert_with(Default::default);
+        let run = text_run(first_run_of(&sdt_content.p_contents), display_text);
+        sdt_content.p_contents = vec![PContent::ContentRunContent(Box::new(ContentRunContent::Run(run)))];
+        Ok(())
+    }
+}
+
+impl SdtBlock {
+    /// Sets the value of a block level date content control. The content of the control is replaced with a single
+    /// paragraph displaying the formatted date. The properties of the first paragraph and its first run are kept.
+    pub fn set_date<T: AsRef<str>>(&mut self, value: T) -> Result<()> {
+        let display_text = self
+            .sdt_properties
+            .as_mut()
+            .ok_or(SdtDateError::NotDateControl)?
+            .set_date(value)?;
+
+        let sdt_content = self.sdt_content.get_or_insert_with(Default::default);
+        let first_paragraph = sdt_content.block_contents.iter().find_map(|content| match content {
+            ContentBlockContent::Paragraph(paragraph) => Some(paragraph.as_ref()),
+            _ => None,
+        });
+
+        let paragraph = P {
+            properties: first_paragraph.and_then(|paragraph| paragraph.properties.clone()),
+            contents: vec![PContent::ContentRunContent(Box::new(ContentRunContent::Run(text_run(
+                first_paragraph.and_then(|paragraph| first_run_of(&paragraph.contents)),
+                display_text,
+            ))))],
+            ..Default::default()
+        };
+
+        sdt_content.block_contents = vec![ContentBlockContent::Paragraph(Box::new(paragraph))];
+        Ok(())
+    }
+}
+
+impl BlockLevelElts {
+    /// Returns the block level date content control of this element, if any.
+    pub fn date_control_mut(&mut self) -> Option<&mut SdtBlock> {
+        match self {
+            BlockLevelElts::Chunk(ContentBlockContent::Sdt(sdt))
+                if sdt.sdt_properties.as_ref().and_then(SdtPr::date).is_some() =>
+            {
+                Some(sdt)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn first_run_of(contents: &[PContent]) -> Option<&R> {
+    contents.iter().find_map(|content| match content {
+        PContent::ContentRunContent(content) => match content.as_ref() {
+            ContentRunContent::Run(run) => Some(run),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn text_run(template: Option<&R>, text: String) -> R {
+    R {
+        run_properties: template.and_then(|run| run.run_properties.clone()),
+        run_inner_contents: vec![RunInnerContent::Text(Text {
+            text,
+            xml_space: Some(String::from("preserve")),
+        })],
+        ..Default::default()
+    }
+}
+
+fn parse_field(field: Option<&str>, min_len: usize) -> Option<u32> {
+    let field = field?;
+    if field.len() < min_len || !field.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    field.parse().ok()
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn twelve_hour(hour: u32) -> u32 {
+    match hour % 12 {
+        0 => 12,
+        hour => hour,
+    }
+}
+
+struct DateNames {
+    months: [&'static str; 12],
+    abbreviated_months: [&'static str; 12],
+    days: [&'static str; 7],
+    abbreviated_days: [&'static str; 7],
+}
+
+impl DateNames {
+    fn for_language(language_id: &str) -> &'static DateNames {
+        let language = language_id.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "de" => &GERMAN,
+            "fr" => &FRENCH,
+            "es" => &SPANISH,
+            "hu" => &HUNGARIAN,
+            _ => &ENGLISH,
+        }
+    }
+}
+
+const ENGLISH: DateNames = DateNames {
+    months: [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ],
+    abbreviated_months: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+    days: [
+        "Sunday",
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+    ],
+    abbreviated_days: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+};
+
+const GERMAN: DateNames = DateNames {
+    months: [
+        "Januar",
+        "Februar",
+        "März",
+        "April",
+        "Mai",
+        "Juni",
+        "Juli",
+        "August",
+        "September",
+        "Oktober",
+        "November",
+        "Dezember",
+    ],
+    abbreviated_months: [
+        "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+    ],
+    days: [
+        "Sonntag",
+        "Montag",
+        "Dienstag",
+        "Mittwoch",
+        "Donnerstag",
+        "Freitag",
+        "Samstag",
+    ],
+    abbreviated_days: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+};
+
+const FRENCH: DateNames = DateNames {
+    months: [
+        "janvier",
+        "février",
+        "mars",
+        "avril",
+        "mai",
+        "juin",
+        "juillet",
+        "août",
+        "septembre",
+        "octobre",
+        "novembre",
+        "décembre",
+    ],
+    abbreviated_months: [
+        "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc.",
+    ],
+    days: ["dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi"],
+    abbreviated_days: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+};
+
+const SPANISH: DateNames = DateNames {
+    months: [
+        "enero",
+        "febrero",
+        "marzo",
+        "abril",
+        "mayo",
+        "junio",
+        "julio",
+        "agosto",
+        "septiembre",
+        "octubre",
+        "noviembre",
+        "diciembre",
+    ],
+    abbreviated_months: [
+        "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+    ],
+    days: ["domingo", "lunes", "martes", "miércoles", "jueves", "viernes", "sábado"],
+    abbreviated_days: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+};
+
+const HUNGARIAN: DateNames = DateNames {
+    months: [
+        "január",
+        "február",
+        "március",
+        "április",
+        "május",
+        "június",
+        "július",
+        "augusztus",
+        "szeptember",
+        "október",
+        "november",
+        "december",
+    ],
+    abbreviated_months: [
+        "jan.", "febr.", "márc.", "ápr.", "máj.", "jún.", "júl.", "aug.", "szept.", "okt.", "nov.", "dec.",
+    ],
+    days: ["vasárnap", "hétfő", "kedd", "szerda", "csütörtök", "péntek", "szombat"],
+    abbreviated_days: ["V", "H", "K", "Sze", "Cs", "P", "Szo"],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn date_control_for_test() -> SdtRun {
+        let xml = r#"<w:sdt>
+            <w:sdtPr>
+                <w:showingPlcHdr />
+                <w:date w:fullDate="2019-05-01T00:00:00Z">
+                    <w:dateFormat w:val="yyyy'年'M'月'd'日'" />
+                    <w:lid w:val="ja-JP" />
+                    <w:calendar w:val="japan" />
+                </w:date>
+            </w:sdtPr>
+            <w:sdtContent>
+                <w:r>
+                    <w:rPr><w:b /></w:rPr>
+                    <w:t>Click to enter a date</w:t>
+                </w:r>
+            </w:sdtContent>
+        </w:sdt>"#;
+
+        SdtRun::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_sdt_date_display_text() {
+        let sdt = date_control_for_test();
+        let date = sdt.sdt_properties.as_ref().and_then(SdtPr::date).unwrap();
+        assert_eq!(date.display_text(), Ok(Some(String::from("1年5月1日"))));
+
+        let date = SdtDate {
+            full_date: Some(String::from("2021-12-31T18:30:00Z")),
+            ..Default::default()
+        };
+        assert_eq!(date.display_text(), Ok(Some(String::from("12/31/2021"))));
+
+        let date = SdtDate {
+            full_date: Some(String::from("2021-12-31T00:00:00Z")),
+            calendar: Some(CalendarType::Hijri),
+            ..Default::default()
+        };
+        assert_eq!(
+            date.display_text(),
+            Err(SdtDateError::UnsupportedCalendar(CalendarType::Hijri))
+        );
+    }
+
+    #[test]
+    pub fn test_sdt_run_set_date() {
+        let mut sdt = date_control_for_test();
+        assert_eq!(
+            sdt.set_date("2021-02-30"),
+            Err(SdtDateError::InvalidDate(String::from("2021-02-30")))
+        );
+
+        sdt.set_date("1989-01-07").unwrap();
+        let sdt_properties = sdt.sdt_properties.as_ref().unwrap();
+        assert_eq!(sdt_properties.showing_placeholder_header, None);
+        assert_eq!(
+            sdt_properties.date().unwrap().full_date.as_deref(),
+            Some("1989-01-07T00:00:00Z")
+        );
+
+        let contents = &sdt.sdt_content.as_ref().unwrap().p_contents;
+        let run = first_run_of(contents).unwrap();
+        assert!(run.run_properties.is_some());
+        assert_eq!(
+            run.run_inner_contents,
+            vec![RunInnerContent::Text(Text {
+                text: String::from("64年1月7日"),
+                xml_space: Some(String::from("preserve")),
+            })]
+        );
+
+        sdt.sdt_properties.as_mut().unwrap().lock = Some(Lock::ContentLocked);
+        assert_eq!(sdt.set_date("2021-01-01"), Err(SdtDateError::ContentLocked));
+    }
+}
@@ -0,0 +1,428 @@
+use super::{
+    text::{for_each_inline, InlineItem},
+    wml::{
+        document::{
+            Attr, BlockLevelElts, ContentBlockContent, ContentRunContent, Document, PContent, RunLevelElts,
+            RunTrackChangeChoice, SmartTagRun,
+        },
+        table::{ContentCellContent, ContentRowContent, Tbl},
+    },
+};
+
+/// The data of a smart tag of the document, e.g. an address or a stock ticker symbol recognized by a legacy version
+/// of Word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmartTagRecord {
+    /// The namespace URI of the smart tag (`w:uri`).
+    pub uri: String,
+    /// The name of the smart tag element (`w:element`).
+    pub element: String,
+    /// The custom attributes of the smart tag (`w:smartTagPr/w:attr`).
+    pub attributes: Vec<Attr>,
+    /// The text covered by the smart tag.
+    pub text: String,
+}
+
+impl SmartTagRecord {
+    fn from_smart_tag(smart_tag: &SmartTagRun) -> Self {
+        let mut text = String::new();
+        for_each_inline(&smart_tag.paragraph_contents, &mut |item| match item {
+            InlineItem::Text(value) => text.push_str(value),
+            InlineItem::Tab => text.push('\t'),
+            InlineItem::Break(_) => text.push('\n'),
+            InlineItem::NoteReference(..) => (),
+        });
+
+        Self {
+            uri: smart_tag.uri.clone(),
+            element: smart_tag.element.clone(),
+            attributes: smart_tag
+                .smart_tag_properties
+                .as_ref()
+                .map(|properties| properties.attributes.clone())
+                .unwrap_or_default(),
+            text,
+        }
+    }
+}
+
+impl Document {
+    /// Lists the smart tags of the document body in document order. Nested smart tags are listed after the smart
+    /// tag containing them.
+    pub fn smart_tags(&self) -> Vec<SmartTagRecord> {
+        let mut records = Vec::new();
+        if let Some(body) = &self.body {
+            for block_level_element in &body.block_level_elements {
+                block_level_element_smart_tags(block_level_element, &mut records);
+            }
+        }
+
+        records
+    }
+
+    /// Replaces every smart tag of the document body with its content and returns the removed smart tags in the
+    /// same order as `smart_tags` does.
+    ///
+    /// Smart tags wrapping content other than runs are kept inside tracked insertions and moves, since only runs can
+    /// be tracked. They are still included in the returned records.
+    pub fn flatten_smart_tags(&mut self) -> Vec<SmartTagRecord> {
+        let mut records = Vec::new();
+        if let Some(body) = &mut self.body {
+            for block_level_element in &mut body.block_level_elements {
+                flatten_block_level_element(block_level_element, &mut records);
+            }
+        }
+
+        records
+    }
+}
+
+fn block_level_element_smart_tags(element: &BlockLevelElts, records: &mut Vec<SmartTagRecord>) {
+    if let BlockLevelElts::Chunk(content) = element {
+        content_block_smart_tags(content, records);
+    }
+}
+
+fn content_block_smart_tags(content: &ContentBlockContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.block_contents {
+                content_block_smart_tags(content, records);
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                content_block_smart_tags(content, records);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => paragraph_contents_smart_tags(&paragraph.contents, records),
+        ContentBlockContent::Table(table) => table_smart_tags(table, records),
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn table_smart_tags(table: &Tbl, records: &mut Vec<SmartTagRecord>) {
+    for row_content in &table.row_contents {
+        row_smart_tags(row_content, records);
+    }
+}
+
+fn row_smart_tags(content: &ContentRowContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentRowContent::Table(row) => {
+            for cell_content in &row.contents {
+                cell_smart_tags(cell_content, records);
+            }
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                row_smart_tags(content, records);
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                row_smart_tags(content, records);
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+fn cell_smart_tags(content: &ContentCellContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            for block_level_element in &cell.block_level_elements {
+                block_level_element_smart_tags(block_level_element, records);
+            }
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                cell_smart_tags(content, records);
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                cell_smart_tags(content, records);
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+fn paragraph_contents_smart_tags(contents: &[PContent], records: &mut Vec<SmartTagRecord>) {
+    for content in contents {
+        match content {
+            PContent::ContentRunContent(content) => content_run_smart_tags(content, records),
+            PContent::SimpleField(field) => paragraph_contents_smart_tags(&field.paragraph_contents, records),
+            PContent::Hyperlink(hyperlink) => paragraph_contents_smart_tags(&hyperlink.paragraph_contents, records),
+            PContent::SubDocument(_) => (),
+        }
+    }
+}
+
+fn content_run_smart_tags(content: &ContentRunContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentRunContent::SmartTag(smart_tag) => {
+            records.push(SmartTagRecord::from_smart_tag(smart_tag));
+            paragraph_contents_smart_tags(&smart_tag.paragraph_contents, records);
+        }
+        ContentRunContent::CustomXml(custom_xml) => {
+            paragraph_contents_smart_tags(&custom_xml.paragraph_contents, records)
+        }
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                paragraph_contents_smart_tags(&sdt_content.p_contents, records);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => paragraph_contents_smart_tags(&dir.p_contents, records),
+        ContentRunContent::BidirectionalOverride(bdo) => paragraph_contents_smart_tags(&bdo.p_contents, records),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            for choice in &track_change.choices {
+                match choice {
+                    RunTrackChangeChoice::ContentRunContent(content) => content_run_smart_tags(content, records),
+                }
+            }
+        }
+        ContentRunContent::Run(_) | ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+fn flatten_block_level_element(element: &mut BlockLevelElts, records: &mut Vec<SmartTagRecord>) {
+    if let BlockLevelElts::Chunk(content) = element {
+        flatten_content_block(content, records);
+    }
+}
+
+fn flatten_content_block(content: &mut ContentBlockContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.block_contents {
+                flatten_content_block(content, records);
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            for content in sdt
+                .sdt_content
+                .iter_mut()
+                .flat_map(|content| content.block_contents.iter_mut())
+            {
+                flatten_content_block(content, records);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => flatten_paragraph_contents(&mut paragraph.contents, records),
+        ContentBlockContent::Table(table) => flatten_table(table, records),
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn flatten_table(table: &mut Tbl, records: &mut Vec<SmartTagRecord>) {
+    for row_content in &mut table.row_contents {
+        flatten_row(row_content, records);
+    }
+}
+
+fn flatten_row(content: &mut ContentRowContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentRowContent::Table(row) => {
+            for cell_content in &mut row.contents {
+                flatten_cell(cell_content, records);
+            }
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.contents {
+                flatten_row(content, records);
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            for content in sdt.content.iter_mut().flat_map(|content| content.contents.iter_mut()) {
+                flatten_row(content, records);
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+fn flatten_cell(content: &mut ContentCellContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            for block_level_element in &mut cell.block_level_elements {
+                flatten_block_level_element(block_level_element, records);
+            }
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for content in &mut custom_xml.contents {
+                flatten_cell(content, records);
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            for content in sdt.content.iter_mut().flat_map(|content| content.contents.iter_mut()) {
+                flatten_cell(content, records);
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+fn flatten_paragraph_contents(contents: &mut Vec<PContent>, records: &mut Vec<SmartTagRecord>) {
+    let mut flattened = Vec::with_capacity(contents.len());
+
+    for mut content in contents.drain(..) {
+        if let PContent::ContentRunContent(run_content) = &content {
+            if let ContentRunContent::SmartTag(smart_tag) = run_content.as_ref() {
+                records.push(SmartTagRecord::from_smart_tag(smart_tag));
+
+                let mut smart_tag_contents = smart_tag.paragraph_contents.clone();
+                flatten_paragraph_contents(&mut smart_tag_contents, records);
+                flattened.extend(smart_tag_contents);
+                continue;
+            }
+        }
+
+        match &mut content {
+            PContent::ContentRunContent(content) => flatten_content_run(content, records),
+            PContent::SimpleField(field) => flatten_paragraph_contents(&mut field.paragraph_contents, records),
+            PContent::Hyperlink(hyperlink) => flatten_paragraph_contents(&mut hyperlink.paragraph_contents, records),
+            PContent::SubDocument(_) => (),
+        }
+
+        flattened.push(content);
+    }
+
+    *contents = flattened;
+}
+
+fn flatten_content_run(content: &mut ContentRunContent, records: &mut Vec<SmartTagRecord>) {
+    match content {
+        ContentRunContent::CustomXml(custom_xml) => {
+            flatten_paragraph_contents(&mut custom_xml.paragraph_contents, records)
+        }
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &mut sdt.sdt_content {
+                flatten_paragraph_contents(&mut sdt_content.p_contents, records);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => flatten_paragraph_contents(&mut dir.p_contents, records),
+        ContentRunContent::BidirectionalOverride(bdo) => flatten_paragraph_contents(&mut bdo.p_contents, records),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            flatten_track_change_choices(&mut track_change.choices, records)
+        }
+        ContentRunContent::SmartTag(_) | ContentRunContent::Run(_) | ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+fn flatten_track_change_choices(choices: &mut Vec<RunTrackChangeChoice>, records: &mut Vec<SmartTagRecord>) {
+    let mut flattened = Vec::with_capacity(choices.len());
+
+    for choice in choices.drain(..) {
+        let RunTrackChangeChoice::ContentRunContent(mut content) = choice;
+
+        if let ContentRunContent::SmartTag(smart_tag) = &content {
+            let all_runs = smart_tag
+                .paragraph_contents
+                .iter()
+                .all(|content| matches!(content, PContent::ContentRunContent(_)));
+
+            if all_runs {
+                records.push(SmartTagRecord::from_smart_tag(smart_tag));
+
+                let mut smart_tag_contents = smart_tag.paragraph_contents.clone();
+                flatten_paragraph_contents(&mut smart_tag_contents, records);
+                flattened.extend(smart_tag_contents.into_iter().filter_map(|content| match content {
+                    PContent::ContentRunContent(content) => Some(RunTrackChangeChoice::ContentRunContent(*content)),
+                    _ => None,
+                }));
+                continue;
+            }
+
+            content_run_smart_tags(&content, records);
+        } else {
+            flatten_content_run(&mut content, records);
+        }
+
+        flattened.push(RunTrackChangeChoice::ContentRunContent(content));
+    }
+
+    *choices = flattened;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn document_for_test() -> Document {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:r><w:t xml:space="preserve">Meet me at </w:t></w:r>
+                    <w:smartTag w:uri="urn:schemas-microsoft-com:office:smarttags" w:element="address">
+                        <w:smartTagPr>
+                            <w:attr w:uri="urn:schemas-microsoft-com:office:smarttags" w:name="country" w:val="US" />
+                        </w:smartTagPr>
+                        <w:smartTag w:uri="urn:schemas-microsoft-com:office:smarttags" w:element="Street">
+                            <w:r><w:t>1 Main St</w:t></w:r>
+                        </w:smartTag>
+                        <w:r><w:t xml:space="preserve">, Springfield</w:t></w:r>
+                    </w:smartTag>
+                </w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid />
+                    <w:tr>
+                        <w:tc>
+                            <w:p>
+                                <w:smartTag w:uri="urn:schemas-microsoft-com:office:smarttags" w:element="stockticker">
+                                    <w:r><w:t>MSFT</w:t></w:r>
+                                </w:smartTag>
+                            </w:p>
+                        </w:tc>
+                    </w:tr>
+                </w:tbl>
+            </w:body>
+        </w:document>"#;
+
+        Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_smart_tags() {
+        let records = document_for_test().smart_tags();
+        assert_eq!(records.len(), 3);
+
+        assert_eq!(records[0].element, "address");
+        assert_eq!(records[0].text, "1 Main St, Springfield");
+        assert_eq!(records[0].attributes[0].name, "country");
+        assert_eq!(records[0].attributes[0].value, "US");
+
+        assert_eq!(records[1].element, "Street");
+        assert_eq!(records[1].text, "1 Main St");
+        assert!(records[1].attributes.is_empty());
+
+        assert_eq!(records[2].uri, "urn:schemas-microsoft-com:office:smarttags");
+        assert_eq!(records[2].element, "stockticker");
+        assert_eq!(records[2].text, "MSFT");
+    }
+
+    #[test]
+    pub fn test_flatten_smart_tags() {
+        let mut document = document_for_test();
+        let expected_records = document.smart_tags();
+
+        assert_eq!(document.flatten_smart_tags(), expected_records);
+        assert!(document.smart_tags().is_empty());
+
+        let paragraph = match &document.body.as_ref().unwrap().block_level_elements[0] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph,
+            _ => panic!("expected a paragraph"),
+        };
+
+        assert_eq!(paragraph.contents.len(), 3);
+        assert!(paragraph.contents.iter().all(|content| match content {
+            PContent::ContentRunContent(content) => matches!(content.as_ref(), ContentRunContent::Run(_)),
+            _ => false,
+        }));
+    }
+}
@@ -0,0 +1,357 @@
+use super::{
+    ids::IdKind,
+    package::Package,
+    wml::{
+        document::{BlockLevelElts, Body, ContentBlockContent, Document, HdrFtrReferences, SectPr, P},
+        numbering::Numbering,
+    },
+};
+use crate::shared::relationship::Relationship;
+use std::collections::BTreeSet;
+
+/// A document split off from the main document of a package, along with the definitions it needs from the package.
+#[derive(Debug)]
+pub struct DocumentSplit {
+    pub document: Document,
+    /// The relationships of the main document targeting the headers and footers used by the sections.
+    pub header_footer_relationships: Vec<Relationship>,
+    /// The numbering instances used by the paragraphs and styles, and the abstract numberings they're based on.
+    /// Picture bullets aren't copied.
+    pub numbering: Numbering,
+}
+
+impl Document {
+    /// Splits the document into one document per section. Every document ends with the properties of its section.
+    pub fn split_by_sections(&self) -> Vec<Document> {
+        let blocks = match &self.body {
+            Some(body) => &body.block_level_elements,
+            None => return Vec::new(),
+        };
+
+        let starts: Vec<_> = (1..blocks.len())
+            .filter(|index| section_break(&blocks[index - 1]).is_some())
+            .collect();
+        self.split_at(&starts)
+    }
+
+    /// Splits the document before every top level paragraph the predicate is true for. The content before the first
+    /// such paragraph becomes a document of its own.
+    ///
+    /// Every document ends with the properties of the section its last paragraph belongs to. Headers and footers
+    /// inherited from the sections left behind are referenced by the first section of each document.
+    pub fn split_before<F: FnMut(&P) -> bool>(&self, mut starts_document: F) -> Vec<Document> {
+        let blocks = match &self.body {
+            Some(body) => &body.block_level_elements,
+            None => return Vec::new(),
+        };
+
+        let starts: Vec<_> = blocks
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, block)| match block {
+                BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => starts_document(paragraph),
+                _ => false,
+            })
+            .map(|(index, _)| index)
+            .collect();
+        self.split_at(&starts)
+    }
+
+    fn split_at(&self, starts: &[usize]) -> Vec<Document> {
+        let body = match &self.body {
+            Some(body) => body,
+            None => return Vec::new(),
+        };
+
+        let blocks = &body.block_level_elements;
+        let section_breaks: Vec<_> = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(index, block)| section_break(block).map(|section_properties| (index, section_properties)))
+            .collect();
+
+        let mut bounds = vec![0];
+        bounds.extend(starts);
+        bounds.push(blocks.len());
+
+        let mut inherited_references = Vec::new();
+        let mut passed_sections = 0;
+        let mut documents = Vec::new();
+        for bound in bounds.windows(2) {
+            let (start, end) = (bound[0], bound[1]);
+            if start >= end {
+                continue;
+            }
+
+            while let Some((_, section_properties)) =
+                section_breaks.get(passed_sections).filter(|(index, _)| *index < start)
+            {
+                inherit_references(&mut inherited_references, section_properties);
+                passed_sections += 1;
+            }
+
+            let mut block_level_elements = blocks[start..end].to_vec();
+            let section_properties = match block_level_elements.last_mut() {
+                Some(BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph))) => paragraph
+                    .properties
+                    .as_mut()
+                    .and_then(|properties| properties.section_properties.take()),
+                _ => None,
+            };
+            let mut section_properties = section_properties.or_else(|| {
+                section_breaks
+                    .iter()
+                    .find(|(index, _)| *index >= end)
+                    .map(|(_, section_properties)| (*section_properties).clone())
+                    .or_else(|| body.section_properties.clone())
+            });
+
+            let first_section_properties = block_level_elements
+                .iter_mut()
+                .find_map(section_break_mut)
+                .or(section_properties.as_mut());
+            if let Some(first_section_properties) = first_section_properties {
+                for reference in &inherited_references {
+                    let is_overridden = first_section_properties
+                        .header_footer_references
+                        .iter()
+                        .any(|existing| is_same_kind(existing, reference));
+                    if !is_overridden {
+                        first_section_properties
+                            .header_footer_references
+                            .push(reference.clone());
+                    }
+                }
+            }
+
+            documents.push(Document {
+                base: self.base.clone(),
+                body: Some(Body {
+                    block_level_elements,
+                    section_properties,
+                    extras: body.extras.clone(),
+                }),
+                conformance: self.conformance,
+            });
+        }
+
+        documents
+    }
+}
+
+impl Package {
+    /// Splits the main document into one document per section, see [`Document::split_by_sections`].
+    pub fn split_by_sections(&self) -> Vec<DocumentSplit> {
+        self.main_document
+            .iter()
+            .flat_map(|document| document.split_by_sections())
+            .map(|document| self.document_split(document))
+            .collect()
+    }
+
+    /// Splits the main document before every heading with the given zero based outline level or a lower one, e.g. 0
+    /// splits the document at every `Heading 1` paragraph. See [`Package::resolve_outline_level`] and
+    /// [`Document::split_before`].
+    pub fn split_by_heading_level(&self, level: i64) -> Vec<DocumentSplit> {
+        self.main_document
+            .iter()
+            .flat_map(|document| {
+                document.split_before(|paragraph| {
+                    self.resolve_outline_level(paragraph)
+                        .is_some_and(|outline_level| outline_level <= level)
+                })
+            })
+            .map(|document| self.document_split(document))
+            .collect()
+    }
+
+    fn document_split(&self, document: Document) -> DocumentSplit {
+        let mut rel_ids = BTreeSet::new();
+        if let Some(body) = &document.body {
+            let section_properties = body
+                .block_level_elements
+                .iter()
+                .filter_map(section_break)
+                .chain(body.section_properties.iter());
+            for reference in
+                section_properties.flat_map(|section_properties| &section_properties.header_footer_references)
+            {
+                match reference {
+                    HdrFtrReferences::Header(reference) | HdrFtrReferences::Footer(reference) => {
+                        rel_ids.insert(reference.base.rel_id.as_str());
+                    }
+                }
+            }
+        }
+
+        let header_footer_relationships = self
+            .main_document_relationships
+            .iter()
+            .filter(|relationship| rel_ids.contains(relationship.id.as_str()))
+            .cloned()
+            .collect();
+
+        let mut numbering_ids = document.id_registry().ids(IdKind::Numbering);
+        let style_numberings = self
+            .styles
+            .iter()
+            .flat_map(|styles| &styles.styles)
+            .filter_map(|style| style.paragraph_properties.as_ref()?.base.numbering_properties.as_ref());
+        numbering_ids.extend(style_numberings.filter_map(|numbering_properties| numbering_properties.numbering_id));
+
+        let mut numbering = Numbering::default();
+        if let Some(package_numbering) = &self.numbering {
+            numbering.numberings = package_numbering
+                .numberings
+                .iter()
+                .filter(|instance| numbering_ids.contains(&instance.numbering_id))
+                .cloned()
+                .collect();
+            numbering.abstract_numberings = package_numbering
+                .abstract_numberings
+                .iter()
+                .filter(|abstract_numbering| {
+                    numbering
+                        .numberings
+                        .iter()
+                        .any(|instance| instance.abstract_num_id == abstract_numbering.abstract_num_id)
+                })
+                .cloned()
+                .collect();
+            numbering.numbering_id_mac_at_cleanup = package_numbering.numbering_id_mac_at_cleanup;
+        }
+
+        DocumentSplit {
+            document,
+            header_footer_relationships,
+            numbering,
+        }
+    }
+}
+
+fn section_break(block: &BlockLevelElts) -> Option<&SectPr> {
+    match block {
+        BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => {
+            paragraph.properties.as_ref()?.section_properties.as_ref()
+        }
+        _ => None,
+    }
+}
+
+fn section_break_mut(block: &mut BlockLevelElts) -> Option<&mut SectPr> {
+    match block {
+        BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => {
+            paragraph.properties.as_mut()?.section_properties.as_mut()
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether two references are for the same kind of header or footer, e.g. both are first page headers.
+fn is_same_kind(lhs: &HdrFtrReferences, rhs: &HdrFtrReferences) -> bool {
+    match (lhs, rhs) {
+        (HdrFtrReferences::Header(lhs), HdrFtrReferences::Header(rhs))
+        | (HdrFtrReferences::Footer(lhs), HdrFtrReferences::Footer(rhs)) => {
+            lhs.header_footer_type == rhs.header_footer_type
+        }
+        _ => false,
+    }
+}
+
+/// Updates the headers and footers in effect after a section. Sections without a header or footer of a kind inherit
+/// it from the previous section.
+fn inherit_references(references: &mut Vec<HdrFtrReferences>, section_properties: &SectPr) {
+    for reference in &section_properties.header_footer_references {
+        references.retain(|existing| !is_same_kind(existing, reference));
+        references.push(reference.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::wml::styles::Styles, xml::XmlNode};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_split_by_heading_level() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p><w:r><w:t>Preface</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Chapter 1</w:t></w:r></w:p>
+            <w:p>
+                <w:pPr>
+                    <w:numPr><w:ilvl w:val="0" /><w:numId w:val="2" /></w:numPr>
+                    <w:sectPr><w:headerReference w:type="default" r:id="rId1" /></w:sectPr>
+                </w:pPr>
+                <w:r><w:t>Item</w:t></w:r>
+            </w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading2" /></w:pPr><w:r><w:t>Section 1.1</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Chapter 2</w:t></w:r></w:p>
+            <w:sectPr><w:footerReference w:type="default" r:id="rId2" /></w:sectPr>
+        </w:body></w:document>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:pPr><w:outlineLvl w:val="0" /></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading2"><w:pPr><w:outlineLvl w:val="1" /></w:pPr></w:style>
+        </w:styles>"#;
+        let numbering_xml = r#"<w:numbering>
+            <w:abstractNum w:abstractNumId="0"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:abstractNum w:abstractNumId="1"><w:lvl w:ilvl="0" /></w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="0" /></w:num>
+            <w:num w:numId="2"><w:abstractNumId w:val="1" /></w:num>
+        </w:numbering>"#;
+        let relationship = |id: &str, rel_type: &str| Relationship {
+            id: String::from(id),
+            rel_type: format!(
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}",
+                rel_type
+            ),
+            target: format!("{}.xml", rel_type),
+            target_mode: None,
+        };
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            main_document_relationships: vec![relationship("rId1", "header"), relationship("rId2", "footer")],
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            numbering: Some(Numbering::from_xml_element(&XmlNode::from_str(numbering_xml).unwrap()).unwrap()),
+            ..Default::default()
+        };
+
+        let splits = package.split_by_heading_level(0);
+        assert_eq!(splits.len(), 3);
+        let block_counts: Vec<_> = splits
+            .iter()
+            .map(|split| split.document.body.as_ref().unwrap().block_level_elements.len())
+            .collect();
+        assert_eq!(block_counts, vec![1, 3, 1]);
+
+        let chapter_1 = &splits[1];
+        let body = chapter_1.document.body.as_ref().unwrap();
+        assert!(section_break(&body.block_level_elements[1]).is_some());
+        assert_eq!(chapter_1.header_footer_relationships.len(), 2);
+        assert_eq!(chapter_1.numbering.numberings.len(), 1);
+        assert_eq!(chapter_1.numbering.abstract_numberings[0].abstract_num_id, 1);
+
+        // The last document inherits the header of the section left behind
+        let chapter_2 = &splits[2];
+        let section_properties = chapter_2.document.body.as_ref().unwrap().section_properties.as_ref();
+        assert_eq!(section_properties.unwrap().header_footer_references.len(), 2);
+        assert_eq!(chapter_2.header_footer_relationships.len(), 2);
+        assert!(chapter_2.numbering.numberings.is_empty());
+
+        let sections = package.split_by_sections();
+        assert_eq!(sections.len(), 2);
+        let body = sections[0].document.body.as_ref().unwrap();
+        assert_eq!(body.block_level_elements.len(), 3);
+        assert!(section_break(&body.block_level_elements[2]).is_none());
+        assert_eq!(
+            sections[0].header_footer_relationships,
+            vec![relationship("rId1", "header")]
+        );
+    }
+}
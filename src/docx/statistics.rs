@@ -0,0 +1,326 @@
+use super::{
+    package::Package,
+    preview::for_each_table_block,
+    resolvedstyle::RunProperties,
+    wml::document::{
+        BlockLevelElts, BrType, ContentBlockContent, ContentRunContent, PContent, RunInnerContent, RunLevelElts,
+        RunTrackChangeChoice, SectPr, SectionMark, P, R,
+    },
+};
+
+/// Statistics of the main document, counted the way Word counts the `Words`, `Characters`, `CharactersWithSpaces`
+/// and `Paragraphs` properties of `docProps/app.xml`.
+///
+/// Only the body of the document is counted, text in headers, footers, notes and text boxes is not. Hidden text,
+/// field instructions and deleted or moved-from text are skipped, field results are counted. A paragraph whose mark
+/// is deleted is counted together with the following paragraph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DocumentStatistics {
+    /// Runs of non-whitespace characters. Every Chinese and Japanese character counts as a word on its own.
+    pub words: usize,
+    /// Characters without whitespace
+    pub characters: usize,
+    pub characters_with_spaces: usize,
+    /// Paragraphs containing at least one non-whitespace character
+    pub paragraphs: usize,
+    /// Manual page breaks and paragraphs with the page break before property
+    pub page_breaks: usize,
+    /// Sections starting on a new page, not counting the first section
+    pub section_page_breaks: usize,
+    /// Page breaks at the positions where the page ended when the document was last saved by Word. The number of pages
+    /// according to the last layout is one more than this.
+    pub rendered_page_breaks: usize,
+}
+
+impl Package {
+    /// Computes the statistics of the main document.
+    pub fn statistics(&self) -> DocumentStatistics {
+        let mut counter = StatisticsCounter {
+            package: self,
+            statistics: Default::default(),
+            paragraph_text: String::new(),
+            section_count: 0,
+        };
+
+        if let Some(body) = self.main_document.as_ref().and_then(|document| document.body.as_ref()) {
+            for element in &body.block_level_elements {
+                counter.block_level_element(element);
+            }
+
+            counter.end_paragraph();
+            counter.section(body.section_properties.as_ref());
+        }
+
+        counter.statistics
+    }
+}
+
+struct StatisticsCounter<'a> {
+    package: &'a Package,
+    statistics: DocumentStatistics,
+    /// The visible text of the current paragraph, which continues into the next paragraph if its mark is deleted
+    paragraph_text: String,
+    section_count: usize,
+}
+
+impl StatisticsCounter<'_> {
+    fn block_level_element(&mut self, element: &BlockLevelElts) {
+        if let BlockLevelElts::Chunk(content) = element {
+            self.content_block(content);
+        }
+    }
+
+    fn content_block(&mut self, content: &ContentBlockContent) {
+        match content {
+            ContentBlockContent::CustomXml(custom_xml) => {
+                for content in &custom_xml.block_contents {
+                    self.content_block(content);
+                }
+            }
+            ContentBlockContent::Sdt(sdt) => {
+                for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                    self.content_block(content);
+                }
+            }
+            ContentBlockContent::Paragraph(paragraph) => self.paragraph(paragraph),
+            ContentBlockContent::Table(table) => {
+                let mut elements = Vec::new();
+                for_each_table_block(table, &mut |element| elements.push(element));
+                for element in elements {
+                    self.block_level_element(element);
+                }
+            }
+            ContentBlockContent::RunLevelElement(_) => (),
+        }
+    }
+
+    fn paragraph(&mut self, paragraph: &P) {
+        let paragraph_style = self.package.resolve_style_inheritance(paragraph, &R::default());
+        let has_page_break_before = paragraph_style
+            .as_ref()
+            .and_then(|style| style.paragraph_properties.start_on_next_page)
+            .or_else(|| paragraph.properties.as_ref()?.base.start_on_next_page)
+            .unwrap_or(false);
+        if has_page_break_before {
+            self.statistics.page_breaks += 1;
+        }
+
+        self.paragraph_contents(paragraph, &paragraph.contents);
+
+        let track_changes = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.run_properties.as_ref())
+            .and_then(|run_properties| run_properties.track_changes.as_ref());
+        let is_mark_deleted = matches!(
+            track_changes,
+            Some(track_changes) if track_changes.deleted.is_some() || track_changes.move_from.is_some()
+        );
+        if !is_mark_deleted {
+            self.end_paragraph();
+        }
+
+        let section_properties = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.section_properties.as_ref());
+        if section_properties.is_some() {
+            self.section(section_properties);
+        }
+    }
+
+    fn paragraph_contents(&mut self, paragraph: &P, contents: &[PContent]) {
+        for content in contents {
+            match content {
+                PContent::ContentRunContent(content) => self.content_run(paragraph, content),
+                PContent::SimpleField(field) => self.paragraph_contents(paragraph, &field.paragraph_contents),
+                PContent::Hyperlink(hyperlink) => self.paragraph_contents(paragraph, &hyperlink.paragraph_contents),
+                PContent::SubDocument(_) => (),
+            }
+        }
+    }
+
+    fn content_run(&mut self, paragraph: &P, content: &ContentRunContent) {
+        match content {
+            ContentRunContent::Run(run) => self.run(paragraph, run),
+            ContentRunContent::CustomXml(custom_xml) => {
+                self.paragraph_contents(paragraph, &custom_xml.paragraph_contents)
+            }
+            ContentRunContent::SmartTag(smart_tag) => self.paragraph_contents(paragraph, &smart_tag.paragraph_contents),
+            ContentRunContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.sdt_content {
+                    self.paragraph_contents(paragraph, &sdt_content.p_contents);
+                }
+            }
+            ContentRunContent::Bidirectional(dir) => self.paragraph_contents(paragraph, &dir.p_contents),
+            ContentRunContent::BidirectionalOverride(bdo) => self.paragraph_contents(paragraph, &bdo.p_contents),
+            ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+            | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+                for choice in &track_change.choices {
+                    match choice {
+                        RunTrackChangeChoice::ContentRunContent(content) => self.content_run(paragraph, content),
+                    }
+                }
+            }
+            ContentRunContent::RunLevelElements(_) => (),
+        }
+    }
+
+    fn run(&mut self, paragraph: &P, run: &R) {
+        // Without any style to inherit from, only the direct formatting applies
+        let run_properties = match self.package.resolve_style_inheritance(paragraph, run) {
+            Some(style) => *style.run_properties,
+            None => run
+                .run_properties
+                .as_ref()
+                .map(|r_pr| RunProperties::from_vec(&r_pr.r_pr_bases))
+                .unwrap_or_default(),
+        };
+        let is_hidden = run_properties.vanish == Some(true) || run_properties.special_vanish == Some(true);
+        if is_hidden {
+            return;
+        }
+
+        for inner_content in &run.run_inner_contents {
+            match inner_content {
+                RunInnerContent::Text(text) => self.paragraph_text.push_str(&text.text),
+                RunInnerContent::Tab | RunInnerContent::PositionTab(_) => self.paragraph_text.push('\t'),
+                RunInnerContent::Break(br) => {
+                    if br.break_type == Some(BrType::Page) {
+                        self.statistics.page_breaks += 1;
+                    }
+
+                    self.paragraph_text.push('\n');
+                }
+                RunInnerContent::CarriageReturn => self.paragraph_text.push('\n'),
+                RunInnerContent::NonBreakingHyphen => self.paragraph_text.push('\u{2011}'),
+                RunInnerContent::Symbol(_) => self.paragraph_text.push('\u{fffc}'),
+                RunInnerContent::LastRenderedPageBreak => self.statistics.rendered_page_breaks += 1,
+                _ => (),
+            }
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        if self.paragraph_text.is_empty() {
+            return;
+        }
+
+        let mut is_in_word = false;
+        let mut has_text = false;
+        for c in self.paragraph_text.chars() {
+            self.statistics.characters_with_spaces += 1;
+            if c.is_whitespace() {
+                is_in_word = false;
+                continue;
+            }
+
+            has_text = true;
+            self.statistics.characters += 1;
+            if is_ideographic(c) {
+                self.statistics.words += 1;
+                is_in_word = false;
+            } else if !is_in_word {
+                self.statistics.words += 1;
+                is_in_word = true;
+            }
+        }
+
+        if has_text {
+            self.statistics.paragraphs += 1;
+        }
+
+        self.paragraph_text.clear();
+    }
+
+    fn section(&mut self, section_properties: Option<&SectPr>) {
+        // The section type defaults to nextPage if omitted
+        let section_type = section_properties
+            .and_then(|properties| properties.contents.as_ref())
+            .and_then(|contents| contents.section_type)
+            .unwrap_or(SectionMark::NextPage);
+        let starts_new_page = match section_type {
+            SectionMark::NextPage | SectionMark::EvenPage | SectionMark::OddPage => true,
+            SectionMark::NextColumn | SectionMark::Continuous => false,
+        };
+
+        if self.section_count > 0 && starts_new_page {
+            self.statistics.section_page_breaks += 1;
+        }
+
+        self.section_count += 1;
+    }
+}
+
+/// Whether the character is a Chinese or Japanese character, which Word counts as a word on its own.
+fn is_ideographic(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30ff}' | '\u{3400}'..='\u{4dbf}' | '\u{4e00}'..='\u{9fff}' | '\u{f900}'..='\u{faff}' | '\u{ff66}'..='\u{ff9f}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{document::Document, styles::Styles},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_statistics() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Hello  world, </w:t></w:r>
+                <w:r><w:rPr><w:vanish /></w:rPr><w:t>hidden</w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Secret" /></w:rPr><w:t>secret</w:t></w:r>
+                <w:del w:id="1" w:author="Jane"><w:r><w:delText>gone</w:delText></w:r></w:del>
+                <w:ins w:id="2" w:author="Jane"><w:r><w:t>again</w:t></w:r></w:ins>
+            </w:p>
+            <w:p>
+                <w:r><w:fldChar w:fldCharType="begin" /></w:r>
+                <w:r><w:instrText xml:space="preserve"> PAGE </w:instrText></w:r>
+                <w:r><w:fldChar w:fldCharType="separate" /></w:r>
+                <w:r><w:t>7</w:t></w:r>
+                <w:r><w:fldChar w:fldCharType="end" /></w:r>
+                <w:r><w:br w:type="page" /><w:lastRenderedPageBreak /><w:t>日本語</w:t></w:r>
+            </w:p>
+            <w:p />
+            <w:p>
+                <w:pPr><w:rPr><w:del w:id="3" w:author="Jane" /></w:rPr><w:sectPr><w:type w:val="continuous" /></w:sectPr></w:pPr>
+                <w:r><w:t>split</w:t></w:r>
+            </w:p>
+            <w:p><w:pPr><w:pageBreakBefore /></w:pPr><w:r><w:t>word</w:t></w:r></w:p>
+            <w:sectPr />
+        </w:body></w:document>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="character" w:styleId="Secret"><w:rPr><w:vanish /></w:rPr></w:style>
+        </w:styles>"#;
+
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            styles: Some(Box::new(
+                Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            package.statistics(),
+            DocumentStatistics {
+                // Hello, world,, again | 7, 日, 本, 語 | splitword
+                words: 8,
+                characters: 29,
+                characters_with_spaces: 33,
+                paragraphs: 3,
+                page_breaks: 2,
+                section_page_breaks: 1,
+                rendered_page_breaks: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,831 @@
+use super::{
+    package::Package,
+    positioning::PositionedObject,
+    resolvedstyle::{ResolvedStyle, RunProperties},
+    wml::{
+        document::{Border, Cnf, Shd, TextDirection, VerticalJc},
+        styles::{Style, TblStyleOverrideType, TblStylePr},
+        table::{
+            ContentCellContent, ContentRowContent, Merge, Row, Tbl, TblBorders, TblCellMar, TblLook, TblWidth, Tc,
+            TcBorders, TcMar, TcPrBase, TrPrBase,
+        },
+    },
+};
+use crate::shared::sharedtypes::TwipsMeasure;
+
+/// The borders of a cell on its four edges.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CellBorders {
+    pub top: Option<Border>,
+    pub start: Option<Border>,
+    pub bottom: Option<Border>,
+    pub end: Option<Border>,
+}
+
+impl CellBorders {
+    fn update_with_tc_borders(&mut self, borders: &TcBorders) {
+        self.top = borders.top.or(self.top);
+        self.start = borders.start.or(self.start);
+        self.bottom = borders.bottom.or(self.bottom);
+        self.end = borders.end.or(self.end);
+    }
+}
+
+/// The margins between the edges of a cell and its contents.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CellMargins {
+    pub top: Option<TblWidth>,
+    pub start: Option<TblWidth>,
+    pub bottom: Option<TblWidth>,
+    pub end: Option<TblWidth>,
+}
+
+impl CellMargins {
+    fn update_with_tbl_cell_mar(&mut self, margins: &TblCellMar) {
+        self.update(&margins.top, &margins.start, &margins.bottom, &margins.end);
+    }
+
+    fn update_with_tc_mar(&mut self, margins: &TcMar) {
+        self.update(&margins.top, &margins.start, &margins.bottom, &margins.end);
+    }
+
+    fn update(
+        &mut self,
+        top: &Option<TblWidth>,
+        start: &Option<TblWidth>,
+        bottom: &Option<TblWidth>,
+        end: &Option<TblWidth>,
+    ) {
+        self.top = top.clone().or_else(|| self.top.take());
+        self.start = start.clone().or_else(|| self.start.take());
+        self.bottom = bottom.clone().or_else(|| self.bottom.take());
+        self.end = end.clone().or_else(|| self.end.take());
+    }
+}
+
+/// A cell of a table placed on the grid of the table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutCell<'a> {
+    pub cell: &'a Tc,
+    /// The index of the first row the cell occupies.
+    pub row: usize,
+    /// The index of the first grid column the cell occupies.
+    pub column: usize,
+    /// The number of rows the cell spans through vertical merging.
+    pub row_span: usize,
+    /// The number of grid columns the cell spans.
+    pub column_span: usize,
+    /// The conditional formats applying to the cell. Taken from the `cnfStyle` of the cell if present, otherwise
+    /// computed from the position of the cell and the table look.
+    pub conditional_formatting: Cnf,
+    /// The effective borders of the cell, as seen from the cell. Conflicts with the borders of the adjacent cells
+    /// aren't resolved.
+    pub borders: CellBorders,
+    /// The effective shading of the cell.
+    pub shading: Option<Shd>,
+    /// The effective margins of the cell, merged from the default cell margins of the table and the cell properties.
+    pub margins: CellMargins,
+    /// The effective vertical alignment of the contents of the cell.
+    pub vertical_alignment: Option<VerticalJc>,
+    /// The effective direction of the text flow in the cell.
+    pub text_direction: Option<TextDirection>,
+    /// Whether the text of the cell is expanded or compressed to fit the width of the cell (`tcFitText`).
+    pub fit_text: bool,
+    /// The paragraph and run properties the table style applies to the contents of the cell, combined from the
+    /// properties of the style and of the conditional formats applying to the cell. Paragraph styles and direct
+    /// formatting of the contents take precedence.
+    pub content_style: ResolvedStyle,
+}
+
+impl LayoutCell<'_> {
+    fn update_with_tc_pr(&mut self, tc_pr: &TcPrBase) {
+        if let Some(tc_borders) = &tc_pr.borders {
+            self.borders.update_with_tc_borders(tc_borders);
+        }
+
+        if let Some(tc_mar) = &tc_pr.margin {
+            self.margins.update_with_tc_mar(tc_mar);
+        }
+
+        self.shading = tc_pr.shading.or(self.shading);
+        self.vertical_alignment = tc_pr.vertical_alignment.or(self.vertical_alignment);
+        self.text_direction = tc_pr.text_direction.or(self.text_direction);
+        self.fit_text = tc_pr.fit_text.unwrap_or(self.fit_text);
+    }
+}
+
+/// A row of a table with its pagination properties resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutRow<'a> {
+    pub row: &'a Row,
+    /// Whether the row is a header row (`tblHeader`), repeated at the top of every page the table continues on if it's
+    /// one of the leading rows of the table.
+    pub header: bool,
+    /// Whether the row must be kept on a single page (`cantSplit`).
+    pub cant_split: bool,
+}
+
+/// A rectangular row × column view of a table with the grid spans and vertical merges resolved.
+///
+/// ```
+/// use oox::docx::{
+///     tablelayout::TableLayout,
+///     wml::table::Tbl,
+/// };
+/// # use oox::xml::XmlNode;
+/// # use std::str::FromStr;
+///
+/// let xml = r#"<w:tbl>
+///     <w:tblPr />
+///     <w:tblGrid><w:gridCol w:w="1000" /><w:gridCol w:w="2000" /></w:tblGrid>
+///     <w:tr>
+///         <w:tc><w:tcPr><w:vMerge w:val="restart" /></w:tcPr><w:p /></w:tc>
+///         <w:tc><w:p /></w:tc>
+///     </w:tr>
+///     <w:tr>
+///         <w:tc><w:tcPr><w:vMerge /></w:tcPr><w:p /></w:tc>
+///         <w:tc><w:p /></w:tc>
+///     </w:tr>
+/// </w:tbl>"#;
+/// let table = Tbl::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+///
+/// let layout = TableLayout::new(&table);
+/// assert_eq!((layout.row_count(), layout.column_count()), (2, 2));
+/// assert_eq!(layout.cell_at(1, 0).unwrap().row, 0);
+/// assert_eq!(layout.cell_at(1, 0).unwrap().row_span, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableLayout<'a> {
+    /// The widths of the grid columns as defined by `tblGrid`. Columns used by the cells but missing from the grid
+    /// have no width.
+    pub column_widths: Vec<Option<TwipsMeasure>>,
+    /// The position of the table if it's a floating table (`tblpPr`).
+    pub position: Option<PositionedObject>,
+    pub rows: Vec<LayoutRow<'a>>,
+    pub cells: Vec<LayoutCell<'a>>,
+    grid: Vec<Vec<Option<usize>>>,
+}
+
+impl<'a> TableLayout<'a> {
+    /// Lays out the table using its direct formatting only.
+    pub fn new(table: &'a Tbl) -> Self {
+        Self::with_styles(table, &[])
+    }
+
+    /// Lays out the table using the conditional formatting of its table style. `styles` is the style of the table
+    /// followed by the styles it's based on, nearest first.
+    pub fn with_styles(table: &'a Tbl, styles: &[&Style]) -> Self {
+        let rows = collect_rows(&table.row_contents);
+
+        let mut cells: Vec<LayoutCell<'a>> = Vec::new();
+        let mut grid: Vec<Vec<Option<usize>>> = Vec::with_capacity(rows.len());
+        for (row_idx, row) in rows.iter().enumerate() {
+            let mut grid_row = Vec::new();
+            let grid_before = row
+                .properties
+                .as_ref()
+                .and_then(|tr_pr| tr_pr.base.grid_column_before_first_cell)
+                .unwrap_or(0)
+                .max(0) as usize;
+            grid_row.resize(grid_before, None);
+
+            for cell in collect_cells(&row.contents) {
+                let column = grid_row.len();
+                let properties = tc_pr_base(cell);
+                let column_span = properties.and_then(|tc_pr| tc_pr.grid_span).unwrap_or(1).max(1) as usize;
+
+                let merged_into = match properties.and_then(|tc_pr| tc_pr.vertical_merge.as_ref()) {
+                    Some(Merge::Continue) => grid
+                        .last()
+                        .and_then(|previous_row| previous_row.get(column).copied().flatten())
+                        .filter(|&cell_idx| cells[cell_idx].column == column),
+                    _ => None,
+                };
+
+                let cell_idx = match merged_into {
+                    Some(cell_idx) => {
+                        cells[cell_idx].row_span = row_idx - cells[cell_idx].row + 1;
+                        cell_idx
+                    }
+                    None => {
+                        cells.push(LayoutCell {
+                            cell,
+                            row: row_idx,
+                            column,
+                            row_span: 1,
+                            column_span,
+                            conditional_formatting: Default::default(),
+                            borders: Default::default(),
+                            shading: None,
+                            margins: Default::default(),
+                            vertical_alignment: None,
+                            text_direction: None,
+                            fit_text: false,
+                            content_style: Default::default(),
+                        });
+                        cells.len() - 1
+                    }
+                };
+
+                grid_row.resize(column + column_span, Some(cell_idx));
+            }
+
+            grid.push(grid_row);
+        }
+
+        let column_widths: Vec<Option<TwipsMeasure>> =
+            table.grid.base.columns.iter().map(|grid_col| grid_col.width).collect();
+        let column_count = grid.iter().map(Vec::len).max().unwrap_or(0).max(column_widths.len());
+        for grid_row in &mut grid {
+            grid_row.resize(column_count, None);
+        }
+
+        let style_row_properties = styles
+            .iter()
+            .find_map(|style| style.table_row_properties.as_ref())
+            .map(|tr_pr| &tr_pr.base);
+        let layout_rows = rows
+            .iter()
+            .map(|row| {
+                let row_properties = row.properties.as_ref().map(|tr_pr| &tr_pr.base);
+                let resolve = |property: fn(&TrPrBase) -> Option<bool>| {
+                    row_properties
+                        .and_then(property)
+                        .or_else(|| style_row_properties.and_then(property))
+                        .unwrap_or(false)
+                };
+
+                LayoutRow {
+                    row,
+                    header: resolve(|tr_pr| tr_pr.header),
+                    cant_split: resolve(|tr_pr| tr_pr.cant_split),
+                }
+            })
+            .collect();
+
+        let mut instance = Self {
+            column_widths,
+            position: table.positioned_object(),
+            rows: layout_rows,
+            cells,
+            grid,
+        };
+        instance.resolve_formatting(table, &rows, styles);
+        instance.column_widths.resize(column_count, None);
+        instance
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.grid.len()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.grid.first().map(Vec::len).unwrap_or(0)
+    }
+
+    /// Returns the number of header rows repeated on every page the table continues on. Only the header rows at the
+    /// start of the table are repeated.
+    pub fn repeated_header_row_count(&self) -> usize {
+        self.rows.iter().take_while(|row| row.header).count()
+    }
+
+    /// Returns whether the row is repeated on every page the table continues on.
+    pub fn is_repeated_header_row(&self, row: usize) -> bool {
+        row < self.repeated_header_row_count()
+    }
+
+    /// Returns the cell occupying the given grid position. Merged positions return the cell the merge starts with.
+    /// Returns `None` for positions out of the table and for positions not covered by any cell, e.g. `gridBefore`.
+    pub fn cell_at(&self, row: usize, column: usize) -> Option<&LayoutCell<'a>> {
+        let cell_idx = (*self.grid.get(row)?.get(column)?)?;
+        self.cells.get(cell_idx)
+    }
+
+    /// Returns the cells of a row, merged cells included once. Cells continuing a vertical merge are returned as the
+    /// cell the merge starts with.
+    pub fn row(&self, row: usize) -> Vec<&LayoutCell<'a>> {
+        let mut cells: Vec<&LayoutCell<'a>> = Vec::new();
+        if let Some(grid_row) = self.grid.get(row) {
+            for &cell_idx in grid_row.iter().flatten() {
+                if !matches!(cells.last(), Some(cell) if std::ptr::eq(*cell, &self.cells[cell_idx])) {
+                    cells.push(&self.cells[cell_idx]);
+                }
+            }
+        }
+
+        cells
+    }
+
+    fn resolve_formatting(&mut self, table: &Tbl, rows: &[&Row], styles: &[&Style]) {
+        let table_properties = &table.properties.base;
+        let style_table_properties = styles.iter().find_map(|style| style.table_properties.as_ref());
+        let look = table_properties
+            .look
+            .as_ref()
+            .or_else(|| style_table_properties.and_then(|tbl_pr| tbl_pr.look.as_ref()));
+        let row_band_size = table_properties
+            .style_row_band_size
+            .or_else(|| style_table_properties.and_then(|tbl_pr| tbl_pr.style_row_band_size))
+            .unwrap_or(1)
+            .max(1) as usize;
+        let column_band_size = table_properties
+            .style_column_band_size
+            .or_else(|| style_table_properties.and_then(|tbl_pr| tbl_pr.style_column_band_size))
+            .unwrap_or(1)
+            .max(1) as usize;
+
+        let table_shading = table_properties
+            .shading
+            .or_else(|| style_table_properties.and_then(|tbl_pr| tbl_pr.shading));
+        let style_cell_properties = styles
+            .iter()
+            .find_map(|style| style.table_cell_properties.as_ref())
+            .map(|tc_pr| &tc_pr.base.base);
+        let style_content_style = styles
+            .iter()
+            .rev()
+            .fold(ResolvedStyle::default(), |resolved_style, style| {
+                resolved_style.update_with(ResolvedStyle::from_wml_style(style))
+            });
+
+        let row_count = self.row_count();
+        let column_count = self.column_count();
+        for layout_cell in &mut self.cells {
+            let cell_properties = tc_pr_base(layout_cell.cell);
+            layout_cell.conditional_formatting = cell_properties
+                .and_then(|tc_pr| tc_pr.conditional_formatting)
+                .unwrap_or_else(|| {
+                    compute_conditional_formatting(
+                        layout_cell,
+                        look,
+                        (row_count, column_count),
+                        (row_band_size, column_band_size),
+                    )
+                });
+
+            let table_borders = merge_table_borders(&[
+                style_table_properties.and_then(|tbl_pr| tbl_pr.borders.as_ref()),
+                table_properties.borders.as_ref(),
+                rows[layout_cell.row]
+                    .property_exceptions
+                    .as_ref()
+                    .and_then(|tbl_pr_ex| tbl_pr_ex.base.borders.as_ref()),
+            ]);
+            let borders = CellBorders {
+                top: if layout_cell.row == 0 {
+                    table_borders.top
+                } else {
+                    table_borders.inside_horizontal
+                },
+                start: if layout_cell.column == 0 {
+                    table_borders.start
+                } else {
+                    table_borders.inside_vertical
+                },
+                bottom: if layout_cell.row + layout_cell.row_span >= row_count {
+                    table_borders.bottom
+                } else {
+                    table_borders.inside_horizontal
+                },
+                end: if layout_cell.column + layout_cell.column_span >= column_count {
+                    table_borders.end
+                } else {
+                    table_borders.inside_vertical
+                },
+            };
+
+            let mut margins = CellMargins::default();
+            let row_exceptions = rows[layout_cell.row].property_exceptions.as_ref();
+            for tbl_cell_mar in [
+                style_table_properties.and_then(|tbl_pr| tbl_pr.cell_margin.as_ref()),
+                table_properties.cell_margin.as_ref(),
+                row_exceptions.and_then(|tbl_pr_ex| tbl_pr_ex.base.cell_margin.as_ref()),
+            ]
+            .iter()
+            .flatten()
+            {
+                margins.update_with_tbl_cell_mar(tbl_cell_mar);
+            }
+
+            layout_cell.borders = borders;
+            layout_cell.shading = table_shading;
+            layout_cell.margins = margins;
+            if let Some(tc_pr) = style_cell_properties {
+                layout_cell.update_with_tc_pr(tc_pr);
+            }
+
+            let mut content_style = style_content_style.clone();
+            for override_type in applying_override_types(&layout_cell.conditional_formatting) {
+                for tbl_style_pr in styles
+                    .iter()
+                    .rev()
+                    .filter_map(|style| find_style_override(style, override_type))
+                {
+                    content_style = content_style.update_with(override_content_style(tbl_style_pr));
+                }
+
+                let conditional_properties = styles
+                    .iter()
+                    .find_map(|style| find_style_override(style, override_type))
+                    .and_then(|tbl_style_pr| tbl_style_pr.table_cell_properties.as_ref())
+                    .map(|tc_pr| &tc_pr.base.base);
+                if let Some(tc_pr) = conditional_properties {
+                    layout_cell.update_with_tc_pr(tc_pr);
+                }
+            }
+
+            if let Some(tc_pr) = cell_properties {
+                layout_cell.update_with_tc_pr(tc_pr);
+            }
+
+            layout_cell.content_style = content_style;
+        }
+    }
+}
+
+impl Package {
+    /// Lays out a table using the conditional formatting of its table style and the styles it's based on.
+    pub fn table_layout<'a>(&self, table: &'a Tbl) -> TableLayout<'a> {
+        let mut styles: Vec<&Style> = Vec::new();
+        let mut style_id = table.properties.base.style.as_deref();
+        while let Some(id) = style_id {
+            let style = self
+                .styles
+                .as_ref()
+                .and_then(|styles| styles.styles.iter().find(|style| style.style_id.as_deref() == Some(id)));
+
+            match style {
+                Some(style) if !styles.iter().any(|visited| std::ptr::eq(*visited, style)) => {
+                    styles.push(style);
+                    style_id = style.based_on.as_deref();
+                }
+                _ => break,
+            }
+        }
+
+        TableLayout::with_styles(table, &styles)
+    }
+}
+
+fn override_content_style(tbl_style_pr: &TblStylePr) -> ResolvedStyle {
+    ResolvedStyle {
+        paragraph_properties: Box::new(
+            tbl_style_pr
+                .paragraph_properties
+                .as_ref()
+                .map(|p_pr| p_pr.base.clone())
+                .unwrap_or_default(),
+        ),
+        run_properties: Box::new(
+            tbl_style_pr
+                .run_properties
+                .as_ref()
+                .map(|r_pr| RunProperties::from_vec(&r_pr.r_pr_bases))
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+fn tc_pr_base(cell: &Tc) -> Option<&TcPrBase> {
+    cell.properties.as_ref().map(|tc_pr| &tc_pr.base.base)
+}
+
+fn collect_rows(row_contents: &[ContentRowContent]) -> Vec<&Row> {
+    let mut rows = Vec::new();
+    for content in row_contents {
+        match content {
+            ContentRowContent::Table(row) => rows.push(row.as_ref()),
+            ContentRowContent::CustomXml(custom_xml) => rows.extend(collect_rows(&custom_xml.contents)),
+            ContentRowContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.content {
+                    rows.extend(collect_rows(&sdt_content.contents));
+                }
+            }
+            ContentRowContent::RunLevelElements(_) => (),
+        }
+    }
+
+    rows
+}
+
+fn collect_cells(cell_contents: &[ContentCellContent]) -> Vec<&Tc> {
+    let mut cells = Vec::new();
+    for content in cell_contents {
+        match content {
+            ContentCellContent::Cell(cell) => cells.push(cell.as_ref()),
+            ContentCellContent::CustomXml(custom_xml) => cells.extend(collect_cells(&custom_xml.contents)),
+            ContentCellContent::Sdt(sdt) => {
+                if let Some(sdt_content) = &sdt.content {
+                    cells.extend(collect_cells(&sdt_content.contents));
+                }
+            }
+            ContentCellContent::RunLevelElement(_) => (),
+        }
+    }
+
+    cells
+}
+
+fn merge_table_borders(borders_list: &[Option<&TblBorders>]) -> TblBorders {
+    borders_list
+        .iter()
+        .flatten()
+        .fold(TblBorders::default(), |merged, borders| TblBorders {
+            top: borders.top.or(merged.top),
+            start: borders.start.or(merged.start),
+            bottom: borders.bottom.or(merged.bottom),
+            end: borders.end.or(merged.end),
+            inside_horizontal: borders.inside_horizontal.or(merged.inside_horizontal),
+            inside_vertical: borders.inside_vertical.or(merged.inside_vertical),
+        })
+}
+
+fn compute_conditional_formatting(
+    layout_cell: &LayoutCell<'_>,
+    look: Option<&TblLook>,
+    (row_count, column_count): (usize, usize),
+    (row_band_size, column_band_size): (usize, usize),
+) -> Cnf {
+    let look = match look {
+        Some(look) => look,
+        None => return Default::default(),
+    };
+
+    let first_row = look.first_row == Some(true) && layout_cell.row == 0;
+    let last_row = look.last_row == Some(true) && layout_cell.row + layout_cell.row_span >= row_count;
+    let first_column = look.first_column == Some(true) && layout_cell.column == 0;
+    let last_column = look.last_column == Some(true) && layout_cell.column + layout_cell.column_span >= column_count;
+
+    // Bands are counted from the first row and column not formatted as a header
+    let horizontal_band = if look.no_horizontal_band == Some(true) || first_row || last_row {
+        None
+    } else {
+        let header_rows = if look.first_row == Some(true) { 1 } else { 0 };
+        Some((layout_cell.row.saturating_sub(header_rows) / row_band_size) % 2 == 0)
+    };
+    let vertical_band = if look.no_vertical_band == Some(true) || first_column || last_column {
+        None
+    } else {
+        let header_columns = if look.first_column == Some(true) { 1 } else { 0 };
+        Some((layout_cell.column.saturating_sub(header_columns) / column_band_size) % 2 == 0)
+    };
+
+    let flag = |value: bool| if value { Some(true) } else { None };
+    Cnf {
+        first_row: flag(first_row),
+        last_row: flag(last_row),
+        first_column: flag(first_column),
+        last_column: flag(last_column),
+        odd_vertical_band: flag(vertical_band == Some(true)),
+        even_vertical_band: flag(vertical_band == Some(false)),
+        odd_horizontal_band: flag(horizontal_band == Some(true)),
+        even_horizontal_band: flag(horizontal_band == Some(false)),
+        first_row_first_column: flag(first_row && first_column),
+        first_row_last_column: flag(first_row && last_column),
+        last_row_first_column: flag(last_row && first_column),
+        last_row_last_column: flag(last_row && last_column),
+    }
+}
+
+/// Returns the conditional formats applying to a cell, from the lowest to the highest priority.
+fn applying_override_types(cnf: &Cnf) -> Vec<TblStyleOverrideType> {
+    let candidates = [
+        (Some(true), TblStyleOverrideType::WholeTable),
+        (cnf.odd_vertical_band, TblStyleOverrideType::Band1Vertical),
+        (cnf.even_vertical_band, TblStyleOverrideType::Band2Vertical),
+        (cnf.odd_horizontal_band, TblStyleOverrideType::Band1Horizontal),
+        (cnf.even_horizontal_band, TblStyleOverrideType::Band2Horizontal),
+        (cnf.first_column, TblStyleOverrideType::FirstColumn),
+        (cnf.last_column, TblStyleOverrideType::LastColumn),
+        (cnf.first_row, TblStyleOverrideType::FirstRow),
+        (cnf.last_row, TblStyleOverrideType::LastRow),
+        (cnf.first_row_last_column, TblStyleOverrideType::NorthEastCell),
+        (cnf.first_row_first_column, TblStyleOverrideType::NorthWestCell),
+        (cnf.last_row_last_column, TblStyleOverrideType::SouthEastCell),
+        (cnf.last_row_first_column, TblStyleOverrideType::SouthWestCell),
+    ];
+
+    candidates
+        .iter()
+        .filter(|(applies, _)| *applies == Some(true))
+        .map(|(_, override_type)| *override_type)
+        .collect()
+}
+
+fn find_style_override(style: &Style, override_type: TblStyleOverrideType) -> Option<&TblStylePr> {
+    style
+        .table_style_properties_vec
+        .iter()
+        .find(|tbl_style_pr| tbl_style_pr.override_type == override_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            document::{HexColor, HpsMeasure, Jc},
+            styles::Styles,
+            table::TblWidthType,
+        },
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    const TABLE_XML: &str = r#"<w:tbl>
+        <w:tblPr>
+            <w:tblStyle w:val="Grid" />
+            <w:tblBorders>
+                <w:top w:val="single" w:sz="4" />
+                <w:insideH w:val="dotted" w:sz="2" />
+            </w:tblBorders>
+            <w:tblLook w:firstRow="1" w:lastRow="0" w:firstColumn="0" w:lastColumn="0" w:noHBand="0" w:noVBand="1" />
+        </w:tblPr>
+        <w:tblGrid><w:gridCol w:w="1000" /><w:gridCol w:w="1000" /><w:gridCol w:w="1000" /></w:tblGrid>
+        <w:tr>
+            <w:tc><w:tcPr><w:gridSpan w:val="2" /></w:tcPr><w:p /></w:tc>
+            <w:tc><w:tcPr><w:vMerge w:val="restart" /></w:tcPr><w:p /></w:tc>
+        </w:tr>
+        <w:tr>
+            <w:tc><w:p /></w:tc>
+            <w:tc><w:tcPr><w:shd w:val="clear" w:fill="00FF00" /></w:tcPr><w:p /></w:tc>
+            <w:tc><w:tcPr><w:vMerge /></w:tcPr><w:p /></w:tc>
+        </w:tr>
+        <w:tr>
+            <w:trPr><w:gridBefore w:val="1" /></w:trPr>
+            <w:tc><w:p /></w:tc>
+            <w:tc><w:tcPr><w:vMerge /></w:tcPr><w:p /></w:tc>
+        </w:tr>
+    </w:tbl>"#;
+
+    const STYLES_XML: &str = r#"<w:styles>
+        <w:style w:type="table" w:styleId="Grid">
+            <w:basedOn w:val="Base" />
+            <w:rPr><w:sz w:val="20" /></w:rPr>
+            <w:tblStylePr w:type="firstRow">
+                <w:pPr><w:jc w:val="center" /></w:pPr>
+                <w:rPr><w:b /></w:rPr>
+                <w:tcPr><w:shd w:val="clear" w:fill="FF0000" /></w:tcPr>
+            </w:tblStylePr>
+        </w:style>
+        <w:style w:type="table" w:styleId="Base">
+            <w:rPr><w:sz w:val="18" /><w:i /></w:rPr>
+            <w:tblStylePr w:type="band2Horz">
+                <w:tcPr><w:shd w:val="clear" w:fill="0000FF" /></w:tcPr>
+            </w:tblStylePr>
+        </w:style>
+    </w:styles>"#;
+
+    fn fill(layout_cell: &LayoutCell<'_>) -> Option<[u8; 3]> {
+        match layout_cell.shading.and_then(|shading| shading.fill) {
+            Some(HexColor::RGB(rgb)) => Some(rgb),
+            _ => None,
+        }
+    }
+
+    #[test]
+    pub fn test_table_layout_grid() {
+        let table = Tbl::from_xml_element(&XmlNode::from_str(TABLE_XML).unwrap()).unwrap();
+        let layout = TableLayout::new(&table);
+
+        assert_eq!(layout.row_count(), 3);
+        assert_eq!(layout.column_count(), 3);
+        assert_eq!(layout.column_widths, vec![Some(TwipsMeasure::Decimal(1000)); 3]);
+        assert_eq!(layout.cells.len(), 5);
+
+        let spanned = layout.cell_at(0, 1).unwrap();
+        assert_eq!((spanned.row, spanned.column, spanned.column_span), (0, 0, 2));
+
+        let merged = layout.cell_at(2, 2).unwrap();
+        assert_eq!((merged.row, merged.column, merged.row_span), (0, 2, 3));
+        assert!(layout.cell_at(2, 0).is_none());
+        assert!(layout.cell_at(3, 0).is_none());
+        assert_eq!(layout.row(1).len(), 3);
+        assert_eq!(layout.row(2).len(), 2);
+
+        assert_eq!(spanned.borders.top.unwrap().size, Some(4));
+        assert_eq!(spanned.borders.bottom.unwrap().size, Some(2));
+        assert_eq!(merged.borders.bottom, None);
+    }
+
+    #[test]
+    pub fn test_table_layout_conditional_formatting() {
+        let table = Tbl::from_xml_element(&XmlNode::from_str(TABLE_XML).unwrap()).unwrap();
+        let styles = Styles::from_xml_element(&XmlNode::from_str(STYLES_XML).unwrap()).unwrap();
+        let package = Package {
+            styles: Some(Box::new(styles)),
+            ..Default::default()
+        };
+        let layout = package.table_layout(&table);
+
+        let header = layout.cell_at(0, 0).unwrap();
+        assert_eq!(header.conditional_formatting.first_row, Some(true));
+        assert_eq!(fill(header), Some([0xFF, 0x00, 0x00]));
+        let header_run_properties = &header.content_style.run_properties;
+        assert_eq!(header_run_properties.bold, Some(true));
+        assert_eq!(header_run_properties.italic, Some(true));
+        assert_eq!(header_run_properties.font_size, Some(HpsMeasure::Decimal(20)));
+        assert_eq!(header.content_style.paragraph_properties.alignment, Some(Jc::Center));
+
+        let odd_band = layout.cell_at(1, 0).unwrap();
+        assert_eq!(odd_band.conditional_formatting.odd_horizontal_band, Some(true));
+        assert_eq!(fill(odd_band), None);
+        assert_eq!(odd_band.content_style.run_properties.bold, None);
+        assert_eq!(fill(layout.cell_at(1, 1).unwrap()), Some([0x00, 0xFF, 0x00]));
+
+        let even_band = layout.cell_at(2, 1).unwrap();
+        assert_eq!(even_band.conditional_formatting.even_horizontal_band, Some(true));
+        assert_eq!(fill(even_band), Some([0x00, 0x00, 0xFF]));
+    }
+
+    #[test]
+    pub fn test_table_layout_cell_properties() {
+        let table_xml = r#"<w:tbl>
+            <w:tblPr>
+                <w:tblStyle w:val="Grid" />
+                <w:tblCellMar><w:left w:w="100" w:type="dxa" /><w:right w:w="100" w:type="dxa" /></w:tblCellMar>
+                <w:tblLook w:firstRow="1" />
+            </w:tblPr>
+            <w:tblGrid><w:gridCol w:w="1000" /><w:gridCol w:w="1000" /></w:tblGrid>
+            <w:tr>
+                <w:tc><w:p /></w:tc>
+                <w:tc>
+                    <w:tcPr>
+                        <w:tcMar><w:start w:w="50" w:type="dxa" /></w:tcMar>
+                        <w:textDirection w:val="btLr" />
+                        <w:tcFitText />
+                        <w:vAlign w:val="bottom" />
+                    </w:tcPr>
+                    <w:p />
+                </w:tc>
+            </w:tr>
+        </w:tbl>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="table" w:styleId="Grid">
+                <w:tcPr><w:vAlign w:val="center" /></w:tcPr>
+                <w:tblStylePr w:type="firstRow">
+                    <w:tcPr><w:tcMar><w:top w:w="20" w:type="dxa" /></w:tcMar></w:tcPr>
+                </w:tblStylePr>
+            </w:style>
+        </w:styles>"#;
+
+        let table = Tbl::from_xml_element(&XmlNode::from_str(table_xml).unwrap()).unwrap();
+        let styles = Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap();
+        let package = Package {
+            styles: Some(Box::new(styles)),
+            ..Default::default()
+        };
+        let layout = package.table_layout(&table);
+        let width = |value: &str| {
+            Some(TblWidth {
+                width: value.parse().ok(),
+                width_type: Some(TblWidthType::TwentiethsOfPoint),
+            })
+        };
+
+        let first = layout.cell_at(0, 0).unwrap();
+        assert_eq!(first.vertical_alignment, Some(VerticalJc::Center));
+        assert_eq!(first.text_direction, None);
+        assert!(!first.fit_text);
+        assert_eq!(first.margins.top, width("20"));
+        assert_eq!(first.margins.start, width("100"));
+
+        let second = layout.cell_at(0, 1).unwrap();
+        assert_eq!(second.vertical_alignment, Some(VerticalJc::Bottom));
+        assert_eq!(second.text_direction, Some(TextDirection::BottomToTopLeftToRight));
+        assert!(second.fit_text);
+        assert_eq!(second.margins.start, width("50"));
+        assert_eq!(second.margins.end, width("100"));
+    }
+
+    #[test]
+    pub fn test_table_layout_rows() {
+        let table_xml = r#"<w:tbl>
+            <w:tblPr><w:tblStyle w:val="Rows" /></w:tblPr>
+            <w:tblGrid><w:gridCol w:w="1000" /></w:tblGrid>
+            <w:tr><w:trPr><w:tblHeader /></w:trPr><w:tc><w:p /></w:tc></w:tr>
+            <w:tr><w:trPr><w:tblHeader /><w:cantSplit w:val="0" /></w:trPr><w:tc><w:p /></w:tc></w:tr>
+            <w:tr><w:tc><w:p /></w:tc></w:tr>
+            <w:tr><w:trPr><w:tblHeader /></w:trPr><w:tc><w:p /></w:tc></w:tr>
+        </w:tbl>"#;
+        let styles_xml = r#"<w:styles>
+            <w:style w:type="table" w:styleId="Rows"><w:trPr><w:cantSplit /></w:trPr></w:style>
+        </w:styles>"#;
+
+        let table = Tbl::from_xml_element(&XmlNode::from_str(table_xml).unwrap()).unwrap();
+        let styles = Styles::from_xml_element(&XmlNode::from_str(styles_xml).unwrap()).unwrap();
+        let package = Package {
+            styles: Some(Box::new(styles)),
+            ..Default::default()
+        };
+        let layout = package.table_layout(&table);
+
+        assert_eq!(layout.rows.len(), 4);
+        assert_eq!(layout.repeated_header_row_count(), 2);
+        assert!(layout.is_repeated_header_row(1));
+        assert!(layout.rows[3].header);
+        assert!(!layout.is_repeated_header_row(3));
+        let cant_split: Vec<bool> = layout.rows.iter().map(|row| row.cant_split).collect();
+        assert_eq!(cant_split, vec![true, false, true, true]);
+    }
+}
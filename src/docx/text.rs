@@ -0,0 +1,535 @@
+use super::{
+    package::Package,
+    wml::{
+        document::{
+            BlockLevelElts, BrType, ContentBlockContent, ContentRunContent, DrawingChoice, Hyperlink, PContent,
+            RunInnerContent, RunLevelElts, RunTrackChangeChoice, P, R,
+        },
+        footnotes::FtnEdn,
+        simpletypes::DecimalNumber,
+        table::{ContentCellContent, ContentRowContent, Tbl},
+    },
+};
+
+/// Controls how footnote and endnote references are rendered by the text and HTML exporters.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NotePlacement {
+    /// References are dropped and the note texts are not exported.
+    #[default]
+    Omit,
+    /// References are replaced by `[n]` markers and the note texts are appended after the document's body. Footnotes
+    /// and endnotes share the same numbering in order of their references.
+    Appendix,
+    /// The note text is inserted in parentheses at the reference point.
+    Inline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NoteKind {
+    Footnote,
+    Endnote,
+}
+
+/// Selects how tracked insertions, deletions and moves are interpreted by the text exporter.
+///
+/// The views are computed from the run level changes only: removing a deleted paragraph mark doesn't merge the
+/// paragraph with the next one. Use [`Document::apply_revisions`](super::wml::document::Document::apply_revisions)
+/// beforehand for an exact result.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RevisionView {
+    /// The text as if every change was accepted: inserted and moved-to text is included, deleted and moved-from text
+    /// is left out.
+    #[default]
+    Accepted,
+    /// The text as if every change was rejected: deleted and moved-from text is included, inserted and moved-to text
+    /// is left out.
+    Rejected,
+    /// The text as stored in the document, with both the inserted and the deleted text included.
+    Original,
+}
+
+impl RevisionView {
+    pub(crate) fn shows_inserted(self) -> bool {
+        self != RevisionView::Rejected
+    }
+
+    pub(crate) fn shows_deleted(self) -> bool {
+        self != RevisionView::Accepted
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextOptions {
+    pub notes: NotePlacement,
+    pub revisions: RevisionView,
+}
+
+impl Package {
+    pub fn find_note(&self, kind: NoteKind, id: DecimalNumber) -> Option<&FtnEdn> {
+        match kind {
+            NoteKind::Footnote => self.find_footnote_with_id(id),
+            NoteKind::Endnote => self.find_endnote_with_id(id),
+        }
+    }
+
+    /// Returns the plain text of a footnote or endnote with its paragraphs joined by spaces.
+    pub fn note_text(&self, kind: NoteKind, id: DecimalNumber) -> Option<String> {
+        self.note_text_in_view(kind, id, RevisionView::default())
+    }
+
+    fn note_text_in_view(&self, kind: NoteKind, id: DecimalNumber, revisions: RevisionView) -> Option<String> {
+        let note = self.find_note(kind, id)?;
+
+        let mut notes = NoteCollector::new(self, NotePlacement::Omit).with_revisions(revisions);
+        let mut lines = Vec::new();
+        for block_level_element in &note.block_level_elements {
+            block_level_element_lines(block_level_element, &mut notes, &mut lines);
+        }
+
+        let text: Vec<&str> = lines
+            .iter()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+
+        Some(text.join(" "))
+    }
+
+    /// Extracts the plain text of the main document. Every paragraph is written on its own line, table rows are
+    /// written as lines with the cells separated by tabs. Tracked changes are interpreted according to
+    /// [`TextOptions::revisions`].
+    pub fn extract_text(&self, options: &TextOptions) -> String {
+        let mut notes = NoteCollector::new(self, options.notes).with_revisions(options.revisions);
+        let mut lines = Vec::new();
+
+        let body = self.main_document.as_ref().and_then(|document| document.body.as_ref());
+        for block_level_element in body.iter().flat_map(|body| body.block_level_elements.iter()) {
+            block_level_element_lines(block_level_element, &mut notes, &mut lines);
+        }
+
+        if !notes.appendix.is_empty() {
+            lines.push(String::new());
+            lines.extend(
+                notes
+                    .appendix
+                    .iter()
+                    .map(|(number, text)| format!("[{}] {}", number, text)),
+            );
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// An inline piece of paragraph content as seen by the exporters.
+pub(crate) enum InlineItem<'a> {
+    Text(&'a str),
+    Tab,
+    Break(Option<BrType>),
+    NoteReference(NoteKind, DecimalNumber),
+}
+
+/// Calls `f` for every visible inline item of the paragraph contents in document order. Deleted text and field
+/// instructions are skipped, field results and inserted text are visited.
+pub(crate) fn for_each_inline<'a>(contents: &'a [PContent], f: &mut dyn FnMut(InlineItem<'a>)) {
+    for_each_inline_in_view(contents, RevisionView::Accepted, f);
+}
+
+/// Calls `f` for every inline item of the paragraph contents that is visible in the given revision view. Field
+/// instructions are always skipped.
+pub(crate) fn for_each_inline_in_view<'a>(
+    contents: &'a [PContent],
+    view: RevisionView,
+    f: &mut dyn FnMut(InlineItem<'a>),
+) {
+    for_each_run_item(contents, view, &mut |item| {
+        if let RunItem::Inline(_, inline) = item {
+            f(inline)
+        }
+    });
+}
+
+/// A piece of paragraph content as seen by the exporters rendering formatting.
+pub(crate) enum RunItem<'a> {
+    /// An inline item with the run containing it
+    Inline(&'a R, InlineItem<'a>),
+    Drawing(&'a R, &'a DrawingChoice),
+    HyperlinkStart(&'a Hyperlink),
+    HyperlinkEnd,
+}
+
+/// Calls `f` for every run item of the paragraph contents that is visible in the given revision view, see
+/// [`for_each_inline_in_view`].
+pub(crate) fn for_each_run_item<'a>(contents: &'a [PContent], view: RevisionView, f: &mut dyn FnMut(RunItem<'a>)) {
+    for content in contents {
+        match content {
+            PContent::ContentRunContent(content) => content_run_items(content, view, f),
+            PContent::SimpleField(field) => for_each_run_item(&field.paragraph_contents, view, f),
+            PContent::Hyperlink(hyperlink) => {
+                f(RunItem::HyperlinkStart(hyperlink));
+                for_each_run_item(&hyperlink.paragraph_contents, view, f);
+                f(RunItem::HyperlinkEnd);
+            }
+            PContent::SubDocument(_) => (),
+        }
+    }
+}
+
+fn content_run_items<'a>(content: &'a ContentRunContent, view: RevisionView, f: &mut dyn FnMut(RunItem<'a>)) {
+    match content {
+        ContentRunContent::Run(run) => {
+            for inner_content in &run.run_inner_contents {
+                let inline = match inner_content {
+                    RunInnerContent::Text(text) => InlineItem::Text(&text.text),
+                    RunInnerContent::DeletedText(text) if view.shows_deleted() => InlineItem::Text(&text.text),
+                    RunInnerContent::NonBreakingHyphen => InlineItem::Text("\u{2011}"),
+                    RunInnerContent::Tab | RunInnerContent::PositionTab(_) => InlineItem::Tab,
+                    RunInnerContent::Break(br) => InlineItem::Break(br.break_type),
+                    RunInnerContent::CarriageReturn => InlineItem::Break(None),
+                    RunInnerContent::FootnoteReference(reference) => {
+                        InlineItem::NoteReference(NoteKind::Footnote, reference.id)
+                    }
+                    RunInnerContent::EndnoteReference(reference) => {
+                        InlineItem::NoteReference(NoteKind::Endnote, reference.id)
+                    }
+                    RunInnerContent::Drawing(drawing) => {
+                        for choice in &drawing.0 {
+                            f(RunItem::Drawing(run, choice));
+                        }
+                        continue;
+                    }
+                    _ => continue,
+                };
+
+                f(RunItem::Inline(run, inline));
+            }
+        }
+        ContentRunContent::CustomXml(custom_xml) => for_each_run_item(&custom_xml.paragraph_contents, view, f),
+        ContentRunContent::SmartTag(smart_tag) => for_each_run_item(&smart_tag.paragraph_contents, view, f),
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                for_each_run_item(&sdt_content.p_contents, view, f);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => for_each_run_item(&dir.p_contents, view, f),
+        ContentRunContent::BidirectionalOverride(bdo) => for_each_run_item(&bdo.p_contents, view, f),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change))
+            if view.shows_inserted() =>
+        {
+            track_change_items(&track_change.choices, view, f)
+        }
+        ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+            if view.shows_deleted() =>
+        {
+            track_change_items(&track_change.choices, view, f)
+        }
+        ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+pub(crate) fn track_change_items<'a>(
+    choices: &'a [RunTrackChangeChoice],
+    view: RevisionView,
+    f: &mut dyn FnMut(RunItem<'a>),
+) {
+    for choice in choices {
+        match choice {
+            RunTrackChangeChoice::ContentRunContent(content) => content_run_items(content, view, f),
+        }
+    }
+}
+
+/// How a note reference should be rendered at the reference point.
+pub(crate) enum NoteAnchor {
+    Marker(usize),
+    Inline(String),
+}
+
+/// Numbers the note references of an export and collects the note texts of the appendix. Also carries the revision
+/// view of the export, so the notes are exported in the same view as the referencing content.
+pub(crate) struct NoteCollector<'a> {
+    package: &'a Package,
+    placement: NotePlacement,
+    revisions: RevisionView,
+    pub(crate) appendix: Vec<(usize, String)>,
+}
+
+impl<'a> NoteCollector<'a> {
+    pub(crate) fn new(package: &'a Package, placement: NotePlacement) -> Self {
+        Self {
+            package,
+            placement,
+            revisions: RevisionView::default(),
+            appendix: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_revisions(mut self, revisions: RevisionView) -> Self {
+        self.revisions = revisions;
+        self
+    }
+
+    pub(crate) fn reference(&mut self, kind: NoteKind, id: DecimalNumber) -> Option<NoteAnchor> {
+        match self.placement {
+            NotePlacement::Omit => None,
+            NotePlacement::Appendix => {
+                let number = self.appendix.len() + 1;
+                let text = self
+                    .package
+                    .note_text_in_view(kind, id, self.revisions)
+                    .unwrap_or_default();
+                self.appendix.push((number, text));
+                Some(NoteAnchor::Marker(number))
+            }
+            NotePlacement::Inline => self
+                .package
+                .note_text_in_view(kind, id, self.revisions)
+                .map(NoteAnchor::Inline),
+        }
+    }
+}
+
+fn block_level_element_lines(element: &BlockLevelElts, notes: &mut NoteCollector<'_>, lines: &mut Vec<String>) {
+    if let BlockLevelElts::Chunk(content) = element {
+        content_block_lines(content, notes, lines);
+    }
+}
+
+pub(crate) fn content_block_lines(
+    content: &ContentBlockContent,
+    notes: &mut NoteCollector<'_>,
+    lines: &mut Vec<String>,
+) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.block_contents {
+                content_block_lines(content, notes, lines);
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            for content in sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter()) {
+                content_block_lines(content, notes, lines);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => lines.push(paragraph_text(paragraph, notes)),
+        ContentBlockContent::Table(table) => table_lines(table, notes, lines),
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn paragraph_text(paragraph: &P, notes: &mut NoteCollector<'_>) -> String {
+    let mut text = String::new();
+    let view = notes.revisions;
+    for_each_inline_in_view(&paragraph.contents, view, &mut |item| match item {
+        InlineItem::Text(value) => text.push_str(value),
+        InlineItem::Tab => text.push('\t'),
+        InlineItem::Break(_) => text.push('\n'),
+        InlineItem::NoteReference(kind, id) => match notes.reference(kind, id) {
+            Some(NoteAnchor::Marker(number)) => text.push_str(&format!("[{}]", number)),
+            Some(NoteAnchor::Inline(note_text)) => text.push_str(&format!(" ({})", note_text)),
+            None => (),
+        },
+    });
+
+    text
+}
+
+fn table_lines(table: &Tbl, notes: &mut NoteCollector<'_>, lines: &mut Vec<String>) {
+    for row_content in &table.row_contents {
+        row_lines(row_content, notes, lines);
+    }
+}
+
+pub(crate) fn row_lines(content: &ContentRowContent, notes: &mut NoteCollector<'_>, lines: &mut Vec<String>) {
+    match content {
+        ContentRowContent::Table(row) => {
+            let is_inserted = matches!(&row.properties, Some(properties) if properties.inserted.is_some());
+            let is_deleted = matches!(&row.properties, Some(properties) if properties.deleted.is_some());
+            if (is_inserted && !notes.revisions.shows_inserted()) || (is_deleted && !notes.revisions.shows_deleted()) {
+                return;
+            }
+
+            let mut cells = Vec::new();
+            for cell_content in &row.contents {
+                cell_texts(cell_content, notes, &mut cells);
+            }
+
+            lines.push(cells.join("\t"));
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                row_lines(content, notes, lines);
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                row_lines(content, notes, lines);
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+pub(crate) fn cell_texts(content: &ContentCellContent, notes: &mut NoteCollector<'_>, cells: &mut Vec<String>) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            let mut lines = Vec::new();
+            for block_level_element in &cell.block_level_elements {
+                block_level_element_lines(block_level_element, notes, &mut lines);
+            }
+
+            cells.push(lines.join(" "));
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for content in &custom_xml.contents {
+                cell_texts(content, notes, cells);
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            for content in sdt.content.iter().flat_map(|content| content.contents.iter()) {
+                cell_texts(content, notes, cells);
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{
+        docx::wml::{
+            document::Document,
+            footnotes::{Endnotes, Footnotes},
+        },
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    pub(crate) fn package_with_notes() -> Package {
+        let document_xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:r><w:t>Some claim</w:t></w:r>
+                    <w:r><w:footnoteReference w:id="1" /></w:r>
+                    <w:r><w:t xml:space="preserve"> and more</w:t></w:r>
+                    <w:r><w:endnoteReference w:id="2" /></w:r>
+                    <w:del w:id="9" w:author="Jane Doe">
+                        <w:r><w:delText>removed</w:delText></w:r>
+                    </w:del>
+                </w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid />
+                    <w:tr>
+                        <w:tc><w:p><w:r><w:t>A &amp; B</w:t></w:r></w:p></w:tc>
+                        <w:tc><w:p><w:r><w:t>C</w:t></w:r></w:p></w:tc>
+                    </w:tr>
+                </w:tbl>
+            </w:body>
+        </w:document>"#;
+
+        let footnotes_xml = r#"<w:footnotes>
+            <w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator /></w:r></w:p></w:footnote>
+            <w:footnote w:id="1">
+                <w:p><w:r><w:footnoteRef /></w:r><w:r><w:t xml:space="preserve"> See the source.</w:t></w:r></w:p>
+            </w:footnote>
+        </w:footnotes>"#;
+
+        let endnotes_xml = r#"<w:endnotes>
+            <w:endnote w:id="2">
+                <w:p><w:r><w:endnoteRef /></w:r><w:r><w:t>First line.</w:t></w:r></w:p>
+                <w:p><w:r><w:t>Second line.</w:t></w:r></w:p>
+            </w:endnote>
+        </w:endnotes>"#;
+
+        Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            footnotes: Some(Footnotes::from_xml_element(&XmlNode::from_str(footnotes_xml).unwrap()).unwrap()),
+            endnotes: Some(Endnotes::from_xml_element(&XmlNode::from_str(endnotes_xml).unwrap()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    pub fn test_note_text() {
+        let package = package_with_notes();
+        assert_eq!(
+            package.note_text(NoteKind::Footnote, 1),
+            Some(String::from("See the source."))
+        );
+        assert_eq!(
+            package.note_text(NoteKind::Endnote, 2),
+            Some(String::from("First line. Second line."))
+        );
+        assert_eq!(package.note_text(NoteKind::Endnote, 1), None);
+    }
+
+    #[test]
+    pub fn test_extract_text() {
+        let package = package_with_notes();
+
+        let omit = package.extract_text(&Default::default());
+        assert_eq!(omit, "Some claim and more\nA & B\tC");
+
+        let appendix = package.extract_text(&TextOptions {
+            notes: NotePlacement::Appendix,
+            ..Default::default()
+        });
+        assert_eq!(
+            appendix,
+            "Some claim[1] and more[2]\nA & B\tC\n\n[1] See the source.\n[2] First line. Second line."
+        );
+
+        let inline = package.extract_text(&TextOptions {
+            notes: NotePlacement::Inline,
+            ..Default::default()
+        });
+        assert_eq!(
+            inline,
+            "Some claim (See the source.) and more (First line. Second line.)\nA & B\tC"
+        );
+    }
+
+    #[test]
+    pub fn test_extract_text_revision_views() {
+        let document_xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">The </w:t></w:r>
+                <w:del w:id="1" w:author="Jane"><w:r><w:delText>old</w:delText></w:r></w:del>
+                <w:ins w:id="2" w:author="Jane"><w:r><w:t>new</w:t></w:r></w:ins>
+                <w:r><w:t xml:space="preserve"> text</w:t></w:r>
+                <w:moveFrom w:id="3" w:author="Jane"><w:r><w:delText>, moved</w:delText></w:r></w:moveFrom>
+            </w:p>
+            <w:tbl>
+                <w:tblPr />
+                <w:tblGrid><w:gridCol w:w="1000" /></w:tblGrid>
+                <w:tr><w:trPr><w:ins w:id="4" w:author="Jane" /></w:trPr><w:tc><w:p><w:r><w:t>Added row</w:t></w:r></w:p></w:tc></w:tr>
+                <w:tr><w:trPr><w:del w:id="5" w:author="Jane" /></w:trPr><w:tc><w:p><w:r><w:t>Removed row</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let package = Package {
+            main_document: Some(Box::new(
+                Document::from_xml_element(&XmlNode::from_str(document_xml).unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+
+        let extract = |revisions| {
+            package.extract_text(&TextOptions {
+                revisions,
+                ..Default::default()
+            })
+        };
+        assert_eq!(extract(RevisionView::Accepted), "The new text\nAdded row");
+        assert_eq!(extract(RevisionView::Rejected), "The old text, moved\nRemoved row");
+        assert_eq!(
+            extract(RevisionView::Original),
+            "The oldnew text, moved\nAdded row\nRemoved row"
+        );
+    }
+}
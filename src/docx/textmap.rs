@@ -0,0 +1,894 @@
+use super::{
+    text::RevisionView,
+    wml::{
+        document::{
+            BlockLevelElts, Body, ContentBlockContent, ContentRunContent, Document, PContent, RunInnerContent,
+            RunLevelElts, RunTrackChangeChoice, P, R,
+        },
+        table::{ContentCellContent, ContentRowContent},
+    },
+};
+
+/// The location of a run in the main document.
+///
+/// Both paths are lists of child indexes. The paragraph path starts at the block level elements of the body and
+/// steps into custom XML elements, content controls, table rows and table cells, see [`Document::paragraph_at`]. The
+/// run path starts at the contents of the paragraph and steps into fields, hyperlinks, custom XML elements, smart tags,
+/// content controls, bidirectional embeddings and tracked changes, see [`P::run_at`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct RunPath {
+    pub paragraph: Vec<usize>,
+    pub run: Vec<usize>,
+}
+
+/// A position in the content of a run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct TextPosition {
+    pub path: RunPath,
+    /// The index of the run content (e.g. `w:t` or `w:tab`) in the run
+    pub content_index: usize,
+    /// The character index in the text of the run content
+    pub char_index: usize,
+}
+
+/// A piece of the extracted text coming from a single run content. Offsets are character offsets into the text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextSpan {
+    pub start: usize,
+    pub end: usize,
+    pub path: RunPath,
+    pub content_index: usize,
+}
+
+/// Extracted text of a document along with the location of every extracted character in the document model, which
+/// lets results of text searches or NLP models be projected back into the document, e.g. to highlight a match.
+///
+/// Characters inserted by the extraction (line separators between paragraphs, the tabs between table cells and the
+/// spaces between paragraphs of a cell) don't map to any run.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextMap {
+    pub text: String,
+    /// The spans of the mapped text in increasing order of offsets
+    pub spans: Vec<TextSpan>,
+}
+
+impl TextMap {
+    /// Returns the position of the character at the given character offset of the text, or `None` if the character
+    /// was inserted by the extraction.
+    pub fn position_at(&self, offset: usize) -> Option<TextPosition> {
+        let span = &self.spans[self.span_index_at(offset)?];
+        Some(TextPosition {
+            path: span.path.clone(),
+            content_index: span.content_index,
+            char_index: offset - span.start,
+        })
+    }
+
+    /// Returns the character offset of the position in the text, or `None` if the position wasn't extracted, e.g.
+    /// because it is in deleted text.
+    pub fn offset_of(&self, position: &TextPosition) -> Option<usize> {
+        self.spans
+            .iter()
+            .find(|span| {
+                span.path == position.path
+                    && span.content_index == position.content_index
+                    && position.char_index < span.end - span.start
+            })
+            .map(|span| span.start + position.char_index)
+    }
+
+    /// Returns the spans overlapping the range of character offsets `start..end`. A match in the text usually spans
+    /// several runs.
+    pub fn spans_between(&self, start: usize, end: usize) -> &[TextSpan] {
+        let first = self.spans.partition_point(|span| span.end <= start);
+        let last = self.spans.partition_point(|span| span.start < end);
+        &self.spans[first..last.max(first)]
+    }
+
+    fn span_index_at(&self, offset: usize) -> Option<usize> {
+        let index = self.spans.partition_point(|span| span.end <= offset);
+        match self.spans.get(index) {
+            Some(span) if span.start <= offset => Some(index),
+            _ => None,
+        }
+    }
+}
+
+impl Document {
+    /// Extracts the text of the document with the location of every character. The text is identical to the output
+    /// of [`Package::extract_text`](super::package::Package::extract_text) with the notes omitted and the same
+    /// revision view.
+    ///
+    /// ```
+    /// use oox::{docx::{text::RevisionView, wml::document::Document}, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r><w:t>Hello </w:t></w:r>
+    ///     <w:hyperlink r:id="rId1"><w:r><w:t>world</w:t></w:r></w:hyperlink>
+    /// </w:p></w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// let text_map = document.text_map(RevisionView::Accepted);
+    /// let offset = text_map.text.find("world").unwrap();
+    /// let position = text_map.position_at(offset).unwrap();
+    /// assert_eq!(position.path.run, vec![1, 0]);
+    /// assert!(document.run_at(&position.path).is_some());
+    /// ```
+    pub fn text_map(&self, view: RevisionView) -> TextMap {
+        let mut lines = Vec::new();
+        if let Some(body) = &self.body {
+            block_lines(&body.block_level_elements, &mut Vec::new(), view, &mut lines);
+        }
+
+        let line = MappedLine::join(lines, '\n');
+        TextMap {
+            text: line.text,
+            spans: line.spans,
+        }
+    }
+
+    /// Returns the paragraph at the path, see [`RunPath`].
+    pub fn paragraph_at(&self, path: &[usize]) -> Option<&P> {
+        paragraph_in_blocks(&self.body.as_ref()?.block_level_elements, path)
+    }
+
+    pub fn paragraph_at_mut(&mut self, path: &[usize]) -> Option<&mut P> {
+        paragraph_in_blocks_mut(&mut self.body.as_mut()?.block_level_elements, path)
+    }
+
+    pub fn run_at(&self, path: &RunPath) -> Option<&R> {
+        self.paragraph_at(&path.paragraph)?.run_at(&path.run)
+    }
+
+    pub fn run_at_mut(&mut self, path: &RunPath) -> Option<&mut R> {
+        self.paragraph_at_mut(&path.paragraph)?.run_at_mut(&path.run)
+    }
+
+    /// Returns every paragraph of the body with its path in document order, see [`Body::paragraphs`].
+    pub fn paragraphs(&self) -> impl Iterator<Item = (Vec<usize>, &P)> {
+        self.body.iter().flat_map(|body| body.paragraphs())
+    }
+
+    /// Returns every run of the body with its path in document order, see [`Body::runs`].
+    ///
+    /// ```
+    /// use oox::{docx::wml::document::Document, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<w:document><w:body><w:p>
+    ///     <w:r><w:t>Hello </w:t></w:r>
+    ///     <w:hyperlink r:id="rId1"><w:r><w:t>world</w:t></w:r></w:hyperlink>
+    /// </w:p></w:body></w:document>"#;
+    /// let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// let paths: Vec<_> = document.iter_runs().map(|(path, _)| path.run).collect();
+    /// assert_eq!(paths, vec![vec![0], vec![1, 0]]);
+    /// ```
+    pub fn iter_runs(&self) -> impl Iterator<Item = (RunPath, &R)> {
+        self.body.iter().flat_map(|body| body.runs())
+    }
+
+    /// Returns the path of every paragraph of the body in document order, see [`RunPath`].
+    pub(crate) fn paragraph_paths(&self) -> Vec<Vec<usize>> {
+        self.paragraphs().map(|(path, _)| path).collect()
+    }
+
+    /// Returns every run of the body with its path in document order, including the runs of tracked changes.
+    pub(crate) fn runs_with_paths(&self) -> Vec<(RunPath, &R)> {
+        self.iter_runs().collect()
+    }
+}
+
+impl Body {
+    /// Returns every paragraph of the body with its path in document order. Paragraphs of tables, content controls
+    /// and custom XML elements are included, see [`RunPath`] for the paths.
+    pub fn paragraphs(&self) -> impl Iterator<Item = (Vec<usize>, &P)> {
+        let mut paragraphs = Vec::new();
+        collect_block_paragraphs(&self.block_level_elements, &mut Vec::new(), &mut paragraphs);
+        paragraphs.into_iter()
+    }
+
+    /// Returns every run of the body with its path in document order. Runs of fields, hyperlinks, content controls
+    /// and tracked changes are included, so inserted and deleted runs are both returned.
+    pub fn runs(&self) -> impl Iterator<Item = (RunPath, &R)> {
+        block_runs_with_paths(&self.block_level_elements).into_iter()
+    }
+}
+
+/// Returns every run of the block level elements with its path relative to the elements in document order, see
+/// [`Document::runs_with_paths`].
+pub(crate) fn block_runs_with_paths(elements: &[BlockLevelElts]) -> Vec<(RunPath, &R)> {
+    let mut paragraphs = Vec::new();
+    collect_block_paragraphs(elements, &mut Vec::new(), &mut paragraphs);
+    paragraphs
+        .into_iter()
+        .flat_map(|(paragraph_path, paragraph)| {
+            paragraph.runs_with_paths().into_iter().map(move |(run, r)| {
+                let path = RunPath {
+                    paragraph: paragraph_path.clone(),
+                    run,
+                };
+                (path, r)
+            })
+        })
+        .collect()
+}
+
+impl P {
+    /// Returns the run at the path relative to the contents of the paragraph, see [`RunPath`].
+    pub fn run_at(&self, path: &[usize]) -> Option<&R> {
+        run_in_contents(&self.contents, path)
+    }
+
+    pub fn run_at_mut(&mut self, path: &[usize]) -> Option<&mut R> {
+        run_in_contents_mut(&mut self.contents, path)
+    }
+
+    /// Returns every run of the paragraph with its path relative to the contents of the paragraph in document order,
+    /// including the runs of tracked changes, see [`RunPath`].
+    pub fn runs(&self) -> impl Iterator<Item = (Vec<usize>, &R)> {
+        let mut runs = Vec::new();
+        collect_runs(&self.contents, &mut Vec::new(), &mut runs);
+        runs.into_iter()
+    }
+
+    pub(crate) fn runs_with_paths(&self) -> Vec<(Vec<usize>, &R)> {
+        self.runs().collect()
+    }
+}
+
+/// A line of extracted text with spans relative to the start of the line.
+#[derive(Default)]
+struct MappedLine {
+    text: String,
+    char_count: usize,
+    spans: Vec<TextSpan>,
+}
+
+impl MappedLine {
+    fn push(&mut self, text: &str, path: &RunPath, content_index: usize) {
+        let char_count = text.chars().count();
+        if char_count == 0 {
+            return;
+        }
+
+        self.spans.push(TextSpan {
+            start: self.char_count,
+            end: self.char_count + char_count,
+            path: path.clone(),
+            content_index,
+        });
+        self.text.push_str(text);
+        self.char_count += char_count;
+    }
+
+    fn join(lines: Vec<MappedLine>, separator: char) -> MappedLine {
+        let mut result = MappedLine::default();
+        for (index, line) in lines.into_iter().enumerate() {
+            if index > 0 {
+                result.text.push(separator);
+                result.char_count += 1;
+            }
+
+            let offset = result.char_count;
+            result.spans.extend(line.spans.into_iter().map(|span| TextSpan {
+                start: span.start + offset,
+                end: span.end + offset,
+                ..span
+            }));
+            result.text.push_str(&line.text);
+            result.char_count += line.char_count;
+        }
+
+        result
+    }
+}
+
+fn block_lines(elements: &[BlockLevelElts], path: &mut Vec<usize>, view: RevisionView, lines: &mut Vec<MappedLine>) {
+    for (index, element) in elements.iter().enumerate() {
+        if let BlockLevelElts::Chunk(content) = element {
+            path.push(index);
+            content_block_lines(content, path, view, lines);
+            path.pop();
+        }
+    }
+}
+
+fn content_block_lines(
+    content: &ContentBlockContent,
+    path: &mut Vec<usize>,
+    view: RevisionView,
+    lines: &mut Vec<MappedLine>,
+) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.block_contents.iter().enumerate() {
+                path.push(index);
+                content_block_lines(content, path, view, lines);
+                path.pop();
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            let contents = sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                content_block_lines(content, path, view, lines);
+                path.pop();
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => {
+            let mut line = MappedLine::default();
+            let mut run_path = RunPath {
+                paragraph: path.clone(),
+                run: Vec::new(),
+            };
+            paragraph_contents_line(&paragraph.contents, &mut run_path, view, &mut line);
+            lines.push(line);
+        }
+        ContentBlockContent::Table(table) => {
+            for (index, row_content) in table.row_contents.iter().enumerate() {
+                path.push(index);
+                row_lines(row_content, path, view, lines);
+                path.pop();
+            }
+        }
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn row_lines(content: &ContentRowContent, path: &mut Vec<usize>, view: RevisionView, lines: &mut Vec<MappedLine>) {
+    match content {
+        ContentRowContent::Table(row) => {
+            let is_inserted = matches!(&row.properties, Some(properties) if properties.inserted.is_some());
+            let is_deleted = matches!(&row.properties, Some(properties) if properties.deleted.is_some());
+            if (is_inserted && !view.shows_inserted()) || (is_deleted && !view.shows_deleted()) {
+                return;
+            }
+
+            let mut cells = Vec::new();
+            for (index, cell_content) in row.contents.iter().enumerate() {
+                path.push(index);
+                cell_lines(cell_content, path, view, &mut cells);
+                path.pop();
+            }
+
+            lines.push(MappedLine::join(cells, '\t'));
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.contents.iter().enumerate() {
+                path.push(index);
+                row_lines(content, path, view, lines);
+                path.pop();
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                row_lines(content, path, view, lines);
+                path.pop();
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+fn cell_lines(content: &ContentCellContent, path: &mut Vec<usize>, view: RevisionView, cells: &mut Vec<MappedLine>) {
+    match content {
+        ContentCellContent::Cell(cell) => {
+            let mut lines = Vec::new();
+            block_lines(&cell.block_level_elements, path, view, &mut lines);
+            cells.push(MappedLine::join(lines, ' '));
+        }
+        ContentCellContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.contents.iter().enumerate() {
+                path.push(index);
+                cell_lines(content, path, view, cells);
+                path.pop();
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                cell_lines(content, path, view, cells);
+                path.pop();
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+fn paragraph_contents_line(contents: &[PContent], path: &mut RunPath, view: RevisionView, line: &mut MappedLine) {
+    for (index, content) in contents.iter().enumerate() {
+        path.run.push(index);
+        match content {
+            PContent::ContentRunContent(content) => content_run_line(content, path, view, line),
+            PContent::SimpleField(field) => paragraph_contents_line(&field.paragraph_contents, path, view, line),
+            PContent::Hyperlink(hyperlink) => paragraph_contents_line(&hyperlink.paragraph_contents, path, view, line),
+            PContent::SubDocument(_) => (),
+        }
+        path.run.pop();
+    }
+}
+
+fn content_run_line(content: &ContentRunContent, path: &mut RunPath, view: RevisionView, line: &mut MappedLine) {
+    match content {
+        ContentRunContent::Run(run) => {
+            for (index, inner_content) in run.run_inner_contents.iter().enumerate() {
+                match inner_content {
+                    RunInnerContent::Text(text) => line.push(&text.text, path, index),
+                    RunInnerContent::DeletedText(text) if view.shows_deleted() => line.push(&text.text, path, index),
+                    RunInnerContent::NonBreakingHyphen => line.push("\u{2011}", path, index),
+                    RunInnerContent::Tab | RunInnerContent::PositionTab(_) => line.push("\t", path, index),
+                    RunInnerContent::Break(_) | RunInnerContent::CarriageReturn => line.push("\n", path, index),
+                    _ => (),
+                }
+            }
+        }
+        ContentRunContent::CustomXml(custom_xml) => {
+            paragraph_contents_line(&custom_xml.paragraph_contents, path, view, line)
+        }
+        ContentRunContent::SmartTag(smart_tag) => {
+            paragraph_contents_line(&smart_tag.paragraph_contents, path, view, line)
+        }
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                paragraph_contents_line(&sdt_content.p_contents, path, view, line);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => paragraph_contents_line(&dir.p_contents, path, view, line),
+        ContentRunContent::BidirectionalOverride(bdo) => paragraph_contents_line(&bdo.p_contents, path, view, line),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change))
+            if view.shows_inserted() =>
+        {
+            track_change_line(&track_change.choices, path, view, line)
+        }
+        ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+            if view.shows_deleted() =>
+        {
+            track_change_line(&track_change.choices, path, view, line)
+        }
+        ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+fn track_change_line(choices: &[RunTrackChangeChoice], path: &mut RunPath, view: RevisionView, line: &mut MappedLine) {
+    for (index, choice) in choices.iter().enumerate() {
+        path.run.push(index);
+        match choice {
+            RunTrackChangeChoice::ContentRunContent(content) => content_run_line(content, path, view, line),
+        }
+        path.run.pop();
+    }
+}
+
+fn paragraph_in_blocks<'a>(elements: &'a [BlockLevelElts], path: &[usize]) -> Option<&'a P> {
+    let (index, rest) = path.split_first()?;
+    match elements.get(*index)? {
+        BlockLevelElts::Chunk(content) => paragraph_in_content_block(content, rest),
+        BlockLevelElts::AltChunk(_) => None,
+    }
+}
+
+fn paragraph_in_content_block<'a>(content: &'a ContentBlockContent, path: &[usize]) -> Option<&'a P> {
+    match content {
+        ContentBlockContent::Paragraph(paragraph) if path.is_empty() => Some(paragraph),
+        ContentBlockContent::CustomXml(custom_xml) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_content_block(custom_xml.block_contents.get(*index)?, rest)
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_content_block(sdt.sdt_content.as_ref()?.block_contents.get(*index)?, rest)
+        }
+        ContentBlockContent::Table(table) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_row(table.row_contents.get(*index)?, rest)
+        }
+        _ => None,
+    }
+}
+
+fn paragraph_in_row<'a>(content: &'a ContentRowContent, path: &[usize]) -> Option<&'a P> {
+    let (index, rest) = path.split_first()?;
+    match content {
+        ContentRowContent::Table(row) => paragraph_in_cell(row.contents.get(*index)?, rest),
+        ContentRowContent::CustomXml(custom_xml) => paragraph_in_row(custom_xml.contents.get(*index)?, rest),
+        ContentRowContent::Sdt(sdt) => paragraph_in_row(sdt.content.as_ref()?.contents.get(*index)?, rest),
+        ContentRowContent::RunLevelElements(_) => None,
+    }
+}
+
+fn paragraph_in_cell<'a>(content: &'a ContentCellContent, path: &[usize]) -> Option<&'a P> {
+    match content {
+        ContentCellContent::Cell(cell) => paragraph_in_blocks(&cell.block_level_elements, path),
+        ContentCellContent::CustomXml(custom_xml) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_cell(custom_xml.contents.get(*index)?, rest)
+        }
+        ContentCellContent::Sdt(sdt) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_cell(sdt.content.as_ref()?.contents.get(*index)?, rest)
+        }
+        ContentCellContent::RunLevelElement(_) => None,
+    }
+}
+
+fn paragraph_in_blocks_mut<'a>(elements: &'a mut [BlockLevelElts], path: &[usize]) -> Option<&'a mut P> {
+    let (index, rest) = path.split_first()?;
+    match elements.get_mut(*index)? {
+        BlockLevelElts::Chunk(content) => paragraph_in_content_block_mut(content, rest),
+        BlockLevelElts::AltChunk(_) => None,
+    }
+}
+
+fn paragraph_in_content_block_mut<'a>(content: &'a mut ContentBlockContent, path: &[usize]) -> Option<&'a mut P> {
+    match content {
+        ContentBlockContent::Paragraph(paragraph) if path.is_empty() => Some(paragraph),
+        ContentBlockContent::CustomXml(custom_xml) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_content_block_mut(custom_xml.block_contents.get_mut(*index)?, rest)
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_content_block_mut(sdt.sdt_content.as_mut()?.block_contents.get_mut(*index)?, rest)
+        }
+        ContentBlockContent::Table(table) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_row_mut(table.row_contents.get_mut(*index)?, rest)
+        }
+        _ => None,
+    }
+}
+
+fn paragraph_in_row_mut<'a>(content: &'a mut ContentRowContent, path: &[usize]) -> Option<&'a mut P> {
+    let (index, rest) = path.split_first()?;
+    match content {
+        ContentRowContent::Table(row) => paragraph_in_cell_mut(row.contents.get_mut(*index)?, rest),
+        ContentRowContent::CustomXml(custom_xml) => paragraph_in_row_mut(custom_xml.contents.get_mut(*index)?, rest),
+        ContentRowContent::Sdt(sdt) => paragraph_in_row_mut(sdt.content.as_mut()?.contents.get_mut(*index)?, rest),
+        ContentRowContent::RunLevelElements(_) => None,
+    }
+}
+
+fn paragraph_in_cell_mut<'a>(content: &'a mut ContentCellContent, path: &[usize]) -> Option<&'a mut P> {
+    match content {
+        ContentCellContent::Cell(cell) => paragraph_in_blocks_mut(&mut cell.block_level_elements, path),
+        ContentCellContent::CustomXml(custom_xml) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_cell_mut(custom_xml.contents.get_mut(*index)?, rest)
+        }
+        ContentCellContent::Sdt(sdt) => {
+            let (index, rest) = path.split_first()?;
+            paragraph_in_cell_mut(sdt.content.as_mut()?.contents.get_mut(*index)?, rest)
+        }
+        ContentCellContent::RunLevelElement(_) => None,
+    }
+}
+
+pub(crate) fn collect_block_paragraphs<'a>(
+    elements: &'a [BlockLevelElts],
+    path: &mut Vec<usize>,
+    paragraphs: &mut Vec<(Vec<usize>, &'a P)>,
+) {
+    for (index, element) in elements.iter().enumerate() {
+        if let BlockLevelElts::Chunk(content) = element {
+            path.push(index);
+            collect_content_block_paragraphs(content, path, paragraphs);
+            path.pop();
+        }
+    }
+}
+
+fn collect_content_block_paragraphs<'a>(
+    content: &'a ContentBlockContent,
+    path: &mut Vec<usize>,
+    paragraphs: &mut Vec<(Vec<usize>, &'a P)>,
+) {
+    match content {
+        ContentBlockContent::Paragraph(paragraph) => paragraphs.push((path.clone(), paragraph)),
+        ContentBlockContent::Table(table) => {
+            for (index, row_content) in table.row_contents.iter().enumerate() {
+                path.push(index);
+                collect_row_paragraphs(row_content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentBlockContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.block_contents.iter().enumerate() {
+                path.push(index);
+                collect_content_block_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentBlockContent::Sdt(sdt) => {
+            let contents = sdt.sdt_content.iter().flat_map(|content| content.block_contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                collect_content_block_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentBlockContent::RunLevelElement(_) => (),
+    }
+}
+
+fn collect_row_paragraphs<'a>(
+    content: &'a ContentRowContent,
+    path: &mut Vec<usize>,
+    paragraphs: &mut Vec<(Vec<usize>, &'a P)>,
+) {
+    match content {
+        ContentRowContent::Table(row) => {
+            for (index, cell_content) in row.contents.iter().enumerate() {
+                path.push(index);
+                collect_cell_paragraphs(cell_content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentRowContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.contents.iter().enumerate() {
+                path.push(index);
+                collect_row_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentRowContent::Sdt(sdt) => {
+            let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                collect_row_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentRowContent::RunLevelElements(_) => (),
+    }
+}
+
+pub(crate) fn collect_cell_paragraphs<'a>(
+    content: &'a ContentCellContent,
+    path: &mut Vec<usize>,
+    paragraphs: &mut Vec<(Vec<usize>, &'a P)>,
+) {
+    match content {
+        ContentCellContent::Cell(cell) => collect_block_paragraphs(&cell.block_level_elements, path, paragraphs),
+        ContentCellContent::CustomXml(custom_xml) => {
+            for (index, content) in custom_xml.contents.iter().enumerate() {
+                path.push(index);
+                collect_cell_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentCellContent::Sdt(sdt) => {
+            let contents = sdt.content.iter().flat_map(|content| content.contents.iter());
+            for (index, content) in contents.enumerate() {
+                path.push(index);
+                collect_cell_paragraphs(content, path, paragraphs);
+                path.pop();
+            }
+        }
+        ContentCellContent::RunLevelElement(_) => (),
+    }
+}
+
+fn collect_runs<'a>(contents: &'a [PContent], path: &mut Vec<usize>, runs: &mut Vec<(Vec<usize>, &'a R)>) {
+    for (index, content) in contents.iter().enumerate() {
+        path.push(index);
+        match content {
+            PContent::ContentRunContent(content) => collect_content_runs(content, path, runs),
+            PContent::SimpleField(field) => collect_runs(&field.paragraph_contents, path, runs),
+            PContent::Hyperlink(hyperlink) => collect_runs(&hyperlink.paragraph_contents, path, runs),
+            PContent::SubDocument(_) => (),
+        }
+        path.pop();
+    }
+}
+
+fn collect_content_runs<'a>(
+    content: &'a ContentRunContent,
+    path: &mut Vec<usize>,
+    runs: &mut Vec<(Vec<usize>, &'a R)>,
+) {
+    match content {
+        ContentRunContent::Run(run) => runs.push((path.clone(), run)),
+        ContentRunContent::CustomXml(custom_xml) => collect_runs(&custom_xml.paragraph_contents, path, runs),
+        ContentRunContent::SmartTag(smart_tag) => collect_runs(&smart_tag.paragraph_contents, path, runs),
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                collect_runs(&sdt_content.p_contents, path, runs);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => collect_runs(&dir.p_contents, path, runs),
+        ContentRunContent::BidirectionalOverride(bdo) => collect_runs(&bdo.p_contents, path, runs),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            for (index, choice) in track_change.choices.iter().enumerate() {
+                path.push(index);
+                match choice {
+                    RunTrackChangeChoice::ContentRunContent(content) => collect_content_runs(content, path, runs),
+                }
+                path.pop();
+            }
+        }
+        ContentRunContent::RunLevelElements(_) => (),
+    }
+}
+
+fn run_in_contents<'a>(contents: &'a [PContent], path: &[usize]) -> Option<&'a R> {
+    let (index, rest) = path.split_first()?;
+    match contents.get(*index)? {
+        PContent::ContentRunContent(content) => run_in_content_run(content, rest),
+        PContent::SimpleField(field) => run_in_contents(&field.paragraph_contents, rest),
+        PContent::Hyperlink(hyperlink) => run_in_contents(&hyperlink.paragraph_contents, rest),
+        PContent::SubDocument(_) => None,
+    }
+}
+
+fn run_in_content_run<'a>(content: &'a ContentRunContent, path: &[usize]) -> Option<&'a R> {
+    match content {
+        ContentRunContent::Run(run) if path.is_empty() => Some(run),
+        ContentRunContent::CustomXml(custom_xml) => run_in_contents(&custom_xml.paragraph_contents, path),
+        ContentRunContent::SmartTag(smart_tag) => run_in_contents(&smart_tag.paragraph_contents, path),
+        ContentRunContent::Sdt(sdt) => run_in_contents(&sdt.sdt_content.as_ref()?.p_contents, path),
+        ContentRunContent::Bidirectional(dir) => run_in_contents(&dir.p_contents, path),
+        ContentRunContent::BidirectionalOverride(bdo) => run_in_contents(&bdo.p_contents, path),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            let (index, rest) = path.split_first()?;
+            match track_change.choices.get(*index)? {
+                RunTrackChangeChoice::ContentRunContent(content) => run_in_content_run(content, rest),
+            }
+        }
+        _ => None,
+    }
+}
+
+fn run_in_contents_mut<'a>(contents: &'a mut [PContent], path: &[usize]) -> Option<&'a mut R> {
+    let (index, rest) = path.split_first()?;
+    match contents.get_mut(*index)? {
+        PContent::ContentRunContent(content) => run_in_content_run_mut(content, rest),
+        PContent::SimpleField(field) => run_in_contents_mut(&mut field.paragraph_contents, rest),
+        PContent::Hyperlink(hyperlink) => run_in_contents_mut(&mut hyperlink.paragraph_contents, rest),
+        PContent::SubDocument(_) => None,
+    }
+}
+
+fn run_in_content_run_mut<'a>(content: &'a mut ContentRunContent, path: &[usize]) -> Option<&'a mut R> {
+    match content {
+        ContentRunContent::Run(run) if path.is_empty() => Some(run),
+        ContentRunContent::CustomXml(custom_xml) => run_in_contents_mut(&mut custom_xml.paragraph_contents, path),
+        ContentRunContent::SmartTag(smart_tag) => run_in_contents_mut(&mut smart_tag.paragraph_contents, path),
+        ContentRunContent::Sdt(sdt) => run_in_contents_mut(&mut sdt.sdt_content.as_mut()?.p_contents, path),
+        ContentRunContent::Bidirectional(dir) => run_in_contents_mut(&mut dir.p_contents, path),
+        ContentRunContent::BidirectionalOverride(bdo) => run_in_contents_mut(&mut bdo.p_contents, path),
+        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::Delete(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveFrom(track_change))
+        | ContentRunContent::RunLevelElements(RunLevelElts::MoveTo(track_change)) => {
+            let (index, rest) = path.split_first()?;
+            match track_change.choices.get_mut(*index)? {
+                RunTrackChangeChoice::ContentRunContent(content) => run_in_content_run_mut(content, rest),
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{package::Package, text::TextOptions},
+        xml::XmlNode,
+    };
+    use std::str::FromStr;
+
+    fn test_document() -> Document {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Grüße </w:t></w:r>
+                <w:ins w:id="1" w:author="Jane"><w:r><w:t>an</w:t><w:tab /></w:r></w:ins>
+                <w:del w:id="2" w:author="Jane"><w:r><w:delText>alle</w:delText></w:r></w:del>
+            </w:p>
+            <w:tbl>
+                <w:tblPr />
+                <w:tblGrid><w:gridCol w:w="1000" /><w:gridCol w:w="1000" /></w:tblGrid>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc>
+                    <w:sdt><w:sdtContent><w:tc><w:p><w:r><w:t>C</w:t></w:r></w:p></w:tc></w:sdtContent></w:sdt>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_text_map() {
+        let document = test_document();
+        let package = Package {
+            main_document: Some(Box::new(document.clone())),
+            ..Default::default()
+        };
+
+        let text_map = document.text_map(RevisionView::Accepted);
+        assert_eq!(text_map.text, package.extract_text(&TextOptions::default()));
+        assert_eq!(text_map.text, "Grüße an\t\nA B\tC");
+
+        let position = text_map.position_at(7).unwrap();
+        assert_eq!(position.path.paragraph, vec![0]);
+        assert_eq!(position.path.run, vec![1, 0]);
+        assert_eq!((position.content_index, position.char_index), (0, 1));
+        assert_eq!(text_map.offset_of(&position), Some(7));
+
+        // The line separator and the separators of the table aren't mapped
+        assert_eq!(text_map.position_at(9), None);
+        assert_eq!(text_map.position_at(11), None);
+
+        let spans = text_map.spans_between(3, 8);
+        assert_eq!(spans.len(), 2);
+        assert_eq!((spans[0].start, spans[1].end), (0, 8));
+
+        let position = text_map.position_at(14).unwrap();
+        assert_eq!(position.path.paragraph, vec![1, 0, 1, 0, 0]);
+        let run = document.run_at(&position.path).unwrap();
+        assert!(matches!(&run.run_inner_contents[0], RunInnerContent::Text(text) if text.text == "C"));
+
+        let rejected = document.text_map(RevisionView::Rejected);
+        assert_eq!(rejected.text, "Grüße alle\nA B\tC");
+        let position = rejected.position_at(6).unwrap();
+        assert_eq!(position.path.run, vec![2, 0]);
+        assert!(document.run_at(&position.path).is_some());
+    }
+
+    #[test]
+    pub fn test_paragraphs_and_runs() {
+        let document = test_document();
+
+        let paragraph_paths: Vec<_> = document.paragraphs().map(|(path, _)| path).collect();
+        assert_eq!(
+            paragraph_paths,
+            vec![vec![0], vec![1, 0, 0, 0], vec![1, 0, 0, 1], vec![1, 0, 1, 0, 0]]
+        );
+        for (path, paragraph) in document.paragraphs() {
+            assert_eq!(document.paragraph_at(&path), Some(paragraph));
+        }
+
+        let run_paths: Vec<_> = document
+            .paragraphs()
+            .next()
+            .unwrap()
+            .1
+            .runs()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(run_paths, vec![vec![0], vec![1, 0], vec![2, 0]]);
+
+        let runs: Vec<_> = document.iter_runs().collect();
+        assert_eq!(runs.len(), 6);
+        for (path, run) in runs {
+            assert_eq!(document.run_at(&path), Some(run));
+        }
+    }
+
+    #[test]
+    pub fn test_run_at_mut() {
+        let mut document = test_document();
+        let path = RunPath {
+            paragraph: vec![1, 0, 0, 0],
+            run: vec![0],
+        };
+
+        document.run_at_mut(&path).unwrap().run_inner_contents.clear();
+        assert_eq!(document.text_map(RevisionView::Accepted).text, "Grüße an\t\n B\tC");
+        assert!(document.paragraph_at(&[1, 0, 0, 2]).is_none());
+        assert!(document.paragraph_at(&[0, 0]).is_none());
+    }
+}
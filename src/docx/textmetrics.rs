@@ -0,0 +1,248 @@
+//! Font substitution and text measurement for layout consumers.
+//!
+//! The crate doesn't read font files. Glyph metrics are provided by a [`GlyphMetrics`] implementation, which can be
+//! backed by a real font stack. [`BuiltinMetrics`] contains approximate metrics of three common fonts and is used to
+//! measure text when nothing better is available.
+//!
+//! ```
+//! use oox::docx::{resolvedstyle::RunProperties, textmetrics::{BuiltinMetrics, TextMeasurer}};
+//! use oox::docx::wml::document::{Fonts, HpsMeasure};
+//!
+//! let properties = RunProperties {
+//!     fonts: Some(Fonts { ascii: Some(String::from("Arial")), ..Default::default() }),
+//!     font_size: Some(HpsMeasure::Decimal(24)),
+//!     ..Default::default()
+//! };
+//! let extent = TextMeasurer::new(&BuiltinMetrics).measure_text("Hello", &properties);
+//! assert!((extent.width.to_points() - 27.336).abs() < 1e-9);
+//! ```
+
+use super::{
+    package::Package,
+    resolvedstyle::RunProperties,
+    wml::{
+        document::{Fonts, RunInnerContent, P, R},
+        fonttable::{FontFamily, FontTable, Pitch},
+    },
+};
+pub use crate::shared::fontmetrics::{BuiltinMetrics, FontFace, GlyphMetrics, LineMetrics};
+use crate::shared::{
+    fontmetrics::{is_east_asian, ARIAL, COURIER_NEW},
+    sharedtypes::{Length, Measure},
+};
+
+/// The font size used when the run properties don't specify one, in points.
+pub const DEFAULT_FONT_SIZE: f64 = 10.0;
+/// The font used when the run properties don't specify one.
+pub const DEFAULT_FONT: &str = "Times New Roman";
+
+/// The measured extent of a text.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TextExtent {
+    pub width: Length,
+    pub ascent: Length,
+    pub descent: Length,
+    /// The single spaced line height of the tallest font used by the text.
+    pub line_height: Length,
+}
+
+/// Measures texts formatted with resolved run properties using a glyph metrics provider.
+pub struct TextMeasurer<'a, M: GlyphMetrics + ?Sized> {
+    metrics: &'a M,
+    font_table: Option<&'a FontTable>,
+}
+
+impl<'a, M: GlyphMetrics + ?Sized> TextMeasurer<'a, M> {
+    pub fn new(metrics: &'a M) -> Self {
+        Self {
+            metrics,
+            font_table: None,
+        }
+    }
+
+    /// Creates a measurer substituting unknown fonts using the family and pitch of the font table entries.
+    pub fn with_font_table(metrics: &'a M, font_table: Option<&'a FontTable>) -> Self {
+        Self { metrics, font_table }
+    }
+
+    /// Returns the name of the font whose metrics are used for the font. Unknown fonts are substituted by one of their
+    /// alternate names from the font table, or by a common font of the same family and pitch. Falls back to the
+    /// [`DEFAULT_FONT`].
+    pub fn substitute_font<'b>(&self, name: &'b str) -> &'b str
+    where
+        'a: 'b,
+    {
+        if self.metrics.has_font(name) {
+            return name;
+        }
+
+        let font = match self.font_table.and_then(|font_table| font_table.font(name)) {
+            Some(font) => font,
+            None => return DEFAULT_FONT,
+        };
+
+        if let Some(alternate_name) = font
+            .alternate_name
+            .iter()
+            .flat_map(|names| names.split(','))
+            .map(str::trim)
+            .find(|alternate_name| self.metrics.has_font(alternate_name))
+        {
+            return alternate_name;
+        }
+
+        match (font.family, font.pitch) {
+            (_, Some(Pitch::Fixed)) | (Some(FontFamily::Modern), _) => COURIER_NEW,
+            (Some(FontFamily::Swiss), _) => ARIAL,
+            _ => DEFAULT_FONT,
+        }
+    }
+
+    /// Measures a text on a single line. The font of each character is picked from the run fonts by its script, theme
+    /// fonts aren't resolved. Character spacing and scaling are applied, capitalization isn't.
+    pub fn measure_text(&self, text: &str, properties: &RunProperties) -> TextExtent {
+        let is_complex_script = properties.complex_script.unwrap_or(false) || properties.rtl.unwrap_or(false);
+        let font_size = match is_complex_script {
+            true => properties
+                .complex_script_font_size
+                .as_ref()
+                .or(properties.font_size.as_ref()),
+            false => properties.font_size.as_ref(),
+        }
+        .map(Measure::to_points)
+        .unwrap_or(DEFAULT_FONT_SIZE);
+        let face = |name| FontFace {
+            name,
+            bold: match is_complex_script {
+                true => properties.complex_script_bold,
+                false => properties.bold,
+            }
+            .unwrap_or(false),
+            italic: match is_complex_script {
+                true => properties.complex_script_italic,
+                false => properties.italic,
+            }
+            .unwrap_or(false),
+        };
+        let scale = properties.width.unwrap_or(100.0) / 100.0;
+        let spacing = properties.spacing.as_ref().map(Measure::to_points).unwrap_or(0.0);
+
+        let mut width = 0.0;
+        let mut line_metrics: Option<LineMetrics> = None;
+        for c in text.chars() {
+            let name = self.substitute_font(script_font(properties.fonts.as_ref(), c, is_complex_script));
+            let face = face(name);
+            let advance_width = self
+                .metrics
+                .advance_width(face, c)
+                .or_else(|| {
+                    self.metrics.advance_width(
+                        FontFace {
+                            name: DEFAULT_FONT,
+                            ..face
+                        },
+                        c,
+                    )
+                })
+                .unwrap_or(0.5);
+            width += advance_width * font_size * scale + spacing;
+
+            let metrics = self.metrics.line_metrics(face);
+            if line_metrics.is_none_or(|line_metrics| metrics.line_height() > line_metrics.line_height()) {
+                line_metrics = Some(metrics);
+            }
+        }
+
+        let line_metrics = line_metrics.unwrap_or_else(|| {
+            let name = self.substitute_font(script_font(properties.fonts.as_ref(), 'a', is_complex_script));
+            self.metrics.line_metrics(face(name))
+        });
+
+        TextExtent {
+            width: Length::from_points(width),
+            ascent: Length::from_points(line_metrics.ascent * font_size),
+            descent: Length::from_points(line_metrics.descent * font_size),
+            line_height: Length::from_points(line_metrics.line_height() * font_size),
+        }
+    }
+}
+
+/// Returns the run font used for the character.
+fn script_font(fonts: Option<&Fonts>, c: char, is_complex_script: bool) -> &str {
+    let fonts = match fonts {
+        Some(fonts) => fonts,
+        None => return DEFAULT_FONT,
+    };
+
+    let font = if is_complex_script {
+        fonts.complex_script.as_ref()
+    } else if is_east_asian(c) {
+        fonts.east_asia.as_ref()
+    } else if c.is_ascii() {
+        fonts.ascii.as_ref()
+    } else {
+        fonts.high_ansi.as_ref()
+    };
+
+    font.or(fonts.ascii.as_ref())
+        .map(String::as_str)
+        .unwrap_or(DEFAULT_FONT)
+}
+
+impl Package {
+    /// Measures the text of a run with its effective run properties, substituting fonts with the font table.
+    pub fn measure_run<M: GlyphMetrics + ?Sized>(&self, metrics: &M, paragraph: &P, run: &R) -> TextExtent {
+        let text: String = run
+            .run_inner_contents
+            .iter()
+            .filter_map(|content| match content {
+                RunInnerContent::Text(text) => Some(text.text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        TextMeasurer::with_font_table(metrics, self.font_table.as_ref())
+            .measure_text(&text, &self.resolve_run_properties(paragraph, run))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::wml::{document::HpsMeasure, fonttable::Font};
+
+    #[test]
+    pub fn test_measure_text() {
+        let mut font = Font::new("Helvetica Neue");
+        font.family = Some(FontFamily::Swiss);
+        let mut font_table = FontTable(vec![font, Font::new("Consolas")]);
+        font_table.0[1].pitch = Some(Pitch::Fixed);
+        let measurer = TextMeasurer::with_font_table(&BuiltinMetrics, Some(&font_table));
+        assert_eq!(measurer.substitute_font("Helvetica Neue"), "Arial");
+        assert_eq!(measurer.substitute_font("Consolas"), "Courier New");
+        assert_eq!(measurer.substitute_font("Unknown"), DEFAULT_FONT);
+
+        let properties = RunProperties {
+            fonts: Some(Fonts {
+                ascii: Some(String::from("Consolas")),
+                east_asia: Some(String::from("MS Mincho")),
+                ..Default::default()
+            }),
+            font_size: Some(HpsMeasure::Decimal(20)),
+            ..Default::default()
+        };
+        let extent = measurer.measure_text("ab\u{65E5}", &properties);
+        assert!((extent.width.to_points() - 22.0).abs() < 1e-9);
+        assert!((extent.line_height.to_points() - 11.49).abs() < 1e-9);
+
+        let scaled = RunProperties {
+            width: Some(200.0),
+            ..properties
+        };
+        assert!((measurer.measure_text("ab", &scaled).width.to_points() - 24.0).abs() < 1e-9);
+        assert_eq!(
+            measurer.measure_text("", &RunProperties::default()).width,
+            Length::default()
+        );
+    }
+}
@@ -0,0 +1,740 @@
+use super::{
+    package::Package,
+    wml::document::{HpsMeasure, SignedTwipsMeasure},
+};
+use crate::{
+    error::{ConformanceClassError, ConformanceError, MaxOccurs},
+    shared::{
+        sharedtypes::{ConformanceClass, TwipsMeasure},
+        validation::{
+            child_paths, AttributeRule, ChildRule, ConformanceReport, ElementRule, Repair, RepairAction, Validator,
+            ValueRule,
+        },
+    },
+    xml::{zip_file_to_xml_node, XmlNode},
+};
+use log::warn;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    fs::File,
+    mem,
+    path::Path,
+};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+/// Options controlling how a package is opened.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ParseOptions {
+    /// Validate the WordprocessingML parts before parsing and fail with a [`ConformanceError`] if any of them violates
+    /// the schema.
+    pub strict: bool,
+    /// Require the main document to be of the given conformance class and fail with a [`ConformanceClassError`]
+    /// otherwise. Documents without a `w:conformance` attribute using the transitional namespaces are transitional.
+    pub conformance_class: Option<ConformanceClass>,
+    /// Recover the parts that fail to parse instead of failing, see [`Package::from_file_lenient`]. The diagnostics
+    /// are logged as warnings. In strict mode the package is still validated first.
+    pub lenient: bool,
+}
+
+/// A problem found and worked around while parsing a package leniently.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseDiagnostic {
+    /// A schema violation that was repaired by dropping content.
+    Repaired { part_name: String, repair: Repair },
+    /// An element that still failed to parse after the repairs, so it was left out.
+    SkippedElement {
+        part_name: String,
+        /// The path of the element in the format of [`Violation::path`](crate::shared::validation::Violation::path).
+        path: String,
+        error: String,
+    },
+}
+
+impl Display for ParseDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseDiagnostic::Repaired { part_name, repair } => {
+                write!(f, "{}:{}, ", part_name, repair.violation)?;
+                match repair.action {
+                    RepairAction::RemovedAttribute(name) => write!(f, "removed attribute {}", name),
+                    RepairAction::RemovedElement => write!(f, "removed element"),
+                    RepairAction::RemovedExcessChildren(count) => write!(f, "removed {} elements", count),
+                }
+            }
+            ParseDiagnostic::SkippedElement { part_name, path, error } => {
+                write!(f, "{}:{}: skipped element: {}", part_name, path, error)
+            }
+        }
+    }
+}
+
+const TWIPS: ValueRule = ValueRule::Custom(|value| value.parse::<TwipsMeasure>().is_ok(), "a twips measure");
+const SIGNED_TWIPS: ValueRule = ValueRule::Custom(
+    |value| value.parse::<SignedTwipsMeasure>().is_ok(),
+    "a signed twips measure",
+);
+const HALF_POINTS: ValueRule = ValueRule::Custom(
+    |value| match value.parse::<HpsMeasure>() {
+        Ok(HpsMeasure::Decimal(value)) => (1..=3276).contains(&value),
+        Ok(HpsMeasure::UniversalMeasure(_)) => true,
+        Err(_) => false,
+    },
+    "a half-point measure in 1..=3276",
+);
+const NON_EMPTY: ValueRule = ValueRule::Custom(|value| !value.is_empty(), "a non-empty string");
+const HEX_COLOR: ValueRule = ValueRule::Custom(
+    |value| value == "auto" || (value.len() == 6 && value.chars().all(|c| c.is_ascii_hexdigit())),
+    "auto or a hex RGB color",
+);
+const DECIMAL_NUMBER: ValueRule = ValueRule::Integer {
+    min: i32::MIN as i64,
+    max: i32::MAX as i64,
+};
+
+const fn optional(name: &'static str, value: ValueRule) -> AttributeRule {
+    AttributeRule {
+        name,
+        required: false,
+        value,
+    }
+}
+
+const fn required(name: &'static str, value: ValueRule) -> AttributeRule {
+    AttributeRule {
+        name,
+        required: true,
+        value,
+    }
+}
+
+const fn at_most_one(name: &'static [&'static str]) -> ChildRule {
+    ChildRule {
+        names: name,
+        min_occurs: 0,
+        max_occurs: MaxOccurs::Value(1),
+    }
+}
+
+const fn exactly_one(name: &'static [&'static str]) -> ChildRule {
+    ChildRule {
+        names: name,
+        min_occurs: 1,
+        max_occurs: MaxOccurs::Value(1),
+    }
+}
+
+const ON_OFF_ATTRIBUTES: &[AttributeRule] = &[optional("w:val", ValueRule::OnOff)];
+
+const fn on_off(name: &'static str) -> ElementRule {
+    ElementRule {
+        name,
+        children: &[],
+        attributes: ON_OFF_ATTRIBUTES,
+    }
+}
+
+/// An element with a single required `w:val` attribute.
+macro_rules! val_element {
+    ($name:expr, $value:expr $(,)?) => {
+        ElementRule {
+            name: $name,
+            children: &[],
+            attributes: &[required("w:val", $value)],
+        }
+    };
+}
+
+const HDR_FTR_REFERENCE_ATTRIBUTES: &[AttributeRule] = &[
+    required("w:type", ValueRule::Enumeration(&["default", "first", "even"])),
+    required("r:id", NON_EMPTY),
+];
+
+const TABLE_WIDTH_ATTRIBUTES: &[AttributeRule] = &[
+    optional(
+        "w:w",
+        ValueRule::Custom(|value| !value.is_empty(), "a measurement or percent"),
+    ),
+    optional("w:type", ValueRule::Enumeration(&["nil", "pct", "dxa", "auto"])),
+];
+
+/// The occurrence and attribute value constraints of the WordprocessingML schema checked by [`validate_wml`].
+pub const WML_RULES: &[ElementRule] = &[
+    ElementRule {
+        name: "w:document",
+        children: &[at_most_one(&["w:background"]), at_most_one(&["w:body"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:body",
+        children: &[at_most_one(&["w:sectPr"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:p",
+        children: &[at_most_one(&["w:pPr"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:pPr",
+        children: &[
+            at_most_one(&["w:pStyle"]),
+            at_most_one(&["w:numPr"]),
+            at_most_one(&["w:ind"]),
+            at_most_one(&["w:jc"]),
+            at_most_one(&["w:rPr"]),
+            at_most_one(&["w:sectPr"]),
+        ],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:r",
+        children: &[at_most_one(&["w:rPr"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:rPr",
+        children: &[
+            at_most_one(&["w:rStyle"]),
+            at_most_one(&["w:b"]),
+            at_most_one(&["w:i"]),
+            at_most_one(&["w:color"]),
+            at_most_one(&["w:sz"]),
+            at_most_one(&["w:szCs"]),
+        ],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:tbl",
+        children: &[exactly_one(&["w:tblPr"]), exactly_one(&["w:tblGrid"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:tr",
+        children: &[at_most_one(&["w:tblPrEx"]), at_most_one(&["w:trPr"])],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:tc",
+        children: &[
+            at_most_one(&["w:tcPr"]),
+            ChildRule {
+                names: &["w:p", "w:tbl", "w:sdt", "w:customXml", "w:altChunk"],
+                min_occurs: 1,
+                max_occurs: MaxOccurs::Unbounded,
+            },
+        ],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:sectPr",
+        children: &[
+            ChildRule {
+                names: &["w:headerReference", "w:footerReference"],
+                min_occurs: 0,
+                max_occurs: MaxOccurs::Value(6),
+            },
+            at_most_one(&["w:pgSz"]),
+            at_most_one(&["w:pgMar"]),
+            at_most_one(&["w:cols"]),
+        ],
+        attributes: &[],
+    },
+    ElementRule {
+        name: "w:headerReference",
+        children: &[],
+        attributes: HDR_FTR_REFERENCE_ATTRIBUTES,
+    },
+    ElementRule {
+        name: "w:footerReference",
+        children: &[],
+        attributes: HDR_FTR_REFERENCE_ATTRIBUTES,
+    },
+    ElementRule {
+        name: "w:pgSz",
+        children: &[],
+        attributes: &[
+            optional("w:w", TWIPS),
+            optional("w:h", TWIPS),
+            optional("w:orient", ValueRule::Enumeration(&["portrait", "landscape"])),
+            optional("w:code", DECIMAL_NUMBER),
+        ],
+    },
+    ElementRule {
+        name: "w:pgMar",
+        children: &[],
+        attributes: &[
+            required("w:top", SIGNED_TWIPS),
+            required("w:right", TWIPS),
+            required("w:bottom", SIGNED_TWIPS),
+            required("w:left", TWIPS),
+            required("w:header", TWIPS),
+            required("w:footer", TWIPS),
+            required("w:gutter", TWIPS),
+        ],
+    },
+    ElementRule {
+        name: "w:cols",
+        children: &[ChildRule {
+            names: &["w:col"],
+            min_occurs: 0,
+            max_occurs: MaxOccurs::Value(45),
+        }],
+        attributes: &[
+            optional("w:equalWidth", ValueRule::OnOff),
+            optional("w:space", TWIPS),
+            optional("w:num", ValueRule::Integer { min: 1, max: 45 }),
+            optional("w:sep", ValueRule::OnOff),
+        ],
+    },
+    ElementRule {
+        name: "w:col",
+        children: &[],
+        attributes: &[optional("w:w", TWIPS), optional("w:space", TWIPS)],
+    },
+    ElementRule {
+        name: "w:ind",
+        children: &[],
+        attributes: &[
+            optional("w:start", SIGNED_TWIPS),
+            optional("w:end", SIGNED_TWIPS),
+            optional("w:left", SIGNED_TWIPS),
+            optional("w:right", SIGNED_TWIPS),
+            optional("w:hanging", TWIPS),
+            optional("w:firstLine", TWIPS),
+        ],
+    },
+    val_element!(
+        "w:jc",
+        ValueRule::Enumeration(&[
+            "start",
+            "center",
+            "end",
+            "both",
+            "mediumKashida",
+            "distribute",
+            "numTab",
+            "highKashida",
+            "lowKashida",
+            "thaiDistribute",
+            "left",
+            "right",
+        ]),
+    ),
+    val_element!("w:pStyle", NON_EMPTY),
+    val_element!("w:rStyle", NON_EMPTY),
+    val_element!("w:tblStyle", NON_EMPTY),
+    val_element!("w:ilvl", ValueRule::Integer { min: 0, max: 8 }),
+    val_element!(
+        "w:numId",
+        ValueRule::Integer {
+            min: 0,
+            max: i32::MAX as i64,
+        },
+    ),
+    val_element!("w:sz", HALF_POINTS),
+    val_element!("w:szCs", HALF_POINTS),
+    val_element!("w:color", HEX_COLOR),
+    val_element!(
+        "w:gridSpan",
+        ValueRule::Integer {
+            min: 1,
+            max: i32::MAX as i64,
+        },
+    ),
+    ElementRule {
+        name: "w:vMerge",
+        children: &[],
+        attributes: &[optional("w:val", ValueRule::Enumeration(&["restart", "continue"]))],
+    },
+    ElementRule {
+        name: "w:tblW",
+        children: &[],
+        attributes: TABLE_WIDTH_ATTRIBUTES,
+    },
+    ElementRule {
+        name: "w:tcW",
+        children: &[],
+        attributes: TABLE_WIDTH_ATTRIBUTES,
+    },
+    on_off("w:b"),
+    on_off("w:bCs"),
+    on_off("w:i"),
+    on_off("w:iCs"),
+    on_off("w:caps"),
+    on_off("w:smallCaps"),
+    on_off("w:strike"),
+    on_off("w:dstrike"),
+    on_off("w:outline"),
+    on_off("w:shadow"),
+    on_off("w:emboss"),
+    on_off("w:imprint"),
+    on_off("w:vanish"),
+    on_off("w:keepNext"),
+    on_off("w:keepLines"),
+    on_off("w:pageBreakBefore"),
+    on_off("w:widowControl"),
+    on_off("w:contextualSpacing"),
+    on_off("w:bidi"),
+    on_off("w:rtl"),
+    on_off("w:titlePg"),
+    on_off("w:noProof"),
+];
+
+/// Validates the xml tree of a WordprocessingML part against [`WML_RULES`].
+pub fn validate_wml(root: &crate::xml::XmlNode) -> ConformanceReport {
+    Validator::new(WML_RULES).validate(root)
+}
+
+/// Repairs the xml tree of a WordprocessingML part that failed to parse. The violations of [`WML_RULES`] are repaired
+/// first. If the part still doesn't parse, the content of the body (or the top level elements of other parts, e.g.
+/// the styles) is searched for the innermost elements failing to parse and those are removed.
+pub(crate) fn repair_wml_part<F>(part_name: &str, root: &mut XmlNode, parse: F) -> Vec<ParseDiagnostic>
+where
+    F: Fn(&XmlNode) -> Result<()>,
+{
+    let diagnostics: Vec<ParseDiagnostic> = Validator::new(WML_RULES)
+        .repair(root)
+        .into_iter()
+        .map(|repair| ParseDiagnostic::Repaired {
+            part_name: String::from(part_name),
+            repair,
+        })
+        .collect();
+    if parse(root).is_ok() {
+        return diagnostics;
+    }
+
+    let body_index = if &*root.name == "w:document" {
+        root.child_nodes
+            .iter()
+            .position(|child_node| &*child_node.name == "w:body")
+    } else {
+        None
+    };
+    let (mut indexes, path) = match body_index {
+        Some(index) => (vec![index], format!("/{}/w:body", root.name)),
+        None => (Vec::new(), format!("/{}", root.name)),
+    };
+
+    let mut repairer = ElementRepairer {
+        part_name,
+        parse,
+        diagnostics,
+    };
+    repairer.repair_element(root, &mut indexes, &path);
+    repairer.diagnostics
+}
+
+struct ElementRepairer<'a, F> {
+    part_name: &'a str,
+    parse: F,
+    diagnostics: Vec<ParseDiagnostic>,
+}
+
+impl<F> ElementRepairer<'_, F>
+where
+    F: Fn(&XmlNode) -> Result<()>,
+{
+    /// Removes the innermost descendants of the element at the child indexes that make the part fail to parse. The
+    /// part must fail to parse with the element. Returns false if the element itself has to be removed, e.g. because of
+    /// an invalid attribute.
+    ///
+    /// The children of the element are removed and added back one by one, descending into the ones failing to parse.
+    /// If the element doesn't parse without its children, e.g. a table without its grid, its children are emptied one
+    /// at a time instead, descending into the ones whose descendants make the part fail. Children that still fail to
+    /// parse when empty are removed if the part parses without them.
+    fn repair_element(&mut self, root: &mut XmlNode, indexes: &mut Vec<usize>, path: &str) -> bool {
+        let paths = child_paths(path, node_at_mut(root, indexes));
+        let children = mem::take(&mut node_at_mut(root, indexes).child_nodes);
+        if (self.parse)(root).is_ok() {
+            for (child, path) in children.into_iter().zip(paths) {
+                let element = node_at_mut(root, indexes);
+                element.child_nodes.push(child);
+                let index = element.child_nodes.len() - 1;
+                if let Err(error) = (self.parse)(root) {
+                    self.repair_child(root, indexes, index, path, error);
+                }
+            }
+
+            return true;
+        }
+
+        node_at_mut(root, indexes).child_nodes = children;
+        let mut index = 0;
+        for path in paths {
+            let error = match (self.parse)(root) {
+                Ok(()) => return true,
+                Err(error) => error,
+            };
+
+            let child = &mut node_at_mut(root, indexes).child_nodes[index];
+            let grandchildren = mem::take(&mut child.child_nodes);
+            let has_grandchildren = !grandchildren.is_empty();
+            let parses_when_empty = has_grandchildren && (self.parse)(root).is_ok();
+            node_at_mut(root, indexes).child_nodes[index].child_nodes = grandchildren;
+            if parses_when_empty {
+                self.repair_child(root, indexes, index, path, error);
+                index += 1;
+                continue;
+            }
+
+            let child = node_at_mut(root, indexes).child_nodes.remove(index);
+            if (self.parse)(root).is_ok() {
+                self.skip_child(path, error);
+            } else {
+                node_at_mut(root, indexes).child_nodes.insert(index, child);
+                index += 1;
+            }
+        }
+
+        (self.parse)(root).is_ok()
+    }
+
+    /// Repairs the child of the element at the child indexes, removing it if it can't be repaired. Returns whether the
+    /// child is kept.
+    fn repair_child(
+        &mut self,
+        root: &mut XmlNode,
+        indexes: &mut Vec<usize>,
+        index: usize,
+        path: String,
+        error: Box<dyn Error>,
+    ) -> bool {
+        indexes.push(index);
+        let kept = self.repair_element(root, indexes, &path);
+        indexes.pop();
+
+        if !kept {
+            node_at_mut(root, indexes).child_nodes.remove(index);
+            self.skip_child(path, error);
+        }
+
+        kept
+    }
+
+    fn skip_child(&mut self, path: String, error: Box<dyn Error>) {
+        self.diagnostics.push(ParseDiagnostic::SkippedElement {
+            part_name: String::from(self.part_name),
+            path,
+            error: error.to_string(),
+        });
+    }
+}
+
+fn node_at_mut<'a>(root: &'a mut XmlNode, indexes: &[usize]) -> &'a mut XmlNode {
+    indexes.iter().fold(root, |node, index| &mut node.child_nodes[*index])
+}
+
+fn is_wml_part(part_name: &str) -> bool {
+    part_name.starts_with("word/") && part_name.ends_with(".xml") && !part_name.contains("/_rels/")
+}
+
+impl Package {
+    /// Opens a package. In strict mode the WordprocessingML parts are validated first and opening fails with a
+    /// [`ConformanceError`] listing every violation. In lenient mode the parts are recovered as much as possible, use
+    /// [`Package::from_file_lenient`] to get the diagnostics as well.
+    pub fn from_file_with_options(file_path: &Path, options: ParseOptions) -> Result<Self> {
+        if options.strict {
+            let report = Self::validate_file(file_path)?;
+            if !report.is_conformant() {
+                return Err(Box::new(ConformanceError { report }));
+            }
+        }
+
+        let package = if options.lenient {
+            let report = Self::from_file_lenient(file_path)?;
+            for diagnostic in &report.diagnostics {
+                warn!("{}", diagnostic);
+            }
+            report.package
+        } else {
+            Self::from_file(file_path)?
+        };
+        if let Some(required) = options.conformance_class {
+            let found = package.conformance_class();
+            if found != required {
+                return Err(Box::new(ConformanceClassError { required, found }));
+            }
+        }
+
+        Ok(package)
+    }
+
+    /// Returns the conformance class of the main document, see
+    /// [`Document::conformance`](super::wml::document::Document::conformance).
+    pub fn conformance_class(&self) -> ConformanceClass {
+        self.main_document
+            .as_ref()
+            .and_then(|document| document.conformance)
+            .unwrap_or(ConformanceClass::Transitional)
+    }
+
+    /// Validates the WordprocessingML parts of the package file. The paths of the violations are prefixed with the
+    /// name of the part, e.g. `word/document.xml:/w:document/w:body/w:tbl`.
+    pub fn validate(&self) -> Result<ConformanceReport> {
+        Self::validate_file(&self.file_path)
+    }
+
+    fn validate_file(file_path: &Path) -> Result<ConformanceReport> {
+        let file = File::open(file_path)?;
+        let mut zipper = ZipArchive::new(&file)?;
+        let validator = Validator::new(WML_RULES);
+
+        let mut report: ConformanceReport = Default::default();
+        for idx in 0..zipper.len() {
+            let mut zip_file = zipper.by_index(idx)?;
+            let part_name = String::from(zip_file.name());
+            if !is_wml_part(&part_name) {
+                continue;
+            }
+
+            let root = zip_file_to_xml_node(&mut zip_file)?;
+            report.merge_part(part_name, validator.validate(&root));
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::wml::document::Document;
+    use super::*;
+    use crate::shared::validation::ViolationKind;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_validate_wml() {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:pPr><w:jc w:val="middle" /></w:pPr>
+                    <w:r><w:rPr><w:b w:val="on" /><w:sz w:val="0" /></w:rPr></w:r>
+                </w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tr><w:tc /></w:tr>
+                </w:tbl>
+                <w:sectPr>
+                    <w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1in" w:header="720" w:footer="720" />
+                    <w:cols w:num="46" />
+                </w:sectPr>
+            </w:body>
+        </w:document>"#;
+
+        let report = validate_wml(&XmlNode::from_str(xml).unwrap());
+        let violations: Vec<(&str, &ViolationKind)> = report
+            .violations
+            .iter()
+            .map(|violation| (violation.path.as_str(), &violation.kind))
+            .collect();
+
+        assert_eq!(violations.len(), 6);
+        assert!(matches!(
+            violations[0],
+            (
+                "/w:document/w:body/w:p/w:pPr/w:jc",
+                ViolationKind::InvalidAttributeValue { name: "w:val", .. }
+            )
+        ));
+        assert!(matches!(
+            violations[1],
+            (
+                "/w:document/w:body/w:p/w:r/w:rPr/w:sz",
+                ViolationKind::InvalidAttributeValue { name: "w:val", .. }
+            )
+        ));
+        assert!(matches!(
+            violations[2],
+            (
+                "/w:document/w:body/w:tbl",
+                ViolationKind::TooFewOccurrences { occurs: 0, .. }
+            )
+        ));
+        assert!(matches!(
+            violations[3],
+            (
+                "/w:document/w:body/w:tbl/w:tr/w:tc",
+                ViolationKind::TooFewOccurrences { occurs: 0, .. }
+            )
+        ));
+        assert_eq!(
+            violations[4],
+            (
+                "/w:document/w:body/w:sectPr/w:pgMar",
+                &ViolationKind::MissingAttribute("w:gutter")
+            )
+        );
+        assert!(matches!(
+            violations[5],
+            (
+                "/w:document/w:body/w:sectPr/w:cols",
+                ViolationKind::InvalidAttributeValue { name: "w:num", .. }
+            )
+        ));
+    }
+
+    #[test]
+    pub fn test_repair_wml_part() {
+        let xml = r#"<w:document>
+            <w:body>
+                <w:p>
+                    <w:pPr><w:jc w:val="middle" /></w:pPr>
+                    <w:r><w:t>First</w:t></w:r>
+                </w:p>
+                <w:p>
+                    <w:pPr><w:spacing w:lineRule="sometimes" /></w:pPr>
+                    <w:r><w:t>Second</w:t></w:r>
+                </w:p>
+                <w:p><w:r><w:t>Third</w:t></w:r></w:p>
+                <w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid><w:gridCol w:w="1000" /></w:tblGrid>
+                    <w:tr><w:tc><w:p><w:r><w:rPr><w:u w:val="wavy" /></w:rPr><w:t>Cell</w:t></w:r></w:p></w:tc></w:tr>
+                </w:tbl>
+                <w:sectPr><w:cols w:num="46" /></w:sectPr>
+            </w:body>
+        </w:document>"#;
+
+        let parse = |xml_node: &XmlNode| Document::from_xml_element(xml_node).map(|_| ());
+        let mut root = XmlNode::from_str(xml).unwrap();
+        assert!(parse(&root).is_err());
+
+        let diagnostics = repair_wml_part("word/document.xml", &mut root, parse);
+        assert_eq!(diagnostics.len(), 4);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "word/document.xml:/w:document/w:body/w:p[1]/w:pPr/w:jc: attribute w:val has value 'middle', expected \
+             one of start, center, end, both, mediumKashida, distribute, numTab, highKashida, lowKashida, \
+             thaiDistribute, left, right, removed element"
+        );
+        assert!(matches!(
+            &diagnostics[1],
+            ParseDiagnostic::Repaired {
+                repair: Repair {
+                    action: RepairAction::RemovedAttribute("w:num"),
+                    ..
+                },
+                ..
+            }
+        ));
+        assert!(matches!(
+            &diagnostics[2],
+            ParseDiagnostic::SkippedElement { path, .. } if path == "/w:document/w:body/w:p[2]/w:pPr/w:spacing"
+        ));
+        assert!(matches!(
+            &diagnostics[3],
+            ParseDiagnostic::SkippedElement { path, .. }
+                if path == "/w:document/w:body/w:tbl/w:tr/w:tc/w:p/w:r/w:rPr/w:u"
+        ));
+
+        let document = Document::from_xml_element(&root).unwrap();
+        assert_eq!(
+            document.text_map(crate::docx::text::RevisionView::Accepted).text,
+            "First\nSecond\nThird\nCell"
+        );
+    }
+}
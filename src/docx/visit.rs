@@ -0,0 +1,807 @@
+//! Traversal of the WordprocessingML tree for transforms and analyses.
+//!
+//! A transform implements [`VisitMut`], an analysis that only reads the tree implements [`Visit`]. Both visit the same
+//! elements in the same order. A visitor overrides only the methods of the elements it handles. Every method walks
+//! into the children of its element by default, using the `walk_*` function of the same element, so an override has
+//! to call the walk function itself to keep visiting the descendants:
+//!
+//! ```
+//! use oox::{
+//!     docx::{
+//!         visit::{walk_run_mut, VisitMut},
+//!         wml::document::{Document, RPrBase, R},
+//!     },
+//!     xml::XmlNode,
+//! };
+//! use std::str::FromStr;
+//!
+//! /// Removes the character style of every run.
+//! struct RemoveRunStyles;
+//!
+//! impl VisitMut for RemoveRunStyles {
+//!     fn visit_run_mut(&mut self, run: &mut R) {
+//!         if let Some(run_properties) = &mut run.run_properties {
+//!             run_properties.r_pr_bases.retain(|base| !matches!(base, RPrBase::RunStyle(_)));
+//!         }
+//!         walk_run_mut(self, run);
+//!     }
+//! }
+//!
+//! let xml = r#"<w:document><w:body><w:tbl><w:tblPr /><w:tblGrid /><w:tr><w:tc>
+//!     <w:p><w:hyperlink r:id="rId1"><w:r><w:rPr><w:rStyle w:val="Hyperlink" /></w:rPr><w:t>Link</w:t></w:r></w:hyperlink></w:p>
+//! </w:tc></w:tr></w:tbl></w:body></w:document>"#;
+//! let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+//!
+//! RemoveRunStyles.visit_document_mut(&mut document);
+//! let (_, run) = document.iter_runs().next().unwrap();
+//! assert!(run.run_properties.as_ref().unwrap().r_pr_bases.is_empty());
+//! ```
+
+use super::{
+    package::Package,
+    wml::{
+        document::{
+            AltChunk, BlockLevelElts, Body, ContentBlockContent, ContentRunContent, Document, Hyperlink, PContent, PPr,
+            RPr, RangeMarkupElements, RunInnerContent, RunLevelElts, RunTrackChange, RunTrackChangeChoice, SectPr, P,
+            R,
+        },
+        table::{ContentCellContent, ContentRowContent, Row, Tbl, Tc},
+    },
+};
+
+/// A mutable visitor of the WordprocessingML tree, see the [module documentation](self).
+pub trait VisitMut {
+    fn visit_document_mut(&mut self, document: &mut Document) {
+        walk_document_mut(self, document);
+    }
+
+    fn visit_body_mut(&mut self, body: &mut Body) {
+        walk_body_mut(self, body);
+    }
+
+    /// Visits a list of block level elements, e.g. the content of the body, a table cell or a note. Elements can be
+    /// added or removed here.
+    fn visit_blocks_mut(&mut self, blocks: &mut Vec<BlockLevelElts>) {
+        walk_blocks_mut(self, blocks);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut BlockLevelElts) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_alt_chunk_mut(&mut self, _alt_chunk: &mut AltChunk) {}
+
+    /// Visits the block level contents of a custom XML element or a structured document tag. Contents can be added or
+    /// removed here.
+    fn visit_content_blocks_mut(&mut self, contents: &mut Vec<ContentBlockContent>) {
+        walk_content_blocks_mut(self, contents);
+    }
+
+    fn visit_content_block_mut(&mut self, content: &mut ContentBlockContent) {
+        walk_content_block_mut(self, content);
+    }
+
+    fn visit_paragraph_mut(&mut self, paragraph: &mut P) {
+        walk_paragraph_mut(self, paragraph);
+    }
+
+    fn visit_paragraph_properties_mut(&mut self, properties: &mut PPr) {
+        walk_paragraph_properties_mut(self, properties);
+    }
+
+    /// Visits the contents of a paragraph, field, hyperlink or other inline container. Contents can be added or
+    /// removed here.
+    fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+        walk_paragraph_contents_mut(self, contents);
+    }
+
+    fn visit_paragraph_content_mut(&mut self, content: &mut PContent) {
+        walk_paragraph_content_mut(self, content);
+    }
+
+    fn visit_hyperlink_mut(&mut self, hyperlink: &mut Hyperlink) {
+        walk_hyperlink_mut(self, hyperlink);
+    }
+
+    fn visit_run_content_mut(&mut self, content: &mut ContentRunContent) {
+        walk_run_content_mut(self, content);
+    }
+
+    fn visit_run_level_element_mut(&mut self, element: &mut RunLevelElts) {
+        walk_run_level_element_mut(self, element);
+    }
+
+    /// Visits an inserted, deleted or moved run container.
+    fn visit_run_track_change_mut(&mut self, track_change: &mut RunTrackChange) {
+        walk_run_track_change_mut(self, track_change);
+    }
+
+    fn visit_range_markup_mut(&mut self, _element: &mut RangeMarkupElements) {}
+
+    fn visit_run_mut(&mut self, run: &mut R) {
+        walk_run_mut(self, run);
+    }
+
+    fn visit_run_properties_mut(&mut self, _run_properties: &mut RPr) {}
+
+    fn visit_run_inner_content_mut(&mut self, _content: &mut RunInnerContent) {}
+
+    fn visit_table_mut(&mut self, table: &mut Tbl) {
+        walk_table_mut(self, table);
+    }
+
+    /// Visits the rows of a table or of an element grouping rows. Rows can be added or removed here.
+    fn visit_row_contents_mut(&mut self, contents: &mut Vec<ContentRowContent>) {
+        walk_row_contents_mut(self, contents);
+    }
+
+    fn visit_row_content_mut(&mut self, content: &mut ContentRowContent) {
+        walk_row_content_mut(self, content);
+    }
+
+    fn visit_row_mut(&mut self, row: &mut Row) {
+        walk_row_mut(self, row);
+    }
+
+    /// Visits the cells of a row or of an element grouping cells. Cells can be added or removed here.
+    fn visit_cell_contents_mut(&mut self, contents: &mut Vec<ContentCellContent>) {
+        walk_cell_contents_mut(self, contents);
+    }
+
+    fn visit_cell_content_mut(&mut self, content: &mut ContentCellContent) {
+        walk_cell_content_mut(self, content);
+    }
+
+    fn visit_cell_mut(&mut self, cell: &mut Tc) {
+        walk_cell_mut(self, cell);
+    }
+
+    fn visit_section_properties_mut(&mut self, _section_properties: &mut SectPr) {}
+}
+
+impl Package {
+    /// Visits the stories of the package: the body of the main document and the content of every footnote, endnote
+    /// and comment, in this order.
+    pub fn visit_stories_mut<V: VisitMut + ?Sized>(&mut self, visitor: &mut V) {
+        if let Some(document) = &mut self.main_document {
+            visitor.visit_document_mut(document);
+        }
+
+        let notes = self.footnotes.iter_mut().flat_map(|footnotes| footnotes.0.iter_mut());
+        for note in notes.chain(self.endnotes.iter_mut().flat_map(|endnotes| endnotes.0.iter_mut())) {
+            visitor.visit_blocks_mut(&mut note.block_level_elements);
+        }
+
+        for comment in self.comments.iter_mut().flat_map(|comments| comments.0.iter_mut()) {
+            visitor.visit_blocks_mut(&mut comment.block_level_elements);
+        }
+    }
+}
+
+pub fn walk_document_mut<V: VisitMut + ?Sized>(visitor: &mut V, document: &mut Document) {
+    if let Some(body) = &mut document.body {
+        visitor.visit_body_mut(body);
+    }
+}
+
+pub fn walk_body_mut<V: VisitMut + ?Sized>(visitor: &mut V, body: &mut Body) {
+    visitor.visit_blocks_mut(&mut body.block_level_elements);
+    if let Some(section_properties) = &mut body.section_properties {
+        visitor.visit_section_properties_mut(section_properties);
+    }
+}
+
+pub fn walk_blocks_mut<V: VisitMut + ?Sized>(visitor: &mut V, blocks: &mut Vec<BlockLevelElts>) {
+    for block in blocks {
+        visitor.visit_block_mut(block);
+    }
+}
+
+pub fn walk_block_mut<V: VisitMut + ?Sized>(visitor: &mut V, block: &mut BlockLevelElts) {
+    match block {
+        BlockLevelElts::Chunk(content) => visitor.visit_content_block_mut(content),
+        BlockLevelElts::AltChunk(alt_chunk) => visitor.visit_alt_chunk_mut(alt_chunk),
+    }
+}
+
+pub fn walk_content_blocks_mut<V: VisitMut + ?Sized>(visitor: &mut V, contents: &mut Vec<ContentBlockContent>) {
+    for content in contents {
+        visitor.visit_content_block_mut(content);
+    }
+}
+
+pub fn walk_content_block_mut<V: VisitMut + ?Sized>(visitor: &mut V, content: &mut ContentBlockContent) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => visitor.visit_content_blocks_mut(&mut custom_xml.block_contents),
+        ContentBlockContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &mut sdt.sdt_content {
+                visitor.visit_content_blocks_mut(&mut sdt_content.block_contents);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => visitor.visit_paragraph_mut(paragraph),
+        ContentBlockContent::Table(table) => visitor.visit_table_mut(table),
+        ContentBlockContent::RunLevelElement(element) => visitor.visit_run_level_element_mut(element),
+    }
+}
+
+pub fn walk_paragraph_mut<V: VisitMut + ?Sized>(visitor: &mut V, paragraph: &mut P) {
+    if let Some(properties) = &mut paragraph.properties {
+        visitor.visit_paragraph_properties_mut(properties);
+    }
+    visitor.visit_paragraph_contents_mut(&mut paragraph.contents);
+}
+
+pub fn walk_paragraph_properties_mut<V: VisitMut + ?Sized>(visitor: &mut V, properties: &mut PPr) {
+    if let Some(section_properties) = &mut properties.section_properties {
+        visitor.visit_section_properties_mut(section_properties);
+    }
+}
+
+pub fn walk_paragraph_contents_mut<V: VisitMut + ?Sized>(visitor: &mut V, contents: &mut Vec<PContent>) {
+    for content in contents {
+        visitor.visit_paragraph_content_mut(content);
+    }
+}
+
+pub fn walk_paragraph_content_mut<V: VisitMut + ?Sized>(visitor: &mut V, content: &mut PContent) {
+    match content {
+        PContent::ContentRunContent(content) => visitor.visit_run_content_mut(content),
+        PContent::SimpleField(field) => visitor.visit_paragraph_contents_mut(&mut field.paragraph_contents),
+        PContent::Hyperlink(hyperlink) => visitor.visit_hyperlink_mut(hyperlink),
+        PContent::SubDocument(_) => (),
+    }
+}
+
+pub fn walk_hyperlink_mut<V: VisitMut + ?Sized>(visitor: &mut V, hyperlink: &mut Hyperlink) {
+    visitor.visit_paragraph_contents_mut(&mut hyperlink.paragraph_contents);
+}
+
+pub fn walk_run_content_mut<V: VisitMut + ?Sized>(visitor: &mut V, content: &mut ContentRunContent) {
+    match content {
+        ContentRunContent::Run(run) => visitor.visit_run_mut(run),
+        ContentRunContent::CustomXml(custom_xml) => {
+            visitor.visit_paragraph_contents_mut(&mut custom_xml.paragraph_contents)
+        }
+        ContentRunContent::SmartTag(smart_tag) => {
+            visitor.visit_paragraph_contents_mut(&mut smart_tag.paragraph_contents)
+        }
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &mut sdt.sdt_content {
+                visitor.visit_paragraph_contents_mut(&mut sdt_content.p_contents);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => visitor.visit_paragraph_contents_mut(&mut dir.p_contents),
+        ContentRunContent::BidirectionalOverride(bdo) => visitor.visit_paragraph_contents_mut(&mut bdo.p_contents),
+        ContentRunContent::RunLevelElements(element) => visitor.visit_run_level_element_mut(element),
+    }
+}
+
+pub fn walk_run_level_element_mut<V: VisitMut + ?Sized>(visitor: &mut V, element: &mut RunLevelElts) {
+    match element {
+        RunLevelElts::Insert(track_change)
+        | RunLevelElts::Delete(track_change)
+        | RunLevelElts::MoveFrom(track_change)
+        | RunLevelElts::MoveTo(track_change) => visitor.visit_run_track_change_mut(track_change),
+        RunLevelElts::RangeMarkupElements(element) => visitor.visit_range_markup_mut(element),
+        _ => (),
+    }
+}
+
+pub fn walk_run_track_change_mut<V: VisitMut + ?Sized>(visitor: &mut V, track_change: &mut RunTrackChange) {
+    for RunTrackChangeChoice::ContentRunContent(content) in &mut track_change.choices {
+        visitor.visit_run_content_mut(content);
+    }
+}
+
+pub fn walk_run_mut<V: VisitMut + ?Sized>(visitor: &mut V, run: &mut R) {
+    if let Some(run_properties) = &mut run.run_properties {
+        visitor.visit_run_properties_mut(run_properties);
+    }
+    for content in &mut run.run_inner_contents {
+        visitor.visit_run_inner_content_mut(content);
+    }
+}
+
+pub fn walk_table_mut<V: VisitMut + ?Sized>(visitor: &mut V, table: &mut Tbl) {
+    for element in &mut table.range_markup_elements {
+        visitor.visit_range_markup_mut(element);
+    }
+    visitor.visit_row_contents_mut(&mut table.row_contents);
+}
+
+pub fn walk_row_contents_mut<V: VisitMut + ?Sized>(visitor: &mut V, contents: &mut Vec<ContentRowContent>) {
+    for content in contents {
+        visitor.visit_row_content_mut(content);
+    }
+}
+
+pub fn walk_row_content_mut<V: VisitMut + ?Sized>(visitor: &mut V, content: &mut ContentRowContent) {
+    match content {
+        ContentRowContent::Table(row) => visitor.visit_row_mut(row),
+        ContentRowContent::CustomXml(custom_xml) => visitor.visit_row_contents_mut(&mut custom_xml.contents),
+        ContentRowContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &mut sdt.content {
+                visitor.visit_row_contents_mut(&mut sdt_content.contents);
+            }
+        }
+        ContentRowContent::RunLevelElements(element) => visitor.visit_run_level_element_mut(element),
+    }
+}
+
+pub fn walk_row_mut<V: VisitMut + ?Sized>(visitor: &mut V, row: &mut Row) {
+    visitor.visit_cell_contents_mut(&mut row.contents);
+}
+
+pub fn walk_cell_contents_mut<V: VisitMut + ?Sized>(visitor: &mut V, contents: &mut Vec<ContentCellContent>) {
+    for content in contents {
+        visitor.visit_cell_content_mut(content);
+    }
+}
+
+pub fn walk_cell_content_mut<V: VisitMut + ?Sized>(visitor: &mut V, content: &mut ContentCellContent) {
+    match content {
+        ContentCellContent::Cell(cell) => visitor.visit_cell_mut(cell),
+        ContentCellContent::CustomXml(custom_xml) => visitor.visit_cell_contents_mut(&mut custom_xml.contents),
+        ContentCellContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &mut sdt.content {
+                visitor.visit_cell_contents_mut(&mut sdt_content.contents);
+            }
+        }
+        ContentCellContent::RunLevelElement(element) => visitor.visit_run_level_element_mut(element),
+    }
+}
+
+pub fn walk_cell_mut<V: VisitMut + ?Sized>(visitor: &mut V, cell: &mut Tc) {
+    visitor.visit_blocks_mut(&mut cell.block_level_elements);
+}
+
+/// A visitor of the WordprocessingML tree for analyses that don't change it. It visits the same elements as
+/// [`VisitMut`].
+pub trait Visit {
+    fn visit_document(&mut self, document: &Document) {
+        walk_document(self, document);
+    }
+
+    fn visit_body(&mut self, body: &Body) {
+        walk_body(self, body);
+    }
+
+    /// Visits a list of block level elements, e.g. the content of the body, a table cell or a note.
+    fn visit_blocks(&mut self, blocks: &[BlockLevelElts]) {
+        walk_blocks(self, blocks);
+    }
+
+    fn visit_block(&mut self, block: &BlockLevelElts) {
+        walk_block(self, block);
+    }
+
+    fn visit_alt_chunk(&mut self, _alt_chunk: &AltChunk) {}
+
+    /// Visits the block level contents of a custom XML element or a structured document tag.
+    fn visit_content_blocks(&mut self, contents: &[ContentBlockContent]) {
+        walk_content_blocks(self, contents);
+    }
+
+    fn visit_content_block(&mut self, content: &ContentBlockContent) {
+        walk_content_block(self, content);
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &P) {
+        walk_paragraph(self, paragraph);
+    }
+
+    fn visit_paragraph_properties(&mut self, properties: &PPr) {
+        walk_paragraph_properties(self, properties);
+    }
+
+    /// Visits the contents of a paragraph, field, hyperlink or other inline container.
+    fn visit_paragraph_contents(&mut self, contents: &[PContent]) {
+        walk_paragraph_contents(self, contents);
+    }
+
+    fn visit_paragraph_content(&mut self, content: &PContent) {
+        walk_paragraph_content(self, content);
+    }
+
+    fn visit_hyperlink(&mut self, hyperlink: &Hyperlink) {
+        walk_hyperlink(self, hyperlink);
+    }
+
+    fn visit_run_content(&mut self, content: &ContentRunContent) {
+        walk_run_content(self, content);
+    }
+
+    fn visit_run_level_element(&mut self, element: &RunLevelElts) {
+        walk_run_level_element(self, element);
+    }
+
+    /// Visits an inserted, deleted or moved run container.
+    fn visit_run_track_change(&mut self, track_change: &RunTrackChange) {
+        walk_run_track_change(self, track_change);
+    }
+
+    fn visit_range_markup(&mut self, _element: &RangeMarkupElements) {}
+
+    fn visit_run(&mut self, run: &R) {
+        walk_run(self, run);
+    }
+
+    fn visit_run_properties(&mut self, _run_properties: &RPr) {}
+
+    fn visit_run_inner_content(&mut self, _content: &RunInnerContent) {}
+
+    fn visit_table(&mut self, table: &Tbl) {
+        walk_table(self, table);
+    }
+
+    /// Visits the rows of a table or of an element grouping rows.
+    fn visit_row_contents(&mut self, contents: &[ContentRowContent]) {
+        walk_row_contents(self, contents);
+    }
+
+    fn visit_row_content(&mut self, content: &ContentRowContent) {
+        walk_row_content(self, content);
+    }
+
+    fn visit_row(&mut self, row: &Row) {
+        walk_row(self, row);
+    }
+
+    /// Visits the cells of a row or of an element grouping cells.
+    fn visit_cell_contents(&mut self, contents: &[ContentCellContent]) {
+        walk_cell_contents(self, contents);
+    }
+
+    fn visit_cell_content(&mut self, content: &ContentCellContent) {
+        walk_cell_content(self, content);
+    }
+
+    fn visit_cell(&mut self, cell: &Tc) {
+        walk_cell(self, cell);
+    }
+
+    fn visit_section_properties(&mut self, _section_properties: &SectPr) {}
+}
+
+pub fn walk_document<V: Visit + ?Sized>(visitor: &mut V, document: &Document) {
+    if let Some(body) = &document.body {
+        visitor.visit_body(body);
+    }
+}
+
+pub fn walk_body<V: Visit + ?Sized>(visitor: &mut V, body: &Body) {
+    visitor.visit_blocks(&body.block_level_elements);
+    if let Some(section_properties) = &body.section_properties {
+        visitor.visit_section_properties(section_properties);
+    }
+}
+
+pub fn walk_blocks<V: Visit + ?Sized>(visitor: &mut V, blocks: &[BlockLevelElts]) {
+    for block in blocks {
+        visitor.visit_block(block);
+    }
+}
+
+pub fn walk_block<V: Visit + ?Sized>(visitor: &mut V, block: &BlockLevelElts) {
+    match block {
+        BlockLevelElts::Chunk(content) => visitor.visit_content_block(content),
+        BlockLevelElts::AltChunk(alt_chunk) => visitor.visit_alt_chunk(alt_chunk),
+    }
+}
+
+pub fn walk_content_blocks<V: Visit + ?Sized>(visitor: &mut V, contents: &[ContentBlockContent]) {
+    for content in contents {
+        visitor.visit_content_block(content);
+    }
+}
+
+pub fn walk_content_block<V: Visit + ?Sized>(visitor: &mut V, content: &ContentBlockContent) {
+    match content {
+        ContentBlockContent::CustomXml(custom_xml) => visitor.visit_content_blocks(&custom_xml.block_contents),
+        ContentBlockContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                visitor.visit_content_blocks(&sdt_content.block_contents);
+            }
+        }
+        ContentBlockContent::Paragraph(paragraph) => visitor.visit_paragraph(paragraph),
+        ContentBlockContent::Table(table) => visitor.visit_table(table),
+        ContentBlockContent::RunLevelElement(element) => visitor.visit_run_level_element(element),
+    }
+}
+
+pub fn walk_paragraph<V: Visit + ?Sized>(visitor: &mut V, paragraph: &P) {
+    if let Some(properties) = &paragraph.properties {
+        visitor.visit_paragraph_properties(properties);
+    }
+    visitor.visit_paragraph_contents(&paragraph.contents);
+}
+
+pub fn walk_paragraph_properties<V: Visit + ?Sized>(visitor: &mut V, properties: &PPr) {
+    if let Some(section_properties) = &properties.section_properties {
+        visitor.visit_section_properties(section_properties);
+    }
+}
+
+pub fn walk_paragraph_contents<V: Visit + ?Sized>(visitor: &mut V, contents: &[PContent]) {
+    for content in contents {
+        visitor.visit_paragraph_content(content);
+    }
+}
+
+pub fn walk_paragraph_content<V: Visit + ?Sized>(visitor: &mut V, content: &PContent) {
+    match content {
+        PContent::ContentRunContent(content) => visitor.visit_run_content(content),
+        PContent::SimpleField(field) => visitor.visit_paragraph_contents(&field.paragraph_contents),
+        PContent::Hyperlink(hyperlink) => visitor.visit_hyperlink(hyperlink),
+        PContent::SubDocument(_) => (),
+    }
+}
+
+pub fn walk_hyperlink<V: Visit + ?Sized>(visitor: &mut V, hyperlink: &Hyperlink) {
+    visitor.visit_paragraph_contents(&hyperlink.paragraph_contents);
+}
+
+pub fn walk_run_content<V: Visit + ?Sized>(visitor: &mut V, content: &ContentRunContent) {
+    match content {
+        ContentRunContent::Run(run) => visitor.visit_run(run),
+        ContentRunContent::CustomXml(custom_xml) => visitor.visit_paragraph_contents(&custom_xml.paragraph_contents),
+        ContentRunContent::SmartTag(smart_tag) => visitor.visit_paragraph_contents(&smart_tag.paragraph_contents),
+        ContentRunContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.sdt_content {
+                visitor.visit_paragraph_contents(&sdt_content.p_contents);
+            }
+        }
+        ContentRunContent::Bidirectional(dir) => visitor.visit_paragraph_contents(&dir.p_contents),
+        ContentRunContent::BidirectionalOverride(bdo) => visitor.visit_paragraph_contents(&bdo.p_contents),
+        ContentRunContent::RunLevelElements(element) => visitor.visit_run_level_element(element),
+    }
+}
+
+pub fn walk_run_level_element<V: Visit + ?Sized>(visitor: &mut V, element: &RunLevelElts) {
+    match element {
+        RunLevelElts::Insert(track_change)
+        | RunLevelElts::Delete(track_change)
+        | RunLevelElts::MoveFrom(track_change)
+        | RunLevelElts::MoveTo(track_change) => visitor.visit_run_track_change(track_change),
+        RunLevelElts::RangeMarkupElements(element) => visitor.visit_range_markup(element),
+        _ => (),
+    }
+}
+
+pub fn walk_run_track_change<V: Visit + ?Sized>(visitor: &mut V, track_change: &RunTrackChange) {
+    for RunTrackChangeChoice::ContentRunContent(content) in &track_change.choices {
+        visitor.visit_run_content(content);
+    }
+}
+
+pub fn walk_run<V: Visit + ?Sized>(visitor: &mut V, run: &R) {
+    if let Some(run_properties) = &run.run_properties {
+        visitor.visit_run_properties(run_properties);
+    }
+    for content in &run.run_inner_contents {
+        visitor.visit_run_inner_content(content);
+    }
+}
+
+pub fn walk_table<V: Visit + ?Sized>(visitor: &mut V, table: &Tbl) {
+    for element in &table.range_markup_elements {
+        visitor.visit_range_markup(element);
+    }
+    visitor.visit_row_contents(&table.row_contents);
+}
+
+pub fn walk_row_contents<V: Visit + ?Sized>(visitor: &mut V, contents: &[ContentRowContent]) {
+    for content in contents {
+        visitor.visit_row_content(content);
+    }
+}
+
+pub fn walk_row_content<V: Visit + ?Sized>(visitor: &mut V, content: &ContentRowContent) {
+    match content {
+        ContentRowContent::Table(row) => visitor.visit_row(row),
+        ContentRowContent::CustomXml(custom_xml) => visitor.visit_row_contents(&custom_xml.contents),
+        ContentRowContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.content {
+                visitor.visit_row_contents(&sdt_content.contents);
+            }
+        }
+        ContentRowContent::RunLevelElements(element) => visitor.visit_run_level_element(element),
+    }
+}
+
+pub fn walk_row<V: Visit + ?Sized>(visitor: &mut V, row: &Row) {
+    visitor.visit_cell_contents(&row.contents);
+}
+
+pub fn walk_cell_contents<V: Visit + ?Sized>(visitor: &mut V, contents: &[ContentCellContent]) {
+    for content in contents {
+        visitor.visit_cell_content(content);
+    }
+}
+
+pub fn walk_cell_content<V: Visit + ?Sized>(visitor: &mut V, content: &ContentCellContent) {
+    match content {
+        ContentCellContent::Cell(cell) => visitor.visit_cell(cell),
+        ContentCellContent::CustomXml(custom_xml) => visitor.visit_cell_contents(&custom_xml.contents),
+        ContentCellContent::Sdt(sdt) => {
+            if let Some(sdt_content) = &sdt.content {
+                visitor.visit_cell_contents(&sdt_content.contents);
+            }
+        }
+        ContentCellContent::RunLevelElement(element) => visitor.visit_run_level_element(element),
+    }
+}
+
+pub fn walk_cell<V: Visit + ?Sized>(visitor: &mut V, cell: &Tc) {
+    visitor.visit_blocks(&cell.block_level_elements);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::text::RevisionView, xml::XmlNode};
+    use std::str::FromStr;
+
+    /// Removes the deleted runs and unwraps the inserted ones, keeping the other run level elements.
+    struct AcceptInsertionsAndDeletions;
+
+    impl VisitMut for AcceptInsertionsAndDeletions {
+        fn visit_paragraph_contents_mut(&mut self, contents: &mut Vec<PContent>) {
+            let mut accepted = Vec::with_capacity(contents.len());
+            for content in contents.drain(..) {
+                match content {
+                    PContent::ContentRunContent(content) => match *content {
+                        ContentRunContent::RunLevelElements(RunLevelElts::Delete(_)) => (),
+                        ContentRunContent::RunLevelElements(RunLevelElts::Insert(track_change)) => {
+                            accepted.extend(track_change.choices.into_iter().map(
+                                |RunTrackChangeChoice::ContentRunContent(content)| {
+                                    PContent::ContentRunContent(Box::new(content))
+                                },
+                            ))
+                        }
+                        content => accepted.push(PContent::ContentRunContent(Box::new(content))),
+                    },
+                    content => accepted.push(content),
+                }
+            }
+            *contents = accepted;
+
+            walk_paragraph_contents_mut(self, contents);
+        }
+    }
+
+    /// Counts the visited elements by kind, to check that [`Visit`] and [`VisitMut`] traverse the same elements.
+    #[derive(Debug, Default, PartialEq)]
+    struct ElementCounter {
+        paragraphs: usize,
+        runs: usize,
+        track_changes: usize,
+        rows: usize,
+        cells: usize,
+        section_properties: usize,
+    }
+
+    impl Visit for ElementCounter {
+        fn visit_paragraph(&mut self, paragraph: &P) {
+            self.paragraphs += 1;
+            walk_paragraph(self, paragraph);
+        }
+
+        fn visit_run(&mut self, run: &R) {
+            self.runs += 1;
+            walk_run(self, run);
+        }
+
+        fn visit_run_track_change(&mut self, track_change: &RunTrackChange) {
+            self.track_changes += 1;
+            walk_run_track_change(self, track_change);
+        }
+
+        fn visit_row(&mut self, row: &Row) {
+            self.rows += 1;
+            walk_row(self, row);
+        }
+
+        fn visit_cell(&mut self, cell: &Tc) {
+            self.cells += 1;
+            walk_cell(self, cell);
+        }
+
+        fn visit_section_properties(&mut self, _section_properties: &SectPr) {
+            self.section_properties += 1;
+        }
+    }
+
+    impl VisitMut for ElementCounter {
+        fn visit_paragraph_mut(&mut self, paragraph: &mut P) {
+            self.paragraphs += 1;
+            walk_paragraph_mut(self, paragraph);
+        }
+
+        fn visit_run_mut(&mut self, run: &mut R) {
+            self.runs += 1;
+            walk_run_mut(self, run);
+        }
+
+        fn visit_run_track_change_mut(&mut self, track_change: &mut RunTrackChange) {
+            self.track_changes += 1;
+            walk_run_track_change_mut(self, track_change);
+        }
+
+        fn visit_row_mut(&mut self, row: &mut Row) {
+            self.rows += 1;
+            walk_row_mut(self, row);
+        }
+
+        fn visit_cell_mut(&mut self, cell: &mut Tc) {
+            self.cells += 1;
+            walk_cell_mut(self, cell);
+        }
+
+        fn visit_section_properties_mut(&mut self, _section_properties: &mut SectPr) {
+            self.section_properties += 1;
+        }
+    }
+
+    #[test]
+    pub fn test_visit_and_visit_mut_agree() {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:pPr><w:sectPr /></w:pPr>
+                <w:customXml w:uri="urn:test" w:element="name"><w:r><w:t>Custom</w:t></w:r></w:customXml>
+                <w:hyperlink r:id="rId1"><w:ins w:id="1" w:author="Jane"><w:r><w:t>Link</w:t></w:r></w:ins></w:hyperlink>
+            </w:p>
+            <w:sdt><w:sdtContent><w:p><w:fldSimple w:instr="PAGE"><w:r><w:t>1</w:t></w:r></w:fldSimple></w:p></w:sdtContent></w:sdt>
+            <w:tbl>
+                <w:tblPr />
+                <w:tblGrid />
+                <w:tr><w:tc><w:p><w:del w:id="2" w:author="Jane"><w:r><w:delText>Cell</w:delText></w:r></w:del></w:p></w:tc></w:tr>
+                <w:sdt><w:sdtContent><w:tr><w:tc><w:p /></w:tc></w:tr></w:sdtContent></w:sdt>
+            </w:tbl>
+            <w:sectPr />
+        </w:body></w:document>"#;
+        let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let mut counter = ElementCounter::default();
+        counter.visit_document(&document);
+        assert_eq!(
+            counter,
+            ElementCounter {
+                paragraphs: 4,
+                runs: 4,
+                track_changes: 2,
+                rows: 2,
+                cells: 2,
+                section_properties: 2,
+            }
+        );
+
+        let mut counter_mut = ElementCounter::default();
+        counter_mut.visit_document_mut(&mut document);
+        assert_eq!(counter_mut, counter);
+    }
+
+    #[test]
+    pub fn test_visit_mut() {
+        let xml = r#"<w:document><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Hello </w:t></w:r>
+                <w:del w:id="1" w:author="Jane"><w:r><w:delText>cruel </w:delText></w:r></w:del>
+                <w:hyperlink r:id="rId1">
+                    <w:ins w:id="2" w:author="Jane"><w:r><w:t>world</w:t></w:r></w:ins>
+                </w:hyperlink>
+            </w:p>
+            <w:tbl>
+                <w:tblPr />
+                <w:tblGrid />
+                <w:tr><w:tc><w:p><w:del w:id="3" w:author="Jane"><w:r><w:delText>Cell</w:delText></w:r></w:del></w:p></w:tc></w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let mut document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let accepted_text = document.text_map(RevisionView::Accepted).text;
+
+        AcceptInsertionsAndDeletions.visit_document_mut(&mut document);
+        assert_eq!(document.text_map(RevisionView::Rejected).text, accepted_text);
+        assert_eq!(document.iter_runs().count(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+use super::{
+    document::BlockLevelElts,
+    simpletypes::{DateTime, DecimalNumber, LongHexNumber},
+};
+use crate::{
+    error::MissingAttributeError,
+    xml::{parse_xml_bool, XmlNode},
+    xsdtypes::XsdChoice,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub id: DecimalNumber,
+    pub author: String,
+    pub date: Option<DateTime>,
+    pub initials: Option<String>,
+    pub block_level_elements: Vec<BlockLevelElts>,
+}
+
+impl Comment {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut id = None;
+        let mut author = None;
+        let mut date = None;
+        let mut initials = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "w:id" => id = Some(value.parse()?),
+                "w:author" => author = Some(value.clone()),
+                "w:date" => date = Some(value.clone()),
+                "w:initials" => initials = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        let id = id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w:id"))?;
+        let author = author.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w:author"))?;
+
+        let block_level_elements = xml_node
+            .child_nodes
+            .iter()
+            .filter_map(BlockLevelElts::try_from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            id,
+            author,
+            date,
+            initials,
+            block_level_elements,
+        })
+    }
+}
+
+/// The comments of the document (`word/comments.xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Comments(pub Vec<Comment>);
+
+impl Comments {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let comments = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "comment")
+            .map(Comment::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(comments))
+    }
+}
+
+/// Threading information of a comment. Comments are identified by the `w14:paraId` of their last paragraph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommentEx {
+    pub paragraph_id: LongHexNumber,
+    /// The paragraph id of the comment this comment replies to.
+    pub parent_paragraph_id: Option<LongHexNumber>,
+    pub done: Option<bool>,
+}
+
+impl CommentEx {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut paragraph_id = None;
+        let mut parent_paragraph_id = None;
+        let mut done = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "w15:paraId" => paragraph_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w15:paraIdParent" => parent_paragraph_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w15:done" => done = Some(parse_xml_bool(value)?),
+                _ => (),
+            }
+        }
+
+        let paragraph_id =
+            paragraph_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w15:paraId"))?;
+
+        Ok(Self {
+            paragraph_id,
+            parent_paragraph_id,
+            done,
+        })
+    }
+}
+
+/// The extended comment information of the document (`word/commentsExtended.xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommentsEx(pub Vec<CommentEx>);
+
+impl CommentsEx {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let comments = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "commentEx")
+            .map(CommentEx::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(comments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_comments_from_xml() {
+        let xml = r#"<w:comments>
+            <w:comment w:id="0" w:author="Jane Doe" w:date="2020-01-01T10:00:00Z" w:initials="JD">
+                <w:p w14:paraId="00000001" />
+            </w:comment>
+        </w:comments>"#;
+
+        let comments = Comments::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(comments.0.len(), 1);
+        assert_eq!(comments.0[0].id, 0);
+        assert_eq!(comments.0[0].author, "Jane Doe");
+        assert_eq!(comments.0[0].initials.as_deref(), Some("JD"));
+        assert_eq!(comments.0[0].block_level_elements.len(), 1);
+    }
+
+    #[test]
+    pub fn test_comments_ex_from_xml() {
+        let xml = r#"<w15:commentsEx>
+            <w15:commentEx w15:paraId="00000001" w15:done="0" />
+            <w15:commentEx w15:paraId="0000000A" w15:paraIdParent="00000001" w15:done="1" />
+        </w15:commentsEx>"#;
+
+        assert_eq!(
+            CommentsEx::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap(),
+            CommentsEx(vec![
+                CommentEx {
+                    paragraph_id: 0x1,
+                    parent_paragraph_id: None,
+                    done: Some(false),
+                },
+                CommentEx {
+                    paragraph_id: 0xa,
+                    parent_paragraph_id: Some(0x1),
+                    done: Some(true),
+                },
+            ])
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use super::{
     drawing::{Anchor, Inline},
+    extensions::RunPropertyExtension,
     simpletypes::{
         parse_on_off_xml_element, parse_text_scale_percent, DateTime, DecimalNumber, EightPointMeasure, FFHelpTextVal,
         FFName, FFStatusTextVal, LongHexNumber, MacroName, PointMeasure, ShortHexNumber, TextScale, UcharHexNumber,
@@ -14,15 +15,18 @@ use crate::{
         ParseHexColorError,
     },
     shared::{
-        drawingml::simpletypes::{parse_hex_color_rgb, HexColorRGB},
+        drawingml::simpletypes::{parse_hex_color_rgb, HexColorRGB, SchemeColorVal},
         relationship::RelationshipId,
         sharedtypes::{
-            CalendarType, ConformanceClass, Lang, OnOff, Percentage, PositiveUniversalMeasure, TwipsMeasure,
-            UniversalMeasure, VerticalAlignRun, XAlign, XmlName, YAlign,
+            CalendarType, ConformanceClass, Lang, Length, Measure, OnOff, Percentage, PositiveUniversalMeasure,
+            TwipsMeasure, UniversalMeasure, VerticalAlignRun, XAlign, XmlName, YAlign,
         },
+        vml,
     },
     update::{update_options, Update},
-    xml::{parse_xml_bool, XmlNode},
+    xml::{
+        is_strict_namespace, parse_xml_bool, XmlExtras, XmlNode, RELATIONSHIPS_NAMESPACE, WORDPROCESSINGML_NAMESPACE,
+    },
     xsdtypes::{XsdChoice, XsdType},
 };
 use log::info;
@@ -116,6 +120,30 @@ pub enum ThemeColor {
     Text2,
 }
 
+impl ThemeColor {
+    pub fn to_scheme_color_val(self) -> Option<SchemeColorVal> {
+        match self {
+            ThemeColor::Dark1 => Some(SchemeColorVal::Dark1),
+            ThemeColor::Light1 => Some(SchemeColorVal::Light1),
+            ThemeColor::Dark2 => Some(SchemeColorVal::Dark2),
+            ThemeColor::Light2 => Some(SchemeColorVal::Light2),
+            ThemeColor::Accent1 => Some(SchemeColorVal::Accent1),
+            ThemeColor::Accent2 => Some(SchemeColorVal::Accent2),
+            ThemeColor::Accent3 => Some(SchemeColorVal::Accent3),
+            ThemeColor::Accent4 => Some(SchemeColorVal::Accent4),
+            ThemeColor::Accent5 => Some(SchemeColorVal::Accent5),
+            ThemeColor::Accent6 => Some(SchemeColorVal::Accent6),
+            ThemeColor::Hyperlink => Some(SchemeColorVal::Hyperlink),
+            ThemeColor::FollowedHyperlink => Some(SchemeColorVal::FollowedHyperlink),
+            ThemeColor::None => None,
+            ThemeColor::Background1 => Some(SchemeColorVal::Background1),
+            ThemeColor::Text1 => Some(SchemeColorVal::Text1),
+            ThemeColor::Background2 => Some(SchemeColorVal::Background2),
+            ThemeColor::Text2 => Some(SchemeColorVal::Text2),
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, EnumString, PartialEq)]
 pub enum HighlightColor {
@@ -222,6 +250,15 @@ impl SignedTwipsMeasure {
     }
 }
 
+impl Measure for SignedTwipsMeasure {
+    fn to_length(&self) -> Length {
+        match self {
+            SignedTwipsMeasure::Decimal(value) => Length::from_twips(f64::from(*value)),
+            SignedTwipsMeasure::UniversalMeasure(measure) => measure.to_length(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum HpsMeasure {
     Decimal(u64),
@@ -246,6 +283,16 @@ impl HpsMeasure {
     }
 }
 
+impl Measure for HpsMeasure {
+    /// Half points are converted to points, so e.g. a font size of `24` is 12 points long.
+    fn to_length(&self) -> Length {
+        match self {
+            HpsMeasure::Decimal(value) => Length::from_points(*value as f64 / 2.0),
+            HpsMeasure::UniversalMeasure(measure) => measure.to_length(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SignedHpsMeasure {
     Decimal(i32),
@@ -272,6 +319,16 @@ impl SignedHpsMeasure {
     }
 }
 
+impl Measure for SignedHpsMeasure {
+    /// Half points are converted to points, so e.g. a font size of `24` is 12 points long.
+    fn to_length(&self) -> Length {
+        match self {
+            SignedHpsMeasure::Decimal(value) => Length::from_points(f64::from(*value) / 2.0),
+            SignedHpsMeasure::UniversalMeasure(measure) => measure.to_length(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Color {
     pub value: HexColor,
@@ -585,7 +642,7 @@ impl TrackChange {
             .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "author"))?
             .clone();
 
-        let date = xml_node.attributes.get("w:date").cloned();
+        let date = xml_node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "date").cloned();
 
         Ok(Self { base, author, date })
     }
@@ -984,7 +1041,7 @@ impl Update for Fonts {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
 pub enum UnderlineType {
     #[strum(serialize = "single")]
     Single,
@@ -1875,12 +1932,18 @@ pub enum RPrBase {
     EastAsianLayout(EastAsianLayout),
     SpecialVanish(OnOff),
     OMath(OnOff),
+    /// A run property of the Word 2010 extensions, e.g. a text effect of WordArt.
+    Extension(RunPropertyExtension),
 }
 
 impl XsdType for RPrBase {
     fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing RPrBase");
 
+        if RunPropertyExtension::is_extension(xml_node) {
+            return Ok(RPrBase::Extension(RunPropertyExtension::from_xml_element(xml_node)?));
+        }
+
         match xml_node.local_name() {
             "rStyle" => Ok(RPrBase::RunStyle(xml_node.get_val_attribute()?.clone())),
             "rFonts" => Ok(RPrBase::RunFonts(Fonts::from_xml_element(xml_node)?)),
@@ -1942,7 +2005,7 @@ impl XsdChoice for RPrBase {
             | "color" | "spacing" | "w" | "kern" | "position" | "sz" | "szCs" | "highlight" | "u" | "effect"
             | "bdr" | "shd" | "fitText" | "vertAlign" | "rtl" | "cs" | "em" | "lang" | "eastAsianLayout"
             | "specVanish" | "oMath" => true,
-            _ => false,
+            node_name => RunPropertyExtension::is_choice_member(node_name),
         }
     }
 }
@@ -1992,19 +2055,28 @@ impl RPrChange {
 pub struct RPr {
     pub r_pr_bases: Vec<RPrBase>,
     pub run_properties_change: Option<RPrChange>,
+    /// The attributes and child elements not recognized by the parser.
+    pub extras: XmlExtras,
 }
 
 impl RPr {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing RPr");
 
-        let mut instance: RPr = Default::default();
+        let mut instance = RPr {
+            r_pr_bases: Vec::with_capacity(xml_node.child_nodes.len()),
+            ..Default::default()
+        };
+        instance.extras.attributes = xml_node.attributes.clone();
+        instance.extras.attribute_namespaces = xml_node.attribute_namespaces.clone();
         for child_node in &xml_node.child_nodes {
             let child_node_name = child_node.local_name();
             if RPrBase::is_choice_member(child_node_name) {
                 instance.r_pr_bases.push(RPrBase::from_xml_element(child_node)?);
             } else if child_node_name == "rPrChange" {
                 instance.run_properties_change = Some(RPrChange::from_xml_element(child_node)?);
+            } else {
+                instance.extras.child_nodes.push(child_node.clone());
             }
         }
 
@@ -2047,7 +2119,7 @@ impl SdtComboBox {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing SdtComboBox");
 
-        let last_value = xml_node.attributes.get("w:lastValue").cloned();
+        let last_value = xml_node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "lastValue").cloned();
 
         let list_items = xml_node
             .child_nodes
@@ -2074,7 +2146,10 @@ impl SdtDateMappingType {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Option<Self>> {
         info!("parsing SdtDateMappingType");
 
-        Ok(xml_node.attributes.get("w:val").map(|val| val.parse()).transpose()?)
+        Ok(xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+            .map(|val| val.parse())
+            .transpose()?)
     }
 }
 
@@ -2093,7 +2168,7 @@ impl SdtDate {
         info!("parsing SdtDate");
 
         let mut instance: Self = Default::default();
-        instance.full_date = xml_node.attributes.get("w:fullDate").cloned();
+        instance.full_date = xml_node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "fullDate").cloned();
 
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
@@ -2103,7 +2178,10 @@ impl SdtDate {
                     instance.store_mapped_data_as = SdtDateMappingType::from_xml_element(child_node)?
                 }
                 "calendar" => {
-                    instance.calendar = child_node.attributes.get("w:val").map(|val| val.parse()).transpose()?;
+                    instance.calendar = child_node
+                        .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+                        .map(|val| val.parse())
+                        .transpose()?;
                 }
                 _ => (),
             }
@@ -2149,7 +2227,7 @@ impl SdtDropDownList {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing SdtDropDownList");
 
-        let last_value = xml_node.attributes.get("w:lastValue").cloned();
+        let last_value = xml_node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "lastValue").cloned();
 
         let list_items = xml_node
             .child_nodes
@@ -2244,7 +2322,10 @@ impl Lock {
     pub fn from_xml_element(xml_node: &XmlNode) -> std::result::Result<Option<Self>, strum::ParseError> {
         info!("parsing Lock");
 
-        xml_node.attributes.get("w:val").map(|val| val.parse()).transpose()
+        xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+            .map(|val| val.parse())
+            .transpose()
     }
 }
 
@@ -2333,7 +2414,12 @@ impl SdtPr {
                 "alias" => instance.alias = Some(child_node.get_val_attribute()?.clone()),
                 "tag" => instance.tag = Some(child_node.get_val_attribute()?.clone()),
                 "id" => instance.id = Some(child_node.get_val_attribute()?.parse()?),
-                "lock" => instance.lock = child_node.attributes.get("w:val").map(|val| val.parse()).transpose()?,
+                "lock" => {
+                    instance.lock = child_node
+                        .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+                        .map(|val| val.parse())
+                        .transpose()?
+                }
                 "placeholder" => instance.placeholder = Some(Placeholder::from_xml_element(child_node)?),
                 "temporary" => instance.temporary = Some(parse_on_off_xml_element(child_node)?),
                 "showingPlcHdr" => instance.showing_placeholder_header = Some(parse_on_off_xml_element(child_node)?),
@@ -2434,7 +2520,10 @@ impl DirContentRun {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing DirContentRun");
 
-        let value = xml_node.attributes.get("w:val").map(|val| val.parse()).transpose()?;
+        let value = xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+            .map(|val| val.parse())
+            .transpose()?;
 
         let p_contents = xml_node
             .child_nodes
@@ -2456,7 +2545,10 @@ impl BdoContentRun {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing BdoContentRun");
 
-        let value = xml_node.attributes.get("w:val").map(|val| val.parse()).transpose()?;
+        let value = xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
+            .map(|val| val.parse())
+            .transpose()?;
 
         let p_contents = xml_node
             .child_nodes
@@ -2468,13 +2560,13 @@ impl BdoContentRun {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
 pub enum BrType {
     #[strum(serialize = "page")]
     Page,
     #[strum(serialize = "column")]
     Column,
-    #[strum(serialzie = "textWrapping")]
+    #[strum(serialize = "textWrapping")]
     TextWrapping,
 }
 
@@ -2704,6 +2796,102 @@ impl ObjectChoice {
             ))),
         }
     }
+
+    /// Returns the metadata of the embedded object. Size and preview information are only available through
+    /// `Object::embedded_object_info`.
+    pub fn info(&self) -> EmbeddedObjectInfo {
+        match self {
+            ObjectChoice::Control(control) => EmbeddedObjectInfo {
+                name: control.name.clone(),
+                rel_id: control.rel_id.clone(),
+                shape_id: control.shapeid.clone(),
+                ..EmbeddedObjectInfo::new(EmbeddedObjectKind::Control)
+            },
+            ObjectChoice::ObjectLink(link) => EmbeddedObjectInfo {
+                update_mode: Some(link.update_mode),
+                ..EmbeddedObjectInfo::from_object_embed(EmbeddedObjectKind::Link, &link.base)
+            },
+            ObjectChoice::ObjectEmbed(embed) => EmbeddedObjectInfo::from_object_embed(EmbeddedObjectKind::Embed, embed),
+            ObjectChoice::Movie(movie) => EmbeddedObjectInfo {
+                rel_id: Some(movie.rel_id.clone()),
+                ..EmbeddedObjectInfo::new(EmbeddedObjectKind::Movie)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmbeddedObjectKind {
+    Control,
+    Link,
+    Embed,
+    Movie,
+}
+
+/// A unified view of the metadata of controls, linked and embedded OLE objects and movies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddedObjectInfo {
+    pub kind: EmbeddedObjectKind,
+    /// The name of an ActiveX control.
+    pub name: Option<String>,
+    /// The programmatic identifier of the OLE server application (`w:progId`), e.g. `Excel.Sheet.12`.
+    pub prog_id: Option<String>,
+    /// The relationship id of the object's data, the linked file or the movie.
+    pub rel_id: Option<RelationshipId>,
+    /// The id of the VML shape that presents the object.
+    pub shape_id: Option<String>,
+    /// The relationship id of the preview image of the presenting VML shape.
+    pub preview_image_rel_id: Option<RelationshipId>,
+    pub draw_aspect: Option<ObjectDrawAspect>,
+    pub update_mode: Option<ObjectUpdateMode>,
+    /// The original width of the object's presentation (`w:dxaOrig`).
+    pub original_width: Option<TwipsMeasure>,
+    /// The original height of the object's presentation (`w:dyaOrig`).
+    pub original_height: Option<TwipsMeasure>,
+}
+
+impl EmbeddedObjectInfo {
+    pub fn new(kind: EmbeddedObjectKind) -> Self {
+        Self {
+            kind,
+            name: None,
+            prog_id: None,
+            rel_id: None,
+            shape_id: None,
+            preview_image_rel_id: None,
+            draw_aspect: None,
+            update_mode: None,
+            original_width: None,
+            original_height: None,
+        }
+    }
+
+    fn from_object_embed(kind: EmbeddedObjectKind, embed: &ObjectEmbed) -> Self {
+        Self {
+            prog_id: embed.application_id.clone(),
+            rel_id: Some(embed.rel_id.clone()),
+            shape_id: embed.shape_id.clone(),
+            draw_aspect: embed.draw_aspect,
+            ..Self::new(kind)
+        }
+    }
+
+    fn with_preview_from_shapes(mut self, shapes: &[vml::Shape]) -> Self {
+        let shape_id = self.shape_id.clone();
+        let presenting_shape = shapes
+            .iter()
+            .find(|shape| shape_id.is_some() && shape.id == shape_id)
+            .or_else(|| shapes.iter().find(|shape| shape.kind != vml::ShapeKind::ShapeType));
+
+        if let Some(shape) = presenting_shape {
+            self.preview_image_rel_id = shape.images().find_map(|image_data| image_data.image_rel_id().cloned());
+            if self.shape_id.is_none() {
+                self.shape_id = shape.id.clone();
+            }
+        }
+
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2754,6 +2942,8 @@ impl Drawing {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Object {
     pub drawing: Option<Drawing>,
+    /// The VML shapes presenting the object, e.g. its preview image.
+    pub shapes: Vec<vml::Shape>,
     pub choice: Option<ObjectChoice>,
     pub original_image_width: Option<TwipsMeasure>,
     pub original_image_height: Option<TwipsMeasure>,
@@ -2779,12 +2969,71 @@ impl Object {
                 node_name if ObjectChoice::is_choice_member(node_name) => {
                     instance.choice = Some(ObjectChoice::from_xml_element(child_node)?)
                 }
+                node_name if vml::ShapeKind::is_choice_member(node_name) => {
+                    instance.shapes.push(vml::Shape::from_xml_element(child_node)?)
+                }
                 _ => (),
             }
         }
 
         Ok(instance)
     }
+
+    /// Returns the metadata of the embedded object including its original size and preview image.
+    pub fn embedded_object_info(&self) -> Option<EmbeddedObjectInfo> {
+        let info = EmbeddedObjectInfo {
+            original_width: self.original_image_width,
+            original_height: self.original_image_height,
+            ..self.choice.as_ref()?.info()
+        };
+
+        Some(info.with_preview_from_shapes(&self.shapes))
+    }
+}
+
+/// A legacy picture (`w:pict`) hosting VML shapes, a movie or an ActiveX control.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Picture {
+    pub shapes: Vec<vml::Shape>,
+    pub movie: Option<Rel>,
+    pub control: Option<Control>,
+}
+
+impl Picture {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing Picture");
+
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "movie" => instance.movie = Some(Rel::from_xml_element(child_node)?),
+                "control" => instance.control = Some(Control::from_xml_element(child_node)),
+                node_name if vml::ShapeKind::is_choice_member(node_name) => {
+                    instance.shapes.push(vml::Shape::from_xml_element(child_node)?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Iterates over the images referenced by the VML shapes of the picture in document order.
+    pub fn images(&self) -> impl Iterator<Item = &vml::ImageData> {
+        self.shapes.iter().flat_map(|shape| shape.images())
+    }
+
+    /// Returns the metadata of the hosted ActiveX control or movie, if any. The control takes precedence.
+    pub fn embedded_object_info(&self) -> Option<EmbeddedObjectInfo> {
+        let info = match (&self.control, &self.movie) {
+            (Some(control), _) => ObjectChoice::Control(control.clone()).info(),
+            (None, Some(movie)) => ObjectChoice::Movie(movie.clone()).info(),
+            (None, None) => return None,
+        };
+
+        Some(info.with_preview_from_shapes(&self.shapes))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, EnumString)]
@@ -3035,7 +3284,8 @@ pub enum FldCharType {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FldChar {
-    pub form_field_properties: Option<FFData>,
+    /// The properties of a legacy form field, the children of `w:ffData`
+    pub form_field_properties: Vec<FFData>,
     pub field_char_type: FldCharType,
     pub field_lock: Option<OnOff>,
     pub dirty: Option<OnOff>,
@@ -3061,8 +3311,10 @@ impl FldChar {
         let form_field_properties = xml_node
             .child_nodes
             .iter()
-            .find_map(|child_node| FFData::try_from_xml_element(child_node))
-            .transpose()?;
+            .filter(|child_node| child_node.local_name() == "ffData")
+            .flat_map(|child_node| child_node.child_nodes.iter())
+            .filter_map(FFData::try_from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
 
         let field_char_type =
             field_char_type.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "fldCharType"))?;
@@ -3351,6 +3603,7 @@ pub enum RunInnerContent {
     CarriageReturn,
     Tab,
     Object(Object),
+    Picture(Picture),
     FieldCharacter(FldChar),
     Ruby(Ruby),
     FootnoteReference(FtnEdnRef),
@@ -3388,6 +3641,7 @@ impl RunInnerContent {
             | "cr"
             | "tab"
             | "object"
+            | "pict"
             | "fldChar"
             | "ruby"
             | "footnoteReference"
@@ -3430,6 +3684,7 @@ impl RunInnerContent {
             "cr" => Ok(RunInnerContent::CarriageReturn),
             "tab" => Ok(RunInnerContent::Tab),
             "object" => Ok(RunInnerContent::Object(Object::from_xml_element(xml_node)?)),
+            "pict" => Ok(RunInnerContent::Picture(Picture::from_xml_element(xml_node)?)),
             "fldChar" => Ok(RunInnerContent::FieldCharacter(FldChar::from_xml_element(xml_node)?)),
             "ruby" => Ok(RunInnerContent::Ruby(Ruby::from_xml_element(xml_node)?)),
             "footnoteReference" => Ok(RunInnerContent::FootnoteReference(FtnEdnRef::from_xml_element(
@@ -4258,29 +4513,104 @@ impl Ind {
 
         Ok(instance)
     }
+
+    /// Returns the indentation of the leading edge of the paragraph, which is the left edge of left-to-right
+    /// paragraphs. `w:start` takes precedence over the deprecated `w:left`, which Word interprets as the leading edge
+    /// as well.
+    pub fn effective_start_indent(&self) -> Option<Length> {
+        self.start.or(self.left).map(|start| start.to_length())
+    }
+
+    /// Returns the indentation of the trailing edge of the paragraph. `w:end` takes precedence over the deprecated
+    /// `w:right`.
+    pub fn effective_end_indent(&self) -> Option<Length> {
+        self.end.or(self.right).map(|end| end.to_length())
+    }
+
+    /// Returns the indentation of the leading edge in hundredths of a character unit.
+    pub fn effective_start_chars(&self) -> Option<DecimalNumber> {
+        self.start_chars.or(self.left_chars)
+    }
+
+    /// Returns the indentation of the trailing edge in hundredths of a character unit.
+    pub fn effective_end_chars(&self) -> Option<DecimalNumber> {
+        self.end_chars.or(self.right_chars)
+    }
+
+    /// Returns the indentation of the left edge, which is the trailing edge of right-to-left (`w:bidi`) paragraphs.
+    pub fn effective_left_indent(&self, bidirectional: bool) -> Option<Length> {
+        if bidirectional {
+            self.effective_end_indent()
+        } else {
+            self.effective_start_indent()
+        }
+    }
+
+    /// Returns the indentation of the right edge, which is the leading edge of right-to-left (`w:bidi`) paragraphs.
+    pub fn effective_right_indent(&self, bidirectional: bool) -> Option<Length> {
+        if bidirectional {
+            self.effective_start_indent()
+        } else {
+            self.effective_end_indent()
+        }
+    }
+
+    /// Returns the indentation of the first line relative to the leading edge. Hanging indentation is negative and
+    /// takes precedence over `w:firstLine` if both are specified.
+    pub fn effective_first_line_indent(&self) -> Option<Length> {
+        match (self.hanging, self.first_line) {
+            (Some(hanging), _) => Some(-hanging.to_length()),
+            (None, Some(first_line)) => Some(first_line.to_length()),
+            (None, None) => None,
+        }
+    }
 }
 
 impl Update for Ind {
     fn update_with(self, other: Self) -> Self {
+        // An edge specified by either the current or the deprecated attribute overrides both attributes of the edge,
+        // so e.g. `w:left` of the direct formatting isn't shadowed by `w:start` of the style. The first line and the
+        // hanging indentation exclude each other the same way.
+        let (start, left) = override_pair((self.start, self.left), (other.start, other.left));
+        let (start_chars, left_chars) = override_pair(
+            (self.start_chars, self.left_chars),
+            (other.start_chars, other.left_chars),
+        );
+        let (end, right) = override_pair((self.end, self.right), (other.end, other.right));
+        let (end_chars, right_chars) =
+            override_pair((self.end_chars, self.right_chars), (other.end_chars, other.right_chars));
+        let (hanging, first_line) = override_pair((self.hanging, self.first_line), (other.hanging, other.first_line));
+        let (hanging_chars, first_line_chars) = override_pair(
+            (self.hanging_chars, self.first_line_chars),
+            (other.hanging_chars, other.first_line_chars),
+        );
+
         Self {
-            start: other.start.or(self.start),
-            start_chars: other.start_chars.or(self.start_chars),
-            end: other.end.or(self.end),
-            end_chars: other.end_chars.or(self.end_chars),
-            left: other.left.or(self.left),
-            left_chars: other.left_chars.or(self.left_chars),
-            right: other.right.or(self.right),
-            right_chars: other.right_chars.or(self.right_chars),
-            hanging: other.hanging.or(self.hanging),
-            hanging_chars: other.hanging_chars.or(self.hanging_chars),
-            first_line: other.first_line.or(self.first_line),
-            first_line_chars: other.first_line_chars.or(self.first_line_chars),
+            start,
+            start_chars,
+            end,
+            end_chars,
+            left,
+            left_chars,
+            right,
+            right_chars,
+            hanging,
+            hanging_chars,
+            first_line,
+            first_line_chars,
         }
     }
 }
 
+fn override_pair<A, B>(pair: (Option<A>, Option<B>), other: (Option<A>, Option<B>)) -> (Option<A>, Option<B>) {
+    match other {
+        (None, None) => pair,
+        other => other,
+    }
+}
+
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
 pub enum Jc {
     #[strum(serialize = "start")]
     Start,
@@ -4468,13 +4798,62 @@ impl PPrBase {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing PPrBase");
 
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), Self::try_update_from_xml_element)
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            instance.update_from_xml_element(child_node)?;
+        }
+
+        Ok(instance)
+    }
+
+    pub fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool {
+        matches!(
+            node_name.as_ref(),
+            "pStyle"
+                | "keepNext"
+                | "keepLines"
+                | "pageBreakBefore"
+                | "framePr"
+                | "widowControl"
+                | "numPr"
+                | "suppressLineNumbers"
+                | "pBdr"
+                | "shd"
+                | "tabs"
+                | "suppressAutoHyphens"
+                | "kinsoku"
+                | "wordWrap"
+                | "overflowPunct"
+                | "topLinePunct"
+                | "autoSpaceDE"
+                | "autoSpaceDN"
+                | "bidi"
+                | "adjustRightInd"
+                | "snapToGrid"
+                | "spacing"
+                | "ind"
+                | "contextualSpacing"
+                | "mirrorIndents"
+                | "suppressOverlap"
+                | "jc"
+                | "textDirection"
+                | "textAlignment"
+                | "textboxTightWrap"
+                | "outlineLvl"
+                | "divId"
+                | "cnfStyle"
+        )
     }
 
     pub fn try_update_from_xml_element(mut self, xml_node: &XmlNode) -> Result<Self> {
+        self.update_from_xml_element(xml_node)?;
+        Ok(self)
+    }
+
+    /// Updates the property parsed from a child element of a `pPr` in place. Prefer this to
+    /// [`PPrBase::try_update_from_xml_element`] in loops, since `PPrBase` is large and moving it for every child element
+    /// is a noticeable part of parsing paragraph properties.
+    pub fn update_from_xml_element(&mut self, xml_node: &XmlNode) -> Result<()> {
         match xml_node.local_name() {
             "pStyle" => self.style = Some(xml_node.get_val_attribute()?.clone()),
             "keepNext" => self.keep_with_next = Some(parse_on_off_xml_element(xml_node)?),
@@ -4512,7 +4891,18 @@ impl PPrBase {
             _ => (),
         }
 
-        Ok(self)
+        Ok(())
+    }
+
+    /// Returns the indentation of the left edge of the paragraph, taking the direction of the paragraph into account.
+    pub fn effective_left_indent(&self) -> Option<Length> {
+        self.indent?.effective_left_indent(self.bidirectional.unwrap_or(false))
+    }
+
+    /// Returns the indentation of the right edge of the paragraph, taking the direction of the paragraph into
+    /// account.
+    pub fn effective_right_indent(&self) -> Option<Length> {
+        self.indent?.effective_right_indent(self.bidirectional.unwrap_or(false))
     }
 }
 
@@ -4570,17 +4960,15 @@ impl PPrGeneral {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("Parsing PPrGeneral");
 
-        xml_node
-            .child_nodes
-            .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
-                match child_node.local_name() {
-                    "pPrChange" => instance.change = Some(PPrChange::from_xml_element(child_node)?),
-                    _ => instance.base = instance.base.try_update_from_xml_element(child_node)?,
-                }
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "pPrChange" => instance.change = Some(PPrChange::from_xml_element(child_node)?),
+                _ => instance.base.update_from_xml_element(child_node)?,
+            }
+        }
 
-                Ok(instance)
-            })
+        Ok(instance)
     }
 }
 
@@ -4655,8 +5043,8 @@ impl ParaRPrOriginal {
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ParaRPrChange {
-    base: TrackChange,
-    run_properties: ParaRPrOriginal,
+    pub base: TrackChange,
+    pub run_properties: ParaRPrOriginal,
 }
 
 impl ParaRPrChange {
@@ -5064,7 +5452,7 @@ pub enum SectionMark {
     OddPage,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
 pub enum PageOrientation {
     #[strum(serialize = "portrait")]
     Portrait,
@@ -5183,7 +5571,10 @@ impl PageBorder {
         info!("parsing PageBorder");
 
         let base = Border::from_xml_element(xml_node)?;
-        let rel_id = xml_node.attributes.get("r:id").map(|value| value.parse()).transpose()?;
+        let rel_id = xml_node
+            .attribute_ns(RELATIONSHIPS_NAMESPACE, "id")
+            .map(|value| value.parse())
+            .transpose()?;
 
         Ok(Self { base, rel_id })
     }
@@ -5201,8 +5592,8 @@ impl TopPageBorder {
         info!("parsing TopPageBorder");
 
         let base = PageBorder::from_xml_element(xml_node)?;
-        let top_left = xml_node.attributes.get("r:topLeft").cloned();
-        let top_right = xml_node.attributes.get("r:topRight").cloned();
+        let top_left = xml_node.attribute_ns(RELATIONSHIPS_NAMESPACE, "topLeft").cloned();
+        let top_right = xml_node.attribute_ns(RELATIONSHIPS_NAMESPACE, "topRight").cloned();
 
         Ok(Self {
             base,
@@ -5224,8 +5615,8 @@ impl BottomPageBorder {
         info!("parsing BottomPageBorder");
 
         let base = PageBorder::from_xml_element(xml_node)?;
-        let bottom_left = xml_node.attributes.get("r:bottomLeft").cloned();
-        let bottom_right = xml_node.attributes.get("r:bottomRight").cloned();
+        let bottom_left = xml_node.attribute_ns(RELATIONSHIPS_NAMESPACE, "bottomLeft").cloned();
+        let bottom_right = xml_node.attribute_ns(RELATIONSHIPS_NAMESPACE, "bottomRight").cloned();
 
         Ok(Self {
             base,
@@ -5649,6 +6040,10 @@ pub struct SectPrAttributes {
 }
 
 impl SectPrAttributes {
+    pub fn is_attribute(name: &str) -> bool {
+        matches!(name, "w:rsidRPr" | "w:rsidDel" | "w:rsidR" | "w:rsidSect")
+    }
+
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing SectPrAttributes");
 
@@ -5716,6 +6111,9 @@ pub struct SectPr {
     pub contents: Option<SectPrContents>,
     pub change: Option<SectPrChange>,
     pub attributes: SectPrAttributes,
+    /// The attributes and child elements not recognized by the parser, including the unknown elements between the
+    /// section properties of the contents.
+    pub extras: XmlExtras,
 }
 
 impl SectPr {
@@ -5725,6 +6123,13 @@ impl SectPr {
         let mut instance: Self = Default::default();
 
         instance.attributes = SectPrAttributes::from_xml_element(xml_node)?;
+        instance.extras.attributes = xml_node
+            .attributes
+            .iter()
+            .filter(|(attr, _)| !SectPrAttributes::is_attribute(attr))
+            .map(|(attr, value)| (attr.clone(), value.clone()))
+            .collect();
+        instance.extras.attribute_namespaces = xml_node.attribute_namespaces.clone();
 
         for child_node in &xml_node.child_nodes {
             if let Some(result) = HdrFtrReferences::try_from_xml_element(child_node) {
@@ -5738,6 +6143,8 @@ impl SectPr {
 
             if child_node.local_name() == "sectPrChange" {
                 instance.change = Some(SectPrChange::from_xml_element(child_node)?);
+            } else {
+                instance.extras.child_nodes.push(child_node.clone());
             }
         }
 
@@ -5782,6 +6189,8 @@ pub struct PPr {
     pub run_properties: Option<ParaRPr>,
     pub section_properties: Option<SectPr>,
     pub properties_change: Option<PPrChange>,
+    /// The attributes and child elements not recognized by the parser.
+    pub extras: XmlExtras,
 }
 
 impl PPr {
@@ -5789,13 +6198,18 @@ impl PPr {
         info!("parsing PPr");
 
         let mut instance: Self = Default::default();
+        instance.extras.attributes = xml_node.attributes.clone();
+        instance.extras.attribute_namespaces = xml_node.attribute_namespaces.clone();
 
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
                 "rPr" => instance.run_properties = Some(ParaRPr::from_xml_element(child_node)?),
                 "sectPr" => instance.section_properties = Some(SectPr::from_xml_element(child_node)?),
                 "pPrChange" => instance.properties_change = Some(PPrChange::from_xml_element(child_node)?),
-                _ => instance.base = instance.base.try_update_from_xml_element(child_node)?,
+                node_name if PPrBase::is_choice_member(node_name) => {
+                    instance.base.update_from_xml_element(child_node)?
+                }
+                _ => instance.extras.child_nodes.push(child_node.clone()),
             }
         }
 
@@ -5812,6 +6226,10 @@ pub struct P {
     pub deletion_revision_id: Option<LongHexNumber>,
     pub paragraph_revision_id: Option<LongHexNumber>,
     pub run_default_revision_id: Option<LongHexNumber>,
+    /// The `w14:paraId` used by comment threading and co-authoring to identify the paragraph.
+    pub paragraph_id: Option<LongHexNumber>,
+    /// The `w14:textId` identifying the version of the paragraph's text.
+    pub text_id: Option<LongHexNumber>,
 }
 
 impl P {
@@ -5827,6 +6245,8 @@ impl P {
                 "w:rsidDel" => instance.deletion_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 "w:rsidP" => instance.paragraph_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 "w:rsidRDefault" => instance.run_default_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w14:paraId" => instance.paragraph_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w14:textId" => instance.text_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 _ => (),
             }
         }
@@ -5935,7 +6355,7 @@ impl AltChunk {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing AltChunk");
 
-        let rel_id = xml_node.attributes.get("r:id").cloned();
+        let rel_id = xml_node.attribute_ns(RELATIONSHIPS_NAMESPACE, "id").cloned();
 
         let properties = xml_node
             .child_nodes
@@ -6045,22 +6465,33 @@ impl DocumentBase {
 pub struct Body {
     pub block_level_elements: Vec<BlockLevelElts>,
     pub section_properties: Option<SectPr>,
+    /// The attributes and child elements not recognized by the parser.
+    pub extras: XmlExtras,
 }
 
 impl Body {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing Body");
 
+        let instance = Self {
+            extras: XmlExtras {
+                attributes: xml_node.attributes.clone(),
+                attribute_namespaces: xml_node.attribute_namespaces.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
         xml_node
             .child_nodes
             .iter()
-            .try_fold(Default::default(), |mut instance: Self, child_node| {
+            .try_fold(instance, |mut instance: Self, child_node| {
                 match child_node.local_name() {
                     "sectPr" => instance.section_properties = Some(SectPr::from_xml_element(child_node)?),
                     node_name if BlockLevelElts::is_choice_member(node_name) => instance
                         .block_level_elements
                         .push(BlockLevelElts::from_xml_element(child_node)?),
-                    _ => (),
+                    _ => instance.extras.child_nodes.push(child_node.clone()),
                 }
 
                 Ok(instance)
@@ -6072,6 +6503,8 @@ impl Body {
 pub struct Document {
     pub base: DocumentBase,
     pub body: Option<Body>,
+    /// The `w:conformance` attribute of the document. Documents using the strict namespace without the attribute
+    /// are strict as well.
     pub conformance: Option<ConformanceClass>,
 }
 
@@ -6082,10 +6515,14 @@ impl Document {
         let mut instance: Self = Default::default();
 
         instance.conformance = xml_node
-            .attributes
-            .get("w:conformance")
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "conformance")
             .map(|value| value.parse())
             .transpose()?;
+        if instance.conformance.is_none()
+            && matches!(&xml_node.namespace, Some(namespace) if is_strict_namespace(namespace))
+        {
+            instance.conformance = Some(ConformanceClass::Strict);
+        }
 
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
@@ -6112,6 +6549,42 @@ mod tests {
         assert_eq!(parse_text_scale_percent("0%").unwrap(), 0.0);
     }
 
+    #[test]
+    pub fn test_parse_non_standard_prefixes() {
+        let xml = r#"<word:p xmlns:word="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <word:pPr><word:pStyle word:val="Heading1" /><word:jc word:val="center" /></word:pPr>
+            <word:r><word:t>Title</word:t></word:r>
+        </word:p>"#;
+
+        let paragraph = P::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let properties = paragraph.properties.unwrap();
+        assert_eq!(properties.base.style.as_deref(), Some("Heading1"));
+        assert_eq!(properties.base.alignment, Some(Jc::Center));
+        assert_eq!(paragraph.contents.len(), 1);
+    }
+
+    #[test]
+    pub fn test_parse_strict_document() {
+        let xml = r#"<w:document xmlns:w="http://purl.oclc.org/ooxml/wordprocessingml/main"><w:body>
+            <w:p><w:pPr><w:ind w:start="720" /><w:jc w:val="start" /></w:pPr></w:p>
+        </w:body></w:document>"#;
+
+        let document = Document::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(document.conformance, Some(ConformanceClass::Strict));
+
+        let transitional =
+            Document::from_xml_element(&XmlNode::from_str("<w:document></w:document>").unwrap()).unwrap();
+        assert_eq!(transitional.conformance, None);
+    }
+
+    #[test]
+    pub fn test_measure_to_length() {
+        assert_eq!(HpsMeasure::Decimal(24).to_points(), 12.0);
+        assert_eq!(SignedHpsMeasure::Decimal(-6).to_twips(), -60.0);
+        assert_eq!(SignedTwipsMeasure::Decimal(-1440).to_mm(), -25.4);
+        assert_eq!("1in".parse::<HpsMeasure>().unwrap().to_points(), 72.0);
+    }
+
     impl SignedTwipsMeasure {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
@@ -7023,6 +7496,7 @@ mod tests {
             Self {
                 r_pr_bases: vec![RPrBase::test_run_style_instance()],
                 run_properties_change: Some(RPrChange::test_instance()),
+                extras: Default::default(),
             }
         }
     }
@@ -7706,6 +8180,7 @@ mod tests {
         pub fn test_instance() -> Self {
             Self {
                 drawing: Some(Drawing::test_instance()),
+                shapes: Vec::new(),
                 choice: Some(ObjectChoice::Control(Control::test_instance())),
                 original_image_width: Some(TwipsMeasure::UniversalMeasure(UniversalMeasure::new(
                     123.456,
@@ -7725,6 +8200,68 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_object_embedded_object_info() {
+        let xml = r##"<w:object w:dxaOrig="1440" w:dyaOrig="720">
+            <v:shapetype id="_x0000_t75" />
+            <v:shape id="_x0000_i1025" type="#_x0000_t75" style="width:72pt;height:36pt">
+                <v:imagedata r:id="rId7" o:title="" />
+            </v:shape>
+            <w:objectLink w:progId="Excel.Sheet.12" w:shapeId="_x0000_i1025" r:id="rId8" w:updateMode="onCall" />
+        </w:object>"##;
+
+        let object = Object::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(
+            object.embedded_object_info(),
+            Some(EmbeddedObjectInfo {
+                kind: EmbeddedObjectKind::Link,
+                name: None,
+                prog_id: Some(String::from("Excel.Sheet.12")),
+                rel_id: Some(String::from("rId8")),
+                shape_id: Some(String::from("_x0000_i1025")),
+                preview_image_rel_id: Some(String::from("rId7")),
+                draw_aspect: None,
+                update_mode: Some(ObjectUpdateMode::OnCall),
+                original_width: Some(TwipsMeasure::Decimal(1440)),
+                original_height: Some(TwipsMeasure::Decimal(720)),
+            })
+        );
+
+        let movie_info = ObjectChoice::Movie(Rel::test_instance()).info();
+        assert_eq!(movie_info.kind, EmbeddedObjectKind::Movie);
+        assert_eq!(movie_info.rel_id, Some(Rel::test_instance().rel_id));
+        assert_eq!(Object::default().embedded_object_info(), None);
+    }
+
+    #[test]
+    pub fn test_picture_from_xml() {
+        let xml = format!(
+            r##"<w:pict>
+                <v:shape id="Picture 1" type="#_x0000_t75" style="width:100pt;height:50pt">
+                    <v:imagedata r:id="rId5" o:title="" />
+                </v:shape>
+                {}
+            </w:pict>"##,
+            Control::test_xml("w:control")
+        );
+
+        let picture = Picture::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap();
+        assert_eq!(picture.shapes.len(), 1);
+        assert_eq!(picture.shapes[0].kind, vml::ShapeKind::Shape);
+        assert_eq!(picture.control, Some(Control::test_instance()));
+        assert_eq!(picture.movie, None);
+
+        let image_rel_ids: Vec<&str> = picture
+            .images()
+            .filter_map(|image_data| image_data.image_rel_id())
+            .map(String::as_str)
+            .collect();
+        assert_eq!(image_rel_ids, vec!["rId5"]);
+
+        let run_inner_content = RunInnerContent::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap();
+        assert_eq!(run_inner_content, RunInnerContent::Picture(picture));
+    }
+
     impl FFHelpText {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
@@ -7885,7 +8422,7 @@ mod tests {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
                 r#"<{node_name} w:fldCharType="begin" w:fldLock="false" w:dirty="false">
-                <name w:val="Some name" />
+                <ffData><name w:val="Some name" /></ffData>
             </{node_name}>"#,
                 node_name = node_name,
             )
@@ -7893,7 +8430,7 @@ mod tests {
 
         pub fn test_instance() -> Self {
             Self {
-                form_field_properties: Some(FFData::Name(FFName::from("Some name"))),
+                form_field_properties: vec![FFData::Name(FFName::from("Some name"))],
                 field_char_type: FldCharType::Begin,
                 field_lock: Some(false),
                 dirty: Some(false),
@@ -8461,6 +8998,49 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_ind_effective_indents() {
+        let xml = r#"<w:ind w:left="720" w:right="360" w:firstLine="240"></w:ind>"#;
+        let indent = Ind::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(indent.effective_start_indent(), Some(Length::from_twips(720.0)));
+        assert_eq!(indent.effective_end_indent(), Some(Length::from_twips(360.0)));
+        assert_eq!(indent.effective_left_indent(true), Some(Length::from_twips(360.0)));
+        assert_eq!(indent.effective_right_indent(true), Some(Length::from_twips(720.0)));
+        assert_eq!(indent.effective_first_line_indent(), Some(Length::from_twips(240.0)));
+
+        // The current attributes take precedence over the deprecated ones
+        let indent = Ind {
+            start: Some(SignedTwipsMeasure::Decimal(100)),
+            hanging: Some(TwipsMeasure::Decimal(50)),
+            ..indent
+        };
+        assert_eq!(indent.effective_start_indent(), Some(Length::from_twips(100.0)));
+        assert_eq!(indent.effective_first_line_indent(), Some(Length::from_twips(-50.0)));
+
+        // A deprecated attribute of the direct formatting overrides the current attribute of the style
+        let style = Ind {
+            start: Some(SignedTwipsMeasure::Decimal(1440)),
+            hanging: Some(TwipsMeasure::Decimal(360)),
+            ..Default::default()
+        };
+        let direct = Ind {
+            left: Some(SignedTwipsMeasure::Decimal(720)),
+            first_line: Some(TwipsMeasure::Decimal(0)),
+            ..Default::default()
+        };
+        let merged = style.update_with(direct);
+        assert_eq!(merged.effective_start_indent(), Some(Length::from_twips(720.0)));
+        assert_eq!(merged.effective_first_line_indent(), Some(Length::from_twips(0.0)));
+
+        let properties = PPrBase {
+            bidirectional: Some(true),
+            indent: Some(merged),
+            ..Default::default()
+        };
+        assert_eq!(properties.effective_right_indent(), Some(Length::from_twips(720.0)));
+        assert_eq!(properties.effective_left_indent(), None);
+    }
+
     impl Cnf {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
@@ -9427,6 +10007,7 @@ mod tests {
                 contents: Some(SectPrContents::test_instance()),
                 change: Some(SectPrChange::test_instance()),
                 attributes: SectPrAttributes::test_instance(),
+                extras: Default::default(),
             }
         }
     }
@@ -9492,6 +10073,7 @@ mod tests {
                 run_properties: Some(ParaRPr::test_instance()),
                 section_properties: Some(SectPr::test_instance()),
                 properties_change: Some(PPrChange::test_instance()),
+                extras: Default::default(),
             }
         }
     }
@@ -9508,7 +10090,8 @@ mod tests {
     impl P {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
-                r#"<{node_name} w:rsidRPr="ffffffff" w:rsidR="fefefefe" w:rsidDel="fdfdfdfd" w:rsidP="fcfcfcfc" w:rsidRDefault="fbfbfbfb">
+                r#"<{node_name} w:rsidRPr="ffffffff" w:rsidR="fefefefe" w:rsidDel="fdfdfdfd" w:rsidP="fcfcfcfc" w:rsidRDefault="fbfbfbfb"
+                w14:paraId="1A2B3C4D" w14:textId="77777777">
                 {}
                 {}
             </{node_name}>"#,
@@ -9527,6 +10110,8 @@ mod tests {
                 deletion_revision_id: Some(0xfdfdfdfd),
                 paragraph_revision_id: Some(0xfcfcfcfc),
                 run_default_revision_id: Some(0xfbfbfbfb),
+                paragraph_id: Some(0x1a2b3c4d),
+                text_id: Some(0x77777777),
             }
         }
     }
@@ -9724,6 +10309,7 @@ mod tests {
                     P::test_instance(),
                 )))],
                 section_properties: Some(SectPr::test_instance()),
+                extras: Default::default(),
             }
         }
     }
@@ -9737,6 +10323,60 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_unknown_content_kept_as_extras() {
+        let xml = r#"<w:body>
+            <w:p>
+                <w:pPr w15:collapsed='1'>
+                    <w:jc w:val='center' />
+                    <w15:custom w15:val='x'><w15:item /></w15:custom>
+                    <w:rPr><w:b /><w:unknownRunProperty /></w:rPr>
+                </w:pPr>
+            </w:p>
+            <w:unknownBlock>text</w:unknownBlock>
+            <w:sectPr w:rsidR='00AB12CD' w:future='1'>
+                <w:pgSz w:w='11906' w:h='16838' />
+                <w:unknownSectionProperty />
+            </w:sectPr>
+        </w:body>"#;
+        let body = Body::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(body.extras.child_nodes.len(), 1);
+        assert_eq!(&*body.extras.child_nodes[0].name, "w:unknownBlock");
+
+        let properties = match &body.block_level_elements[0] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => paragraph.properties.as_ref().unwrap(),
+            _ => panic!("expected a paragraph"),
+        };
+        assert_eq!(properties.base.alignment, Some(Jc::Center));
+        assert_eq!(
+            properties.extras.attributes.get("w15:collapsed").map(String::as_str),
+            Some("1")
+        );
+        assert_eq!(
+            properties.extras.child_nodes[0].to_xml_string(),
+            "<w15:custom w15:val=\"x\"><w15:item/></w15:custom>"
+        );
+
+        let section_properties = body.section_properties.unwrap();
+        assert_eq!(section_properties.attributes.run_revision_id, Some(0x00AB_12CD));
+        assert_eq!(section_properties.extras.attributes.len(), 1);
+        assert_eq!(
+            &*section_properties.extras.child_nodes[0].name,
+            "w:unknownSectionProperty"
+        );
+    }
+
+    #[test]
+    pub fn test_r_pr_extras_from_xml() {
+        let xml = "<w:rPr><w:b /><w:unknownRunProperty w:val='1' /></w:rPr>";
+        let run_properties = RPr::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(run_properties.r_pr_bases.len(), 1);
+        assert_eq!(
+            run_properties.extras.child_nodes[0].to_xml_string(),
+            "<w:unknownRunProperty w:val=\"1\"/>"
+        );
+    }
+
     impl Document {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
@@ -1163,6 +1163,8 @@ mod tests {
         GraphicalObject {
             graphic_data: GraphicalObjectData {
                 uri: String::from("http://some/url"),
+                pictures: Vec::new(),
+                diagram_relationship_ids: None,
             },
         }
     }
@@ -0,0 +1,812 @@
+use crate::{
+    error::{MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
+    xml::{parse_xml_bool, XmlNode},
+};
+use std::str::FromStr;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// A length in EMUs.
+pub type PositiveCoordinate = u64;
+/// A percentage in 1000th of a percent, e.g. 60000 is 60%.
+pub type Percentage = i32;
+/// An angle in 60000th of a degree.
+pub type Angle = i32;
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum SchemeColorVal {
+    #[strum(serialize = "bg1")]
+    Background1,
+    #[strum(serialize = "tx1")]
+    Text1,
+    #[strum(serialize = "bg2")]
+    Background2,
+    #[strum(serialize = "tx2")]
+    Text2,
+    #[strum(serialize = "accent1")]
+    Accent1,
+    #[strum(serialize = "accent2")]
+    Accent2,
+    #[strum(serialize = "accent3")]
+    Accent3,
+    #[strum(serialize = "accent4")]
+    Accent4,
+    #[strum(serialize = "accent5")]
+    Accent5,
+    #[strum(serialize = "accent6")]
+    Accent6,
+    #[strum(serialize = "hlink")]
+    Hyperlink,
+    #[strum(serialize = "folHlink")]
+    FollowedHyperlink,
+    #[strum(serialize = "dk1")]
+    Dark1,
+    #[strum(serialize = "lt1")]
+    Light1,
+    #[strum(serialize = "dk2")]
+    Dark2,
+    #[strum(serialize = "lt2")]
+    Light2,
+    #[strum(serialize = "phClr")]
+    PlaceholderColor,
+}
+
+/// A transformation applied to the base color of an effect.
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum ColorTransformType {
+    #[strum(serialize = "tint")]
+    Tint,
+    #[strum(serialize = "shade")]
+    Shade,
+    #[strum(serialize = "alpha")]
+    Alpha,
+    #[strum(serialize = "hueMod")]
+    HueModulate,
+    #[strum(serialize = "sat")]
+    Saturation,
+    #[strum(serialize = "satOff")]
+    SaturationOffset,
+    #[strum(serialize = "satMod")]
+    SaturationModulate,
+    #[strum(serialize = "lum")]
+    Luminance,
+    #[strum(serialize = "lumOff")]
+    LuminanceOffset,
+    #[strum(serialize = "lumMod")]
+    LuminanceModulate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorTransform {
+    pub transform_type: ColorTransformType,
+    pub value: Percentage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorChoice {
+    /// An RGB color as 6 hex digits (`w14:srgbClr`).
+    Rgb(String),
+    /// A color of the theme (`w14:schemeClr`).
+    Scheme(SchemeColorVal),
+}
+
+/// The color of a text effect.
+///
+/// # Xml example
+///
+/// ```xml
+/// <w14:schemeClr w14:val="accent1">
+///   <w14:alpha w14:val="60000"/>
+///   <w14:satMod w14:val="175000"/>
+/// </w14:schemeClr>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectColor {
+    pub color: ColorChoice,
+    pub transforms: Vec<ColorTransform>,
+}
+
+impl EffectColor {
+    pub fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool {
+        matches!(node_name.as_ref(), "srgbClr" | "schemeClr")
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let value = xml_node
+            .attributes
+            .get("w14:val")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w14:val"))?;
+
+        let color = match xml_node.local_name() {
+            "srgbClr" => ColorChoice::Rgb(value.clone()),
+            "schemeClr" => ColorChoice::Scheme(value.parse()?),
+            _ => {
+                return Err(Box::new(NotGroupMemberError::new(
+                    xml_node.name.clone(),
+                    "EG_ColorChoice",
+                )))
+            }
+        };
+
+        let transforms = xml_node
+            .child_nodes
+            .iter()
+            .filter_map(|child_node| {
+                let transform_type = child_node.local_name().parse::<ColorTransformType>().ok()?;
+                Some(
+                    child_node
+                        .attributes
+                        .get("w14:val")
+                        .ok_or_else(|| MissingAttributeError::new(child_node.name.clone(), "w14:val").into())
+                        .and_then(|value| Ok(value.parse()?))
+                        .map(|value| ColorTransform { transform_type, value }),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { color, transforms })
+    }
+}
+
+fn child_effect_color(xml_node: &XmlNode) -> Result<Option<EffectColor>> {
+    xml_node
+        .child_nodes
+        .iter()
+        .find(|child_node| EffectColor::is_choice_member(child_node.local_name()))
+        .map(EffectColor::from_xml_element)
+        .transpose()
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum RectAlignment {
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "tl")]
+    TopLeft,
+    #[strum(serialize = "t")]
+    Top,
+    #[strum(serialize = "tr")]
+    TopRight,
+    #[strum(serialize = "l")]
+    Left,
+    #[strum(serialize = "ctr")]
+    Center,
+    #[strum(serialize = "r")]
+    Right,
+    #[strum(serialize = "bl")]
+    BottomLeft,
+    #[strum(serialize = "b")]
+    Bottom,
+    #[strum(serialize = "br")]
+    BottomRight,
+}
+
+/// This element specifies a glow around the text (`w14:glow`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glow {
+    /// The radius of the glow in EMUs. Defaults to 0.
+    pub radius: Option<PositiveCoordinate>,
+    pub color: Option<EffectColor>,
+}
+
+impl Glow {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let radius = xml_node
+            .attributes
+            .get("w14:rad")
+            .map(|value| value.parse())
+            .transpose()?;
+        let color = child_effect_color(xml_node)?;
+        Ok(Self { radius, color })
+    }
+}
+
+/// This element specifies a shadow of the text (`w14:shadow`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Shadow {
+    /// The blur radius in EMUs.
+    pub blur_radius: Option<PositiveCoordinate>,
+    /// The distance of the shadow from the text in EMUs.
+    pub distance: Option<PositiveCoordinate>,
+    /// The direction the shadow is offset to.
+    pub direction: Option<Angle>,
+    pub horizontal_scale: Option<Percentage>,
+    pub vertical_scale: Option<Percentage>,
+    pub horizontal_skew: Option<Angle>,
+    pub vertical_skew: Option<Angle>,
+    pub alignment: Option<RectAlignment>,
+    pub color: Option<EffectColor>,
+}
+
+impl Shadow {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "w14:blurRad" => instance.blur_radius = Some(value.parse()?),
+                "w14:dist" => instance.distance = Some(value.parse()?),
+                "w14:dir" => instance.direction = Some(value.parse()?),
+                "w14:sx" => instance.horizontal_scale = Some(value.parse()?),
+                "w14:sy" => instance.vertical_scale = Some(value.parse()?),
+                "w14:kx" => instance.horizontal_skew = Some(value.parse()?),
+                "w14:ky" => instance.vertical_skew = Some(value.parse()?),
+                "w14:algn" => instance.alignment = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        instance.color = child_effect_color(xml_node)?;
+        Ok(instance)
+    }
+}
+
+/// This element specifies a reflection of the text (`w14:reflection`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Reflection {
+    /// The blur radius in EMUs.
+    pub blur_radius: Option<PositiveCoordinate>,
+    /// The opacity at the start of the reflection.
+    pub start_alpha: Option<Percentage>,
+    /// The position of the start opacity.
+    pub start_position: Option<Percentage>,
+    /// The opacity at the end of the reflection.
+    pub end_alpha: Option<Percentage>,
+    /// The position of the end opacity.
+    pub end_position: Option<Percentage>,
+    /// The distance of the reflection from the text in EMUs.
+    pub distance: Option<PositiveCoordinate>,
+    /// The direction the reflection is offset to.
+    pub direction: Option<Angle>,
+    /// The direction the opacity fades in.
+    pub fade_direction: Option<Angle>,
+    pub horizontal_scale: Option<Percentage>,
+    pub vertical_scale: Option<Percentage>,
+    pub horizontal_skew: Option<Angle>,
+    pub vertical_skew: Option<Angle>,
+    pub alignment: Option<RectAlignment>,
+}
+
+impl Reflection {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "w14:blurRad" => instance.blur_radius = Some(value.parse()?),
+                "w14:stA" => instance.start_alpha = Some(value.parse()?),
+                "w14:stPos" => instance.start_position = Some(value.parse()?),
+                "w14:endA" => instance.end_alpha = Some(value.parse()?),
+                "w14:endPos" => instance.end_position = Some(value.parse()?),
+                "w14:dist" => instance.distance = Some(value.parse()?),
+                "w14:dir" => instance.direction = Some(value.parse()?),
+                "w14:fadeDir" => instance.fade_direction = Some(value.parse()?),
+                "w14:sx" => instance.horizontal_scale = Some(value.parse()?),
+                "w14:sy" => instance.vertical_scale = Some(value.parse()?),
+                "w14:kx" => instance.horizontal_skew = Some(value.parse()?),
+                "w14:ky" => instance.vertical_skew = Some(value.parse()?),
+                "w14:algn" => instance.alignment = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+/// A color stop of a gradient fill (`w14:gs`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradientStop {
+    pub position: Percentage,
+    pub color: EffectColor,
+}
+
+impl GradientStop {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let position = xml_node
+            .attributes
+            .get("w14:pos")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w14:pos"))?
+            .parse()?;
+        let color = child_effect_color(xml_node)?
+            .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_ColorChoice"))?;
+
+        Ok(Self { position, color })
+    }
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum PathShadeType {
+    #[strum(serialize = "shape")]
+    Shape,
+    #[strum(serialize = "circle")]
+    Circle,
+    #[strum(serialize = "rect")]
+    Rect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientShade {
+    /// A linear gradient (`w14:lin`) with its angle.
+    Linear { angle: Option<Angle>, scaled: Option<bool> },
+    /// A gradient following a path (`w14:path`).
+    Path(PathShadeType),
+}
+
+/// This element specifies a gradient fill (`w14:gradFill`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GradientFill {
+    pub stops: Vec<GradientStop>,
+    pub shade: Option<GradientShade>,
+}
+
+impl GradientFill {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "gsLst" => {
+                    instance.stops = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|stop_node| stop_node.local_name() == "gs")
+                        .map(GradientStop::from_xml_element)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                "lin" => {
+                    instance.shade = Some(GradientShade::Linear {
+                        angle: child_node
+                            .attributes
+                            .get("w14:ang")
+                            .map(|value| value.parse())
+                            .transpose()?,
+                        scaled: child_node
+                            .attributes
+                            .get("w14:scaled")
+                            .map(parse_xml_bool)
+                            .transpose()?,
+                    })
+                }
+                "path" => {
+                    let path = child_node
+                        .attributes
+                        .get("w14:path")
+                        .ok_or_else(|| MissingAttributeError::new(child_node.name.clone(), "w14:path"))?;
+                    instance.shade = Some(GradientShade::Path(path.parse()?));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+/// The fill of the text or of the outline of the text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillProperties {
+    NoFill,
+    SolidFill(Option<EffectColor>),
+    GradientFill(GradientFill),
+}
+
+impl FillProperties {
+    pub fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool {
+        matches!(node_name.as_ref(), "noFill" | "solidFill" | "gradFill")
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        match xml_node.local_name() {
+            "noFill" => Ok(FillProperties::NoFill),
+            "solidFill" => Ok(FillProperties::SolidFill(child_effect_color(xml_node)?)),
+            "gradFill" => Ok(FillProperties::GradientFill(GradientFill::from_xml_element(xml_node)?)),
+            _ => Err(Box::new(NotGroupMemberError::new(
+                xml_node.name.clone(),
+                "EG_FillProperties",
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum LineCap {
+    #[strum(serialize = "rnd")]
+    Round,
+    #[strum(serialize = "sq")]
+    Square,
+    #[strum(serialize = "flat")]
+    Flat,
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum CompoundLine {
+    #[strum(serialize = "sng")]
+    Single,
+    #[strum(serialize = "dbl")]
+    Double,
+    #[strum(serialize = "thickThin")]
+    ThickThin,
+    #[strum(serialize = "thinThick")]
+    ThinThick,
+    #[strum(serialize = "tri")]
+    Triple,
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum PenAlignment {
+    #[strum(serialize = "ctr")]
+    Center,
+    #[strum(serialize = "in")]
+    Inset,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineJoin {
+    Round,
+    Bevel,
+    /// A miter join with its limit in 1000th of a percent.
+    Miter(Option<Percentage>),
+}
+
+/// This element specifies the outline of the text (`w14:textOutline`).
+///
+/// # Xml example
+///
+/// ```xml
+/// <w14:textOutline w14:w="9525" w14:cap="flat" w14:cmpd="sng" w14:algn="ctr">
+///   <w14:solidFill><w14:srgbClr w14:val="FF0000"/></w14:solidFill>
+///   <w14:prstDash w14:val="solid"/>
+///   <w14:round/>
+/// </w14:textOutline>
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextOutline {
+    /// The width of the outline in EMUs.
+    pub width: Option<PositiveCoordinate>,
+    pub cap: Option<LineCap>,
+    pub compound: Option<CompoundLine>,
+    pub alignment: Option<PenAlignment>,
+    pub fill: Option<FillProperties>,
+    /// The preset dash of the outline, e.g. `solid` or `dash`.
+    pub preset_dash: Option<String>,
+    pub join: Option<LineJoin>,
+}
+
+impl TextOutline {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "w14:w" => instance.width = Some(value.parse()?),
+                "w14:cap" => instance.cap = Some(value.parse()?),
+                "w14:cmpd" => instance.compound = Some(value.parse()?),
+                "w14:algn" => instance.alignment = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                node_name if FillProperties::is_choice_member(node_name) => {
+                    instance.fill = Some(FillProperties::from_xml_element(child_node)?)
+                }
+                "prstDash" => instance.preset_dash = child_node.attributes.get("w14:val").cloned(),
+                "round" => instance.join = Some(LineJoin::Round),
+                "bevel" => instance.join = Some(LineJoin::Bevel),
+                "miter" => {
+                    let limit = child_node
+                        .attributes
+                        .get("w14:lim")
+                        .map(|value| value.parse())
+                        .transpose()?;
+                    instance.join = Some(LineJoin::Miter(limit));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+/// The ligatures applied to the text (`w14:ligatures`). The value is a combination of the ligature types, e.g.
+/// `standardContextual`, or `none` or `all`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Ligatures {
+    pub standard: bool,
+    pub contextual: bool,
+    pub historical: bool,
+    pub discretional: bool,
+}
+
+impl FromStr for Ligatures {
+    type Err = NotGroupMemberError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if s == "none" {
+            return Ok(Default::default());
+        }
+
+        if s == "all" {
+            return Ok(Self {
+                standard: true,
+                contextual: true,
+                historical: true,
+                discretional: true,
+            });
+        }
+
+        let mut instance: Self = Default::default();
+        let mut rest = s;
+        while !rest.is_empty() {
+            let lower_case_rest = rest.to_lowercase();
+            let name = ["standard", "contextual", "historical", "discretional"]
+                .iter()
+                .find(|name| lower_case_rest.starts_with(*name))
+                .ok_or_else(|| NotGroupMemberError::new(String::from(s), "ST_Ligatures"))?;
+            match *name {
+                "standard" => instance.standard = true,
+                "contextual" => instance.contextual = true,
+                "historical" => instance.historical = true,
+                _ => instance.discretional = true,
+            }
+
+            rest = &rest[name.len()..];
+        }
+
+        Ok(instance)
+    }
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum NumberForm {
+    #[strum(serialize = "default")]
+    Default,
+    #[strum(serialize = "lining")]
+    Lining,
+    #[strum(serialize = "oldStyle")]
+    OldStyle,
+}
+
+#[derive(Debug, Clone, Copy, EnumString, AsRefStr, PartialEq)]
+pub enum NumberSpacing {
+    #[strum(serialize = "default")]
+    Default,
+    #[strum(serialize = "proportional")]
+    Proportional,
+    #[strum(serialize = "tabular")]
+    Tabular,
+}
+
+/// A stylistic set of the font enabled or disabled for the text (`w14:styleSet`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StylisticSet {
+    pub id: u32,
+    /// Defaults to true
+    pub value: Option<bool>,
+}
+
+impl StylisticSet {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let id = xml_node
+            .attributes
+            .get("w14:id")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w14:id"))?
+            .parse()?;
+        let value = xml_node.attributes.get("w14:val").map(parse_xml_bool).transpose()?;
+        Ok(Self { id, value })
+    }
+}
+
+/// A run property of the Word 2010 extensions (`w14` namespace). They specify the text effects of WordArt like glow,
+/// reflection and gradient fills, and OpenType font features like ligatures and stylistic sets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunPropertyExtension {
+    Glow(Glow),
+    Shadow(Box<Shadow>),
+    Reflection(Box<Reflection>),
+    TextOutline(Box<TextOutline>),
+    TextFill(FillProperties),
+    Ligatures(Ligatures),
+    NumberForm(NumberForm),
+    NumberSpacing(NumberSpacing),
+    StylisticSets(Vec<StylisticSet>),
+    ContextualAlternatives(bool),
+}
+
+impl RunPropertyExtension {
+    /// Returns whether the element is a run property of the Word 2010 extensions. Elements are matched by their
+    /// qualified name, since some of them share the local name with a run property of the main namespace, e.g.
+    /// `w14:shadow` and `w:shadow`.
+    pub fn is_extension(xml_node: &XmlNode) -> bool {
+        xml_node.name.starts_with("w14:") && Self::is_choice_member(xml_node.local_name())
+    }
+
+    pub fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool {
+        matches!(
+            node_name.as_ref(),
+            "glow"
+                | "shadow"
+                | "reflection"
+                | "textOutline"
+                | "textFill"
+                | "ligatures"
+                | "numForm"
+                | "numSpacing"
+                | "stylisticSets"
+                | "cntxtAlts"
+        )
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let value = || {
+            xml_node
+                .attributes
+                .get("w14:val")
+                .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w14:val"))
+        };
+
+        match xml_node.local_name() {
+            "glow" => Ok(RunPropertyExtension::Glow(Glow::from_xml_element(xml_node)?)),
+            "shadow" => Ok(RunPropertyExtension::Shadow(Box::new(Shadow::from_xml_element(
+                xml_node,
+            )?))),
+            "reflection" => Ok(RunPropertyExtension::Reflection(Box::new(
+                Reflection::from_xml_element(xml_node)?,
+            ))),
+            "textOutline" => Ok(RunPropertyExtension::TextOutline(Box::new(
+                TextOutline::from_xml_element(xml_node)?,
+            ))),
+            "textFill" => {
+                let fill_node = xml_node
+                    .child_nodes
+                    .iter()
+                    .find(|child_node| FillProperties::is_choice_member(child_node.local_name()))
+                    .ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "EG_FillProperties"))?;
+                Ok(RunPropertyExtension::TextFill(FillProperties::from_xml_element(
+                    fill_node,
+                )?))
+            }
+            "ligatures" => Ok(RunPropertyExtension::Ligatures(value()?.parse()?)),
+            "numForm" => Ok(RunPropertyExtension::NumberForm(value()?.parse()?)),
+            "numSpacing" => Ok(RunPropertyExtension::NumberSpacing(value()?.parse()?)),
+            "stylisticSets" => Ok(RunPropertyExtension::StylisticSets(
+                xml_node
+                    .child_nodes
+                    .iter()
+                    .filter(|child_node| child_node.local_name() == "styleSet")
+                    .map(StylisticSet::from_xml_element)
+                    .collect::<Result<Vec<_>>>()?,
+            )),
+            "cntxtAlts" => Ok(RunPropertyExtension::ContextualAlternatives(
+                xml_node
+                    .attributes
+                    .get("w14:val")
+                    .map(parse_xml_bool)
+                    .transpose()?
+                    .unwrap_or(true),
+            )),
+            _ => Err(Box::new(NotGroupMemberError::new(
+                xml_node.name.clone(),
+                "RunPropertyExtension",
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docx::wml::document::{RPr, RPrBase};
+
+    #[test]
+    pub fn test_run_property_extensions_from_xml() {
+        let xml = r#"<w:rPr>
+            <w14:glow w14:rad="63500">
+                <w14:schemeClr w14:val="accent1"><w14:alpha w14:val="60000" /></w14:schemeClr>
+            </w14:glow>
+            <w14:textOutline w14:w="9525" w14:cap="flat" w14:cmpd="sng" w14:algn="ctr">
+                <w14:gradFill>
+                    <w14:gsLst>
+                        <w14:gs w14:pos="0"><w14:srgbClr w14:val="FF0000" /></w14:gs>
+                        <w14:gs w14:pos="100000"><w14:schemeClr w14:val="tx1" /></w14:gs>
+                    </w14:gsLst>
+                    <w14:lin w14:ang="5400000" w14:scaled="0" />
+                </w14:gradFill>
+                <w14:prstDash w14:val="solid" />
+                <w14:miter w14:lim="800000" />
+            </w14:textOutline>
+            <w14:ligatures w14:val="standardContextual" />
+            <w14:stylisticSets><w14:styleSet w14:id="2" /></w14:stylisticSets>
+            <w14:cntxtAlts />
+        </w:rPr>"#;
+        let xml_node = XmlNode::from_str(xml).unwrap();
+        let extensions = xml_node
+            .child_nodes
+            .iter()
+            .map(RunPropertyExtension::from_xml_element)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            extensions[0],
+            RunPropertyExtension::Glow(Glow {
+                radius: Some(63500),
+                color: Some(EffectColor {
+                    color: ColorChoice::Scheme(SchemeColorVal::Accent1),
+                    transforms: vec![ColorTransform {
+                        transform_type: ColorTransformType::Alpha,
+                        value: 60000,
+                    }],
+                }),
+            })
+        );
+        match &extensions[1] {
+            RunPropertyExtension::TextOutline(outline) => {
+                assert_eq!(outline.width, Some(9525));
+                assert_eq!(outline.cap, Some(LineCap::Flat));
+                assert_eq!(outline.join, Some(LineJoin::Miter(Some(800000))));
+                assert_eq!(outline.preset_dash, Some(String::from("solid")));
+                match &outline.fill {
+                    Some(FillProperties::GradientFill(fill)) => {
+                        assert_eq!(fill.stops.len(), 2);
+                        assert_eq!(fill.stops[0].color.color, ColorChoice::Rgb(String::from("FF0000")));
+                        assert_eq!(
+                            fill.shade,
+                            Some(GradientShade::Linear {
+                                angle: Some(5400000),
+                                scaled: Some(false),
+                            })
+                        );
+                    }
+                    fill => panic!("unexpected fill {:?}", fill),
+                }
+            }
+            extension => panic!("unexpected extension {:?}", extension),
+        }
+        assert_eq!(
+            extensions[2],
+            RunPropertyExtension::Ligatures(Ligatures {
+                standard: true,
+                contextual: true,
+                historical: false,
+                discretional: false,
+            })
+        );
+        assert_eq!(
+            extensions[3],
+            RunPropertyExtension::StylisticSets(vec![StylisticSet { id: 2, value: None }])
+        );
+        assert_eq!(extensions[4], RunPropertyExtension::ContextualAlternatives(true));
+    }
+
+    #[test]
+    pub fn test_run_properties_with_extensions() {
+        let xml = r#"<w:rPr>
+            <w:shadow />
+            <w14:shadow w14:blurRad="38100" w14:dist="19050" w14:dir="2700000" w14:algn="tl">
+                <w14:srgbClr w14:val="000000" />
+            </w14:shadow>
+            <w14:numForm w14:val="oldStyle" />
+        </w:rPr>"#;
+        let run_properties = RPr::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(run_properties.r_pr_bases[0], RPrBase::Shadow(true));
+        assert_eq!(
+            run_properties.r_pr_bases[1],
+            RPrBase::Extension(RunPropertyExtension::Shadow(Box::new(Shadow {
+                blur_radius: Some(38100),
+                distance: Some(19050),
+                direction: Some(2700000),
+                alignment: Some(RectAlignment::TopLeft),
+                color: Some(EffectColor {
+                    color: ColorChoice::Rgb(String::from("000000")),
+                    transforms: Vec::new(),
+                }),
+                ..Default::default()
+            })))
+        );
+        assert_eq!(
+            run_properties.r_pr_bases[2],
+            RPrBase::Extension(RunPropertyExtension::NumberForm(NumberForm::OldStyle))
+        );
+    }
+}
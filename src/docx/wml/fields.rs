@@ -0,0 +1,602 @@
+use super::document::SimpleField;
+use crate::error::FieldParseError;
+
+pub type Result<T> = ::std::result::Result<T, FieldParseError>;
+
+/// Switches of every field type that take an argument: format (`\*`), numeric picture (`\#`) and date-time picture
+/// (`\@`).
+const GENERAL_SWITCHES_WITH_ARGUMENT: &[char] = &['*', '#', '@'];
+
+/// A switch of a field instruction, e.g. `\o "1-3"` or `\h`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSwitch {
+    /// The character following the backslash. Letters are lower case.
+    pub name: char,
+    pub argument: Option<String>,
+}
+
+/// A field instruction split into its field type, positional arguments and switches. Quoted arguments are unquoted
+/// and the `\\` and `\"` escapes are resolved.
+///
+/// ```
+/// use oox::docx::wml::fields::FieldInstruction;
+///
+/// let instruction = FieldInstruction::parse(r#" HYPERLINK "https://example.com" \l "top" \o "Tool tip" "#).unwrap();
+/// assert_eq!(instruction.field_type, "HYPERLINK");
+/// assert_eq!(instruction.arguments, vec!["https://example.com"]);
+/// assert_eq!(instruction.switch_argument('l'), Some("top"));
+/// assert_eq!(instruction.switch_argument('o'), Some("Tool tip"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInstruction {
+    /// The upper case field type, e.g. `PAGE` or `MERGEFIELD`.
+    pub field_type: String,
+    pub arguments: Vec<String>,
+    pub switches: Vec<FieldSwitch>,
+}
+
+impl FieldInstruction {
+    pub fn parse(instruction: &str) -> Result<Self> {
+        let mut tokens = tokenize(instruction).into_iter().peekable();
+        let field_type = match tokens.next() {
+            Some(Token::Word(field_type)) | Some(Token::Quoted(field_type)) => field_type.to_uppercase(),
+            _ => return Err(FieldParseError::Empty),
+        };
+
+        let switches_with_argument = field_switches_with_argument(&field_type);
+        let mut arguments = Vec::new();
+        let mut switches = Vec::new();
+
+        while let Some(token) = tokens.next() {
+            match token {
+                Token::Word(argument) | Token::Quoted(argument) => arguments.push(argument),
+                Token::Switch(name, attached_argument) => {
+                    let takes_argument = GENERAL_SWITCHES_WITH_ARGUMENT.contains(&name)
+                        || !matches!(switches_with_argument, Some(switches) if !switches.contains(&name));
+
+                    let argument = match attached_argument {
+                        Some(argument) => Some(argument),
+                        None if takes_argument => match tokens.peek() {
+                            Some(Token::Word(_)) | Some(Token::Quoted(_)) => match tokens.next() {
+                                Some(Token::Word(argument)) | Some(Token::Quoted(argument)) => Some(argument),
+                                _ => None,
+                            },
+                            _ => None,
+                        },
+                        None => None,
+                    };
+
+                    switches.push(FieldSwitch { name, argument });
+                }
+            }
+        }
+
+        Ok(Self {
+            field_type,
+            arguments,
+            switches,
+        })
+    }
+
+    pub fn has_switch(&self, name: char) -> bool {
+        self.switches.iter().any(|switch| switch.name == name)
+    }
+
+    /// Returns the argument of the first occurrence of a switch.
+    pub fn switch_argument(&self, name: char) -> Option<&str> {
+        self.switches
+            .iter()
+            .find(|switch| switch.name == name)
+            .and_then(|switch| switch.argument.as_deref())
+    }
+
+    /// Returns the general formatting switches of the field.
+    pub fn format(&self) -> FieldFormat {
+        FieldFormat {
+            formats: self
+                .switches
+                .iter()
+                .filter(|switch| switch.name == '*')
+                .filter_map(|switch| switch.argument.clone())
+                .collect(),
+            numeric_picture: self.switch_argument('#').map(String::from),
+            date_time_picture: self.switch_argument('@').map(String::from),
+        }
+    }
+
+    fn required_argument(&self, index: usize, field_type: &'static str, argument: &'static str) -> Result<String> {
+        self.arguments
+            .get(index)
+            .cloned()
+            .ok_or(FieldParseError::MissingArgument { field_type, argument })
+    }
+}
+
+/// The general formatting switches of a field.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FieldFormat {
+    /// The arguments of the `\*` switches, e.g. `ARABIC` or `MERGEFORMAT`.
+    pub formats: Vec<String>,
+    /// The argument of the `\#` switch, e.g. `0.00`.
+    pub numeric_picture: Option<String>,
+    /// The argument of the `\@` switch, e.g. `dd/MM/yyyy`.
+    pub date_time_picture: Option<String>,
+}
+
+impl FieldFormat {
+    /// Returns the number format of the field, e.g. `ROMAN` or `alphabetic`. The case of the format is significant.
+    pub fn number_format(&self) -> Option<&str> {
+        self.formats
+            .iter()
+            .map(String::as_str)
+            .find(|format| !is_text_format(format))
+    }
+
+    /// Returns whether the formatting of the previous result is kept when the field is updated.
+    pub fn merge_format(&self) -> bool {
+        self.formats
+            .iter()
+            .any(|format| format.eq_ignore_ascii_case("MERGEFORMAT"))
+    }
+}
+
+/// The PAGE field, which is replaced by the current page number.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PageField {
+    pub format: FieldFormat,
+}
+
+/// The REF field, which inserts the content of a bookmark.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RefField {
+    pub bookmark: String,
+    /// `\h`: The result is a hyperlink to the bookmark.
+    pub hyperlink: bool,
+    /// `\f`: Increments the footnote, endnote or annotation numbers of the bookmark.
+    pub increment_notes: bool,
+    /// `\n`: Inserts the paragraph number of the bookmark without trailing periods.
+    pub paragraph_number: bool,
+    /// `\r`: Inserts the paragraph number of the bookmark in relative context.
+    pub relative_paragraph_number: bool,
+    /// `\w`: Inserts the paragraph number of the bookmark in full context.
+    pub full_paragraph_number: bool,
+    /// `\p`: Inserts "above" or "below" depending on the position of the bookmark.
+    pub relative_position: bool,
+    /// `\t`: Suppresses non-delimiter and non-numerical text of paragraph numbers.
+    pub suppress_non_numeric: bool,
+    /// `\d`: The separator of sequence numbers.
+    pub separator: Option<String>,
+    pub format: FieldFormat,
+}
+
+/// The HYPERLINK field.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HyperlinkField {
+    /// The target URL or file. None for links to a location of the document.
+    pub target: Option<String>,
+    /// `\l`: The location in the target, e.g. a bookmark.
+    pub location: Option<String>,
+    /// `\o`: The tooltip of the link.
+    pub tooltip: Option<String>,
+    /// `\t`: The frame the target is opened in.
+    pub target_frame: Option<String>,
+    /// `\m`: Appends coordinates to the link for a server-side image map.
+    pub image_map: bool,
+    /// `\n`: Opens the target in a new window.
+    pub new_window: bool,
+}
+
+/// The TOC field, which builds a table of contents.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TocField {
+    /// `\o`: The range of outline levels of the headings included, e.g. `(1, 3)` for `\o "1-3"`.
+    pub heading_levels: Option<(u8, u8)>,
+    /// `\t`: Additional paragraph styles and the levels of their entries.
+    pub styles: Vec<(String, u8)>,
+    /// `\u`: Includes paragraphs by their outline level.
+    pub use_outline_levels: bool,
+    /// `\h`: The entries are hyperlinks.
+    pub hyperlinks: bool,
+    /// `\z`: Hides the tab leaders and page numbers in web layout view.
+    pub hide_in_web_layout: bool,
+    /// `\w`: Preserves the tab entries of the headings.
+    pub preserve_tabs: bool,
+    /// `\x`: Preserves the line breaks of the headings.
+    pub preserve_line_breaks: bool,
+    /// `\c`: Builds a table of figures from the SEQ fields with the given identifier.
+    pub caption_identifier: Option<String>,
+    /// `\a`: Like `\c`, without the caption label and number.
+    pub caption_identifier_without_label: Option<String>,
+    /// `\b`: Only includes the entries of the given bookmark.
+    pub bookmark: Option<String>,
+    /// `\f`: Includes the TC fields with the given identifier.
+    pub entry_identifier: Option<String>,
+    /// `\l`: The range of levels of TC fields included.
+    pub entry_levels: Option<(u8, u8)>,
+    /// `\n`: The range of levels without page numbers. Omitting the argument omits every page number.
+    pub omit_page_numbers: Option<Option<(u8, u8)>>,
+    /// `\p`: The separator between the entries and their page numbers.
+    pub page_number_separator: Option<String>,
+}
+
+/// The SEQ field, which numbers captions, tables, figures and other items sequentially.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SeqField {
+    pub identifier: String,
+    /// The bookmark of an item to refer to.
+    pub bookmark: Option<String>,
+    /// `\c`: Repeats the closest preceding sequence number.
+    pub repeat: bool,
+    /// `\h`: Hides the result.
+    pub hidden: bool,
+    /// `\n`: Inserts the next sequence number, which is the default.
+    pub next: bool,
+    /// `\r`: Resets the sequence number to the given number.
+    pub reset: Option<u32>,
+    /// `\s`: Resets the sequence number at the heading of the given level.
+    pub reset_at_heading_level: Option<u8>,
+    pub format: FieldFormat,
+}
+
+/// The MERGEFIELD field, which is replaced by a data field of a mail merge.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MergeField {
+    pub name: String,
+    /// `\b`: Text inserted before the value if it's not empty.
+    pub text_before: Option<String>,
+    /// `\f`: Text inserted after the value if it's not empty.
+    pub text_after: Option<String>,
+    /// `\m`: The field is a mapped field.
+    pub mapped: bool,
+    /// `\v`: Enables vertical formatting.
+    pub vertical: bool,
+    pub format: FieldFormat,
+}
+
+/// A parsed field instruction. Fields without a typed model are kept as a generic instruction.
+///
+/// ```
+/// use oox::docx::wml::fields::Field;
+///
+/// match Field::parse(r#"TOC \o "1-3" \h \z \u"#).unwrap() {
+///     Field::Toc(toc) => {
+///         assert_eq!(toc.heading_levels, Some((1, 3)));
+///         assert!(toc.hyperlinks);
+///     }
+///     _ => panic!("expected a TOC field"),
+/// }
+///
+/// match Field::parse(r#" MERGEFIELD  FirstName \f " " \* MERGEFORMAT "#).unwrap() {
+///     Field::MergeField(merge_field) => {
+///         assert_eq!(merge_field.name, "FirstName");
+///         assert_eq!(merge_field.text_after.as_deref(), Some(" "));
+///     }
+///     _ => panic!("expected a MERGEFIELD field"),
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+    Page(PageField),
+    Ref(RefField),
+    Hyperlink(HyperlinkField),
+    Toc(TocField),
+    Seq(SeqField),
+    MergeField(MergeField),
+    Other(FieldInstruction),
+}
+
+impl Field {
+    pub fn parse(instruction: &str) -> Result<Self> {
+        Self::from_instruction(FieldInstruction::parse(instruction)?)
+    }
+
+    pub fn from_instruction(instruction: FieldInstruction) -> Result<Self> {
+        let format = instruction.format();
+        let field = match instruction.field_type.as_str() {
+            "PAGE" => Field::Page(PageField { format }),
+            "REF" => Field::Ref(RefField {
+                bookmark: instruction.required_argument(0, "REF", "bookmark")?,
+                hyperlink: instruction.has_switch('h'),
+                increment_notes: instruction.has_switch('f'),
+                paragraph_number: instruction.has_switch('n'),
+                relative_paragraph_number: instruction.has_switch('r'),
+                full_paragraph_number: instruction.has_switch('w'),
+                relative_position: instruction.has_switch('p'),
+                suppress_non_numeric: instruction.has_switch('t'),
+                separator: instruction.switch_argument('d').map(String::from),
+                format,
+            }),
+            "HYPERLINK" => Field::Hyperlink(HyperlinkField {
+                target: instruction.arguments.first().cloned(),
+                location: instruction.switch_argument('l').map(String::from),
+                tooltip: instruction.switch_argument('o').map(String::from),
+                target_frame: instruction.switch_argument('t').map(String::from),
+                image_map: instruction.has_switch('m'),
+                new_window: instruction.has_switch('n'),
+            }),
+            "TOC" => Field::Toc(TocField {
+                heading_levels: level_range_switch(&instruction, 'o')?,
+                styles: toc_styles(instruction.switch_argument('t')),
+                use_outline_levels: instruction.has_switch('u'),
+                hyperlinks: instruction.has_switch('h'),
+                hide_in_web_layout: instruction.has_switch('z'),
+                preserve_tabs: instruction.has_switch('w'),
+                preserve_line_breaks: instruction.has_switch('x'),
+                caption_identifier: instruction.switch_argument('c').map(String::from),
+                caption_identifier_without_label: instruction.switch_argument('a').map(String::from),
+                bookmark: instruction.switch_argument('b').map(String::from),
+                entry_identifier: instruction.switch_argument('f').map(String::from),
+                entry_levels: level_range_switch(&instruction, 'l')?,
+                omit_page_numbers: match instruction.has_switch('n') {
+                    true => Some(level_range_switch(&instruction, 'n')?),
+                    false => None,
+                },
+                page_number_separator: instruction.switch_argument('p').map(String::from),
+            }),
+            "SEQ" => Field::Seq(SeqField {
+                identifier: instruction.required_argument(0, "SEQ", "identifier")?,
+                bookmark: instruction.arguments.get(1).cloned(),
+                repeat: instruction.has_switch('c'),
+                hidden: instruction.has_switch('h'),
+                next: instruction.has_switch('n'),
+                reset: number_switch(&instruction, 'r')?,
+                reset_at_heading_level: number_switch(&instruction, 's')?,
+                format,
+            }),
+            "MERGEFIELD" => Field::MergeField(MergeField {
+                name: instruction.required_argument(0, "MERGEFIELD", "name")?,
+                text_before: instruction.switch_argument('b').map(String::from),
+                text_after: instruction.switch_argument('f').map(String::from),
+                mapped: instruction.has_switch('m'),
+                vertical: instruction.has_switch('v'),
+                format,
+            }),
+            _ => Field::Other(instruction),
+        };
+
+        Ok(field)
+    }
+}
+
+impl SimpleField {
+    /// Parses the instruction of the field.
+    pub fn field(&self) -> Result<Field> {
+        Field::parse(&self.field_codes)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    /// A switch with the argument written right after the switch name, e.g. `\#0.00`
+    Switch(char, Option<String>),
+}
+
+fn tokenize(instruction: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = instruction.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '"' => {
+                let mut quoted = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"') | Some('\\')) => quoted.extend(chars.next()),
+                        c => quoted.push(c),
+                    }
+                }
+                tokens.push(Token::Quoted(quoted));
+            }
+            '\\' => {
+                let name = match chars.next() {
+                    Some(name) if !name.is_whitespace() => name.to_ascii_lowercase(),
+                    _ => continue,
+                };
+
+                let mut attached_argument = String::new();
+                while let Some(c) = chars.peek().copied().filter(|c| !c.is_whitespace()) {
+                    chars.next();
+                    attached_argument.push(c);
+                }
+
+                let attached_argument =
+                    Some(attached_argument.trim_matches('"').to_owned()).filter(|arg| !arg.is_empty());
+                tokens.push(Token::Switch(name, attached_argument));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.peek().copied().filter(|c| !c.is_whitespace()) {
+                    chars.next();
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Returns the field specific switches that take an argument, or None if the field type is not known. Switches of
+/// unknown fields take the following argument, if any.
+fn field_switches_with_argument(field_type: &str) -> Option<&'static [char]> {
+    match field_type {
+        "PAGE" => Some(&[]),
+        "REF" => Some(&['d']),
+        "HYPERLINK" => Some(&['l', 'o', 't']),
+        "TOC" => Some(&['a', 'b', 'c', 'd', 'f', 'l', 'n', 'o', 'p', 's', 't']),
+        "SEQ" => Some(&['r', 's']),
+        "MERGEFIELD" => Some(&['b', 'f']),
+        _ => None,
+    }
+}
+
+fn is_text_format(format: &str) -> bool {
+    ["MERGEFORMAT", "CHARFORMAT", "Caps", "FirstCap", "Lower", "Upper"]
+        .iter()
+        .any(|text_format| format.eq_ignore_ascii_case(text_format))
+}
+
+fn number_switch<T: std::str::FromStr>(instruction: &FieldInstruction, name: char) -> Result<Option<T>> {
+    instruction
+        .switch_argument(name)
+        .map(|argument| {
+            argument
+                .trim()
+                .parse()
+                .map_err(|_| FieldParseError::InvalidSwitchArgument {
+                    switch: name,
+                    argument: String::from(argument),
+                })
+        })
+        .transpose()
+}
+
+/// Parses a level range argument like `1-3`. A single number is a range of one level.
+fn level_range_switch(instruction: &FieldInstruction, name: char) -> Result<Option<(u8, u8)>> {
+    let argument = match instruction.switch_argument(name) {
+        Some(argument) => argument,
+        None => return Ok(None),
+    };
+
+    let invalid = || FieldParseError::InvalidSwitchArgument {
+        switch: name,
+        argument: String::from(argument),
+    };
+
+    let mut bounds = argument.splitn(2, '-').map(|bound| bound.trim().parse::<u8>());
+    let from = bounds.next().and_then(|bound| bound.ok()).ok_or_else(invalid)?;
+    let to = match bounds.next() {
+        Some(bound) => bound.map_err(|_| invalid())?,
+        None => from,
+    };
+
+    Ok(Some((from, to)))
+}
+
+/// Parses the style list of the `\t` switch of a TOC field, e.g. `Heading,1,Subheading,2`. Word uses the list
+/// separator of the system locale, so both commas and semicolons are accepted.
+fn toc_styles(argument: Option<&str>) -> Vec<(String, u8)> {
+    let items: Vec<&str> = match argument {
+        Some(argument) => argument.split([',', ';']).map(str::trim).collect(),
+        None => return Vec::new(),
+    };
+
+    items
+        .chunks(2)
+        .filter(|chunk| !chunk[0].is_empty())
+        .map(|chunk| {
+            let level = chunk.get(1).and_then(|level| level.parse().ok()).unwrap_or(1);
+            (String::from(chunk[0]), level)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse_field_instruction() {
+        let instruction = FieldInstruction::parse(r#"  ref  _Ref123 \h \d "-" \*MERGEFORMAT "#).unwrap();
+        assert_eq!(
+            instruction,
+            FieldInstruction {
+                field_type: String::from("REF"),
+                arguments: vec![String::from("_Ref123")],
+                switches: vec![
+                    FieldSwitch {
+                        name: 'h',
+                        argument: None,
+                    },
+                    FieldSwitch {
+                        name: 'd',
+                        argument: Some(String::from("-")),
+                    },
+                    FieldSwitch {
+                        name: '*',
+                        argument: Some(String::from("MERGEFORMAT")),
+                    },
+                ],
+            }
+        );
+
+        let instruction = FieldInstruction::parse(r#"INCLUDETEXT "C:\\Docs\\a \"b\".docx" \c AnsiText"#).unwrap();
+        assert_eq!(instruction.arguments, vec![r#"C:\Docs\a "b".docx"#]);
+        assert_eq!(instruction.switch_argument('c'), Some("AnsiText"));
+
+        assert_eq!(FieldInstruction::parse("   "), Err(FieldParseError::Empty));
+    }
+
+    #[test]
+    pub fn test_parse_typed_fields() {
+        assert_eq!(
+            Field::parse(r#"PAGE \* roman \* MERGEFORMAT"#).unwrap(),
+            Field::Page(PageField {
+                format: FieldFormat {
+                    formats: vec![String::from("roman"), String::from("MERGEFORMAT")],
+                    ..Default::default()
+                },
+            })
+        );
+
+        match Field::parse(r#"SEQ Figure \* ARABIC \r 5 \s 1"#).unwrap() {
+            Field::Seq(seq) => {
+                assert_eq!(seq.identifier, "Figure");
+                assert_eq!(seq.reset, Some(5));
+                assert_eq!(seq.reset_at_heading_level, Some(1));
+                assert_eq!(seq.format.number_format(), Some("ARABIC"));
+            }
+            field => panic!("unexpected field: {:?}", field),
+        }
+
+        match Field::parse(r#"TOC \t "Title;1;Subtitle;2" \n \c "Table" \h"#).unwrap() {
+            Field::Toc(toc) => {
+                assert_eq!(
+                    toc.styles,
+                    vec![(String::from("Title"), 1), (String::from("Subtitle"), 2)]
+                );
+                assert_eq!(toc.omit_page_numbers, Some(None));
+                assert_eq!(toc.caption_identifier.as_deref(), Some("Table"));
+                assert!(toc.hyperlinks);
+            }
+            field => panic!("unexpected field: {:?}", field),
+        }
+
+        match Field::parse(r#"HYPERLINK \l "_Toc1" \o "Go""#).unwrap() {
+            Field::Hyperlink(hyperlink) => {
+                assert_eq!(hyperlink.target, None);
+                assert_eq!(hyperlink.location.as_deref(), Some("_Toc1"));
+                assert_eq!(hyperlink.tooltip.as_deref(), Some("Go"));
+            }
+            field => panic!("unexpected field: {:?}", field),
+        }
+
+        match Field::parse(r#"DATE \@ "yyyy-MM-dd""#).unwrap() {
+            Field::Other(instruction) => {
+                assert_eq!(instruction.field_type, "DATE");
+                assert_eq!(instruction.format().date_time_picture.as_deref(), Some("yyyy-MM-dd"));
+            }
+            field => panic!("unexpected field: {:?}", field),
+        }
+
+        assert_eq!(
+            Field::parse("MERGEFIELD"),
+            Err(FieldParseError::MissingArgument {
+                field_type: "MERGEFIELD",
+                argument: "name",
+            })
+        );
+        assert_eq!(
+            Field::parse(r#"TOC \o "a-3""#),
+            Err(FieldParseError::InvalidSwitchArgument {
+                switch: 'o',
+                argument: String::from("a-3"),
+            })
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use super::simpletypes::{parse_on_off_xml_element, LongHexNumber};
+use crate::{
+    error::MissingAttributeError,
+    shared::{relationship::RelationshipId, sharedtypes::OnOff},
+    xml::{parse_xml_bool, XmlNode},
+};
+use log::info;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// The GUID deobfuscating an embedded font, e.g. `{0FC8B2C4-D5B1-4A5E-A5F3-43E4C1A0E1F2}`.
+pub type Guid = String;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum FontFamily {
+    #[strum(serialize = "decorative")]
+    Decorative,
+    #[strum(serialize = "modern")]
+    Modern,
+    #[strum(serialize = "roman")]
+    Roman,
+    #[strum(serialize = "script")]
+    Script,
+    #[strum(serialize = "swiss")]
+    Swiss,
+    #[strum(serialize = "auto")]
+    Auto,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum Pitch {
+    #[strum(serialize = "fixed")]
+    Fixed,
+    #[strum(serialize = "variable")]
+    Variable,
+    #[strum(serialize = "default")]
+    Default,
+}
+
+/// The Unicode subset and code page bitfields supported by a font, as stored in its OS/2 table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontSig {
+    pub usb0: LongHexNumber,
+    pub usb1: LongHexNumber,
+    pub usb2: LongHexNumber,
+    pub usb3: LongHexNumber,
+    pub csb0: LongHexNumber,
+    pub csb1: LongHexNumber,
+}
+
+impl FontSig {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing FontSig");
+
+        let parse_attribute = |name: &'static str| -> Result<LongHexNumber> {
+            let value = xml_node
+                .attributes
+                .get(name)
+                .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), name))?;
+            Ok(LongHexNumber::from_str_radix(value, 16)?)
+        };
+
+        Ok(Self {
+            usb0: parse_attribute("w:usb0")?,
+            usb1: parse_attribute("w:usb1")?,
+            usb2: parse_attribute("w:usb2")?,
+            usb3: parse_attribute("w:usb3")?,
+            csb0: parse_attribute("w:csb0")?,
+            csb1: parse_attribute("w:csb1")?,
+        })
+    }
+
+    /// Returns whether the font supports the code page with the given bit of the code page range, e.g. 0 for Latin 1.
+    pub fn supports_code_page(&self, bit: u32) -> bool {
+        match bit {
+            0..=31 => self.csb0 & (1 << bit) != 0,
+            32..=63 => self.csb1 & (1 << (bit - 32)) != 0,
+            _ => false,
+        }
+    }
+}
+
+/// A reference to an embedded font part of the font table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontRel {
+    pub rel_id: RelationshipId,
+    /// The key obfuscating the embedded font, see [`FontRel::is_obfuscated`].
+    pub font_key: Option<Guid>,
+    /// Whether only the characters used by the document are embedded.
+    pub subsetted: Option<OnOff>,
+}
+
+impl FontRel {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing FontRel");
+
+        let mut rel_id = None;
+        let mut font_key = None;
+        let mut subsetted = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "r:id" => rel_id = Some(value.clone()),
+                "w:fontKey" => font_key = Some(value.clone()),
+                "w:subsetted" => subsetted = Some(parse_xml_bool(value)?),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            rel_id: rel_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:id"))?,
+            font_key,
+            subsetted,
+        })
+    }
+
+    /// Returns whether the embedded font is obfuscated with the font key, like `.odttf` parts written by Word.
+    pub fn is_obfuscated(&self) -> bool {
+        self.font_key.is_some()
+    }
+}
+
+/// A font used by the document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    pub name: String,
+    /// The alternate names of the font, separated by commas.
+    pub alternate_name: Option<String>,
+    /// The PANOSE-1 classification of the font as a hexadecimal string.
+    pub panose1: Option<String>,
+    /// The character set of the font as the hexadecimal number stored in the `w:val` attribute.
+    pub charset: Option<String>,
+    /// The IANA name of the character set, overriding the value of [`Font::charset`].
+    pub character_set: Option<String>,
+    pub family: Option<FontFamily>,
+    pub not_true_type: Option<OnOff>,
+    pub pitch: Option<Pitch>,
+    pub signature: Option<FontSig>,
+    pub embed_regular: Option<FontRel>,
+    pub embed_bold: Option<FontRel>,
+    pub embed_italic: Option<FontRel>,
+    pub embed_bold_italic: Option<FontRel>,
+}
+
+impl Font {
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            alternate_name: None,
+            panose1: None,
+            charset: None,
+            character_set: None,
+            family: None,
+            not_true_type: None,
+            pitch: None,
+            signature: None,
+            embed_regular: None,
+            embed_bold: None,
+            embed_italic: None,
+            embed_bold_italic: None,
+        }
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing Font");
+
+        let name = xml_node
+            .attributes
+            .get("w:name")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w:name"))?;
+
+        xml_node
+            .child_nodes
+            .iter()
+            .try_fold(Font::new(name.as_str()), |mut instance, child_node| {
+                match child_node.local_name() {
+                    "altName" => instance.alternate_name = child_node.attributes.get("w:val").cloned(),
+                    "panose1" => instance.panose1 = child_node.attributes.get("w:val").cloned(),
+                    "charset" => {
+                        instance.charset = child_node.attributes.get("w:val").cloned();
+                        instance.character_set = child_node.attributes.get("w:characterSet").cloned();
+                    }
+                    "family" => {
+                        instance.family = child_node
+                            .attributes
+                            .get("w:val")
+                            .map(|value| value.parse())
+                            .transpose()?
+                    }
+                    "notTrueType" => instance.not_true_type = Some(parse_on_off_xml_element(child_node)?),
+                    "pitch" => {
+                        instance.pitch = child_node
+                            .attributes
+                            .get("w:val")
+                            .map(|value| value.parse())
+                            .transpose()?
+                    }
+                    "sig" => instance.signature = Some(FontSig::from_xml_element(child_node)?),
+                    "embedRegular" => instance.embed_regular = Some(FontRel::from_xml_element(child_node)?),
+                    "embedBold" => instance.embed_bold = Some(FontRel::from_xml_element(child_node)?),
+                    "embedItalic" => instance.embed_italic = Some(FontRel::from_xml_element(child_node)?),
+                    "embedBoldItalic" => instance.embed_bold_italic = Some(FontRel::from_xml_element(child_node)?),
+                    _ => (),
+                }
+
+                Ok(instance)
+            })
+    }
+
+    /// Returns whether the font is called by the name, either by its name or one of its alternate names. Font names
+    /// are case insensitive.
+    pub fn is_named<T: AsRef<str>>(&self, name: T) -> bool {
+        let name = name.as_ref().trim();
+        self.name.eq_ignore_ascii_case(name)
+            || self.alternate_name.iter().any(|alternate_names| {
+                alternate_names
+                    .split(',')
+                    .any(|alternate_name| alternate_name.trim().eq_ignore_ascii_case(name))
+            })
+    }
+}
+
+/// The fonts used by the document (`word/fontTable.xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FontTable(pub Vec<Font>);
+
+impl FontTable {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing FontTable");
+
+        let fonts = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "font")
+            .map(Font::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(fonts))
+    }
+
+    /// Returns the font with the given name. Fonts are matched by their name first, then by their alternate names.
+    pub fn font<T: AsRef<str>>(&self, name: T) -> Option<&Font> {
+        let name = name.as_ref();
+        self.0
+            .iter()
+            .find(|font| font.name.eq_ignore_ascii_case(name.trim()))
+            .or_else(|| self.0.iter().find(|font| font.is_named(name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_font_table_from_xml() {
+        let xml = r#"<w:fonts>
+            <w:font w:name="Calibri">
+                <w:altName w:val="Body Font, Carlito" />
+                <w:panose1 w:val="020F0502020204030204" />
+                <w:charset w:val="00" />
+                <w:family w:val="swiss" />
+                <w:pitch w:val="variable" />
+                <w:sig w:usb0="E4002EFF" w:usb1="C000247B" w:usb2="00000009" w:usb3="00000000" w:csb0="000001FF"
+                    w:csb1="00000000" />
+                <w:embedRegular r:id="rId1" w:fontKey="{0FC8B2C4-D5B1-4A5E-A5F3-43E4C1A0E1F2}" w:subsetted="1" />
+            </w:font>
+            <w:font w:name="Courier New">
+                <w:family w:val="modern" />
+                <w:notTrueType />
+                <w:pitch w:val="fixed" />
+            </w:font>
+        </w:fonts>"#;
+
+        let font_table = FontTable::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(font_table.0.len(), 2);
+
+        let calibri = &font_table.0[0];
+        assert_eq!(calibri.family, Some(FontFamily::Swiss));
+        assert_eq!(calibri.pitch, Some(Pitch::Variable));
+        assert_eq!(calibri.panose1.as_deref(), Some("020F0502020204030204"));
+        let signature = calibri.signature.unwrap();
+        assert_eq!(signature.usb0, 0xE400_2EFF);
+        assert!(signature.supports_code_page(0));
+        assert!(!signature.supports_code_page(40));
+        assert_eq!(
+            calibri.embed_regular,
+            Some(FontRel {
+                rel_id: String::from("rId1"),
+                font_key: Some(String::from("{0FC8B2C4-D5B1-4A5E-A5F3-43E4C1A0E1F2}")),
+                subsetted: Some(true),
+            })
+        );
+
+        assert_eq!(font_table.0[1].not_true_type, Some(true));
+        assert_eq!(
+            font_table.font("courier new").map(|font| font.name.as_str()),
+            Some("Courier New")
+        );
+        assert_eq!(
+            font_table.font("Carlito").map(|font| font.name.as_str()),
+            Some("Calibri")
+        );
+        assert!(font_table.font("Arial").is_none());
+    }
+}
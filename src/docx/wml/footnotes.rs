@@ -20,6 +20,7 @@ pub enum FtnEdnType {
     ContinuationNotice,
 }
 
+/// A footnote or endnote. Separators and continuation notices are stored as notes of the special types.
 #[derive(Debug, Clone, PartialEq)]
 pub struct FtnEdn {
     pub ftn_edn_type: Option<FtnEdnType>,
@@ -64,8 +65,29 @@ impl FtnEdn {
             )))
         }
     }
+
+    /// Returns the type of the note. Notes without a type are normal notes.
+    pub fn note_type(&self) -> FtnEdnType {
+        self.ftn_edn_type.unwrap_or(FtnEdnType::Normal)
+    }
+
+    /// Returns whether the note is referenced from the document, rather than being a separator or continuation notice
+    /// displayed by the application.
+    pub fn is_normal(&self) -> bool {
+        self.note_type() == FtnEdnType::Normal
+    }
 }
 
+/// The notes of a footnotes or endnotes part. The special notes are stored next to the normal ones, like in the part.
+fn normal_notes(notes: &[FtnEdn]) -> impl Iterator<Item = &FtnEdn> {
+    notes.iter().filter(|note| note.is_normal())
+}
+
+fn special_note(notes: &[FtnEdn], note_type: FtnEdnType) -> Option<&FtnEdn> {
+    notes.iter().find(|note| note.note_type() == note_type)
+}
+
+/// The footnotes of the document (`word/footnotes.xml`), including the separators and the continuation notice.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Footnotes(pub Vec<FtnEdn>);
 
@@ -80,6 +102,73 @@ impl Footnotes {
 
         Ok(Self(footnotes))
     }
+
+    /// Returns the footnotes referenced from the document, skipping separators and continuation notices.
+    pub fn notes(&self) -> impl Iterator<Item = &FtnEdn> {
+        normal_notes(&self.0)
+    }
+
+    /// Returns the footnote with the id.
+    pub fn note(&self, id: DecimalNumber) -> Option<&FtnEdn> {
+        self.0.iter().find(|note| note.id == id)
+    }
+
+    /// Returns the separator displayed between the body text and the footnotes.
+    pub fn separator(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::Separator)
+    }
+
+    /// Returns the separator displayed above footnotes continued from the previous page.
+    pub fn continuation_separator(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::ContinuationSeparator)
+    }
+
+    /// Returns the notice displayed below footnotes continued on the next page.
+    pub fn continuation_notice(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::ContinuationNotice)
+    }
+}
+
+/// The endnotes of the document (`word/endnotes.xml`), including the separators and the continuation notice.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Endnotes(pub Vec<FtnEdn>);
+
+impl Endnotes {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let endnotes = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "endnote")
+            .map(FtnEdn::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self(endnotes))
+    }
+
+    /// Returns the endnotes referenced from the document, skipping separators and continuation notices.
+    pub fn notes(&self) -> impl Iterator<Item = &FtnEdn> {
+        normal_notes(&self.0)
+    }
+
+    /// Returns the endnote with the id.
+    pub fn note(&self, id: DecimalNumber) -> Option<&FtnEdn> {
+        self.0.iter().find(|note| note.id == id)
+    }
+
+    /// Returns the separator displayed between the body text and the endnotes.
+    pub fn separator(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::Separator)
+    }
+
+    /// Returns the separator displayed above endnotes continued from the previous page.
+    pub fn continuation_separator(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::ContinuationSeparator)
+    }
+
+    /// Returns the notice displayed below endnotes continued on the next page.
+    pub fn continuation_notice(&self) -> Option<&FtnEdn> {
+        special_note(&self.0, FtnEdnType::ContinuationNotice)
+    }
 }
 
 #[cfg(test)]
@@ -113,6 +202,38 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_endnotes_from_xml() {
+        let xml = format!(r#"<w:endnotes>{}</w:endnotes>"#, FtnEdn::test_xml("w:endnote"));
+        assert_eq!(
+            Endnotes::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            Endnotes(vec![FtnEdn::test_instance()]),
+        );
+    }
+
+    #[test]
+    pub fn test_footnotes_special_notes() {
+        let xml = r#"<w:footnotes>
+            <w:footnote w:type="separator" w:id="-1"><w:p><w:r><w:separator /></w:r></w:p></w:footnote>
+            <w:footnote w:type="continuationSeparator" w:id="0">
+                <w:p><w:r><w:continuationSeparator /></w:r></w:p>
+            </w:footnote>
+            <w:footnote w:id="1"><w:p /></w:footnote>
+            <w:footnote w:type="normal" w:id="2"><w:p /></w:footnote>
+        </w:footnotes>"#;
+        let footnotes = Footnotes::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(footnotes.notes().map(|note| note.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(footnotes.separator().map(|note| note.id), Some(-1));
+        assert_eq!(footnotes.continuation_separator().map(|note| note.id), Some(0));
+        assert!(footnotes.continuation_notice().is_none());
+        assert_eq!(
+            footnotes.note(0).unwrap().note_type(),
+            FtnEdnType::ContinuationSeparator
+        );
+        assert!(footnotes.note(1).unwrap().is_normal());
+    }
+
     impl FtnEdn {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
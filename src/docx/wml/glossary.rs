@@ -0,0 +1,555 @@
+use super::{
+    document::{Body, DocumentBase},
+    util::XmlNodeExt,
+};
+use crate::{
+    error::{LimitViolationError, MaxOccurs, MissingChildNodeError},
+    shared::sharedtypes::OnOff,
+    xml::{parse_xml_bool, XmlNode, WORDPROCESSINGML_NAMESPACE},
+};
+use log::info;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum DocPartGallery {
+    #[strum(serialize = "placeholder")]
+    Placeholder,
+    #[strum(serialize = "any")]
+    Any,
+    #[strum(serialize = "default")]
+    Default,
+    #[strum(serialize = "docParts")]
+    DocParts,
+    #[strum(serialize = "coverPg")]
+    CoverPage,
+    #[strum(serialize = "eq")]
+    Equations,
+    #[strum(serialize = "ftrs")]
+    Footers,
+    #[strum(serialize = "hdrs")]
+    Headers,
+    #[strum(serialize = "pgNum")]
+    PageNumbers,
+    #[strum(serialize = "tbls")]
+    Tables,
+    #[strum(serialize = "watermarks")]
+    Watermarks,
+    #[strum(serialize = "autoTxt")]
+    AutoText,
+    #[strum(serialize = "txtBox")]
+    TextBox,
+    #[strum(serialize = "pgNumT")]
+    PageNumbersTop,
+    #[strum(serialize = "pgNumB")]
+    PageNumbersBottom,
+    #[strum(serialize = "pgNumMargins")]
+    PageNumbersMargins,
+    #[strum(serialize = "tblOfContents")]
+    TableOfContents,
+    #[strum(serialize = "bib")]
+    Bibliography,
+    #[strum(serialize = "custQuickParts")]
+    CustomQuickParts,
+    #[strum(serialize = "custCoverPg")]
+    CustomCoverPage,
+    #[strum(serialize = "custEq")]
+    CustomEquations,
+    #[strum(serialize = "custFtrs")]
+    CustomFooters,
+    #[strum(serialize = "custHdrs")]
+    CustomHeaders,
+    #[strum(serialize = "custPgNum")]
+    CustomPageNumbers,
+    #[strum(serialize = "custTbls")]
+    CustomTables,
+    #[strum(serialize = "custWatermarks")]
+    CustomWatermarks,
+    #[strum(serialize = "custAutoTxt")]
+    CustomAutoText,
+    #[strum(serialize = "custTxtBox")]
+    CustomTextBox,
+    #[strum(serialize = "custPgNumT")]
+    CustomPageNumbersTop,
+    #[strum(serialize = "custPgNumB")]
+    CustomPageNumbersBottom,
+    #[strum(serialize = "custPgNumMargins")]
+    CustomPageNumbersMargins,
+    #[strum(serialize = "custTblOfContents")]
+    CustomTableOfContents,
+    #[strum(serialize = "custBib")]
+    CustomBibliography,
+    #[strum(serialize = "custom1")]
+    Custom1,
+    #[strum(serialize = "custom2")]
+    Custom2,
+    #[strum(serialize = "custom3")]
+    Custom3,
+    #[strum(serialize = "custom4")]
+    Custom4,
+    #[strum(serialize = "custom5")]
+    Custom5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum DocPartType {
+    #[strum(serialize = "none")]
+    None,
+    #[strum(serialize = "normal")]
+    Normal,
+    #[strum(serialize = "autoExp")]
+    AutoExpand,
+    #[strum(serialize = "toolbar")]
+    Toolbar,
+    #[strum(serialize = "speller")]
+    Speller,
+    #[strum(serialize = "formFld")]
+    FormField,
+    #[strum(serialize = "bbPlcHdr")]
+    BuildingBlockPlaceholder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum DocPartBehavior {
+    #[strum(serialize = "content")]
+    Content,
+    #[strum(serialize = "p")]
+    Paragraph,
+    #[strum(serialize = "pg")]
+    Page,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocPartName {
+    pub value: String,
+    pub decorated: Option<OnOff>,
+}
+
+impl DocPartName {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPartName");
+
+        let value = xml_node.get_val_attribute()?.clone();
+        let decorated = xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "decorated")
+            .map(parse_xml_bool)
+            .transpose()?;
+
+        Ok(Self { value, decorated })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocPartCategory {
+    pub name: String,
+    pub gallery: DocPartGallery,
+}
+
+impl DocPartCategory {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPartCategory");
+
+        let mut name = None;
+        let mut gallery = None;
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "name" => name = Some(child_node.get_val_attribute()?.clone()),
+                "gallery" => gallery = Some(child_node.get_val_attribute()?.parse()?),
+                _ => (),
+            }
+        }
+
+        let name = name.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "name"))?;
+        let gallery = gallery.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "gallery"))?;
+
+        Ok(Self { name, gallery })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocPartTypes {
+    pub types: Vec<DocPartType>,
+    pub all: Option<OnOff>,
+}
+
+impl DocPartTypes {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPartTypes");
+
+        let all = xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "all")
+            .map(parse_xml_bool)
+            .transpose()?;
+
+        let types = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "type")
+            .map(|child_node| Ok(child_node.get_val_attribute()?.parse()?))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { types, all })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocPartBehaviors(pub Vec<DocPartBehavior>);
+
+impl DocPartBehaviors {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPartBehaviors");
+
+        let behaviors = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "behavior")
+            .map(|child_node| Ok(child_node.get_val_attribute()?.parse()?))
+            .collect::<Result<Vec<_>>>()?;
+
+        if !behaviors.is_empty() {
+            Ok(Self(behaviors))
+        } else {
+            Err(Box::new(LimitViolationError::new(
+                xml_node.name.clone(),
+                "behavior",
+                1,
+                MaxOccurs::Unbounded,
+                0,
+            )))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocPartPr {
+    pub name: Option<DocPartName>,
+    pub style: Option<String>,
+    pub category: Option<DocPartCategory>,
+    pub types: Option<DocPartTypes>,
+    pub behaviors: Option<DocPartBehaviors>,
+    pub description: Option<String>,
+    pub guid: Option<String>,
+}
+
+impl DocPartPr {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPartPr");
+
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "name" => instance.name = Some(DocPartName::from_xml_element(child_node)?),
+                "style" => instance.style = Some(child_node.get_val_attribute()?.clone()),
+                "category" => instance.category = Some(DocPartCategory::from_xml_element(child_node)?),
+                "types" => instance.types = Some(DocPartTypes::from_xml_element(child_node)?),
+                "behaviors" => instance.behaviors = Some(DocPartBehaviors::from_xml_element(child_node)?),
+                "description" => instance.description = Some(child_node.get_val_attribute()?.clone()),
+                "guid" => instance.guid = Some(child_node.get_val_attribute()?.clone()),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns true if this part is flagged as a placeholder building block
+    pub fn is_placeholder(&self) -> bool {
+        self.types
+            .as_ref()
+            .filter(|types| types.types.contains(&DocPartType::BuildingBlockPlaceholder))
+            .is_some()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocPart {
+    pub properties: Option<DocPartPr>,
+    pub body: Option<Body>,
+}
+
+impl DocPart {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocPart");
+
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "docPartPr" => instance.properties = Some(DocPartPr::from_xml_element(child_node)?),
+                "docPartBody" => instance.body = Some(Body::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.properties.as_ref()?.name.as_ref().map(|name| name.value.as_str())
+    }
+
+    pub fn gallery(&self) -> Option<DocPartGallery> {
+        self.properties
+            .as_ref()?
+            .category
+            .as_ref()
+            .map(|category| category.gallery)
+    }
+
+    pub fn category_name(&self) -> Option<&str> {
+        self.properties
+            .as_ref()?
+            .category
+            .as_ref()
+            .map(|category| category.name.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DocParts(pub Vec<DocPart>);
+
+impl DocParts {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DocParts");
+
+        let doc_parts = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "docPart")
+            .map(DocPart::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        if !doc_parts.is_empty() {
+            Ok(Self(doc_parts))
+        } else {
+            Err(Box::new(LimitViolationError::new(
+                xml_node.name.clone(),
+                "docPart",
+                1,
+                MaxOccurs::Unbounded,
+                0,
+            )))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GlossaryDocument {
+    pub base: DocumentBase,
+    pub doc_parts: Option<DocParts>,
+}
+
+impl GlossaryDocument {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing GlossaryDocument");
+
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "docParts" => instance.doc_parts = Some(DocParts::from_xml_element(child_node)?),
+                _ => instance.base = instance.base.try_update_from_xml_element(child_node)?,
+            }
+        }
+
+        Ok(instance)
+    }
+
+    pub fn doc_parts(&self) -> std::slice::Iter<'_, DocPart> {
+        match &self.doc_parts {
+            Some(doc_parts) => doc_parts.0.iter(),
+            None => [].iter(),
+        }
+    }
+
+    pub fn find_doc_part_by_name<T: AsRef<str>>(&self, name: T) -> Option<&DocPart> {
+        self.doc_parts().find(|doc_part| doc_part.name() == Some(name.as_ref()))
+    }
+
+    pub fn doc_parts_in_gallery(&self, gallery: DocPartGallery) -> impl Iterator<Item = &DocPart> {
+        self.doc_parts()
+            .filter(move |doc_part| doc_part.gallery() == Some(gallery))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    impl DocPartName {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name} w:val="Cover Page" w:decorated="false"></{node_name}>"#,
+                node_name = node_name,
+            )
+        }
+
+        pub fn test_instance() -> Self {
+            Self {
+                value: String::from("Cover Page"),
+                decorated: Some(false),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_doc_part_name_from_xml() {
+        let xml = DocPartName::test_xml("w:name");
+        assert_eq!(
+            DocPartName::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            DocPartName::test_instance(),
+        );
+    }
+
+    impl DocPartCategory {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                <w:name w:val="Built-In"></w:name>
+                <w:gallery w:val="coverPg"></w:gallery>
+            </{node_name}>"#,
+                node_name = node_name,
+            )
+        }
+
+        pub fn test_instance() -> Self {
+            Self {
+                name: String::from("Built-In"),
+                gallery: DocPartGallery::CoverPage,
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_doc_part_category_from_xml() {
+        let xml = DocPartCategory::test_xml("w:category");
+        assert_eq!(
+            DocPartCategory::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            DocPartCategory::test_instance(),
+        );
+    }
+
+    impl DocPartPr {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                {}
+                <w:style w:val="Normal"></w:style>
+                {}
+                <w:types w:all="false">
+                    <w:type w:val="bbPlcHdr"></w:type>
+                </w:types>
+                <w:behaviors>
+                    <w:behavior w:val="pg"></w:behavior>
+                </w:behaviors>
+                <w:description w:val="Sample cover page"></w:description>
+                <w:guid w:val="{{D5A2B6F1-0F3A-4B8E-9C52-3F6A1E2B7C90}}"></w:guid>
+            </{node_name}>"#,
+                DocPartName::test_xml("w:name"),
+                DocPartCategory::test_xml("w:category"),
+                node_name = node_name,
+            )
+        }
+
+        pub fn test_instance() -> Self {
+            Self {
+                name: Some(DocPartName::test_instance()),
+                style: Some(String::from("Normal")),
+                category: Some(DocPartCategory::test_instance()),
+                types: Some(DocPartTypes {
+                    types: vec![DocPartType::BuildingBlockPlaceholder],
+                    all: Some(false),
+                }),
+                behaviors: Some(DocPartBehaviors(vec![DocPartBehavior::Page])),
+                description: Some(String::from("Sample cover page")),
+                guid: Some(String::from("{D5A2B6F1-0F3A-4B8E-9C52-3F6A1E2B7C90}")),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_doc_part_pr_from_xml() {
+        let xml = DocPartPr::test_xml("w:docPartPr");
+        assert_eq!(
+            DocPartPr::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            DocPartPr::test_instance(),
+        );
+    }
+
+    impl DocPart {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                {}
+                {}
+            </{node_name}>"#,
+                DocPartPr::test_xml("w:docPartPr"),
+                Body::test_xml("w:docPartBody"),
+                node_name = node_name,
+            )
+        }
+
+        pub fn test_instance() -> Self {
+            Self {
+                properties: Some(DocPartPr::test_instance()),
+                body: Some(Body::test_instance()),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_doc_part_from_xml() {
+        let xml = DocPart::test_xml("w:docPart");
+        assert_eq!(
+            DocPart::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            DocPart::test_instance(),
+        );
+    }
+
+    impl GlossaryDocument {
+        pub fn test_xml(node_name: &'static str) -> String {
+            format!(
+                r#"<{node_name}>
+                {}
+                <w:docParts>
+                    {}
+                </w:docParts>
+            </{node_name}>"#,
+                DocumentBase::test_extension_xml(),
+                DocPart::test_xml("w:docPart"),
+                node_name = node_name,
+            )
+        }
+
+        pub fn test_instance() -> Self {
+            Self {
+                base: DocumentBase::test_instance(),
+                doc_parts: Some(DocParts(vec![DocPart::test_instance()])),
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_glossary_document_from_xml() {
+        let xml = GlossaryDocument::test_xml("w:glossaryDocument");
+        assert_eq!(
+            GlossaryDocument::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            GlossaryDocument::test_instance(),
+        );
+    }
+
+    #[test]
+    pub fn test_glossary_document_lookup() {
+        let glossary = GlossaryDocument::test_instance();
+        let doc_part = glossary.find_doc_part_by_name("Cover Page").unwrap();
+        assert_eq!(doc_part.gallery(), Some(DocPartGallery::CoverPage));
+        assert_eq!(doc_part.category_name(), Some("Built-In"));
+        assert!(doc_part.properties.as_ref().unwrap().is_placeholder());
+        assert_eq!(glossary.doc_parts_in_gallery(DocPartGallery::CoverPage).count(), 1);
+        assert_eq!(glossary.doc_parts_in_gallery(DocPartGallery::Headers).count(), 0);
+        assert!(glossary.find_doc_part_by_name("Missing").is_none());
+    }
+}
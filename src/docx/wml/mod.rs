@@ -1,9 +1,15 @@
+pub mod comments;
 pub mod document;
 pub mod drawing;
+pub mod extensions;
+pub mod fields;
+pub mod fonttable;
 pub mod footnotes;
+pub mod glossary;
 pub mod numbering;
 pub mod settings;
 pub mod simpletypes;
 pub mod styles;
 pub mod table;
 pub mod util;
+pub mod websettings;
@@ -16,8 +16,8 @@ type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 #[derive(Debug, Default)]
 pub struct PictureBase {
-    pub vml_element: Option<Box<dyn Any>>,
-    pub office_element: Option<Box<dyn Any>>,
+    pub vml_element: Option<Box<dyn Any + Send + Sync>>,
+    pub office_element: Option<Box<dyn Any + Send + Sync>>,
 }
 
 #[derive(Debug, Default)]
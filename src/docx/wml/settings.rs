@@ -21,12 +21,60 @@ pub type PixelsMeasure = UnsignedDecimalNumber;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum CryptProv {
+    #[strum(serialize = "rsaAES")]
+    RsaAes,
+    #[strum(serialize = "rsaFull")]
+    RsaFull,
+    #[strum(serialize = "custom")]
+    Custom,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum AlgClass {
+    #[strum(serialize = "hash")]
+    Hash,
+    #[strum(serialize = "custom")]
+    Custom,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum AlgType {
+    #[strum(serialize = "typeAny")]
+    TypeAny,
+    #[strum(serialize = "custom")]
+    Custom,
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Password {
     pub algorithm_name: Option<String>,
     pub hash_value: Option<Base64Binary>,
     pub salt_value: Option<Base64Binary>,
     pub spin_count: Option<DecimalNumber>,
+    /// The legacy hashing attributes written by Word 2007 and older, including transitional documents of newer
+    /// versions.
+    pub legacy: TransitionalPassword,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TransitionalPassword {
+    pub crypt_provider_type: Option<CryptProv>,
+    pub crypt_algorithm_class: Option<AlgClass>,
+    pub crypt_algorithm_type: Option<AlgType>,
+    pub crypt_algorithm_sid: Option<DecimalNumber>,
+    pub crypt_spin_count: Option<DecimalNumber>,
+    pub crypt_provider: Option<String>,
+    pub algorithm_id_extension: Option<LongHexNumber>,
+    pub algorithm_id_extension_source: Option<String>,
+    pub crypt_provider_type_extension: Option<LongHexNumber>,
+    pub crypt_provider_type_extension_source: Option<String>,
+    pub hash: Option<Base64Binary>,
+    pub salt: Option<Base64Binary>,
 }
 
 impl Password {
@@ -39,17 +87,39 @@ impl Password {
             .try_fold(Default::default(), Self::try_update_from_xml_attribute)
     }
 
-    pub fn try_update_from_xml_attribute(mut self, (attr, value): (&String, &String)) -> Result<Self> {
+    pub fn try_update_from_xml_attribute<K: AsRef<str> + ?Sized>(
+        mut self,
+        (attr, value): (&K, &String),
+    ) -> Result<Self> {
         match attr.as_ref() {
             "w:algorithmName" => self.algorithm_name = Some(value.clone()),
             "w:hashValue" => self.hash_value = Some(value.clone()),
             "w:saltValue" => self.salt_value = Some(value.clone()),
             "w:spinCount" => self.spin_count = Some(value.parse()?),
+            "w:cryptProviderType" => self.legacy.crypt_provider_type = Some(value.parse()?),
+            "w:cryptAlgorithmClass" => self.legacy.crypt_algorithm_class = Some(value.parse()?),
+            "w:cryptAlgorithmType" => self.legacy.crypt_algorithm_type = Some(value.parse()?),
+            "w:cryptAlgorithmSid" => self.legacy.crypt_algorithm_sid = Some(value.parse()?),
+            "w:cryptSpinCount" => self.legacy.crypt_spin_count = Some(value.parse()?),
+            "w:cryptProvider" => self.legacy.crypt_provider = Some(value.clone()),
+            "w:algIdExt" => self.legacy.algorithm_id_extension = Some(LongHexNumber::from_str_radix(value, 16)?),
+            "w:algIdExtSource" => self.legacy.algorithm_id_extension_source = Some(value.clone()),
+            "w:cryptProviderTypeExt" => {
+                self.legacy.crypt_provider_type_extension = Some(LongHexNumber::from_str_radix(value, 16)?)
+            }
+            "w:cryptProviderTypeExtSource" => self.legacy.crypt_provider_type_extension_source = Some(value.clone()),
+            "w:hash" => self.legacy.hash = Some(value.clone()),
+            "w:salt" => self.legacy.salt = Some(value.clone()),
             _ => (),
         }
 
         Ok(self)
     }
+
+    /// Returns whether a password hash is set, either with the agile or the legacy attributes.
+    pub fn is_set(&self) -> bool {
+        self.hash_value.is_some() || self.legacy.hash.is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -568,8 +638,8 @@ impl DocProtect {
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
                     "w:edit" => instance.edit = Some(value.parse()?),
-                    "w:formatting" => instance.formatting = Some(value.parse()?),
-                    "w:enforcement" => instance.enforcement = Some(value.parse()?),
+                    "w:formatting" => instance.formatting = Some(parse_xml_bool(value)?),
+                    "w:enforcement" => instance.enforcement = Some(parse_xml_bool(value)?),
                     _ => instance.password = instance.password.try_update_from_xml_attribute((attr, value))?,
                 }
 
@@ -1527,6 +1597,7 @@ mod tests {
                 hash_value: Some(Base64Binary::from("Some hash")),
                 salt_value: Some(Base64Binary::from("Some salt")),
                 spin_count: Some(1),
+                legacy: Default::default(),
             }
         }
     }
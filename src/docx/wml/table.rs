@@ -10,7 +10,7 @@ use super::{
 use crate::{
     error::{LimitViolationError, MaxOccurs, MissingAttributeError, MissingChildNodeError, NotGroupMemberError},
     shared::sharedtypes::{OnOff, TwipsMeasure, XAlign, XmlName, YAlign},
-    xml::{parse_xml_bool, XmlNode},
+    xml::{parse_xml_bool, XmlNode, WORDPROCESSINGML_NAMESPACE},
     xsdtypes::{XsdChoice, XsdType},
 };
 use log::info;
@@ -25,7 +25,7 @@ pub enum TblOverlap {
     Overlap,
 }
 
-#[derive(Debug, Clone, PartialEq, EnumString)]
+#[derive(Debug, Clone, PartialEq, EnumString, AsRefStr)]
 pub enum TblWidthType {
     #[strum(serialize = "nil")]
     NoWidth,
@@ -118,8 +118,10 @@ pub enum JcTable {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TblBorders {
     pub top: Option<Border>,
+    /// Parsed from `w:left` in transitional documents
     pub start: Option<Border>,
     pub bottom: Option<Border>,
+    /// Parsed from `w:right` in transitional documents
     pub end: Option<Border>,
     pub inside_horizontal: Option<Border>,
     pub inside_vertical: Option<Border>,
@@ -134,9 +136,9 @@ impl TblBorders {
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
                 "top" => instance.top = Some(Border::from_xml_element(child_node)?),
-                "start" => instance.start = Some(Border::from_xml_element(child_node)?),
+                "start" | "left" => instance.start = Some(Border::from_xml_element(child_node)?),
                 "bottom" => instance.bottom = Some(Border::from_xml_element(child_node)?),
-                "end" => instance.end = Some(Border::from_xml_element(child_node)?),
+                "end" | "right" => instance.end = Some(Border::from_xml_element(child_node)?),
                 "insideH" => instance.inside_horizontal = Some(Border::from_xml_element(child_node)?),
                 "insideV" => instance.inside_vertical = Some(Border::from_xml_element(child_node)?),
                 _ => (),
@@ -158,8 +160,10 @@ pub enum TblLayoutType {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TblCellMar {
     pub top: Option<TblWidth>,
+    /// Parsed from `w:left` in transitional documents
     pub start: Option<TblWidth>,
     pub bottom: Option<TblWidth>,
+    /// Parsed from `w:right` in transitional documents
     pub end: Option<TblWidth>,
 }
 
@@ -172,9 +176,9 @@ impl TblCellMar {
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
                 "top" => instance.top = Some(TblWidth::from_xml_element(child_node)?),
-                "start" => instance.start = Some(TblWidth::from_xml_element(child_node)?),
+                "start" | "left" => instance.start = Some(TblWidth::from_xml_element(child_node)?),
                 "bottom" => instance.bottom = Some(TblWidth::from_xml_element(child_node)?),
-                "end" => instance.end = Some(TblWidth::from_xml_element(child_node)?),
+                "end" | "right" => instance.end = Some(TblWidth::from_xml_element(child_node)?),
                 _ => (),
             }
         }
@@ -329,7 +333,10 @@ impl TblGridCol {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         info!("parsing TblGridCol");
 
-        let width = xml_node.attributes.get("w:w").map(|value| value.parse()).transpose()?;
+        let width = xml_node
+            .attribute_ns(WORDPROCESSINGML_NAMESPACE, "w")
+            .map(|value| value.parse())
+            .transpose()?;
 
         Ok(Self { width })
     }
@@ -592,8 +599,10 @@ pub enum Merge {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TcBorders {
     pub top: Option<Border>,
+    /// Parsed from `w:left` in transitional documents
     pub start: Option<Border>,
     pub bottom: Option<Border>,
+    /// Parsed from `w:right` in transitional documents
     pub end: Option<Border>,
     pub inside_horizontal: Option<Border>,
     pub inside_vertical: Option<Border>,
@@ -610,9 +619,9 @@ impl TcBorders {
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
                 "top" => instance.top = Some(Border::from_xml_element(child_node)?),
-                "start" => instance.start = Some(Border::from_xml_element(child_node)?),
+                "start" | "left" => instance.start = Some(Border::from_xml_element(child_node)?),
                 "bottom" => instance.bottom = Some(Border::from_xml_element(child_node)?),
-                "end" => instance.end = Some(Border::from_xml_element(child_node)?),
+                "end" | "right" => instance.end = Some(Border::from_xml_element(child_node)?),
                 "insideH" => instance.inside_horizontal = Some(Border::from_xml_element(child_node)?),
                 "insideV" => instance.inside_vertical = Some(Border::from_xml_element(child_node)?),
                 "tl2br" => instance.top_left_to_bottom_right = Some(Border::from_xml_element(child_node)?),
@@ -628,8 +637,10 @@ impl TcBorders {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct TcMar {
     pub top: Option<TblWidth>,
+    /// Parsed from `w:left` in transitional documents
     pub start: Option<TblWidth>,
     pub bottom: Option<TblWidth>,
+    /// Parsed from `w:right` in transitional documents
     pub end: Option<TblWidth>,
 }
 
@@ -642,9 +653,9 @@ impl TcMar {
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
                 "top" => instance.top = Some(TblWidth::from_xml_element(child_node)?),
-                "start" => instance.start = Some(TblWidth::from_xml_element(child_node)?),
+                "start" | "left" => instance.start = Some(TblWidth::from_xml_element(child_node)?),
                 "bottom" => instance.bottom = Some(TblWidth::from_xml_element(child_node)?),
-                "end" => instance.end = Some(TblWidth::from_xml_element(child_node)?),
+                "end" | "right" => instance.end = Some(TblWidth::from_xml_element(child_node)?),
                 _ => (),
             }
         }
@@ -888,7 +899,7 @@ impl Tc {
 
         let mut instance: Self = Default::default();
 
-        instance.id = xml_node.attributes.get("w:id").cloned();
+        instance.id = xml_node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "id").cloned();
 
         for child_node in &xml_node.child_nodes {
             match child_node.local_name() {
@@ -1054,6 +1065,10 @@ pub struct Row {
     pub run_revision_id: Option<LongHexNumber>,
     pub deletion_revision_id: Option<LongHexNumber>,
     pub row_revision_id: Option<LongHexNumber>,
+    /// The `w14:paraId` of the row.
+    pub paragraph_id: Option<LongHexNumber>,
+    /// The `w14:textId` of the row.
+    pub text_id: Option<LongHexNumber>,
 }
 
 impl Row {
@@ -1068,6 +1083,8 @@ impl Row {
                 "w:rsidR" => instance.run_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 "w:rsidDel" => instance.deletion_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 "w:rsidTr" => instance.row_revision_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w14:paraId" => instance.paragraph_id = Some(LongHexNumber::from_str_radix(value, 16)?),
+                "w14:textId" => instance.text_id = Some(LongHexNumber::from_str_radix(value, 16)?),
                 _ => (),
             }
         }
@@ -1427,6 +1444,21 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_tbl_cell_mar_from_transitional_xml() {
+        let xml = format!(
+            "<tblCellMar>{}{}{}{}</tblCellMar>",
+            TblWidth::test_xml("top"),
+            TblWidth::test_xml("left"),
+            TblWidth::test_xml("bottom"),
+            TblWidth::test_xml("right"),
+        );
+        assert_eq!(
+            TblCellMar::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap(),
+            TblCellMar::test_instance(),
+        );
+    }
+
     impl TblLook {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
@@ -2343,7 +2375,7 @@ mod tests {
     impl Row {
         pub fn test_xml(node_name: &'static str) -> String {
             format!(
-                r#"<{node_name} w:rsidRPr="ffffffff" w:rsidR="fefefefe" w:rsidDel="fdfdfdfd" w:rsidTr="fcfcfcfc">
+                r#"<{node_name} w:rsidRPr="ffffffff" w:rsidR="fefefefe" w:rsidDel="fdfdfdfd" w:rsidTr="fcfcfcfc" w14:paraId="0A0B0C0D" w14:textId="0E0F1011">
                 {}
                 {}
                 {}
@@ -2364,6 +2396,8 @@ mod tests {
                 run_revision_id: Some(0xfefefefe),
                 deletion_revision_id: Some(0xfdfdfdfd),
                 row_revision_id: Some(0xfcfcfcfc),
+                paragraph_id: Some(0x0a0b0c0d),
+                text_id: Some(0x0e0f1011),
             }
         }
     }
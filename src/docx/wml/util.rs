@@ -1,4 +1,7 @@
-use crate::{error::MissingAttributeError, xml::XmlNode};
+use crate::{
+    error::MissingAttributeError,
+    xml::{XmlNode, WORDPROCESSINGML_NAMESPACE},
+};
 
 pub(crate) trait XmlNodeExt {
     // It's a common pattern throughout the OpenOffice XML file format that a simple type is wrapped in a complex type
@@ -9,8 +12,7 @@ pub(crate) trait XmlNodeExt {
 
 impl XmlNodeExt for XmlNode {
     fn get_val_attribute(&self) -> std::result::Result<&String, MissingAttributeError> {
-        self.attributes
-            .get("w:val")
+        self.attribute_ns(WORDPROCESSINGML_NAMESPACE, "val")
             .ok_or_else(|| MissingAttributeError::new(self.name.clone(), "val"))
     }
 }
@@ -0,0 +1,427 @@
+use super::{
+    document::{Border, HexColor, SignedTwipsMeasure},
+    settings::PixelsMeasure,
+    simpletypes::{parse_on_off_xml_element, DecimalNumber},
+    util::XmlNodeExt,
+};
+use crate::{
+    error::MissingAttributeError,
+    shared::{
+        relationship::RelationshipId,
+        sharedtypes::{OnOff, TwipsMeasure},
+    },
+    xml::XmlNode,
+};
+use log::info;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DivBdr {
+    pub top: Option<Border>,
+    pub left: Option<Border>,
+    pub bottom: Option<Border>,
+    pub right: Option<Border>,
+}
+
+impl DivBdr {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing DivBdr");
+
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "top" => instance.top = Some(Border::from_xml_element(child_node)?),
+                "left" => instance.left = Some(Border::from_xml_element(child_node)?),
+                "bottom" => instance.bottom = Some(Border::from_xml_element(child_node)?),
+                "right" => instance.right = Some(Border::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+/// An HTML `div` the paragraphs and tables referencing its id were imported from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Div {
+    pub id: DecimalNumber,
+    /// Whether the div is a `blockquote` element.
+    pub block_quote: Option<OnOff>,
+    /// Whether the div is the `body` element.
+    pub body_div: Option<OnOff>,
+    pub left_margin: SignedTwipsMeasure,
+    pub right_margin: SignedTwipsMeasure,
+    pub top_margin: SignedTwipsMeasure,
+    pub bottom_margin: SignedTwipsMeasure,
+    pub borders: Option<DivBdr>,
+    /// The divs nested in the div.
+    pub child_divs: Vec<Div>,
+}
+
+impl Div {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing Div");
+
+        let id = xml_node
+            .attributes
+            .get("w:id")
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "w:id"))?
+            .parse()?;
+
+        let mut block_quote = None;
+        let mut body_div = None;
+        let mut left_margin = None;
+        let mut right_margin = None;
+        let mut top_margin = None;
+        let mut bottom_margin = None;
+        let mut borders = None;
+        let mut child_divs = Vec::new();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "blockQuote" => block_quote = Some(parse_on_off_xml_element(child_node)?),
+                "bodyDiv" => body_div = Some(parse_on_off_xml_element(child_node)?),
+                "marLeft" => left_margin = Some(SignedTwipsMeasure::from_xml_element(child_node)?),
+                "marRight" => right_margin = Some(SignedTwipsMeasure::from_xml_element(child_node)?),
+                "marTop" => top_margin = Some(SignedTwipsMeasure::from_xml_element(child_node)?),
+                "marBottom" => bottom_margin = Some(SignedTwipsMeasure::from_xml_element(child_node)?),
+                "divBdr" => borders = Some(DivBdr::from_xml_element(child_node)?),
+                "divsChild" => child_divs = parse_divs(child_node)?,
+                _ => (),
+            }
+        }
+
+        let missing_margin = |name| MissingAttributeError::new(xml_node.name.clone(), name);
+        Ok(Self {
+            id,
+            block_quote,
+            body_div,
+            left_margin: left_margin.ok_or_else(|| missing_margin("marLeft"))?,
+            right_margin: right_margin.ok_or_else(|| missing_margin("marRight"))?,
+            top_margin: top_margin.ok_or_else(|| missing_margin("marTop"))?,
+            bottom_margin: bottom_margin.ok_or_else(|| missing_margin("marBottom"))?,
+            borders,
+            child_divs,
+        })
+    }
+
+    /// Returns the div with the id, searching the div and the divs nested in it.
+    pub fn find(&self, id: DecimalNumber) -> Option<&Div> {
+        if self.id == id {
+            Some(self)
+        } else {
+            self.child_divs.iter().find_map(|div| div.find(id))
+        }
+    }
+}
+
+fn parse_divs(xml_node: &XmlNode) -> Result<Vec<Div>> {
+    xml_node
+        .child_nodes
+        .iter()
+        .filter(|child_node| child_node.local_name() == "div")
+        .map(Div::from_xml_element)
+        .collect()
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum FrameLayout {
+    #[strum(serialize = "rows")]
+    Rows,
+    #[strum(serialize = "cols")]
+    Columns,
+    #[strum(serialize = "none")]
+    None,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum FrameScrollbar {
+    #[strum(serialize = "on")]
+    On,
+    #[strum(serialize = "off")]
+    Off,
+    #[strum(serialize = "auto")]
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FramesetSplitbar {
+    pub width: Option<TwipsMeasure>,
+    pub color: Option<HexColor>,
+    pub no_border: Option<OnOff>,
+    pub flat_borders: Option<OnOff>,
+}
+
+impl FramesetSplitbar {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing FramesetSplitbar");
+
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "w" => instance.width = Some(child_node.get_val_attribute()?.parse()?),
+                "color" => instance.color = Some(child_node.get_val_attribute()?.parse()?),
+                "noBorder" => instance.no_border = Some(parse_on_off_xml_element(child_node)?),
+                "flatBorders" => instance.flat_borders = Some(parse_on_off_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+/// A frame of a frames page, displaying the document it's linked to.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Frame {
+    /// The size of the frame as an HTML length, e.g. `50%` or `*`.
+    pub size: Option<String>,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub long_description_rel_id: Option<RelationshipId>,
+    /// The relationship targeting the document displayed by the frame.
+    pub source_file_rel_id: Option<RelationshipId>,
+    pub margin_width: Option<PixelsMeasure>,
+    pub margin_height: Option<PixelsMeasure>,
+    pub scrollbar: Option<FrameScrollbar>,
+    pub no_resize_allowed: Option<OnOff>,
+    pub linked_to_file: Option<OnOff>,
+}
+
+impl Frame {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing Frame");
+
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "sz" => instance.size = Some(child_node.get_val_attribute()?.clone()),
+                "name" => instance.name = Some(child_node.get_val_attribute()?.clone()),
+                "title" => instance.title = Some(child_node.get_val_attribute()?.clone()),
+                "longDesc" => instance.long_description_rel_id = child_node.attributes.get("r:id").cloned(),
+                "sourceFileName" => instance.source_file_rel_id = child_node.attributes.get("r:id").cloned(),
+                "marW" => instance.margin_width = Some(child_node.get_val_attribute()?.parse()?),
+                "marH" => instance.margin_height = Some(child_node.get_val_attribute()?.parse()?),
+                "scrollbar" => instance.scrollbar = Some(child_node.get_val_attribute()?.parse()?),
+                "noResizeAllowed" => instance.no_resize_allowed = Some(parse_on_off_xml_element(child_node)?),
+                "linkedToFile" => instance.linked_to_file = Some(parse_on_off_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FramesetChoice {
+    Frameset(Box<Frameset>),
+    Frame(Frame),
+}
+
+/// The layout of a frames page, splitting the window into frames and nested framesets.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Frameset {
+    pub size: Option<String>,
+    pub splitbar: Option<FramesetSplitbar>,
+    pub layout: Option<FrameLayout>,
+    pub title: Option<String>,
+    pub contents: Vec<FramesetChoice>,
+}
+
+impl Frameset {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing Frameset");
+
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "sz" => instance.size = Some(child_node.get_val_attribute()?.clone()),
+                "framesetSplitbar" => instance.splitbar = Some(FramesetSplitbar::from_xml_element(child_node)?),
+                "frameLayout" => instance.layout = Some(child_node.get_val_attribute()?.parse()?),
+                "title" => instance.title = Some(child_node.get_val_attribute()?.clone()),
+                "frameset" => instance
+                    .contents
+                    .push(FramesetChoice::Frameset(Box::new(Frameset::from_xml_element(
+                        child_node,
+                    )?))),
+                "frame" => instance
+                    .contents
+                    .push(FramesetChoice::Frame(Frame::from_xml_element(child_node)?)),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns the frames of the frameset and its nested framesets in document order.
+    pub fn frames(&self) -> Vec<&Frame> {
+        let mut frames = Vec::new();
+        for content in &self.contents {
+            match content {
+                FramesetChoice::Frameset(frameset) => frames.extend(frameset.frames()),
+                FramesetChoice::Frame(frame) => frames.push(frame),
+            }
+        }
+
+        frames
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum TargetScreenSz {
+    #[strum(serialize = "544x376")]
+    Size544x376,
+    #[strum(serialize = "640x480")]
+    Size640x480,
+    #[strum(serialize = "720x512")]
+    Size720x512,
+    #[strum(serialize = "800x600")]
+    Size800x600,
+    #[strum(serialize = "1024x768")]
+    Size1024x768,
+    #[strum(serialize = "1152x882")]
+    Size1152x882,
+    #[strum(serialize = "1152x900")]
+    Size1152x900,
+    #[strum(serialize = "1280x1024")]
+    Size1280x1024,
+    #[strum(serialize = "1600x1200")]
+    Size1600x1200,
+    #[strum(serialize = "1800x1440")]
+    Size1800x1440,
+    #[strum(serialize = "1920x1200")]
+    Size1920x1200,
+}
+
+/// The settings used when the document is saved as a web page (`word/webSettings.xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebSettings {
+    pub frameset: Option<Frameset>,
+    /// The HTML divs referenced by the `w:divId` of paragraphs and table rows, see [`WebSettings::div`].
+    pub divs: Vec<Div>,
+    pub encoding: Option<String>,
+    pub optimize_for_browser: Option<OnOff>,
+    /// The browser the document is optimized for.
+    pub optimize_for_browser_target: Option<String>,
+    pub rely_on_vml: Option<OnOff>,
+    pub allow_png: Option<OnOff>,
+    pub do_not_rely_on_css: Option<OnOff>,
+    pub do_not_save_as_single_file: Option<OnOff>,
+    pub do_not_organize_in_folder: Option<OnOff>,
+    pub do_not_use_long_file_names: Option<OnOff>,
+    pub pixels_per_inch: Option<DecimalNumber>,
+    pub target_screen_size: Option<TargetScreenSz>,
+    pub save_smart_tags_as_xml: Option<OnOff>,
+}
+
+impl WebSettings {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing WebSettings");
+
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "frameset" => instance.frameset = Some(Frameset::from_xml_element(child_node)?),
+                "divs" => instance.divs = parse_divs(child_node)?,
+                "encoding" => instance.encoding = Some(child_node.get_val_attribute()?.clone()),
+                "optimizeForBrowser" => {
+                    instance.optimize_for_browser = Some(parse_on_off_xml_element(child_node)?);
+                    instance.optimize_for_browser_target = child_node.attributes.get("w:target").cloned();
+                }
+                "relyOnVML" => instance.rely_on_vml = Some(parse_on_off_xml_element(child_node)?),
+                "allowPNG" => instance.allow_png = Some(parse_on_off_xml_element(child_node)?),
+                "doNotRelyOnCSS" => instance.do_not_rely_on_css = Some(parse_on_off_xml_element(child_node)?),
+                "doNotSaveAsSingleFile" => {
+                    instance.do_not_save_as_single_file = Some(parse_on_off_xml_element(child_node)?)
+                }
+                "doNotOrganizeInFolder" => {
+                    instance.do_not_organize_in_folder = Some(parse_on_off_xml_element(child_node)?)
+                }
+                "doNotUseLongFileNames" => {
+                    instance.do_not_use_long_file_names = Some(parse_on_off_xml_element(child_node)?)
+                }
+                "pixelsPerInch" => instance.pixels_per_inch = Some(child_node.get_val_attribute()?.parse()?),
+                "targetScreenSz" => instance.target_screen_size = Some(child_node.get_val_attribute()?.parse()?),
+                "saveSmartTagsAsXml" => instance.save_smart_tags_as_xml = Some(parse_on_off_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns the div with the id, including the nested divs.
+    pub fn div(&self, id: DecimalNumber) -> Option<&Div> {
+        self.divs.iter().find_map(|div| div.find(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_web_settings_from_xml() {
+        let xml = r#"<w:webSettings>
+            <w:frameset>
+                <w:frameLayout w:val="cols" />
+                <w:frame><w:sz w:val="25%" /><w:name w:val="toc" /><w:sourceFileName r:id="rId1" /></w:frame>
+                <w:frameset>
+                    <w:frame><w:name w:val="main" /><w:scrollbar w:val="auto" /><w:noResizeAllowed /></w:frame>
+                </w:frameset>
+            </w:frameset>
+            <w:divs>
+                <w:div w:id="1">
+                    <w:bodyDiv />
+                    <w:marLeft w:val="0" />
+                    <w:marRight w:val="0" />
+                    <w:marTop w:val="0" />
+                    <w:marBottom w:val="0" />
+                    <w:divBdr><w:top w:val="single" w:sz="4" w:color="auto" /></w:divBdr>
+                    <w:divsChild>
+                        <w:div w:id="2">
+                            <w:blockQuote w:val="true" />
+                            <w:marLeft w:val="720" />
+                            <w:marRight w:val="720" />
+                            <w:marTop w:val="100" />
+                            <w:marBottom w:val="100" />
+                        </w:div>
+                    </w:divsChild>
+                </w:div>
+            </w:divs>
+            <w:optimizeForBrowser w:target="HTML4" />
+            <w:allowPNG />
+            <w:targetScreenSz w:val="1024x768" />
+        </w:webSettings>"#;
+
+        let web_settings = WebSettings::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(web_settings.optimize_for_browser, Some(true));
+        assert_eq!(web_settings.optimize_for_browser_target.as_deref(), Some("HTML4"));
+        assert_eq!(web_settings.allow_png, Some(true));
+        assert_eq!(web_settings.target_screen_size, Some(TargetScreenSz::Size1024x768));
+
+        let frameset = web_settings.frameset.as_ref().unwrap();
+        assert_eq!(frameset.layout, Some(FrameLayout::Columns));
+        let frames = frameset.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].size.as_deref(), Some("25%"));
+        assert_eq!(frames[0].source_file_rel_id.as_deref(), Some("rId1"));
+        assert_eq!(frames[1].scrollbar, Some(FrameScrollbar::Auto));
+        assert_eq!(frames[1].no_resize_allowed, Some(true));
+
+        let body_div = web_settings.div(1).unwrap();
+        assert_eq!(body_div.body_div, Some(true));
+        assert!(body_div.borders.unwrap().top.is_some());
+        let quote_div = web_settings.div(2).unwrap();
+        assert_eq!(quote_div.block_quote, Some(true));
+        assert_eq!(quote_div.left_margin, SignedTwipsMeasure::Decimal(720));
+        assert!(web_settings.div(3).is_none());
+    }
+}
@@ -0,0 +1,874 @@
+use super::wml::{
+    document::{
+        BlockLevelElts, ContentBlockContent, ContentRunContent, DecimalNumberOrPercent, HexColor, HpsMeasure,
+        MeasurementOrPercent, NumPr, PContent, PPr, PPrBase, RPr, RPrBase, RunInnerContent, SectPr, SignedTwipsMeasure,
+        P, R,
+    },
+    table::{ContentCellContent, ContentRowContent, Row, Tbl, TblPr, TblPrBase, TblWidth, Tc, TcPr, TcPrBase},
+};
+use crate::{
+    error::StreamWriteError,
+    shared::{
+        contenttypes::CONTENT_TYPES_PART_NAME,
+        sharedtypes::{TwipsMeasure, UniversalMeasure},
+    },
+    xml::{escape_xml, known_namespace, XmlExtras, XmlNode},
+};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Write as _,
+    io::{Seek, Write},
+    sync::Arc,
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+const CONTENT_TYPES_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>"#,
+    "</Types>",
+);
+
+const PACKAGE_RELATIONSHIPS_XML: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>"#,
+    "</Relationships>",
+);
+
+const DOCUMENT_START: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main""#,
+    r#" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#,
+    r#" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml">"#,
+    "<w:body>",
+);
+
+const DOCUMENT_NAMESPACE_PREFIXES: [&str; 3] = ["w", "r", "w14"];
+
+/// Writes a document package element by element, straight into the compressed `word/document.xml` entry. Only the
+/// element being written is kept in memory, so tables with hundreds of thousands of rows can be generated.
+///
+/// The writer supports the content produced by the builders: paragraphs with style, alignment, numbering and outline
+/// level, runs with text, tabs, breaks and basic formatting, tables with cell widths and grid spans and the page
+/// setup of the section. Other content results in a [`StreamWriteError::UnsupportedContent`] error.
+///
+/// ```
+/// use oox::docx::{
+///     pagesetup::PageSetup,
+///     wml::{document::{P, SectPr}, table::{Row, Tbl}},
+///     writer::DocumentStreamWriter,
+/// };
+/// use std::io::Cursor;
+///
+/// let mut writer = DocumentStreamWriter::new(Cursor::new(Vec::new())).unwrap();
+/// writer.write_paragraph(&P::builder().style("Title").text("Report").build()).unwrap();
+///
+/// writer.start_table(&Tbl::builder().column_widths(vec![2000, 2000]).build()).unwrap();
+/// for idx in 0..1000 {
+///     let row = Row::builder().text_cell(idx.to_string()).text_cell("value").build();
+///     writer.write_row(&row).unwrap();
+/// }
+/// writer.end_table().unwrap();
+///
+/// let bytes = writer
+///     .finish(Some(&SectPr::default().with_page_setup(PageSetup::A4_PORTRAIT)))
+///     .unwrap()
+///     .into_inner();
+/// assert!(bytes.starts_with(b"PK"));
+/// ```
+pub struct DocumentStreamWriter<W: Write + Seek> {
+    zip_writer: ZipWriter<W>,
+    table_depth: usize,
+    buffer: String,
+}
+
+impl<W: Write + Seek> DocumentStreamWriter<W> {
+    /// Writes the package parts preceding the main document and starts the body of the document.
+    pub fn new(inner: W) -> Result<Self> {
+        let mut zip_writer = ZipWriter::new(inner);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip_writer.start_file(CONTENT_TYPES_PART_NAME, options)?;
+        zip_writer.write_all(CONTENT_TYPES_XML.as_bytes())?;
+        zip_writer.start_file("_rels/.rels", options)?;
+        zip_writer.write_all(PACKAGE_RELATIONSHIPS_XML.as_bytes())?;
+        zip_writer.start_file("word/document.xml", options)?;
+        zip_writer.write_all(DOCUMENT_START.as_bytes())?;
+
+        Ok(Self {
+            zip_writer,
+            table_depth: 0,
+            buffer: String::new(),
+        })
+    }
+
+    pub fn write_paragraph(&mut self, paragraph: &P) -> Result<()> {
+        self.ensure_no_open_table()?;
+        write_paragraph(&mut self.buffer, paragraph)?;
+        self.flush()
+    }
+
+    /// Writes a complete table.
+    pub fn write_table(&mut self, table: &Tbl) -> Result<()> {
+        self.ensure_no_open_table()?;
+        write_table(&mut self.buffer, table)?;
+        self.flush()
+    }
+
+    /// Writes a block level element of the body. Only paragraphs and tables are supported.
+    pub fn write_block(&mut self, block: &BlockLevelElts) -> Result<()> {
+        self.ensure_no_open_table()?;
+        write_block(&mut self.buffer, block)?;
+        self.flush()
+    }
+
+    /// Starts a table with the properties and grid of `table`. The rows of `table` are written immediately, further
+    /// rows can be appended with [`write_row`](Self::write_row) until the table is ended.
+    pub fn start_table(&mut self, table: &Tbl) -> Result<()> {
+        self.ensure_no_open_table()?;
+        write_table_start(&mut self.buffer, table)?;
+        for row_content in &table.row_contents {
+            write_row_content(&mut self.buffer, row_content)?;
+        }
+
+        self.table_depth += 1;
+        self.flush()
+    }
+
+    pub fn write_row(&mut self, row: &Row) -> Result<()> {
+        if self.table_depth == 0 {
+            return Err(Box::new(StreamWriteError::NoOpenTable));
+        }
+
+        write_row(&mut self.buffer, row)?;
+        self.flush()
+    }
+
+    pub fn end_table(&mut self) -> Result<()> {
+        if self.table_depth == 0 {
+            return Err(Box::new(StreamWriteError::NoOpenTable));
+        }
+
+        self.table_depth -= 1;
+        self.buffer.push_str("</w:tbl>");
+        self.flush()
+    }
+
+    /// Writes the properties of the last section, closes the document and finishes the package. Returns the
+    /// underlying writer.
+    pub fn finish(mut self, section_properties: Option<&SectPr>) -> Result<W> {
+        self.ensure_no_open_table()?;
+        if let Some(section_properties) = section_properties {
+            write_section_properties(&mut self.buffer, section_properties)?;
+        }
+
+        self.buffer.push_str("</w:body></w:document>");
+        self.flush()?;
+
+        Ok(self.zip_writer.finish()?)
+    }
+
+    fn ensure_no_open_table(&self) -> Result<()> {
+        if self.table_depth > 0 {
+            Err(Box::new(StreamWriteError::TableAlreadyOpen))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.zip_writer.write_all(self.buffer.as_bytes())?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+fn unsupported(element: &'static str) -> Box<dyn Error> {
+    Box::new(StreamWriteError::UnsupportedContent(element))
+}
+
+fn write_block(xml: &mut String, block: &BlockLevelElts) -> Result<()> {
+    match block {
+        BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => write_paragraph(xml, paragraph),
+        BlockLevelElts::Chunk(ContentBlockContent::Table(table)) => write_table(xml, table),
+        BlockLevelElts::Chunk(ContentBlockContent::CustomXml(_)) => Err(unsupported("w:customXml")),
+        BlockLevelElts::Chunk(ContentBlockContent::Sdt(_)) => Err(unsupported("w:sdt")),
+        BlockLevelElts::Chunk(ContentBlockContent::RunLevelElement(_)) => Err(unsupported("run level element")),
+        BlockLevelElts::AltChunk(_) => Err(unsupported("w:altChunk")),
+    }
+}
+
+fn write_paragraph(xml: &mut String, paragraph: &P) -> Result<()> {
+    xml.push_str("<w:p");
+    if let Some(paragraph_id) = paragraph.paragraph_id {
+        write!(xml, r#" w14:paraId="{:08X}""#, paragraph_id)?;
+    }
+    if let Some(text_id) = paragraph.text_id {
+        write!(xml, r#" w14:textId="{:08X}""#, text_id)?;
+    }
+    xml.push('>');
+
+    if let Some(properties) = &paragraph.properties {
+        write_paragraph_properties(xml, properties)?;
+    }
+
+    for content in &paragraph.contents {
+        match content {
+            PContent::ContentRunContent(content) => match content.as_ref() {
+                ContentRunContent::Run(run) => write_run(xml, run)?,
+                _ => return Err(unsupported("run content other than w:r")),
+            },
+            PContent::SimpleField(_) => return Err(unsupported("w:fldSimple")),
+            PContent::Hyperlink(_) => return Err(unsupported("w:hyperlink")),
+            PContent::SubDocument(_) => return Err(unsupported("w:subDoc")),
+        }
+    }
+
+    xml.push_str("</w:p>");
+    Ok(())
+}
+
+fn write_paragraph_properties(xml: &mut String, properties: &PPr) -> Result<()> {
+    ensure_supported_paragraph_properties(properties)?;
+
+    let base = &properties.base;
+    xml.push_str("<w:pPr");
+    write_extra_attributes(xml, &properties.extras);
+    xml.push('>');
+
+    if let Some(style) = &base.style {
+        write!(xml, r#"<w:pStyle w:val="{}"/>"#, escape_xml(style))?;
+    }
+    if let Some(keep_with_next) = base.keep_with_next {
+        write_on_off(xml, "w:keepNext", keep_with_next);
+    }
+    if let Some(numbering_properties) = &base.numbering_properties {
+        xml.push_str("<w:numPr>");
+        if let Some(level) = numbering_properties.indent_level {
+            write!(xml, r#"<w:ilvl w:val="{}"/>"#, level)?;
+        }
+        if let Some(numbering_id) = numbering_properties.numbering_id {
+            write!(xml, r#"<w:numId w:val="{}"/>"#, numbering_id)?;
+        }
+        xml.push_str("</w:numPr>");
+    }
+    if let Some(alignment) = &base.alignment {
+        write!(xml, r#"<w:jc w:val="{}"/>"#, alignment.as_ref())?;
+    }
+    if let Some(outline_level) = base.outline_level {
+        write!(xml, r#"<w:outlineLvl w:val="{}"/>"#, outline_level)?;
+    }
+
+    write_extra_child_nodes(xml, &properties.extras);
+    xml.push_str("</w:pPr>");
+    Ok(())
+}
+
+/// Checks that the paragraph properties don't contain anything besides the ones written by
+/// [`write_paragraph_properties`], so no property is lost silently.
+fn ensure_supported_paragraph_properties(properties: &PPr) -> Result<()> {
+    let unsupported_base = PPrBase {
+        style: None,
+        keep_with_next: None,
+        numbering_properties: None,
+        alignment: None,
+        outline_level: None,
+        ..properties.base.clone()
+    };
+    if unsupported_base != PPrBase::default() {
+        return Err(unsupported("paragraph property"));
+    }
+    if matches!(
+        &properties.base.numbering_properties,
+        Some(NumPr { inserted: Some(_), .. })
+    ) {
+        return Err(unsupported("w:ins of w:numPr"));
+    }
+    if properties.run_properties.is_some() {
+        return Err(unsupported("w:rPr of w:pPr"));
+    }
+    if properties.section_properties.is_some() {
+        return Err(unsupported("w:sectPr of w:pPr"));
+    }
+    if properties.properties_change.is_some() {
+        return Err(unsupported("w:pPrChange"));
+    }
+
+    Ok(())
+}
+
+fn write_run(xml: &mut String, run: &R) -> Result<()> {
+    xml.push_str("<w:r>");
+    if let Some(run_properties) = &run.run_properties {
+        write_run_properties(xml, run_properties)?;
+    }
+
+    for inner_content in &run.run_inner_contents {
+        match inner_content {
+            RunInnerContent::Text(text) => {
+                let needs_preserve =
+                    text.text.starts_with(char::is_whitespace) || text.text.ends_with(char::is_whitespace);
+                if needs_preserve || text.xml_space.as_deref() == Some("preserve") {
+                    write!(xml, r#"<w:t xml:space="preserve">{}</w:t>"#, escape_xml(&text.text))?;
+                } else {
+                    write!(xml, "<w:t>{}</w:t>", escape_xml(&text.text))?;
+                }
+            }
+            RunInnerContent::Tab => xml.push_str("<w:tab/>"),
+            RunInnerContent::CarriageReturn => xml.push_str("<w:cr/>"),
+            RunInnerContent::Break(br) => match &br.break_type {
+                Some(break_type) => write!(xml, r#"<w:br w:type="{}"/>"#, break_type.as_ref())?,
+                None => xml.push_str("<w:br/>"),
+            },
+            _ => return Err(unsupported("run content other than text, tabs and breaks")),
+        }
+    }
+
+    xml.push_str("</w:r>");
+    Ok(())
+}
+
+fn write_run_properties(xml: &mut String, run_properties: &RPr) -> Result<()> {
+    xml.push_str("<w:rPr");
+    write_extra_attributes(xml, &run_properties.extras);
+    xml.push('>');
+    for property in &run_properties.r_pr_bases {
+        match property {
+            RPrBase::RunStyle(style) => write!(xml, r#"<w:rStyle w:val="{}"/>"#, escape_xml(style))?,
+            RPrBase::Bold(value) => write_on_off(xml, "w:b", *value),
+            RPrBase::ComplexScriptBold(value) => write_on_off(xml, "w:bCs", *value),
+            RPrBase::Italic(value) => write_on_off(xml, "w:i", *value),
+            RPrBase::ComplexScriptItalic(value) => write_on_off(xml, "w:iCs", *value),
+            RPrBase::Strikethrough(value) => write_on_off(xml, "w:strike", *value),
+            RPrBase::Color(color) => match &color.value {
+                HexColor::Auto => xml.push_str(r#"<w:color w:val="auto"/>"#),
+                HexColor::RGB(rgb) => write!(xml, r#"<w:color w:val="{:02X}{:02X}{:02X}"/>"#, rgb[0], rgb[1], rgb[2])?,
+            },
+            RPrBase::FontSize(size) => write!(xml, r#"<w:sz w:val="{}"/>"#, format_hps_measure(size))?,
+            RPrBase::ComplexScriptFontSize(size) => write!(xml, r#"<w:szCs w:val="{}"/>"#, format_hps_measure(size))?,
+            RPrBase::Underline(underline) => match &underline.value {
+                Some(value) => write!(xml, r#"<w:u w:val="{}"/>"#, value.as_ref())?,
+                None => xml.push_str("<w:u/>"),
+            },
+            _ => return Err(unsupported("run property")),
+        }
+    }
+
+    write_extra_child_nodes(xml, &run_properties.extras);
+    xml.push_str("</w:rPr>");
+    Ok(())
+}
+
+fn write_table(xml: &mut String, table: &Tbl) -> Result<()> {
+    write_table_start(xml, table)?;
+    for row_content in &table.row_contents {
+        write_row_content(xml, row_content)?;
+    }
+
+    xml.push_str("</w:tbl>");
+    Ok(())
+}
+
+fn write_table_start(xml: &mut String, table: &Tbl) -> Result<()> {
+    ensure_supported_table_properties(&table.properties)?;
+
+    xml.push_str("<w:tbl><w:tblPr>");
+    let properties = &table.properties.base;
+    if let Some(style) = &properties.style {
+        write!(xml, r#"<w:tblStyle w:val="{}"/>"#, escape_xml(style))?;
+    }
+    if let Some(width) = &properties.width {
+        write_table_width(xml, "w:tblW", width)?;
+    }
+    xml.push_str("</w:tblPr><w:tblGrid>");
+
+    for column in &table.grid.base.columns {
+        match &column.width {
+            Some(width) => write!(xml, r#"<w:gridCol w:w="{}"/>"#, format_twips_measure(width))?,
+            None => xml.push_str("<w:gridCol/>"),
+        }
+    }
+
+    xml.push_str("</w:tblGrid>");
+    Ok(())
+}
+
+/// Checks that the table properties don't contain anything besides the style and the width written by
+/// [`write_table_start`].
+fn ensure_supported_table_properties(properties: &TblPr) -> Result<()> {
+    let unsupported_base = TblPrBase {
+        style: None,
+        width: None,
+        ..properties.base.clone()
+    };
+    if unsupported_base != TblPrBase::default() {
+        return Err(unsupported("table property"));
+    }
+    if properties.change.is_some() {
+        return Err(unsupported("w:tblPrChange"));
+    }
+
+    Ok(())
+}
+
+fn write_row_content(xml: &mut String, row_content: &ContentRowContent) -> Result<()> {
+    match row_content {
+        ContentRowContent::Table(row) => write_row(xml, row),
+        ContentRowContent::CustomXml(_) => Err(unsupported("w:customXml")),
+        ContentRowContent::Sdt(_) => Err(unsupported("w:sdt")),
+        ContentRowContent::RunLevelElements(_) => Err(unsupported("run level element")),
+    }
+}
+
+fn write_row(xml: &mut String, row: &Row) -> Result<()> {
+    xml.push_str("<w:tr");
+    if let Some(paragraph_id) = row.paragraph_id {
+        write!(xml, r#" w14:paraId="{:08X}""#, paragraph_id)?;
+    }
+    xml.push('>');
+
+    for cell_content in &row.contents {
+        match cell_content {
+            ContentCellContent::Cell(cell) => write_cell(xml, cell)?,
+            ContentCellContent::CustomXml(_) => return Err(unsupported("w:customXml")),
+            ContentCellContent::Sdt(_) => return Err(unsupported("w:sdt")),
+            ContentCellContent::RunLevelElement(_) => return Err(unsupported("run level element")),
+        }
+    }
+
+    xml.push_str("</w:tr>");
+    Ok(())
+}
+
+fn write_cell(xml: &mut String, cell: &Tc) -> Result<()> {
+    xml.push_str("<w:tc>");
+    if let Some(properties) = &cell.properties {
+        ensure_supported_cell_properties(properties)?;
+
+        let properties = &properties.base.base;
+        xml.push_str("<w:tcPr>");
+        if let Some(width) = &properties.width {
+            write_table_width(xml, "w:tcW", width)?;
+        }
+        if let Some(grid_span) = properties.grid_span {
+            write!(xml, r#"<w:gridSpan w:val="{}"/>"#, grid_span)?;
+        }
+        xml.push_str("</w:tcPr>");
+    }
+
+    for block in &cell.block_level_elements {
+        write_block(xml, block)?;
+    }
+
+    xml.push_str("</w:tc>");
+    Ok(())
+}
+
+/// Checks that the cell properties don't contain anything besides the width and the grid span written by
+/// [`write_cell`].
+fn ensure_supported_cell_properties(properties: &TcPr) -> Result<()> {
+    let unsupported_base = TcPrBase {
+        width: None,
+        grid_span: None,
+        ..properties.base.base.clone()
+    };
+    if unsupported_base != TcPrBase::default() {
+        return Err(unsupported("cell property"));
+    }
+    if properties.base.markup_element.is_some() {
+        return Err(unsupported("w:cellIns|w:cellDel|w:cellMerge"));
+    }
+    if properties.change.is_some() {
+        return Err(unsupported("w:tcPrChange"));
+    }
+
+    Ok(())
+}
+
+fn write_table_width(xml: &mut String, element: &str, width: &TblWidth) -> Result<()> {
+    write!(xml, "<{}", element)?;
+    match &width.width {
+        Some(MeasurementOrPercent::DecimalOrPercent(DecimalNumberOrPercent::Decimal(value))) => {
+            write!(xml, r#" w:w="{}""#, value)?
+        }
+        Some(MeasurementOrPercent::DecimalOrPercent(DecimalNumberOrPercent::Percentage(percentage))) => {
+            write!(xml, r#" w:w="{}%""#, percentage.0)?
+        }
+        Some(MeasurementOrPercent::UniversalMeasure(measure)) => {
+            write!(xml, r#" w:w="{}""#, format_universal_measure(measure))?
+        }
+        None => (),
+    }
+
+    if let Some(width_type) = &width.width_type {
+        write!(xml, r#" w:type="{}""#, width_type.as_ref())?;
+    }
+
+    xml.push_str("/>");
+    Ok(())
+}
+
+fn write_section_properties(xml: &mut String, section_properties: &SectPr) -> Result<()> {
+    if !section_properties.header_footer_references.is_empty() {
+        return Err(unsupported("w:headerReference|w:footerReference"));
+    }
+
+    xml.push_str("<w:sectPr");
+    write_extra_attributes(xml, &section_properties.extras);
+    xml.push('>');
+    if let Some(contents) = &section_properties.contents {
+        if let Some(page_size) = &contents.page_size {
+            xml.push_str("<w:pgSz");
+            if let Some(width) = &page_size.width {
+                write!(xml, r#" w:w="{}""#, format_twips_measure(width))?;
+            }
+            if let Some(height) = &page_size.height {
+                write!(xml, r#" w:h="{}""#, format_twips_measure(height))?;
+            }
+            if let Some(orientation) = &page_size.orientation {
+                write!(xml, r#" w:orient="{}""#, orientation.as_ref())?;
+            }
+            xml.push_str("/>");
+        }
+
+        if let Some(page_margin) = &contents.page_margin {
+            write!(
+                xml,
+                r#"<w:pgMar w:top="{}" w:right="{}" w:bottom="{}" w:left="{}" w:header="{}" w:footer="{}" w:gutter="{}"/>"#,
+                format_signed_twips_measure(&page_margin.top),
+                format_twips_measure(&page_margin.right),
+                format_signed_twips_measure(&page_margin.bottom),
+                format_twips_measure(&page_margin.left),
+                format_twips_measure(&page_margin.header),
+                format_twips_measure(&page_margin.footer),
+                format_twips_measure(&page_margin.gutter),
+            )?;
+        }
+
+        if let Some(columns) = &contents.columns {
+            xml.push_str("<w:cols");
+            if let Some(number) = columns.number {
+                write!(xml, r#" w:num="{}""#, number)?;
+            }
+            if let Some(spacing) = &columns.spacing {
+                write!(xml, r#" w:space="{}""#, format_twips_measure(spacing))?;
+            }
+            xml.push_str("/>");
+        }
+    }
+
+    write_extra_child_nodes(xml, &section_properties.extras);
+    xml.push_str("</w:sectPr>");
+    Ok(())
+}
+
+/// Writes the unrecognized attributes kept by the parser. Namespaces of the attributes not declared by the document
+/// element are declared on the element.
+fn write_extra_attributes(xml: &mut String, extras: &XmlExtras) {
+    let mut declarations = Vec::new();
+    collect_undeclared_attribute_namespaces(&extras.attributes, &extras.attribute_namespaces, &mut declarations);
+    if declarations.is_empty() {
+        extras.write_attributes(xml);
+        return;
+    }
+
+    let mut attributes = extras.attributes.clone();
+    declare_namespaces(&mut attributes, declarations);
+    XmlExtras {
+        attributes,
+        ..Default::default()
+    }
+    .write_attributes(xml);
+}
+
+/// Writes the unrecognized child elements kept by the parser. Namespaces not declared by the document element are
+/// declared on the elements using them.
+fn write_extra_child_nodes(xml: &mut String, extras: &XmlExtras) {
+    for child_node in &extras.child_nodes {
+        let mut declarations = Vec::new();
+        collect_undeclared_namespaces(child_node, &mut declarations);
+        if declarations.is_empty() {
+            child_node.write_xml(xml);
+            continue;
+        }
+
+        let mut child_node = child_node.clone();
+        declare_namespaces(&mut child_node.attributes, declarations);
+        child_node.write_xml(xml);
+    }
+}
+
+fn declare_namespaces(attributes: &mut HashMap<Arc<str>, String>, declarations: Vec<(&str, &str)>) {
+    for (prefix, namespace) in declarations {
+        attributes
+            .entry(format!("xmlns:{}", prefix).into())
+            .or_insert_with(|| namespace.to_string());
+    }
+}
+
+fn collect_undeclared_namespaces<'a>(xml_node: &'a XmlNode, declarations: &mut Vec<(&'a str, &'a str)>) {
+    if let (Some((prefix, _)), Some(namespace)) = (xml_node.name.split_once(':'), &xml_node.namespace) {
+        push_undeclared_namespace(prefix, namespace, declarations);
+    }
+    collect_undeclared_attribute_namespaces(&xml_node.attributes, &xml_node.attribute_namespaces, declarations);
+
+    for child_node in &xml_node.child_nodes {
+        collect_undeclared_namespaces(child_node, declarations);
+    }
+}
+
+fn collect_undeclared_attribute_namespaces<'a>(
+    attributes: &'a HashMap<Arc<str>, String>,
+    attribute_namespaces: &'a HashMap<Arc<str>, Arc<str>>,
+    declarations: &mut Vec<(&'a str, &'a str)>,
+) {
+    for key in attributes.keys() {
+        let prefix = match key.split_once(':') {
+            Some(("xmlns", _)) | Some(("xml", _)) | None => continue,
+            Some((prefix, _)) => prefix,
+        };
+        let namespace = attribute_namespaces
+            .get(prefix)
+            .map(|namespace| &**namespace)
+            .or_else(|| known_namespace(prefix));
+        if let Some(namespace) = namespace {
+            push_undeclared_namespace(prefix, namespace, declarations);
+        }
+    }
+}
+
+fn push_undeclared_namespace<'a>(prefix: &'a str, namespace: &'a str, declarations: &mut Vec<(&'a str, &'a str)>) {
+    let is_declared = DOCUMENT_NAMESPACE_PREFIXES.contains(&prefix)
+        || declarations
+            .iter()
+            .any(|(declared_prefix, _)| *declared_prefix == prefix);
+    if !is_declared {
+        declarations.push((prefix, namespace));
+    }
+}
+
+fn write_on_off(xml: &mut String, element: &str, value: bool) {
+    if value {
+        let _ = write!(xml, "<{}/>", element);
+    } else {
+        let _ = write!(xml, r#"<{} w:val="0"/>"#, element);
+    }
+}
+
+fn format_universal_measure<T>(measure: &UniversalMeasure<T>) -> String {
+    format!("{}{}", measure.value, measure.unit.as_ref())
+}
+
+fn format_twips_measure(measure: &TwipsMeasure) -> String {
+    match measure {
+        TwipsMeasure::Decimal(value) => value.to_string(),
+        TwipsMeasure::UniversalMeasure(measure) => format_universal_measure(measure),
+    }
+}
+
+fn format_signed_twips_measure(measure: &SignedTwipsMeasure) -> String {
+    match measure {
+        SignedTwipsMeasure::Decimal(value) => value.to_string(),
+        SignedTwipsMeasure::UniversalMeasure(measure) => format_universal_measure(measure),
+    }
+}
+
+fn format_hps_measure(measure: &HpsMeasure) -> String {
+    match measure {
+        HpsMeasure::Decimal(value) => value.to_string(),
+        HpsMeasure::UniversalMeasure(measure) => format_universal_measure(measure),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        docx::{
+            pagesetup::PageSetup,
+            wml::document::{Document, Jc},
+        },
+        xml::zip_file_to_xml_node,
+    };
+    use std::{io::Cursor, str::FromStr};
+    use zip::ZipArchive;
+
+    fn write_document_for_test() -> Vec<u8> {
+        let mut writer = DocumentStreamWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .write_paragraph(
+                &P::builder()
+                    .style("Title")
+                    .alignment(Jc::Center)
+                    .run(R::builder().text("Fish & Chips").bold(true).build())
+                    .build(),
+            )
+            .unwrap();
+
+        writer
+            .start_table(
+                &Tbl::builder()
+                    .style("TableGrid")
+                    .column_widths(vec![1000, 2000])
+                    .build(),
+            )
+            .unwrap();
+        assert!(writer.write_paragraph(&P::default()).is_err());
+        for idx in 0..3 {
+            writer
+                .write_row(&Row::builder().text_cell(idx.to_string()).text_cell(" x ").build())
+                .unwrap();
+        }
+        writer.end_table().unwrap();
+        assert!(writer.write_row(&Row::default()).is_err());
+
+        writer
+            .finish(Some(&SectPr::default().with_page_setup(PageSetup::LETTER_LANDSCAPE)))
+            .unwrap()
+            .into_inner()
+    }
+
+    #[test]
+    pub fn test_stream_written_document_round_trips() {
+        let bytes = write_document_for_test();
+        let mut zipper = ZipArchive::new(Cursor::new(bytes)).unwrap();
+        assert!(zipper.by_name(CONTENT_TYPES_PART_NAME).is_ok());
+        assert!(zipper.by_name("_rels/.rels").is_ok());
+
+        let xml_node = zip_file_to_xml_node(&mut zipper.by_name("word/document.xml").unwrap()).unwrap();
+        let document = Document::from_xml_element(&xml_node).unwrap();
+        let body = document.body.unwrap();
+
+        assert_eq!(body.block_level_elements.len(), 2);
+        match &body.block_level_elements[0] {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => {
+                let properties = &paragraph.properties.as_ref().unwrap().base;
+                assert_eq!(properties.style.as_deref(), Some("Title"));
+                assert_eq!(properties.alignment, Some(Jc::Center));
+            }
+            _ => panic!("expected a paragraph"),
+        }
+
+        match &body.block_level_elements[1] {
+            BlockLevelElts::Chunk(ContentBlockContent::Table(table)) => {
+                assert_eq!(table.properties.base.style.as_deref(), Some("TableGrid"));
+                assert_eq!(table.grid.base.columns.len(), 2);
+                assert_eq!(table.row_contents.len(), 3);
+            }
+            _ => panic!("expected a table"),
+        }
+
+        let section_contents = body.section_properties.unwrap().contents.unwrap();
+        assert_eq!(
+            section_contents.page_size,
+            Some(PageSetup::LETTER_LANDSCAPE.page_size())
+        );
+        assert_eq!(
+            section_contents.page_margin,
+            Some(PageSetup::LETTER_LANDSCAPE.page_margin())
+        );
+    }
+
+    #[test]
+    pub fn test_escaped_text() {
+        let mut xml = String::new();
+        write_run(&mut xml, &R::builder().text("a < b").build()).unwrap();
+        assert_eq!(xml, r#"<w:r><w:t xml:space="preserve">a &lt; b</w:t></w:r>"#);
+    }
+
+    #[test]
+    pub fn test_extras_written() {
+        let xml = r#"<w:rPr xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml" w:future="1">
+            <w:b /><w15:unknown w15:val="1" />
+        </w:rPr>"#;
+        let run_properties = RPr::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        let mut written = String::new();
+        write_run_properties(&mut written, &run_properties).unwrap();
+        assert_eq!(
+            written,
+            concat!(
+                r#"<w:rPr w:future="1" xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml"><w:b/>"#,
+                r#"<w15:unknown w15:val="1" xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml"/></w:rPr>"#,
+            )
+        );
+
+        // The namespaces of foreign attributes are declared by an ancestor that isn't written
+        let xml = r#"<w:r xmlns:w16du="http://schemas.microsoft.com/office/word/2023/wordml/word16du">
+            <w:rPr w16du:dateUtc="2024-01-01T00:00:00Z">
+                <w:b /><w:unknown w16du:dateUtc="2024-01-01T00:00:00Z" w14:val="1" />
+            </w:rPr>
+        </w:r>"#;
+        let run_properties = RPr::from_xml_element(&XmlNode::from_str(xml).unwrap().child_nodes[0]).unwrap();
+        let mut written = String::new();
+        write_run_properties(&mut written, &run_properties).unwrap();
+        assert_eq!(
+            written,
+            concat!(
+                r#"<w:rPr w16du:dateUtc="2024-01-01T00:00:00Z" "#,
+                r#"xmlns:w16du="http://schemas.microsoft.com/office/word/2023/wordml/word16du"><w:b/>"#,
+                r#"<w:unknown w14:val="1" w16du:dateUtc="2024-01-01T00:00:00Z" "#,
+                r#"xmlns:w16du="http://schemas.microsoft.com/office/word/2023/wordml/word16du"/></w:rPr>"#,
+            )
+        );
+        assert!(XmlNode::from_str(&written).is_ok());
+    }
+
+    fn unsupported_content(result: Result<()>) -> Option<&'static str> {
+        match result.unwrap_err().downcast_ref::<StreamWriteError>() {
+            Some(StreamWriteError::UnsupportedContent(element)) => Some(element),
+            _ => None,
+        }
+    }
+
+    #[test]
+    pub fn test_unsupported_properties() {
+        let write_paragraph_xml = |xml: &str| {
+            let paragraph = P::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+            write_paragraph(&mut String::new(), &paragraph)
+        };
+        assert_eq!(
+            unsupported_content(write_paragraph_xml(
+                r#"<w:p><w:pPr><w:pStyle w:val="Title" /><w:spacing w:after="200" /></w:pPr></w:p>"#
+            )),
+            Some("paragraph property")
+        );
+        assert_eq!(
+            unsupported_content(write_paragraph_xml(
+                r#"<w:p><w:pPr><w:rPr><w:b /></w:rPr></w:pPr></w:p>"#
+            )),
+            Some("w:rPr of w:pPr")
+        );
+        assert_eq!(
+            unsupported_content(write_paragraph_xml(r#"<w:p><w:pPr><w:sectPr /></w:pPr></w:p>"#)),
+            Some("w:sectPr of w:pPr")
+        );
+        assert!(write_paragraph_xml(r#"<w:p><w:pPr><w:pStyle w:val="Title" /><w:keepNext /></w:pPr></w:p>"#).is_ok());
+
+        let write_table_xml = |xml: &str| {
+            let table = Tbl::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+            write_table(&mut String::new(), &table)
+        };
+        assert_eq!(
+            unsupported_content(write_table_xml(
+                r#"<w:tbl><w:tblPr><w:tblStyle w:val="TableGrid" /><w:jc w:val="center" /></w:tblPr><w:tblGrid /></w:tbl>"#
+            )),
+            Some("table property")
+        );
+        assert_eq!(
+            unsupported_content(write_table_xml(
+                r#"<w:tbl>
+                    <w:tblPr />
+                    <w:tblGrid><w:gridCol w:w="100" /></w:tblGrid>
+                    <w:tr><w:tc><w:tcPr><w:vAlign w:val="center" /></w:tcPr><w:p /></w:tc></w:tr>
+                </w:tbl>"#
+            )),
+            Some("cell property")
+        );
+    }
+}
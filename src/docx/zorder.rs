@@ -0,0 +1,198 @@
+use super::{
+    package::Package,
+    textmap::{block_runs_with_paths, RunPath},
+    wml::{
+        document::{BlockLevelElts, Document, DrawingChoice, RunInnerContent},
+        drawing::Anchor,
+        footnotes::FtnEdn,
+    },
+};
+
+/// A story of the document, i.e. a separate flow of text with its own floating objects.
+///
+/// Headers and footers are not loaded by the package, so they're not listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Story {
+    Body,
+    /// The footnote with the id
+    Footnote(i64),
+    /// The endnote with the id
+    Endnote(i64),
+}
+
+/// The layer an anchored drawing is composited in. Drawings behind the text are painted before the text of the
+/// story, drawings in front of it after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DrawingLayer {
+    BehindText,
+    InFrontOfText,
+}
+
+/// An anchored drawing of a story.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchoredDrawing<'a> {
+    /// The path of the run containing the drawing, relative to the block level elements of the story.
+    pub path: RunPath,
+    /// The index of the drawing in the contents of the run.
+    pub content_index: usize,
+    pub anchor: &'a Anchor,
+}
+
+impl AnchoredDrawing<'_> {
+    pub fn layer(&self) -> DrawingLayer {
+        if self.anchor.behind_document_text {
+            DrawingLayer::BehindText
+        } else {
+            DrawingLayer::InFrontOfText
+        }
+    }
+
+    /// The z-order of the drawing relative to the other drawings of the story. Drawings with a higher value are
+    /// displayed on top of the ones with a lower one in the same layer.
+    pub fn relative_height(&self) -> u32 {
+        self.anchor.relative_height
+    }
+
+    /// Returns whether the drawing may overlap other anchored drawings with overlap allowed. Drawings that don't
+    /// allow overlapping are moved by the layout so they don't overlap other drawings.
+    pub fn allows_overlap(&self) -> bool {
+        self.anchor.allow_overlap
+    }
+}
+
+impl Document {
+    /// Returns the anchored drawings of the body in painting order, see [`Package::anchored_drawings`].
+    pub fn anchored_drawings(&self) -> Vec<AnchoredDrawing<'_>> {
+        match &self.body {
+            Some(body) => sorted_anchored_drawings(&body.block_level_elements),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl FtnEdn {
+    /// Returns the anchored drawings of the note in painting order, see [`Package::anchored_drawings`].
+    pub fn anchored_drawings(&self) -> Vec<AnchoredDrawing<'_>> {
+        sorted_anchored_drawings(&self.block_level_elements)
+    }
+}
+
+impl Package {
+    /// Returns the anchored drawings of each story in painting order. Stories without anchored drawings are left out.
+    ///
+    /// The drawings behind the text come first, then the ones in front of it. Within a layer drawings are ordered by
+    /// their relative height, drawings with the same height keep their document order, so later drawings are painted
+    /// on top of earlier ones.
+    pub fn anchored_drawings(&self) -> Vec<(Story, Vec<AnchoredDrawing<'_>>)> {
+        let body = self
+            .main_document
+            .iter()
+            .map(|document| (Story::Body, document.anchored_drawings()));
+        let footnotes = self
+            .footnotes
+            .iter()
+            .flat_map(|footnotes| footnotes.notes())
+            .map(|footnote| (Story::Footnote(footnote.id), footnote.anchored_drawings()));
+        let endnotes = self
+            .endnotes
+            .iter()
+            .flat_map(|endnotes| endnotes.notes())
+            .map(|endnote| (Story::Endnote(endnote.id), endnote.anchored_drawings()));
+
+        body.chain(footnotes)
+            .chain(endnotes)
+            .filter(|(_, drawings)| !drawings.is_empty())
+            .collect()
+    }
+}
+
+fn sorted_anchored_drawings(elements: &[BlockLevelElts]) -> Vec<AnchoredDrawing<'_>> {
+    let mut drawings = Vec::new();
+    for (path, run) in block_runs_with_paths(elements) {
+        for (content_index, content) in run.run_inner_contents.iter().enumerate() {
+            if let RunInnerContent::Drawing(drawing) = content {
+                for choice in &drawing.0 {
+                    if let DrawingChoice::Anchor(anchor) = choice {
+                        drawings.push(AnchoredDrawing {
+                            path: path.clone(),
+                            content_index,
+                            anchor,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // Stable sort, so drawings with the same height keep their document order
+    drawings.sort_by_key(|drawing| (drawing.layer(), drawing.relative_height()));
+    drawings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::wml::footnotes::Footnotes, xml::XmlNode};
+    use std::str::FromStr;
+
+    fn anchor_run_xml(relative_height: u32, behind_document_text: bool) -> String {
+        let anchor = Anchor::test_xml("wp:anchor")
+            .replacen(
+                r#"relativeHeight="100""#,
+                &format!(r#"relativeHeight="{}""#, relative_height),
+                1,
+            )
+            .replacen(
+                r#"behindDoc="false""#,
+                &format!(r#"behindDoc="{}""#, behind_document_text),
+                1,
+            );
+        format!("<w:r><w:drawing>{}</w:drawing></w:r>", anchor)
+    }
+
+    #[test]
+    pub fn test_anchored_drawings() {
+        let xml = format!(
+            r#"<w:document><w:body>
+                <w:p>{}{}</w:p>
+                <w:p>{}{}</w:p>
+            </w:body></w:document>"#,
+            anchor_run_xml(5, false),
+            anchor_run_xml(3, false),
+            anchor_run_xml(10, true),
+            anchor_run_xml(3, false),
+        );
+        let document = Document::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap();
+
+        let order: Vec<_> = document
+            .anchored_drawings()
+            .iter()
+            .map(|drawing| (drawing.path.paragraph[0], drawing.path.run[0], drawing.layer()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (1, 0, DrawingLayer::BehindText),
+                (0, 1, DrawingLayer::InFrontOfText),
+                (1, 1, DrawingLayer::InFrontOfText),
+                (0, 0, DrawingLayer::InFrontOfText),
+            ]
+        );
+
+        let footnote_xml = format!(
+            r#"<w:footnotes><w:footnote w:id="1"><w:p>{}</w:p></w:footnote><w:footnote w:id="2"><w:p/></w:footnote></w:footnotes>"#,
+            anchor_run_xml(1, false),
+        );
+        let package = Package {
+            main_document: Some(Box::new(document)),
+            footnotes: Some(Footnotes::from_xml_element(&XmlNode::from_str(&footnote_xml).unwrap()).unwrap()),
+            ..Default::default()
+        };
+        let stories: Vec<_> = package
+            .anchored_drawings()
+            .iter()
+            .map(|(story, drawings)| (*story, drawings.len()))
+            .collect();
+        assert_eq!(stories, vec![(Story::Body, 4), (Story::Footnote(1), 1)]);
+    }
+}
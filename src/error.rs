@@ -1,3 +1,8 @@
+use crate::shared::{
+    filekind::FileKind,
+    sharedtypes::{CalendarType, ConformanceClass},
+    validation::ConformanceReport,
+};
 use std::{
     error::Error,
     fmt::{Display, Formatter, Result},
@@ -12,9 +17,9 @@ pub struct MissingAttributeError {
 }
 
 impl MissingAttributeError {
-    pub fn new<T: Into<String>>(node_name: T, attr: &'static str) -> Self {
+    pub fn new<T: AsRef<str>>(node_name: T, attr: &'static str) -> Self {
         Self {
-            node_name: node_name.into(),
+            node_name: String::from(node_name.as_ref()),
             attr,
         }
     }
@@ -44,9 +49,9 @@ pub struct MissingChildNodeError {
 }
 
 impl MissingChildNodeError {
-    pub fn new<T: Into<String>>(node_name: T, child_node: &'static str) -> Self {
+    pub fn new<T: AsRef<str>>(node_name: T, child_node: &'static str) -> Self {
         Self {
-            node_name: node_name.into(),
+            node_name: String::from(node_name.as_ref()),
             child_node,
         }
     }
@@ -76,9 +81,9 @@ pub struct NotGroupMemberError {
 }
 
 impl NotGroupMemberError {
-    pub fn new<T: Into<String>>(node_name: T, group: &'static str) -> Self {
+    pub fn new<T: AsRef<str>>(node_name: T, group: &'static str) -> Self {
         Self {
-            node_name: node_name.into(),
+            node_name: String::from(node_name.as_ref()),
             group,
         }
     }
@@ -126,7 +131,7 @@ pub struct LimitViolationError {
 }
 
 impl LimitViolationError {
-    pub fn new<T: Into<String>>(
+    pub fn new<T: AsRef<str>>(
         node_name: T,
         violating_node_name: &'static str,
         min_occurs: u32,
@@ -134,7 +139,7 @@ impl LimitViolationError {
         occurs: u32,
     ) -> Self {
         LimitViolationError {
-            node_name: node_name.into(),
+            node_name: String::from(node_name.as_ref()),
             violating_node_name,
             min_occurs,
             max_occurs,
@@ -318,3 +323,499 @@ impl From<ParseHexColorRGBError> for ParseHexColorError {
         ParseHexColorError::HexColorRGB(v)
     }
 }
+
+/// An error indicating that a building block couldn't be inserted into a document
+#[derive(Debug, Clone, PartialEq)]
+pub enum InsertBuildingBlockError {
+    /// The glossary document doesn't contain a building block with the given name
+    NotFound(String),
+    /// The target document or the building block doesn't have a body
+    MissingBody,
+    /// The insertion position is greater than the number of block level elements in the target body
+    PositionOutOfBounds { position: usize, len: usize },
+}
+
+impl Display for InsertBuildingBlockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            InsertBuildingBlockError::NotFound(name) => write!(f, "Building block '{}' not found", name),
+            InsertBuildingBlockError::MissingBody => write!(f, "Document or building block has no body"),
+            InsertBuildingBlockError::PositionOutOfBounds { position, len } => write!(
+                f,
+                "Insertion position {} is out of bounds. Body has {} block level elements",
+                position, len
+            ),
+        }
+    }
+}
+
+impl Error for InsertBuildingBlockError {}
+
+/// An error indicating that the tabular data of a chart is invalid
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartDataError {
+    /// The chart doesn't have any series
+    NoSeries,
+    /// A series has a different number of values than the number of categories
+    SeriesLengthMismatch {
+        series: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// A value of a series is NaN or infinite
+    NonFiniteValue { series: String, index: usize },
+}
+
+impl Display for ChartDataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ChartDataError::NoSeries => write!(f, "Chart doesn't have any series"),
+            ChartDataError::SeriesLengthMismatch {
+                series,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Series '{}' has {} values but the chart has {} categories",
+                series, actual, expected
+            ),
+            ChartDataError::NonFiniteValue { series, index } => {
+                write!(f, "Value {} of series '{}' is not a finite number", index, series)
+            }
+        }
+    }
+}
+
+impl Error for ChartDataError {}
+
+/// An error indicating that a presentation doesn't have a slide with the requested index
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideIndexOutOfBoundsError {
+    pub index: usize,
+    pub len: usize,
+}
+
+impl Display for SlideIndexOutOfBoundsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Slide index {} is out of bounds. Presentation has {} slides",
+            self.index, self.len
+        )
+    }
+}
+
+impl Error for SlideIndexOutOfBoundsError {}
+
+/// An error indicating that the value of a date content control couldn't be evaluated or set
+#[derive(Debug, Clone, PartialEq)]
+pub enum SdtDateError {
+    /// The value is not a valid ISO 8601 date, e.g. `2020-02-30`
+    InvalidDate(String),
+    /// Dates can't be displayed in the calendar of the content control
+    UnsupportedCalendar(CalendarType),
+    /// The structured document tag is not a date content control
+    NotDateControl,
+    /// The content of the content control is locked
+    ContentLocked,
+}
+
+impl Display for SdtDateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SdtDateError::InvalidDate(value) => write!(f, "'{}' is not a valid date", value),
+            SdtDateError::UnsupportedCalendar(calendar) => write!(f, "Unsupported calendar: {:?}", calendar),
+            SdtDateError::NotDateControl => write!(f, "Content control is not a date control"),
+            SdtDateError::ContentLocked => write!(f, "Content of the content control is locked"),
+        }
+    }
+}
+
+impl Error for SdtDateError {}
+
+/// An error indicating that a package opened in strict mode doesn't conform to the schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceError {
+    pub report: ConformanceReport,
+}
+
+impl Display for ConformanceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Package has {} schema violations", self.report.violations.len())?;
+        if let Some(violation) = self.report.violations.first() {
+            write!(f, ", first: {}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ConformanceError {}
+
+/// An error indicating that a package doesn't have the conformance class required by the parse options
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConformanceClassError {
+    pub required: ConformanceClass,
+    pub found: ConformanceClass,
+}
+
+impl Display for ConformanceClassError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Package is {:?} but {:?} conformance is required",
+            self.found, self.required
+        )
+    }
+}
+
+impl Error for ConformanceClassError {}
+
+/// An error indicating that the streaming document writer can't write an element or was used in the wrong order
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamWriteError {
+    /// The element can't be serialized by the streaming writer yet
+    UnsupportedContent(&'static str),
+    /// A row was written without starting a table first
+    NoOpenTable,
+    /// A block level element was written while a table was open
+    TableAlreadyOpen,
+}
+
+impl Display for StreamWriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            StreamWriteError::UnsupportedContent(element) => {
+                write!(f, "Writing {} is not supported by the streaming writer", element)
+            }
+            StreamWriteError::NoOpenTable => write!(f, "Rows can only be written after starting a table"),
+            StreamWriteError::TableAlreadyOpen => write!(f, "The open table must be ended first"),
+        }
+    }
+}
+
+impl Error for StreamWriteError {}
+
+/// An error indicating that a part name or a relationship target violates the part naming rules of the Open Packaging
+/// Conventions
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartNameError {
+    Empty,
+    /// Part names must start with a forward slash, e.g. `/word/document.xml`
+    MissingLeadingSlash(String),
+    TrailingSlash(String),
+    EmptySegment(String),
+    /// Segments can't end with a dot, which also rules out the `.` and `..` segments
+    SegmentEndsWithDot(String),
+    InvalidCharacter(String, char),
+    InvalidPercentEncoding(String),
+    /// Segments can't contain percent-encoded slashes, backslashes or unreserved characters
+    EncodedCharacter(String, char),
+    /// The target leaves the root of the package, e.g. `../../media/image1.png` from `/word/document.xml`
+    OutsideOfPackage(String),
+}
+
+impl Display for PartNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            PartNameError::Empty => write!(f, "Part name is empty"),
+            PartNameError::MissingLeadingSlash(name) => write!(f, "Part name '{}' doesn't start with a slash", name),
+            PartNameError::TrailingSlash(name) => write!(f, "Part name '{}' ends with a slash", name),
+            PartNameError::EmptySegment(name) => write!(f, "Part name '{}' has an empty segment", name),
+            PartNameError::SegmentEndsWithDot(name) => {
+                write!(f, "Part name '{}' has a segment ending with a dot", name)
+            }
+            PartNameError::InvalidCharacter(name, c) => {
+                write!(f, "Part name '{}' contains invalid character '{}'", name, c)
+            }
+            PartNameError::InvalidPercentEncoding(name) => {
+                write!(f, "Part name '{}' contains an invalid percent-encoding", name)
+            }
+            PartNameError::EncodedCharacter(name, c) => {
+                write!(f, "Part name '{}' contains percent-encoded character '{}'", name, c)
+            }
+            PartNameError::OutsideOfPackage(target) => write!(f, "Target '{}' points outside of the package", target),
+        }
+    }
+}
+
+impl Error for PartNameError {}
+
+/// An error indicating that a field instruction can't be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldParseError {
+    /// The instruction doesn't contain a field type
+    Empty,
+    /// A required argument of the field is missing, e.g. the bookmark of a REF field
+    MissingArgument {
+        field_type: &'static str,
+        argument: &'static str,
+    },
+    /// A switch argument has an invalid value, e.g. `\o "a-b"` of a TOC field
+    InvalidSwitchArgument { switch: char, argument: String },
+}
+
+impl Display for FieldParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FieldParseError::Empty => write!(f, "Field instruction is empty"),
+            FieldParseError::MissingArgument { field_type, argument } => {
+                write!(f, "{} field is missing its {} argument", field_type, argument)
+            }
+            FieldParseError::InvalidSwitchArgument { switch, argument } => {
+                write!(f, "Invalid argument of switch \\{}: '{}'", switch, argument)
+            }
+        }
+    }
+}
+
+impl Error for FieldParseError {}
+
+/// An error indicating that a part of a package can't be parsed
+#[derive(Debug)]
+pub struct PartParseError {
+    /// The name of the part as it's stored in the zip archive, e.g. `word/footnotes.xml`
+    pub part_name: String,
+    pub error: Box<dyn Error>,
+}
+
+impl Display for PartParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Failed to parse part '{}': {}", self.part_name, self.error)
+    }
+}
+
+impl Error for PartParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// An error indicating that a part of a [`PartCache`](crate::shared::partcache::PartCache) can't be provided
+#[derive(Debug, Clone, PartialEq)]
+pub enum PartCacheError {
+    /// The package doesn't contain the part
+    MissingPart(String),
+    /// No parser is registered for the content type of the part
+    NoParser {
+        part_name: String,
+        content_type: Option<String>,
+    },
+    /// The part was parsed into a different type than the requested one
+    TypeMismatch(String),
+}
+
+impl Display for PartCacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            PartCacheError::MissingPart(part_name) => write!(f, "Package has no part '{}'", part_name),
+            PartCacheError::NoParser {
+                part_name,
+                content_type: Some(content_type),
+            } => write!(
+                f,
+                "No parser is registered for content type '{}' of part '{}'",
+                content_type, part_name
+            ),
+            PartCacheError::NoParser {
+                part_name,
+                content_type: None,
+            } => write!(f, "Part '{}' has no content type", part_name),
+            PartCacheError::TypeMismatch(part_name) => {
+                write!(f, "Part '{}' isn't of the requested type", part_name)
+            }
+        }
+    }
+}
+
+impl Error for PartCacheError {}
+
+/// An error indicating that a file isn't an Office Open XML package, e.g. a legacy binary `.doc` document
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedFormat {
+    pub detected: FileKind,
+}
+
+impl Display for UnsupportedFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "Unsupported file format: {}", self.detected)?;
+        match self.detected {
+            FileKind::FlatOpc => write!(f, ". Flat OPC documents are opened with Package::from_flat_opc_file"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Error for UnsupportedFormat {}
+
+/// An error indicating that a Flat OPC document can't be read
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlatOpcError {
+    /// The root element isn't `pkg:package`
+    NotAPackage(String),
+    /// The `pkg:binaryData` of the part isn't valid base64
+    InvalidBinaryData(String),
+}
+
+impl Display for FlatOpcError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FlatOpcError::NotAPackage(name) => write!(f, "Xml element '{}' is not a Flat OPC package", name),
+            FlatOpcError::InvalidBinaryData(part_name) => {
+                write!(f, "Binary data of part '{}' is not valid base64", part_name)
+            }
+        }
+    }
+}
+
+impl Error for FlatOpcError {}
+
+/// An error indicating that a range of the extracted text can't be annotated
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnnotateRangeError {
+    /// The package doesn't have a main document
+    MissingMainDocument,
+    /// The range doesn't contain any character coming from a run, e.g. it's empty or only contains the separators
+    /// inserted by the text extraction
+    EmptyRange,
+    /// The range ends after the end of the extracted text
+    OutOfBounds { end: usize, len: usize },
+}
+
+impl Display for AnnotateRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AnnotateRangeError::MissingMainDocument => write!(f, "Package has no main document"),
+            AnnotateRangeError::EmptyRange => write!(f, "Range doesn't contain any text of the document"),
+            AnnotateRangeError::OutOfBounds { end, len } => {
+                write!(f, "Range end {} is out of bounds. Text has {} characters", end, len)
+            }
+        }
+    }
+}
+
+impl Error for AnnotateRangeError {}
+
+/// An error indicating that the value of a legacy form field couldn't be set
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormFieldError {
+    /// The document doesn't have a form field with the name
+    NotFound(String),
+    /// The value doesn't match the type of the form field, e.g. a text is set on a check box
+    ValueMismatch,
+    /// The text is longer than the maximum length of the text input
+    TextTooLong { max_length: i64 },
+    /// The index of the selected entry is out of the entries of the drop-down list
+    EntryOutOfBounds { index: usize, len: usize },
+    /// The text input doesn't have a `separate` field character to place its result after
+    MissingResult,
+}
+
+impl Display for FormFieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FormFieldError::NotFound(name) => write!(f, "Form field '{}' not found", name),
+            FormFieldError::ValueMismatch => write!(f, "Value doesn't match the type of the form field"),
+            FormFieldError::TextTooLong { max_length } => {
+                write!(f, "Text is longer than the maximum length of {} characters", max_length)
+            }
+            FormFieldError::EntryOutOfBounds { index, len } => {
+                write!(
+                    f,
+                    "Entry {} is out of bounds. Drop-down list has {} entries",
+                    index, len
+                )
+            }
+            FormFieldError::MissingResult => write!(f, "Form field has no result"),
+        }
+    }
+}
+
+impl Error for FormFieldError {}
+
+/// An error indicating that the key of an obfuscated embedded font isn't a GUID
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidFontKeyError {
+    pub font_key: String,
+}
+
+impl Display for InvalidFontKeyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "'{}' is not a valid font key", self.font_key)
+    }
+}
+
+impl Error for InvalidFontKeyError {}
+
+/// An error indicating that a query of the [`query`](crate::query) module can't be parsed. Positions are byte offsets
+/// into the query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryParseError {
+    /// A step has no element name, e.g. the query ends with `/`
+    EmptyStep { position: usize },
+    /// A predicate isn't closed by `]`
+    UnclosedPredicate { position: usize },
+    /// A predicate is neither a position nor a path, e.g. `[0]` or `[@]`
+    InvalidPredicate { predicate: String, position: usize },
+    /// A step is followed by something other than a predicate or a separator
+    UnexpectedCharacter { position: usize },
+}
+
+impl Display for QueryParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            QueryParseError::EmptyStep { position } => write!(f, "Missing element name at {}", position),
+            QueryParseError::UnclosedPredicate { position } => write!(f, "Unclosed predicate at {}", position),
+            QueryParseError::InvalidPredicate { predicate, position } => {
+                write!(f, "Invalid predicate '{}' at {}", predicate, position)
+            }
+            QueryParseError::UnexpectedCharacter { position } => write!(f, "Unexpected character at {}", position),
+        }
+    }
+}
+
+impl Error for QueryParseError {}
+
+#[cfg(feature = "tokio")]
+/// An error indicating that a package couldn't be loaded from a
+/// [`PartSource`](crate::shared::partsource::PartSource)
+#[derive(Debug)]
+pub enum AsyncLoadError {
+    /// The parts couldn't be listed, or a part couldn't be fetched if `part_name` is set
+    Fetch {
+        part_name: Option<String>,
+        error: std::io::Error,
+    },
+    /// A part couldn't be parsed. The message of the parse error is kept, as parse errors can't be sent between
+    /// threads
+    Parse { part_name: String, message: String },
+    /// The parsing task panicked or was cancelled
+    Join(String),
+}
+
+#[cfg(feature = "tokio")]
+impl Display for AsyncLoadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            AsyncLoadError::Fetch { part_name: None, error } => write!(f, "Failed to list parts: {}", error),
+            AsyncLoadError::Fetch {
+                part_name: Some(part_name),
+                error,
+            } => write!(f, "Failed to fetch part '{}': {}", part_name, error),
+            AsyncLoadError::Parse { part_name, message } => {
+                write!(f, "Failed to parse part '{}': {}", part_name, message)
+            }
+            AsyncLoadError::Join(message) => write!(f, "Parsing task failed: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Error for AsyncLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AsyncLoadError::Fetch { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
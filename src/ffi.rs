@@ -0,0 +1,347 @@
+//! A C API over the WordprocessingML parser.
+//!
+//! Documents are opened into opaque handles, which are released with [`oox_document_free`]. Every string returned by
+//! the API is owned by the caller and must be released with [`oox_string_free`]. Functions that fail return a null
+//! pointer or a negative value and store the error message, which can be read with [`oox_last_error`]. The message is
+//! cleared by every call that can fail, so a null string without an error means the value isn't set. Panics are
+//! caught at the boundary and reported as errors.
+//!
+//! The C library is built with `cargo rustc --release --features ffi --crate-type cdylib`. The declarations of the API
+//! are in `include/oox.h`, which has to be updated along with this module.
+//!
+//! ```c
+//! OoxDocument *document = oox_document_open("report.docx");
+//! if (document == NULL) {
+//!     fprintf(stderr, "%s\n", oox_last_error());
+//!     return 1;
+//! }
+//! char *text = oox_document_extract_text(document);
+//! puts(text);
+//! oox_string_free(text);
+//! oox_document_free(document);
+//! ```
+
+use crate::docx::{package::Package, text::TextOptions};
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString},
+    os::raw::{c_char, c_int, c_longlong},
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    ptr,
+};
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error<T: ToString>(error: T) {
+    // Interior nul bytes would make the message unrepresentable, so they are dropped
+    let message = error.to_string().replace('\0', "");
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = CString::new(message).ok());
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+}
+
+/// Runs an entry point that can fail with the last error cleared. A panic is reported as an error instead of unwinding
+/// into the caller, which is undefined behavior.
+fn call<T, F: FnOnce() -> T>(failure: T, body: F) -> T {
+    clear_last_error();
+    catch_panic(failure, body)
+}
+
+fn catch_panic<T, F: FnOnce() -> T>(failure: T, body: F) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| String::from(*message))
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        set_last_error(format!("Panicked: {}", message));
+        failure
+    })
+}
+
+fn into_c_string(value: String) -> *mut c_char {
+    match CString::new(value) {
+        Ok(value) => value.into_raw(),
+        Err(error) => {
+            set_last_error(error);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// An opened WordprocessingML document.
+pub struct OoxDocument {
+    package: Package,
+}
+
+/// The core properties of a document, see [`oox_document_core_property`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OoxCoreProperty {
+    Title = 0,
+    Creator = 1,
+    LastModifiedBy = 2,
+    Created = 3,
+    Modified = 4,
+}
+
+impl OoxCoreProperty {
+    /// Converts the value passed by the caller. It's received as an integer, since an out of range value of the enum
+    /// itself would be undefined behavior.
+    fn from_raw(value: c_int) -> Option<Self> {
+        match value {
+            0 => Some(OoxCoreProperty::Title),
+            1 => Some(OoxCoreProperty::Creator),
+            2 => Some(OoxCoreProperty::LastModifiedBy),
+            3 => Some(OoxCoreProperty::Created),
+            4 => Some(OoxCoreProperty::Modified),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the message of the error of the last call that could fail on the calling thread, or null if it succeeded.
+/// The message is owned by the library and is valid until the next such call on the same thread.
+#[no_mangle]
+pub extern "C" fn oox_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Opens and parses a document. Returns null on failure.
+///
+/// # Safety
+///
+/// `path` must be a valid nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_open(path: *const c_char) -> *mut OoxDocument {
+    call(ptr::null_mut(), || {
+        if path.is_null() {
+            set_last_error("Path is null");
+            return ptr::null_mut();
+        }
+
+        let path = match CStr::from_ptr(path).to_str() {
+            Ok(path) => path,
+            Err(error) => {
+                set_last_error(error);
+                return ptr::null_mut();
+            }
+        };
+
+        match Package::from_file(Path::new(path)) {
+            Ok(package) => Box::into_raw(Box::new(OoxDocument { package })),
+            Err(error) => {
+                set_last_error(error);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Releases a document. Does nothing if `document` is null.
+///
+/// # Safety
+///
+/// `document` must be null or a handle returned by [`oox_document_open`] that hasn't been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_free(document: *mut OoxDocument) {
+    catch_panic((), || {
+        if !document.is_null() {
+            drop(Box::from_raw(document));
+        }
+    })
+}
+
+/// Extracts the plain text of the main document with the default [`TextOptions`]. Returns null on failure.
+///
+/// # Safety
+///
+/// `document` must be a valid handle returned by [`oox_document_open`].
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_extract_text(document: *const OoxDocument) -> *mut c_char {
+    call(ptr::null_mut(), || match document.as_ref() {
+        Some(document) => into_c_string(document.package.extract_text(&TextOptions::default())),
+        None => {
+            set_last_error("Document is null");
+            ptr::null_mut()
+        }
+    })
+}
+
+/// Returns the number of paragraphs of the body of the main document, including the paragraphs of tables. Returns -1
+/// on failure.
+///
+/// # Safety
+///
+/// `document` must be a valid handle returned by [`oox_document_open`].
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_paragraph_count(document: *const OoxDocument) -> c_longlong {
+    call(-1, || match document.as_ref() {
+        Some(document) => document
+            .package
+            .main_document
+            .as_ref()
+            .map_or(0, |main_document| main_document.paragraphs().count() as c_longlong),
+        None => {
+            set_last_error("Document is null");
+            -1
+        }
+    })
+}
+
+/// Returns a core property of the document. `property` is one of the values of [`OoxCoreProperty`]. Returns null if
+/// the property isn't set, in which case the last error is cleared, or on failure, in which case the last error is
+/// set.
+///
+/// # Safety
+///
+/// `document` must be a valid handle returned by [`oox_document_open`].
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_core_property(document: *const OoxDocument, property: c_int) -> *mut c_char {
+    call(ptr::null_mut(), || {
+        let document = match document.as_ref() {
+            Some(document) => document,
+            None => {
+                set_last_error("Document is null");
+                return ptr::null_mut();
+            }
+        };
+        let property = match OoxCoreProperty::from_raw(property) {
+            Some(property) => property,
+            None => {
+                set_last_error(format!("Invalid core property: {}", property));
+                return ptr::null_mut();
+            }
+        };
+
+        let value = document.package.core.as_ref().and_then(|core| match property {
+            OoxCoreProperty::Title => core.title.clone(),
+            OoxCoreProperty::Creator => core.creator.clone(),
+            OoxCoreProperty::LastModifiedBy => core.last_modified_by.clone(),
+            OoxCoreProperty::Created => core.created_time.clone(),
+            OoxCoreProperty::Modified => core.modified_time.clone(),
+        });
+        value.map_or(ptr::null_mut(), into_c_string)
+    })
+}
+
+/// Returns the revision number of the document. Returns -1 if it isn't set, in which case the last error is cleared,
+/// or on failure, in which case the last error is set.
+///
+/// # Safety
+///
+/// `document` must be a valid handle returned by [`oox_document_open`].
+#[no_mangle]
+pub unsafe extern "C" fn oox_document_revision(document: *const OoxDocument) -> c_int {
+    call(-1, || match document.as_ref() {
+        Some(document) => document
+            .package
+            .core
+            .as_ref()
+            .and_then(|core| core.revision)
+            .unwrap_or(-1),
+        None => {
+            set_last_error("Document is null");
+            -1
+        }
+    })
+}
+
+/// Releases a string returned by the library. Does nothing if `value` is null.
+///
+/// # Safety
+///
+/// `value` must be null or a string returned by the library that hasn't been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn oox_string_free(value: *mut c_char) {
+    catch_panic((), || {
+        if !value.is_null() {
+            drop(CString::from_raw(value));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_document_handle() {
+        let document = Box::into_raw(Box::new(OoxDocument {
+            package: Package::default(),
+        }));
+        unsafe {
+            assert_eq!(oox_document_paragraph_count(document), 0);
+            assert!(oox_document_core_property(document, OoxCoreProperty::Title as c_int).is_null());
+            assert_eq!(oox_document_revision(document), -1);
+            assert!(oox_last_error().is_null());
+            assert!(oox_document_core_property(document, 5).is_null());
+            assert!(!oox_last_error().is_null());
+            assert_eq!(oox_document_revision(ptr::null()), -1);
+            assert!(!oox_last_error().is_null());
+
+            let text = oox_document_extract_text(document);
+            assert_eq!(CStr::from_ptr(text).to_str(), Ok(""));
+            oox_string_free(text);
+            oox_document_free(document);
+
+            let path = CString::new("missing.docx").unwrap();
+            assert!(oox_document_open(path.as_ptr()).is_null());
+            assert!(!oox_last_error().is_null());
+
+            let document = Box::into_raw(Box::new(OoxDocument {
+                package: Package::default(),
+            }));
+            assert!(oox_document_core_property(document, OoxCoreProperty::Creator as c_int).is_null());
+            assert!(oox_last_error().is_null());
+            oox_document_free(document);
+        }
+    }
+
+    #[test]
+    pub fn test_catch_panic() {
+        assert_eq!(call(-1, || panic!("invalid state")), -1);
+        let message = unsafe { CStr::from_ptr(oox_last_error()) };
+        assert_eq!(message.to_str(), Ok("Panicked: invalid state"));
+
+        assert_eq!(call(-1, || 1), 1);
+        assert!(oox_last_error().is_null());
+    }
+
+    #[test]
+    pub fn test_header_declarations() {
+        let header = include_str!("../include/oox.h");
+        for declaration in &[
+            "const char *oox_last_error(void);",
+            "OoxDocument *oox_document_open(const char *path);",
+            "void oox_document_free(OoxDocument *document);",
+            "char *oox_document_extract_text(const OoxDocument *document);",
+            "long long oox_document_paragraph_count(const OoxDocument *document);",
+            "char *oox_document_core_property(const OoxDocument *document, int property);",
+            "int oox_document_revision(const OoxDocument *document);",
+            "void oox_string_free(char *value);",
+        ] {
+            assert!(header.contains(declaration), "{} is missing from oox.h", declaration);
+        }
+
+        for (name, property) in &[
+            ("OOX_CORE_PROPERTY_TITLE", OoxCoreProperty::Title),
+            ("OOX_CORE_PROPERTY_CREATOR", OoxCoreProperty::Creator),
+            ("OOX_CORE_PROPERTY_LAST_MODIFIED_BY", OoxCoreProperty::LastModifiedBy),
+            ("OOX_CORE_PROPERTY_CREATED", OoxCoreProperty::Created),
+            ("OOX_CORE_PROPERTY_MODIFIED", OoxCoreProperty::Modified),
+        ] {
+            assert!(header.contains(&format!("{} = {},", name, *property as c_int)));
+        }
+    }
+}
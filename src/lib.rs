@@ -1,10 +1,19 @@
-#![forbid(unsafe_code)]
+// The C API and the Python bindings need unsafe code, which is only allowed in their modules
+#![cfg_attr(not(any(feature = "ffi", feature = "python")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "ffi", feature = "python"), deny(unsafe_code))]
 
 #[cfg(any(test, feature = "docx"))]
 pub mod docx;
 pub mod error;
+#[cfg(feature = "ffi")]
+#[allow(unsafe_code)]
+pub mod ffi;
 #[cfg(any(test, feature = "pptx"))]
 pub mod pptx;
+#[cfg(feature = "python")]
+#[allow(unsafe_code)]
+pub mod python;
+pub mod query;
 pub mod shared;
 pub mod update;
 pub mod xml;
@@ -0,0 +1,484 @@
+//! Evaluation of the auto-fit settings of text bodies (`a:normAutofit` and `a:spAutoFit`).
+//!
+//! PowerPoint stores the result of the last auto-fit in the file, which is stale when the text was edited by another
+//! producer. [`TextFitter`] lays out the text with the metrics of a [`GlyphMetrics`] implementation and computes the
+//! font scale and line spacing reduction PowerPoint would apply, or the shape height needed to fit the text.
+//!
+//! The layout is approximate: words are wrapped greedily, kerning, bullets and tab stops are ignored and theme fonts
+//! are measured with the metrics of Arial.
+
+use super::pml::slides::Shape;
+use crate::{
+    shared::{
+        drawingml::{
+            coordsys::PositiveSize2D,
+            core::TextBody,
+            simpletypes::{TextSpacingPercent, TextWrappingType},
+            text::{
+                bodyformatting::{TextAutoFit, TextBodyProperties, TextNormalAutoFit},
+                bullet::TextListStyle,
+                paragraphs::{TextCharacterProperties, TextParagraph, TextParagraphProperties, TextSpacing},
+                runformatting::TextRun,
+            },
+        },
+        fontmetrics::{is_east_asian, FontFace, GlyphMetrics, ARIAL},
+        sharedtypes::Length,
+    },
+    update::Update,
+};
+
+/// The font size used when neither the runs nor the list styles specify one, in points.
+pub const DEFAULT_FONT_SIZE: f64 = 18.0;
+
+/// The default left and right insets of a text body in EMUs.
+const DEFAULT_HORIZONTAL_INSET: f64 = 91_440.0;
+/// The default top and bottom insets of a text body in EMUs.
+const DEFAULT_VERTICAL_INSET: f64 = 45_720.0;
+
+/// The font scale and line spacing reduction pairs tried by the shrink on overflow auto-fit, in the order PowerPoint
+/// tries them. Percentages are in thousandths of a percent like the attributes of `a:normAutofit`.
+const NORMAL_AUTOFIT_STEPS: [(TextSpacingPercent, TextSpacingPercent); 13] = [
+    (100_000.0, 0.0),
+    (100_000.0, 10_000.0),
+    (100_000.0, 20_000.0),
+    (92_500.0, 20_000.0),
+    (85_000.0, 20_000.0),
+    (77_500.0, 20_000.0),
+    (70_000.0, 20_000.0),
+    (62_500.0, 20_000.0),
+    (55_000.0, 20_000.0),
+    (47_500.0, 20_000.0),
+    (40_000.0, 20_000.0),
+    (32_500.0, 20_000.0),
+    (25_000.0, 20_000.0),
+];
+
+/// The result of fitting a text body into its shape, see [`TextFitter::fit`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextFit {
+    /// The font scale and line spacing reduction the text is laid out with. Both are `None` at 100% and no reduction,
+    /// which is always the case unless the text body shrinks text on overflow.
+    pub normal_autofit: TextNormalAutoFit,
+    /// The height of the laid out text, without the insets.
+    pub text_height: Length,
+    /// The height of the shape needed to fit the text, including the insets. A shape resized to fit its text
+    /// (`a:spAutoFit`) takes this height.
+    pub shape_height: Length,
+    /// Whether the text fits into the shape. Always `true` for shapes resized to fit their text.
+    pub fits: bool,
+    /// The effective font sizes of the regular runs and text fields of each paragraph in points, after applying the
+    /// font scale.
+    pub font_sizes: Vec<Vec<f64>>,
+}
+
+/// Lays out text bodies to evaluate their auto-fit settings using a glyph metrics provider.
+pub struct TextFitter<'a, M: GlyphMetrics + ?Sized> {
+    metrics: &'a M,
+    list_style: Option<&'a TextListStyle>,
+}
+
+impl<'a, M: GlyphMetrics + ?Sized> TextFitter<'a, M> {
+    pub fn new(metrics: &'a M) -> Self {
+        Self {
+            metrics,
+            list_style: None,
+        }
+    }
+
+    /// Creates a fitter using the inherited list style as the base of the paragraph and run properties, e.g. the
+    /// merged list style of a [`ResolvedPlaceholder`](super::inheritance::ResolvedPlaceholder). The list style of the
+    /// text body is merged on top of it.
+    pub fn with_list_style(metrics: &'a M, list_style: Option<&'a TextListStyle>) -> Self {
+        Self { metrics, list_style }
+    }
+
+    /// Fits the text body into a shape of the given extents according to its auto-fit type.
+    ///
+    /// ```
+    /// use oox::{
+    ///     pptx::autofit::TextFitter,
+    ///     shared::{drawingml::{coordsys::PositiveSize2D, core::TextBody}, fontmetrics::BuiltinMetrics},
+    ///     xml::XmlNode,
+    /// };
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<p:txBody>
+    ///     <a:bodyPr><a:normAutofit /></a:bodyPr>
+    ///     <a:p><a:r><a:rPr sz="4000" /><a:t>A long title that needs to shrink</a:t></a:r></a:p>
+    /// </p:txBody>"#;
+    /// let text_body = TextBody::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// let extents = PositiveSize2D { width: 3_000_000, height: 600_000 };
+    ///
+    /// let fit = TextFitter::new(&BuiltinMetrics).fit(&text_body, &extents);
+    /// assert!(fit.fits);
+    /// assert!(fit.normal_autofit.font_scale.is_some());
+    /// assert!(fit.font_sizes[0][0] < 40.0);
+    /// ```
+    pub fn fit(&self, text_body: &TextBody, extents: &PositiveSize2D) -> TextFit {
+        let body_properties = &text_body.body_properties;
+        let (left_inset, top_inset, right_inset, bottom_inset) = insets(body_properties);
+        let width = (extents.width as f64 - left_inset - right_inset).max(0.0);
+        let height = (extents.height as f64 - top_inset - bottom_inset).max(0.0);
+        let wrap_width = match body_properties.wrap_type {
+            Some(TextWrappingType::None) => None,
+            _ => Some(Length::from_emu(width).to_points()),
+        };
+        let list_style = self.effective_list_style(text_body);
+
+        let layout = |font_scale: TextSpacingPercent, line_spacing_reduction: TextSpacingPercent| {
+            let text_height: f64 = text_body
+                .paragraph_array
+                .iter()
+                .map(|paragraph| {
+                    self.paragraph_height(
+                        paragraph,
+                        &list_style,
+                        wrap_width,
+                        f64::from(font_scale) / 100_000.0,
+                        f64::from(line_spacing_reduction) / 100_000.0,
+                    )
+                })
+                .sum();
+            Length::from_points(text_height)
+        };
+
+        let (font_scale, line_spacing_reduction, text_height, fits) = match &body_properties.auto_fit_type {
+            Some(TextAutoFit::NormalAutoFit(_)) => {
+                let mut steps = NORMAL_AUTOFIT_STEPS.iter().map(|(font_scale, line_spacing_reduction)| {
+                    let text_height = layout(*font_scale, *line_spacing_reduction);
+                    (*font_scale, *line_spacing_reduction, text_height)
+                });
+                let mut last = None;
+                let fitting_step = steps.find(|step| {
+                    last = Some(*step);
+                    step.2.to_emu() <= height
+                });
+
+                match fitting_step {
+                    Some((font_scale, line_spacing_reduction, text_height)) => {
+                        (font_scale, line_spacing_reduction, text_height, true)
+                    }
+                    None => {
+                        let (font_scale, line_spacing_reduction, text_height) = last.unwrap();
+                        (font_scale, line_spacing_reduction, text_height, false)
+                    }
+                }
+            }
+            Some(TextAutoFit::ShapeAutoFit) => (100_000.0, 0.0, layout(100_000.0, 0.0), true),
+            _ => {
+                let text_height = layout(100_000.0, 0.0);
+                (100_000.0, 0.0, text_height, text_height.to_emu() <= height)
+            }
+        };
+
+        let font_sizes = text_body
+            .paragraph_array
+            .iter()
+            .map(|paragraph| {
+                let paragraph_properties = paragraph_properties(paragraph, &list_style);
+                paragraph
+                    .text_run_list
+                    .iter()
+                    .filter_map(|run| match run {
+                        TextRun::RegularTextRun(run) => Some(run.char_properties.as_deref()),
+                        TextRun::TextField(field) => Some(field.char_properties.as_deref()),
+                        TextRun::LineBreak(_) => None,
+                    })
+                    .map(|char_properties| {
+                        font_size(&run_properties(&paragraph_properties, char_properties)) * f64::from(font_scale)
+                            / 100_000.0
+                    })
+                    .collect()
+            })
+            .collect();
+
+        TextFit {
+            normal_autofit: TextNormalAutoFit {
+                font_scale: Some(font_scale).filter(|font_scale| *font_scale < 100_000.0),
+                line_spacing_reduction: Some(line_spacing_reduction)
+                    .filter(|line_spacing_reduction| *line_spacing_reduction > 0.0),
+            },
+            text_height,
+            shape_height: Length::from_emu(text_height.to_emu() + top_inset + bottom_inset),
+            fits,
+            font_sizes,
+        }
+    }
+
+    fn effective_list_style(&self, text_body: &TextBody) -> TextListStyle {
+        let inherited = self.list_style.cloned().unwrap_or_default();
+        match &text_body.list_style {
+            Some(list_style) => inherited.update_with((**list_style).clone()),
+            None => inherited,
+        }
+    }
+
+    /// Returns the height of the paragraph in points including the space before and after it.
+    fn paragraph_height(
+        &self,
+        paragraph: &TextParagraph,
+        list_style: &TextListStyle,
+        wrap_width: Option<f64>,
+        font_scale: f64,
+        line_spacing_reduction: f64,
+    ) -> f64 {
+        let paragraph_properties = paragraph_properties(paragraph, list_style);
+        let lines = self.layout_lines(paragraph, &paragraph_properties, wrap_width, font_scale);
+
+        let line_heights = lines.iter().map(|line| match &paragraph_properties.line_spacing {
+            Some(TextSpacing::Point(points)) => f64::from(*points) / 100.0,
+            Some(TextSpacing::Percent(percent)) => {
+                line.height * (f64::from(*percent) / 100_000.0 - line_spacing_reduction).max(0.0)
+            }
+            None => line.height * (1.0 - line_spacing_reduction),
+        });
+        let first_line_size = lines.first().map_or(0.0, |line| line.font_size);
+        let spacing = |spacing: Option<&TextSpacing>| match spacing {
+            Some(TextSpacing::Point(points)) => f64::from(*points) / 100.0,
+            Some(TextSpacing::Percent(percent)) => first_line_size * f64::from(*percent) / 100_000.0,
+            None => 0.0,
+        };
+
+        line_heights.sum::<f64>()
+            + spacing(paragraph_properties.space_before.as_ref())
+            + spacing(paragraph_properties.space_after.as_ref())
+    }
+
+    /// Breaks the paragraph into lines. An empty paragraph still takes a line with the size of its end paragraph mark.
+    fn layout_lines(
+        &self,
+        paragraph: &TextParagraph,
+        paragraph_properties: &TextParagraphProperties,
+        wrap_width: Option<f64>,
+        font_scale: f64,
+    ) -> Vec<Line> {
+        let mut words = Vec::new();
+        let mut word = Word::default();
+        for run in &paragraph.text_run_list {
+            let (text, char_properties) = match run {
+                TextRun::RegularTextRun(run) => (run.text.as_str(), run.char_properties.as_deref()),
+                TextRun::TextField(field) => (field.text.as_deref().unwrap_or(""), field.char_properties.as_deref()),
+                TextRun::LineBreak(line_break) => {
+                    let properties = run_properties(paragraph_properties, line_break.char_properties.as_deref());
+                    word.extend_height(
+                        self.line_height(&properties, font_scale),
+                        font_size(&properties) * font_scale,
+                    );
+                    word.line_break = true;
+                    words.push(std::mem::take(&mut word));
+                    continue;
+                }
+            };
+
+            let properties = run_properties(paragraph_properties, char_properties);
+            let size = font_size(&properties) * font_scale;
+            let line_height = self.line_height(&properties, font_scale);
+            let face = font_face(&properties);
+            for c in text.chars() {
+                let advance_width = self
+                    .metrics
+                    .advance_width(face, c)
+                    .or_else(|| self.metrics.advance_width(FontFace { name: ARIAL, ..face }, c))
+                    .unwrap_or(0.5)
+                    * size;
+
+                if c.is_whitespace() {
+                    word.trailing_width += advance_width;
+                } else {
+                    if word.trailing_width > 0.0 || (is_east_asian(c) && word.width > 0.0) {
+                        words.push(std::mem::take(&mut word));
+                    }
+                    word.width += advance_width;
+                }
+                word.extend_height(line_height, size);
+            }
+        }
+
+        if word.width > 0.0 || word.trailing_width > 0.0 || words.last().is_none_or(|word| word.line_break) {
+            if word.height == 0.0 {
+                let properties =
+                    run_properties(paragraph_properties, paragraph.end_paragraph_char_properties.as_deref());
+                word.extend_height(
+                    self.line_height(&properties, font_scale),
+                    font_size(&properties) * font_scale,
+                );
+            }
+            words.push(word);
+        }
+
+        let mut lines: Vec<Line> = Vec::new();
+        let mut line_width = 0.0;
+        let mut starts_line = true;
+        for word in words {
+            let overflows = wrap_width.is_some_and(|wrap_width| line_width + word.width > wrap_width);
+            if starts_line || (overflows && line_width > 0.0) {
+                lines.push(Line::default());
+                line_width = 0.0;
+            }
+
+            let line = lines.last_mut().unwrap();
+            line.height = line.height.max(word.height);
+            line.font_size = line.font_size.max(word.font_size);
+            line_width += word.width + word.trailing_width;
+            starts_line = word.line_break;
+        }
+
+        lines
+    }
+
+    /// Returns the single spaced line height of the run in points.
+    fn line_height(&self, properties: &TextCharacterProperties, font_scale: f64) -> f64 {
+        self.metrics.line_metrics(font_face(properties)).line_height() * font_size(properties) * font_scale
+    }
+}
+
+impl Shape {
+    /// Fits the text of the shape into the extents of its transform, see [`TextFitter::fit`]. Returns `None` if the
+    /// shape has no text body or no extents, e.g. a placeholder inheriting its position from the slide layout.
+    pub fn fit_text<M: GlyphMetrics + ?Sized>(&self, metrics: &M) -> Option<TextFit> {
+        let extents = self.shape_props.transform.as_ref()?.extents.as_ref()?;
+        Some(TextFitter::new(metrics).fit(self.text_body.as_ref()?, extents))
+    }
+}
+
+/// A word and the whitespace following it, which doesn't count towards the width when wrapping lines.
+#[derive(Debug, Default)]
+struct Word {
+    width: f64,
+    trailing_width: f64,
+    /// The tallest single spaced line height of the characters of the word.
+    height: f64,
+    /// The largest font size of the characters of the word.
+    font_size: f64,
+    line_break: bool,
+}
+
+impl Word {
+    fn extend_height(&mut self, height: f64, font_size: f64) {
+        self.height = self.height.max(height);
+        self.font_size = self.font_size.max(font_size);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Line {
+    height: f64,
+    font_size: f64,
+}
+
+/// Returns the left, top, right and bottom insets of the text body in EMUs.
+fn insets(body_properties: &TextBodyProperties) -> (f64, f64, f64, f64) {
+    let inset = |inset: Option<i32>, default| inset.map_or(default, f64::from);
+    (
+        inset(body_properties.left_inset, DEFAULT_HORIZONTAL_INSET),
+        inset(body_properties.top_inset, DEFAULT_VERTICAL_INSET),
+        inset(body_properties.right_inset, DEFAULT_HORIZONTAL_INSET),
+        inset(body_properties.bottom_inset, DEFAULT_VERTICAL_INSET),
+    )
+}
+
+fn paragraph_properties(paragraph: &TextParagraph, list_style: &TextListStyle) -> TextParagraphProperties {
+    let level = paragraph
+        .properties
+        .as_ref()
+        .and_then(|properties| properties.level)
+        .unwrap_or(0);
+    let inherited = list_style.paragraph_properties(level);
+    match &paragraph.properties {
+        Some(properties) => inherited.update_with((**properties).clone()),
+        None => inherited,
+    }
+}
+
+fn run_properties(
+    paragraph_properties: &TextParagraphProperties,
+    char_properties: Option<&TextCharacterProperties>,
+) -> TextCharacterProperties {
+    let inherited = paragraph_properties
+        .default_run_properties
+        .as_deref()
+        .cloned()
+        .unwrap_or_default();
+    match char_properties {
+        Some(char_properties) => inherited.update_with(char_properties.clone()),
+        None => inherited,
+    }
+}
+
+/// Returns the unscaled font size of the run in points.
+fn font_size(properties: &TextCharacterProperties) -> f64 {
+    properties
+        .font_size
+        .map_or(DEFAULT_FONT_SIZE, |font_size| f64::from(font_size) / 100.0)
+}
+
+/// Returns the face of the latin font of the run. Theme fonts (`+mj-lt`, `+mn-lt`) aren't resolved and are measured as
+/// Arial.
+fn font_face(properties: &TextCharacterProperties) -> FontFace<'_> {
+    let name = properties
+        .latin_font
+        .as_ref()
+        .map(|font| font.typeface.as_str())
+        .filter(|typeface| !typeface.starts_with('+'))
+        .unwrap_or(ARIAL);
+
+    FontFace {
+        name,
+        bold: properties.bold.unwrap_or(false),
+        italic: properties.italic.unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shared::fontmetrics::BuiltinMetrics, xml::XmlNode};
+    use std::str::FromStr;
+
+    fn text_body(autofit: &str, text: &str) -> TextBody {
+        let xml = format!(
+            r#"<p:txBody>
+                <a:bodyPr lIns="0" tIns="0" rIns="0" bIns="0">{}</a:bodyPr>
+                <a:lstStyle>
+                    <a:lvl1pPr><a:defRPr sz="2000"><a:latin typeface="Arial" /></a:defRPr></a:lvl1pPr>
+                </a:lstStyle>
+                <a:p><a:r><a:t>{}</a:t></a:r></a:p>
+                <a:p><a:endParaRPr sz="1000" /></a:p>
+            </p:txBody>"#,
+            autofit, text
+        );
+        TextBody::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_fit_text_body() {
+        let fitter = TextFitter::new(&BuiltinMetrics);
+        // "Hello " is 51.12 points wide at 20 points, so the width of 4 inches fits five words per line
+        let extents = PositiveSize2D {
+            width: 3_657_600,
+            height: 457_200,
+        };
+        let text = "Hello Hello Hello Hello Hello Hello";
+
+        let line_height = 1.15 * 20.0;
+        let fit = fitter.fit(&text_body("", text), &extents);
+        assert!(!fit.fits);
+        assert_eq!(fit.normal_autofit, TextNormalAutoFit::default());
+        assert!((fit.text_height.to_points() - (2.0 * line_height + 1.15 * 10.0)).abs() < 1e-9);
+        assert_eq!(fit.font_sizes, vec![vec![20.0], vec![]]);
+
+        let fit = fitter.fit(&text_body("<a:spAutoFit />", text), &extents);
+        assert!(fit.fits);
+        assert_eq!(fit.shape_height, fit.text_height);
+
+        let fit = fitter.fit(&text_body("<a:normAutofit />", text), &extents);
+        assert!(fit.fits);
+        assert_eq!(fit.normal_autofit.font_scale, Some(92_500.0));
+        assert_eq!(fit.normal_autofit.line_spacing_reduction, Some(20_000.0));
+        assert_eq!(fit.font_sizes[0], vec![18.5]);
+        assert!((fit.text_height.to_points() - 0.74 * (line_height + 1.15 * 10.0)).abs() < 1e-9);
+
+        let fit = fitter.fit(&text_body("<a:normAutofit />", "Hello"), &extents);
+        assert_eq!(fit.normal_autofit, TextNormalAutoFit::default());
+    }
+}
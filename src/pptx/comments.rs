@@ -0,0 +1,289 @@
+use super::{
+    package::Package,
+    pml::comments::{
+        Comment, CommentAuthorList, CommentList, ModernAuthorList, ModernComment, ModernCommentList,
+        ModernCommentStatus, COMMENTS_RELATION_TYPE, COMMENT_AUTHORS_RELATION_TYPE, MODERN_AUTHORS_RELATION_TYPE,
+        MODERN_COMMENTS_RELATION_TYPE,
+    },
+    text::{text_body_paragraphs, ParagraphText},
+};
+use crate::{
+    error::SlideIndexOutOfBoundsError,
+    shared::{
+        drawingml::{coordsys::Point2D, core::TextBody, simpletypes::DrawingElementId},
+        relationship::{join_part_path, relationships_from_zip_file, rels_path_for_part},
+    },
+    xml::{zip_file_to_xml_node, XmlNode},
+};
+use std::{
+    error::Error,
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+use zip::ZipArchive;
+
+const PRESENTATION_PART_NAME: &str = "ppt/presentation.xml";
+
+/// A reply to a modern comment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideCommentReply {
+    pub author: Option<String>,
+    pub date: Option<String>,
+    pub text: String,
+}
+
+/// A legacy or modern comment of a slide with its author resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideComment {
+    /// The 0-based index of the slide in presentation order.
+    pub slide_index: usize,
+    /// The name of the author, `None` if the author isn't in the author list.
+    pub author: Option<String>,
+    pub author_initials: Option<String>,
+    pub date: Option<String>,
+    /// The shape the comment is anchored to. Legacy comments are only anchored to a position.
+    pub anchor_shape_id: Option<DrawingElementId>,
+    pub position: Option<Point2D>,
+    pub text: String,
+    /// The status of a modern comment, `None` for legacy comments.
+    pub status: Option<ModernCommentStatus>,
+    pub replies: Vec<SlideCommentReply>,
+}
+
+impl SlideComment {
+    pub fn from_legacy_comment(slide_index: usize, comment: &Comment, authors: &CommentAuthorList) -> Self {
+        let author = authors.author(comment.author_id);
+        Self {
+            slide_index,
+            author: author.map(|author| author.name.clone()),
+            author_initials: author.map(|author| author.initials.clone()),
+            date: comment.date.clone(),
+            anchor_shape_id: None,
+            position: Some(comment.position),
+            text: comment.text.clone(),
+            status: None,
+            replies: Vec::new(),
+        }
+    }
+
+    pub fn from_modern_comment(slide_index: usize, comment: &ModernComment, authors: &ModernAuthorList) -> Self {
+        let author = authors.author(&comment.author_id);
+        let replies = comment
+            .replies
+            .iter()
+            .map(|reply| SlideCommentReply {
+                author: authors.author(&reply.author_id).map(|author| author.name.clone()),
+                date: reply.created.clone(),
+                text: text_body_text(reply.text_body.as_ref()),
+            })
+            .collect();
+
+        Self {
+            slide_index,
+            author: author.map(|author| author.name.clone()),
+            author_initials: author.and_then(|author| author.initials.clone()),
+            date: comment.created.clone(),
+            anchor_shape_id: comment.anchor_shape_id,
+            position: comment.position,
+            text: text_body_text(comment.text_body.as_ref()),
+            status: Some(comment.status.unwrap_or(ModernCommentStatus::Active)),
+            replies,
+        }
+    }
+}
+
+fn text_body_text(text_body: Option<&TextBody>) -> String {
+    text_body
+        .map(|text_body| {
+            text_body_paragraphs(text_body)
+                .iter()
+                .map(ParagraphText::text)
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
+}
+
+impl Package {
+    /// Returns the authors of the legacy comments. Empty if the presentation has no comment authors part.
+    pub fn comment_authors(&self) -> Result<CommentAuthorList, Box<dyn Error>> {
+        let mut zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+        match presentation_related_part(&mut zipper, COMMENT_AUTHORS_RELATION_TYPE)? {
+            Some(xml_node) => CommentAuthorList::from_xml_element(&xml_node),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Returns the authors of the modern comments. Empty if the presentation has no authors part.
+    pub fn modern_comment_authors(&self) -> Result<ModernAuthorList, Box<dyn Error>> {
+        let mut zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+        match presentation_related_part(&mut zipper, MODERN_AUTHORS_RELATION_TYPE)? {
+            Some(xml_node) => ModernAuthorList::from_xml_element(&xml_node),
+            None => Ok(Default::default()),
+        }
+    }
+
+    /// Returns the legacy and modern comments of the slide at the given 0-based index, legacy comments first.
+    pub fn slide_comments(&self, index: usize) -> Result<Vec<SlideComment>, Box<dyn Error>> {
+        let slide_part = self
+            .slide_parts
+            .get(index)
+            .ok_or(SlideIndexOutOfBoundsError {
+                index,
+                len: self.slide_parts.len(),
+            })?
+            .to_string_lossy()
+            .into_owned();
+        let relationships = self
+            .slide_rels_map
+            .get(Path::new(&rels_path_for_part(&slide_part)))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let comment_parts = |rel_type| {
+            relationships
+                .iter()
+                .filter(move |rel| rel.rel_type == rel_type && !rel.is_external())
+                .map(|rel| join_part_path(&slide_part, &rel.target))
+                .collect::<Vec<_>>()
+        };
+        let legacy_parts = comment_parts(COMMENTS_RELATION_TYPE);
+        let modern_parts = comment_parts(MODERN_COMMENTS_RELATION_TYPE);
+        if legacy_parts.is_empty() && modern_parts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut zipper = ZipArchive::new(File::open(&self.file_path)?)?;
+        let mut comments = Vec::new();
+        if !legacy_parts.is_empty() {
+            let authors = match presentation_related_part(&mut zipper, COMMENT_AUTHORS_RELATION_TYPE)? {
+                Some(xml_node) => CommentAuthorList::from_xml_element(&xml_node)?,
+                None => Default::default(),
+            };
+            for part_name in legacy_parts {
+                let xml_node = zip_file_to_xml_node(&mut zipper.by_name(&part_name)?)?;
+                comments.extend(
+                    CommentList::from_xml_element(&xml_node)?
+                        .0
+                        .iter()
+                        .map(|comment| SlideComment::from_legacy_comment(index, comment, &authors)),
+                );
+            }
+        }
+
+        if !modern_parts.is_empty() {
+            let authors = match presentation_related_part(&mut zipper, MODERN_AUTHORS_RELATION_TYPE)? {
+                Some(xml_node) => ModernAuthorList::from_xml_element(&xml_node)?,
+                None => Default::default(),
+            };
+            for part_name in modern_parts {
+                let xml_node = zip_file_to_xml_node(&mut zipper.by_name(&part_name)?)?;
+                comments.extend(
+                    ModernCommentList::from_xml_element(&xml_node)?
+                        .0
+                        .iter()
+                        .map(|comment| SlideComment::from_modern_comment(index, comment, &authors)),
+                );
+            }
+        }
+
+        Ok(comments)
+    }
+
+    /// Returns the comments of the slides of the presentation in presentation order, see
+    /// [`Package::slide_comments`].
+    pub fn comments(&self) -> Result<Vec<SlideComment>, Box<dyn Error>> {
+        let mut comments = Vec::new();
+        for index in 0..self.slide_count() {
+            comments.extend(self.slide_comments(index)?);
+        }
+
+        Ok(comments)
+    }
+}
+
+/// Reads the part targeted by the first relationship of the given type of the presentation part.
+fn presentation_related_part<R: Read + Seek>(
+    zipper: &mut ZipArchive<R>,
+    rel_type: &str,
+) -> Result<Option<XmlNode>, Box<dyn Error>> {
+    let relationships = match zipper.by_name(&rels_path_for_part(PRESENTATION_PART_NAME)) {
+        Ok(mut zip_file) => relationships_from_zip_file(&mut zip_file)?,
+        Err(_) => return Ok(None),
+    };
+
+    let part_name = match relationships
+        .iter()
+        .find(|rel| rel.rel_type == rel_type && !rel.is_external())
+    {
+        Some(relationship) => join_part_path(PRESENTATION_PART_NAME, &relationship.target),
+        None => return Ok(None),
+    };
+
+    let xml_node = zip_file_to_xml_node(&mut zipper.by_name(&part_name)?)?;
+    Ok(Some(xml_node))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pptx::pml::comments::ModernCommentList;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_slide_comments() {
+        let authors = r#"<p:cmAuthorLst>
+            <p:cmAuthor id="0" name="Jane Doe" initials="JD" lastIdx="1" clrIdx="0" />
+        </p:cmAuthorLst>"#;
+        let comments = r#"<p:cmLst>
+            <p:cm authorId="0" dt="2020-05-04T10:00:00.000" idx="1">
+                <p:pos x="10" y="20" />
+                <p:text>Check the numbers</p:text>
+            </p:cm>
+        </p:cmLst>"#;
+        let authors = CommentAuthorList::from_xml_element(&XmlNode::from_str(authors).unwrap()).unwrap();
+        let comments = CommentList::from_xml_element(&XmlNode::from_str(comments).unwrap()).unwrap();
+        let comment = SlideComment::from_legacy_comment(0, &comments.0[0], &authors);
+        assert_eq!(comment.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(comment.position, Some(Point2D::new(10, 20)));
+        assert_eq!(comment.text, "Check the numbers");
+        assert_eq!(comment.status, None);
+
+        let authors = r#"<p188:authorLst>
+            <p188:author id="{A1}" name="John Roe" initials="JR" userId="john" providerId="AD" />
+        </p188:authorLst>"#;
+        let comments = r#"<p188:cmLst>
+            <p188:cm id="{C1}" authorId="{A1}" created="2021-03-02T08:00:00.000" status="resolved">
+                <ac:deMkLst>
+                    <pc:docMk />
+                    <pc:sldMk cId="1234" sldId="256" />
+                    <ac:spMk id="3" creationId="{S1}" />
+                </ac:deMkLst>
+                <p188:pos x="100" y="200" />
+                <p188:replyLst>
+                    <p188:reply id="{R1}" authorId="{A1}" created="2021-03-03T08:00:00.000">
+                        <p188:txBody><a:bodyPr /><a:p><a:r><a:t>Fixed</a:t></a:r></a:p></p188:txBody>
+                    </p188:reply>
+                </p188:replyLst>
+                <p188:txBody><a:bodyPr /><a:p><a:r><a:t>Typo</a:t></a:r></a:p></p188:txBody>
+            </p188:cm>
+        </p188:cmLst>"#;
+        let authors = ModernAuthorList::from_xml_element(&XmlNode::from_str(authors).unwrap()).unwrap();
+        let comments = ModernCommentList::from_xml_element(&XmlNode::from_str(comments).unwrap()).unwrap();
+        assert_eq!(comments.0[0].slide_id, Some(256));
+
+        let comment = SlideComment::from_modern_comment(1, &comments.0[0], &authors);
+        assert_eq!(comment.author.as_deref(), Some("John Roe"));
+        assert_eq!(comment.anchor_shape_id, Some(3));
+        assert_eq!(comment.text, "Typo");
+        assert_eq!(comment.status, Some(ModernCommentStatus::Resolved));
+        assert_eq!(
+            comment.replies,
+            vec![SlideCommentReply {
+                author: Some(String::from("John Roe")),
+                date: Some(String::from("2021-03-03T08:00:00.000")),
+                text: String::from("Fixed"),
+            }]
+        );
+    }
+}
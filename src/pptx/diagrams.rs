@@ -0,0 +1,118 @@
+use super::{
+    package::Package,
+    pml::slides::{GroupShape, ShapeGroup, Slide},
+};
+use crate::shared::{
+    drawingml::diagrams::{DataModel, DiagramRelationshipIds},
+    relationship::{join_part_path, rels_path_for_part},
+};
+use std::{error::Error, fs::File, path::Path};
+use zip::ZipArchive;
+
+type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+impl Slide {
+    /// Walks the shape tree of the slide, including group shapes, and returns the relationships to the parts of the
+    /// diagrams (SmartArt) displayed by the graphic frames of the slide in document order.
+    pub fn diagram_relationship_ids(&self) -> Vec<&DiagramRelationshipIds> {
+        let mut relationship_ids = Vec::new();
+        collect_group_shape_diagrams(&self.common_slide_data.shape_tree, &mut relationship_ids);
+        relationship_ids
+    }
+}
+
+impl Package {
+    /// Reads the data models of the diagrams displayed by the slide at the given 0-based index from the package file,
+    /// e.g. to extract the text of every SmartArt of the slide with [`DataModel::text`]. Diagrams with missing
+    /// relationships are skipped.
+    pub fn slide_diagram_data(&self, index: usize) -> Result<Vec<DataModel>> {
+        let slide = self.slide(index)?;
+        let slide_part = self.slide_parts[index].to_string_lossy().into_owned();
+        let relationships = self
+            .slide_rels_map
+            .get(Path::new(&rels_path_for_part(&slide_part)))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let part_names: Vec<_> = slide
+            .diagram_relationship_ids()
+            .into_iter()
+            .filter_map(|relationship_ids| relationships.iter().find(|rel| rel.id == relationship_ids.data))
+            .filter(|relationship| !relationship.is_external())
+            .map(|relationship| join_part_path(&slide_part, &relationship.target))
+            .collect();
+
+        if part_names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let pptx_file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&pptx_file)?;
+        part_names
+            .iter()
+            .map(|part_name| DataModel::from_zip_file(&mut zipper.by_name(part_name)?))
+            .collect()
+    }
+}
+
+fn collect_group_shape_diagrams<'a>(
+    group_shape: &'a GroupShape,
+    relationship_ids: &mut Vec<&'a DiagramRelationshipIds>,
+) {
+    for shape_group in &group_shape.shape_array {
+        match shape_group {
+            ShapeGroup::GraphicFrame(frame) => {
+                relationship_ids.extend(frame.graphic.graphic_data.diagram_relationship_ids.as_ref())
+            }
+            ShapeGroup::GroupShape(group_shape) => collect_group_shape_diagrams(group_shape, relationship_ids),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_slide_diagram_relationship_ids() {
+        let xml = r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:graphicFrame>
+                        <p:nvGraphicFramePr>
+                            <p:cNvPr id="4" name="Diagram 3" />
+                            <p:cNvGraphicFramePr />
+                            <p:nvPr />
+                        </p:nvGraphicFramePr>
+                        <p:xfrm><a:off x="0" y="0" /><a:ext cx="100" cy="100" /></p:xfrm>
+                        <a:graphic>
+                            <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/diagram">
+                                <dgm:relIds r:dm="rId2" r:lo="rId3" r:qs="rId4" r:cs="rId5" />
+                            </a:graphicData>
+                        </a:graphic>
+                    </p:graphicFrame>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(
+            slide.diagram_relationship_ids(),
+            vec![&DiagramRelationshipIds {
+                data: String::from("rId2"),
+                layout: String::from("rId3"),
+                quick_style: String::from("rId4"),
+                colors: String::from("rId5"),
+            }]
+        );
+    }
+}
@@ -0,0 +1,399 @@
+use super::{
+    package::Package,
+    pml::slides::{GroupShape, ShapeGroup, Slide},
+};
+use crate::shared::{
+    drawingml::{
+        core::{Hyperlink, NonVisualDrawingProps, TextBody},
+        simpletypes::DrawingElementId,
+        text::{paragraphs::TextCharacterProperties, runformatting::TextRun},
+    },
+    relationship::{join_part_path, rels_path_for_part, RelationshipId},
+};
+use std::{error::Error, path::Path};
+
+/// The prefix of the actions of hyperlinks understood by PowerPoint.
+pub const ACTION_PREFIX: &str = "ppaction://";
+
+/// What activates a hyperlink.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HyperlinkTrigger {
+    /// `a:hlinkClick`
+    Click,
+    /// `a:hlinkHover` of shapes and `a:hlinkMouseOver` of text runs
+    Hover,
+}
+
+/// The kind of a hyperlink, parsed from its `action` attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActionKind {
+    /// A hyperlink without an action, e.g. to a web page or an e-mail address.
+    Hyperlink,
+    /// Jumps to the slide targeted by the relationship of the hyperlink (`ppaction://hlinksldjump`).
+    SlideJump,
+    NextSlide,
+    PreviousSlide,
+    FirstSlide,
+    LastSlide,
+    LastSlideViewed,
+    EndShow,
+    /// Opens another presentation targeted by the relationship (`ppaction://hlinkpres`).
+    OtherPresentation,
+    /// Opens a file targeted by the relationship (`ppaction://hlinkfile`).
+    File,
+    /// Shows a custom show of the presentation (`ppaction://customshow?id=0&return=true`).
+    CustomShow {
+        id: Option<u32>,
+        return_to_slide: bool,
+    },
+    /// Runs the program targeted by the relationship (`ppaction://program`).
+    LaunchProgram,
+    /// Runs a macro of the presentation (`ppaction://macro?name=Module1.Main`).
+    Macro(String),
+    /// Performs a verb of an embedded OLE object (`ppaction://ole?verb=0`).
+    Ole {
+        verb: Option<i32>,
+    },
+    /// Plays the media of the shape (`ppaction://media`).
+    Media,
+    /// `ppaction://noaction`, used to attach only a sound to a shape.
+    NoAction,
+    /// An action this crate doesn't know about.
+    Unknown(String),
+}
+
+impl ActionKind {
+    pub fn from_action(action: Option<&str>) -> Self {
+        let action = match action.filter(|action| !action.is_empty()) {
+            Some(action) => action,
+            None => return ActionKind::Hyperlink,
+        };
+
+        let unknown = || ActionKind::Unknown(String::from(action));
+        let (verb, query) = match action.strip_prefix(ACTION_PREFIX) {
+            Some(rest) => rest.split_once('?').unwrap_or((rest, "")),
+            None => return unknown(),
+        };
+        let parameter = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value)
+        };
+
+        match verb.to_ascii_lowercase().as_str() {
+            "hlinksldjump" => ActionKind::SlideJump,
+            "hlinkshowjump" => match parameter("jump").map(str::to_ascii_lowercase).as_deref() {
+                Some("nextslide") => ActionKind::NextSlide,
+                Some("previousslide") => ActionKind::PreviousSlide,
+                Some("firstslide") => ActionKind::FirstSlide,
+                Some("lastslide") => ActionKind::LastSlide,
+                Some("lastslideviewed") => ActionKind::LastSlideViewed,
+                Some("endshow") => ActionKind::EndShow,
+                _ => unknown(),
+            },
+            "hlinkpres" => ActionKind::OtherPresentation,
+            "hlinkfile" => ActionKind::File,
+            "customshow" => ActionKind::CustomShow {
+                id: parameter("id").and_then(|id| id.parse().ok()),
+                return_to_slide: parameter("return").is_some_and(|value| value == "true" || value == "1"),
+            },
+            "program" => ActionKind::LaunchProgram,
+            "macro" => ActionKind::Macro(String::from(parameter("name").unwrap_or_default())),
+            "ole" => ActionKind::Ole {
+                verb: parameter("verb").and_then(|verb| verb.parse().ok()),
+            },
+            "media" => ActionKind::Media,
+            "noaction" => ActionKind::NoAction,
+            _ => unknown(),
+        }
+    }
+
+    /// Returns whether the action uses the target of the hyperlink's relationship.
+    pub fn has_target(&self) -> bool {
+        matches!(
+            self,
+            ActionKind::Hyperlink
+                | ActionKind::SlideJump
+                | ActionKind::OtherPresentation
+                | ActionKind::File
+                | ActionKind::LaunchProgram
+        )
+    }
+}
+
+/// A hyperlink of a shape or of a text run of a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperlinkReference {
+    pub shape_id: DrawingElementId,
+    pub trigger: HyperlinkTrigger,
+    /// Whether the hyperlink is set on a text run instead of the shape itself.
+    pub is_text: bool,
+    pub action: ActionKind,
+    pub hyperlink: Hyperlink,
+}
+
+/// A hyperlink of a slide with its relationship resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideHyperlink {
+    /// The 0-based index of the slide in presentation order.
+    pub slide_index: usize,
+    pub shape_id: DrawingElementId,
+    pub trigger: HyperlinkTrigger,
+    pub is_text: bool,
+    pub action: ActionKind,
+    pub relationship_id: Option<RelationshipId>,
+    /// The part name of the target stored in the package, or the URI of an external target.
+    pub target: Option<String>,
+    pub is_external: bool,
+    /// The 0-based index of the slide targeted by a [`ActionKind::SlideJump`].
+    pub target_slide_index: Option<usize>,
+    pub tooltip: Option<String>,
+}
+
+impl Slide {
+    /// Walks the shape tree of the slide, including group shapes, and returns the hyperlinks of the shapes and their
+    /// text runs in document order.
+    pub fn hyperlinks(&self) -> Vec<HyperlinkReference> {
+        let mut references = Vec::new();
+        collect_group_shape_hyperlinks(&self.common_slide_data.shape_tree, &mut references);
+        references
+    }
+}
+
+impl Package {
+    /// Returns the hyperlinks of the slide at the given 0-based index with their relationship targets resolved.
+    /// Targets are `None` if the hyperlink has no relationship, e.g. for [`ActionKind::NextSlide`], or if it's missing.
+    pub fn slide_hyperlinks(&self, index: usize) -> Result<Vec<SlideHyperlink>, Box<dyn Error>> {
+        let slide = self.slide(index)?;
+        let slide_part = self.slide_parts[index].to_string_lossy().into_owned();
+        let relationships = self
+            .slide_rels_map
+            .get(Path::new(&rels_path_for_part(&slide_part)))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let hyperlinks = slide
+            .hyperlinks()
+            .into_iter()
+            .map(|reference| {
+                let relationship_id = reference.hyperlink.relationship_id.filter(|id| !id.is_empty());
+                let relationship = relationship_id
+                    .as_ref()
+                    .and_then(|id| relationships.iter().find(|rel| &rel.id == id));
+                let is_external = relationship.is_some_and(|relationship| relationship.is_external());
+                let target = relationship.map(|relationship| match is_external {
+                    true => relationship.target.clone(),
+                    false => join_part_path(&slide_part, &relationship.target),
+                });
+                let target_slide_index = match (&reference.action, &target) {
+                    (ActionKind::SlideJump, Some(target)) if !is_external => self
+                        .slide_parts
+                        .iter()
+                        .position(|part| part.to_string_lossy() == target.as_str()),
+                    _ => None,
+                };
+
+                SlideHyperlink {
+                    slide_index: index,
+                    shape_id: reference.shape_id,
+                    trigger: reference.trigger,
+                    is_text: reference.is_text,
+                    action: reference.action,
+                    relationship_id,
+                    target,
+                    is_external,
+                    target_slide_index,
+                    tooltip: reference.hyperlink.tooltip,
+                }
+            })
+            .collect();
+
+        Ok(hyperlinks)
+    }
+
+    /// Returns the hyperlinks of the slides of the presentation in presentation order, see
+    /// [`Package::slide_hyperlinks`].
+    pub fn hyperlinks(&self) -> Result<Vec<SlideHyperlink>, Box<dyn Error>> {
+        let mut hyperlinks = Vec::new();
+        for index in 0..self.slide_count() {
+            hyperlinks.extend(self.slide_hyperlinks(index)?);
+        }
+
+        Ok(hyperlinks)
+    }
+}
+
+fn collect_group_shape_hyperlinks(group_shape: &GroupShape, references: &mut Vec<HyperlinkReference>) {
+    for shape_group in &group_shape.shape_array {
+        match shape_group {
+            ShapeGroup::Shape(shape) => {
+                let drawing_props = &shape.non_visual_props.drawing_props;
+                collect_drawing_props_hyperlinks(drawing_props, references);
+                if let Some(text_body) = &shape.text_body {
+                    collect_text_body_hyperlinks(drawing_props.id, text_body, references);
+                }
+            }
+            ShapeGroup::Picture(picture) => {
+                collect_drawing_props_hyperlinks(&picture.non_visual_props.drawing_props, references)
+            }
+            ShapeGroup::Connector(connector) => {
+                collect_drawing_props_hyperlinks(&connector.non_visual_props.drawing_props, references)
+            }
+            ShapeGroup::GraphicFrame(frame) => {
+                collect_drawing_props_hyperlinks(&frame.non_visual_props.drawing_props, references)
+            }
+            ShapeGroup::GroupShape(group_shape) => {
+                collect_drawing_props_hyperlinks(&group_shape.non_visual_props.drawing_props, references);
+                collect_group_shape_hyperlinks(group_shape, references);
+            }
+            ShapeGroup::ContentPart(_) => (),
+        }
+    }
+}
+
+fn hyperlink_reference(
+    shape_id: DrawingElementId,
+    trigger: HyperlinkTrigger,
+    is_text: bool,
+    hyperlink: &Hyperlink,
+) -> HyperlinkReference {
+    HyperlinkReference {
+        shape_id,
+        trigger,
+        is_text,
+        action: ActionKind::from_action(hyperlink.action.as_deref()),
+        hyperlink: hyperlink.clone(),
+    }
+}
+
+fn collect_drawing_props_hyperlinks(drawing_props: &NonVisualDrawingProps, references: &mut Vec<HyperlinkReference>) {
+    let hyperlinks = [
+        (HyperlinkTrigger::Click, &drawing_props.hyperlink_click),
+        (HyperlinkTrigger::Hover, &drawing_props.hyperlink_hover),
+    ];
+    for (trigger, hyperlink) in &hyperlinks {
+        if let Some(hyperlink) = hyperlink {
+            references.push(hyperlink_reference(drawing_props.id, *trigger, false, hyperlink));
+        }
+    }
+}
+
+fn collect_text_body_hyperlinks(
+    shape_id: DrawingElementId,
+    text_body: &TextBody,
+    references: &mut Vec<HyperlinkReference>,
+) {
+    let char_properties = text_body
+        .paragraph_array
+        .iter()
+        .flat_map(|paragraph| &paragraph.text_run_list)
+        .filter_map(|run| match run {
+            TextRun::RegularTextRun(run) => run.char_properties.as_deref(),
+            TextRun::TextField(field) => field.char_properties.as_deref(),
+            TextRun::LineBreak(_) => None,
+        });
+
+    for TextCharacterProperties {
+        hyperlink_click,
+        hyperlink_mouse_over,
+        ..
+    } in char_properties
+    {
+        if let Some(hyperlink) = hyperlink_click {
+            references.push(hyperlink_reference(shape_id, HyperlinkTrigger::Click, true, hyperlink));
+        }
+        if let Some(hyperlink) = hyperlink_mouse_over {
+            references.push(hyperlink_reference(shape_id, HyperlinkTrigger::Hover, true, hyperlink));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_slide_hyperlinks() {
+        let xml = r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Next">
+                                <a:hlinkClick r:id="" action="ppaction://hlinkshowjump?jump=nextslide" />
+                                <a:hlinkHover r:id="rId3" action="ppaction://hlinksldjump" />
+                            </p:cNvPr>
+                            <p:cNvSpPr />
+                            <p:nvPr />
+                        </p:nvSpPr>
+                        <p:spPr />
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:p>
+                                <a:r><a:rPr><a:hlinkClick r:id="rId2" tooltip="Home" /></a:rPr><a:t>Site</a:t></a:r>
+                            </a:p>
+                        </p:txBody>
+                    </p:sp>
+                    <p:pic>
+                        <p:nvPicPr>
+                            <p:cNvPr id="3" name="Picture"><a:hlinkClick r:id="rId4" action="ppaction://program" /></p:cNvPr>
+                            <p:cNvPicPr />
+                            <p:nvPr />
+                        </p:nvPicPr>
+                        <p:blipFill />
+                        <p:spPr />
+                    </p:pic>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let hyperlinks: Vec<_> = slide
+            .hyperlinks()
+            .into_iter()
+            .map(|reference| {
+                (
+                    reference.shape_id,
+                    reference.trigger,
+                    reference.is_text,
+                    reference.action,
+                )
+            })
+            .collect();
+        assert_eq!(
+            hyperlinks,
+            vec![
+                (2, HyperlinkTrigger::Click, false, ActionKind::NextSlide),
+                (2, HyperlinkTrigger::Hover, false, ActionKind::SlideJump),
+                (2, HyperlinkTrigger::Click, true, ActionKind::Hyperlink),
+                (3, HyperlinkTrigger::Click, false, ActionKind::LaunchProgram),
+            ]
+        );
+
+        assert_eq!(
+            ActionKind::from_action(Some("ppaction://customshow?id=2&return=true")),
+            ActionKind::CustomShow {
+                id: Some(2),
+                return_to_slide: true
+            }
+        );
+        assert_eq!(
+            ActionKind::from_action(Some("ppaction://macro?name=Module1.Main")),
+            ActionKind::Macro(String::from("Module1.Main"))
+        );
+        assert_eq!(
+            ActionKind::from_action(Some("javascript:void")),
+            ActionKind::Unknown(String::from("javascript:void"))
+        );
+    }
+}
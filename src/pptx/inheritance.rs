@@ -0,0 +1,430 @@
+//! Resolution of the placeholder geometry, text styles and color mapping a slide inherits from its slide layout and
+//! slide master.
+//!
+//! Properties are merged the same way as the WML style hierarchy: starting from the most generic definition (the
+//! text styles of the master) each more specific definition is merged on top of the previous one with
+//! [`Update`](crate::update::Update), so explicitly set properties always win over inherited ones.
+
+use super::pml::slides::{GroupShape, PlaceholderType, Shape, ShapeGroup, Slide, SlideLayout, SlideMaster};
+use crate::{
+    shared::drawingml::{
+        colors::ColorMappingOverride,
+        coordsys::Transform2D,
+        sharedstylesheet::ColorMapping,
+        text::{
+            bullet::TextListStyle,
+            paragraphs::{TextCharacterProperties, TextParagraphProperties},
+        },
+    },
+    update::Update,
+};
+
+/// The effective properties of a placeholder shape after applying the inheritance chain of slide, slide layout and
+/// slide master.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedPlaceholder {
+    /// The type of the placeholder. If neither the shape nor its layout placeholder specify it, `obj` is assumed.
+    pub placeholder_type: PlaceholderType,
+    pub index: Option<u32>,
+    /// The transform of the first shape in the chain that specifies one.
+    pub transform: Option<Transform2D>,
+    /// The text list style merged from the master's text styles and the list styles of the master, layout and slide
+    /// placeholders.
+    pub list_style: TextListStyle,
+}
+
+impl ResolvedPlaceholder {
+    /// Returns the effective paragraph properties for a 0-based paragraph level of the merged list style, see
+    /// [`TextListStyle::paragraph_properties`].
+    pub fn paragraph_properties(&self, level: i32) -> TextParagraphProperties {
+        self.list_style.paragraph_properties(level)
+    }
+
+    /// Returns the effective default run properties for a 0-based paragraph level.
+    pub fn run_properties(&self, level: i32) -> TextCharacterProperties {
+        self.paragraph_properties(level)
+            .default_run_properties
+            .map(|properties| *properties)
+            .unwrap_or_default()
+    }
+}
+
+/// The slide layout and slide master a slide is based on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlideInheritance<'a> {
+    pub layout: &'a SlideLayout,
+    pub master: &'a SlideMaster,
+}
+
+impl<'a> SlideInheritance<'a> {
+    pub fn new(layout: &'a SlideLayout, master: &'a SlideMaster) -> Self {
+        Self { layout, master }
+    }
+
+    /// Returns the placeholder of the slide layout the given placeholder shape inherits from.
+    ///
+    /// Placeholders are matched by their index first, then by their type.
+    pub fn layout_placeholder(&self, shape: &Shape) -> Option<&'a Shape> {
+        let placeholder = shape.non_visual_props.app_props.placeholder.as_ref()?;
+        let layout_shapes = placeholder_shapes(&self.layout.common_slide_data.shape_tree);
+
+        placeholder
+            .index
+            .and_then(|index| {
+                layout_shapes
+                    .iter()
+                    .find(|layout_shape| placeholder_index(layout_shape) == Some(index))
+            })
+            .or_else(|| {
+                let placeholder_type = placeholder.placeholder_type.unwrap_or(PlaceholderType::Object);
+                layout_shapes
+                    .iter()
+                    .find(|layout_shape| placeholder_type_of(layout_shape) == Some(placeholder_type))
+            })
+            .copied()
+    }
+
+    /// Returns the placeholder of the slide master a placeholder of the given type inherits from.
+    ///
+    /// Master placeholders are matched by type only. Content placeholders (`obj`, `subTitle`, `tbl`, etc.) inherit
+    /// from the `body` placeholder and centered titles from the `title` placeholder of the master.
+    pub fn master_placeholder(&self, placeholder_type: PlaceholderType) -> Option<&'a Shape> {
+        let master_type = master_placeholder_type(placeholder_type);
+
+        placeholder_shapes(&self.master.common_slide_data.shape_tree)
+            .into_iter()
+            .find(|master_shape| placeholder_type_of(master_shape).map(master_placeholder_type) == Some(master_type))
+    }
+
+    /// Resolves the effective properties of a placeholder shape of a slide. Returns `None` if the shape is not a
+    /// placeholder.
+    pub fn resolve_placeholder(&self, shape: &Shape) -> Option<ResolvedPlaceholder> {
+        let placeholder = shape.non_visual_props.app_props.placeholder.as_ref()?;
+        let layout_shape = self.layout_placeholder(shape);
+
+        let placeholder_type = placeholder
+            .placeholder_type
+            .or_else(|| layout_shape.and_then(placeholder_type_of))
+            .unwrap_or(PlaceholderType::Object);
+
+        let master_shape = self.master_placeholder(placeholder_type);
+
+        let transform = shape
+            .shape_props
+            .transform
+            .as_ref()
+            .or_else(|| layout_shape.and_then(|layout_shape| layout_shape.shape_props.transform.as_ref()))
+            .or_else(|| master_shape.and_then(|master_shape| master_shape.shape_props.transform.as_ref()))
+            .map(|transform| **transform);
+
+        let list_style = [master_shape, layout_shape, Some(shape)]
+            .iter()
+            .filter_map(|shape| shape.and_then(shape_list_style))
+            .fold(
+                self.master_text_style(placeholder_type),
+                |list_style, shape_list_style| list_style.update_with(shape_list_style.clone()),
+            );
+
+        Some(ResolvedPlaceholder {
+            placeholder_type,
+            index: placeholder.index,
+            transform,
+            list_style,
+        })
+    }
+
+    /// Returns the text style of the slide master used by placeholders of the given type. Titles use the title style,
+    /// date, footer, header and slide number placeholders use the other style and every other placeholder uses the
+    /// body style.
+    pub fn master_text_style(&self, placeholder_type: PlaceholderType) -> TextListStyle {
+        let text_styles = match &self.master.text_styles {
+            Some(text_styles) => text_styles,
+            None => return Default::default(),
+        };
+
+        let text_style = match placeholder_type {
+            PlaceholderType::Title | PlaceholderType::CenteredTitle => &text_styles.title_styles,
+            PlaceholderType::DateTime
+            | PlaceholderType::SlideNumber
+            | PlaceholderType::Footer
+            | PlaceholderType::Header => &text_styles.other_styles,
+            _ => &text_styles.body_styles,
+        };
+
+        text_style
+            .as_ref()
+            .map(|text_style| (**text_style).clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the color mapping in effect for the slide. A color mapping override of the slide takes precedence over
+    /// the one of the layout, which takes precedence over the color mapping of the master.
+    pub fn color_mapping<'b>(&'b self, slide: &'b Slide) -> &'b ColorMapping {
+        [&slide.color_mapping_override, &self.layout.color_mapping_override]
+            .iter()
+            .find_map(|color_mapping_override| match color_mapping_override {
+                Some(ColorMappingOverride::Override(color_mapping)) => Some(&**color_mapping),
+                _ => None,
+            })
+            .unwrap_or(&self.master.color_mapping)
+    }
+}
+
+fn master_placeholder_type(placeholder_type: PlaceholderType) -> PlaceholderType {
+    match placeholder_type {
+        PlaceholderType::Title | PlaceholderType::CenteredTitle => PlaceholderType::Title,
+        PlaceholderType::DateTime
+        | PlaceholderType::SlideNumber
+        | PlaceholderType::Footer
+        | PlaceholderType::Header
+        | PlaceholderType::SlideImage => placeholder_type,
+        _ => PlaceholderType::Body,
+    }
+}
+
+fn placeholder_type_of(shape: &Shape) -> Option<PlaceholderType> {
+    shape
+        .non_visual_props
+        .app_props
+        .placeholder
+        .as_ref()
+        .map(|placeholder| placeholder.placeholder_type.unwrap_or(PlaceholderType::Object))
+}
+
+fn placeholder_index(shape: &Shape) -> Option<u32> {
+    shape
+        .non_visual_props
+        .app_props
+        .placeholder
+        .as_ref()
+        .and_then(|placeholder| placeholder.index)
+}
+
+fn shape_list_style(shape: &Shape) -> Option<&TextListStyle> {
+    shape
+        .text_body
+        .as_ref()
+        .and_then(|text_body| text_body.list_style.as_deref())
+}
+
+fn placeholder_shapes(group_shape: &GroupShape) -> Vec<&Shape> {
+    let mut shapes = Vec::new();
+    collect_placeholder_shapes(group_shape, &mut shapes);
+    shapes
+}
+
+fn collect_placeholder_shapes<'a>(group_shape: &'a GroupShape, shapes: &mut Vec<&'a Shape>) {
+    for shape_group in &group_shape.shape_array {
+        match shape_group {
+            ShapeGroup::Shape(shape) if shape.non_visual_props.app_props.placeholder.is_some() => shapes.push(shape),
+            ShapeGroup::GroupShape(group_shape) => collect_placeholder_shapes(group_shape, shapes),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    const SHAPE_TREE_HEADER: &str = r#"<p:nvGrpSpPr>
+            <p:cNvPr id="1" name="" />
+            <p:cNvGrpSpPr />
+            <p:nvPr />
+        </p:nvGrpSpPr>
+        <p:grpSpPr />"#;
+
+    fn master_for_test() -> SlideMaster {
+        let xml = format!(
+            r#"<p:sldMaster>
+            <p:cSld>
+                <p:spTree>
+                    {}
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Title Placeholder 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="title" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="100" y="200" /><a:ext cx="3000" cy="400" /></a:xfrm>
+                        </p:spPr>
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="3" name="Text Placeholder 2" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="body" idx="1" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="100" y="700" /><a:ext cx="3000" cy="2000" /></a:xfrm>
+                        </p:spPr>
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+            <p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3"
+                accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink" />
+            <p:txStyles>
+                <p:titleStyle>
+                    <a:lvl1pPr algn="l"><a:defRPr sz="4400" /></a:lvl1pPr>
+                </p:titleStyle>
+                <p:bodyStyle>
+                    <a:defPPr rtl="0" />
+                    <a:lvl1pPr marL="228600"><a:defRPr sz="2800" b="0" /></a:lvl1pPr>
+                    <a:lvl2pPr marL="685800"><a:defRPr sz="2400" /></a:lvl2pPr>
+                </p:bodyStyle>
+            </p:txStyles>
+        </p:sldMaster>"#,
+            SHAPE_TREE_HEADER
+        );
+
+        SlideMaster::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap()
+    }
+
+    fn layout_for_test() -> SlideLayout {
+        let xml = format!(
+            r#"<p:sldLayout>
+            <p:cSld>
+                <p:spTree>
+                    {}
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Title 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="title" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="3" name="Content Placeholder 2" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph idx="1" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="100" y="900" /><a:ext cx="1500" cy="1800" /></a:xfrm>
+                        </p:spPr>
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:lstStyle>
+                                <a:lvl1pPr><a:defRPr sz="2000" /></a:lvl1pPr>
+                            </a:lstStyle>
+                        </p:txBody>
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+            <p:clrMapOvr>
+                <a:overrideClrMapping bg1="dk1" tx1="lt1" bg2="dk2" tx2="lt2" accent1="accent1" accent2="accent2"
+                    accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink"
+                    folHlink="folHlink" />
+            </p:clrMapOvr>
+        </p:sldLayout>"#,
+            SHAPE_TREE_HEADER
+        );
+
+        SlideLayout::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap()
+    }
+
+    fn slide_for_test() -> Slide {
+        let xml = format!(
+            r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    {}
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Title 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="title" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="3" name="Content Placeholder 2" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph idx="1" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:lstStyle>
+                                <a:lvl2pPr><a:defRPr b="1" /></a:lvl2pPr>
+                            </a:lstStyle>
+                            <a:p><a:r><a:t>Content</a:t></a:r></a:p>
+                        </p:txBody>
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#,
+            SHAPE_TREE_HEADER
+        );
+
+        Slide::from_xml_element(&XmlNode::from_str(xml.as_str()).unwrap()).unwrap()
+    }
+
+    fn slide_shape(slide: &Slide, index: usize) -> &Shape {
+        match &slide.common_slide_data.shape_tree.shape_array[index] {
+            ShapeGroup::Shape(shape) => shape,
+            _ => panic!("expected a shape"),
+        }
+    }
+
+    #[test]
+    pub fn test_resolve_title_placeholder() {
+        let (master, layout, slide) = (master_for_test(), layout_for_test(), slide_for_test());
+        let inheritance = SlideInheritance::new(&layout, &master);
+
+        let resolved = inheritance.resolve_placeholder(slide_shape(&slide, 0)).unwrap();
+        assert_eq!(resolved.placeholder_type, PlaceholderType::Title);
+
+        let transform = resolved.transform.unwrap();
+        assert_eq!(transform.offset.unwrap().y, 200);
+        assert_eq!(transform.extents.unwrap().width, 3000);
+        assert_eq!(resolved.run_properties(0).font_size, Some(4400));
+    }
+
+    #[test]
+    pub fn test_resolve_content_placeholder() {
+        let (master, layout, slide) = (master_for_test(), layout_for_test(), slide_for_test());
+        let inheritance = SlideInheritance::new(&layout, &master);
+
+        let shape = slide_shape(&slide, 1);
+        assert_eq!(
+            inheritance
+                .layout_placeholder(shape)
+                .map(|layout_shape| layout_shape.non_visual_props.drawing_props.id),
+            Some(3)
+        );
+
+        let resolved = inheritance.resolve_placeholder(shape).unwrap();
+        assert_eq!(resolved.placeholder_type, PlaceholderType::Object);
+        assert_eq!(resolved.index, Some(1));
+        assert_eq!(resolved.transform.unwrap().offset.unwrap().y, 900);
+
+        let first_level = resolved.paragraph_properties(0);
+        assert_eq!(first_level.margin_left, Some(228600));
+        assert_eq!(first_level.rtl, Some(false));
+
+        let first_level_run = resolved.run_properties(0);
+        assert_eq!(first_level_run.font_size, Some(2000));
+        assert_eq!(first_level_run.bold, Some(false));
+
+        let second_level_run = resolved.run_properties(1);
+        assert_eq!(second_level_run.font_size, Some(2400));
+        assert_eq!(second_level_run.bold, Some(true));
+    }
+
+    #[test]
+    pub fn test_color_mapping() {
+        let (master, mut layout, slide) = (master_for_test(), layout_for_test(), slide_for_test());
+        let inheritance = SlideInheritance::new(&layout, &master);
+        assert_ne!(inheritance.color_mapping(&slide), &*master.color_mapping);
+
+        layout.color_mapping_override = Some(ColorMappingOverride::UseMaster);
+        let inheritance = SlideInheritance::new(&layout, &master);
+        assert_eq!(inheritance.color_mapping(&slide), &*master.color_mapping);
+    }
+}
@@ -0,0 +1,289 @@
+use super::{
+    package::Package,
+    pml::slides::{ApplicationNonVisualDrawingProps, GroupShape, ShapeGroup, Slide},
+};
+use crate::shared::{
+    drawingml::{audiovideo::Media, simpletypes::DrawingElementId},
+    relationship::{join_part_path, rels_path_for_part, RelationshipId},
+};
+use std::{error::Error, path::Path};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaKind {
+    Audio,
+    Video,
+    /// A QuickTime movie (`a:quickTimeFile`).
+    QuickTime,
+    /// Tracks of an audio CD (`a:audioCd`). It doesn't reference any file.
+    AudioCd,
+}
+
+/// A reference to audio or video from the non-visual properties of a shape.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaReference {
+    pub shape_id: DrawingElementId,
+    pub kind: MediaKind,
+    /// The relationship id of the media file. `None` for audio CDs.
+    pub relationship_id: Option<RelationshipId>,
+    /// The content type declared by the media element.
+    pub content_type: Option<String>,
+}
+
+/// A media file used by a slide with its relationship resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideMedia {
+    /// The 0-based index of the slide in presentation order.
+    pub slide_index: usize,
+    pub shape_id: DrawingElementId,
+    pub kind: MediaKind,
+    pub relationship_id: RelationshipId,
+    /// The part name of the media stored in the package, or the URI of an external file.
+    pub target: String,
+    pub is_external: bool,
+    /// The content type declared by the media element, or the content type of the media part in the package.
+    pub content_type: Option<String>,
+}
+
+impl Slide {
+    /// Walks the shape tree of the slide, including group shapes, and returns the media references of the shapes in
+    /// document order. A shape with both a media element and the PowerPoint 2010 media extension yields a reference
+    /// for each of them.
+    pub fn media_references(&self) -> Vec<MediaReference> {
+        let mut references = Vec::new();
+        collect_group_shape_media(&self.common_slide_data.shape_tree, &mut references);
+        references
+    }
+}
+
+impl Package {
+    /// Returns the audio and video files used by the shapes of the slide at the given 0-based index. References to
+    /// the same target from a shape are reported once. References with missing relationships are skipped.
+    pub fn slide_media(&self, index: usize) -> Result<Vec<SlideMedia>, Box<dyn Error>> {
+        let slide = self.slide(index)?;
+        let slide_part = self.slide_parts[index].to_string_lossy().into_owned();
+        let relationships = self
+            .slide_rels_map
+            .get(Path::new(&rels_path_for_part(&slide_part)))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let mut slide_media: Vec<SlideMedia> = Vec::new();
+        for reference in slide.media_references() {
+            let relationship = match reference
+                .relationship_id
+                .as_ref()
+                .and_then(|id| relationships.iter().find(|rel| &rel.id == id))
+            {
+                Some(relationship) => relationship,
+                None => continue,
+            };
+
+            let is_external = relationship.is_external();
+            let target = if is_external {
+                relationship.target.clone()
+            } else {
+                join_part_path(&slide_part, &relationship.target)
+            };
+
+            let is_duplicate = slide_media
+                .iter()
+                .any(|media| media.shape_id == reference.shape_id && media.target == target);
+            if is_duplicate {
+                continue;
+            }
+
+            let content_type = reference.content_type.or_else(|| {
+                self.content_types
+                    .as_ref()
+                    .filter(|_| !is_external)
+                    .and_then(|content_types| content_types.content_type_for(&target))
+                    .map(String::from)
+            });
+
+            slide_media.push(SlideMedia {
+                slide_index: index,
+                shape_id: reference.shape_id,
+                kind: reference.kind,
+                relationship_id: relationship.id.clone(),
+                target,
+                is_external,
+                content_type,
+            });
+        }
+
+        Ok(slide_media)
+    }
+
+    /// Returns the audio and video files used by the slides of the presentation in presentation order, see
+    /// [`Package::slide_media`].
+    pub fn media(&self) -> Result<Vec<SlideMedia>, Box<dyn Error>> {
+        let mut media = Vec::new();
+        for index in 0..self.slide_count() {
+            media.extend(self.slide_media(index)?);
+        }
+
+        Ok(media)
+    }
+}
+
+fn collect_group_shape_media(group_shape: &GroupShape, references: &mut Vec<MediaReference>) {
+    for shape_group in &group_shape.shape_array {
+        let (drawing_props, app_props) = match shape_group {
+            ShapeGroup::Shape(shape) => (&shape.non_visual_props.drawing_props, &shape.non_visual_props.app_props),
+            ShapeGroup::Picture(picture) => (
+                &picture.non_visual_props.drawing_props,
+                &picture.non_visual_props.app_props,
+            ),
+            ShapeGroup::Connector(connector) => (
+                &connector.non_visual_props.drawing_props,
+                &connector.non_visual_props.app_props,
+            ),
+            ShapeGroup::GraphicFrame(frame) => {
+                (&frame.non_visual_props.drawing_props, &frame.non_visual_props.app_props)
+            }
+            ShapeGroup::GroupShape(group_shape) => {
+                collect_group_shape_media(group_shape, references);
+                continue;
+            }
+            ShapeGroup::ContentPart(_) => continue,
+        };
+
+        collect_app_props_media(drawing_props.id, app_props, references);
+    }
+}
+
+fn collect_app_props_media(
+    shape_id: DrawingElementId,
+    app_props: &ApplicationNonVisualDrawingProps,
+    references: &mut Vec<MediaReference>,
+) {
+    let media_reference =
+        |kind, relationship_id: Option<&RelationshipId>, content_type: Option<&String>| MediaReference {
+            shape_id,
+            kind,
+            relationship_id: relationship_id.cloned(),
+            content_type: content_type.cloned(),
+        };
+
+    let kind = match &app_props.media {
+        Some(Media::AudioCd(_)) => {
+            references.push(media_reference(MediaKind::AudioCd, None, None));
+            MediaKind::Audio
+        }
+        Some(Media::WavAudioFile(file)) => {
+            references.push(media_reference(MediaKind::Audio, Some(&file.embed_rel_id), None));
+            MediaKind::Audio
+        }
+        Some(Media::AudioFile(file)) => {
+            references.push(media_reference(
+                MediaKind::Audio,
+                Some(&file.link),
+                file.content_type.as_ref(),
+            ));
+            MediaKind::Audio
+        }
+        Some(Media::VideoFile(file)) => {
+            references.push(media_reference(
+                MediaKind::Video,
+                Some(&file.link),
+                file.content_type.as_ref(),
+            ));
+            MediaKind::Video
+        }
+        Some(Media::QuickTimeFile(file)) => {
+            references.push(media_reference(MediaKind::QuickTime, Some(&file.link), None));
+            MediaKind::QuickTime
+        }
+        // The media extension is written for videos and audio files only, without a media element it's most likely a
+        // video.
+        None => MediaKind::Video,
+    };
+
+    if let Some(embedded_media) = &app_props.embedded_media {
+        let relationship_ids = embedded_media.embed_rel_id.iter().chain(&embedded_media.link_rel_id);
+        for relationship_id in relationship_ids {
+            references.push(media_reference(kind, Some(relationship_id), None));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_slide_media_references() {
+        let xml = r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:pic>
+                        <p:nvPicPr>
+                            <p:cNvPr id="4" name="movie.mp4" />
+                            <p:cNvPicPr />
+                            <p:nvPr>
+                                <a:videoFile r:link="rId1" />
+                                <p:extLst>
+                                    <p:ext uri="{DAA4B4D4-6D71-4841-9C94-3DE7FCFB9230}">
+                                        <p14:media r:embed="rId2" />
+                                    </p:ext>
+                                </p:extLst>
+                            </p:nvPr>
+                        </p:nvPicPr>
+                        <p:blipFill><a:blip r:embed="rId3" /></p:blipFill>
+                        <p:spPr />
+                    </p:pic>
+                    <p:grpSp>
+                        <p:nvGrpSpPr>
+                            <p:cNvPr id="5" name="Group 4" />
+                            <p:cNvGrpSpPr />
+                            <p:nvPr />
+                        </p:nvGrpSpPr>
+                        <p:grpSpPr />
+                        <p:pic>
+                            <p:nvPicPr>
+                                <p:cNvPr id="6" name="sound.mp3" />
+                                <p:cNvPicPr />
+                                <p:nvPr><a:audioFile r:link="rId4" contentType="audio/mpeg" /></p:nvPr>
+                            </p:nvPicPr>
+                            <p:blipFill><a:blip r:embed="rId3" /></p:blipFill>
+                            <p:spPr />
+                        </p:pic>
+                    </p:grpSp>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(
+            slide.media_references(),
+            vec![
+                MediaReference {
+                    shape_id: 4,
+                    kind: MediaKind::Video,
+                    relationship_id: Some(String::from("rId1")),
+                    content_type: None,
+                },
+                MediaReference {
+                    shape_id: 4,
+                    kind: MediaKind::Video,
+                    relationship_id: Some(String::from("rId2")),
+                    content_type: None,
+                },
+                MediaReference {
+                    shape_id: 6,
+                    kind: MediaKind::Audio,
+                    relationship_id: Some(String::from("rId4")),
+                    content_type: Some(String::from("audio/mpeg")),
+                },
+            ]
+        );
+    }
+}
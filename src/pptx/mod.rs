@@ -1,2 +1,12 @@
+pub mod autofit;
+pub mod comments;
+pub mod diagrams;
+pub mod hyperlinks;
+pub mod inheritance;
+pub mod media;
 pub mod package;
 pub mod pml;
+pub mod shapetree;
+pub mod svg;
+pub mod text;
+pub mod timing;
@@ -1,19 +1,107 @@
-use super::pml::{
-    presentation::Presentation,
-    slides::{Slide, SlideLayout, SlideMaster},
+use super::{
+    inheritance::SlideInheritance,
+    pml::{
+        presentation::{Presentation, SlideSize},
+        slides::{HandoutMaster, NotesMaster, NotesSlide, Slide, SlideLayout, SlideMaster},
+    },
 };
-use crate::shared::{
-    docprops::{AppInfo, Core},
-    drawingml::sharedstylesheet::OfficeStyleSheet,
-    relationship::{relationships_from_zip_file, Relationship},
+use crate::{
+    error::SlideIndexOutOfBoundsError,
+    shared::{
+        contenttypes::{ContentTypes, CONTENT_TYPES_PART_NAME},
+        docprops::{AppInfo, Core},
+        drawingml::{sharedstylesheet::OfficeStyleSheet, text::bullet::TextListStyle},
+        filekind::ensure_zip_package,
+        partcache::PartParsers,
+        relationship::{
+            join_part_path, relationships_from_zip_file, rels_path_for_part, Relationship, THEME_RELATION_TYPE,
+        },
+        sharedtypes::Length,
+    },
 };
 use log::info;
 use std::collections::HashMap;
 use std::fs::File;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use zip::ZipArchive;
 
-#[derive(Debug, Clone, PartialEq)]
+type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+
+pub const SLIDE_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide";
+pub const SLIDE_LAYOUT_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout";
+pub const SLIDE_MASTER_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster";
+pub const NOTES_SLIDE_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesSlide";
+
+/// The prefix of the content types of the PresentationML parts.
+const PML_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.presentationml.";
+
+/// Registers the parsers of the PresentationML parts for the [`PartCache`](crate::shared::partcache::PartCache).
+pub(crate) fn register_part_parsers(parsers: &mut PartParsers) {
+    let content_type = |subtype: &str| format!("{}{}", PML_CONTENT_TYPE, subtype);
+    for main_content_type in [
+        content_type("presentation.main+xml"),
+        content_type("slideshow.main+xml"),
+        content_type("template.main+xml"),
+        String::from("application/vnd.ms-powerpoint.presentation.macroEnabled.main+xml"),
+        String::from("application/vnd.ms-powerpoint.slideshow.macroEnabled.main+xml"),
+    ] {
+        parsers.register(&main_content_type, Presentation::from_xml_element);
+    }
+    parsers.register(&content_type("slide+xml"), Slide::from_xml_element);
+    parsers.register(&content_type("slideLayout+xml"), SlideLayout::from_xml_element);
+    parsers.register(&content_type("slideMaster+xml"), SlideMaster::from_xml_element);
+    parsers.register(&content_type("notesSlide+xml"), NotesSlide::from_xml_element);
+    parsers.register(&content_type("notesMaster+xml"), NotesMaster::from_xml_element);
+    parsers.register(&content_type("handoutMaster+xml"), HandoutMaster::from_xml_element);
+}
+
+/// A cache of the slides that have been parsed on demand. The cache can be filled through a shared reference, also
+/// from multiple threads.
+#[derive(Debug, Default)]
+pub struct SlideCache(RwLock<HashMap<PathBuf, Arc<Slide>>>);
+
+impl SlideCache {
+    pub fn get(&self, part_name: &Path) -> Option<Arc<Slide>> {
+        self.read().get(part_name).cloned()
+    }
+
+    pub fn insert(&self, part_name: PathBuf, slide: Arc<Slide>) {
+        self.write().insert(part_name, slide);
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    // A panic while holding the lock can't leave the map half updated, so a poisoned lock is still usable
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<PathBuf, Arc<Slide>>> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<PathBuf, Arc<Slide>>> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for SlideCache {
+    fn clone(&self) -> Self {
+        Self(RwLock::new(self.read().clone()))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Package {
     pub file_path: PathBuf,
     pub app: Option<Box<AppInfo>>,
@@ -22,16 +110,48 @@ pub struct Package {
     pub theme_map: HashMap<PathBuf, Box<OfficeStyleSheet>>,
     pub slide_master_map: HashMap<PathBuf, Box<SlideMaster>>,
     pub slide_layout_map: HashMap<PathBuf, Box<SlideLayout>>,
-    pub slide_map: HashMap<PathBuf, Box<Slide>>,
+    /// The part names of the slides in presentation order. Slides are only parsed when they are first accessed.
+    pub slide_parts: Vec<PathBuf>,
+    pub slide_cache: SlideCache,
     pub slide_master_rels_map: HashMap<PathBuf, Vec<Relationship>>,
     pub slide_layout_rels_map: HashMap<PathBuf, Vec<Relationship>>,
     pub slide_rels_map: HashMap<PathBuf, Vec<Relationship>>,
+    pub notes_master_map: HashMap<PathBuf, Box<NotesMaster>>,
+    pub notes_slide_map: HashMap<PathBuf, Box<NotesSlide>>,
+    pub notes_slide_rels_map: HashMap<PathBuf, Vec<Relationship>>,
+    pub handout_master_map: HashMap<PathBuf, Box<HandoutMaster>>,
     pub medias: Vec<PathBuf>,
+    pub content_types: Option<ContentTypes>,
+}
+
+/// Packages are compared by their content. The slide cache is left out, since it only depends on which slides have
+/// been accessed.
+impl PartialEq for Package {
+    fn eq(&self, other: &Self) -> bool {
+        self.file_path == other.file_path
+            && self.app == other.app
+            && self.core == other.core
+            && self.presentation == other.presentation
+            && self.theme_map == other.theme_map
+            && self.slide_master_map == other.slide_master_map
+            && self.slide_layout_map == other.slide_layout_map
+            && self.slide_parts == other.slide_parts
+            && self.slide_master_rels_map == other.slide_master_rels_map
+            && self.slide_layout_rels_map == other.slide_layout_rels_map
+            && self.slide_rels_map == other.slide_rels_map
+            && self.notes_master_map == other.notes_master_map
+            && self.notes_slide_map == other.notes_slide_map
+            && self.notes_slide_rels_map == other.notes_slide_rels_map
+            && self.handout_master_map == other.handout_master_map
+            && self.medias == other.medias
+            && self.content_types == other.content_types
+    }
 }
 
 impl Package {
-    pub fn from_file(pptx_path: &Path) -> Result<Self, Box<dyn (::std::error::Error)>> {
-        let pptx_file = File::open(&pptx_path)?;
+    pub fn from_file(pptx_path: &Path) -> Result<Self> {
+        let mut pptx_file = File::open(&pptx_path)?;
+        ensure_zip_package(&mut pptx_file)?;
         let mut zipper = ZipArchive::new(&pptx_file)?;
 
         info!("parsing docProps/app.xml");
@@ -43,11 +163,17 @@ impl Package {
         let mut theme_map = HashMap::new();
         let mut slide_master_map = HashMap::new();
         let mut slide_layout_map = HashMap::new();
-        let mut slide_map = HashMap::new();
+        let mut slide_files = Vec::new();
+        let mut presentation_rels = Vec::new();
         let mut slide_master_rels_map = HashMap::new();
         let mut slide_layout_rels_map = HashMap::new();
         let mut slide_rels_map = HashMap::new();
+        let mut notes_master_map = HashMap::new();
+        let mut notes_slide_map = HashMap::new();
+        let mut notes_slide_rels_map = HashMap::new();
+        let mut handout_master_map = HashMap::new();
         let mut medias = Vec::new();
+        let mut content_types = None;
 
         for i in 0..zipper.len() {
             let mut zip_file = zipper.by_index(i)?;
@@ -102,16 +228,57 @@ impl Package {
                         continue;
                     }
 
-                    info!("parsing slide file: {}", zip_file.name());
-                    slide_map.insert(file_path, Box::new(Slide::from_zip_file(&mut zip_file)?));
+                    slide_files.push(file_path);
+                }
+                file_path if file_path.starts_with("ppt/notesMasters") => {
+                    if file_path.extension().unwrap_or_default() != "xml" {
+                        continue;
+                    }
+
+                    info!("parsing notes master file: {}", zip_file.name());
+                    notes_master_map.insert(file_path, Box::new(NotesMaster::from_zip_file(&mut zip_file)?));
+                }
+                file_path if file_path.starts_with("ppt/notesSlides/_rels") => {
+                    if file_path.extension().unwrap_or_default() != "rels" {
+                        continue;
+                    }
+
+                    info!("parsing notes slide relationship file: {}", zip_file.name());
+                    notes_slide_rels_map.insert(file_path, relationships_from_zip_file(&mut zip_file)?);
+                }
+                file_path if file_path.starts_with("ppt/notesSlides") => {
+                    if file_path.extension().unwrap_or_default() != "xml" {
+                        continue;
+                    }
+
+                    info!("parsing notes slide file: {}", zip_file.name());
+                    notes_slide_map.insert(file_path, Box::new(NotesSlide::from_zip_file(&mut zip_file)?));
+                }
+                file_path if file_path.starts_with("ppt/handoutMasters") => {
+                    if file_path.extension().unwrap_or_default() != "xml" {
+                        continue;
+                    }
+
+                    info!("parsing handout master file: {}", zip_file.name());
+                    handout_master_map.insert(file_path, Box::new(HandoutMaster::from_zip_file(&mut zip_file)?));
+                }
+                file_path if file_path == Path::new("ppt/_rels/presentation.xml.rels") => {
+                    info!("parsing presentation relationship file: {}", zip_file.name());
+                    presentation_rels = relationships_from_zip_file(&mut zip_file)?;
                 }
                 file_path if file_path.starts_with("ppt/media") => {
                     medias.push(file_path);
                 }
+                file_path if file_path == Path::new(CONTENT_TYPES_PART_NAME) => {
+                    info!("parsing content types file: {}", zip_file.name());
+                    content_types = Some(ContentTypes::from_zip_file(&mut zip_file)?);
+                }
                 _ => (),
             }
         }
 
+        let slide_parts = order_slide_parts(presentation.as_deref(), &presentation_rels, slide_files);
+
         Ok(Self {
             file_path: PathBuf::from(pptx_path),
             app,
@@ -120,45 +287,278 @@ impl Package {
             theme_map,
             slide_master_map,
             slide_layout_map,
-            slide_map,
+            slide_parts,
+            slide_cache: Default::default(),
             slide_master_rels_map,
             slide_layout_rels_map,
             slide_rels_map,
+            notes_master_map,
+            notes_slide_map,
+            notes_slide_rels_map,
+            handout_master_map,
             medias,
+            content_types,
         })
     }
 
-    pub fn slides(&self) -> Slides {
-        Slides::new(&self.slide_map)
+    pub fn slide_count(&self) -> usize {
+        self.slide_parts.len()
     }
+
+    /// Returns the slide at the given 0-based index in presentation order.
+    ///
+    /// The slide is parsed from the package file on first access and cached afterwards, so subsequent calls return
+    /// the same shared instance.
+    pub fn slide(&self, index: usize) -> Result<Arc<Slide>> {
+        let part_name = self.slide_parts.get(index).ok_or(SlideIndexOutOfBoundsError {
+            index,
+            len: self.slide_parts.len(),
+        })?;
+
+        if let Some(slide) = self.slide_cache.get(part_name) {
+            return Ok(slide);
+        }
+
+        info!("parsing slide file: {}", part_name.display());
+        let pptx_file = File::open(&self.file_path)?;
+        let mut zipper = ZipArchive::new(&pptx_file)?;
+        let mut zip_file = zipper.by_name(&part_name.to_string_lossy())?;
+        let slide = Arc::new(Slide::from_zip_file(&mut zip_file)?);
+
+        self.slide_cache.insert(part_name.clone(), Arc::clone(&slide));
+        Ok(slide)
+    }
+
+    /// Returns an iterator that lazily parses the slides in presentation order.
+    pub fn slides(&self) -> Slides<'_> {
+        Slides::new(self)
+    }
+
+    /// Returns the slide layout and slide master the slide at the given 0-based index is based on. Returns `None` if
+    /// the index is out of bounds or the layout or master part is missing from the package.
+    pub fn slide_inheritance(&self, index: usize) -> Option<SlideInheritance<'_>> {
+        let slide_part = self.slide_parts.get(index)?;
+        let layout_part = related_part(&self.slide_rels_map, slide_part, SLIDE_LAYOUT_RELATION_TYPE)?;
+        let master_part = related_part(&self.slide_layout_rels_map, &layout_part, SLIDE_MASTER_RELATION_TYPE)?;
+
+        Some(SlideInheritance::new(
+            self.slide_layout_map.get(&layout_part)?,
+            self.slide_master_map.get(&master_part)?,
+        ))
+    }
+
+    /// Returns the theme of the slide master the slide at the given 0-based index is based on.
+    pub fn slide_theme(&self, index: usize) -> Option<&OfficeStyleSheet> {
+        let slide_part = self.slide_parts.get(index)?;
+        let layout_part = related_part(&self.slide_rels_map, slide_part, SLIDE_LAYOUT_RELATION_TYPE)?;
+        let master_part = related_part(&self.slide_layout_rels_map, &layout_part, SLIDE_MASTER_RELATION_TYPE)?;
+        let theme_part = related_part(&self.slide_master_rels_map, &master_part, THEME_RELATION_TYPE)?;
+
+        self.theme_map.get(&theme_part).map(Box::as_ref)
+    }
+
+    /// Returns the part name of the notes slide of the slide at the given 0-based index.
+    pub fn notes_slide_part(&self, index: usize) -> Option<PathBuf> {
+        related_part(
+            &self.slide_rels_map,
+            self.slide_parts.get(index)?,
+            NOTES_SLIDE_RELATION_TYPE,
+        )
+    }
+
+    /// Returns the notes slide of the slide at the given 0-based index. Returns `None` if the slide has no notes.
+    pub fn notes_slide(&self, index: usize) -> Option<&NotesSlide> {
+        self.notes_slide_map
+            .get(&self.notes_slide_part(index)?)
+            .map(Box::as_ref)
+    }
+
+    /// Returns the notes master the notes slides of the presentation are based on.
+    pub fn notes_master(&self) -> Option<&NotesMaster> {
+        self.notes_master_map.values().next().map(Box::as_ref)
+    }
+
+    pub fn handout_master(&self) -> Option<&HandoutMaster> {
+        self.handout_master_map.values().next().map(Box::as_ref)
+    }
+
+    /// Returns the width and height of the slides.
+    pub fn slide_size(&self) -> Option<(Length, Length)> {
+        self.presentation.as_ref()?.slide_size.as_ref().map(SlideSize::size)
+    }
+
+    /// Returns the width and height of the notes slides and handouts.
+    pub fn notes_size(&self) -> Option<(Length, Length)> {
+        let notes_size = self.presentation.as_ref()?.notes_size.as_ref()?;
+        Some((
+            Length::from_emu(notes_size.width as f64),
+            Length::from_emu(notes_size.height as f64),
+        ))
+    }
+
+    /// Returns the default text styles of the presentation, used by text outside of placeholders.
+    pub fn default_text_style(&self) -> Option<&TextListStyle> {
+        self.presentation.as_ref()?.default_text_style.as_deref()
+    }
+}
+
+/// Returns the part targeted by the first relationship of the given type of a part.
+fn related_part(rels_map: &HashMap<PathBuf, Vec<Relationship>>, part_name: &Path, rel_type: &str) -> Option<PathBuf> {
+    let part_name = part_name.to_str()?;
+    rels_map
+        .get(Path::new(&rels_path_for_part(part_name)))?
+        .iter()
+        .find(|rel| rel.rel_type == rel_type)
+        .map(|rel| PathBuf::from(join_part_path(part_name, &rel.target)))
 }
+
+/// Orders the slide parts by the slide id list of the presentation. Falls back to the number in the slide part's
+/// name (`ppt/slides/slide{n}.xml`) if the presentation or its relationships are missing.
+fn order_slide_parts(
+    presentation: Option<&Presentation>,
+    presentation_rels: &[Relationship],
+    mut slide_files: Vec<PathBuf>,
+) -> Vec<PathBuf> {
+    if let Some(presentation) = presentation.filter(|_| !presentation_rels.is_empty()) {
+        return presentation
+            .slide_id_list
+            .iter()
+            .filter_map(|entry| {
+                presentation_rels
+                    .iter()
+                    .find(|rel| rel.id == entry.relationship_id && rel.rel_type == SLIDE_RELATION_TYPE)
+            })
+            .map(|rel| PathBuf::from(join_part_path("ppt/presentation.xml", &rel.target)))
+            .collect();
+    }
+
+    let slide_number = |path: &PathBuf| {
+        path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.trim_start_matches("slide").parse::<usize>().ok())
+    };
+
+    slide_files.sort_by_key(slide_number);
+    slide_files
+}
+
 #[derive(Debug, Clone)]
 pub struct Slides<'a> {
-    slide_map: &'a HashMap<PathBuf, Box<Slide>>,
-    current_page_num: usize,
+    package: &'a Package,
+    current_index: usize,
 }
 
 impl<'a> Slides<'a> {
-    pub fn new(slide_map: &'a HashMap<PathBuf, Box<Slide>>) -> Self {
+    pub fn new(package: &'a Package) -> Self {
         Self {
-            slide_map,
-            current_page_num: 1,
+            package,
+            current_index: 0,
         }
     }
 }
 
 impl<'a> Iterator for Slides<'a> {
-    type Item = &'a Slide;
+    type Item = Result<Arc<Slide>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        for i in self.current_page_num..=self.slide_map.len() {
-            let opt_slide = self.slide_map.get(&PathBuf::from(format!("ppt/slides/slide{}.xml", i)));
-            self.current_page_num += 1;
-            if let Some(slide) = opt_slide {
-                return Some(slide);
-            }
+        if self.current_index >= self.package.slide_count() {
+            return None;
+        }
+
+        let slide = self.package.slide(self.current_index);
+        self.current_index += 1;
+        Some(slide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn relationship(id: &str, target: &str) -> Relationship {
+        Relationship {
+            id: String::from(id),
+            rel_type: String::from(SLIDE_RELATION_TYPE),
+            target: String::from(target),
+            target_mode: None,
         }
+    }
+
+    #[test]
+    pub fn test_order_slide_parts_by_file_name() {
+        let slide_files = vec![
+            PathBuf::from("ppt/slides/slide10.xml"),
+            PathBuf::from("ppt/slides/slide2.xml"),
+            PathBuf::from("ppt/slides/slide1.xml"),
+        ];
+
+        assert_eq!(
+            order_slide_parts(None, &[], slide_files),
+            vec![
+                PathBuf::from("ppt/slides/slide1.xml"),
+                PathBuf::from("ppt/slides/slide2.xml"),
+                PathBuf::from("ppt/slides/slide10.xml"),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_order_slide_parts_by_slide_id_list() {
+        let presentation = Presentation::from_xml_element(
+            &XmlNode::from_str(
+                r#"<p:presentation>
+                    <p:sldIdLst>
+                        <p:sldId id="256" r:id="rId3" />
+                        <p:sldId id="257" r:id="rId2" />
+                    </p:sldIdLst>
+                    <p:notesSz cx="6858000" cy="9144000" />
+                </p:presentation>"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let presentation_rels = vec![
+            relationship("rId2", "slides/slide1.xml"),
+            relationship("rId3", "slides/slide2.xml"),
+        ];
+
+        assert_eq!(
+            order_slide_parts(Some(&presentation), &presentation_rels, Vec::new()),
+            vec![
+                PathBuf::from("ppt/slides/slide2.xml"),
+                PathBuf::from("ppt/slides/slide1.xml"),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_slide_size_conversion() {
+        let slide_size = SlideSize::from_xml_element(
+            &XmlNode::from_str(r#"<p:sldSz cx="9144000" cy="6858000" type="screen4x3"></p:sldSz>"#).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(slide_size.to_points(), (720.0, 540.0));
+        assert_eq!(slide_size.to_pixels(96.0), (960.0, 720.0));
 
-        None
+        let handout_master = HandoutMaster::from_xml_element(
+            &XmlNode::from_str(
+                r#"<p:handoutMaster>
+                    <p:cSld><p:spTree><p:nvGrpSpPr><p:cNvPr id="1" name="" /><p:cNvGrpSpPr /><p:nvPr /></p:nvGrpSpPr><p:grpSpPr /></p:spTree></p:cSld>
+                    <p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink" />
+                    <p:hf hdr="0" />
+                </p:handoutMaster>"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            handout_master
+                .header_footer
+                .and_then(|header_footer| header_footer.header_enabled),
+            Some(false)
+        );
     }
 }
@@ -262,7 +262,7 @@ pub enum TLTimeNodeType {
     WithEffect,
     #[strum(serialize = "afterEffect")]
     AfterEffect,
-    #[strum(serialize = "mainSequence")]
+    #[strum(serialize = "mainSeq", serialize = "mainSequence")]
     MainSequence,
     #[strum(serialize = "interactiveSeq")]
     InteractiveSequence,
@@ -452,7 +452,7 @@ impl IndexRange {
         let mut end = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "st" => start = Some(value.parse()?),
                 "end" => end = Some(value.parse()?),
                 _ => (),
@@ -766,13 +766,13 @@ impl XsdType for TimeNodeGroup {
     fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         match xml_node.local_name() {
             "par" => Ok(TimeNodeGroup::Parallel(Box::new(
-                TLCommonTimeNodeData::from_xml_element(xml_node)?,
+                TLCommonTimeNodeData::from_container_element(xml_node)?,
             ))),
             "seq" => Ok(TimeNodeGroup::Sequence(Box::new(TLTimeNodeSequence::from_xml_element(
                 xml_node,
             )?))),
             "excl" => Ok(TimeNodeGroup::Exclusive(Box::new(
-                TLCommonTimeNodeData::from_xml_element(xml_node)?,
+                TLCommonTimeNodeData::from_container_element(xml_node)?,
             ))),
             "anim" => Ok(TimeNodeGroup::Animate(Box::new(TLAnimateBehavior::from_xml_element(
                 xml_node,
@@ -1041,7 +1041,7 @@ impl TLCommonBehaviorData {
         let mut override_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "additive" => additive = Some(value.parse()?),
                 "accumulate" => accumulate = Some(value.parse()?),
                 "xfrmType" => transform_type = Some(value.parse()?),
@@ -1162,7 +1162,7 @@ impl TLCommonMediaNodeData {
         let mut show_when_stopped = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "vol" => volume = Some(value.parse()?),
                 "mute" => mute = Some(parse_xml_bool(value)?),
                 "numSld" => number_of_slides = Some(value.parse()?),
@@ -1259,7 +1259,7 @@ impl TLBuildParagraph {
         let mut auto_advance_time = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "spid" => shape_id = Some(value.parse()?),
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
@@ -1314,7 +1314,7 @@ impl TLPoint {
         let mut y = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "x" => x = Some(value.parse()?),
                 "y" => y = Some(value.parse()?),
                 _ => (),
@@ -1428,7 +1428,7 @@ impl TLBuildDiagram {
         let mut build_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "spid" => shape_id = Some(value.parse()?),
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
@@ -1474,7 +1474,7 @@ impl TLOleBuildChart {
         let mut animate_bg = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "spid" => shape_id = Some(value.parse()?),
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
@@ -1512,7 +1512,7 @@ impl TLGraphicalObjectBuild {
         let mut ui_expand = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "spid" => shape_id = Some(value.parse()?),
                 "grpId" => group_id = Some(value.parse()?),
                 "uiExpand" => ui_expand = Some(parse_xml_bool(value)?),
@@ -1680,7 +1680,7 @@ impl TLTimeNodeSequence {
         let mut next_action_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "concurrent" => concurrent = Some(parse_xml_bool(value)?),
                 "prevAc" => prev_action_type = Some(value.parse()?),
                 "nextAc" => next_action_type = Some(value.parse()?),
@@ -1760,7 +1760,7 @@ impl TLAnimateBehavior {
         let mut value_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "by" => by = Some(value.clone()),
                 "from" => from = Some(value.clone()),
                 "to" => to = Some(value.clone()),
@@ -1881,7 +1881,7 @@ impl TLAnimateColorBehavior {
         let mut direction = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "clrSpc" => color_space = Some(value.parse()?),
                 "dir" => direction = Some(value.parse()?),
                 _ => (),
@@ -2031,7 +2031,7 @@ impl TLAnimateEffectBehavior {
         let mut property_list = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "transition" => transition = Some(value.parse()?),
                 "filter" => filter = Some(value.clone()),
                 "prLst" => property_list = Some(value.clone()),
@@ -2172,7 +2172,7 @@ impl TLAnimateMotionBehavior {
         let mut points_types = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "origin" => origin = Some(value.parse()?),
                 "path" => path = Some(value.clone()),
                 "pathEditMode" => path_edit_mode = Some(value.parse()?),
@@ -2235,7 +2235,7 @@ impl TLAnimateRotationBehavior {
         let mut to = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "by" => by = Some(value.parse()?),
                 "from" => from = Some(value.parse()?),
                 "to" => to = Some(value.parse()?),
@@ -2434,7 +2434,7 @@ impl TLCommandBehavior {
         let mut command = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "type" => command_type = Some(value.parse()?),
                 "cmd" => command = Some(value.clone()),
                 _ => (),
@@ -3305,7 +3305,7 @@ impl TLOleChartTargetElement {
         let mut level = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "type" => element_type = Some(value.parse()?),
                 "lvl" => level = Some(value.parse()?),
                 _ => (),
@@ -3431,7 +3431,7 @@ impl TLTimeCondition {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "evt" => instance.trigger_event = Some(value.parse()?),
                     "delay" => instance.delay = Some(value.parse()?),
                     _ => (),
@@ -3664,6 +3664,19 @@ pub struct TLCommonTimeNodeData {
 }
 
 impl TLCommonTimeNodeData {
+    /// Parses the common time node data of a par or excl element from its cTn child element. Elements without a cTn
+    /// child element are parsed as common time node data themselves.
+    pub fn from_container_element(xml_node: &XmlNode) -> Result<Self> {
+        match xml_node
+            .child_nodes
+            .iter()
+            .find(|child_node| child_node.local_name() == "cTn")
+        {
+            Some(common_time_node) => Self::from_xml_element(common_time_node),
+            None => Self::from_xml_element(xml_node),
+        }
+    }
+
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         xml_node
             .attributes
@@ -3816,7 +3829,7 @@ impl TLIterateData {
         let mut backwards = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "type" => iterate_type = Some(value.parse()?),
                 "backwards" => backwards = Some(parse_xml_bool(value)?),
                 _ => (),
@@ -3929,7 +3942,7 @@ impl TLByRgbColorTransform {
         let mut b = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "r" => r = Some(value.parse()?),
                 "g" => g = Some(value.parse()?),
                 "b" => b = Some(value.parse()?),
@@ -3962,7 +3975,7 @@ impl TLByHslColorTransform {
         let mut l = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "h" => h = Some(value.parse()?),
                 "s" => s = Some(value.parse()?),
                 "l" => l = Some(value.parse()?),
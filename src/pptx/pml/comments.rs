@@ -0,0 +1,358 @@
+//! The legacy comment parts (`p:cmAuthorLst`, `p:cmLst`) and the modern comment parts of PowerPoint 365
+//! (`p188:authorLst`, `p188:cmLst`).
+
+use super::presentation::SlideId;
+use crate::{
+    error::{MissingAttributeError, MissingChildNodeError},
+    shared::drawingml::{
+        coordsys::Point2D,
+        core::TextBody,
+        simpletypes::{DrawingElementId, Guid},
+    },
+    xml::XmlNode,
+};
+use std::error::Error;
+use strum_macros::EnumString;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn Error>>;
+
+pub const COMMENT_AUTHORS_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/commentAuthors";
+pub const COMMENTS_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments";
+pub const MODERN_AUTHORS_RELATION_TYPE: &str = "http://schemas.microsoft.com/office/2018/10/relationships/authors";
+pub const MODERN_COMMENTS_RELATION_TYPE: &str = "http://schemas.microsoft.com/office/2018/10/relationships/comments";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentAuthor {
+    pub id: u32,
+    pub name: String,
+    pub initials: String,
+    /// The index of the last comment added by the author.
+    pub last_index: u32,
+    pub color_index: u32,
+}
+
+impl CommentAuthor {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut id = None;
+        let mut name = None;
+        let mut initials = None;
+        let mut last_index = None;
+        let mut color_index = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "id" => id = Some(value.parse()?),
+                "name" => name = Some(value.clone()),
+                "initials" => initials = Some(value.clone()),
+                "lastIdx" => last_index = Some(value.parse()?),
+                "clrIdx" => color_index = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            id: id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?,
+            name: name.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?,
+            initials: initials.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "initials"))?,
+            last_index: last_index.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "lastIdx"))?,
+            color_index: color_index.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "clrIdx"))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommentAuthorList(pub Vec<CommentAuthor>);
+
+impl CommentAuthorList {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "cmAuthor")
+            .map(CommentAuthor::from_xml_element)
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    pub fn author(&self, id: u32) -> Option<&CommentAuthor> {
+        self.0.iter().find(|author| author.id == id)
+    }
+}
+
+/// A legacy comment of a slide.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    pub author_id: u32,
+    pub date: Option<String>,
+    /// The index of the comment among the comments of its author.
+    pub index: u32,
+    /// The position of the comment on the slide. The unit of the coordinates isn't specified, PowerPoint uses eighths
+    /// of a point.
+    pub position: Point2D,
+    pub text: String,
+}
+
+impl Comment {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut author_id = None;
+        let mut date = None;
+        let mut index = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "authorId" => author_id = Some(value.parse()?),
+                "dt" => date = Some(value.clone()),
+                "idx" => index = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        let mut position = None;
+        let mut text = None;
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "pos" => position = Some(Point2D::from_xml_element(child_node)?),
+                "text" => text = Some(child_node.text.clone().unwrap_or_default()),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            author_id: author_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "authorId"))?,
+            date,
+            index: index.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "idx"))?,
+            position: position.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "pos"))?,
+            text: text.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "text"))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommentList(pub Vec<Comment>);
+
+impl CommentList {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "cm")
+            .map(Comment::from_xml_element)
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModernAuthor {
+    pub id: Guid,
+    pub name: String,
+    pub initials: Option<String>,
+    pub user_id: Option<String>,
+    /// The identity provider of [`ModernAuthor::user_id`], e.g. `AD` or `Windows Live`.
+    pub provider_id: Option<String>,
+}
+
+impl ModernAuthor {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut id = None;
+        let mut name = None;
+        let mut initials = None;
+        let mut user_id = None;
+        let mut provider_id = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "id" => id = Some(value.clone()),
+                "name" => name = Some(value.clone()),
+                "initials" => initials = Some(value.clone()),
+                "userId" => user_id = Some(value.clone()),
+                "providerId" => provider_id = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            id: id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?,
+            name: name.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "name"))?,
+            initials,
+            user_id,
+            provider_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModernAuthorList(pub Vec<ModernAuthor>);
+
+impl ModernAuthorList {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "author")
+            .map(ModernAuthor::from_xml_element)
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    pub fn author<T: AsRef<str>>(&self, id: T) -> Option<&ModernAuthor> {
+        self.0.iter().find(|author| author.id == id.as_ref())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum ModernCommentStatus {
+    #[strum(serialize = "active")]
+    Active,
+    #[strum(serialize = "resolved")]
+    Resolved,
+    #[strum(serialize = "closed")]
+    Closed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModernCommentReply {
+    pub id: Guid,
+    pub author_id: Guid,
+    pub created: Option<String>,
+    pub text_body: Option<TextBody>,
+}
+
+impl ModernCommentReply {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut id = None;
+        let mut author_id = None;
+        let mut created = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "id" => id = Some(value.clone()),
+                "authorId" => author_id = Some(value.clone()),
+                "created" => created = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        let text_body = xml_node
+            .child_nodes
+            .iter()
+            .find(|child_node| child_node.local_name() == "txBody")
+            .map(TextBody::from_xml_element)
+            .transpose()?;
+
+        Ok(Self {
+            id: id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?,
+            author_id: author_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "authorId"))?,
+            created,
+            text_body,
+        })
+    }
+}
+
+/// A modern comment of a slide with its replies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModernComment {
+    pub id: Guid,
+    pub author_id: Guid,
+    pub created: Option<String>,
+    /// Defaults to [`ModernCommentStatus::Active`].
+    pub status: Option<ModernCommentStatus>,
+    /// The id of the slide the comment is anchored to, from the `pc:sldMk` moniker.
+    pub slide_id: Option<SlideId>,
+    /// The id of the shape the comment is anchored to, from a drawing element moniker such as `ac:spMk`.
+    pub anchor_shape_id: Option<DrawingElementId>,
+    pub position: Option<Point2D>,
+    pub text_body: Option<TextBody>,
+    pub replies: Vec<ModernCommentReply>,
+}
+
+impl ModernComment {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut id = None;
+        let mut author_id = None;
+        let mut created = None;
+        let mut status = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "id" => id = Some(value.clone()),
+                "authorId" => author_id = Some(value.clone()),
+                "created" => created = Some(value.clone()),
+                "status" => status = Some(value.parse()?),
+                _ => (),
+            }
+        }
+
+        let mut slide_id = None;
+        let mut anchor_shape_id = None;
+        let mut position = None;
+        let mut text_body = None;
+        let mut replies = Vec::new();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "sldMkLst" | "deMkLst" => {
+                    for moniker in child_node.child_nodes.iter().flat_map(|node| moniker_nodes(node)) {
+                        let id = moniker.attributes.get("id").or_else(|| moniker.attributes.get("sldId"));
+                        match (moniker.local_name(), id) {
+                            ("sldMk", Some(id)) => slide_id = Some(id.parse()?),
+                            ("spMk", Some(id))
+                            | ("picMk", Some(id))
+                            | ("cxnSpMk", Some(id))
+                            | ("grpSpMk", Some(id))
+                            | ("graphicFrameMk", Some(id)) => anchor_shape_id = Some(id.parse()?),
+                            _ => (),
+                        }
+                    }
+                }
+                "pos" => position = Some(Point2D::from_xml_element(child_node)?),
+                "txBody" => text_body = Some(TextBody::from_xml_element(child_node)?),
+                "replyLst" => {
+                    replies = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|reply_node| reply_node.local_name() == "reply")
+                        .map(ModernCommentReply::from_xml_element)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            id: id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "id"))?,
+            author_id: author_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "authorId"))?,
+            created,
+            status,
+            slide_id,
+            anchor_shape_id,
+            position,
+            text_body,
+            replies,
+        })
+    }
+}
+
+/// Returns the moniker and the monikers nested in it, e.g. the slide moniker of a drawing element moniker list.
+fn moniker_nodes(xml_node: &XmlNode) -> Vec<&XmlNode> {
+    let mut nodes = vec![xml_node];
+    for child_node in &xml_node.child_nodes {
+        nodes.extend(moniker_nodes(child_node));
+    }
+    nodes
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModernCommentList(pub Vec<ModernComment>);
+
+impl ModernCommentList {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "cm")
+            .map(ModernComment::from_xml_element)
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod animation;
+pub mod comments;
 pub mod presentation;
 pub mod slides;
 pub mod util;
@@ -7,9 +7,9 @@ use crate::{
             text::{bullet::TextListStyle, runformatting::TextFont},
         },
         relationship::RelationshipId,
-        sharedtypes::ConformanceClass,
+        sharedtypes::{ConformanceClass, Length},
     },
-    xml::{parse_xml_bool, XmlNode},
+    xml::{parse_xml_bool, XmlNode, RELATIONSHIPS_NAMESPACE},
 };
 use std::{
     error::Error,
@@ -240,7 +240,7 @@ impl SlideSize {
         let mut size_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "cx" => width = Some(value.parse()?),
                 "cy" => height = Some(value.parse()?),
                 "type" => size_type = Some(value.parse()?),
@@ -257,6 +257,26 @@ impl SlideSize {
             size_type,
         })
     }
+
+    /// Returns the width and height of the slide.
+    pub fn size(&self) -> (Length, Length) {
+        (
+            Length::from_emu(f64::from(self.width)),
+            Length::from_emu(f64::from(self.height)),
+        )
+    }
+
+    /// Returns the width and height of the slide in points.
+    pub fn to_points(&self) -> (f64, f64) {
+        let (width, height) = self.size();
+        (width.to_points(), height.to_points())
+    }
+
+    /// Returns the width and height of the slide in pixels at the given resolution.
+    pub fn to_pixels(&self, dpi: f64) -> (f64, f64) {
+        let (width, height) = self.size();
+        (width.to_pixels(dpi), height.to_pixels(dpi))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -275,7 +295,7 @@ impl SlideIdListEntry {
         let mut relationship_id = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
                 _ => (),
@@ -307,7 +327,7 @@ impl SlideLayoutIdListEntry {
         let mut id = None;
         let mut relationship_id = None;
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
                 _ => (),
@@ -354,7 +374,7 @@ impl SlideMasterIdListEntry {
         let mut relationship_id = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => id = Some(value.parse()?),
                 "r:id" => relationship_id = Some(value.clone()),
                 _ => (),
@@ -579,17 +599,17 @@ pub struct SlideRelationshipList(pub Vec<RelationshipId>);
 
 impl SlideRelationshipList {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
-        let relationship_ids =
-            xml_node
-                .child_nodes
-                .iter()
-                .filter(|child_node| child_node.local_name() == "sld")
-                .map(|child_node| {
-                    child_node.attributes.get("r:id").cloned().ok_or_else(|| {
-                        Box::<dyn Error>::from(MissingAttributeError::new(child_node.name.clone(), "r:id"))
-                    })
-                })
-                .collect::<Result<Vec<_>>>()?;
+        let relationship_ids = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "sld")
+            .map(|child_node| {
+                child_node
+                    .attribute_ns(RELATIONSHIPS_NAMESPACE, "id")
+                    .cloned()
+                    .ok_or_else(|| Box::<dyn Error>::from(MissingAttributeError::new(child_node.name.clone(), "r:id")))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Self(relationship_ids))
     }
@@ -611,7 +631,7 @@ impl CustomShow {
         let mut id = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "name" => name = Some(value.clone()),
                 "id" => id = Some(value.parse::<u32>()?),
                 _ => (),
@@ -690,7 +710,7 @@ impl Kinsoku {
         let mut invalid_end_chars = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "lang" => language = Some(value.clone()),
                 "invalStChars" => invalid_start_chars = Some(value.clone()),
                 "invalEndChars" => invalid_end_chars = Some(value.clone()),
@@ -820,7 +840,7 @@ impl ModifyVerifier {
         let mut instance: Self = Default::default();
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "algorithmName" => instance.algorithm_name = Some(value.clone()),
                 "hashValue" => instance.hash_value = Some(value.clone()),
                 "saltValue" => instance.salt_value = Some(value.clone()),
@@ -407,6 +407,11 @@ impl SlideMaster {
                 "clrMap" => color_mapping = Some(Box::new(ColorMapping::from_xml_element(child_node)?)),
                 "sldLayoutIdLst" => slide_layout_id_list = Some(SlideLayoutIdList::from_xml_element(child_node)?),
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
+                "AlternateContent" => {
+                    if let Some(alternate_transition) = SlideTransition::from_alternate_content(child_node)? {
+                        transition = Some(Box::new(alternate_transition));
+                    }
+                }
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
                 "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
                 "txStyles" => text_styles = Some(SlideMasterTextStyles::from_xml_element(child_node)?),
@@ -494,7 +499,7 @@ impl SlideLayout {
         let mut show_master_placeholder_animations = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "matchingName" => matching_name = Some(value.clone()),
                 "type" => slide_layout_type = Some(value.parse()?),
                 "preserve" => preserve = Some(parse_xml_bool(value)?),
@@ -530,6 +535,11 @@ impl SlideLayout {
                     );
                 }
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
+                "AlternateContent" => {
+                    if let Some(alternate_transition) = SlideTransition::from_alternate_content(child_node)? {
+                        transition = Some(Box::new(alternate_transition));
+                    }
+                }
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
                 "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
                 _ => (),
@@ -619,7 +629,7 @@ impl Slide {
         let mut show_master_placeholder_animations = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "show" => show = Some(parse_xml_bool(value)?),
                 "showMasterSp" => show_master_shapes = Some(parse_xml_bool(value)?),
                 "showMasterPhAnim" => show_master_placeholder_animations = Some(parse_xml_bool(value)?),
@@ -651,6 +661,11 @@ impl Slide {
                     );
                 }
                 "transition" => transition = Some(Box::new(SlideTransition::from_xml_element(child_node)?)),
+                "AlternateContent" => {
+                    if let Some(alternate_transition) = SlideTransition::from_alternate_content(child_node)? {
+                        transition = Some(Box::new(alternate_transition));
+                    }
+                }
                 "timing" => timing = Some(SlideTiming::from_xml_element(child_node)?),
                 _ => (),
             }
@@ -671,6 +686,189 @@ impl Slide {
     }
 }
 
+/// This element specifies the existence of a notes slide along with its corresponding data. Contained within a notes
+/// slide are all the common slide elements along with addition properties that are specific to the notes element.
+///
+/// # Xml example
+///
+/// ```xml
+/// <p:notes>
+///   <p:cSld>
+///     ...
+///   </p:cSld>
+///   <p:clrMapOvr>
+///     <a:masterClrMapping/>
+///   </p:clrMapOvr>
+/// </p:notes>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotesSlide {
+    /// Specifies if shapes on the notes master should be shown on the notes slide or not.
+    ///
+    /// Defaults to true
+    pub show_master_shapes: Option<bool>,
+    /// Specifies whether or not to display animations on placeholders from the notes master.
+    ///
+    /// Defaults to true
+    pub show_master_placeholder_animations: Option<bool>,
+    pub common_slide_data: Box<CommonSlideData>,
+    /// This element provides a mechanism with which to override the color schemes listed within the
+    /// NotesMaster::color_mapping element.
+    pub color_mapping_override: Option<ColorMappingOverride>,
+}
+
+impl NotesSlide {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut show_master_shapes = None;
+        let mut show_master_placeholder_animations = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "showMasterSp" => show_master_shapes = Some(parse_xml_bool(value)?),
+                "showMasterPhAnim" => show_master_placeholder_animations = Some(parse_xml_bool(value)?),
+                _ => (),
+            }
+        }
+
+        let mut common_slide_data = None;
+        let mut color_mapping_override = None;
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
+                "clrMapOvr" => {
+                    color_mapping_override = Some(
+                        child_node
+                            .child_nodes
+                            .iter()
+                            .find_map(ColorMappingOverride::try_from_xml_element)
+                            .transpose()?
+                            .ok_or_else(|| {
+                                MissingChildNodeError::new(
+                                    child_node.name.clone(),
+                                    "masterClrMapping|overrideClrMapping",
+                                )
+                            })?,
+                    );
+                }
+                _ => (),
+            }
+        }
+
+        let common_slide_data =
+            common_slide_data.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cSld"))?;
+
+        Ok(Self {
+            show_master_shapes,
+            show_master_placeholder_animations,
+            common_slide_data,
+            color_mapping_override,
+        })
+    }
+}
+
+/// This element specifies an instance of a notes master. The notes master contains the layout and the default text
+/// styles used by the notes slides of the presentation when printing a slide along with its notes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotesMaster {
+    pub common_slide_data: Box<CommonSlideData>,
+    /// This element specifies the mapping layer that transforms one color scheme definition to another.
+    pub color_mapping: Box<ColorMapping>,
+    /// This element specifies the header and footer information for the notes master.
+    pub header_footer: Option<HeaderFooter>,
+    /// This element specifies the text formatting of the notes text within the notes slides.
+    pub notes_style: Option<Box<TextListStyle>>,
+}
+
+impl NotesMaster {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut common_slide_data = None;
+        let mut color_mapping = None;
+        let mut header_footer = None;
+        let mut notes_style = None;
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
+                "clrMap" => color_mapping = Some(Box::new(ColorMapping::from_xml_element(child_node)?)),
+                "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
+                "notesStyle" => notes_style = Some(Box::new(TextListStyle::from_xml_element(child_node)?)),
+                _ => (),
+            }
+        }
+
+        let common_slide_data =
+            common_slide_data.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cSld"))?;
+        let color_mapping = color_mapping.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrMap"))?;
+
+        Ok(Self {
+            common_slide_data,
+            color_mapping,
+            header_footer,
+            notes_style,
+        })
+    }
+}
+
+/// This element specifies an instance of a handout master. The handout master contains the layout of the handouts
+/// printed for the audience.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandoutMaster {
+    pub common_slide_data: Box<CommonSlideData>,
+    /// This element specifies the mapping layer that transforms one color scheme definition to another.
+    pub color_mapping: Box<ColorMapping>,
+    /// This element specifies the header and footer information for the handout master.
+    pub header_footer: Option<HeaderFooter>,
+}
+
+impl HandoutMaster {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut common_slide_data = None;
+        let mut color_mapping = None;
+        let mut header_footer = None;
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "cSld" => common_slide_data = Some(Box::new(CommonSlideData::from_xml_element(child_node)?)),
+                "clrMap" => color_mapping = Some(Box::new(ColorMapping::from_xml_element(child_node)?)),
+                "hf" => header_footer = Some(HeaderFooter::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        let common_slide_data =
+            common_slide_data.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "cSld"))?;
+        let color_mapping = color_mapping.ok_or_else(|| MissingChildNodeError::new(xml_node.name.clone(), "clrMap"))?;
+
+        Ok(Self {
+            common_slide_data,
+            color_mapping,
+            header_footer,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BackgroundProperties {
     /// Specifies whether the background of the slide is of a shade to title background type. This
@@ -854,6 +1052,9 @@ pub struct ApplicationNonVisualDrawingProps {
     pub placeholder: Option<Placeholder>,
     pub media: Option<Media>,
     pub customer_data_list: Option<CustomerDataList>,
+    /// The media embedded in the package by the PowerPoint 2010 extension (`p14:media`). It usually references the same
+    /// media part as `media`.
+    pub embedded_media: Option<EmbeddedMedia>,
 }
 
 impl ApplicationNonVisualDrawingProps {
@@ -883,6 +1084,17 @@ impl ApplicationNonVisualDrawingProps {
                             local_name if Media::is_choice_member(local_name) => {
                                 instance.media = Some(Media::from_xml_element(child_node)?)
                             }
+                            "extLst" => {
+                                let media_node = child_node
+                                    .child_nodes
+                                    .iter()
+                                    .flat_map(|ext_node| &ext_node.child_nodes)
+                                    .find(|ext_child_node| ext_child_node.local_name() == "media");
+
+                                if let Some(media_node) = media_node {
+                                    instance.embedded_media = Some(EmbeddedMedia::from_xml_element(media_node)?);
+                                }
+                            }
                             _ => (),
                         }
 
@@ -892,6 +1104,44 @@ impl ApplicationNonVisualDrawingProps {
     }
 }
 
+/// This element specifies audio or video stored in the package or linked from an external file. It's written by
+/// PowerPoint 2010 and later to the extension list of the application non-visual properties.
+///
+/// # Xml example
+///
+/// ```xml
+/// <p:nvPr>
+///   <a:videoFile r:link="rId1"/>
+///   <p:extLst>
+///     <p:ext uri="{DAA4B4D4-6D71-4841-9C94-3DE7FCFB9230}">
+///       <p14:media r:embed="rId2"/>
+///     </p:ext>
+///   </p:extLst>
+/// </p:nvPr>
+/// ```
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EmbeddedMedia {
+    /// Specifies the relationship id of the media part stored in the package.
+    pub embed_rel_id: Option<RelationshipId>,
+    /// Specifies the relationship id of an external media file.
+    pub link_rel_id: Option<RelationshipId>,
+}
+
+impl EmbeddedMedia {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "r:embed" => instance.embed_rel_id = Some(value.clone()),
+                "r:link" => instance.link_rel_id = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ShapeGroup {
     /// This element specifies the existence of a single shape. A shape can either be a preset or a custom geometry,
@@ -2364,6 +2614,9 @@ pub struct SlideTransition {
     /// be used in conjunction with the advance_on_click attribute. If this attribute is not specified then it
     /// is assumed that no auto-advance occurs.
     pub advance_on_time: Option<u32>,
+    /// The duration of the transition in milliseconds. This is the `p14:dur` attribute of PowerPoint 2010, which
+    /// overrides the speed of the transition.
+    pub duration: Option<u32>,
     pub transition_type: Option<SlideTransitionGroup>,
     /// This element describes a sound action for slide transition. This element specifies that the start of the slide
     /// transition is accompanied by the playback of an audio file; the actual audio file used is specified by the snd
@@ -2392,8 +2645,9 @@ impl SlideTransition {
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
                 match attr.as_ref() {
                     "spd" => instance.speed = Some(value.parse()?),
-                    "advClick" => instance.advance_on_click = Some(value.parse()?),
+                    "advClick" => instance.advance_on_click = Some(parse_xml_bool(value)?),
                     "advTm" => instance.advance_on_time = Some(value.parse()?),
+                    attr if attr.ends_with(":dur") => instance.duration = Some(value.parse()?),
                     _ => (),
                 }
 
@@ -2418,6 +2672,35 @@ impl SlideTransition {
                     })
             })
     }
+
+    /// Parses the transition of a `mc:AlternateContent` element. PowerPoint writes the transitions introduced in
+    /// PowerPoint 2010 and the duration of every transition in a `mc:Choice`, with a `mc:Fallback` for older readers.
+    /// The fallback transition is used, since its type is known by this library, with the duration of the choice.
+    pub fn from_alternate_content(xml_node: &XmlNode) -> Result<Option<Self>> {
+        let transition_of = |local_name: &str| {
+            xml_node
+                .child_nodes
+                .iter()
+                .filter(|child_node| child_node.local_name() == local_name)
+                .flat_map(|child_node| child_node.child_nodes.iter())
+                .find(|child_node| child_node.local_name() == "transition")
+        };
+
+        let choice = transition_of("Choice")
+            .map(SlideTransition::from_xml_element)
+            .transpose()?;
+        let fallback = transition_of("Fallback")
+            .map(SlideTransition::from_xml_element)
+            .transpose()?;
+        Ok(match (choice, fallback) {
+            (Some(choice), Some(fallback)) => Some(Self {
+                duration: choice.duration,
+                transition_type: fallback.transition_type.or(choice.transition_type),
+                ..fallback
+            }),
+            (choice, fallback) => fallback.or(choice),
+        })
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -2463,7 +2746,7 @@ impl SlideTiming {
                         let vec = child_node
                             .child_nodes
                             .iter()
-                            .filter(|tn_node| tn_node.local_name() == "tn")
+                            .filter(|tn_node| TimeNodeGroup::is_choice_member(tn_node.local_name()))
                             .map(TimeNodeGroup::from_xml_element)
                             .collect::<Result<Vec<_>>>()?;
 
@@ -2472,7 +2755,7 @@ impl SlideTiming {
                         } else {
                             return Err(Box::<dyn Error>::from(MissingChildNodeError::new(
                                 child_node.name.clone(),
-                                "tn",
+                                "par|seq|excl|anim|animClr|animEffect|animMotion|animRot|animScale|cmd|set|audio|video",
                             )));
                         }
                     }
@@ -2480,7 +2763,7 @@ impl SlideTiming {
                         let vec = child_node
                             .child_nodes
                             .iter()
-                            .filter(|bld_node| bld_node.local_name() == "bld")
+                            .filter(|bld_node| Build::is_choice_member(bld_node.local_name()))
                             .map(Build::from_xml_element)
                             .collect::<Result<Vec<_>>>()?;
 
@@ -2489,7 +2772,7 @@ impl SlideTiming {
                         } else {
                             return Err(Box::<dyn Error>::from(MissingChildNodeError::new(
                                 child_node.name.clone(),
-                                "bld",
+                                "bldP|bldDgm|bldOleChart|bldGraphic",
                             )));
                         }
                     }
@@ -2547,7 +2830,9 @@ impl Control {
         let mut instance: Self = Default::default();
 
         for (attr, value) in &xml_node.attributes {
-            instance.ole_attributes.try_attribute_parse(attr, value)?;
+            instance
+                .ole_attributes
+                .try_attribute_parse(attr.as_ref(), value.as_str())?;
         }
 
         instance.picture = xml_node
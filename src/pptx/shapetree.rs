@@ -0,0 +1,194 @@
+use super::pml::slides::{ApplicationNonVisualDrawingProps, GroupShape, Placeholder, ShapeGroup, Slide};
+use crate::shared::drawingml::{core::NonVisualDrawingProps, simpletypes::DrawingElementId};
+
+impl ShapeGroup {
+    /// Returns the non-visual drawing properties (`cNvPr`) holding the id and the name of the shape. Content parts
+    /// don't have any.
+    pub fn drawing_props(&self) -> Option<&NonVisualDrawingProps> {
+        match self {
+            ShapeGroup::Shape(shape) => Some(&shape.non_visual_props.drawing_props),
+            ShapeGroup::GroupShape(group_shape) => Some(&group_shape.non_visual_props.drawing_props),
+            ShapeGroup::GraphicFrame(frame) => Some(&frame.non_visual_props.drawing_props),
+            ShapeGroup::Connector(connector) => Some(&connector.non_visual_props.drawing_props),
+            ShapeGroup::Picture(picture) => Some(&picture.non_visual_props.drawing_props),
+            ShapeGroup::ContentPart(_) => None,
+        }
+    }
+
+    /// Returns the application non-visual properties (`nvPr`) of the shape. Content parts don't have any.
+    pub fn app_props(&self) -> Option<&ApplicationNonVisualDrawingProps> {
+        match self {
+            ShapeGroup::Shape(shape) => Some(&shape.non_visual_props.app_props),
+            ShapeGroup::GroupShape(group_shape) => Some(&group_shape.non_visual_props.app_props),
+            ShapeGroup::GraphicFrame(frame) => Some(&frame.non_visual_props.app_props),
+            ShapeGroup::Connector(connector) => Some(&connector.non_visual_props.app_props),
+            ShapeGroup::Picture(picture) => Some(&picture.non_visual_props.app_props),
+            ShapeGroup::ContentPart(_) => None,
+        }
+    }
+
+    pub fn id(&self) -> Option<DrawingElementId> {
+        self.drawing_props().map(|drawing_props| drawing_props.id)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.drawing_props().map(|drawing_props| drawing_props.name.as_str())
+    }
+
+    pub fn placeholder(&self) -> Option<&Placeholder> {
+        self.app_props()?.placeholder.as_ref()
+    }
+}
+
+impl GroupShape {
+    /// Returns every shape of the group in document order, descending into nested groups. A nested group is returned
+    /// before its members.
+    pub fn descendants(&self) -> Vec<&ShapeGroup> {
+        let mut shapes = Vec::new();
+        collect_descendants(self, &mut shapes);
+        shapes
+    }
+
+    /// Returns the first shape in document order with the given name, descending into nested groups.
+    pub fn find_shape_by_name(&self, name: &str) -> Option<&ShapeGroup> {
+        self.descendants().into_iter().find(|shape| shape.name() == Some(name))
+    }
+
+    /// Returns the shape with the given id, descending into nested groups.
+    pub fn shape_by_id(&self, id: DrawingElementId) -> Option<&ShapeGroup> {
+        self.descendants().into_iter().find(|shape| shape.id() == Some(id))
+    }
+
+    /// Returns the placeholder shapes in document order with their placeholder properties, descending into nested
+    /// groups.
+    pub fn placeholders(&self) -> Vec<(&ShapeGroup, &Placeholder)> {
+        self.descendants()
+            .into_iter()
+            .filter_map(|shape| Some((shape, shape.placeholder()?)))
+            .collect()
+    }
+}
+
+impl Slide {
+    /// Returns the first shape of the slide with the given name, see [`GroupShape::find_shape_by_name`].
+    ///
+    /// ```
+    /// use oox::{pptx::pml::slides::{ShapeGroup, Slide}, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<p:sld><p:cSld><p:spTree>
+    ///     <p:nvGrpSpPr><p:cNvPr id="1" name="" /><p:cNvGrpSpPr /><p:nvPr /></p:nvGrpSpPr>
+    ///     <p:grpSpPr />
+    ///     <p:sp>
+    ///         <p:nvSpPr><p:cNvPr id="2" name="Title 1" /><p:cNvSpPr /><p:nvPr><p:ph type="title" /></p:nvPr></p:nvSpPr>
+    ///         <p:spPr />
+    ///     </p:sp>
+    /// </p:spTree></p:cSld></p:sld>"#;
+    /// let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// assert!(matches!(slide.find_shape_by_name("Title 1"), Some(ShapeGroup::Shape(_))));
+    /// assert_eq!(slide.shape_by_id(2).and_then(ShapeGroup::name), Some("Title 1"));
+    /// assert_eq!(slide.placeholders().len(), 1);
+    /// ```
+    pub fn find_shape_by_name(&self, name: &str) -> Option<&ShapeGroup> {
+        self.common_slide_data.shape_tree.find_shape_by_name(name)
+    }
+
+    /// Returns the shape of the slide with the given id, see [`GroupShape::shape_by_id`].
+    pub fn shape_by_id(&self, id: DrawingElementId) -> Option<&ShapeGroup> {
+        self.common_slide_data.shape_tree.shape_by_id(id)
+    }
+
+    /// Returns the placeholder shapes of the slide, see [`GroupShape::placeholders`].
+    pub fn placeholders(&self) -> Vec<(&ShapeGroup, &Placeholder)> {
+        self.common_slide_data.shape_tree.placeholders()
+    }
+}
+
+fn collect_descendants<'a>(group_shape: &'a GroupShape, shapes: &mut Vec<&'a ShapeGroup>) {
+    for shape in &group_shape.shape_array {
+        shapes.push(shape);
+        if let ShapeGroup::GroupShape(group_shape) = shape {
+            collect_descendants(group_shape, shapes);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pptx::pml::slides::PlaceholderType, xml::XmlNode};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_shape_tree_queries() {
+        let xml = r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Title 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="title" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                    </p:sp>
+                    <p:grpSp>
+                        <p:nvGrpSpPr>
+                            <p:cNvPr id="3" name="Group 2" />
+                            <p:cNvGrpSpPr />
+                            <p:nvPr />
+                        </p:nvGrpSpPr>
+                        <p:grpSpPr />
+                        <p:sp>
+                            <p:nvSpPr>
+                                <p:cNvPr id="4" name="Content 3" />
+                                <p:cNvSpPr />
+                                <p:nvPr><p:ph idx="1" /></p:nvPr>
+                            </p:nvSpPr>
+                            <p:spPr />
+                        </p:sp>
+                    </p:grpSp>
+                    <p:cxnSp>
+                        <p:nvCxnSpPr>
+                            <p:cNvPr id="5" name="Connector 4" />
+                            <p:cNvCxnSpPr />
+                            <p:nvPr />
+                        </p:nvCxnSpPr>
+                        <p:spPr />
+                    </p:cxnSp>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let ids: Vec<_> = slide
+            .common_slide_data
+            .shape_tree
+            .descendants()
+            .into_iter()
+            .filter_map(ShapeGroup::id)
+            .collect();
+        assert_eq!(ids, vec![2, 3, 4, 5]);
+
+        assert!(matches!(
+            slide.find_shape_by_name("Group 2"),
+            Some(ShapeGroup::GroupShape(_))
+        ));
+        assert!(matches!(slide.shape_by_id(5), Some(ShapeGroup::Connector(_))));
+        assert_eq!(slide.shape_by_id(4).and_then(ShapeGroup::name), Some("Content 3"));
+        assert!(slide.find_shape_by_name("Missing").is_none());
+
+        let placeholders = slide.placeholders();
+        assert_eq!(placeholders.len(), 2);
+        assert_eq!(placeholders[0].1.placeholder_type, Some(PlaceholderType::Title));
+        assert_eq!(placeholders[1].0.id(), Some(4));
+        assert_eq!(placeholders[1].1.index, Some(1));
+    }
+}
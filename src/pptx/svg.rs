@@ -0,0 +1,819 @@
+//! An SVG approximation of slides.
+//!
+//! Only the geometry of the shape tree is exported: shapes, connectors and pictures with their transforms, solid fills
+//! and outlines. Text, effects and graphic frames (tables, charts, diagrams) are not rendered. The SVG uses EMUs as
+//! user units, so coordinates are copied from the document without conversion.
+
+use super::{
+    inheritance::SlideInheritance,
+    package::Package,
+    pml::slides::{BackgroundGroup, CommonSlideData, GroupShape, Picture, ShapeGroup, Slide},
+};
+use crate::{
+    shared::{
+        drawingml::{
+            colors::Color,
+            coordsys::{AffineTransform, PositiveSize2D, Transform2D},
+            core::{LineProperties, ShapeProperties, ShapeStyle},
+            fill::{apply_color_transforms, ColorResolver, Rgba},
+            shapedefs::{CustomGeometry2D, GeomGuideList, Geometry, Path2DCommand, PresetGeometry2D},
+            shapeprops::{FillProperties, LineFillProperties},
+            simpletypes::{AdjAngle, AdjCoordinate, HexColorRGB, PathFillMode, ShapeType},
+            styles::StyleMatrixReference,
+            theme::Theme,
+        },
+        relationship::{join_part_path, rels_path_for_part, Relationship},
+        sharedtypes::EMUS_PER_POINT,
+    },
+    xml::escape_xml,
+};
+use std::{fmt::Write, path::Path};
+
+type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
+
+/// The default slide size (10in x 7.5in) used when the presentation doesn't specify one.
+const DEFAULT_SLIDE_SIZE: PositiveSize2D = PositiveSize2D {
+    width: 9_144_000,
+    height: 6_858_000,
+};
+
+/// The default width of outlines in EMUs.
+const DEFAULT_LINE_WIDTH: f64 = 12_700.0;
+
+/// The parts a slide needs for rendering.
+#[derive(Debug, Clone, Copy)]
+pub struct SvgContext<'a> {
+    /// The size of the slide in EMUs.
+    pub slide_size: PositiveSize2D,
+    /// The layout and master of the slide. Without it only the slide's own shapes are rendered.
+    pub inheritance: Option<SlideInheritance<'a>>,
+    /// The theme scheme colors and style references are resolved with.
+    pub theme: Option<&'a Theme>,
+    /// The part name of the slide, used to resolve the relationships of pictures.
+    pub slide_part: Option<&'a str>,
+    pub relationships: &'a [Relationship],
+}
+
+impl Package {
+    /// Exports the slide at the given 0-based index as SVG. See [`slide_to_svg`] for the supported content.
+    pub fn slide_to_svg(&self, index: usize) -> Result<String> {
+        let slide = self.slide(index)?;
+        let slide_part = self.slide_parts[index].to_str();
+        let slide_size = self
+            .presentation
+            .as_ref()
+            .and_then(|presentation| presentation.slide_size.as_ref())
+            .map(|size| PositiveSize2D::new(u64::from(size.width), u64::from(size.height)))
+            .unwrap_or(DEFAULT_SLIDE_SIZE);
+        let relationships = slide_part
+            .and_then(|slide_part| self.slide_rels_map.get(Path::new(&rels_path_for_part(slide_part))))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        Ok(slide_to_svg(
+            &slide,
+            &SvgContext {
+                slide_size,
+                inheritance: self.slide_inheritance(index),
+                theme: self.slide_theme(index),
+                slide_part,
+                relationships,
+            },
+        ))
+    }
+}
+
+/// Exports a slide as SVG.
+///
+/// The background and the non-placeholder shapes of the master and layout are painted first, unless the slide hides
+/// them with `showMasterSp`, then the shape tree of the slide. Placeholders without a transform take the one they
+/// inherit. Preset geometries that have no dedicated outline are drawn as their bounding rectangle, custom geometries
+/// are drawn when their paths don't reference guides. Gradients are approximated with the color of their first stop,
+/// pictures are linked by their part name.
+pub fn slide_to_svg(slide: &Slide, context: &SvgContext) -> String {
+    let mut writer = SvgWriter {
+        context,
+        color_mapping_slide: slide,
+        output: String::new(),
+    };
+
+    let _ = writeln!(
+        writer.output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{}pt" height="{}pt" viewBox="0 0 {} {}">"#,
+        number(context.slide_size.width as f64 / EMUS_PER_POINT),
+        number(context.slide_size.height as f64 / EMUS_PER_POINT),
+        context.slide_size.width,
+        context.slide_size.height,
+    );
+
+    writer.background(slide);
+
+    if let Some(inheritance) = &context.inheritance {
+        if slide.show_master_shapes.unwrap_or(true) {
+            if inheritance.layout.show_master_shapes.unwrap_or(true) {
+                writer.template_shapes(&inheritance.master.common_slide_data);
+            }
+            writer.template_shapes(&inheritance.layout.common_slide_data);
+        }
+    }
+
+    for shape_group in &slide.common_slide_data.shape_tree.shape_array {
+        writer.shape_group(shape_group, None, true);
+    }
+
+    writer.output.push_str("</svg>\n");
+    writer.output
+}
+
+struct SvgWriter<'a> {
+    context: &'a SvgContext<'a>,
+    color_mapping_slide: &'a Slide,
+    output: String,
+}
+
+impl SvgWriter<'_> {
+    fn background(&mut self, slide: &Slide) {
+        let backgrounds = [
+            Some(&*slide.common_slide_data),
+            self.context
+                .inheritance
+                .as_ref()
+                .map(|inheritance| &*inheritance.layout.common_slide_data),
+            self.context
+                .inheritance
+                .as_ref()
+                .map(|inheritance| &*inheritance.master.common_slide_data),
+        ];
+
+        let background = backgrounds
+            .iter()
+            .flatten()
+            .find_map(|common_slide_data| common_slide_data.background.as_ref());
+        let paint = match background.map(|background| &background.background) {
+            Some(BackgroundGroup::Properties(properties)) => self.fill_paint(&properties.fill, None),
+            Some(BackgroundGroup::Reference(reference)) => self.style_fill_paint(reference),
+            None => None,
+        };
+
+        if let Some(paint) = paint {
+            let _ = writeln!(
+                self.output,
+                r#"<rect width="{}" height="{}"{}/>"#,
+                self.context.slide_size.width,
+                self.context.slide_size.height,
+                paint.fill_attributes(),
+            );
+        }
+    }
+
+    /// Renders the shapes of a master or layout. Placeholders only define the position of the slide's content, so
+    /// they're not rendered.
+    fn template_shapes(&mut self, common_slide_data: &CommonSlideData) {
+        for shape_group in &common_slide_data.shape_tree.shape_array {
+            self.shape_group(shape_group, None, false);
+        }
+    }
+
+    /// Renders an element of a shape tree. `group_fill` is the fill of the enclosing group, used by shapes with a
+    /// group fill. `is_slide` tells whether the shape belongs to the slide or to its master or layout.
+    fn shape_group(&mut self, shape_group: &ShapeGroup, group_fill: Option<&Paint>, is_slide: bool) {
+        match shape_group {
+            ShapeGroup::Shape(shape) => {
+                let placeholder = shape.non_visual_props.app_props.placeholder.is_some();
+                if placeholder && !is_slide {
+                    return;
+                }
+
+                let inherited_transform = match &self.context.inheritance {
+                    Some(inheritance) if placeholder && shape.shape_props.transform.is_none() => inheritance
+                        .resolve_placeholder(shape)
+                        .and_then(|placeholder| placeholder.transform),
+                    _ => None,
+                };
+                let transform = shape.shape_props.transform.as_deref().or(inherited_transform.as_ref());
+
+                self.shape(
+                    &shape.shape_props,
+                    transform,
+                    shape.shape_style.as_deref(),
+                    group_fill,
+                    true,
+                );
+            }
+            ShapeGroup::Connector(connector) => self.shape(
+                &connector.shape_props,
+                connector.shape_props.transform.as_deref(),
+                connector.shape_style.as_deref(),
+                group_fill,
+                false,
+            ),
+            ShapeGroup::Picture(picture) => self.picture(picture, is_slide),
+            ShapeGroup::GroupShape(group_shape) => self.group_shape(group_shape, group_fill, is_slide),
+            ShapeGroup::GraphicFrame(_) | ShapeGroup::ContentPart(_) => (),
+        }
+    }
+
+    fn group_shape(&mut self, group_shape: &GroupShape, group_fill: Option<&Paint>, is_slide: bool) {
+        let properties = &group_shape.group_shape_props;
+        let fill = match &properties.fill_properties {
+            Some(FillProperties::GroupFill) | None => group_fill.cloned(),
+            Some(fill) => self.fill_paint(fill, None),
+        };
+
+        let transform = properties
+            .transform
+            .as_ref()
+            .map(|transform| transform.to_affine())
+            .unwrap_or_default();
+        let _ = writeln!(self.output, r#"<g transform="{}">"#, matrix(&transform));
+        for shape_group in &group_shape.shape_array {
+            self.shape_group(shape_group, fill.as_ref(), is_slide);
+        }
+        self.output.push_str("</g>\n");
+    }
+
+    fn shape(
+        &mut self,
+        properties: &ShapeProperties,
+        transform: Option<&Transform2D>,
+        style: Option<&ShapeStyle>,
+        group_fill: Option<&Paint>,
+        fillable: bool,
+    ) {
+        let transform = match transform {
+            Some(transform) => transform,
+            None => return,
+        };
+        let extents = transform.extents.unwrap_or(PositiveSize2D::new(0, 0));
+        let (width, height) = (extents.width as f64, extents.height as f64);
+
+        let fill = if fillable {
+            match &properties.fill_properties {
+                Some(FillProperties::GroupFill) => group_fill.cloned(),
+                Some(fill) => self.fill_paint(fill, None),
+                None => style.and_then(|style| self.style_fill_paint(&style.fill_reference)),
+            }
+        } else {
+            None
+        };
+        let stroke = self.stroke(properties.line_properties.as_deref(), style);
+
+        let paths = match &properties.geometry {
+            Some(Geometry::Preset(geometry)) => vec![SvgPath::filled(preset_path(geometry, width, height))],
+            Some(Geometry::Custom(geometry)) => custom_paths(geometry, width, height),
+            None => vec![SvgPath::filled(rect_path(width, height))],
+        };
+
+        let _ = writeln!(self.output, r#"<g transform="{}">"#, matrix(&transform.to_affine()));
+        for path in paths.iter().filter(|path| !path.data.is_empty()) {
+            let fill_attributes = match &fill {
+                Some(fill) if path.fill => fill.fill_attributes(),
+                _ => String::from(r#" fill="none""#),
+            };
+            let stroke_attributes = match &stroke {
+                Some((paint, width)) if path.stroke => format!(
+                    r#" stroke="{}" stroke-width="{}"{}"#,
+                    paint.color(),
+                    number(*width),
+                    paint.opacity_attribute("stroke-opacity")
+                ),
+                _ => String::new(),
+            };
+
+            let _ = writeln!(
+                self.output,
+                r#"<path d="{}"{}{}/>"#,
+                path.data, fill_attributes, stroke_attributes
+            );
+        }
+        self.output.push_str("</g>\n");
+    }
+
+    fn picture(&mut self, picture: &Picture, is_slide: bool) {
+        let transform = match &picture.shape_props.transform {
+            Some(transform) => transform,
+            None => return,
+        };
+        let rel_id = picture
+            .blip_fill
+            .blip
+            .as_ref()
+            .and_then(|blip| blip.embed_rel_id.as_ref().or(blip.linked_rel_id.as_ref()));
+
+        // Relationships of the master and layout aren't part of the context
+        let href = match (rel_id, self.context.slide_part) {
+            (Some(rel_id), Some(slide_part)) if is_slide => self
+                .context
+                .relationships
+                .iter()
+                .find(|relationship| &relationship.id == rel_id)
+                .map(|relationship| join_part_path(slide_part, &relationship.target)),
+            _ => None,
+        };
+
+        if let Some(href) = href {
+            let extents = transform.extents.unwrap_or(PositiveSize2D::new(0, 0));
+            let _ = writeln!(
+                self.output,
+                r#"<image transform="{}" width="{}" height="{}" preserveAspectRatio="none" xlink:href="{}"/>"#,
+                matrix(&transform.to_affine()),
+                extents.width,
+                extents.height,
+                escape_xml(&href),
+            );
+        }
+    }
+
+    /// Returns the stroke paint and width of a shape. Without explicit line properties the line style referenced by
+    /// the shape style is used.
+    fn stroke(&self, line: Option<&LineProperties>, style: Option<&ShapeStyle>) -> Option<(Paint, f64)> {
+        let (style_line, style_color) = match style {
+            Some(style) => (
+                self.style_matrix_entry(&style.line_reference, |theme| {
+                    &theme.theme_elements.format_scheme.line_style_list
+                }),
+                style.line_reference.color.as_ref(),
+            ),
+            None => (None, None),
+        };
+
+        let fill = line
+            .and_then(|line| line.fill_properties.as_ref())
+            .map(|fill| (fill, None))
+            .or_else(|| {
+                style_line
+                    .and_then(|line| line.fill_properties.as_ref())
+                    .map(|fill| (fill, style_color))
+            })?;
+        let paint = match fill {
+            (LineFillProperties::SolidFill(color), placeholder_color) => self.color_paint(color, placeholder_color)?,
+            (LineFillProperties::GradientFill(gradient), placeholder_color) => {
+                self.color_paint(&gradient.gradient_stop_list.as_ref()?.first()?.color, placeholder_color)?
+            }
+            _ => return None,
+        };
+
+        let width = line
+            .and_then(|line| line.width)
+            .or_else(|| style_line.and_then(|line| line.width))
+            .map_or(DEFAULT_LINE_WIDTH, f64::from);
+
+        Some((paint, width))
+    }
+
+    /// Returns the paint of the fill style referenced by a shape or background style. Indices 1-999 refer to the
+    /// fill styles, indices from 1001 to the background fill styles of the theme.
+    fn style_fill_paint(&self, reference: &StyleMatrixReference) -> Option<Paint> {
+        let fill = if reference.index > 1000 {
+            self.context
+                .theme?
+                .theme_elements
+                .format_scheme
+                .bg_fill_style_list
+                .get(reference.index as usize - 1001)
+        } else {
+            self.style_matrix_entry(reference, |theme| &theme.theme_elements.format_scheme.fill_style_list)
+        }?;
+
+        self.fill_paint(fill, reference.color.as_ref())
+    }
+
+    fn style_matrix_entry<'b, T>(
+        &'b self,
+        reference: &StyleMatrixReference,
+        list: impl Fn(&'b Theme) -> &'b Vec<T>,
+    ) -> Option<&'b T> {
+        let index = (reference.index as usize).checked_sub(1)?;
+        list(self.context.theme?).get(index)
+    }
+
+    /// Returns the paint of a fill. `placeholder_color` is the color of the style reference, substituted for `phClr`.
+    fn fill_paint(&self, fill: &FillProperties, placeholder_color: Option<&Color>) -> Option<Paint> {
+        match fill {
+            FillProperties::SolidFill(color) => self.color_paint(color, placeholder_color),
+            FillProperties::GradientFill(gradient) => {
+                self.color_paint(&gradient.gradient_stop_list.as_ref()?.first()?.color, placeholder_color)
+            }
+            _ => None,
+        }
+    }
+
+    fn color_paint(&self, color: &Color, placeholder_color: Option<&Color>) -> Option<Paint> {
+        let rgba = match self.context.theme {
+            Some(theme) => {
+                let resolver = ColorResolver::new(theme).with_placeholder_color(placeholder_color);
+                match &self.context.inheritance {
+                    Some(inheritance) => resolver
+                        .with_color_mapping(inheritance.color_mapping(self.color_mapping_slide))
+                        .resolve_color(color),
+                    None => resolver.resolve_color(color),
+                }?
+            }
+            None => apply_color_transforms(Rgba::opaque(color.to_rgb()?), color.color_transforms()),
+        };
+
+        Some(Paint {
+            rgb: rgba.rgb,
+            alpha: rgba.alpha,
+        })
+    }
+}
+
+/// A resolved color with its opacity.
+#[derive(Debug, Clone, PartialEq)]
+struct Paint {
+    rgb: HexColorRGB,
+    alpha: f32,
+}
+
+impl Paint {
+    fn color(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.rgb[0], self.rgb[1], self.rgb[2])
+    }
+
+    fn fill_attributes(&self) -> String {
+        format!(r#" fill="{}"{}"#, self.color(), self.opacity_attribute("fill-opacity"))
+    }
+
+    fn opacity_attribute(&self, name: &str) -> String {
+        if self.alpha < 1.0 {
+            format!(r#" {}="{}""#, name, number(f64::from(self.alpha)))
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// The data of an SVG path with the fill and stroke flags of the DrawingML path it was created from.
+#[derive(Debug, Clone, PartialEq)]
+struct SvgPath {
+    data: String,
+    fill: bool,
+    stroke: bool,
+}
+
+impl SvgPath {
+    fn filled(data: String) -> Self {
+        Self {
+            data,
+            fill: true,
+            stroke: true,
+        }
+    }
+}
+
+/// Returns the value of an adjust value of a preset geometry, e.g. `<a:gd name="adj" fmla="val 25000" />`.
+fn adjust_value(adjust_values: Option<&GeomGuideList>, name: &str, default: f64) -> f64 {
+    adjust_values
+        .and_then(|adjust_values| adjust_values.0.iter().find(|guide| guide.name == name))
+        .and_then(|guide| guide.formula.strip_prefix("val "))
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(default)
+}
+
+fn rect_path(width: f64, height: f64) -> String {
+    polygon_path(&[(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)])
+}
+
+fn polygon_path(points: &[(f64, f64)]) -> String {
+    let mut data = String::new();
+    for (idx, (x, y)) in points.iter().enumerate() {
+        let command = if idx == 0 { 'M' } else { 'L' };
+        let _ = write!(data, "{}{} {} ", command, number(*x), number(*y));
+    }
+    data.push('Z');
+    data
+}
+
+fn preset_path(geometry: &PresetGeometry2D, width: f64, height: f64) -> String {
+    let adjust_values = geometry.adjust_value_list.as_ref();
+    let short_side = width.min(height);
+
+    match geometry.preset {
+        ShapeType::Line | ShapeType::StraightConnector1 => {
+            format!("M0 0 L{} {}", number(width), number(height))
+        }
+        ShapeType::Ellipse => {
+            let (rx, ry) = (width / 2.0, height / 2.0);
+            format!(
+                "M0 {ry} A{rx} {ry} 0 1 1 {w} {ry} A{rx} {ry} 0 1 1 0 {ry} Z",
+                rx = number(rx),
+                ry = number(ry),
+                w = number(width)
+            )
+        }
+        ShapeType::RoundRect => {
+            let radius = short_side * adjust_value(adjust_values, "adj", 16_667.0) / 100_000.0;
+            let (r, w, h) = (number(radius), number(width), number(height));
+            format!(
+                "M{r} 0 L{wr} 0 A{r} {r} 0 0 1 {w} {r} L{w} {hr} A{r} {r} 0 0 1 {wr} {h} L{r} {h} A{r} {r} 0 0 1 0 {hr} L0 {r} A{r} {r} 0 0 1 {r} 0 Z",
+                r = r,
+                w = w,
+                h = h,
+                wr = number(width - radius),
+                hr = number(height - radius)
+            )
+        }
+        ShapeType::Triangle => {
+            let top = width * adjust_value(adjust_values, "adj", 50_000.0) / 100_000.0;
+            polygon_path(&[(top, 0.0), (width, height), (0.0, height)])
+        }
+        ShapeType::RightTriangle => polygon_path(&[(0.0, 0.0), (width, height), (0.0, height)]),
+        ShapeType::Diamond => polygon_path(&[
+            (width / 2.0, 0.0),
+            (width, height / 2.0),
+            (width / 2.0, height),
+            (0.0, height / 2.0),
+        ]),
+        ShapeType::Parallelogram => {
+            let offset = short_side * adjust_value(adjust_values, "adj", 25_000.0) / 100_000.0;
+            polygon_path(&[(offset, 0.0), (width, 0.0), (width - offset, height), (0.0, height)])
+        }
+        ShapeType::Trapezoid => {
+            let offset = short_side * adjust_value(adjust_values, "adj", 25_000.0) / 100_000.0;
+            polygon_path(&[(offset, 0.0), (width - offset, 0.0), (width, height), (0.0, height)])
+        }
+        ShapeType::Hexagon => {
+            let offset = short_side * adjust_value(adjust_values, "adj", 25_000.0) / 100_000.0;
+            polygon_path(&[
+                (offset, 0.0),
+                (width - offset, 0.0),
+                (width, height / 2.0),
+                (width - offset, height),
+                (offset, height),
+                (0.0, height / 2.0),
+            ])
+        }
+        ShapeType::RightArrow => {
+            let shaft = height * adjust_value(adjust_values, "adj1", 50_000.0) / 100_000.0;
+            let head = (short_side * adjust_value(adjust_values, "adj2", 50_000.0) / 100_000.0).min(width);
+            let (top, bottom) = ((height - shaft) / 2.0, (height + shaft) / 2.0);
+            polygon_path(&[
+                (0.0, top),
+                (width - head, top),
+                (width - head, 0.0),
+                (width, height / 2.0),
+                (width - head, height),
+                (width - head, bottom),
+                (0.0, bottom),
+            ])
+        }
+        _ => rect_path(width, height),
+    }
+}
+
+/// Converts the paths of a custom geometry to SVG paths in the coordinate space of the shape. Paths that reference
+/// guides other than the built-in shape guides (`l`, `t`, `r`, `b`, `w`, `h`, `hc`, `vc`) are skipped, since guide
+/// formulas are not evaluated.
+fn custom_paths(geometry: &CustomGeometry2D, width: f64, height: f64) -> Vec<SvgPath> {
+    geometry
+        .path_list
+        .iter()
+        .filter_map(|path| {
+            let path_width = path.width.map_or(width, |path_width| path_width as f64);
+            let path_height = path.height.map_or(height, |path_height| path_height as f64);
+            let scale_x = if path_width != 0.0 { width / path_width } else { 1.0 };
+            let scale_y = if path_height != 0.0 { height / path_height } else { 1.0 };
+
+            let coordinate = |value: &AdjCoordinate, horizontal: bool| -> Option<f64> {
+                let value = match value {
+                    AdjCoordinate::Coordinate(value) => *value as f64,
+                    AdjCoordinate::GeomGuideName(name) => match name.as_str() {
+                        "l" | "t" => 0.0,
+                        "r" | "w" => path_width,
+                        "b" | "h" => path_height,
+                        "hc" => path_width / 2.0,
+                        "vc" => path_height / 2.0,
+                        _ => return None,
+                    },
+                };
+
+                Some(if horizontal { value * scale_x } else { value * scale_y })
+            };
+            let angle = |value: &AdjAngle| match value {
+                AdjAngle::Angle(angle) => Some(f64::from(*angle) / 60_000.0),
+                AdjAngle::GeomGuideName(_) => None,
+            };
+
+            let mut data = String::new();
+            let mut current = (0.0, 0.0);
+            for command in &path.commands {
+                match command {
+                    Path2DCommand::Close => data.push_str("Z "),
+                    Path2DCommand::MoveTo(point) | Path2DCommand::LineTo(point) => {
+                        current = (coordinate(&point.x, true)?, coordinate(&point.y, false)?);
+                        let command = if let Path2DCommand::MoveTo(_) = command {
+                            'M'
+                        } else {
+                            'L'
+                        };
+                        let _ = write!(data, "{}{} {} ", command, number(current.0), number(current.1));
+                    }
+                    Path2DCommand::QuadBezierTo(control, end) => {
+                        let control = (coordinate(&control.x, true)?, coordinate(&control.y, false)?);
+                        current = (coordinate(&end.x, true)?, coordinate(&end.y, false)?);
+                        let _ = write!(
+                            data,
+                            "Q{} {} {} {} ",
+                            number(control.0),
+                            number(control.1),
+                            number(current.0),
+                            number(current.1)
+                        );
+                    }
+                    Path2DCommand::CubicBezTo(control1, control2, end) => {
+                        let control1 = (coordinate(&control1.x, true)?, coordinate(&control1.y, false)?);
+                        let control2 = (coordinate(&control2.x, true)?, coordinate(&control2.y, false)?);
+                        current = (coordinate(&end.x, true)?, coordinate(&end.y, false)?);
+                        let _ = write!(
+                            data,
+                            "C{} {} {} {} {} {} ",
+                            number(control1.0),
+                            number(control1.1),
+                            number(control2.0),
+                            number(control2.1),
+                            number(current.0),
+                            number(current.1)
+                        );
+                    }
+                    Path2DCommand::ArcTo(arc) => {
+                        let radius_x = coordinate(&arc.width_radius, true)?;
+                        let radius_y = coordinate(&arc.height_radius, false)?;
+                        current = arc_to(
+                            &mut data,
+                            current,
+                            (radius_x, radius_y),
+                            angle(&arc.start_angle)?,
+                            angle(&arc.swing_angle)?,
+                        );
+                    }
+                }
+            }
+
+            Some(SvgPath {
+                data: String::from(data.trim_end()),
+                fill: path.fill_mode != Some(PathFillMode::None),
+                stroke: path.stroke.unwrap_or(true),
+            })
+        })
+        .collect()
+}
+
+/// Writes an `arcTo` command as SVG elliptical arcs and returns the end point. The arc starts at the current point,
+/// which lies on the ellipse at the start angle. The swing is split in two, so full ellipses can be drawn as well.
+fn arc_to(
+    data: &mut String,
+    current: (f64, f64),
+    radius: (f64, f64),
+    start_angle: f64,
+    swing_angle: f64,
+) -> (f64, f64) {
+    let (radius_x, radius_y) = radius;
+    // DrawingML angles are visual angles, convert them to the parametric angles of the ellipse
+    let parametric = |angle: f64| {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        (radius_x * sin).atan2(radius_y * cos)
+    };
+    let point_at = |angle: f64| {
+        let (sin, cos) = parametric(angle).sin_cos();
+        (radius_x * cos, radius_y * sin)
+    };
+
+    let start = point_at(start_angle);
+    let center = (current.0 - start.0, current.1 - start.1);
+    let sweep_flag = if swing_angle >= 0.0 { 1 } else { 0 };
+
+    let mut end = current;
+    for half in 1..=2 {
+        let point = point_at(start_angle + swing_angle * f64::from(half) / 2.0);
+        end = (center.0 + point.0, center.1 + point.1);
+        let _ = write!(
+            data,
+            "A{} {} 0 0 {} {} {} ",
+            number(radius_x),
+            number(radius_y),
+            sweep_flag,
+            number(end.0),
+            number(end.1)
+        );
+    }
+
+    end
+}
+
+fn matrix(transform: &AffineTransform) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        number(transform.a),
+        number(transform.b),
+        number(transform.c),
+        number(transform.d),
+        number(transform.e),
+        number(transform.f)
+    )
+}
+
+/// Formats a number with at most 4 decimals, without trailing zeros.
+fn number(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+    match formatted {
+        "-0" => String::from("0"),
+        formatted => String::from(formatted),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shared::drawingml::coordsys::GroupTransform2D, xml::XmlNode};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_affine_transform() {
+        let transform = Transform2D::from_xml_element(
+            &XmlNode::from_str(
+                r#"<a:xfrm rot="5400000"><a:off x="100" y="100" /><a:ext cx="200" cy="100" /></a:xfrm>"#,
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .to_affine();
+        let (x, y) = transform.apply(0.0, 0.0);
+        assert_eq!((x.round(), y.round()), (250.0, 50.0));
+
+        let group_transform = GroupTransform2D::from_xml_element(
+            &XmlNode::from_str(
+                r#"<a:xfrm><a:off x="1000" y="0" /><a:ext cx="200" cy="200" /><a:chOff x="10" y="10" /><a:chExt cx="100" cy="100" /></a:xfrm>"#,
+            )
+            .unwrap(),
+        )
+        .unwrap()
+        .to_affine();
+        assert_eq!(group_transform.apply(60.0, 110.0), (1100.0, 200.0));
+    }
+
+    #[test]
+    pub fn test_slide_to_svg() {
+        let slide_xml = r#"<p:sld>
+            <p:cSld>
+                <p:bg><p:bgPr><a:solidFill><a:srgbClr val="FFFFFF" /></a:solidFill><a:effectLst /></p:bgPr></p:bg>
+                <p:spTree>
+                    <p:nvGrpSpPr><p:cNvPr id="1" name="" /><p:cNvGrpSpPr /><p:nvPr /></p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="2" name="Rectangle 1" /><p:cNvSpPr /><p:nvPr /></p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="100" y="200" /><a:ext cx="300" cy="400" /></a:xfrm>
+                            <a:prstGeom prst="rect"><a:avLst /></a:prstGeom>
+                            <a:solidFill><a:srgbClr val="FF0000"><a:alpha val="50000" /></a:srgbClr></a:solidFill>
+                            <a:ln w="25400"><a:solidFill><a:srgbClr val="0000FF" /></a:solidFill></a:ln>
+                        </p:spPr>
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr><p:cNvPr id="3" name="Freeform 2" /><p:cNvSpPr /><p:nvPr /></p:nvSpPr>
+                        <p:spPr>
+                            <a:xfrm><a:off x="0" y="0" /><a:ext cx="200" cy="200" /></a:xfrm>
+                            <a:custGeom>
+                                <a:pathLst>
+                                    <a:path w="100" h="100">
+                                        <a:moveTo><a:pt x="0" y="0" /></a:moveTo>
+                                        <a:lnTo><a:pt x="r" y="b" /></a:lnTo>
+                                        <a:close />
+                                    </a:path>
+                                </a:pathLst>
+                            </a:custGeom>
+                            <a:noFill />
+                        </p:spPr>
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(slide_xml).unwrap()).unwrap();
+
+        let svg = slide_to_svg(
+            &slide,
+            &SvgContext {
+                slide_size: PositiveSize2D::new(1000, 500),
+                inheritance: None,
+                theme: None,
+                slide_part: None,
+                relationships: &[],
+            },
+        );
+
+        assert_eq!(
+            svg,
+            r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="0.0787pt" height="0.0394pt" viewBox="0 0 1000 500">
+<rect width="1000" height="500" fill="#FFFFFF"/>
+<g transform="matrix(1 0 0 1 100 200)">
+<path d="M0 0 L300 0 L300 400 L0 400 Z" fill="#FF0000" fill-opacity="0.5" stroke="#0000FF" stroke-width="25400"/>
+</g>
+<g transform="matrix(1 0 0 1 0 0)">
+<path d="M0 0 L200 200 Z" fill="none"/>
+</g>
+</svg>
+"##
+        );
+    }
+}
@@ -0,0 +1,297 @@
+use super::pml::slides::{GroupShape, NotesSlide, PlaceholderType, ShapeGroup, Slide};
+use crate::shared::drawingml::{
+    core::TextBody,
+    simpletypes::DrawingElementId,
+    text::{paragraphs::TextParagraph, runformatting::TextRun},
+};
+
+/// The character used to represent line breaks (`a:br`) in extracted text. Matches the vertical tab used by
+/// PowerPoint when copying text, so line breaks can be distinguished from paragraph breaks.
+pub const LINE_BREAK: char = '\u{b}';
+
+/// The text of a single paragraph of a text body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParagraphText {
+    /// The 0-based indentation level of the paragraph (`lvl`). Defaults to 0.
+    pub level: i32,
+    /// The text of the runs, line breaks and text fields of the paragraph in order.
+    pub runs: Vec<String>,
+}
+
+impl ParagraphText {
+    pub fn from_text_paragraph(paragraph: &TextParagraph) -> Self {
+        let level = paragraph
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.level)
+            .unwrap_or(0);
+
+        let runs = paragraph
+            .text_run_list
+            .iter()
+            .map(|run| match run {
+                TextRun::RegularTextRun(run) => run.text.clone(),
+                TextRun::LineBreak(_) => LINE_BREAK.to_string(),
+                TextRun::TextField(field) => field.text.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        Self { level, runs }
+    }
+
+    pub fn text(&self) -> String {
+        self.runs.concat()
+    }
+}
+
+/// The text of a shape's text body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeText {
+    pub shape_id: DrawingElementId,
+    pub shape_name: String,
+    pub placeholder_type: Option<PlaceholderType>,
+    pub paragraphs: Vec<ParagraphText>,
+}
+
+impl ShapeText {
+    /// Returns the text of the shape with the paragraphs separated by newlines.
+    pub fn text(&self) -> String {
+        paragraphs_text(&self.paragraphs)
+    }
+}
+
+/// Returns the text of the paragraphs of a text body.
+pub fn text_body_paragraphs(text_body: &TextBody) -> Vec<ParagraphText> {
+    text_body
+        .paragraph_array
+        .iter()
+        .map(ParagraphText::from_text_paragraph)
+        .collect()
+}
+
+fn paragraphs_text(paragraphs: &[ParagraphText]) -> String {
+    paragraphs
+        .iter()
+        .map(ParagraphText::text)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Slide {
+    /// Walks the shape tree of the slide, including group shapes, and returns the text of every shape that has a
+    /// text body in document order.
+    pub fn shape_texts(&self) -> Vec<ShapeText> {
+        let mut shape_texts = Vec::new();
+        collect_group_shape_texts(&self.common_slide_data.shape_tree, &mut shape_texts);
+        shape_texts
+    }
+
+    /// Returns the text of all shapes of the slide separated by newlines.
+    pub fn text(&self) -> String {
+        self.shape_texts()
+            .iter()
+            .map(ShapeText::text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl NotesSlide {
+    /// Walks the shape tree of the notes slide and returns the text of every shape that has a text body in document
+    /// order.
+    pub fn shape_texts(&self) -> Vec<ShapeText> {
+        let mut shape_texts = Vec::new();
+        collect_group_shape_texts(&self.common_slide_data.shape_tree, &mut shape_texts);
+        shape_texts
+    }
+
+    /// Returns the speaker notes, i.e. the text of the body placeholders of the notes slide. The slide image,
+    /// header, footer and slide number placeholders are skipped.
+    pub fn notes_text(&self) -> String {
+        self.shape_texts()
+            .iter()
+            .filter(|shape_text| shape_text.placeholder_type == Some(PlaceholderType::Body))
+            .map(ShapeText::text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn collect_group_shape_texts(group_shape: &GroupShape, shape_texts: &mut Vec<ShapeText>) {
+    for shape_group in &group_shape.shape_array {
+        match shape_group {
+            ShapeGroup::Shape(shape) => {
+                if let Some(text_body) = &shape.text_body {
+                    let placeholder_type = shape
+                        .non_visual_props
+                        .app_props
+                        .placeholder
+                        .as_ref()
+                        .and_then(|placeholder| placeholder.placeholder_type);
+
+                    shape_texts.push(ShapeText {
+                        shape_id: shape.non_visual_props.drawing_props.id,
+                        shape_name: shape.non_visual_props.drawing_props.name.clone(),
+                        placeholder_type,
+                        paragraphs: text_body_paragraphs(text_body),
+                    });
+                }
+            }
+            ShapeGroup::GroupShape(group_shape) => collect_group_shape_texts(group_shape, shape_texts),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shared::drawingml::colors::ColorMappingOverride, xml::XmlNode};
+    use std::str::FromStr;
+
+    fn slide_for_test() -> Slide {
+        let xml = r#"<p:sld>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Title 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="title" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:p><a:r><a:t>Quarterly </a:t></a:r><a:r><a:t>results</a:t></a:r></a:p>
+                        </p:txBody>
+                    </p:sp>
+                    <p:grpSp>
+                        <p:nvGrpSpPr>
+                            <p:cNvPr id="3" name="Group 2" />
+                            <p:cNvGrpSpPr />
+                            <p:nvPr />
+                        </p:nvGrpSpPr>
+                        <p:grpSpPr />
+                        <p:sp>
+                            <p:nvSpPr>
+                                <p:cNvPr id="4" name="Content 3" />
+                                <p:cNvSpPr />
+                                <p:nvPr />
+                            </p:nvSpPr>
+                            <p:spPr />
+                            <p:txBody>
+                                <a:bodyPr />
+                                <a:p><a:r><a:t>Revenue</a:t></a:r><a:br /><a:r><a:t>up</a:t></a:r></a:p>
+                                <a:p><a:pPr lvl="1" /><a:r><a:t>Costs down</a:t></a:r></a:p>
+                            </p:txBody>
+                        </p:sp>
+                    </p:grpSp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="5" name="Rectangle 4" />
+                            <p:cNvSpPr />
+                            <p:nvPr />
+                        </p:nvSpPr>
+                        <p:spPr />
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+        </p:sld>"#;
+
+        Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_slide_shape_texts() {
+        let shape_texts = slide_for_test().shape_texts();
+        assert_eq!(shape_texts.len(), 2);
+
+        assert_eq!(shape_texts[0].shape_id, 2);
+        assert_eq!(shape_texts[0].placeholder_type, Some(PlaceholderType::Title));
+        assert_eq!(shape_texts[0].text(), "Quarterly results");
+
+        assert_eq!(shape_texts[1].shape_name, "Content 3");
+        assert_eq!(
+            shape_texts[1].paragraphs,
+            vec![
+                ParagraphText {
+                    level: 0,
+                    runs: vec![String::from("Revenue"), String::from("\u{b}"), String::from("up")],
+                },
+                ParagraphText {
+                    level: 1,
+                    runs: vec![String::from("Costs down")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_slide_text() {
+        assert_eq!(slide_for_test().text(), "Quarterly results\nRevenue\u{b}up\nCosts down");
+    }
+
+    #[test]
+    pub fn test_notes_slide_notes_text() {
+        let xml = r#"<p:notes>
+            <p:cSld>
+                <p:spTree>
+                    <p:nvGrpSpPr>
+                        <p:cNvPr id="1" name="" />
+                        <p:cNvGrpSpPr />
+                        <p:nvPr />
+                    </p:nvGrpSpPr>
+                    <p:grpSpPr />
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="2" name="Slide Image Placeholder 1" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="sldImg" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="3" name="Notes Placeholder 2" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="body" idx="1" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:p><a:r><a:t>Mention the new office</a:t></a:r></a:p>
+                            <a:p><a:r><a:t>Ask for questions</a:t></a:r></a:p>
+                        </p:txBody>
+                    </p:sp>
+                    <p:sp>
+                        <p:nvSpPr>
+                            <p:cNvPr id="4" name="Slide Number Placeholder 3" />
+                            <p:cNvSpPr />
+                            <p:nvPr><p:ph type="sldNum" idx="5" /></p:nvPr>
+                        </p:nvSpPr>
+                        <p:spPr />
+                        <p:txBody>
+                            <a:bodyPr />
+                            <a:p><a:fld id="{B6F15528-21DE-4FAA-801E-634DDDAF4B2B}" type="slidenum"><a:t>1</a:t></a:fld></a:p>
+                        </p:txBody>
+                    </p:sp>
+                </p:spTree>
+            </p:cSld>
+            <p:clrMapOvr><a:masterClrMapping /></p:clrMapOvr>
+        </p:notes>"#;
+
+        let notes_slide = NotesSlide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(
+            notes_slide.color_mapping_override,
+            Some(ColorMappingOverride::UseMaster)
+        );
+        assert_eq!(notes_slide.shape_texts().len(), 2);
+        assert_eq!(notes_slide.notes_text(), "Mention the new office\nAsk for questions");
+    }
+}
@@ -0,0 +1,320 @@
+use super::pml::{
+    animation::{
+        TLCommonTimeNodeData, TLTime, TLTimeNodeId, TLTimeNodePresetClassType, TLTimeNodeType, TLTimeTargetElement,
+        TimeNodeGroup,
+    },
+    slides::{SlideTiming, SlideTransition, TransitionSpeed},
+};
+use crate::shared::drawingml::simpletypes::DrawingElementId;
+
+/// The kind of an animation behavior of an effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BehaviorKind {
+    Animate,
+    AnimateColor,
+    AnimateEffect,
+    AnimateMotion,
+    AnimateRotation,
+    AnimateScale,
+    Command,
+    Set,
+    Audio,
+    Video,
+}
+
+/// An animation effect of a slide, as listed in the animation pane of PowerPoint, e.g. a "Fly In" entrance of a
+/// shape.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AnimationEffect {
+    pub time_node_id: Option<TLTimeNodeId>,
+    /// The class of the effect, e.g. entrance or emphasis
+    pub preset_class: Option<TLTimeNodePresetClassType>,
+    /// The identifier of the preset effect in its class, e.g. 2 is "Fly In" in the entrance class
+    pub preset_id: Option<i32>,
+    pub preset_subtype: Option<i32>,
+    /// How the effect is started: on click, with or after the previous effect
+    pub node_type: Option<TLTimeNodeType>,
+    /// The delay of the effect in milliseconds after it's started
+    pub delay: u32,
+    /// The time in milliseconds from the start of the effect until all of its behaviors end, or `None` if a behavior
+    /// lasts indefinitely
+    pub duration: Option<u32>,
+    /// The distinct ids of the shapes animated by the effect
+    pub shape_ids: Vec<DrawingElementId>,
+    pub behaviors: Vec<BehaviorKind>,
+}
+
+impl SlideTiming {
+    /// Returns the animation effects of the timing tree in document order. A time node is an effect if it has an
+    /// effect preset class or it's a click, with or after effect node.
+    ///
+    /// ```
+    /// use oox::{pptx::pml::{animation::TLTimeNodePresetClassType, slides::SlideTiming}, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<p:timing><p:tnLst><p:par>
+    ///     <p:cTn id="1" dur="indefinite" nodeType="tmRoot"><p:childTnLst><p:par>
+    ///         <p:cTn id="2" presetID="10" presetClass="entr" nodeType="clickEffect">
+    ///             <p:stCondLst><p:cond delay="500" /></p:stCondLst>
+    ///             <p:childTnLst><p:animEffect transition="in" filter="fade"><p:cBhvr>
+    ///                 <p:cTn id="3" dur="1000" />
+    ///                 <p:tgtEl><p:spTgt spid="4" /></p:tgtEl>
+    ///             </p:cBhvr></p:animEffect></p:childTnLst>
+    ///         </p:cTn>
+    ///     </p:par></p:childTnLst></p:cTn>
+    /// </p:par></p:tnLst></p:timing>"#;
+    /// let timing = SlideTiming::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    ///
+    /// let effects = timing.effects();
+    /// assert_eq!(effects.len(), 1);
+    /// assert_eq!(effects[0].preset_class, Some(TLTimeNodePresetClassType::Entrance));
+    /// assert_eq!(effects[0].delay, 500);
+    /// assert_eq!(effects[0].duration, Some(1000));
+    /// assert_eq!(effects[0].shape_ids, vec![4]);
+    /// ```
+    pub fn effects(&self) -> Vec<AnimationEffect> {
+        let mut effects = Vec::new();
+        for time_node in self.time_node_list.iter().flatten() {
+            collect_effects(time_node, &mut effects);
+        }
+
+        effects
+    }
+}
+
+impl SlideTransition {
+    /// Returns the duration of the transition in milliseconds. The duration is either explicitly specified or derived
+    /// from the speed of the transition, which is fast by default.
+    pub fn duration(&self) -> u32 {
+        self.duration
+            .unwrap_or(match self.speed.unwrap_or(TransitionSpeed::Fast) {
+                TransitionSpeed::Slow => 1000,
+                TransitionSpeed::Medium => 750,
+                TransitionSpeed::Fast => 500,
+            })
+    }
+}
+
+fn collect_effects(time_node: &TimeNodeGroup, effects: &mut Vec<AnimationEffect>) {
+    let data = match container_data(time_node) {
+        Some(data) => data,
+        None => return,
+    };
+
+    let is_effect = data.preset_class.is_some()
+        || matches!(
+            data.node_type,
+            Some(TLTimeNodeType::ClickEffect) | Some(TLTimeNodeType::WithEffect) | Some(TLTimeNodeType::AfterEffect)
+        );
+
+    if is_effect {
+        let mut effect = AnimationEffect {
+            time_node_id: data.id,
+            preset_class: data.preset_class,
+            preset_id: data.preset_id,
+            preset_subtype: data.preset_subtype,
+            node_type: data.node_type,
+            delay: start_delay(data).unwrap_or(0),
+            duration: Some(0),
+            ..Default::default()
+        };
+        for child in child_time_nodes(data) {
+            let end = collect_behaviors(child, &mut effect);
+            effect.duration = effect.duration.zip(end).map(|(duration, end)| duration.max(end));
+        }
+        effects.push(effect);
+    } else {
+        for child in child_time_nodes(data) {
+            collect_effects(child, effects);
+        }
+    }
+}
+
+/// Collects the behaviors of an effect. Returns the end time of the time node relative to the start of its parent,
+/// or `None` if it's indefinite.
+fn collect_behaviors(time_node: &TimeNodeGroup, effect: &mut AnimationEffect) -> Option<u32> {
+    let (kind, data) = match time_node {
+        TimeNodeGroup::Animate(behavior) => (BehaviorKind::Animate, &behavior.common_behavior_data),
+        TimeNodeGroup::AnimateColor(behavior) => (BehaviorKind::AnimateColor, &behavior.common_behavior_data),
+        TimeNodeGroup::AnimateEffect(behavior) => (BehaviorKind::AnimateEffect, &behavior.common_behavior_data),
+        TimeNodeGroup::AnimateMotion(behavior) => (BehaviorKind::AnimateMotion, &behavior.common_behavior_data),
+        TimeNodeGroup::AnimateRotation(behavior) => (BehaviorKind::AnimateRotation, &behavior.common_behavior_data),
+        TimeNodeGroup::AnimateScale(behavior) => (BehaviorKind::AnimateScale, &behavior.common_behavior_data),
+        TimeNodeGroup::Command(behavior) => (BehaviorKind::Command, &behavior.common_behavior_data),
+        TimeNodeGroup::Set(behavior) => (BehaviorKind::Set, &behavior.common_behavior_data),
+        TimeNodeGroup::Audio(audio) => {
+            let media = &audio.common_media_node_data;
+            return behavior_end(
+                BehaviorKind::Audio,
+                &media.common_time_node_data,
+                &media.target_element,
+                effect,
+            );
+        }
+        TimeNodeGroup::Video(video) => {
+            let media = &video.common_media_node_data;
+            return behavior_end(
+                BehaviorKind::Video,
+                &media.common_time_node_data,
+                &media.target_element,
+                effect,
+            );
+        }
+        container => {
+            let data = container_data(container)?;
+            let mut end = Some(0);
+            for child in child_time_nodes(data) {
+                let child_end = collect_behaviors(child, effect);
+                end = end.zip(child_end).map(|(end, child_end)| end.max(child_end));
+            }
+            return end.map(|end| end + start_delay(data).unwrap_or(0));
+        }
+    };
+
+    behavior_end(kind, &data.common_time_node_data, &data.target_element, effect)
+}
+
+fn behavior_end(
+    kind: BehaviorKind,
+    data: &TLCommonTimeNodeData,
+    target: &TLTimeTargetElement,
+    effect: &mut AnimationEffect,
+) -> Option<u32> {
+    effect.behaviors.push(kind);
+    if let TLTimeTargetElement::ShapeTarget(shape_target) = target {
+        if !effect.shape_ids.contains(&shape_target.shape_id) {
+            effect.shape_ids.push(shape_target.shape_id);
+        }
+    }
+
+    let duration = match data.duration {
+        Some(TLTime::TimePoint(duration)) => duration,
+        Some(TLTime::Indefinite) => return None,
+        None => 0,
+    };
+
+    Some(start_delay(data).unwrap_or(0) + duration)
+}
+
+fn container_data(time_node: &TimeNodeGroup) -> Option<&TLCommonTimeNodeData> {
+    match time_node {
+        TimeNodeGroup::Parallel(data) | TimeNodeGroup::Exclusive(data) => Some(data),
+        TimeNodeGroup::Sequence(sequence) => Some(&sequence.common_time_node_data),
+        _ => None,
+    }
+}
+
+fn child_time_nodes(data: &TLCommonTimeNodeData) -> impl Iterator<Item = &TimeNodeGroup> {
+    data.child_time_node_list.iter().flat_map(|list| list.0.iter())
+}
+
+/// Returns the delay of the first start condition with a definite delay.
+fn start_delay(data: &TLCommonTimeNodeData) -> Option<u32> {
+    data.start_condition_list
+        .iter()
+        .flat_map(|list| list.0.iter())
+        .find_map(|condition| match condition.delay {
+            Some(TLTime::TimePoint(delay)) => Some(delay),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{pptx::pml::slides::Slide, xml::XmlNode};
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_slide_timing_and_transition() {
+        let xml = r#"<p:sld>
+            <p:cSld><p:spTree>
+                <p:nvGrpSpPr><p:cNvPr id="1" name="" /><p:cNvGrpSpPr /><p:nvPr /></p:nvGrpSpPr>
+                <p:grpSpPr />
+            </p:spTree></p:cSld>
+            <mc:AlternateContent>
+                <mc:Choice Requires="p14">
+                    <p:transition spd="slow" p14:dur="1250" advClick="0"><p14:vortex dir="r" /></p:transition>
+                </mc:Choice>
+                <mc:Fallback>
+                    <p:transition spd="slow" advClick="0"><p:fade /></p:transition>
+                </mc:Fallback>
+            </mc:AlternateContent>
+            <p:timing><p:tnLst><p:par>
+                <p:cTn id="1" dur="indefinite" restart="never" nodeType="tmRoot"><p:childTnLst>
+                    <p:seq concurrent="1" nextAc="seek">
+                        <p:cTn id="2" dur="indefinite" nodeType="mainSeq"><p:childTnLst>
+                            <p:par><p:cTn id="3" fill="hold"><p:childTnLst><p:par><p:cTn id="4"><p:childTnLst>
+                                <p:par>
+                                    <p:cTn id="5" presetID="2" presetClass="entr" presetSubtype="4" nodeType="clickEffect">
+                                        <p:childTnLst>
+                                            <p:set><p:cBhvr>
+                                                <p:cTn id="6" dur="1"><p:stCondLst><p:cond delay="0" /></p:stCondLst></p:cTn>
+                                                <p:tgtEl><p:spTgt spid="7" /></p:tgtEl>
+                                                <p:attrNameLst><p:attrName>style.visibility</p:attrName></p:attrNameLst>
+                                            </p:cBhvr><p:to><p:strVal val="visible" /></p:to></p:set>
+                                            <p:anim calcmode="lin" valueType="num"><p:cBhvr additive="base">
+                                                <p:cTn id="8" dur="500" fill="hold" />
+                                                <p:tgtEl><p:spTgt spid="7" /></p:tgtEl>
+                                                <p:attrNameLst><p:attrName>ppt_y</p:attrName></p:attrNameLst>
+                                            </p:cBhvr></p:anim>
+                                        </p:childTnLst>
+                                    </p:cTn>
+                                </p:par>
+                                <p:par>
+                                    <p:cTn id="9" presetID="1" presetClass="exit" nodeType="withEffect">
+                                        <p:stCondLst><p:cond delay="250" /></p:stCondLst>
+                                        <p:childTnLst>
+                                            <p:set><p:cBhvr>
+                                                <p:cTn id="10" dur="1" fill="hold"><p:stCondLst><p:cond delay="499" /></p:stCondLst></p:cTn>
+                                                <p:tgtEl><p:spTgt spid="11" /></p:tgtEl>
+                                                <p:attrNameLst><p:attrName>style.visibility</p:attrName></p:attrNameLst>
+                                            </p:cBhvr><p:to><p:strVal val="hidden" /></p:to></p:set>
+                                        </p:childTnLst>
+                                    </p:cTn>
+                                </p:par>
+                            </p:childTnLst></p:cTn></p:par></p:childTnLst></p:cTn></p:par>
+                        </p:childTnLst></p:cTn>
+                        <p:prevCondLst><p:cond evt="onPrev" delay="0"><p:tgtEl><p:sldTgt /></p:tgtEl></p:cond></p:prevCondLst>
+                        <p:nextCondLst><p:cond evt="onNext" delay="0"><p:tgtEl><p:sldTgt /></p:tgtEl></p:cond></p:nextCondLst>
+                    </p:seq>
+                </p:childTnLst></p:cTn>
+            </p:par></p:tnLst></p:timing>
+        </p:sld>"#;
+        let slide = Slide::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        let transition = slide.transition.unwrap();
+        assert_eq!(transition.advance_on_click, Some(false));
+        assert!(transition.transition_type.is_some());
+        assert_eq!(transition.duration(), 1250);
+
+        let effects = slide.timing.unwrap().effects();
+        assert_eq!(
+            effects,
+            vec![
+                AnimationEffect {
+                    time_node_id: Some(5),
+                    preset_class: Some(TLTimeNodePresetClassType::Entrance),
+                    preset_id: Some(2),
+                    preset_subtype: Some(4),
+                    node_type: Some(TLTimeNodeType::ClickEffect),
+                    delay: 0,
+                    duration: Some(500),
+                    shape_ids: vec![7],
+                    behaviors: vec![BehaviorKind::Set, BehaviorKind::Animate],
+                },
+                AnimationEffect {
+                    time_node_id: Some(9),
+                    preset_class: Some(TLTimeNodePresetClassType::Exit),
+                    preset_id: Some(1),
+                    preset_subtype: None,
+                    node_type: Some(TLTimeNodeType::WithEffect),
+                    delay: 250,
+                    duration: Some(500),
+                    shape_ids: vec![11],
+                    behaviors: vec![BehaviorKind::Set],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Python bindings of the WordprocessingML and PresentationML parsers.
+//!
+//! The extension module is built with
+//! `PYO3_BUILD_EXTENSION_MODULE=1 cargo rustc --release --features python --crate-type cdylib`, then the library is
+//! renamed to `oox.so` (`oox.pyd` on Windows) so Python can import it. Without the environment variable the library
+//! links to libpython, which the tests of the bindings rely on.
+//!
+//! ```python
+//! import oox
+//!
+//! document = oox.Document("report.docx")
+//! print(document.text())
+//! for control in document.content_controls():
+//!     print(control["tag"], control["value"])
+//!
+//! presentation = oox.Presentation("slides.pptx")
+//! for index in range(presentation.slide_count):
+//!     print(presentation.slide_text(index))
+//! ```
+
+use crate::{
+    docx::{
+        contentcontrols::{ContentControlLevel, ContentControlValue},
+        package::Package as DocxPackage,
+        text::{RevisionView, TextOptions},
+        wml::{document::HexColor, styles::StyleType},
+    },
+    pptx::package::Package as PptxPackage,
+    shared::{docprops::Core, sharedtypes::Measure},
+};
+use pyo3::{create_exception, exceptions::PyException, prelude::*, types::PyDict};
+use std::{error::Error, path::Path};
+
+create_exception!(
+    oox,
+    OoxError,
+    PyException,
+    "Raised when a document can't be opened or parsed."
+);
+
+fn to_py_error(error: Box<dyn Error>) -> PyErr {
+    OoxError::new_err(error.to_string())
+}
+
+/// Maps the name of a revision view used by [`Document::text`] to the view.
+fn revision_view(name: &str) -> Option<RevisionView> {
+    match name {
+        "accepted" => Some(RevisionView::Accepted),
+        "rejected" => Some(RevisionView::Rejected),
+        "original" => Some(RevisionView::Original),
+        _ => None,
+    }
+}
+
+fn core_properties<'py>(py: Python<'py>, core: Option<&Core>) -> PyResult<Bound<'py, PyDict>> {
+    let properties = PyDict::new(py);
+    if let Some(core) = core {
+        properties.set_item("title", &core.title)?;
+        properties.set_item("creator", &core.creator)?;
+        properties.set_item("last_modified_by", &core.last_modified_by)?;
+        properties.set_item("revision", core.revision)?;
+        properties.set_item("created", &core.created_time)?;
+        properties.set_item("modified", &core.modified_time)?;
+    }
+    Ok(properties)
+}
+
+/// A parsed WordprocessingML document.
+#[pyclass(module = "oox")]
+pub struct Document {
+    package: DocxPackage,
+}
+
+#[pymethods]
+impl Document {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        let package = DocxPackage::from_file(Path::new(path)).map_err(to_py_error)?;
+        Ok(Self { package })
+    }
+
+    /// Returns the plain text of the main document. Tracked changes are `"accepted"` (the default), `"rejected"` or
+    /// shown as stored in the document with `"original"`.
+    #[pyo3(signature = (revisions = "accepted"))]
+    fn text(&self, revisions: &str) -> PyResult<String> {
+        let revisions = revision_view(revisions)
+            .ok_or_else(|| OoxError::new_err(format!("Unknown revision view '{}'", revisions)))?;
+        let options = TextOptions {
+            revisions,
+            ..Default::default()
+        };
+        Ok(self.package.extract_text(&options))
+    }
+
+    /// The number of paragraphs of the body, including the paragraphs of tables.
+    #[getter]
+    fn paragraph_count(&self) -> usize {
+        self.package
+            .main_document
+            .as_ref()
+            .map_or(0, |document| document.paragraphs().count())
+    }
+
+    /// The core properties of the document, e.g. `title` and `creator`.
+    #[getter]
+    fn core_properties<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        core_properties(py, self.package.core.as_ref())
+    }
+
+    /// Looks up a style by its id and returns its name, type and the most common run properties resolved through
+    /// the styles it's based on. Returns `None` if the document has no such style.
+    fn style<'py>(&self, py: Python<'py>, style_id: &str) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let style = match self.package.styles.as_ref().and_then(|styles| {
+            styles
+                .styles
+                .iter()
+                .find(|style| style.style_id.as_deref() == Some(style_id))
+        }) {
+            Some(style) => style,
+            None => return Ok(None),
+        };
+        let resolved_style = match self.package.resolve_style_with_id(style_id) {
+            Some(resolved_style) => resolved_style,
+            None => return Ok(None),
+        };
+
+        let run_properties = &resolved_style.run_properties;
+        let dict = PyDict::new(py);
+        dict.set_item("id", style_id)?;
+        dict.set_item("name", &style.name)?;
+        dict.set_item(
+            "type",
+            style.style_type.map(|style_type| match style_type {
+                StyleType::Paragraph => "paragraph",
+                StyleType::Character => "character",
+                StyleType::Table => "table",
+                StyleType::Numbering => "numbering",
+            }),
+        )?;
+        dict.set_item("based_on", &style.based_on)?;
+        dict.set_item("bold", run_properties.bold)?;
+        dict.set_item("italic", run_properties.italic)?;
+        dict.set_item(
+            "font",
+            run_properties.fonts.as_ref().and_then(|fonts| fonts.ascii.clone()),
+        )?;
+        dict.set_item("font_size", run_properties.font_size.as_ref().map(Measure::to_points))?;
+        dict.set_item(
+            "color",
+            run_properties.color.as_ref().map(|color| match color.value {
+                HexColor::Auto => String::from("auto"),
+                HexColor::RGB([red, green, blue]) => format!("{:02X}{:02X}{:02X}", red, green, blue),
+            }),
+        )?;
+        Ok(Some(dict))
+    }
+
+    /// Returns the content controls of the main document in document order. Every control is a dict with its `tag`,
+    /// `alias`, `id`, `level`, `text` and typed `value`. Dates are returned as ISO 8601 strings.
+    fn content_controls<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let document = match &self.package.main_document {
+            Some(document) => document,
+            None => return Ok(Vec::new()),
+        };
+
+        document
+            .content_controls()
+            .map(|control| {
+                let dict = PyDict::new(py);
+                dict.set_item("tag", control.tag())?;
+                dict.set_item("alias", control.alias())?;
+                dict.set_item("id", control.id())?;
+                dict.set_item(
+                    "level",
+                    match control.level {
+                        ContentControlLevel::Block => "block",
+                        ContentControlLevel::Run => "run",
+                        ContentControlLevel::Row => "row",
+                        ContentControlLevel::Cell => "cell",
+                    },
+                )?;
+                dict.set_item("text", &control.text)?;
+                match control.value() {
+                    ContentControlValue::Text(text) => dict.set_item("value", text)?,
+                    ContentControlValue::Date(date) => dict.set_item(
+                        "value",
+                        date.map(|date| {
+                            format!(
+                                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                                date.year, date.month, date.day, date.hour, date.minute, date.second
+                            )
+                        }),
+                    )?,
+                    ContentControlValue::Selection { value, .. } => dict.set_item("value", value)?,
+                    ContentControlValue::Picture => dict.set_item("value", py.None())?,
+                }
+                Ok(dict)
+            })
+            .collect()
+    }
+}
+
+/// A parsed PresentationML document. Slides are parsed when they are first accessed.
+#[pyclass(module = "oox")]
+pub struct Presentation {
+    package: PptxPackage,
+}
+
+#[pymethods]
+impl Presentation {
+    #[new]
+    fn new(path: &str) -> PyResult<Self> {
+        let package = PptxPackage::from_file(Path::new(path)).map_err(to_py_error)?;
+        Ok(Self { package })
+    }
+
+    #[getter]
+    fn slide_count(&self) -> usize {
+        self.package.slide_count()
+    }
+
+    /// The core properties of the presentation, e.g. `title` and `creator`.
+    #[getter]
+    fn core_properties<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        core_properties(py, self.package.core.as_deref())
+    }
+
+    /// Returns the text of the shapes of the slide at the given 0-based index, one shape per line.
+    fn slide_text(&self, index: usize) -> PyResult<String> {
+        Ok(self.package.slide(index).map_err(to_py_error)?.text())
+    }
+
+    /// Returns the speaker notes of the slide at the given 0-based index, or `None` if the slide has no notes.
+    fn notes_text(&self, index: usize) -> Option<String> {
+        self.package
+            .notes_slide(index)
+            .map(|notes_slide| notes_slide.notes_text())
+    }
+}
+
+#[pymodule]
+fn oox(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Document>()?;
+    module.add_class::<Presentation>()?;
+    module.add("OoxError", module.py().get_type::<OoxError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> String {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join(name);
+        path.to_string_lossy().into_owned()
+    }
+
+    fn assert_oox_error(error: Option<PyErr>, message: &str) {
+        let error = error.expect("expected an error");
+        Python::initialize();
+        Python::attach(|py| {
+            assert!(error.is_instance_of::<OoxError>(py));
+            assert_eq!(error.value(py).to_string(), message);
+        });
+    }
+
+    #[test]
+    pub fn test_document_text() {
+        assert_eq!(revision_view("accepted"), Some(RevisionView::Accepted));
+        assert_eq!(revision_view("rejected"), Some(RevisionView::Rejected));
+        assert_eq!(revision_view("original"), Some(RevisionView::Original));
+        assert_eq!(revision_view("Accepted"), None);
+
+        let document = Document::new(&fixture("sample.docx")).unwrap();
+        for revisions in &["accepted", "rejected", "original"] {
+            let options = TextOptions {
+                revisions: revision_view(revisions).unwrap(),
+                ..Default::default()
+            };
+            assert_eq!(
+                document.text(revisions).unwrap(),
+                document.package.extract_text(&options)
+            );
+        }
+        assert_oox_error(document.text("all").err(), "Unknown revision view 'all'");
+    }
+
+    #[test]
+    pub fn test_to_py_error() {
+        let path = fixture("presentation.xml");
+        let message = DocxPackage::from_file(Path::new(&path)).unwrap_err().to_string();
+        assert_oox_error(Document::new(&path).err(), &message);
+
+        let path = fixture("missing.pptx");
+        let message = PptxPackage::from_file(Path::new(&path)).unwrap_err().to_string();
+        assert_oox_error(Presentation::new(&path).err(), &message);
+
+        let presentation = Presentation::new(&fixture("sample.pptx")).unwrap();
+        assert!(presentation.slide_text(presentation.slide_count()).is_err());
+    }
+}
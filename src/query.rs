@@ -0,0 +1,434 @@
+//! A small XPath-like query language to locate elements of [`XmlNode`] trees.
+//!
+//! A query is a list of steps separated by `/`, selecting the children of the previous step, or by `//`, selecting
+//! all of its descendants. Queries are evaluated relative to the node they're run on, unless they start with `/` or
+//! `//`, in which case the first step is matched against the node itself or any of its descendants as well.
+//!
+//! A step is an element name and any number of predicates:
+//!
+//! * `p` matches elements by their local name, `w:p` by their qualified name and `*` matches any element,
+//! * `[3]` keeps the third element matched by the step under the same parent,
+//! * `[pPr/pStyle]` keeps elements having the relative path, `[@w:val]` elements having the attribute,
+//! * `[pPr/pStyle=Heading1]` compares the `val` attribute or the text of the element at the end of the path, while
+//!   `[@w:val='center']` compares the attribute. Values can be quoted with `'` or `"`.
+//!
+//! ```
+//! use oox::xml::XmlNode;
+//! use std::str::FromStr;
+//!
+//! let xml = r#"<w:document><w:body>
+//!     <w:p><w:pPr><w:pStyle w:val="Heading1" /></w:pPr><w:r><w:t>Title</w:t></w:r></w:p>
+//!     <w:p><w:r><w:t>Text</w:t></w:r></w:p>
+//! </w:body></w:document>"#;
+//! let document = XmlNode::from_str(xml).unwrap();
+//!
+//! let texts = document.select("body/p[pPr/pStyle=Heading1]/r/t").unwrap();
+//! assert_eq!(texts.len(), 1);
+//! assert_eq!(texts[0].text.as_deref(), Some("Title"));
+//! assert_eq!(document.select("//t").unwrap().len(), 2);
+//! ```
+
+use crate::{error::QueryParseError, xml::XmlNode};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+/// A parsed query, see the [module documentation](self) for the syntax.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    is_absolute: bool,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum NameTest {
+    Any,
+    LocalName(String),
+    QualifiedName(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    /// The one based position of the element among the ones matched under the same parent
+    Position(usize),
+    Path {
+        elements: Vec<NameTest>,
+        attribute: Option<String>,
+        value: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Step {
+    axis: Axis,
+    name: NameTest,
+    predicates: Vec<Predicate>,
+}
+
+impl NameTest {
+    fn parse(name: &str) -> Self {
+        match name {
+            "*" => NameTest::Any,
+            name if name.contains(':') => NameTest::QualifiedName(String::from(name)),
+            name => NameTest::LocalName(String::from(name)),
+        }
+    }
+
+    fn ignoring_prefix(self) -> Self {
+        match self {
+            NameTest::QualifiedName(name) => match name.split_once(':') {
+                Some((_, local_name)) => NameTest::LocalName(String::from(local_name)),
+                None => NameTest::LocalName(name),
+            },
+            name => name,
+        }
+    }
+
+    fn matches(&self, node: &XmlNode) -> bool {
+        match self {
+            NameTest::Any => true,
+            NameTest::LocalName(name) => node.local_name() == name,
+            NameTest::QualifiedName(name) => *node.name == **name,
+        }
+    }
+}
+
+impl Predicate {
+    fn parse(predicate: &str, position: usize) -> Result<Self, QueryParseError> {
+        let invalid = || QueryParseError::InvalidPredicate {
+            predicate: String::from(predicate),
+            position,
+        };
+
+        let predicate = predicate.trim();
+        if let Ok(index) = predicate.parse::<usize>() {
+            return match index {
+                0 => Err(invalid()),
+                index => Ok(Predicate::Position(index)),
+            };
+        }
+
+        let (path, value) = match predicate.split_once('=') {
+            Some((path, value)) => (path.trim(), Some(unquote(value.trim()))),
+            None => (predicate, None),
+        };
+        if path.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut segments: Vec<_> = path.split('/').map(str::trim).collect();
+        let attribute = match segments.last() {
+            Some(segment) if segment.starts_with('@') => {
+                let attribute = &segment[1..];
+                if attribute.is_empty() {
+                    return Err(invalid());
+                }
+                segments.pop();
+                Some(String::from(attribute))
+            }
+            _ => None,
+        };
+        if segments
+            .iter()
+            .any(|segment| segment.is_empty() || segment.starts_with('@'))
+        {
+            return Err(invalid());
+        }
+
+        Ok(Predicate::Path {
+            elements: segments.into_iter().map(NameTest::parse).collect(),
+            attribute,
+            value,
+        })
+    }
+
+    fn matches(&self, node: &XmlNode, position: usize) -> bool {
+        match self {
+            Predicate::Position(index) => *index == position,
+            Predicate::Path {
+                elements,
+                attribute,
+                value,
+            } => {
+                let mut nodes = vec![node];
+                for name in elements {
+                    nodes = nodes
+                        .into_iter()
+                        .flat_map(|node| node.child_nodes.iter().filter(move |child| name.matches(child)))
+                        .collect();
+                }
+
+                nodes.into_iter().any(|node| {
+                    let actual = match attribute {
+                        Some(attribute) => match attribute_value(node, attribute) {
+                            Some(actual) => Some(actual),
+                            None => return false,
+                        },
+                        None => attribute_value(node, "val").or(node.text.as_deref()),
+                    };
+                    match value {
+                        Some(value) => actual == Some(value.as_str()),
+                        None => true,
+                    }
+                })
+            }
+        }
+    }
+}
+
+/// Returns the value of an attribute by its qualified name, or by its local name if the name has no prefix.
+pub(crate) fn attribute_value<'a>(node: &'a XmlNode, name: &str) -> Option<&'a str> {
+    if name.contains(':') {
+        return node.attributes.get(name).map(String::as_str);
+    }
+
+    node.attributes
+        .iter()
+        .find(|(key, _)| key.rsplit(':').next() == Some(name) && !key.starts_with("xmlns"))
+        .map(|(_, value)| value.as_str())
+}
+
+fn unquote(value: &str) -> String {
+    let quoted = value.len() >= 2
+        && ((value.starts_with('\'') && value.ends_with('\'')) || (value.starts_with('"') && value.ends_with('"')));
+    match quoted {
+        true => String::from(&value[1..value.len() - 1]),
+        false => String::from(value),
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryParseError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        let is_absolute = query.starts_with('/');
+        let mut steps = Vec::new();
+        let mut axis = Axis::Child;
+        let mut position = 0;
+        let mut rest = query;
+        if is_absolute {
+            let separator_len = if rest.starts_with("//") { 2 } else { 1 };
+            if separator_len == 2 {
+                axis = Axis::Descendant;
+            }
+            rest = &rest[separator_len..];
+            position += separator_len;
+        }
+
+        loop {
+            let name_len = rest.find(['/', '[']).unwrap_or(rest.len());
+            let name = rest[..name_len].trim();
+            if name.is_empty() {
+                return Err(QueryParseError::EmptyStep { position });
+            }
+
+            let mut step = Step {
+                axis,
+                name: NameTest::parse(name),
+                predicates: Vec::new(),
+            };
+            rest = &rest[name_len..];
+            position += name_len;
+
+            while rest.starts_with('[') {
+                let end = find_predicate_end(rest).ok_or(QueryParseError::UnclosedPredicate { position })?;
+                step.predicates.push(Predicate::parse(&rest[1..end], position)?);
+                rest = &rest[end + 1..];
+                position += end + 1;
+            }
+            steps.push(step);
+
+            if rest.is_empty() {
+                break;
+            } else if rest.starts_with("//") {
+                axis = Axis::Descendant;
+                rest = &rest[2..];
+                position += 2;
+            } else if rest.starts_with('/') {
+                axis = Axis::Child;
+                rest = &rest[1..];
+                position += 1;
+            } else {
+                return Err(QueryParseError::UnexpectedCharacter { position });
+            }
+        }
+
+        Ok(Self { is_absolute, steps })
+    }
+}
+
+/// Returns the index of the `]` closing the predicate at the start of the string, skipping quoted values.
+fn find_predicate_end(predicate: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in predicate.char_indices().skip(1) {
+        match (quote, c) {
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (Some(open), c) if open == c => quote = None,
+            (None, ']') => return Some(index),
+            _ => (),
+        }
+    }
+
+    None
+}
+
+impl Query {
+    /// Matches element names by their local names only. Used when the prefixes of the query aren't bound to the
+    /// namespaces of the document, e.g. for the XPath of data bindings.
+    pub(crate) fn ignoring_prefixes(mut self) -> Self {
+        for step in &mut self.steps {
+            step.name = step.name.clone().ignoring_prefix();
+            for predicate in &mut step.predicates {
+                if let Predicate::Path { elements, .. } = predicate {
+                    *elements = elements.drain(..).map(NameTest::ignoring_prefix).collect();
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns the elements matching the query in document order.
+    pub fn select<'a>(&self, root: &'a XmlNode) -> Vec<&'a XmlNode> {
+        self.select_with_paths(root).into_iter().map(|(_, node)| node).collect()
+    }
+
+    /// Returns the elements matching the query in document order along with their paths, the indexes of the child
+    /// nodes leading from the root to the element, see [`XmlNode::node_at`].
+    pub fn select_with_paths<'a>(&self, root: &'a XmlNode) -> Vec<(Vec<usize>, &'a XmlNode)> {
+        let mut contexts = vec![(Vec::new(), root)];
+        for (index, step) in self.steps.iter().enumerate() {
+            let mut matches = Vec::new();
+            let mut seen = HashSet::new();
+            for (path, node) in &contexts {
+                let candidates = if index == 0 && self.is_absolute {
+                    let mut candidates = vec![(path.clone(), *node)];
+                    if step.axis == Axis::Descendant {
+                        collect_descendants(node, &mut path.clone(), &mut candidates);
+                    }
+                    candidates
+                } else {
+                    let mut candidates = Vec::new();
+                    match step.axis {
+                        Axis::Child => candidates.extend(node.child_nodes.iter().enumerate().map(|(index, child)| {
+                            let mut child_path = path.clone();
+                            child_path.push(index);
+                            (child_path, child)
+                        })),
+                        Axis::Descendant => collect_descendants(node, &mut path.clone(), &mut candidates),
+                    }
+                    candidates
+                };
+
+                let mut step_matches: Vec<_> = candidates
+                    .into_iter()
+                    .filter(|(_, candidate)| step.name.matches(candidate))
+                    .collect();
+                for predicate in &step.predicates {
+                    // Positions are counted separately for the candidates of every parent, e.g. `//p[1]` selects
+                    // the first paragraph of the body and of every table cell.
+                    let mut positions: HashMap<Option<Vec<usize>>, usize> = HashMap::new();
+                    step_matches.retain(|(path, candidate)| {
+                        let position = positions
+                            .entry(path.split_last().map(|(_, parent)| parent.to_vec()))
+                            .or_default();
+                        *position += 1;
+                        predicate.matches(candidate, *position)
+                    });
+                }
+
+                for (path, node) in step_matches {
+                    if seen.insert(path.clone()) {
+                        matches.push((path, node));
+                    }
+                }
+            }
+
+            matches.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+            contexts = matches;
+        }
+
+        contexts
+    }
+}
+
+fn collect_descendants<'a>(node: &'a XmlNode, path: &mut Vec<usize>, descendants: &mut Vec<(Vec<usize>, &'a XmlNode)>) {
+    for (index, child) in node.child_nodes.iter().enumerate() {
+        path.push(index);
+        descendants.push((path.clone(), child));
+        collect_descendants(child, path, descendants);
+        path.pop();
+    }
+}
+
+impl XmlNode {
+    /// Returns the elements matching the query in document order, see the [`query`](crate::query) module for the
+    /// syntax.
+    pub fn select(&self, query: &str) -> Result<Vec<&XmlNode>, QueryParseError> {
+        Ok(query.parse::<Query>()?.select(self))
+    }
+
+    /// Returns the descendant at the path of child indexes, or the node itself if the path is empty.
+    pub fn node_at(&self, path: &[usize]) -> Option<&XmlNode> {
+        path.iter().try_fold(self, |node, index| node.child_nodes.get(*index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_select() {
+        let xml = r#"<w:document><w:body>
+            <w:p><w:pPr><w:jc w:val="center" /></w:pPr><w:r><w:t>First</w:t></w:r><w:r><w:t>Second</w:t></w:r></w:p>
+            <w:tbl><w:tr><w:tc><w:p><w:r><w:t>Cell</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+            <w:p><w:pPr><w:jc w:val="left" /></w:pPr><w:r><w:t>Last</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let document = XmlNode::from_str(xml).unwrap();
+        let texts = |query: &str| -> Vec<String> {
+            document
+                .select(query)
+                .unwrap()
+                .into_iter()
+                .filter_map(|node| node.text.clone())
+                .collect()
+        };
+
+        assert_eq!(texts("body/p/r/t"), vec!["First", "Second", "Last"]);
+        assert_eq!(texts("//t"), vec!["First", "Second", "Cell", "Last"]);
+        assert_eq!(texts("/document//tc//w:t"), vec!["Cell"]);
+        assert_eq!(texts("body/p[pPr/jc/@w:val='left']//t"), vec!["Last"]);
+        assert_eq!(texts("body/p[pPr/jc=center]/r[2]/t"), vec!["Second"]);
+        assert_eq!(texts("body/*[2]//t"), vec!["Cell"]);
+        assert_eq!(texts("body/p[r/t=\"Last\"]/r/t"), vec!["Last"]);
+        assert!(texts("body/p[pPr/pStyle]").is_empty());
+        assert_eq!(texts("//p[1]/r[1]/t"), vec!["First", "Cell"]);
+        assert_eq!(texts("//r[2]/t"), vec!["Second"]);
+
+        let query: Query = "//p[pPr]".parse().unwrap();
+        let paths: Vec<_> = query
+            .select_with_paths(&document)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, vec![vec![0, 0], vec![0, 2]]);
+        assert_eq!(document.node_at(&paths[1]).unwrap().local_name(), "p");
+
+        assert_eq!(
+            "body//".parse::<Query>(),
+            Err(QueryParseError::EmptyStep { position: 6 })
+        );
+        assert_eq!(
+            "body/p[r".parse::<Query>(),
+            Err(QueryParseError::UnclosedPredicate { position: 6 })
+        );
+        assert!("p[0]".parse::<Query>().is_err());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{error::MissingAttributeError, xml::XmlNode};
+use std::{collections::HashMap, io::Read, str::FromStr};
+use zip::read::ZipFile;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub const CONTENT_TYPES_PART_NAME: &str = "[Content_Types].xml";
+
+/// The content types of the parts of a package (`[Content_Types].xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ContentTypes {
+    /// Content types by lower case file extension (`Default` elements).
+    pub defaults: HashMap<String, String>,
+    /// Content types by lower case part name without the leading slash (`Override` elements).
+    pub overrides: HashMap<String, String>,
+}
+
+impl ContentTypes {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+
+        Self::from_xml_element(&XmlNode::from_str(xml_string.as_str())?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+
+        for child_node in &xml_node.child_nodes {
+            let (key_attr, map) = match child_node.local_name() {
+                "Default" => ("Extension", &mut instance.defaults),
+                "Override" => ("PartName", &mut instance.overrides),
+                _ => continue,
+            };
+
+            let key = child_node
+                .attributes
+                .get(key_attr)
+                .ok_or_else(|| MissingAttributeError::new(child_node.name.clone(), key_attr))?;
+
+            let content_type = child_node
+                .attributes
+                .get("ContentType")
+                .ok_or_else(|| MissingAttributeError::new(child_node.name.clone(), "ContentType"))?;
+
+            map.insert(key.trim_start_matches('/').to_lowercase(), content_type.clone());
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns the content type of a part. Part names are compared case insensitively, with or without the leading
+    /// slash. If the part has no override, the default content type of its extension is returned.
+    pub fn content_type_for<T: AsRef<str>>(&self, part_name: T) -> Option<&str> {
+        let part_name = part_name.as_ref().trim_start_matches('/').to_lowercase();
+        if let Some(content_type) = self.overrides.get(&part_name) {
+            return Some(content_type);
+        }
+
+        let file_name = part_name.rsplit('/').next()?;
+        let extension = &file_name[file_name.rfind('.')? + 1..];
+        self.defaults.get(extension).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_content_types_from_xml() {
+        let xml = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+            <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+            <Default Extension="PNG" ContentType="image/png" />
+            <Override PartName="/word/document.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml" />
+            <Override PartName="/word/media/image1.png" ContentType="image/x-png" />
+        </Types>"#;
+
+        let content_types = ContentTypes::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(
+            content_types.content_type_for("word/document.xml"),
+            Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml")
+        );
+        assert_eq!(
+            content_types.content_type_for("/word/media/image1.png"),
+            Some("image/x-png")
+        );
+        assert_eq!(
+            content_types.content_type_for("word/media/image2.png"),
+            Some("image/png")
+        );
+        assert_eq!(content_types.content_type_for("word/media/image3.emf"), None);
+        assert_eq!(content_types.content_type_for("word/media"), None);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    error::MissingAttributeError,
+    query::{attribute_value, Query},
+    shared::relationship::{join_part_path, Relationships},
+    xml::XmlNode,
+};
+use std::collections::HashMap;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub const CUSTOM_XML_PROPS_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps";
+
+/// The custom XML data properties part (`ds:datastoreItem`) of a custom XML data storage part.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataStoreItem {
+    /// The GUID of the custom XML data. Referenced by the `storeItemID` of data bindings.
+    pub item_id: String,
+    pub schema_refs: Vec<String>,
+}
+
+impl DataStoreItem {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let item_id = xml_node
+            .attributes
+            .iter()
+            .find(|(attr, _)| local_name_of(attr) == "itemID")
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "itemID"))?;
+
+        let schema_refs = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "schemaRefs")
+            .flat_map(|child_node| child_node.child_nodes.iter())
+            .filter(|child_node| child_node.local_name() == "schemaRef")
+            .map(|schema_ref| {
+                schema_ref
+                    .attributes
+                    .iter()
+                    .find(|(attr, _)| local_name_of(attr) == "uri")
+                    .map(|(_, value)| value.clone())
+                    .ok_or_else(|| MissingAttributeError::new(schema_ref.name.clone(), "uri").into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { item_id, schema_refs })
+    }
+}
+
+/// A custom XML data storage part (e.g. `customXml/item1.xml`) together with its properties part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomXmlPart {
+    pub part_name: String,
+    pub xml: XmlNode,
+    pub properties: Option<DataStoreItem>,
+}
+
+impl CustomXmlPart {
+    pub fn item_id(&self) -> Option<&str> {
+        self.properties.as_ref().map(|properties| properties.item_id.as_str())
+    }
+
+    /// Returns true if the given store item id matches the item id of this part. GUIDs are compared case
+    /// insensitively.
+    pub fn has_item_id<T: AsRef<str>>(&self, store_item_id: T) -> bool {
+        self.item_id()
+            .filter(|item_id| item_id.eq_ignore_ascii_case(store_item_id.as_ref()))
+            .is_some()
+    }
+
+    /// Evaluates an XPath expression against the custom XML data. See `evaluate_xpath` for the supported subset.
+    pub fn evaluate_xpath<T: AsRef<str>>(&self, xpath: T) -> Option<String> {
+        evaluate_xpath(&self.xml, xpath.as_ref())
+    }
+}
+
+/// Pairs custom XML data parts with their properties parts.
+///
+/// Properties are looked up through the relationships of the data parts. If a data part doesn't have a relationship
+/// part, the properties part with the same number is used (`customXml/item1.xml` and `customXml/itemProps1.xml`).
+pub fn pair_custom_xml_parts(
+    items: HashMap<String, XmlNode>,
+    mut properties: HashMap<String, DataStoreItem>,
+    relationships: &HashMap<String, Relationships>,
+) -> Vec<CustomXmlPart> {
+    let mut parts: Vec<CustomXmlPart> = items
+        .into_iter()
+        .map(|(part_name, xml)| {
+            let properties_part_name = match relationships.get(&part_name) {
+                Some(rels) => rels
+                    .relationships_of_type(CUSTOM_XML_PROPS_RELATION_TYPE)
+                    .next()
+                    .map(|rel| join_part_path(&part_name, &rel.target)),
+                None => part_name
+                    .rfind("/item")
+                    .map(|idx| format!("{}/itemProps{}", &part_name[..idx], &part_name[idx + 5..])),
+            };
+
+            let properties =
+                properties_part_name.and_then(|properties_part_name| properties.remove(&properties_part_name));
+
+            CustomXmlPart {
+                part_name,
+                xml,
+                properties,
+            }
+        })
+        .collect();
+
+    parts.sort_by(|lhs, rhs| lhs.part_name.cmp(&rhs.part_name));
+    parts
+}
+
+/// Evaluates a simple XPath expression, as used by data bindings, against an xml document and returns the string
+/// value of the first selected node.
+///
+/// The location path is evaluated as a [`Query`], so it has to be absolute, e.g. `/ns0:root/ns0:a[2]`. Namespace
+/// prefixes are not resolved, element and attribute names are compared by their local names. The last step can also
+/// be an attribute (`@name`) or `text()`.
+///
+/// ```
+/// use oox::{shared::customxml::evaluate_xpath, xml::XmlNode};
+/// use std::str::FromStr;
+///
+/// let xml = XmlNode::from_str(r#"<ns0:root><ns0:a>first</ns0:a><ns0:a id="2">second</ns0:a></ns0:root>"#).unwrap();
+/// assert_eq!(evaluate_xpath(&xml, "/ns0:root[1]/ns0:a[2]"), Some(String::from("second")));
+/// assert_eq!(evaluate_xpath(&xml, "/ns0:root/ns0:a[2]/@id"), Some(String::from("2")));
+/// assert_eq!(evaluate_xpath(&xml, "/ns0:root/ns0:b"), None);
+/// ```
+pub fn evaluate_xpath(root: &XmlNode, xpath: &str) -> Option<String> {
+    let xpath = xpath.trim();
+    if !xpath.starts_with('/') {
+        return None;
+    }
+
+    let (path, last_step) = xpath.rsplit_once('/')?;
+    let (path, attribute) = match last_step.strip_prefix('@') {
+        Some(attribute) => (path, Some(attribute)),
+        None => (xpath, None),
+    };
+    let (path, is_text) = match path.strip_suffix("/text()") {
+        Some(path) if attribute.is_none() => (path, true),
+        _ => (path, false),
+    };
+
+    let query = path.parse::<Query>().ok()?.ignoring_prefixes();
+    let node = query.select(root).into_iter().next()?;
+    match (attribute, is_text) {
+        (Some(attribute), _) => attribute_value(node, local_name_of(attribute)).map(String::from),
+        (None, true) => node.text.clone(),
+        (None, false) => Some(string_value(node)),
+    }
+}
+
+fn local_name_of(name: &str) -> &str {
+    match name.find(':') {
+        Some(idx) => &name[idx + 1..],
+        None => name,
+    }
+}
+
+fn string_value(xml_node: &XmlNode) -> String {
+    let mut value = xml_node.text.clone().unwrap_or_default();
+    for child_node in &xml_node.child_nodes {
+        value.push_str(&string_value(child_node));
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::relationship::Relationship;
+    use std::str::FromStr;
+
+    const ITEM_XML: &str = r#"<ns0:employees xmlns:ns0="urn:example">
+        <ns0:employee id="1">
+            <ns0:name>John Doe</ns0:name>
+            <ns0:hireDate>2020-01-15</ns0:hireDate>
+        </ns0:employee>
+        <ns0:employee id="2">
+            <ns0:name>Jane Doe</ns0:name>
+        </ns0:employee>
+    </ns0:employees>"#;
+
+    const ITEM_PROPS_XML: &str = r#"<ds:datastoreItem ds:itemID="{1E9F5C1B-7A4B-4B8C-9E2D-3C4B5A6D7E8F}"
+        xmlns:ds="http://schemas.openxmlformats.org/officeDocument/2006/customXml">
+        <ds:schemaRefs>
+            <ds:schemaRef ds:uri="urn:example"/>
+        </ds:schemaRefs>
+    </ds:datastoreItem>"#;
+
+    #[test]
+    pub fn test_data_store_item_from_xml() {
+        let item = DataStoreItem::from_xml_element(&XmlNode::from_str(ITEM_PROPS_XML).unwrap()).unwrap();
+        assert_eq!(
+            item,
+            DataStoreItem {
+                item_id: String::from("{1E9F5C1B-7A4B-4B8C-9E2D-3C4B5A6D7E8F}"),
+                schema_refs: vec![String::from("urn:example")],
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_evaluate_xpath() {
+        let xml = XmlNode::from_str(ITEM_XML).unwrap();
+        assert_eq!(
+            evaluate_xpath(&xml, "/ns0:employees[1]/ns0:employee[1]/ns0:name[1]"),
+            Some(String::from("John Doe"))
+        );
+        assert_eq!(
+            evaluate_xpath(&xml, "/x:employees/x:employee[2]/x:name"),
+            Some(String::from("Jane Doe"))
+        );
+        assert_eq!(evaluate_xpath(&xml, "/ns0:employees/*[2]/@id"), Some(String::from("2")));
+        assert_eq!(
+            evaluate_xpath(&xml, "/ns0:employees/ns0:employee/ns0:hireDate/text()"),
+            Some(String::from("2020-01-15"))
+        );
+        assert_eq!(evaluate_xpath(&xml, "/ns0:employees/ns0:employee[3]"), None);
+        assert_eq!(evaluate_xpath(&xml, "/ns0:other"), None);
+        assert_eq!(evaluate_xpath(&xml, "ns0:employees"), None);
+    }
+
+    #[test]
+    pub fn test_pair_custom_xml_parts() {
+        let mut items = HashMap::new();
+        items.insert(
+            String::from("customXml/item1.xml"),
+            XmlNode::from_str(ITEM_XML).unwrap(),
+        );
+        items.insert(String::from("customXml/item2.xml"), XmlNode::new("root"));
+
+        let mut properties = HashMap::new();
+        properties.insert(
+            String::from("customXml/itemProps1.xml"),
+            DataStoreItem::from_xml_element(&XmlNode::from_str(ITEM_PROPS_XML).unwrap()).unwrap(),
+        );
+        properties.insert(
+            String::from("customXml/itemProps2.xml"),
+            DataStoreItem {
+                item_id: String::from("{00000000-0000-0000-0000-000000000002}"),
+                ..Default::default()
+            },
+        );
+
+        let mut relationships = HashMap::new();
+        relationships.insert(
+            String::from("customXml/item1.xml"),
+            Relationships::new(
+                "customXml/item1.xml",
+                vec![Relationship {
+                    id: String::from("rId1"),
+                    rel_type: String::from(CUSTOM_XML_PROPS_RELATION_TYPE),
+                    target: String::from("itemProps1.xml"),
+                    target_mode: None,
+                }],
+            ),
+        );
+
+        let parts = pair_custom_xml_parts(items, properties, &relationships);
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].has_item_id("{1e9f5c1b-7a4b-4b8c-9e2d-3c4b5a6d7e8f}"));
+        assert_eq!(parts[1].item_id(), Some("{00000000-0000-0000-0000-000000000002}"));
+        assert_eq!(
+            parts[0].evaluate_xpath("/ns0:employees/ns0:employee[2]/ns0:name"),
+            Some(String::from("Jane Doe"))
+        );
+    }
+}
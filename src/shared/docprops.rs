@@ -25,9 +25,11 @@ impl AppInfo {
     pub fn from_zip_file(zip_file: &mut ZipFile) -> Result<Self> {
         let mut xml_string = String::new();
         zip_file.read_to_string(&mut xml_string)?;
-        let root = XmlNode::from_str(&xml_string)?;
+        Self::from_xml_element(&XmlNode::from_str(&xml_string)?)
+    }
 
-        Ok(root
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        Ok(xml_node
             .child_nodes
             .iter()
             .fold(Default::default(), |mut instance: Self, child_node| {
@@ -63,9 +65,12 @@ impl Core {
     pub fn from_zip_file(zip_file: &mut ZipFile) -> Result<Self> {
         let mut xml_string = String::new();
         zip_file.read_to_string(&mut xml_string)?;
-        let root = XmlNode::from_str(&xml_string)?;
+        Self::from_xml_element(&XmlNode::from_str(&xml_string)?)
+    }
 
-        root.child_nodes
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        xml_node
+            .child_nodes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, child_node| {
                 match child_node.local_name() {
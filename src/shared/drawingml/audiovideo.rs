@@ -97,7 +97,7 @@ impl AudioFile {
         let mut content_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "r:link" => link = Some(value.clone()),
                 "contentType" => content_type = Some(value.clone()),
                 _ => (),
@@ -128,7 +128,7 @@ impl AudioCDTime {
         let mut time = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "track" => track = Some(value.parse()?),
                 "time" => time = Some(value.parse()?),
                 _ => (),
@@ -190,7 +190,7 @@ impl VideoFile {
         let mut content_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "r:link" => link = Some(value.clone()),
                 "contentType" => content_type = Some(value.clone()),
                 _ => (),
@@ -222,7 +222,7 @@ impl EmbeddedWAVAudioFile {
         let mut name = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "r:embed" => embed_rel_id = Some(value.clone()),
                 "name" => name = Some(value.clone()),
                 _ => (),
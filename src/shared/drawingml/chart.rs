@@ -0,0 +1,627 @@
+use crate::{error::ChartDataError, xml::escape_xml};
+use std::{
+    fmt::Write as FmtWrite,
+    io::{Cursor, Write},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub const CHART_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
+pub const PACKAGE_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/package";
+pub const CHART_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.drawingml.chart+xml";
+pub const WORKBOOK_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+pub const CHART_GRAPHIC_DATA_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
+
+const SHEET_NAME: &str = "Sheet1";
+const CATEGORY_AXIS_ID: u32 = 111_111_111;
+const VALUE_AXIS_ID: u32 = 222_222_222;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartType {
+    /// Clustered column chart
+    Bar,
+    Line,
+    /// Pie chart. Only the first series is displayed by consuming applications.
+    Pie,
+}
+
+/// Specifies the kind of document the graphic frame hosting the chart is created for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartHost {
+    /// An inline `w:drawing` element which can be placed in a WordprocessingML run.
+    Docx,
+    /// A `p:graphicFrame` element which can be placed in a PresentationML shape tree. The offset is in EMUs.
+    Pptx { x: i64, y: i64 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+/// The generated parts of a chart.
+///
+/// The chart part should be stored in the package (e.g. as `word/charts/chart1.xml` or `ppt/charts/chart1.xml`) and
+/// referenced from the hosting part with a relationship of type `CHART_RELATION_TYPE`. The embedded workbook should
+/// be stored next to it (e.g. `word/embeddings/Microsoft_Excel_Worksheet1.xlsx`) and referenced from the chart part
+/// with a relationship of type `PACKAGE_RELATION_TYPE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartParts {
+    pub chart_xml: String,
+    pub graphic_frame_xml: String,
+    pub embedded_workbook: Vec<u8>,
+}
+
+/// Builds a chart from tabular data.
+///
+/// ```
+/// use oox::shared::drawingml::chart::{ChartBuilder, ChartHost, ChartType};
+///
+/// let parts = ChartBuilder::new(ChartType::Bar)
+///     .title("Sales")
+///     .categories(vec!["Q1", "Q2"])
+///     .series("2019", vec![10.0, 12.5])
+///     .series("2020", vec![11.0, 14.0])
+///     .build(ChartHost::Docx, "rId5", "rId1", 1)
+///     .unwrap();
+///
+/// assert!(parts.chart_xml.contains("<c:barChart>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartBuilder {
+    chart_type: ChartType,
+    title: Option<String>,
+    categories: Vec<String>,
+    series: Vec<ChartSeries>,
+    width: i64,
+    height: i64,
+}
+
+impl ChartBuilder {
+    pub fn new(chart_type: ChartType) -> Self {
+        Self {
+            chart_type,
+            title: None,
+            categories: Vec::new(),
+            series: Vec::new(),
+            width: 5_486_400,
+            height: 3_200_400,
+        }
+    }
+
+    pub fn title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn categories<T: Into<String>>(mut self, categories: Vec<T>) -> Self {
+        self.categories = categories.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn series<T: Into<String>>(mut self, name: T, values: Vec<f64>) -> Self {
+        self.series.push(ChartSeries {
+            name: name.into(),
+            values,
+        });
+        self
+    }
+
+    /// Sets the size of the graphic frame in EMUs.
+    pub fn size(mut self, width: i64, height: i64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Creates the chart part, the graphic frame hosting it and the embedded workbook containing the data.
+    ///
+    /// `chart_rel_id` is the relationship id of the chart part in the hosting part, `workbook_rel_id` is the
+    /// relationship id of the embedded workbook in the chart part and `shape_id` is the unique drawing object id of
+    /// the graphic frame.
+    pub fn build(
+        &self,
+        host: ChartHost,
+        chart_rel_id: &str,
+        workbook_rel_id: &str,
+        shape_id: u32,
+    ) -> Result<ChartParts> {
+        self.validate()?;
+
+        Ok(ChartParts {
+            chart_xml: self.chart_xml(workbook_rel_id),
+            graphic_frame_xml: self.graphic_frame_xml(host, chart_rel_id, shape_id),
+            embedded_workbook: self.workbook()?,
+        })
+    }
+
+    fn validate(&self) -> ::std::result::Result<(), ChartDataError> {
+        if self.series.is_empty() {
+            return Err(ChartDataError::NoSeries);
+        }
+
+        for series in &self.series {
+            if series.values.len() != self.categories.len() {
+                return Err(ChartDataError::SeriesLengthMismatch {
+                    series: series.name.clone(),
+                    expected: self.categories.len(),
+                    actual: series.values.len(),
+                });
+            }
+
+            // NaN and infinite values have no representation in the chart part or the workbook
+            if let Some(index) = series.values.iter().position(|value| !value.is_finite()) {
+                return Err(ChartDataError::NonFiniteValue {
+                    series: series.name.clone(),
+                    index,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn chart_xml(&self, workbook_rel_id: &str) -> String {
+        let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
+        xml.push_str(concat!(
+            r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" "#,
+            r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<c:roundedCorners val="0"/><c:chart>"#,
+        ));
+
+        match &self.title {
+            Some(title) => {
+                let _ = write!(
+                    xml,
+                    r#"<c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>{}</a:t></a:r></a:p></c:rich></c:tx><c:overlay val="0"/></c:title><c:autoTitleDeleted val="0"/>"#,
+                    escape_xml(title)
+                );
+            }
+            None => xml.push_str(r#"<c:autoTitleDeleted val="1"/>"#),
+        }
+
+        xml.push_str("<c:plotArea><c:layout/>");
+        match self.chart_type {
+            ChartType::Bar => {
+                xml.push_str(r#"<c:barChart><c:barDir val="col"/><c:grouping val="clustered"/><c:varyColors val="0"/>"#)
+            }
+            ChartType::Line => xml.push_str(r#"<c:lineChart><c:grouping val="standard"/><c:varyColors val="0"/>"#),
+            ChartType::Pie => xml.push_str(r#"<c:pieChart><c:varyColors val="1"/>"#),
+        }
+
+        for (idx, series) in self.series.iter().enumerate() {
+            self.write_series(&mut xml, idx, series);
+        }
+
+        match self.chart_type {
+            ChartType::Bar => {
+                let _ = write!(
+                    xml,
+                    r#"<c:gapWidth val="150"/><c:axId val="{}"/><c:axId val="{}"/></c:barChart>"#,
+                    CATEGORY_AXIS_ID, VALUE_AXIS_ID
+                );
+            }
+            ChartType::Line => {
+                let _ = write!(
+                    xml,
+                    r#"<c:marker val="1"/><c:axId val="{}"/><c:axId val="{}"/></c:lineChart>"#,
+                    CATEGORY_AXIS_ID, VALUE_AXIS_ID
+                );
+            }
+            ChartType::Pie => xml.push_str(r#"<c:firstSliceAng val="0"/></c:pieChart>"#),
+        }
+
+        if self.chart_type != ChartType::Pie {
+            let _ = write!(
+                xml,
+                concat!(
+                    r#"<c:catAx><c:axId val="{cat}"/><c:scaling><c:orientation val="minMax"/></c:scaling>"#,
+                    r#"<c:delete val="0"/><c:axPos val="b"/><c:crossAx val="{val}"/></c:catAx>"#,
+                    r#"<c:valAx><c:axId val="{val}"/><c:scaling><c:orientation val="minMax"/></c:scaling>"#,
+                    r#"<c:delete val="0"/><c:axPos val="l"/><c:majorGridlines/><c:crossAx val="{cat}"/></c:valAx>"#,
+                ),
+                cat = CATEGORY_AXIS_ID,
+                val = VALUE_AXIS_ID,
+            );
+        }
+
+        let _ = write!(
+            xml,
+            concat!(
+                r#"</c:plotArea><c:legend><c:legendPos val="r"/><c:overlay val="0"/></c:legend>"#,
+                r#"<c:plotVisOnly val="1"/></c:chart><c:externalData r:id="{}"><c:autoUpdate val="0"/></c:externalData>"#,
+                "</c:chartSpace>",
+            ),
+            escape_xml(workbook_rel_id)
+        );
+
+        xml
+    }
+
+    fn write_series(&self, xml: &mut String, idx: usize, series: &ChartSeries) {
+        let column = column_name(idx + 1);
+        let last_row = self.categories.len() + 1;
+
+        let _ = write!(
+            xml,
+            r#"<c:ser><c:idx val="{idx}"/><c:order val="{idx}"/><c:tx><c:strRef><c:f>{sheet}!${column}$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>{name}</c:v></c:pt></c:strCache></c:strRef></c:tx>"#,
+            idx = idx,
+            sheet = SHEET_NAME,
+            column = column,
+            name = escape_xml(&series.name),
+        );
+
+        let _ = write!(
+            xml,
+            r#"<c:cat><c:strRef><c:f>{}!$A$2:$A${}</c:f><c:strCache><c:ptCount val="{}"/>"#,
+            SHEET_NAME,
+            last_row,
+            self.categories.len()
+        );
+        for (point_idx, category) in self.categories.iter().enumerate() {
+            let _ = write!(
+                xml,
+                r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#,
+                point_idx,
+                escape_xml(category)
+            );
+        }
+        xml.push_str("</c:strCache></c:strRef></c:cat>");
+
+        let _ = write!(
+            xml,
+            r#"<c:val><c:numRef><c:f>{sheet}!${column}$2:${column}${last_row}</c:f><c:numCache><c:formatCode>General</c:formatCode><c:ptCount val="{count}"/>"#,
+            sheet = SHEET_NAME,
+            column = column,
+            last_row = last_row,
+            count = series.values.len(),
+        );
+        for (point_idx, value) in series.values.iter().enumerate() {
+            let _ = write!(xml, r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, point_idx, value);
+        }
+        xml.push_str("</c:numCache></c:numRef></c:val>");
+
+        if self.chart_type == ChartType::Line {
+            xml.push_str(r#"<c:smooth val="0"/>"#);
+        }
+
+        xml.push_str("</c:ser>");
+    }
+
+    fn graphic_frame_xml(&self, host: ChartHost, chart_rel_id: &str, shape_id: u32) -> String {
+        let name = format!("Chart {}", shape_id);
+        let graphic = format!(
+            concat!(
+                r#"<a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+                r#"<a:graphicData uri="{uri}"><c:chart xmlns:c="{uri}" "#,
+                r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="{rel_id}"/>"#,
+                "</a:graphicData></a:graphic>",
+            ),
+            uri = CHART_GRAPHIC_DATA_URI,
+            rel_id = escape_xml(chart_rel_id),
+        );
+
+        match host {
+            ChartHost::Docx => format!(
+                concat!(
+                    r#"<w:drawing xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" "#,
+                    r#"xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing">"#,
+                    r#"<wp:inline distT="0" distB="0" distL="0" distR="0"><wp:extent cx="{cx}" cy="{cy}"/>"#,
+                    r#"<wp:effectExtent l="0" t="0" r="0" b="0"/><wp:docPr id="{id}" name="{name}"/>"#,
+                    "<wp:cNvGraphicFramePr/>{graphic}</wp:inline></w:drawing>",
+                ),
+                cx = self.width,
+                cy = self.height,
+                id = shape_id,
+                name = name,
+                graphic = graphic,
+            ),
+            ChartHost::Pptx { x, y } => format!(
+                concat!(
+                    r#"<p:graphicFrame xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" "#,
+                    r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#,
+                    r#"<p:nvGraphicFramePr><p:cNvPr id="{id}" name="{name}"/><p:cNvGraphicFramePr/><p:nvPr/>"#,
+                    r#"</p:nvGraphicFramePr><p:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="{cx}" cy="{cy}"/></p:xfrm>"#,
+                    "{graphic}</p:graphicFrame>",
+                ),
+                id = shape_id,
+                name = name,
+                x = x,
+                y = y,
+                cx = self.width,
+                cy = self.height,
+                graphic = graphic,
+            ),
+        }
+    }
+
+    fn workbook(&self) -> Result<Vec<u8>> {
+        let mut sheet_xml = String::from(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+        ));
+
+        let header = std::iter::once(String::new()).chain(self.series.iter().map(|series| series.name.clone()));
+        write_string_row(&mut sheet_xml, 1, header);
+
+        for (idx, category) in self.categories.iter().enumerate() {
+            let row = idx + 2;
+            let _ = write!(
+                sheet_xml,
+                r#"<row r="{row}"><c r="A{row}" t="inlineStr"><is><t>{}</t></is></c>"#,
+                escape_xml(category),
+                row = row
+            );
+            for (series_idx, series) in self.series.iter().enumerate() {
+                let _ = write!(
+                    sheet_xml,
+                    r#"<c r="{}{}"><v>{}</v></c>"#,
+                    column_name(series_idx + 1),
+                    row,
+                    series.values[idx]
+                );
+            }
+            sheet_xml.push_str("</row>");
+        }
+        sheet_xml.push_str("</sheetData></worksheet>");
+
+        let files = [
+            (
+                "[Content_Types].xml",
+                String::from(concat!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+                    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+                    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+                    r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+                    r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+                    "</Types>",
+                )),
+            ),
+            (
+                "_rels/.rels",
+                String::from(concat!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+                    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+                    "</Relationships>",
+                )),
+            ),
+            (
+                "xl/workbook.xml",
+                format!(
+                    concat!(
+                        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                        r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+                        r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+                        r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+                    ),
+                    SHEET_NAME
+                ),
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                String::from(concat!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+                    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+                    "</Relationships>",
+                )),
+            ),
+            ("xl/worksheets/sheet1.xml", sheet_xml),
+        ];
+
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        for (file_name, content) in files.iter() {
+            zip_writer.start_file(*file_name, options)?;
+            zip_writer.write_all(content.as_bytes())?;
+        }
+
+        Ok(zip_writer.finish()?.into_inner())
+    }
+}
+
+fn write_string_row<I: Iterator<Item = String>>(xml: &mut String, row: usize, values: I) {
+    let _ = write!(xml, r#"<row r="{}">"#, row);
+    for (idx, value) in values.enumerate() {
+        if value.is_empty() {
+            continue;
+        }
+
+        let _ = write!(
+            xml,
+            r#"<c r="{}{}" t="inlineStr"><is><t>{}</t></is></c>"#,
+            column_name(idx),
+            row,
+            escape_xml(&value)
+        );
+    }
+    xml.push_str("</row>");
+}
+
+/// Returns the spreadsheet column name of a zero based column index, e.g. 0 is `A`, 26 is `AA`.
+fn column_name(mut idx: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (idx % 26) as u8);
+        if idx < 26 {
+            break;
+        }
+        idx = idx / 26 - 1;
+    }
+
+    name.iter().rev().map(|&c| c as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::wml::drawing::Inline, xml::XmlNode};
+    use std::{io::Read, str::FromStr};
+    use zip::ZipArchive;
+
+    fn builder_for_test(chart_type: ChartType) -> ChartBuilder {
+        ChartBuilder::new(chart_type)
+            .title("Sales & Revenue")
+            .categories(vec!["Q1", "Q2", "Q3"])
+            .series("2019", vec![10.0, 12.5, 8.0])
+            .series("2020", vec![11.0, 14.0, 9.5])
+    }
+
+    #[test]
+    pub fn test_column_name() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(2), "C");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    pub fn test_chart_xml() {
+        let parts = builder_for_test(ChartType::Line)
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap();
+
+        let chart_space = XmlNode::from_str(&parts.chart_xml).unwrap();
+        let plot_area = chart_space.child_nodes[1]
+            .child_nodes
+            .iter()
+            .find(|node| node.local_name() == "plotArea")
+            .unwrap();
+        let line_chart = plot_area
+            .child_nodes
+            .iter()
+            .find(|node| node.local_name() == "lineChart")
+            .unwrap();
+
+        let series: Vec<&XmlNode> = line_chart
+            .child_nodes
+            .iter()
+            .filter(|node| node.local_name() == "ser")
+            .collect();
+        assert_eq!(series.len(), 2);
+        assert!(parts.chart_xml.contains("<c:f>Sheet1!$C$2:$C$4</c:f>"));
+        assert!(parts.chart_xml.contains("<a:t>Sales &amp; Revenue</a:t>"));
+        assert!(parts.chart_xml.contains(r#"<c:externalData r:id="rId1">"#));
+        assert!(plot_area.child_nodes.iter().any(|node| node.local_name() == "valAx"));
+    }
+
+    #[test]
+    pub fn test_pie_chart_has_no_axes() {
+        let parts = builder_for_test(ChartType::Pie)
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap();
+
+        assert!(parts.chart_xml.contains("<c:pieChart>"));
+        assert!(!parts.chart_xml.contains("<c:catAx>"));
+    }
+
+    #[test]
+    pub fn test_docx_graphic_frame() {
+        let parts = builder_for_test(ChartType::Bar)
+            .size(100, 200)
+            .build(ChartHost::Docx, "rId4", "rId1", 7)
+            .unwrap();
+
+        let drawing = XmlNode::from_str(&parts.graphic_frame_xml).unwrap();
+        let inline = Inline::from_xml_element(&drawing.child_nodes[0]).unwrap();
+        assert_eq!(inline.extent.width, 100);
+        assert_eq!(inline.extent.height, 200);
+        assert_eq!(inline.doc_properties.id, 7);
+        assert_eq!(inline.graphic.graphic_data.uri, CHART_GRAPHIC_DATA_URI);
+    }
+
+    #[test]
+    pub fn test_pptx_graphic_frame() {
+        let parts = builder_for_test(ChartType::Bar)
+            .build(ChartHost::Pptx { x: 10, y: 20 }, "rId2", "rId1", 3)
+            .unwrap();
+
+        let graphic_frame = XmlNode::from_str(&parts.graphic_frame_xml).unwrap();
+        assert_eq!(graphic_frame.local_name(), "graphicFrame");
+        assert!(parts.graphic_frame_xml.contains(r#"<a:off x="10" y="20"/>"#));
+        assert!(parts.graphic_frame_xml.contains(r#"r:id="rId2""#));
+    }
+
+    #[test]
+    pub fn test_embedded_workbook() {
+        let parts = builder_for_test(ChartType::Bar)
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap();
+
+        let mut archive = ZipArchive::new(Cursor::new(parts.embedded_workbook)).unwrap();
+        assert!(archive.by_name("[Content_Types].xml").is_ok());
+        assert!(archive.by_name("xl/workbook.xml").is_ok());
+
+        let mut sheet_xml = String::new();
+        archive
+            .by_name("xl/worksheets/sheet1.xml")
+            .unwrap()
+            .read_to_string(&mut sheet_xml)
+            .unwrap();
+
+        let worksheet = XmlNode::from_str(&sheet_xml).unwrap();
+        let rows = &worksheet.child_nodes[0].child_nodes;
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0].child_nodes.len(), 2);
+        assert_eq!(rows[1].child_nodes.len(), 3);
+        assert!(sheet_xml.contains(r#"<c r="C4"><v>9.5</v></c>"#));
+    }
+
+    #[test]
+    pub fn test_invalid_chart_data() {
+        let error = ChartBuilder::new(ChartType::Bar)
+            .categories(vec!["Q1"])
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap_err();
+        assert_eq!(error.downcast_ref::<ChartDataError>(), Some(&ChartDataError::NoSeries));
+
+        let error = ChartBuilder::new(ChartType::Bar)
+            .categories(vec!["Q1", "Q2"])
+            .series("2019", vec![1.0])
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ChartDataError>(),
+            Some(&ChartDataError::SeriesLengthMismatch {
+                series: String::from("2019"),
+                expected: 2,
+                actual: 1,
+            })
+        );
+
+        let error = ChartBuilder::new(ChartType::Line)
+            .categories(vec!["Q1", "Q2"])
+            .series("2019", vec![1.0, 2.0])
+            .series("2020", vec![3.0, f64::NAN])
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ChartDataError>(),
+            Some(&ChartDataError::NonFiniteValue {
+                series: String::from("2020"),
+                index: 1,
+            })
+        );
+
+        let error = ChartBuilder::new(ChartType::Bar)
+            .categories(vec!["Q1"])
+            .series("2019", vec![f64::INFINITY])
+            .build(ChartHost::Docx, "rId4", "rId1", 1)
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ChartDataError>(),
+            Some(&ChartDataError::NonFiniteValue {
+                series: String::from("2019"),
+                index: 0,
+            })
+        );
+    }
+}
@@ -502,7 +502,7 @@ impl ScRgbColor {
         let mut opt_b = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "r" => opt_r = Some(value.parse()?),
                 "g" => opt_g = Some(value.parse()?),
                 "b" => opt_b = Some(value.parse()?),
@@ -584,7 +584,7 @@ impl HslColor {
         let mut opt_l = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "hue" => opt_h = Some(value.parse::<PositiveFixedAngle>()?),
                 "sat" => opt_s = Some(value.parse()?),
                 "lum" => opt_l = Some(value.parse()?),
@@ -629,7 +629,7 @@ impl SystemColor {
         let mut last_color = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "val" => opt_val = Some(value.parse()?),
                 "lastClr" => last_color = Some(parse_hex_color_rgb(value)?),
                 _ => (),
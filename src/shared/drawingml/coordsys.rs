@@ -27,7 +27,7 @@ impl Point2D {
         let mut y = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "x" => x = Some(value.parse()?),
                 "y" => y = Some(value.parse()?),
                 _ => (),
@@ -61,7 +61,7 @@ impl PositiveSize2D {
         let mut opt_height = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "cx" => opt_width = Some(value.parse::<PositiveCoordinate>()?),
                 "cy" => opt_height = Some(value.parse::<PositiveCoordinate>()?),
                 _ => (),
@@ -105,7 +105,7 @@ impl Transform2D {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (key, value)| {
-                match key.as_str() {
+                match key.as_ref() {
                     "rot" => instance.rotate_angle = Some(value.parse()?),
                     "flipH" => instance.flip_horizontal = Some(parse_xml_bool(value)?),
                     "flipV" => instance.flip_vertical = Some(parse_xml_bool(value)?),
@@ -172,7 +172,7 @@ impl GroupTransform2D {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "rot" => instance.rotate_angle = Some(value.parse()?),
                     "flipH" => instance.flip_horizontal = Some(parse_xml_bool(value)?),
                     "flipV" => instance.flip_vertical = Some(parse_xml_bool(value)?),
@@ -199,3 +199,152 @@ impl GroupTransform2D {
             })
     }
 }
+
+/// A 2D affine transform in the same matrix form SVG and PDF use. A point `(x, y)` is mapped to
+/// `(a * x + c * y + e, b * x + d * y + f)`.
+///
+/// DrawingML coordinates are y-down, so positive rotation angles turn clockwise, the same way as `rot` does.
+///
+/// ```
+/// use oox::shared::drawingml::coordsys::AffineTransform;
+///
+/// let transform = AffineTransform::scale(2.0, 2.0).then(&AffineTransform::translate(10.0, 0.0));
+/// assert_eq!(transform.apply(1.0, 1.0), (12.0, 2.0));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AffineTransform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for AffineTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl AffineTransform {
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    pub fn translate(dx: f64, dy: f64) -> Self {
+        Self {
+            e: dx,
+            f: dy,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self {
+            a: sx,
+            d: sy,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// A clockwise rotation around the origin.
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// Returns the transform that applies this transform first, then `other`.
+    pub fn then(&self, other: &Self) -> Self {
+        Self {
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f,
+        }
+    }
+
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.a * x + self.c * y + self.e, self.b * x + self.d * y + self.f)
+    }
+
+    /// Returns the transform that places a box of the given extents: the box is flipped and rotated around its center,
+    /// then moved to the offset.
+    fn placement(
+        offset: Option<Point2D>,
+        extents: Option<PositiveSize2D>,
+        rotate_angle: Option<Angle>,
+        flip_horizontal: Option<bool>,
+        flip_vertical: Option<bool>,
+    ) -> Self {
+        let (width, height) = extents.map_or((0.0, 0.0), |extents| (extents.width as f64, extents.height as f64));
+        let (x, y) = offset.map_or((0.0, 0.0), |offset| (offset.x as f64, offset.y as f64));
+        let flip = |flip: Option<bool>| if flip.unwrap_or(false) { -1.0 } else { 1.0 };
+
+        Self::translate(-width / 2.0, -height / 2.0)
+            .then(&Self::scale(flip(flip_horizontal), flip(flip_vertical)))
+            .then(&Self::rotate(f64::from(rotate_angle.unwrap_or(0)) / 60_000.0))
+            .then(&Self::translate(x + width / 2.0, y + height / 2.0))
+    }
+}
+
+impl Transform2D {
+    /// Returns the transform mapping the coordinate space of the object, where the object spans from `(0, 0)` to its
+    /// extents, to the coordinate space of its parent.
+    pub fn to_affine(&self) -> AffineTransform {
+        AffineTransform::placement(
+            self.offset,
+            self.extents,
+            self.rotate_angle,
+            self.flip_horizontal,
+            self.flip_vertical,
+        )
+    }
+}
+
+impl GroupTransform2D {
+    /// Returns the transform mapping the child coordinate space of the group to the coordinate space of its parent.
+    /// The child extents rectangle is scaled to the extents of the group, then placed like a single object.
+    pub fn to_affine(&self) -> AffineTransform {
+        let scale = |extent: Option<u64>, child_extent: Option<u64>| match (extent, child_extent) {
+            (Some(extent), Some(child_extent)) if child_extent != 0 => extent as f64 / child_extent as f64,
+            _ => 1.0,
+        };
+        let child_offset = self
+            .child_offset
+            .map_or((0.0, 0.0), |offset| (offset.x as f64, offset.y as f64));
+
+        AffineTransform::translate(-child_offset.0, -child_offset.1)
+            .then(&AffineTransform::scale(
+                scale(
+                    self.extents.map(|extents| extents.width),
+                    self.child_extents.map(|extents| extents.width),
+                ),
+                scale(
+                    self.extents.map(|extents| extents.height),
+                    self.child_extents.map(|extents| extents.height),
+                ),
+            ))
+            .then(&AffineTransform::placement(
+                self.offset,
+                self.extents,
+                self.rotate_angle,
+                self.flip_horizontal,
+                self.flip_vertical,
+            ))
+    }
+}
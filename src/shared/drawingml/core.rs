@@ -1,6 +1,8 @@
 use super::{
     audiovideo::EmbeddedWAVAudioFile,
     coordsys::{GroupTransform2D, Transform2D},
+    diagrams::DiagramRelationshipIds,
+    picture::Picture,
     shapedefs::Geometry,
     shapeprops::{
         EffectProperties, FillProperties, LineDashProperties, LineEndProperties, LineFillProperties, LineJoinProperties,
@@ -109,7 +111,7 @@ impl AnimationDgmBuildProperties {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "bld" => instance.build_type = Some(value.parse()?),
                     "rev" => instance.reverse = Some(parse_xml_bool(value)?),
                     _ => (),
@@ -144,7 +146,7 @@ impl AnimationChartBuildProperties {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "bld" => instance.build_type = Some(value.parse()?),
                     "animBg" => instance.animate_bg = Some(parse_xml_bool(value)?),
                     _ => (),
@@ -216,7 +218,7 @@ impl AnimationDgmElement {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "id" => instance.id = Some(value.clone()),
                     "bldStep" => instance.build_step = Some(value.parse()?),
                     _ => (),
@@ -557,7 +559,7 @@ impl NonVisualDrawingProps {
         let mut hyperlink_hover = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => opt_id = Some(value.parse()?),
                 "name" => opt_name = Some(value.clone()),
                 "descr" => description = Some(value.clone()),
@@ -672,7 +674,10 @@ impl Locking {
             .try_fold(Default::default(), Self::try_update_from_xml_attribute)
     }
 
-    pub fn try_update_from_xml_attribute(mut self, (attr, value): (&String, &String)) -> Result<Self> {
+    pub fn try_update_from_xml_attribute<K: AsRef<str> + ?Sized>(
+        mut self,
+        (attr, value): (&K, &String),
+    ) -> Result<Self> {
         match attr.as_ref() {
             "noGrp" => self.no_grouping = Some(parse_xml_bool(value)?),
             "noSelect" => self.no_select = Some(parse_xml_bool(value)?),
@@ -921,7 +926,7 @@ impl Connection {
         let mut shape_index = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => id = Some(value.parse()?),
                 "idx" => shape_index = Some(value.parse()?),
                 _ => (),
@@ -968,6 +973,12 @@ pub struct GraphicalObjectData {
     /// this tag. The URI is used to identify the correct 'server' that can process the contents of
     /// this tag.
     pub uri: String,
+    /// The pictures (`pic:pic`) stored in the graphic data, e.g. when the uri is
+    /// `http://schemas.openxmlformats.org/drawingml/2006/picture`.
+    pub pictures: Vec<Picture>,
+    /// The relationships to the parts of the diagram stored in the graphic data, e.g. when the uri is
+    /// `http://schemas.openxmlformats.org/drawingml/2006/diagram`.
+    pub diagram_relationship_ids: Option<DiagramRelationshipIds>,
 }
 
 impl GraphicalObjectData {
@@ -978,7 +989,25 @@ impl GraphicalObjectData {
             .ok_or_else(|| Box::<dyn Error>::from(MissingAttributeError::new(xml_node.name.clone(), "uri")))?
             .clone();
 
-        Ok(Self { uri })
+        let pictures = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "pic")
+            .map(Picture::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        let diagram_relationship_ids = xml_node
+            .child_nodes
+            .iter()
+            .find(|child_node| child_node.local_name() == "relIds")
+            .map(DiagramRelationshipIds::from_xml_element)
+            .transpose()?;
+
+        Ok(Self {
+            uri,
+            pictures,
+            diagram_relationship_ids,
+        })
     }
 }
 
@@ -1380,7 +1409,7 @@ impl Hyperlink {
                 ..Default::default()
             },
             |mut instance, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "r:id" => instance.relationship_id = Some(value.clone()),
                     "invalidUrl" => instance.invalid_url = Some(value.clone()),
                     "action" => instance.action = Some(value.clone()),
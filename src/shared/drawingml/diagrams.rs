@@ -1,14 +1,26 @@
 use super::{
-    core::LineProperties,
+    core::{LineProperties, TextBody},
     shapeprops::{EffectProperties, FillProperties},
+    text::runformatting::TextRun,
 };
 use crate::{
-    xml::XmlNode,
+    error::MissingAttributeError,
+    shared::relationship::RelationshipId,
+    xml::{zip_file_to_xml_node, XmlNode},
     xsdtypes::{XsdChoice, XsdType},
 };
+use std::collections::HashMap;
+use zip::read::ZipFile;
 
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
+pub const DIAGRAM_GRAPHIC_DATA_URI: &str = "http://schemas.openxmlformats.org/drawingml/2006/diagram";
+pub const DIAGRAM_DATA_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/diagramData";
+
+/// The identifier of a point or connection of a diagram data model. It's either an integer or a GUID.
+pub type ModelId = String;
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct BackgroundFormatting {
     pub fill: Option<FillProperties>,
@@ -58,6 +70,382 @@ impl WholeE2oFormatting {
     }
 }
 
+/// The relationships of a diagram to its parts (`dgm:relIds`), stored in the graphic data of the graphic frame
+/// displaying the diagram.
+///
+/// # Xml example
+///
+/// ```xml
+/// <a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/diagram">
+///   <dgm:relIds r:dm="rId2" r:lo="rId3" r:qs="rId4" r:cs="rId5"/>
+/// </a:graphicData>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramRelationshipIds {
+    /// Specifies the relationship id of the data model part.
+    pub data: RelationshipId,
+    /// Specifies the relationship id of the layout definition part.
+    pub layout: RelationshipId,
+    /// Specifies the relationship id of the style definition part.
+    pub quick_style: RelationshipId,
+    /// Specifies the relationship id of the color transform part.
+    pub colors: RelationshipId,
+}
+
+impl DiagramRelationshipIds {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut data = None;
+        let mut layout = None;
+        let mut quick_style = None;
+        let mut colors = None;
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "r:dm" => data = Some(value.clone()),
+                "r:lo" => layout = Some(value.clone()),
+                "r:qs" => quick_style = Some(value.clone()),
+                "r:cs" => colors = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            data: data.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:dm"))?,
+            layout: layout.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:lo"))?,
+            quick_style: quick_style.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:qs"))?,
+            colors: colors.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "r:cs"))?,
+        })
+    }
+}
+
+/// This simple type specifies the type of a point of the data model.
+#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+pub enum PointType {
+    /// A node holding the content of the diagram.
+    #[strum(serialize = "node")]
+    Node,
+    /// An assistant node, e.g. an assistant in an organization chart.
+    #[strum(serialize = "asst")]
+    Assistant,
+    /// The root of the data model representing the whole diagram.
+    #[strum(serialize = "doc")]
+    Document,
+    /// A presentation point, i.e. a shape generated by the layout.
+    #[strum(serialize = "pres")]
+    Presentation,
+    /// The transition between a parent and a child node.
+    #[strum(serialize = "parTrans")]
+    ParentTransition,
+    /// The transition between sibling nodes.
+    #[strum(serialize = "sibTrans")]
+    SiblingTransition,
+}
+
+/// This simple type specifies the type of a connection of the data model.
+#[derive(Debug, Clone, Copy, EnumString, PartialEq)]
+pub enum ConnectionType {
+    /// The destination point is a child of the source point.
+    #[strum(serialize = "parOf")]
+    ParentOf,
+    /// The destination presentation point displays the source point.
+    #[strum(serialize = "presOf")]
+    PresentationOf,
+    /// The destination presentation point is a child of the source presentation point.
+    #[strum(serialize = "presParOf")]
+    PresentationParentOf,
+    #[strum(serialize = "unknownRelationship")]
+    UnknownRelationship,
+}
+
+/// This element specifies a point of the data model, i.e. a node or transition of the diagram, a presentation point
+/// generated by the layout, or the document point at the root.
+///
+/// # Xml example
+///
+/// ```xml
+/// <dgm:pt modelId="{4A6D6A39-3F36-4A7B-9D1F-3B4A4DB0A8B1}">
+///   <dgm:prSet phldrT="[Text]"/>
+///   <dgm:spPr/>
+///   <dgm:t>
+///     <a:bodyPr/>
+///     <a:lstStyle/>
+///     <a:p><a:r><a:t>Step 1</a:t></a:r></a:p>
+///   </dgm:t>
+/// </dgm:pt>
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Point {
+    pub model_id: ModelId,
+    /// Defaults to PointType::Node
+    pub point_type: Option<PointType>,
+    /// Specifies the connection of a transition point.
+    pub connection_id: Option<ModelId>,
+    /// Specifies the placeholder text displayed while the point has no text (`dgm:prSet/@phldrT`).
+    pub placeholder_text: Option<String>,
+    pub text_body: Option<TextBody>,
+}
+
+impl Point {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        let mut model_id = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "modelId" => model_id = Some(value.clone()),
+                "type" => instance.point_type = Some(value.parse()?),
+                "cxnId" => instance.connection_id = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        instance.model_id = model_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "modelId"))?;
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "prSet" => instance.placeholder_text = child_node.attributes.get("phldrT").cloned(),
+                "t" => instance.text_body = Some(TextBody::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns whether the point holds content of the diagram, i.e. it's a node or an assistant node.
+    pub fn is_content(&self) -> bool {
+        matches!(
+            self.point_type.unwrap_or(PointType::Node),
+            PointType::Node | PointType::Assistant
+        )
+    }
+
+    /// Returns the text of the point with the paragraphs separated by newlines.
+    pub fn text(&self) -> String {
+        self.text_body.as_ref().map(text_body_text).unwrap_or_default()
+    }
+}
+
+/// This element specifies a connection between two points of the data model.
+///
+/// # Xml example
+///
+/// ```xml
+/// <dgm:cxn modelId="{5}" srcId="{0}" destId="{1}" srcOrd="0" destOrd="0" parTransId="{6}" sibTransId="{7}"/>
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Connection {
+    pub model_id: ModelId,
+    /// Defaults to ConnectionType::ParentOf
+    pub connection_type: Option<ConnectionType>,
+    pub source_id: ModelId,
+    pub destination_id: ModelId,
+    /// Specifies the position of the destination among the children of the source.
+    pub source_order: u32,
+    pub destination_order: u32,
+    pub parent_transition_id: Option<ModelId>,
+    pub sibling_transition_id: Option<ModelId>,
+    /// Specifies the presentation point that created the connection.
+    pub presentation_id: Option<String>,
+}
+
+impl Connection {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        let mut model_id = None;
+        let mut source_id = None;
+        let mut destination_id = None;
+        let mut source_order = None;
+        let mut destination_order = None;
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "modelId" => model_id = Some(value.clone()),
+                "type" => instance.connection_type = Some(value.parse()?),
+                "srcId" => source_id = Some(value.clone()),
+                "destId" => destination_id = Some(value.clone()),
+                "srcOrd" => source_order = Some(value.parse()?),
+                "destOrd" => destination_order = Some(value.parse()?),
+                "parTransId" => instance.parent_transition_id = Some(value.clone()),
+                "sibTransId" => instance.sibling_transition_id = Some(value.clone()),
+                "presId" => instance.presentation_id = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        instance.model_id = model_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "modelId"))?;
+        instance.source_id = source_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "srcId"))?;
+        instance.destination_id =
+            destination_id.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "destId"))?;
+        instance.source_order =
+            source_order.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "srcOrd"))?;
+        instance.destination_order =
+            destination_order.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "destOrd"))?;
+
+        Ok(instance)
+    }
+}
+
+/// The text of a content point of a diagram with its depth in the hierarchy of the diagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagramText {
+    pub model_id: ModelId,
+    /// The 0-based depth of the point, top level points of the diagram are at level 0.
+    pub level: usize,
+    pub text: String,
+}
+
+/// The data model of a diagram (SmartArt), stored in the diagram data part (`dgm:dataModel`). It describes the
+/// content of the diagram as a tree of points connected by parent-child connections. The layout, style and color parts
+/// only affect the appearance of the diagram.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DataModel {
+    pub points: Vec<Point>,
+    pub connections: Vec<Connection>,
+    pub background: Option<BackgroundFormatting>,
+    pub whole: Option<WholeE2oFormatting>,
+}
+
+impl DataModel {
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        Self::from_xml_element(&zip_file_to_xml_node(zip_file)?)
+    }
+
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        let mut instance: Self = Default::default();
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "ptLst" => {
+                    instance.points = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|point_node| point_node.local_name() == "pt")
+                        .map(Point::from_xml_element)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                "cxnLst" => {
+                    instance.connections = child_node
+                        .child_nodes
+                        .iter()
+                        .filter(|connection_node| connection_node.local_name() == "cxn")
+                        .map(Connection::from_xml_element)
+                        .collect::<Result<Vec<_>>>()?
+                }
+                "bg" => instance.background = Some(BackgroundFormatting::from_xml_element(child_node)?),
+                "whole" => instance.whole = Some(WholeE2oFormatting::from_xml_element(child_node)?),
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    pub fn point(&self, model_id: &str) -> Option<&Point> {
+        self.points.iter().find(|point| point.model_id == model_id)
+    }
+
+    /// Returns the children of a point in order, i.e. the destinations of the parent-child connections of the point.
+    pub fn children(&self, model_id: &str) -> Vec<&Point> {
+        let mut connections: Vec<_> = self
+            .connections
+            .iter()
+            .filter(|connection| {
+                connection.connection_type.unwrap_or(ConnectionType::ParentOf) == ConnectionType::ParentOf
+                    && connection.source_id == model_id
+            })
+            .collect();
+        connections.sort_by_key(|connection| connection.source_order);
+
+        connections
+            .into_iter()
+            .filter_map(|connection| self.point(&connection.destination_id))
+            .collect()
+    }
+
+    /// Returns the text of the nodes of the diagram in hierarchy order, i.e. every node is followed by its children.
+    /// Nodes without text are included, so the hierarchy stays complete.
+    ///
+    /// ```
+    /// use oox::{shared::drawingml::diagrams::DataModel, xml::XmlNode};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<dgm:dataModel>
+    ///     <dgm:ptLst>
+    ///         <dgm:pt modelId="0" type="doc" />
+    ///         <dgm:pt modelId="1"><dgm:t><a:bodyPr /><a:p><a:r><a:t>Plan</a:t></a:r></a:p></dgm:t></dgm:pt>
+    ///     </dgm:ptLst>
+    ///     <dgm:cxnLst><dgm:cxn modelId="2" srcId="0" destId="1" srcOrd="0" destOrd="0" /></dgm:cxnLst>
+    /// </dgm:dataModel>"#;
+    /// let data_model = DataModel::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+    /// assert_eq!(data_model.texts()[0].text, "Plan");
+    /// ```
+    pub fn texts(&self) -> Vec<DiagramText> {
+        let child_ids: HashMap<_, _> = self
+            .connections
+            .iter()
+            .filter(|connection| {
+                connection.connection_type.unwrap_or(ConnectionType::ParentOf) == ConnectionType::ParentOf
+            })
+            .map(|connection| (connection.destination_id.as_str(), ()))
+            .collect();
+
+        let mut texts = Vec::new();
+        let roots = self.points.iter().filter(|point| {
+            point.point_type == Some(PointType::Document)
+                || (point.is_content() && !child_ids.contains_key(point.model_id.as_str()))
+        });
+        for root in roots {
+            self.collect_texts(root, 0, &mut texts);
+        }
+
+        texts
+    }
+
+    fn collect_texts(&self, point: &Point, level: usize, texts: &mut Vec<DiagramText>) {
+        let child_level = if point.is_content() {
+            texts.push(DiagramText {
+                model_id: point.model_id.clone(),
+                level,
+                text: point.text(),
+            });
+            level + 1
+        } else {
+            level
+        };
+
+        for child in self.children(&point.model_id) {
+            if child.is_content() {
+                self.collect_texts(child, child_level, texts);
+            }
+        }
+    }
+
+    /// Returns the text of the nodes of the diagram separated by newlines.
+    pub fn text(&self) -> String {
+        self.texts()
+            .into_iter()
+            .map(|text| text.text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn text_body_text(text_body: &TextBody) -> String {
+    text_body
+        .paragraph_array
+        .iter()
+        .map(|paragraph| {
+            paragraph
+                .text_run_list
+                .iter()
+                .map(|run| match run {
+                    TextRun::RegularTextRun(run) => run.text.clone(),
+                    TextRun::LineBreak(_) => String::from("\u{b}"),
+                    TextRun::TextField(field) => field.text.clone().unwrap_or_default(),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +494,48 @@ mod tests {
             WholeE2oFormatting::test_instance(),
         );
     }
+
+    #[test]
+    pub fn test_data_model_texts() {
+        let xml = r#"<dgm:dataModel>
+            <dgm:ptLst>
+                <dgm:pt modelId="0" type="doc"><dgm:prSet /><dgm:spPr /><dgm:t><a:bodyPr /><a:p /></dgm:t></dgm:pt>
+                <dgm:pt modelId="1"><dgm:prSet phldrT="[Text]" /><dgm:t><a:bodyPr /><a:p><a:r><a:t>Plan</a:t></a:r></a:p></dgm:t></dgm:pt>
+                <dgm:pt modelId="2"><dgm:t><a:bodyPr /><a:p><a:r><a:t>Build</a:t></a:r></a:p></dgm:t></dgm:pt>
+                <dgm:pt modelId="3" type="asst"><dgm:t><a:bodyPr /><a:p><a:r><a:t>Budget</a:t></a:r></a:p><a:p><a:r><a:t>Staff</a:t></a:r></a:p></dgm:t></dgm:pt>
+                <dgm:pt modelId="4" type="parTrans" cxnId="10" />
+                <dgm:pt modelId="5" type="pres"><dgm:prSet presName="node" /></dgm:pt>
+            </dgm:ptLst>
+            <dgm:cxnLst>
+                <dgm:cxn modelId="10" srcId="0" destId="2" srcOrd="1" destOrd="0" parTransId="4" />
+                <dgm:cxn modelId="11" srcId="0" destId="1" srcOrd="0" destOrd="0" />
+                <dgm:cxn modelId="12" srcId="1" destId="3" srcOrd="0" destOrd="0" />
+                <dgm:cxn modelId="13" type="presOf" srcId="1" destId="5" srcOrd="0" destOrd="0" />
+            </dgm:cxnLst>
+            <dgm:bg />
+            <dgm:whole />
+        </dgm:dataModel>"#;
+        let data_model = DataModel::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+
+        assert_eq!(data_model.points.len(), 6);
+        assert_eq!(data_model.points[1].placeholder_text, Some(String::from("[Text]")));
+        assert_eq!(data_model.connections[0].parent_transition_id, Some(String::from("4")));
+        assert_eq!(
+            data_model.connections[3].connection_type,
+            Some(ConnectionType::PresentationOf)
+        );
+        assert_eq!(
+            data_model
+                .texts()
+                .into_iter()
+                .map(|text| (text.level, text.text))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, String::from("Plan")),
+                (1, String::from("Budget\nStaff")),
+                (0, String::from("Build")),
+            ]
+        );
+        assert_eq!(data_model.text(), "Plan\nBudget\nStaff\nBuild");
+    }
 }
@@ -0,0 +1,523 @@
+//! Evaluation of DrawingML fills to concrete colors.
+//!
+//! Fills reference scheme colors which are mapped to the color scheme of the theme, and every color can be modified
+//! by a list of color transforms (`a:tint`, `a:lumMod`, `a:alpha`, ...). [`ColorResolver`] resolves them into
+//! [`Rgba`] values, so every renderer of shapes evaluates them the same way.
+
+use super::{
+    colors::{Color, ColorTransform},
+    shapeprops::{FillProperties, ShadeProperties},
+    sharedstylesheet::ColorMapping,
+    simpletypes::{HexColorRGB, PresetPatternVal, SchemeColorVal},
+    theme::Theme,
+};
+use crate::shared::relationship::RelationshipId;
+
+/// The value of 100% in the percentages of the color transforms.
+const PERCENTAGE_SCALE: f32 = 100_000.0;
+
+/// The value of a full circle in the angles of the color transforms.
+const FULL_CIRCLE: f32 = 21_600_000.0;
+
+/// A concrete color with its opacity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub rgb: HexColorRGB,
+    /// The opacity from 0.0 (transparent) to 1.0 (opaque).
+    pub alpha: f32,
+}
+
+impl Rgba {
+    pub fn opaque(rgb: HexColorRGB) -> Self {
+        Self { rgb, alpha: 1.0 }
+    }
+}
+
+/// A stop of a resolved gradient.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedGradientStop {
+    /// The position of the stop along the gradient from 0.0 to 1.0.
+    pub position: f32,
+    pub color: Rgba,
+}
+
+/// A fill with its colors resolved, see [`ColorResolver::resolve_fill`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolvedFill {
+    NoFill,
+    Solid(Rgba),
+    Gradient {
+        /// The stops ordered by their position.
+        stops: Vec<ResolvedGradientStop>,
+        shade_properties: Option<ShadeProperties>,
+    },
+    Pattern {
+        preset: Option<PresetPatternVal>,
+        foreground: Option<Rgba>,
+        background: Option<Rgba>,
+    },
+    /// A picture fill. Only the relationship of the embedded picture is resolved, the picture isn't loaded.
+    Picture(Option<RelationshipId>),
+    /// The fill of the enclosing group, which has to be resolved by the caller.
+    Group,
+}
+
+/// Resolves the colors of fills against a theme.
+#[derive(Debug, Clone, Copy)]
+pub struct ColorResolver<'a> {
+    theme: &'a Theme,
+    color_mapping: Option<&'a ColorMapping>,
+    placeholder_color: Option<&'a Color>,
+}
+
+impl<'a> ColorResolver<'a> {
+    /// Creates a resolver mapping the background and text colors the same way as the default color mapping does, see
+    /// [`Theme::resolve_color`].
+    pub fn new(theme: &'a Theme) -> Self {
+        Self {
+            theme,
+            color_mapping: None,
+            placeholder_color: None,
+        }
+    }
+
+    /// Maps the background and text colors with the given color mapping, e.g. the `p:clrMap` of a slide master.
+    pub fn with_color_mapping(mut self, color_mapping: &'a ColorMapping) -> Self {
+        self.color_mapping = Some(color_mapping);
+        self
+    }
+
+    /// Substitutes the placeholder color (`phClr`) with the given color, e.g. the color of a style matrix reference.
+    pub fn with_placeholder_color(mut self, placeholder_color: Option<&'a Color>) -> Self {
+        self.placeholder_color = placeholder_color;
+        self
+    }
+
+    /// Resolves a color and applies its color transforms in order.
+    ///
+    /// Returns None for preset colors, system colors without a last computed value and placeholder colors without a
+    /// substitute.
+    pub fn resolve_color(&self, color: &Color) -> Option<Rgba> {
+        let base = match color {
+            Color::SchemeColor(scheme_color) if scheme_color.value == SchemeColorVal::PlaceholderColor => {
+                let resolver = self.with_placeholder_color(None);
+                resolver.resolve_color(self.placeholder_color?)?
+            }
+            Color::SchemeColor(scheme_color) => Rgba::opaque(match self.color_mapping {
+                Some(color_mapping) => {
+                    self.theme
+                        .resolve_mapped_color(scheme_color.value, color_mapping, None, None)?
+                }
+                None => self.theme.resolve_color(scheme_color.value, None, None)?,
+            }),
+            color => Rgba::opaque(color.to_rgb()?),
+        };
+
+        Some(apply_color_transforms(base, color.color_transforms()))
+    }
+
+    /// Resolves the colors of a fill. Returns None if a color of the fill can't be resolved, see
+    /// [`ColorResolver::resolve_color`].
+    pub fn resolve_fill(&self, fill: &FillProperties) -> Option<ResolvedFill> {
+        let resolved_fill = match fill {
+            FillProperties::NoFill => ResolvedFill::NoFill,
+            FillProperties::SolidFill(color) => ResolvedFill::Solid(self.resolve_color(color)?),
+            FillProperties::GradientFill(gradient) => {
+                let mut stops = gradient
+                    .gradient_stop_list
+                    .iter()
+                    .flatten()
+                    .map(|stop| {
+                        Some(ResolvedGradientStop {
+                            position: (stop.position / PERCENTAGE_SCALE).clamp(0.0, 1.0),
+                            color: self.resolve_color(&stop.color)?,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                stops.sort_by(|lhs, rhs| lhs.position.total_cmp(&rhs.position));
+
+                ResolvedFill::Gradient {
+                    stops,
+                    shade_properties: gradient.shade_properties.clone(),
+                }
+            }
+            FillProperties::PatternFill(pattern) => ResolvedFill::Pattern {
+                preset: pattern.preset,
+                foreground: match &pattern.fg_color {
+                    Some(color) => Some(self.resolve_color(color)?),
+                    None => None,
+                },
+                background: match &pattern.bg_color {
+                    Some(color) => Some(self.resolve_color(color)?),
+                    None => None,
+                },
+            },
+            FillProperties::BlipFill(blip_fill) => {
+                ResolvedFill::Picture(blip_fill.blip.as_ref().and_then(|blip| blip.embed_rel_id.clone()))
+            }
+            FillProperties::GroupFill => ResolvedFill::Group,
+        };
+
+        Some(resolved_fill)
+    }
+}
+
+impl Color {
+    pub fn color_transforms(&self) -> &[ColorTransform] {
+        match self {
+            Color::ScRgbColor(color) => &color.color_transforms,
+            Color::SRgbColor(color) => &color.color_transforms,
+            Color::HslColor(color) => &color.color_transforms,
+            Color::SystemColor(color) => &color.color_transforms,
+            Color::SchemeColor(color) => &color.color_transforms,
+            Color::PresetColor(color) => &color.color_transforms,
+        }
+    }
+}
+
+/// Applies color transforms to a color. Tints, shades and the red, green and blue transforms work on linear RGB
+/// components, the hue, saturation and luminance transforms on HSL components.
+///
+/// ```
+/// use oox::shared::drawingml::{
+///     colors::ColorTransform,
+///     fill::{apply_color_transforms, Rgba},
+/// };
+///
+/// let transforms = [ColorTransform::LuminanceModulate(50_000.0), ColorTransform::Alpha(50_000.0)];
+/// assert_eq!(
+///     apply_color_transforms(Rgba::opaque([0xFF, 0x00, 0x00]), &transforms),
+///     Rgba { rgb: [0x80, 0x00, 0x00], alpha: 0.5 }
+/// );
+/// ```
+pub fn apply_color_transforms(color: Rgba, transforms: &[ColorTransform]) -> Rgba {
+    let mut rgb = color.rgb.map(|component| f32::from(component) / 255.0);
+    let mut alpha = color.alpha;
+
+    for transform in transforms {
+        match transform {
+            ColorTransform::Tint(tint) => {
+                let tint = *tint / PERCENTAGE_SCALE;
+                rgb = map_linear(rgb, |component| component * tint + (1.0 - tint));
+            }
+            ColorTransform::Shade(shade) => {
+                let shade = *shade / PERCENTAGE_SCALE;
+                rgb = map_linear(rgb, |component| component * shade);
+            }
+            ColorTransform::Complement => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| [hue + 0.5, saturation, luminance])
+            }
+            ColorTransform::Inverse => rgb = rgb.map(|component| 1.0 - component),
+            ColorTransform::Grayscale => {
+                let gray = 0.3 * rgb[0] + 0.59 * rgb[1] + 0.11 * rgb[2];
+                rgb = [gray; 3];
+            }
+            ColorTransform::Alpha(value) => alpha = *value / PERCENTAGE_SCALE,
+            ColorTransform::AlphaOffset(offset) => alpha += *offset / PERCENTAGE_SCALE,
+            ColorTransform::AlphaModulate(modulate) => alpha *= *modulate / PERCENTAGE_SCALE,
+            ColorTransform::Hue(hue) => {
+                rgb = map_hsl(rgb, |[_, saturation, luminance]| {
+                    [*hue as f32 / FULL_CIRCLE, saturation, luminance]
+                })
+            }
+            ColorTransform::HueOffset(offset) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue + *offset as f32 / FULL_CIRCLE, saturation, luminance]
+                })
+            }
+            ColorTransform::HueModulate(modulate) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue * *modulate / PERCENTAGE_SCALE, saturation, luminance]
+                })
+            }
+            ColorTransform::Saturation(value) => {
+                rgb = map_hsl(rgb, |[hue, _, luminance]| [hue, *value / PERCENTAGE_SCALE, luminance])
+            }
+            ColorTransform::SaturationOffset(offset) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue, saturation + *offset / PERCENTAGE_SCALE, luminance]
+                })
+            }
+            ColorTransform::SaturationModulate(modulate) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue, saturation * *modulate / PERCENTAGE_SCALE, luminance]
+                })
+            }
+            ColorTransform::Luminance(value) => {
+                rgb = map_hsl(rgb, |[hue, saturation, _]| [hue, saturation, *value / PERCENTAGE_SCALE])
+            }
+            ColorTransform::LuminanceOffset(offset) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue, saturation, luminance + *offset / PERCENTAGE_SCALE]
+                })
+            }
+            ColorTransform::LuminanceModulate(modulate) => {
+                rgb = map_hsl(rgb, |[hue, saturation, luminance]| {
+                    [hue, saturation, luminance * *modulate / PERCENTAGE_SCALE]
+                })
+            }
+            ColorTransform::Red(value) => rgb[0] = srgb_component(*value / PERCENTAGE_SCALE),
+            ColorTransform::RedOffset(offset) => rgb[0] = offset_linear(rgb[0], |red| red + *offset / PERCENTAGE_SCALE),
+            ColorTransform::RedModulate(modulate) => {
+                rgb[0] = offset_linear(rgb[0], |red| red * *modulate / PERCENTAGE_SCALE)
+            }
+            ColorTransform::Green(value) => rgb[1] = srgb_component(*value / PERCENTAGE_SCALE),
+            ColorTransform::GreenOffset(offset) => {
+                rgb[1] = offset_linear(rgb[1], |green| green + *offset / PERCENTAGE_SCALE)
+            }
+            ColorTransform::GreenModulate(modulate) => {
+                rgb[1] = offset_linear(rgb[1], |green| green * *modulate / PERCENTAGE_SCALE)
+            }
+            ColorTransform::Blue(value) => rgb[2] = srgb_component(*value / PERCENTAGE_SCALE),
+            ColorTransform::BlueOffset(offset) => {
+                rgb[2] = offset_linear(rgb[2], |blue| blue + *offset / PERCENTAGE_SCALE)
+            }
+            ColorTransform::BlueModulate(modulate) => {
+                rgb[2] = offset_linear(rgb[2], |blue| blue * *modulate / PERCENTAGE_SCALE)
+            }
+            ColorTransform::Gamma => rgb = rgb.map(srgb_component),
+            ColorTransform::InverseGamma => rgb = rgb.map(linear_component),
+        }
+
+        rgb = rgb.map(|component| component.clamp(0.0, 1.0));
+        alpha = alpha.clamp(0.0, 1.0);
+    }
+
+    Rgba {
+        rgb: rgb.map(|component| (component * 255.0).round() as u8),
+        alpha,
+    }
+}
+
+fn linear_component(component: f32) -> f32 {
+    if component <= 0.040_45 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn srgb_component(component: f32) -> f32 {
+    let component = component.clamp(0.0, 1.0);
+    if component <= 0.003_130_8 {
+        component * 12.92
+    } else {
+        1.055 * component.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn offset_linear(component: f32, f: impl Fn(f32) -> f32) -> f32 {
+    srgb_component(f(linear_component(component)))
+}
+
+fn map_linear(rgb: [f32; 3], f: impl Fn(f32) -> f32) -> [f32; 3] {
+    rgb.map(|component| offset_linear(component, &f))
+}
+
+/// Converts the color to HSL components, each of them from 0.0 to 1.0, transforms them and converts them back.
+fn map_hsl(rgb: [f32; 3], f: impl Fn([f32; 3]) -> [f32; 3]) -> [f32; 3] {
+    let [hue, saturation, luminance] = f(rgb_to_hsl(rgb));
+    hsl_to_rgb([
+        hue.rem_euclid(1.0),
+        saturation.clamp(0.0, 1.0),
+        luminance.clamp(0.0, 1.0),
+    ])
+}
+
+fn rgb_to_hsl([red, green, blue]: [f32; 3]) -> [f32; 3] {
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let luminance = (max + min) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return [0.0, 0.0, luminance];
+    }
+
+    let saturation = if luminance < 0.5 {
+        delta / (max + min)
+    } else {
+        delta / (2.0 - max - min)
+    };
+    let hue = if max == red {
+        (green - blue) / delta
+    } else if max == green {
+        (blue - red) / delta + 2.0
+    } else {
+        (red - green) / delta + 4.0
+    };
+
+    [(hue / 6.0).rem_euclid(1.0), saturation, luminance]
+}
+
+fn hsl_to_rgb([hue, saturation, luminance]: [f32; 3]) -> [f32; 3] {
+    if saturation == 0.0 {
+        return [luminance; 3];
+    }
+
+    let q = if luminance < 0.5 {
+        luminance * (1.0 + saturation)
+    } else {
+        luminance + saturation - luminance * saturation
+    };
+    let p = 2.0 * luminance - q;
+    let hue_to_component = |t: f32| {
+        let t = t.rem_euclid(1.0);
+        if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        }
+    };
+
+    [
+        hue_to_component(hue + 1.0 / 3.0),
+        hue_to_component(hue),
+        hue_to_component(hue - 1.0 / 3.0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{xml::XmlNode, xsdtypes::XsdType};
+    use std::str::FromStr;
+
+    fn theme_for_test() -> Theme {
+        let xml = r#"<a:clrScheme name="Office">
+            <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+            <a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1>
+            <a:dk2><a:srgbClr val="44546A"/></a:dk2>
+            <a:lt2><a:srgbClr val="E7E6E6"/></a:lt2>
+            <a:accent1><a:srgbClr val="4472C4"/></a:accent1>
+            <a:accent2><a:srgbClr val="ED7D31"/></a:accent2>
+            <a:accent3><a:srgbClr val="A5A5A5"/></a:accent3>
+            <a:accent4><a:srgbClr val="FFC000"/></a:accent4>
+            <a:accent5><a:srgbClr val="5B9BD5"/></a:accent5>
+            <a:accent6><a:srgbClr val="70AD47"/></a:accent6>
+            <a:hlink><a:srgbClr val="0563C1"/></a:hlink>
+            <a:folHlink><a:srgbClr val="954F72"/></a:folHlink>
+        </a:clrScheme>"#;
+
+        let xml = format!(
+            r#"<a:theme name="Office">
+                <a:themeElements>
+                    {}
+                    <a:fontScheme name="Office">
+                        <a:majorFont><a:latin typeface="Calibri Light"/><a:ea typeface=""/><a:cs typeface=""/></a:majorFont>
+                        <a:minorFont><a:latin typeface="Calibri"/><a:ea typeface=""/><a:cs typeface=""/></a:minorFont>
+                    </a:fontScheme>
+                    <a:fmtScheme name="Office">
+                        <a:fillStyleLst>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                        </a:fillStyleLst>
+                        <a:lnStyleLst>
+                            <a:ln><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+                            <a:ln><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+                            <a:ln><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+                        </a:lnStyleLst>
+                        <a:effectStyleLst>
+                            <a:effectStyle><a:effectLst/></a:effectStyle>
+                            <a:effectStyle><a:effectLst/></a:effectStyle>
+                            <a:effectStyle><a:effectLst/></a:effectStyle>
+                        </a:effectStyleLst>
+                        <a:bgFillStyleLst>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                            <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+                        </a:bgFillStyleLst>
+                    </a:fmtScheme>
+                </a:themeElements>
+            </a:theme>"#,
+            xml
+        );
+
+        Theme::from_xml_element(&XmlNode::from_str(&xml).unwrap()).unwrap()
+    }
+
+    fn fill_from_xml(xml: &str) -> FillProperties {
+        FillProperties::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_resolve_color_transforms() {
+        let theme = theme_for_test();
+        let resolver = ColorResolver::new(&theme);
+
+        let fill = fill_from_xml(
+            r#"<a:solidFill><a:schemeClr val="tx1"><a:lumMod val="65000"/><a:lumOff val="35000"/></a:schemeClr></a:solidFill>"#,
+        );
+        assert_eq!(
+            resolver.resolve_fill(&fill),
+            Some(ResolvedFill::Solid(Rgba::opaque([0x59, 0x59, 0x59])))
+        );
+
+        let fill = fill_from_xml(
+            r#"<a:solidFill><a:schemeClr val="accent1"><a:alpha val="40000"/></a:schemeClr></a:solidFill>"#,
+        );
+        assert_eq!(
+            resolver.resolve_fill(&fill),
+            Some(ResolvedFill::Solid(Rgba {
+                rgb: [0x44, 0x72, 0xC4],
+                alpha: 0.4
+            }))
+        );
+
+        let fill = fill_from_xml(r#"<a:solidFill><a:srgbClr val="000000"><a:inv/></a:srgbClr></a:solidFill>"#);
+        assert_eq!(
+            resolver.resolve_fill(&fill),
+            Some(ResolvedFill::Solid(Rgba::opaque([0xFF, 0xFF, 0xFF])))
+        );
+
+        let fill = fill_from_xml(
+            r#"<a:solidFill><a:schemeClr val="phClr"><a:shade val="50000"/></a:schemeClr></a:solidFill>"#,
+        );
+        assert_eq!(resolver.resolve_fill(&fill), None);
+    }
+
+    #[test]
+    pub fn test_resolve_gradient_fill() {
+        let theme = theme_for_test();
+        let placeholder_color =
+            Color::from_xml_element(&XmlNode::from_str(r#"<a:schemeClr val="accent2"></a:schemeClr>"#).unwrap())
+                .unwrap();
+        let resolver = ColorResolver::new(&theme).with_placeholder_color(Some(&placeholder_color));
+
+        let fill = fill_from_xml(
+            r#"<a:gradFill>
+                <a:gsLst>
+                    <a:gs pos="100000"><a:srgbClr val="FFFFFF"/></a:gs>
+                    <a:gs pos="0"><a:schemeClr val="phClr"/></a:gs>
+                </a:gsLst>
+                <a:lin ang="5400000" scaled="0"/>
+            </a:gradFill>"#,
+        );
+        match resolver.resolve_fill(&fill) {
+            Some(ResolvedFill::Gradient {
+                stops,
+                shade_properties,
+            }) => {
+                assert_eq!(
+                    stops,
+                    vec![
+                        ResolvedGradientStop {
+                            position: 0.0,
+                            color: Rgba::opaque([0xED, 0x7D, 0x31]),
+                        },
+                        ResolvedGradientStop {
+                            position: 1.0,
+                            color: Rgba::opaque([0xFF, 0xFF, 0xFF]),
+                        },
+                    ]
+                );
+                assert!(matches!(shade_properties, Some(ShadeProperties::Linear(_))));
+            }
+            fill => panic!("Unexpected fill {:?}", fill),
+        }
+    }
+}
@@ -1,8 +1,10 @@
 pub mod audiovideo;
+pub mod chart;
 pub mod colors;
 pub mod coordsys;
 pub mod core;
 pub mod diagrams;
+pub mod fill;
 pub mod picture;
 pub mod shapedefs;
 pub mod shapeprops;
@@ -10,4 +12,5 @@ pub mod sharedstylesheet;
 pub mod simpletypes;
 pub mod styles;
 pub mod text;
+pub mod theme;
 pub mod util;
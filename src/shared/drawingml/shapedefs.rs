@@ -46,7 +46,7 @@ impl GeomRect {
         let mut bottom = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "l" => left = Some(value.parse()?),
                 "t" => top = Some(value.parse()?),
                 "r" => right = Some(value.parse()?),
@@ -111,7 +111,7 @@ impl PolarAdjustHandle {
         let mut max_angle = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "gdRefR" => guide_reference_radial = Some(value.clone()),
                 "gdRefAng" => guide_reference_angle = Some(value.clone()),
                 "minR" => min_radial = Some(value.parse()?),
@@ -231,7 +231,7 @@ impl XYAdjustHandle {
         let mut max_y = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "gdRefX" => guide_reference_x = Some(value.clone()),
                 "gdRefY" => guide_reference_y = Some(value.clone()),
                 "minX" => min_x = Some(value.parse()?),
@@ -372,7 +372,7 @@ impl AdjPoint2D {
         let mut y = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "x" => x = Some(value.parse()?),
                 "y" => y = Some(value.parse()?),
                 _ => (),
@@ -418,7 +418,7 @@ impl Path2DArcTo {
         let mut swing_angle = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "wR" => width_radius = Some(value.parse()?),
                 "hR" => height_radius = Some(value.parse()?),
                 "stAng" => start_angle = Some(value.parse()?),
@@ -735,7 +735,7 @@ impl GeomGuide {
         let mut formula = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "name" => name = Some(value.clone()),
                 "fmla" => formula = Some(value.clone()),
                 _ => (),
@@ -867,7 +867,7 @@ impl XsdType for Path2DCommand {
 
         match xml_node.local_name() {
             "close" => Ok(Path2DCommand::Close),
-            "moveTo" => Ok(Path2DCommand::LineTo(get_point_at(0)?)),
+            "moveTo" => Ok(Path2DCommand::MoveTo(get_point_at(0)?)),
             "lnTo" => Ok(Path2DCommand::LineTo(get_point_at(0)?)),
             "arcTo" => Ok(Path2DCommand::ArcTo(Path2DArcTo::from_xml_element(xml_node)?)),
             "quadBezTo" => Ok(Path2DCommand::QuadBezierTo(get_point_at(0)?, get_point_at(1)?)),
@@ -39,7 +39,7 @@ impl RelativeRect {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "l" => instance.left = Some(value.parse()?),
                     "t" => instance.top = Some(value.parse()?),
                     "r" => instance.right = Some(value.parse()?),
@@ -540,7 +540,7 @@ impl InnerShadowEffect {
         let mut direction = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "blurRad" => blur_radius = Some(value.parse()?),
                 "dist" => distance = Some(value.parse()?),
                 "dir" => direction = Some(value.parse()?),
@@ -628,7 +628,7 @@ impl OuterShadowEffect {
         let mut rotate_with_shape = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "blurRad" => blur_radius = Some(value.parse()?),
                 "dist" => distance = Some(value.parse()?),
                 "dir" => direction = Some(value.parse()?),
@@ -693,7 +693,7 @@ impl PresetShadowEffect {
         let mut direction = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "prst" => preset = Some(value.parse()?),
                 "dist" => distance = Some(value.parse()?),
                 "dir" => direction = Some(value.parse()?),
@@ -836,7 +836,7 @@ impl RelativeOffsetEffect {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "tx" => instance.translate_x = Some(value.parse()?),
                     "ty" => instance.translate_y = Some(value.parse()?),
                     _ => (),
@@ -886,7 +886,7 @@ impl TintEffect {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "hue" => instance.hue = Some(value.parse()?),
                     "amt" => instance.amount = Some(value.parse()?),
                     _ => (),
@@ -1278,7 +1278,7 @@ impl EffectContainer {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "type" => instance.container_type = Some(value.parse::<EffectContainerType>()?),
                     "name" => instance.name = Some(value.clone()),
                     _ => (),
@@ -1347,7 +1347,7 @@ impl GradientFillProperties {
             .attributes
             .iter()
             .try_fold(Default::default(), |mut instance: Self, (attr, value)| {
-                match attr.as_str() {
+                match attr.as_ref() {
                     "flip" => instance.flip = Some(value.parse()?),
                     "rotWithShape" => instance.rotate_with_shape = Some(parse_xml_bool(value)?),
                     _ => (),
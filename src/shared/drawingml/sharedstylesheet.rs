@@ -69,7 +69,7 @@ impl ColorMapping {
         let mut followed_hyperlink = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "bg1" => background1 = Some(value.parse()?),
                 "tx1" => text1 = Some(value.parse()?),
                 "bg2" => background2 = Some(value.parse()?),
@@ -340,7 +340,7 @@ impl SupplementalFont {
         let mut typeface = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "script" => script = Some(value.clone()),
                 "typeface" => typeface = Some(value.clone()),
                 _ => (),
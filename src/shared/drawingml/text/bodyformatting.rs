@@ -590,7 +590,7 @@ impl TextNormalAutoFit {
         let mut line_spacing_reduction = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "fontScale" => font_scale = Some(value.parse::<TextFontScalePercent>()?),
                 "lnSpcReduction" => line_spacing_reduction = Some(value.parse::<TextSpacingPercent>()?),
                 _ => (),
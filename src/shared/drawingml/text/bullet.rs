@@ -6,6 +6,7 @@ use crate::{
         shapeprops::Blip,
         simpletypes::{TextAutonumberScheme, TextBulletSizePercent, TextBulletStartAtNum, TextFontSize},
     },
+    update::{update_boxed_options, Update},
     xml::XmlNode,
     xsdtypes::{XsdChoice, XsdType},
 };
@@ -503,7 +504,7 @@ impl TextAutonumberedBullet {
         let mut start_at = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "type" => scheme = Some(value.parse()?),
                 "startAt" => start_at = Some(value.parse()?),
                 _ => (),
@@ -608,7 +609,51 @@ pub struct TextListStyle {
     pub lvl9_paragraph_props: Option<Box<TextParagraphProperties>>,
 }
 
+impl Update for TextListStyle {
+    fn update_with(self, other: Self) -> Self {
+        Self {
+            def_paragraph_props: update_boxed_options(self.def_paragraph_props, other.def_paragraph_props),
+            lvl1_paragraph_props: update_boxed_options(self.lvl1_paragraph_props, other.lvl1_paragraph_props),
+            lvl2_paragraph_props: update_boxed_options(self.lvl2_paragraph_props, other.lvl2_paragraph_props),
+            lvl3_paragraph_props: update_boxed_options(self.lvl3_paragraph_props, other.lvl3_paragraph_props),
+            lvl4_paragraph_props: update_boxed_options(self.lvl4_paragraph_props, other.lvl4_paragraph_props),
+            lvl5_paragraph_props: update_boxed_options(self.lvl5_paragraph_props, other.lvl5_paragraph_props),
+            lvl6_paragraph_props: update_boxed_options(self.lvl6_paragraph_props, other.lvl6_paragraph_props),
+            lvl7_paragraph_props: update_boxed_options(self.lvl7_paragraph_props, other.lvl7_paragraph_props),
+            lvl8_paragraph_props: update_boxed_options(self.lvl8_paragraph_props, other.lvl8_paragraph_props),
+            lvl9_paragraph_props: update_boxed_options(self.lvl9_paragraph_props, other.lvl9_paragraph_props),
+        }
+    }
+}
+
 impl TextListStyle {
+    /// Returns the effective paragraph properties for a 0-based paragraph level. The level specific properties are
+    /// merged on top of the default paragraph properties of the list style.
+    pub fn paragraph_properties(&self, level: i32) -> TextParagraphProperties {
+        let level_properties = match level {
+            0 => &self.lvl1_paragraph_props,
+            1 => &self.lvl2_paragraph_props,
+            2 => &self.lvl3_paragraph_props,
+            3 => &self.lvl4_paragraph_props,
+            4 => &self.lvl5_paragraph_props,
+            5 => &self.lvl6_paragraph_props,
+            6 => &self.lvl7_paragraph_props,
+            7 => &self.lvl8_paragraph_props,
+            _ => &self.lvl9_paragraph_props,
+        };
+
+        let default_properties = self
+            .def_paragraph_props
+            .as_ref()
+            .map(|properties| (**properties).clone())
+            .unwrap_or_default();
+
+        match level_properties {
+            Some(level_properties) => default_properties.update_with((**level_properties).clone()),
+            None => default_properties,
+        }
+    }
+
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
         xml_node
             .child_nodes
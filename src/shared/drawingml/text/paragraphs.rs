@@ -15,6 +15,7 @@ use crate::{
         },
         util::XmlNodeExt,
     },
+    update::{update_boxed_options, Update},
     xml::{parse_xml_bool, XmlNode},
     xsdtypes::{XsdChoice, XsdType},
 };
@@ -105,7 +106,7 @@ impl TextField {
         let mut field_type = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "id" => id = Some(value.clone()),
                 "type" => field_type = Some(value.clone()),
                 _ => (),
@@ -528,6 +529,33 @@ pub struct TextParagraphProperties {
     pub default_run_properties: Option<Box<TextCharacterProperties>>,
 }
 
+impl Update for TextParagraphProperties {
+    fn update_with(self, other: Self) -> Self {
+        Self {
+            margin_left: other.margin_left.or(self.margin_left),
+            margin_right: other.margin_right.or(self.margin_right),
+            level: other.level.or(self.level),
+            indent: other.indent.or(self.indent),
+            align: other.align.or(self.align),
+            default_tab_size: other.default_tab_size.or(self.default_tab_size),
+            rtl: other.rtl.or(self.rtl),
+            east_asian_line_break: other.east_asian_line_break.or(self.east_asian_line_break),
+            font_align: other.font_align.or(self.font_align),
+            latin_line_break: other.latin_line_break.or(self.latin_line_break),
+            hanging_punctuations: other.hanging_punctuations.or(self.hanging_punctuations),
+            line_spacing: other.line_spacing.or(self.line_spacing),
+            space_before: other.space_before.or(self.space_before),
+            space_after: other.space_after.or(self.space_after),
+            bullet_color: other.bullet_color.or(self.bullet_color),
+            bullet_size: other.bullet_size.or(self.bullet_size),
+            bullet_typeface: other.bullet_typeface.or(self.bullet_typeface),
+            bullet: other.bullet.or(self.bullet),
+            tab_stop_list: other.tab_stop_list.or(self.tab_stop_list),
+            default_run_properties: update_boxed_options(self.default_run_properties, other.default_run_properties),
+        }
+    }
+}
+
 impl TextParagraphProperties {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextParagraphProperties> {
         xml_node
@@ -1060,6 +1088,45 @@ pub struct TextCharacterProperties {
     pub rtl: Option<bool>,
 }
 
+impl Update for TextCharacterProperties {
+    fn update_with(self, other: Self) -> Self {
+        Self {
+            kumimoji: other.kumimoji.or(self.kumimoji),
+            language: other.language.or(self.language),
+            alternative_language: other.alternative_language.or(self.alternative_language),
+            font_size: other.font_size.or(self.font_size),
+            bold: other.bold.or(self.bold),
+            italic: other.italic.or(self.italic),
+            underline: other.underline.or(self.underline),
+            strikethrough: other.strikethrough.or(self.strikethrough),
+            kerning: other.kerning.or(self.kerning),
+            capitalization: other.capitalization.or(self.capitalization),
+            spacing: other.spacing.or(self.spacing),
+            normalize_heights: other.normalize_heights.or(self.normalize_heights),
+            baseline: other.baseline.or(self.baseline),
+            no_proofing: other.no_proofing.or(self.no_proofing),
+            dirty: other.dirty.or(self.dirty),
+            spelling_error: other.spelling_error.or(self.spelling_error),
+            smarttag_clean: other.smarttag_clean.or(self.smarttag_clean),
+            smarttag_id: other.smarttag_id.or(self.smarttag_id),
+            bookmark_link_target: other.bookmark_link_target.or(self.bookmark_link_target),
+            line_properties: other.line_properties.or(self.line_properties),
+            fill_properties: other.fill_properties.or(self.fill_properties),
+            effect_properties: other.effect_properties.or(self.effect_properties),
+            highlight_color: other.highlight_color.or(self.highlight_color),
+            text_underline_line: other.text_underline_line.or(self.text_underline_line),
+            text_underline_fill: other.text_underline_fill.or(self.text_underline_fill),
+            latin_font: other.latin_font.or(self.latin_font),
+            east_asian_font: other.east_asian_font.or(self.east_asian_font),
+            complex_script_font: other.complex_script_font.or(self.complex_script_font),
+            symbol_font: other.symbol_font.or(self.symbol_font),
+            hyperlink_click: other.hyperlink_click.or(self.hyperlink_click),
+            hyperlink_mouse_over: other.hyperlink_mouse_over.or(self.hyperlink_mouse_over),
+            rtl: other.rtl.or(self.rtl),
+        }
+    }
+}
+
 impl TextCharacterProperties {
     pub fn from_xml_element(xml_node: &XmlNode) -> Result<TextCharacterProperties> {
         xml_node
@@ -107,7 +107,7 @@ impl TextFont {
         let mut charset = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "typeface" => typeface = Some(value.clone()),
                 "panose" => panose = Some(value.clone()),
                 "pitchFamily" => pitch_family = Some(value.parse::<i32>()?),
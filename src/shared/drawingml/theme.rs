@@ -0,0 +1,267 @@
+use super::{
+    colors::{Color, HslColor, ScRgbColor},
+    sharedstylesheet::{ColorMapping, ColorScheme},
+    simpletypes::{ColorSchemeIndex, HexColorRGB, SchemeColorVal},
+};
+
+/// The theme part (`a:theme`) of a package. A theme consists of a color scheme, a font scheme and a format scheme
+/// which are contained by the theme elements.
+pub use super::sharedstylesheet::OfficeStyleSheet as Theme;
+
+impl Theme {
+    /// Resolves a scheme color to an RGB value using the color scheme of this theme. Background and text colors
+    /// are mapped to the light and dark colors the same way as the default color mapping does (bg1 = lt1, tx1 = dk1,
+    /// bg2 = lt2, tx2 = dk2).
+    ///
+    /// `tint` and `shade` are the 0-255 modifiers found in WordprocessingML (`w:themeTint` and `w:themeShade`). A
+    /// tint of 255 or a shade of 255 leaves the color unmodified.
+    ///
+    /// Returns None if the color can't be resolved, e.g. it's the placeholder color, or the scheme defines the color
+    /// as a preset or system color without a last computed value.
+    pub fn resolve_color(&self, color: SchemeColorVal, tint: Option<u8>, shade: Option<u8>) -> Option<HexColorRGB> {
+        let index = default_color_scheme_index(color)?;
+        self.resolve_color_scheme_index(index, tint, shade)
+    }
+
+    /// Same as `resolve_color` but background and text colors are mapped with the provided color mapping, e.g. the
+    /// `p:clrMap` of a slide master.
+    pub fn resolve_mapped_color(
+        &self,
+        color: SchemeColorVal,
+        mapping: &ColorMapping,
+        tint: Option<u8>,
+        shade: Option<u8>,
+    ) -> Option<HexColorRGB> {
+        let index = match color {
+            SchemeColorVal::Background1 => mapping.background1,
+            SchemeColorVal::Text1 => mapping.text1,
+            SchemeColorVal::Background2 => mapping.background2,
+            SchemeColorVal::Text2 => mapping.text2,
+            SchemeColorVal::Accent1 => mapping.accent1,
+            SchemeColorVal::Accent2 => mapping.accent2,
+            SchemeColorVal::Accent3 => mapping.accent3,
+            SchemeColorVal::Accent4 => mapping.accent4,
+            SchemeColorVal::Accent5 => mapping.accent5,
+            SchemeColorVal::Accent6 => mapping.accent6,
+            SchemeColorVal::Hyperlink => mapping.hyperlink,
+            SchemeColorVal::FollowedHyperlink => mapping.followed_hyperlink,
+            _ => default_color_scheme_index(color)?,
+        };
+
+        self.resolve_color_scheme_index(index, tint, shade)
+    }
+
+    fn resolve_color_scheme_index(
+        &self,
+        index: ColorSchemeIndex,
+        tint: Option<u8>,
+        shade: Option<u8>,
+    ) -> Option<HexColorRGB> {
+        let rgb = self.theme_elements.color_scheme.get_color(index).to_rgb()?;
+        Some(apply_shade(apply_tint(rgb, tint), shade))
+    }
+}
+
+impl ColorScheme {
+    pub fn get_color(&self, index: ColorSchemeIndex) -> &Color {
+        match index {
+            ColorSchemeIndex::Dark1 => &self.dark1,
+            ColorSchemeIndex::Light1 => &self.light1,
+            ColorSchemeIndex::Dark2 => &self.dark2,
+            ColorSchemeIndex::Light2 => &self.light2,
+            ColorSchemeIndex::Accent1 => &self.accent1,
+            ColorSchemeIndex::Accent2 => &self.accent2,
+            ColorSchemeIndex::Accent3 => &self.accent3,
+            ColorSchemeIndex::Accent4 => &self.accent4,
+            ColorSchemeIndex::Accent5 => &self.accent5,
+            ColorSchemeIndex::Accent6 => &self.accent6,
+            ColorSchemeIndex::Hyperlink => &self.hyperlink,
+            ColorSchemeIndex::FollowedHyperlink => &self.followed_hyperlink,
+        }
+    }
+}
+
+impl Color {
+    /// Converts the base color to an RGB value. Color transforms are not applied.
+    ///
+    /// Returns None for scheme colors, preset colors and system colors without a last computed value.
+    pub fn to_rgb(&self) -> Option<HexColorRGB> {
+        match self {
+            Color::SRgbColor(color) => Some([(color.value >> 16) as u8, (color.value >> 8) as u8, color.value as u8]),
+            Color::ScRgbColor(color) => Some(sc_rgb_to_rgb(color)),
+            Color::HslColor(color) => Some(hsl_to_rgb(color)),
+            Color::SystemColor(color) => color.last_color,
+            Color::SchemeColor(_) | Color::PresetColor(_) => None,
+        }
+    }
+}
+
+fn default_color_scheme_index(color: SchemeColorVal) -> Option<ColorSchemeIndex> {
+    match color {
+        SchemeColorVal::Background1 | SchemeColorVal::Light1 => Some(ColorSchemeIndex::Light1),
+        SchemeColorVal::Text1 | SchemeColorVal::Dark1 => Some(ColorSchemeIndex::Dark1),
+        SchemeColorVal::Background2 | SchemeColorVal::Light2 => Some(ColorSchemeIndex::Light2),
+        SchemeColorVal::Text2 | SchemeColorVal::Dark2 => Some(ColorSchemeIndex::Dark2),
+        SchemeColorVal::Accent1 => Some(ColorSchemeIndex::Accent1),
+        SchemeColorVal::Accent2 => Some(ColorSchemeIndex::Accent2),
+        SchemeColorVal::Accent3 => Some(ColorSchemeIndex::Accent3),
+        SchemeColorVal::Accent4 => Some(ColorSchemeIndex::Accent4),
+        SchemeColorVal::Accent5 => Some(ColorSchemeIndex::Accent5),
+        SchemeColorVal::Accent6 => Some(ColorSchemeIndex::Accent6),
+        SchemeColorVal::Hyperlink => Some(ColorSchemeIndex::Hyperlink),
+        SchemeColorVal::FollowedHyperlink => Some(ColorSchemeIndex::FollowedHyperlink),
+        SchemeColorVal::PlaceholderColor => None,
+    }
+}
+
+fn apply_tint(rgb: HexColorRGB, tint: Option<u8>) -> HexColorRGB {
+    match tint {
+        Some(tint) => {
+            let tint = f32::from(tint) / 255.0;
+            let tint_component = |component: u8| (f32::from(component) * tint + 255.0 * (1.0 - tint)).round() as u8;
+            [tint_component(rgb[0]), tint_component(rgb[1]), tint_component(rgb[2])]
+        }
+        None => rgb,
+    }
+}
+
+fn apply_shade(rgb: HexColorRGB, shade: Option<u8>) -> HexColorRGB {
+    match shade {
+        Some(shade) => {
+            let shade = f32::from(shade) / 255.0;
+            let shade_component = |component: u8| (f32::from(component) * shade).round() as u8;
+            [
+                shade_component(rgb[0]),
+                shade_component(rgb[1]),
+                shade_component(rgb[2]),
+            ]
+        }
+        None => rgb,
+    }
+}
+
+fn sc_rgb_to_rgb(color: &ScRgbColor) -> HexColorRGB {
+    // scRGB components are linear percentages which need to be gamma corrected to get sRGB values
+    let to_srgb = |percentage: f32| {
+        let linear = (percentage / 100_000.0).clamp(0.0, 1.0);
+        let srgb = if linear <= 0.003_130_8 {
+            linear * 12.92
+        } else {
+            1.055 * linear.powf(1.0 / 2.4) - 0.055
+        };
+
+        (srgb * 255.0).round() as u8
+    };
+
+    [to_srgb(color.r), to_srgb(color.g), to_srgb(color.b)]
+}
+
+fn hsl_to_rgb(color: &HslColor) -> HexColorRGB {
+    let hue = (color.hue as f32 / 60_000.0).rem_euclid(360.0) / 360.0;
+    let saturation = (color.saturation / 100_000.0).clamp(0.0, 1.0);
+    let luminance = (color.luminance / 100_000.0).clamp(0.0, 1.0);
+
+    if saturation == 0.0 {
+        let value = (luminance * 255.0).round() as u8;
+        return [value, value, value];
+    }
+
+    let q = if luminance < 0.5 {
+        luminance * (1.0 + saturation)
+    } else {
+        luminance + saturation - luminance * saturation
+    };
+    let p = 2.0 * luminance - q;
+
+    let hue_to_component = |t: f32| {
+        let t = t.rem_euclid(1.0);
+        let value = if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        };
+
+        (value * 255.0).round() as u8
+    };
+
+    [
+        hue_to_component(hue + 1.0 / 3.0),
+        hue_to_component(hue),
+        hue_to_component(hue - 1.0 / 3.0),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    fn test_color_scheme() -> ColorScheme {
+        let xml = r#"<a:clrScheme name="Office">
+            <a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1>
+            <a:lt1><a:sysClr val="window" lastClr="FFFFFF"/></a:lt1>
+            <a:dk2><a:srgbClr val="44546A"/></a:dk2>
+            <a:lt2><a:srgbClr val="E7E6E6"/></a:lt2>
+            <a:accent1><a:srgbClr val="4472C4"/></a:accent1>
+            <a:accent2><a:srgbClr val="ED7D31"/></a:accent2>
+            <a:accent3><a:scrgbClr r="100000" g="0" b="0"/></a:accent3>
+            <a:accent4><a:hslClr hue="14400000" sat="100000" lum="25000"/></a:accent4>
+            <a:accent5><a:prstClr val="black"/></a:accent5>
+            <a:accent6><a:srgbClr val="70AD47"/></a:accent6>
+            <a:hlink><a:srgbClr val="0563C1"/></a:hlink>
+            <a:folHlink><a:srgbClr val="954F72"/></a:folHlink>
+        </a:clrScheme>"#;
+
+        ColorScheme::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_color_to_rgb() {
+        let color_scheme = test_color_scheme();
+
+        assert_eq!(
+            color_scheme.get_color(ColorSchemeIndex::Dark1).to_rgb(),
+            Some([0x00, 0x00, 0x00])
+        );
+        assert_eq!(
+            color_scheme.get_color(ColorSchemeIndex::Accent1).to_rgb(),
+            Some([0x44, 0x72, 0xC4])
+        );
+        assert_eq!(
+            color_scheme.get_color(ColorSchemeIndex::Accent3).to_rgb(),
+            Some([0xFF, 0x00, 0x00])
+        );
+        assert_eq!(
+            color_scheme.get_color(ColorSchemeIndex::Accent4).to_rgb(),
+            Some([0x00, 0x00, 0x80])
+        );
+        assert_eq!(color_scheme.get_color(ColorSchemeIndex::Accent5).to_rgb(), None);
+    }
+
+    #[test]
+    pub fn test_apply_tint_and_shade() {
+        assert_eq!(apply_tint([0x44, 0x72, 0xC4], None), [0x44, 0x72, 0xC4]);
+        assert_eq!(apply_tint([0x44, 0x72, 0xC4], Some(0xFF)), [0x44, 0x72, 0xC4]);
+        assert_eq!(apply_tint([0x00, 0x00, 0x00], Some(0x99)), [0x66, 0x66, 0x66]);
+        assert_eq!(apply_shade([0xFF, 0xFF, 0xFF], Some(0xBF)), [0xBF, 0xBF, 0xBF]);
+        assert_eq!(apply_shade([0x44, 0x72, 0xC4], Some(0x00)), [0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    pub fn test_default_color_scheme_index() {
+        assert_eq!(
+            default_color_scheme_index(SchemeColorVal::Background1),
+            Some(ColorSchemeIndex::Light1)
+        );
+        assert_eq!(
+            default_color_scheme_index(SchemeColorVal::Text2),
+            Some(ColorSchemeIndex::Dark2)
+        );
+        assert_eq!(default_color_scheme_index(SchemeColorVal::PlaceholderColor), None);
+    }
+}
@@ -0,0 +1,136 @@
+use super::flatopc::FLAT_OPC_NAMESPACE;
+use crate::error::UnsupportedFormat;
+use std::{
+    fmt::{Display, Formatter},
+    io::{self, Read, Seek, SeekFrom},
+};
+
+/// The number of bytes read from the start of a file to detect its kind.
+pub const SNIFF_LENGTH: usize = 1024;
+
+const ZIP_SIGNATURES: [&[u8]; 2] = [b"PK\x03\x04", b"PK\x05\x06"];
+const COMPOUND_FILE_SIGNATURE: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+const RTF_SIGNATURE: &[u8] = b"{\\rtf";
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The kind of a file detected from its signature, see [`sniff_file_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// A zip archive, which is the container of Office Open XML packages
+    Zip,
+    /// An OLE2 compound file, e.g. a legacy binary `.doc` or `.ppt` document, or an encrypted Office Open XML package
+    CompoundFile,
+    /// A Rich Text Format document
+    Rtf,
+    /// A Flat OPC document storing all the parts of a package in a single xml document
+    FlatOpc,
+    /// An xml document other than Flat OPC, e.g. a WordprocessingML 2003 document
+    Xml,
+    Unknown,
+}
+
+impl Display for FileKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            FileKind::Zip => "zip archive",
+            FileKind::CompoundFile => "OLE2 compound file (legacy binary Office document or encrypted package)",
+            FileKind::Rtf => "Rich Text Format document",
+            FileKind::FlatOpc => "Flat OPC xml document",
+            FileKind::Xml => "xml document",
+            FileKind::Unknown => "unknown file format",
+        };
+        f.write_str(description)
+    }
+}
+
+/// Detects the kind of a file from its first bytes. Passing the first [`SNIFF_LENGTH`] bytes is enough to tell Flat
+/// OPC documents from other xml documents.
+///
+/// ```
+/// use oox::shared::filekind::{sniff_file_kind, FileKind};
+///
+/// assert_eq!(sniff_file_kind(b"PK\x03\x04\x14\x00"), FileKind::Zip);
+/// assert_eq!(sniff_file_kind(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1\x00"), FileKind::CompoundFile);
+/// assert_eq!(sniff_file_kind(br"{\rtf1\ansi"), FileKind::Rtf);
+/// ```
+pub fn sniff_file_kind(header: &[u8]) -> FileKind {
+    if ZIP_SIGNATURES.iter().any(|signature| header.starts_with(signature)) {
+        return FileKind::Zip;
+    }
+
+    if header.starts_with(COMPOUND_FILE_SIGNATURE) {
+        return FileKind::CompoundFile;
+    }
+
+    let text = header.strip_prefix(UTF8_BOM).unwrap_or(header);
+    let text = &text[text.iter().take_while(|c| c.is_ascii_whitespace()).count()..];
+    if text.starts_with(RTF_SIGNATURE) {
+        return FileKind::Rtf;
+    }
+
+    if text.starts_with(b"<") {
+        let is_flat_opc = text
+            .windows(FLAT_OPC_NAMESPACE.len())
+            .any(|window| window == FLAT_OPC_NAMESPACE.as_bytes());
+        return match is_flat_opc {
+            true => FileKind::FlatOpc,
+            false => FileKind::Xml,
+        };
+    }
+
+    FileKind::Unknown
+}
+
+/// Detects the kind of a file from its first [`SNIFF_LENGTH`] bytes and rewinds the reader to where it was.
+pub fn sniff_reader<R: Read + Seek>(reader: &mut R) -> io::Result<FileKind> {
+    let position = reader.stream_position()?;
+    let mut header = Vec::with_capacity(SNIFF_LENGTH);
+    reader.by_ref().take(SNIFF_LENGTH as u64).read_to_end(&mut header)?;
+    reader.seek(SeekFrom::Start(position))?;
+    Ok(sniff_file_kind(&header))
+}
+
+/// Returns an [`UnsupportedFormat`] error if the reader holds a known format other than a zip archive. Unknown
+/// formats are left to the zip reader, which reports a more specific error for damaged archives.
+pub(crate) fn ensure_zip_package<R: Read + Seek>(reader: &mut R) -> Result<(), Box<dyn std::error::Error>> {
+    match sniff_reader(reader)? {
+        FileKind::Zip | FileKind::Unknown => Ok(()),
+        detected => Err(UnsupportedFormat { detected }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    pub fn test_sniff_file_kind() {
+        assert_eq!(sniff_file_kind(b"PK\x05\x06"), FileKind::Zip);
+        assert_eq!(sniff_file_kind(b"\xEF\xBB\xBF\r\n {\\rtf1"), FileKind::Rtf);
+        assert_eq!(
+            sniff_file_kind(
+                br#"<?xml version="1.0" standalone="yes"?><?mso-application progid="Word.Document"?>
+                <pkg:package xmlns:pkg="http://schemas.microsoft.com/office/2006/xmlPackage">"#
+            ),
+            FileKind::FlatOpc
+        );
+        assert_eq!(
+            sniff_file_kind(br#"<?xml version="1.0"?><w:wordDocument>"#),
+            FileKind::Xml
+        );
+        assert_eq!(sniff_file_kind(b"%PDF-1.7"), FileKind::Unknown);
+        assert_eq!(sniff_file_kind(b""), FileKind::Unknown);
+
+        let mut reader = Cursor::new(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1\x00\x00".to_vec());
+        match ensure_zip_package(&mut reader)
+            .unwrap_err()
+            .downcast::<UnsupportedFormat>()
+        {
+            Ok(error) => assert_eq!(error.detected, FileKind::CompoundFile),
+            Err(error) => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(reader.position(), 0);
+        assert!(ensure_zip_package(&mut Cursor::new(b"PK\x03\x04")).is_ok());
+    }
+}
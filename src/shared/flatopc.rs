@@ -0,0 +1,144 @@
+//! Reading of Flat OPC documents, which store all the parts of a package in a single xml document (`pkg:package`),
+//! e.g. the "Word XML Document" format of Word.
+//!
+//! Xml parts are embedded as the child element of `pkg:xmlData`, other parts are base64 encoded in `pkg:binaryData`.
+
+use crate::{
+    error::{FlatOpcError, MissingAttributeError},
+    shared::contenttypes::ContentTypes,
+    xml::XmlNode,
+};
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+pub const FLAT_OPC_NAMESPACE: &str = "http://schemas.microsoft.com/office/2006/xmlPackage";
+
+/// A part of a Flat OPC document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatOpcPart {
+    /// The name of the part without the leading slash, the same way it's stored in a zip archive.
+    pub name: String,
+    pub content_type: String,
+    /// The content of the part. Xml parts are serialized from their parsed xml tree, so they aren't byte identical to
+    /// the embedded xml.
+    pub content: Vec<u8>,
+}
+
+/// Reads the parts of a Flat OPC document in document order.
+///
+/// ```
+/// use oox::{shared::flatopc::read_flat_opc_parts, xml::XmlNode};
+/// use std::str::FromStr;
+///
+/// let xml = r#"<pkg:package xmlns:pkg="http://schemas.microsoft.com/office/2006/xmlPackage">
+///     <pkg:part pkg:name="/word/media/image1.png" pkg:contentType="image/png">
+///         <pkg:binaryData>iVBORw==</pkg:binaryData>
+///     </pkg:part>
+/// </pkg:package>"#;
+/// let parts = read_flat_opc_parts(&XmlNode::from_str(xml).unwrap()).unwrap();
+/// assert_eq!(parts[0].name, "word/media/image1.png");
+/// assert_eq!(parts[0].content, vec![0x89, b'P', b'N', b'G']);
+/// ```
+pub fn read_flat_opc_parts(xml_node: &XmlNode) -> Result<Vec<FlatOpcPart>> {
+    if xml_node.local_name() != "package" {
+        return Err(FlatOpcError::NotAPackage(String::from(&*xml_node.name)).into());
+    }
+
+    xml_node
+        .child_nodes
+        .iter()
+        .filter(|child_node| child_node.local_name() == "part")
+        .map(|part_node| {
+            let name = package_attribute(part_node, "name")
+                .ok_or_else(|| MissingAttributeError::new(part_node.name.clone(), "name"))?
+                .trim_start_matches('/');
+            let content_type = package_attribute(part_node, "contentType")
+                .ok_or_else(|| MissingAttributeError::new(part_node.name.clone(), "contentType"))?;
+
+            let data_node = part_node
+                .child_nodes
+                .iter()
+                .find(|child_node| matches!(child_node.local_name(), "xmlData" | "binaryData"));
+            let content = match data_node {
+                Some(data_node) if data_node.local_name() == "xmlData" => data_node
+                    .child_nodes
+                    .first()
+                    .map(|xml| xml.to_xml_string().into_bytes())
+                    .unwrap_or_default(),
+                Some(data_node) if data_node.local_name() == "binaryData" => {
+                    decode_base64(data_node.text.as_deref().unwrap_or_default())
+                        .ok_or_else(|| FlatOpcError::InvalidBinaryData(String::from(name)))?
+                }
+                _ => Vec::new(),
+            };
+
+            Ok(FlatOpcPart {
+                name: String::from(name),
+                content_type: String::from(content_type),
+                content,
+            })
+        })
+        .collect()
+}
+
+/// Returns the content types of the parts, as the content types part of a zip package would declare them.
+pub fn flat_opc_content_types(parts: &[FlatOpcPart]) -> ContentTypes {
+    ContentTypes {
+        overrides: parts
+            .iter()
+            .map(|part| (part.name.to_lowercase(), part.content_type.clone()))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn package_attribute<'a>(xml_node: &'a XmlNode, local_name: &str) -> Option<&'a str> {
+    xml_node
+        .attributes
+        .iter()
+        .find(|(attr, _)| attr.rsplit(':').next() == Some(local_name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Decodes base64 encoded data, ignoring whitespace. Returns `None` if the data contains invalid characters.
+fn decode_base64(data: &str) -> Option<Vec<u8>> {
+    let mut decoded = Vec::with_capacity(data.len() / 4 * 3);
+    let mut group = 0u32;
+    let mut bits = 0;
+    for c in data.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return None,
+        };
+
+        group = group << 6 | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_decode_base64() {
+        assert_eq!(decode_base64("").unwrap(), b"");
+        assert_eq!(decode_base64("TWFu").unwrap(), b"Man");
+        assert_eq!(decode_base64("TWE=").unwrap(), b"Ma");
+        assert_eq!(decode_base64("TQ==\r\n").unwrap(), b"M");
+        assert_eq!(decode_base64("aGVs\nbG8=").unwrap(), b"hello");
+        assert_eq!(decode_base64("TW*u"), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! Glyph metrics used to measure text, shared by the WordprocessingML text measurement and the PresentationML
+//! auto-fit.
+
+/// A face of a font family.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontFace<'a> {
+    pub name: &'a str,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// The vertical metrics of a font as fractions of the font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    pub ascent: f64,
+    pub descent: f64,
+    pub line_gap: f64,
+}
+
+impl LineMetrics {
+    /// Returns the height of a single line as a fraction of the font size.
+    pub fn line_height(&self) -> f64 {
+        self.ascent + self.descent + self.line_gap
+    }
+}
+
+/// Trait providing the glyph metrics of fonts. Widths and heights are fractions of the font size, e.g. the advance
+/// width of a glyph 600 units wide in a font of 1000 units per em is `0.6`.
+pub trait GlyphMetrics {
+    /// Returns whether the metrics of the font are known. Consumers substitute unknown fonts with a known one.
+    fn has_font(&self, name: &str) -> bool;
+
+    /// Returns the advance width of the character, or `None` if the font has no glyph for it.
+    fn advance_width(&self, face: FontFace<'_>, c: char) -> Option<f64>;
+
+    fn line_metrics(&self, face: FontFace<'_>) -> LineMetrics;
+}
+
+/// Approximate metrics of Arial, Times New Roman and Courier New, based on the metrics of the metric compatible
+/// standard PostScript fonts. The faces of a font share the metrics of the regular face. Characters outside of ASCII
+/// are half an em wide, or a full em if they are East Asian.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuiltinMetrics;
+
+pub(crate) const ARIAL: &str = "Arial";
+pub(crate) const TIMES_NEW_ROMAN: &str = "Times New Roman";
+pub(crate) const COURIER_NEW: &str = "Courier New";
+
+/// The advance widths of the printable ASCII characters from space to tilde in thousandths of an em.
+#[rustfmt::skip]
+const ARIAL_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278,
+    556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556,
+    1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778,
+    667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556,
+    333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556,
+    556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+#[rustfmt::skip]
+const TIMES_NEW_ROMAN_WIDTHS: [u16; 95] = [
+    250, 333, 408, 500, 500, 833, 778, 180, 333, 333, 500, 564, 250, 333, 250, 278,
+    500, 500, 500, 500, 500, 500, 500, 500, 500, 500, 278, 278, 564, 564, 564, 444,
+    921, 722, 667, 667, 722, 611, 556, 722, 722, 333, 389, 722, 611, 889, 722, 722,
+    556, 722, 667, 556, 611, 722, 722, 944, 722, 722, 611, 333, 278, 333, 469, 500,
+    333, 444, 500, 444, 500, 444, 333, 500, 500, 278, 278, 500, 278, 778, 500, 500,
+    500, 500, 333, 389, 278, 500, 500, 722, 500, 500, 444, 480, 200, 480, 541,
+];
+
+impl GlyphMetrics for BuiltinMetrics {
+    fn has_font(&self, name: &str) -> bool {
+        [ARIAL, TIMES_NEW_ROMAN, COURIER_NEW]
+            .iter()
+            .any(|font| font.eq_ignore_ascii_case(name))
+    }
+
+    fn advance_width(&self, face: FontFace<'_>, c: char) -> Option<f64> {
+        if is_east_asian(c) {
+            return Some(1.0);
+        }
+
+        let widths = if face.name.eq_ignore_ascii_case(COURIER_NEW) {
+            return Some(0.6);
+        } else if face.name.eq_ignore_ascii_case(TIMES_NEW_ROMAN) {
+            &TIMES_NEW_ROMAN_WIDTHS
+        } else {
+            &ARIAL_WIDTHS
+        };
+
+        match c {
+            ' '..='~' => Some(f64::from(widths[c as usize - 0x20]) / 1000.0),
+            c if c.is_control() => Some(0.0),
+            _ => Some(0.5),
+        }
+    }
+
+    fn line_metrics(&self, face: FontFace<'_>) -> LineMetrics {
+        let (ascent, descent, line_gap) = if face.name.eq_ignore_ascii_case(COURIER_NEW) {
+            (0.833, 0.300, 0.0)
+        } else if face.name.eq_ignore_ascii_case(TIMES_NEW_ROMAN) {
+            (0.891, 0.216, 0.042)
+        } else {
+            (0.905, 0.212, 0.033)
+        };
+
+        LineMetrics {
+            ascent,
+            descent,
+            line_gap,
+        }
+    }
+}
+
+/// Returns whether the character is in one of the common CJK blocks, which are rendered with the East Asian font.
+pub(crate) fn is_east_asian(c: char) -> bool {
+    matches!(c as u32,
+        0x1100..=0x11FF
+        | 0x2E80..=0x9FFF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7FF
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFFEF
+        | 0x20000..=0x3FFFF)
+}
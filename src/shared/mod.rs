@@ -1,6 +1,19 @@
 #![forbid(unsafe_code)]
 
+pub mod contenttypes;
+pub mod customxml;
 pub mod docprops;
 pub mod drawingml;
+pub mod filekind;
+pub mod flatopc;
+pub mod fontmetrics;
+pub mod package;
+pub mod partcache;
+pub mod partkind;
+pub mod partname;
+#[cfg(feature = "tokio")]
+pub mod partsource;
 pub mod relationship;
 pub mod sharedtypes;
+pub mod validation;
+pub mod vml;
@@ -0,0 +1,194 @@
+use super::{
+    contenttypes::ContentTypes,
+    docprops::{AppInfo, Core},
+    filekind::ensure_zip_package,
+    partkind::{PackageParts, PartKind},
+    relationship::{
+        Relationships, CORE_PROPERTIES_RELATION_TYPE, EXTENDED_PROPERTIES_RELATION_TYPE, OFFICE_DOCUMENT_RELATION_TYPE,
+    },
+};
+use std::{
+    fs::File,
+    io::{Read, Seek},
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+const DEFAULT_CORE_PROPERTIES_PART_NAME: &str = "docProps/core.xml";
+const DEFAULT_EXTENDED_PROPERTIES_PART_NAME: &str = "docProps/app.xml";
+
+/// The metadata of an Office Open XML package of any kind, without the content of its parts.
+///
+/// Only the content types, the relationship parts and the document properties are read, which makes opening fast
+/// even for very large documents, e.g. when cataloguing a document repository.
+///
+/// ```no_run
+/// use oox::shared::{package::OoxPackage, partkind::PartKind};
+/// use std::path::Path;
+///
+/// let package = OoxPackage::open_metadata_only(Path::new("report.docx")).unwrap();
+/// if package.main_part_kind() == Some(PartKind::MainDocument) {
+///     let title = package.core.as_ref().and_then(|core| core.title.as_ref());
+///     println!("{:?}", title);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OoxPackage {
+    pub file_path: PathBuf,
+    /// The parts of the package classified by their content types.
+    pub parts: PackageParts,
+    /// The relationships of the package (`_rels/.rels`) followed by the relationships of the parts.
+    pub relationships: Vec<Relationships>,
+    pub app_info: Option<AppInfo>,
+    pub core: Option<Core>,
+}
+
+impl OoxPackage {
+    /// Opens a package reading only its content types, relationships and document properties.
+    pub fn open_metadata_only(file_path: &Path) -> Result<Self> {
+        let mut file = File::open(file_path)?;
+        ensure_zip_package(&mut file)?;
+        let mut instance = Self::from_zip_metadata_only(&mut ZipArchive::new(file)?)?;
+        instance.file_path = PathBuf::from(file_path);
+        Ok(instance)
+    }
+
+    pub fn from_zip_metadata_only<R: Read + Seek>(zipper: &mut ZipArchive<R>) -> Result<Self> {
+        let parts = PackageParts::from_zip(zipper)?;
+
+        let mut relationships = Vec::new();
+        for part in parts.parts_of_kind(PartKind::Relationships) {
+            let mut zip_file = zipper.by_name(&part.name)?;
+            relationships.push(Relationships::from_zip_file(&mut zip_file)?);
+        }
+        relationships.sort_by_key(|rels| !rels.source_part.is_empty());
+
+        let mut instance = Self {
+            parts,
+            relationships,
+            ..Default::default()
+        };
+
+        let core_part_name = instance
+            .package_part_of_type(CORE_PROPERTIES_RELATION_TYPE)
+            .unwrap_or_else(|| String::from(DEFAULT_CORE_PROPERTIES_PART_NAME));
+        if let Ok(mut zip_file) = zipper.by_name(&core_part_name) {
+            instance.core = Some(Core::from_zip_file(&mut zip_file)?);
+        }
+
+        let app_part_name = instance
+            .package_part_of_type(EXTENDED_PROPERTIES_RELATION_TYPE)
+            .unwrap_or_else(|| String::from(DEFAULT_EXTENDED_PROPERTIES_PART_NAME));
+        if let Ok(mut zip_file) = zipper.by_name(&app_part_name) {
+            instance.app_info = Some(AppInfo::from_zip_file(&mut zip_file)?);
+        }
+
+        Ok(instance)
+    }
+
+    pub fn content_types(&self) -> &ContentTypes {
+        &self.parts.content_types
+    }
+
+    /// Returns the package relationships stored in `_rels/.rels`.
+    pub fn package_relationships(&self) -> Option<&Relationships> {
+        self.relationships_of("")
+    }
+
+    /// Returns the relationships of a part. Part names are compared case insensitively, with or without the leading
+    /// slash.
+    pub fn relationships_of<T: AsRef<str>>(&self, part_name: T) -> Option<&Relationships> {
+        let part_name = part_name.as_ref().trim_start_matches('/');
+        self.relationships
+            .iter()
+            .find(|rels| rels.source_part.eq_ignore_ascii_case(part_name))
+    }
+
+    /// Returns the name of the main part of the package, e.g. `word/document.xml`.
+    pub fn main_part_name(&self) -> Option<String> {
+        self.package_part_of_type(OFFICE_DOCUMENT_RELATION_TYPE)
+    }
+
+    /// Returns the kind of the main part, which tells what kind of document the package is.
+    pub fn main_part_kind(&self) -> Option<PartKind> {
+        self.parts.kind_of(self.main_part_name()?)
+    }
+
+    fn package_part_of_type(&self, rel_type: &str) -> Option<String> {
+        let package_relationships = self.package_relationships()?;
+        let rel = package_relationships.relationships_of_type(rel_type).next()?;
+        package_relationships.resolve_target(&rel.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, ZipWriter};
+
+    #[test]
+    pub fn test_open_metadata_only() {
+        let files = [
+            (
+                "[Content_Types].xml",
+                r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+                    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+                    <Default Extension="xml" ContentType="application/xml" />
+                    <Override PartName="/ppt/presentation.xml"
+                        ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml" />
+                    <Override PartName="/props/core.xml"
+                        ContentType="application/vnd.openxmlformats-package.core-properties+xml" />
+                </Types>"#,
+            ),
+            (
+                "_rels/.rels",
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                    <Relationship Id="rId1" Target="ppt/presentation.xml"
+                        Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" />
+                    <Relationship Id="rId2" Target="props/core.xml"
+                        Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties" />
+                </Relationships>"#,
+            ),
+            (
+                "ppt/_rels/presentation.xml.rels",
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                    <Relationship Id="rId1" Target="slides/slide1.xml"
+                        Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" />
+                </Relationships>"#,
+            ),
+            (
+                "props/core.xml",
+                r#"<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+                    xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title>Quarterly report</dc:title></cp:coreProperties>"#,
+            ),
+            ("ppt/presentation.xml", "<p:presentation"),
+        ];
+
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files.iter() {
+            zip_writer.start_file(*name, FileOptions::default()).unwrap();
+            zip_writer.write_all(contents.as_bytes()).unwrap();
+        }
+        let cursor = zip_writer.finish().unwrap();
+
+        // The malformed presentation part isn't read
+        let package = OoxPackage::from_zip_metadata_only(&mut ZipArchive::new(cursor).unwrap()).unwrap();
+        assert_eq!(package.main_part_name().as_deref(), Some("ppt/presentation.xml"));
+        assert_eq!(package.main_part_kind(), Some(PartKind::Presentation));
+        assert_eq!(package.relationships.len(), 2);
+        assert_eq!(package.package_relationships().unwrap().relationships.len(), 2);
+        assert_eq!(
+            package
+                .relationships_of("/ppt/presentation.xml")
+                .unwrap()
+                .resolve_target("rId1")
+                .as_deref(),
+            Some("ppt/slides/slide1.xml")
+        );
+        assert_eq!(package.core.unwrap().title.as_deref(), Some("Quarterly report"));
+        assert_eq!(package.app_info, None);
+    }
+}
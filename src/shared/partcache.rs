@@ -0,0 +1,376 @@
+use super::{
+    docprops::{AppInfo, Core},
+    drawingml::sharedstylesheet::OfficeStyleSheet,
+    filekind::ensure_zip_package,
+    package::OoxPackage,
+    partkind::PartInfo,
+    relationship::transitional_relation_type,
+};
+use crate::{
+    error::PartCacheError,
+    xml::{zip_file_to_xml_node, XmlNode},
+};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    fs::File,
+    io::{Read, Seek},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+type ParseFn = dyn Fn(&XmlNode) -> Result<Rc<dyn Any>>;
+
+/// The parsers of the parts of a [`PartCache`] by content type.
+///
+/// Parsers of parts unknown to this library can be registered next to the built-in ones, or replace them.
+///
+/// ```
+/// use oox::{shared::partcache::PartParsers, xml::XmlNode};
+///
+/// struct Bibliography {
+///     source_count: usize,
+/// }
+///
+/// let mut parsers = PartParsers::with_builtin_parsers();
+/// parsers.register(
+///     "application/vnd.openxmlformats-officedocument.bibliography+xml",
+///     |xml_node: &XmlNode| Ok(Bibliography { source_count: xml_node.child_nodes.len() }),
+/// );
+/// assert!(parsers.has_parser("application/vnd.openxmlformats-officedocument.bibliography+xml"));
+/// ```
+#[derive(Default)]
+pub struct PartParsers {
+    parsers: HashMap<String, Box<ParseFn>>,
+}
+
+impl PartParsers {
+    /// Creates the parsers of the document properties and the theme, along with the WordprocessingML and
+    /// PresentationML parts if the corresponding features are enabled.
+    pub fn with_builtin_parsers() -> Self {
+        let mut instance = Self::default();
+        instance.register(
+            "application/vnd.openxmlformats-package.core-properties+xml",
+            Core::from_xml_element,
+        );
+        instance.register(
+            "application/vnd.openxmlformats-officedocument.extended-properties+xml",
+            AppInfo::from_xml_element,
+        );
+        instance.register(
+            "application/vnd.openxmlformats-officedocument.theme+xml",
+            OfficeStyleSheet::from_xml_element,
+        );
+
+        #[cfg(any(test, feature = "docx"))]
+        crate::docx::package::register_part_parsers(&mut instance);
+        #[cfg(any(test, feature = "pptx"))]
+        crate::pptx::package::register_part_parsers(&mut instance);
+
+        instance
+    }
+
+    /// Registers the parser of the parts with the given content type, replacing the previous one.
+    pub fn register<T, F>(&mut self, content_type: &str, parser: F)
+    where
+        T: Any,
+        F: Fn(&XmlNode) -> Result<T> + 'static,
+    {
+        self.parsers.insert(
+            content_type.to_lowercase(),
+            Box::new(move |xml_node| Ok(Rc::new(parser(xml_node)?) as Rc<dyn Any>)),
+        );
+    }
+
+    pub fn has_parser(&self, content_type: &str) -> bool {
+        self.parsers.contains_key(&content_type.to_lowercase())
+    }
+
+    fn parser(&self, content_type: &str) -> Option<&ParseFn> {
+        self.parsers.get(&content_type.to_lowercase()).map(Box::as_ref)
+    }
+}
+
+impl Debug for PartParsers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut content_types: Vec<_> = self.parsers.keys().collect();
+        content_types.sort();
+        f.debug_struct("PartParsers")
+            .field("content_types", &content_types)
+            .finish()
+    }
+}
+
+/// A package whose parts are parsed on demand and cached by part name.
+///
+/// Parts are requested by name, content type or relationship type and parsed with the parser registered for their
+/// content type. The parsed part is returned as the type produced by the parser.
+///
+/// ```no_run
+/// # #[cfg(feature = "docx")]
+/// # fn main() {
+/// use oox::{
+///     docx::wml::{document::Document, styles::Styles},
+///     shared::{partcache::PartCache, relationship::OFFICE_DOCUMENT_RELATION_TYPE},
+/// };
+/// use std::path::Path;
+///
+/// let cache = PartCache::open(Path::new("report.docx")).unwrap();
+/// let document = cache.part_by_relationship_type::<Document>("", OFFICE_DOCUMENT_RELATION_TYPE).unwrap();
+/// let styles = cache
+///     .part_by_content_type::<Styles>("application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml")
+///     .unwrap();
+/// # }
+/// # #[cfg(not(feature = "docx"))]
+/// # fn main() {}
+/// ```
+pub struct PartCache<R: Read + Seek> {
+    pub file_path: PathBuf,
+    zipper: RefCell<ZipArchive<R>>,
+    metadata: OoxPackage,
+    parsers: PartParsers,
+    parsed_parts: RefCell<HashMap<String, Rc<dyn Any>>>,
+}
+
+impl PartCache<File> {
+    /// Opens a package with the built-in parsers. Only the content types, relationships and document properties are
+    /// read up front.
+    pub fn open(file_path: &Path) -> Result<Self> {
+        Self::open_with_parsers(file_path, PartParsers::with_builtin_parsers())
+    }
+
+    pub fn open_with_parsers(file_path: &Path, parsers: PartParsers) -> Result<Self> {
+        let mut file = File::open(file_path)?;
+        ensure_zip_package(&mut file)?;
+        let mut instance = Self::from_zip(ZipArchive::new(file)?, parsers)?;
+        instance.file_path = PathBuf::from(file_path);
+        instance.metadata.file_path = PathBuf::from(file_path);
+        Ok(instance)
+    }
+}
+
+impl<R: Read + Seek> PartCache<R> {
+    pub fn from_zip(mut zipper: ZipArchive<R>, parsers: PartParsers) -> Result<Self> {
+        let metadata = OoxPackage::from_zip_metadata_only(&mut zipper)?;
+        Ok(Self {
+            file_path: PathBuf::new(),
+            zipper: RefCell::new(zipper),
+            metadata,
+            parsers,
+            parsed_parts: Default::default(),
+        })
+    }
+
+    /// Returns the content types, relationships and document properties of the package.
+    pub fn metadata(&self) -> &OoxPackage {
+        &self.metadata
+    }
+
+    pub fn parsers(&self) -> &PartParsers {
+        &self.parsers
+    }
+
+    /// Returns the names of the parts with the given content type in the order of the zip archive.
+    pub fn part_names_with_content_type(&self, content_type: &str) -> Vec<&str> {
+        self.metadata
+            .parts
+            .parts
+            .iter()
+            .filter(|part| {
+                part.content_type
+                    .as_deref()
+                    .is_some_and(|part_content_type| part_content_type.eq_ignore_ascii_case(content_type))
+            })
+            .map(|part| part.name.as_str())
+            .collect()
+    }
+
+    /// Returns the names of the parts targeted by the relationships of the given type of a part. The relationships of
+    /// the package are the ones of the empty part name. External targets are skipped.
+    pub fn related_part_names(&self, source_part: &str, rel_type: &str) -> Vec<String> {
+        let relationships = match self.metadata.relationships_of(source_part) {
+            Some(relationships) => relationships,
+            None => return Vec::new(),
+        };
+
+        let rel_type = transitional_relation_type(rel_type);
+        relationships
+            .relationships_of_type(&rel_type)
+            .filter_map(|rel| relationships.resolve_target(&rel.id))
+            .collect()
+    }
+
+    /// Returns a part parsed with the parser of its content type. The part is only parsed on the first request.
+    /// Part names are compared case insensitively, with or without the leading slash.
+    pub fn part<T: Any>(&self, part_name: &str) -> Result<Rc<T>> {
+        let key = part_name.trim_start_matches('/').to_lowercase();
+        let parsed_part = self.parsed_parts.borrow().get(&key).cloned();
+        let parsed_part = match parsed_part {
+            Some(parsed_part) => parsed_part,
+            None => {
+                let parsed_part = self.parse_part(&key)?;
+                self.parsed_parts.borrow_mut().insert(key, Rc::clone(&parsed_part));
+                parsed_part
+            }
+        };
+
+        parsed_part
+            .downcast::<T>()
+            .map_err(|_| PartCacheError::TypeMismatch(String::from(part_name)).into())
+    }
+
+    /// Returns the first part with the given content type, or `None` if the package has no such part.
+    pub fn part_by_content_type<T: Any>(&self, content_type: &str) -> Result<Option<Rc<T>>> {
+        self.part_names_with_content_type(content_type)
+            .first()
+            .map(|part_name| self.part(part_name))
+            .transpose()
+    }
+
+    /// Returns the first part targeted by a relationship of the given type of a part, or `None` if there's no such
+    /// relationship, e.g. the main document of a package with [`OFFICE_DOCUMENT_RELATION_TYPE`] from the empty part
+    /// name.
+    ///
+    /// [`OFFICE_DOCUMENT_RELATION_TYPE`]: super::relationship::OFFICE_DOCUMENT_RELATION_TYPE
+    pub fn part_by_relationship_type<T: Any>(&self, source_part: &str, rel_type: &str) -> Result<Option<Rc<T>>> {
+        self.related_part_names(source_part, rel_type)
+            .first()
+            .map(|part_name| self.part(part_name))
+            .transpose()
+    }
+
+    /// Returns the raw content of a part, e.g. an image.
+    pub fn part_bytes(&self, part_name: &str) -> Result<Vec<u8>> {
+        let part = self.find_part(part_name)?;
+        let mut zipper = self.zipper.borrow_mut();
+        let mut zip_file = zipper.by_name(&part.name)?;
+        let mut content = Vec::with_capacity(zip_file.size() as usize);
+        zip_file.read_to_end(&mut content)?;
+        Ok(content)
+    }
+
+    /// Drops the parsed parts, so they are parsed again when they are next requested.
+    pub fn clear(&self) {
+        self.parsed_parts.borrow_mut().clear();
+    }
+
+    fn parse_part(&self, part_name: &str) -> Result<Rc<dyn Any>> {
+        let part = self.find_part(part_name)?;
+        let content_type = part.content_type.as_deref();
+        let parser = content_type
+            .and_then(|content_type| self.parsers.parser(content_type))
+            .ok_or_else(|| PartCacheError::NoParser {
+                part_name: part.name.clone(),
+                content_type: content_type.map(String::from),
+            })?;
+
+        let mut zipper = self.zipper.borrow_mut();
+        let xml_node = zip_file_to_xml_node(&mut zipper.by_name(&part.name)?)?;
+        parser(&xml_node)
+    }
+
+    fn find_part(&self, part_name: &str) -> Result<&PartInfo> {
+        let part_name = part_name.trim_start_matches('/');
+        self.metadata
+            .parts
+            .parts
+            .iter()
+            .find(|part| part.name.eq_ignore_ascii_case(part_name))
+            .ok_or_else(|| PartCacheError::MissingPart(String::from(part_name)).into())
+    }
+}
+
+impl<R: Read + Seek> Debug for PartCache<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PartCache")
+            .field("file_path", &self.file_path)
+            .field("metadata", &self.metadata)
+            .field("parsers", &self.parsers)
+            .field("parsed_parts", &self.parsed_parts.borrow().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{docx::wml::document::Document, shared::relationship::OFFICE_DOCUMENT_RELATION_TYPE};
+    use std::io::{Cursor, Write};
+    use zip::{write::FileOptions, ZipWriter};
+
+    const MAIN_DOCUMENT_CONTENT_TYPE: &str =
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml";
+    const BIBLIOGRAPHY_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.bibliography+xml";
+
+    fn package_for_test() -> ZipArchive<Cursor<Vec<u8>>> {
+        let files = [
+            (
+                "[Content_Types].xml",
+                r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+                    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+                    <Default Extension="xml" ContentType="application/xml" />
+                    <Override PartName="/word/document.xml"
+                        ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml" />
+                    <Override PartName="/customXml/item1.xml"
+                        ContentType="application/vnd.openxmlformats-officedocument.bibliography+xml" />
+                </Types>"#,
+            ),
+            (
+                "_rels/.rels",
+                r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+                    <Relationship Id="rId1" Target="word/document.xml"
+                        Type="http://purl.oclc.org/ooxml/officeDocument/relationships/officeDocument" />
+                </Relationships>"#,
+            ),
+            (
+                "word/document.xml",
+                r#"<w:document><w:body><w:p><w:r><w:t>Hello</w:t></w:r></w:p></w:body></w:document>"#,
+            ),
+            (
+                "customXml/item1.xml",
+                r#"<b:Sources><b:Source></b:Source><b:Source></b:Source></b:Sources>"#,
+            ),
+        ];
+
+        let mut zip_writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files.iter() {
+            zip_writer.start_file(*name, FileOptions::default()).unwrap();
+            zip_writer.write_all(contents.as_bytes()).unwrap();
+        }
+        ZipArchive::new(zip_writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_part_cache() {
+        let mut parsers = PartParsers::with_builtin_parsers();
+        parsers.register(BIBLIOGRAPHY_CONTENT_TYPE, |xml_node: &XmlNode| {
+            Ok(xml_node.child_nodes.len())
+        });
+        let cache = PartCache::from_zip(package_for_test(), parsers).unwrap();
+
+        let document = cache
+            .part_by_relationship_type::<Document>("", OFFICE_DOCUMENT_RELATION_TYPE)
+            .unwrap()
+            .unwrap();
+        assert_eq!(document.paragraphs().count(), 1);
+        let cached = cache
+            .part_by_content_type::<Document>(MAIN_DOCUMENT_CONTENT_TYPE)
+            .unwrap()
+            .unwrap();
+        assert!(Rc::ptr_eq(&document, &cached));
+
+        assert_eq!(*cache.part::<usize>("/customXml/item1.xml").unwrap(), 2);
+        assert!(cache.part::<Document>("customXml/item1.xml").is_err());
+        assert!(cache.part::<usize>("_rels/.rels").is_err());
+        assert!(cache.part::<usize>("word/missing.xml").is_err());
+        assert!(cache
+            .part_bytes("word/document.xml")
+            .unwrap()
+            .starts_with(b"<w:document>"));
+    }
+}
@@ -0,0 +1,309 @@
+use super::{
+    contenttypes::{ContentTypes, CONTENT_TYPES_PART_NAME},
+    filekind::ensure_zip_package,
+};
+use std::{
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+use zip::ZipArchive;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+/// The kind of a package part, derived from its content type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartKind {
+    CoreProperties,
+    ExtendedProperties,
+    CustomProperties,
+    Relationships,
+    Theme,
+    ThemeOverride,
+    Chart,
+    Diagram,
+    CustomXmlProperties,
+    Image,
+    /// Audio and video files
+    Media,
+    OleObject,
+    /// An embedded package, e.g. a spreadsheet embedded in a document
+    EmbeddedPackage,
+    // WordprocessingML
+    MainDocument,
+    GlossaryDocument,
+    Styles,
+    Numbering,
+    Settings,
+    WebSettings,
+    FontTable,
+    Footnotes,
+    Endnotes,
+    Comments,
+    CommentsExtended,
+    Header,
+    Footer,
+    // PresentationML
+    Presentation,
+    Slide,
+    SlideLayout,
+    SlideMaster,
+    NotesSlide,
+    NotesMaster,
+    HandoutMaster,
+    PresentationProperties,
+    ViewProperties,
+    TableStyles,
+    CommentAuthors,
+    /// A part with a content type that's not known by this library
+    Unknown,
+}
+
+const WML: &str = "application/vnd.openxmlformats-officedocument.wordprocessingml.";
+const PML: &str = "application/vnd.openxmlformats-officedocument.presentationml.";
+const DML: &str = "application/vnd.openxmlformats-officedocument.drawingml.";
+
+impl PartKind {
+    /// Returns the kind of the parts with the given content type.
+    ///
+    /// ```
+    /// use oox::shared::partkind::PartKind;
+    ///
+    /// assert_eq!(
+    ///     PartKind::from_content_type("application/vnd.openxmlformats-officedocument.presentationml.slide+xml"),
+    ///     PartKind::Slide
+    /// );
+    /// assert_eq!(PartKind::from_content_type("image/png"), PartKind::Image);
+    /// assert_eq!(PartKind::from_content_type("application/xml"), PartKind::Unknown);
+    /// ```
+    pub fn from_content_type(content_type: &str) -> Self {
+        let content_type = content_type.trim();
+        if let Some(subtype) = content_type.strip_prefix(WML) {
+            return match subtype {
+                "document.main+xml" | "template.main+xml" => PartKind::MainDocument,
+                "document.glossary+xml" => PartKind::GlossaryDocument,
+                "styles+xml" => PartKind::Styles,
+                "numbering+xml" => PartKind::Numbering,
+                "settings+xml" => PartKind::Settings,
+                "webSettings+xml" => PartKind::WebSettings,
+                "fontTable+xml" => PartKind::FontTable,
+                "footnotes+xml" => PartKind::Footnotes,
+                "endnotes+xml" => PartKind::Endnotes,
+                "comments+xml" => PartKind::Comments,
+                "commentsExtended+xml" => PartKind::CommentsExtended,
+                "header+xml" => PartKind::Header,
+                "footer+xml" => PartKind::Footer,
+                _ => PartKind::Unknown,
+            };
+        }
+
+        if let Some(subtype) = content_type.strip_prefix(PML) {
+            return match subtype {
+                "presentation.main+xml" | "slideshow.main+xml" | "template.main+xml" => PartKind::Presentation,
+                "slide+xml" => PartKind::Slide,
+                "slideLayout+xml" => PartKind::SlideLayout,
+                "slideMaster+xml" => PartKind::SlideMaster,
+                "notesSlide+xml" => PartKind::NotesSlide,
+                "notesMaster+xml" => PartKind::NotesMaster,
+                "handoutMaster+xml" => PartKind::HandoutMaster,
+                "presProps+xml" => PartKind::PresentationProperties,
+                "viewProps+xml" => PartKind::ViewProperties,
+                "tableStyles+xml" => PartKind::TableStyles,
+                "comments+xml" => PartKind::Comments,
+                "commentAuthors+xml" => PartKind::CommentAuthors,
+                _ => PartKind::Unknown,
+            };
+        }
+
+        if let Some(subtype) = content_type.strip_prefix(DML) {
+            return match subtype {
+                "chart+xml" => PartKind::Chart,
+                subtype if subtype.starts_with("diagram") => PartKind::Diagram,
+                _ => PartKind::Unknown,
+            };
+        }
+
+        match content_type {
+            "application/vnd.ms-word.document.macroEnabled.main+xml"
+            | "application/vnd.ms-word.template.macroEnabledTemplate.main+xml" => PartKind::MainDocument,
+            "application/vnd.ms-powerpoint.presentation.macroEnabled.main+xml"
+            | "application/vnd.ms-powerpoint.slideshow.macroEnabled.main+xml" => PartKind::Presentation,
+            "application/vnd.openxmlformats-package.core-properties+xml" => PartKind::CoreProperties,
+            "application/vnd.openxmlformats-officedocument.extended-properties+xml" => PartKind::ExtendedProperties,
+            "application/vnd.openxmlformats-officedocument.custom-properties+xml" => PartKind::CustomProperties,
+            "application/vnd.openxmlformats-package.relationships+xml" => PartKind::Relationships,
+            "application/vnd.openxmlformats-officedocument.theme+xml" => PartKind::Theme,
+            "application/vnd.openxmlformats-officedocument.themeOverride+xml" => PartKind::ThemeOverride,
+            "application/vnd.openxmlformats-officedocument.customXmlProperties+xml" => PartKind::CustomXmlProperties,
+            "application/vnd.openxmlformats-officedocument.oleObject" => PartKind::OleObject,
+            content_type if content_type.starts_with("image/") => PartKind::Image,
+            content_type if content_type.starts_with("audio/") || content_type.starts_with("video/") => PartKind::Media,
+            content_type
+                if content_type.starts_with("application/vnd.openxmlformats-officedocument.")
+                    && !content_type.ends_with("+xml") =>
+            {
+                PartKind::EmbeddedPackage
+            }
+            _ => PartKind::Unknown,
+        }
+    }
+}
+
+impl ContentTypes {
+    /// Returns the kind of a part, or None if the part has no content type.
+    pub fn part_kind_for<T: AsRef<str>>(&self, part_name: T) -> Option<PartKind> {
+        self.content_type_for(part_name).map(PartKind::from_content_type)
+    }
+}
+
+/// A part of a package with its content type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartInfo {
+    /// The name of the part without the leading slash, as it's stored in the zip archive.
+    pub name: String,
+    pub content_type: Option<String>,
+    pub kind: PartKind,
+}
+
+/// The parts of a package classified by their content types, without parsing the parts themselves.
+///
+/// ```no_run
+/// use oox::shared::partkind::{PackageParts, PartKind};
+/// use std::path::Path;
+///
+/// let parts = PackageParts::from_file(Path::new("presentation.pptx")).unwrap();
+/// for slide in parts.parts_of_kind(PartKind::Slide) {
+///     println!("{}", slide.name);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PackageParts {
+    pub content_types: ContentTypes,
+    /// The parts of the package in the order of the zip archive. The content types part is not included.
+    pub parts: Vec<PartInfo>,
+}
+
+impl PackageParts {
+    pub fn from_file(file_path: &Path) -> Result<Self> {
+        let mut file = File::open(file_path)?;
+        ensure_zip_package(&mut file)?;
+        Self::from_zip(&mut ZipArchive::new(file)?)
+    }
+
+    pub fn from_zip<R: Read + Seek>(zipper: &mut ZipArchive<R>) -> Result<Self> {
+        let content_types = match zipper.by_name(CONTENT_TYPES_PART_NAME) {
+            Ok(mut zip_file) => ContentTypes::from_zip_file(&mut zip_file)?,
+            Err(_) => Default::default(),
+        };
+
+        let mut part_names = Vec::with_capacity(zipper.len());
+        for idx in 0..zipper.len() {
+            let zip_file = zipper.by_index(idx)?;
+            if !zip_file.name().ends_with('/') && zip_file.name() != CONTENT_TYPES_PART_NAME {
+                part_names.push(String::from(zip_file.name()));
+            }
+        }
+
+        Ok(Self::new(content_types, part_names))
+    }
+
+    pub fn new<I: IntoIterator<Item = String>>(content_types: ContentTypes, part_names: I) -> Self {
+        let parts = part_names
+            .into_iter()
+            .map(|name| {
+                let content_type = content_types.content_type_for(&name).map(String::from);
+                let kind = content_type
+                    .as_deref()
+                    .map(PartKind::from_content_type)
+                    .unwrap_or(PartKind::Unknown);
+
+                PartInfo {
+                    name,
+                    content_type,
+                    kind,
+                }
+            })
+            .collect();
+
+        Self { content_types, parts }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, PartInfo> {
+        self.parts.iter()
+    }
+
+    pub fn parts_of_kind(&self, kind: PartKind) -> impl Iterator<Item = &PartInfo> {
+        self.parts.iter().filter(move |part| part.kind == kind)
+    }
+
+    /// Returns the first part of the given kind, e.g. the main document.
+    pub fn first_of_kind(&self, kind: PartKind) -> Option<&PartInfo> {
+        self.parts_of_kind(kind).next()
+    }
+
+    /// Returns the kind of a part. Part names are compared case insensitively, with or without the leading slash.
+    pub fn kind_of<T: AsRef<str>>(&self, part_name: T) -> Option<PartKind> {
+        let part_name = part_name.as_ref().trim_start_matches('/');
+        self.parts
+            .iter()
+            .find(|part| part.name.eq_ignore_ascii_case(part_name))
+            .map(|part| part.kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml::XmlNode;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_package_parts_by_kind() {
+        let xml = r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+            <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml" />
+            <Default Extension="xml" ContentType="application/xml" />
+            <Default Extension="jpeg" ContentType="image/jpeg" />
+            <Override PartName="/ppt/presentation.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml" />
+            <Override PartName="/ppt/slides/slide1.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml" />
+            <Override PartName="/ppt/slides/slide2.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml" />
+            <Override PartName="/ppt/theme/theme1.xml"
+                ContentType="application/vnd.openxmlformats-officedocument.theme+xml" />
+        </Types>"#;
+
+        let content_types = ContentTypes::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(
+            content_types.part_kind_for("/ppt/theme/theme1.xml"),
+            Some(PartKind::Theme)
+        );
+
+        let parts = PackageParts::new(
+            content_types,
+            vec![
+                String::from("_rels/.rels"),
+                String::from("ppt/presentation.xml"),
+                String::from("ppt/slides/slide1.xml"),
+                String::from("ppt/slides/slide2.xml"),
+                String::from("ppt/theme/theme1.xml"),
+                String::from("ppt/media/image1.jpeg"),
+                String::from("ppt/unknown.bin"),
+            ],
+        );
+
+        let slides: Vec<&str> = parts
+            .parts_of_kind(PartKind::Slide)
+            .map(|part| part.name.as_str())
+            .collect();
+        assert_eq!(slides, vec!["ppt/slides/slide1.xml", "ppt/slides/slide2.xml"]);
+        assert_eq!(
+            parts.first_of_kind(PartKind::Presentation).unwrap().name,
+            "ppt/presentation.xml"
+        );
+        assert_eq!(parts.kind_of("/PPT/media/image1.jpeg"), Some(PartKind::Image));
+        assert_eq!(parts.kind_of("_rels/.rels"), Some(PartKind::Relationships));
+        assert_eq!(parts.kind_of("ppt/unknown.bin"), Some(PartKind::Unknown));
+        assert_eq!(parts.kind_of("ppt/missing.xml"), None);
+    }
+}
@@ -0,0 +1,408 @@
+use super::relationship::{rels_path_for_part, Relationship};
+use crate::error::PartNameError;
+use std::{
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+pub type Result<T> = ::std::result::Result<T, PartNameError>;
+
+/// A validated part name of an Open Packaging Conventions package, e.g. `/word/document.xml`.
+///
+/// Part names are compared case-insensitively as required by the specification, so `/Word/Document.xml` and
+/// `/word/document.xml` name the same part.
+///
+/// ```
+/// use oox::shared::partname::PartName;
+///
+/// let document = PartName::new("/word/document.xml").unwrap();
+/// assert_eq!(document.zip_name(), "word/document.xml");
+/// assert_eq!(document.extension(), Some("xml"));
+/// assert_eq!(document, PartName::new("/WORD/Document.xml").unwrap());
+///
+/// let image = document.resolve("../media/./image1.png").unwrap();
+/// assert_eq!(image.as_str(), "/media/image1.png");
+///
+/// assert!(PartName::new("word/document.xml").is_err());
+/// assert!(PartName::new("/word/../document.xml").is_err());
+/// ```
+#[derive(Debug, Clone, Eq)]
+pub struct PartName(String);
+
+impl PartName {
+    /// Creates a part name, validating it against the part naming rules. The name must already be in normalized form,
+    /// use [`normalize`](Self::normalize) for arbitrary paths.
+    pub fn new<T: Into<String>>(part_name: T) -> Result<Self> {
+        let part_name = part_name.into();
+        validate_part_name(&part_name)?;
+        Ok(Self(part_name))
+    }
+
+    /// Creates a part name from the name of a zip file entry, e.g. `word/document.xml`.
+    pub fn from_zip_name(zip_name: &str) -> Result<Self> {
+        Self::new(format!("/{}", zip_name))
+    }
+
+    /// Normalizes a path into a part name. Backslashes are treated as slashes, `.` and empty segments are removed,
+    /// `..` segments are resolved, the fragment is dropped and percent-encoded unreserved characters are decoded.
+    ///
+    /// ```
+    /// use oox::shared::partname::PartName;
+    ///
+    /// assert_eq!(PartName::normalize(r"word\media\%41.png").unwrap().as_str(), "/word/media/A.png");
+    /// assert_eq!(PartName::normalize("/ppt/slides/../slideLayouts//slideLayout1.xml#top").unwrap().as_str(), "/ppt/slideLayouts/slideLayout1.xml");
+    /// assert!(PartName::normalize("/word/../../document.xml").is_err());
+    /// ```
+    pub fn normalize(path: &str) -> Result<Self> {
+        let path_without_fragment = path.split('#').next().unwrap_or_default();
+        let (segments, escapes_root) = resolve_segments(path_without_fragment);
+        if escapes_root {
+            return Err(PartNameError::OutsideOfPackage(String::from(path)));
+        }
+
+        if segments.is_empty() {
+            return Err(PartNameError::Empty);
+        }
+
+        let mut part_name = String::with_capacity(path.len() + 1);
+        for segment in segments {
+            part_name.push('/');
+            part_name.push_str(&normalize_percent_encoding(segment));
+        }
+
+        Self::new(part_name)
+    }
+
+    /// Resolves a relationship target against this part as the source part. Targets starting with a slash are
+    /// relative to the package root.
+    pub fn resolve(&self, target: &str) -> Result<Self> {
+        if target.starts_with(&['/', '\\'][..]) {
+            Self::normalize(target)
+        } else {
+            Self::normalize(&format!("{}/{}", self.directory(), target))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the name of the zip file entry storing the part, which is the part name without the leading slash.
+    pub fn zip_name(&self) -> &str {
+        &self.0[1..]
+    }
+
+    /// Returns the directory of the part without a trailing slash. Empty for parts in the package root.
+    pub fn directory(&self) -> &str {
+        self.0.rfind('/').map(|idx| &self.0[..idx]).unwrap_or_default()
+    }
+
+    pub fn file_name(&self) -> &str {
+        self.0.rfind('/').map(|idx| &self.0[idx + 1..]).unwrap_or_default()
+    }
+
+    pub fn extension(&self) -> Option<&str> {
+        let file_name = self.file_name();
+        file_name.rfind('.').map(|idx| &file_name[idx + 1..])
+    }
+
+    /// Returns the name of the relationship part that belongs to this part.
+    pub fn relationships_part_name(&self) -> Self {
+        Self(format!("/{}", rels_path_for_part(self.zip_name())))
+    }
+}
+
+impl PartialEq for PartName {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Hash for PartName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+    }
+}
+
+impl Display for PartName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for PartName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// The resolved target of a relationship.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RelationshipTarget {
+    /// A part of the package
+    Internal(PartName),
+    /// A resource outside of the package, e.g. the URL of a hyperlink
+    External(String),
+}
+
+/// Resolves a relationship target against the name of its source part. An empty source part stands for the package
+/// relationships (`_rels/.rels`), whose targets are relative to the package root.
+///
+/// ```
+/// use oox::shared::partname::{resolve_target, PartName, RelationshipTarget};
+///
+/// assert_eq!(
+///     resolve_target("word/document.xml", "media/image1.png").unwrap(),
+///     RelationshipTarget::Internal(PartName::new("/word/media/image1.png").unwrap())
+/// );
+/// assert_eq!(
+///     resolve_target("", "word/document.xml").unwrap(),
+///     RelationshipTarget::Internal(PartName::new("/word/document.xml").unwrap())
+/// );
+/// assert_eq!(
+///     resolve_target("word/document.xml", "https://example.com").unwrap(),
+///     RelationshipTarget::External(String::from("https://example.com"))
+/// );
+/// ```
+pub fn resolve_target(source_part: &str, target: &str) -> Result<RelationshipTarget> {
+    if has_uri_scheme(target) {
+        return Ok(RelationshipTarget::External(String::from(target)));
+    }
+
+    let source_part = source_part.trim_start_matches('/');
+    if source_part.is_empty() {
+        PartName::normalize(target).map(RelationshipTarget::Internal)
+    } else {
+        PartName::normalize(source_part)?
+            .resolve(target)
+            .map(RelationshipTarget::Internal)
+    }
+}
+
+impl Relationship {
+    /// Resolves the target of the relationship against the name of its source part. Targets with the `External`
+    /// target mode are returned as they are.
+    pub fn resolve(&self, source_part: &str) -> Result<RelationshipTarget> {
+        if self.is_external() {
+            Ok(RelationshipTarget::External(self.target.clone()))
+        } else {
+            resolve_target(source_part, &self.target)
+        }
+    }
+}
+
+/// Splits a path into segments, removing `.` and empty segments and resolving `..` segments. The returned flag
+/// indicates whether a `..` segment tried to leave the root, such segments are dropped.
+pub(crate) fn resolve_segments(path: &str) -> (Vec<&str>, bool) {
+    let mut segments: Vec<&str> = Vec::new();
+    let mut escapes_root = false;
+    for segment in path.split(&['/', '\\'][..]) {
+        match segment {
+            "" | "." => (),
+            ".." => escapes_root |= segments.pop().is_none(),
+            segment => segments.push(segment),
+        }
+    }
+
+    (segments, escapes_root)
+}
+
+fn validate_part_name(part_name: &str) -> Result<()> {
+    if part_name.is_empty() {
+        return Err(PartNameError::Empty);
+    }
+
+    let segments = match part_name.strip_prefix('/') {
+        Some(segments) => segments,
+        None => return Err(PartNameError::MissingLeadingSlash(String::from(part_name))),
+    };
+
+    if segments.is_empty() || segments.ends_with('/') {
+        return Err(PartNameError::TrailingSlash(String::from(part_name)));
+    }
+
+    for segment in segments.split('/') {
+        validate_segment(part_name, segment)?;
+    }
+
+    Ok(())
+}
+
+fn validate_segment(part_name: &str, segment: &str) -> Result<()> {
+    if segment.is_empty() {
+        return Err(PartNameError::EmptySegment(String::from(part_name)));
+    }
+
+    if segment.ends_with('.') {
+        return Err(PartNameError::SegmentEndsWithDot(String::from(part_name)));
+    }
+
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            let decoded = chars
+                .next()
+                .zip(chars.next())
+                .and_then(|(high, low)| Some(high.to_digit(16)? * 16 + low.to_digit(16)?))
+                .ok_or_else(|| PartNameError::InvalidPercentEncoding(String::from(part_name)))?;
+
+            let decoded = decoded as u8 as char;
+            if matches!(decoded, '/' | '\\') || is_unreserved(decoded) {
+                return Err(PartNameError::EncodedCharacter(String::from(part_name), decoded));
+            }
+        } else if !is_pchar(c) {
+            return Err(PartNameError::InvalidCharacter(String::from(part_name), c));
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes percent-encoded unreserved characters and uppercases the hex digits of the remaining escapes.
+fn normalize_percent_encoding(segment: &str) -> String {
+    let mut normalized = String::with_capacity(segment.len());
+    let mut rest = segment;
+    while let Some(idx) = rest.find('%') {
+        normalized.push_str(&rest[..idx]);
+        let escape = &rest[idx..];
+        match escape.get(1..3).and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+            Some(byte) if is_unreserved(byte as char) => {
+                normalized.push(byte as char);
+                rest = &escape[3..];
+            }
+            Some(_) => {
+                normalized.push_str(&escape[..3].to_ascii_uppercase());
+                rest = &escape[3..];
+            }
+            None => {
+                normalized.push('%');
+                rest = &escape[1..];
+            }
+        }
+    }
+
+    normalized.push_str(rest);
+    normalized
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+fn is_pchar(c: char) -> bool {
+    is_unreserved(c) || !c.is_ascii() || "!$&'()*+,;=:@".contains(c)
+}
+
+fn has_uri_scheme(target: &str) -> bool {
+    match target.find(':') {
+        Some(idx) => {
+            let scheme = &target[..idx];
+            // Avoid treating single letter drive names (e.g. `C:\`) as schemes
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::relationship::TargetMode;
+    use std::collections::HashSet;
+
+    #[test]
+    pub fn test_part_name_validation() {
+        assert!(PartName::new("/word/document.xml").is_ok());
+        assert!(PartName::new("/customXml/item%C3%A9.xml").is_ok());
+        assert!(PartName::new("/media/image (1).png").is_err());
+
+        assert_eq!(PartName::new(""), Err(PartNameError::Empty));
+        assert_eq!(
+            PartName::new("word/document.xml"),
+            Err(PartNameError::MissingLeadingSlash(String::from("word/document.xml")))
+        );
+        assert_eq!(
+            PartName::new("/word/"),
+            Err(PartNameError::TrailingSlash(String::from("/word/")))
+        );
+        assert_eq!(
+            PartName::new("/word//document.xml"),
+            Err(PartNameError::EmptySegment(String::from("/word//document.xml")))
+        );
+        assert_eq!(
+            PartName::new("/word./document.xml"),
+            Err(PartNameError::SegmentEndsWithDot(String::from("/word./document.xml")))
+        );
+        assert_eq!(
+            PartName::new("/word/doc%2Fument.xml"),
+            Err(PartNameError::EncodedCharacter(
+                String::from("/word/doc%2Fument.xml"),
+                '/'
+            ))
+        );
+        assert_eq!(
+            PartName::new("/word/%4.xml"),
+            Err(PartNameError::InvalidPercentEncoding(String::from("/word/%4.xml")))
+        );
+        assert_eq!(
+            PartName::new("/word/doc?.xml"),
+            Err(PartNameError::InvalidCharacter(String::from("/word/doc?.xml"), '?'))
+        );
+    }
+
+    #[test]
+    pub fn test_part_name_equivalence() {
+        let mut part_names = HashSet::new();
+        part_names.insert(PartName::new("/word/document.xml").unwrap());
+        assert!(part_names.contains(&PartName::new("/Word/DOCUMENT.xml").unwrap()));
+        assert_eq!(
+            PartName::normalize("word/media/image%2a.png").unwrap().as_str(),
+            "/word/media/image%2A.png"
+        );
+    }
+
+    #[test]
+    pub fn test_resolve_relationship_targets() {
+        let slide = PartName::new("/ppt/slides/slide1.xml").unwrap();
+        assert_eq!(slide.directory(), "/ppt/slides");
+        assert_eq!(
+            slide.relationships_part_name().as_str(),
+            "/ppt/slides/_rels/slide1.xml.rels"
+        );
+        assert_eq!(
+            slide.resolve("../slideLayouts/slideLayout1.xml").unwrap().as_str(),
+            "/ppt/slideLayouts/slideLayout1.xml"
+        );
+        assert_eq!(
+            slide.resolve("/ppt/media/image1.png").unwrap().as_str(),
+            "/ppt/media/image1.png"
+        );
+        assert_eq!(
+            slide.resolve("../../../image1.png"),
+            Err(PartNameError::OutsideOfPackage(String::from(
+                "/ppt/slides/../../../image1.png"
+            )))
+        );
+
+        let external = Relationship {
+            id: String::from("rId1"),
+            rel_type: String::new(),
+            target: String::from("file.docx"),
+            target_mode: Some(TargetMode::External),
+        };
+        assert_eq!(
+            external.resolve("word/document.xml").unwrap(),
+            RelationshipTarget::External(String::from("file.docx"))
+        );
+        assert_eq!(
+            resolve_target("word/document.xml", "mailto:jane@example.com").unwrap(),
+            RelationshipTarget::External(String::from("mailto:jane@example.com"))
+        );
+    }
+}
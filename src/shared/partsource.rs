@@ -0,0 +1,133 @@
+//! Async access to the parts of a package, e.g. when the parts of a document are fetched from an object storage.
+//!
+//! Only the bytes of the parts are fetched asynchronously, the xml parsing itself is synchronous. See
+//! [`Package::from_part_source`](crate::docx::package::Package::from_part_source) for loading a WordprocessingML
+//! package from a [`PartSource`].
+
+use std::{
+    future::{poll_fn, Future},
+    io::{self, Cursor, Read},
+    pin::Pin,
+    task::Poll,
+};
+use zip::ZipArchive;
+
+/// The future returned by the methods of [`PartSource`].
+pub type PartFuture<'a, T> = Pin<Box<dyn Future<Output = io::Result<T>> + Send + 'a>>;
+
+/// A part of a package listed by a [`PartSource`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartEntry {
+    /// The name of the part as it's stored in the zip archive, e.g. `word/document.xml`
+    pub name: String,
+    /// The uncompressed size of the part in bytes.
+    pub size: u64,
+}
+
+/// An async source of the parts of a package.
+///
+/// ```no_run
+/// use oox::shared::partsource::{PartEntry, PartFuture, PartSource};
+/// use std::io;
+///
+/// struct BucketSource {
+///     prefix: String,
+/// }
+///
+/// impl PartSource for BucketSource {
+///     fn list_parts(&self) -> PartFuture<'_, Vec<PartEntry>> {
+///         Box::pin(async move { Ok(Vec::new()) })
+///     }
+///
+///     fn read_part<'a>(&'a self, name: &'a str) -> PartFuture<'a, Vec<u8>> {
+///         Box::pin(async move { Err(io::Error::new(io::ErrorKind::NotFound, format!("{}/{}", self.prefix, name))) })
+///     }
+/// }
+/// ```
+pub trait PartSource: Send + Sync {
+    /// Lists the parts of the package in their storage order.
+    fn list_parts(&self) -> PartFuture<'_, Vec<PartEntry>>;
+
+    /// Fetches the content of a part.
+    fn read_part<'a>(&'a self, name: &'a str) -> PartFuture<'a, Vec<u8>>;
+}
+
+/// A [`PartSource`] holding the parts of a package in memory, e.g. a package downloaded as a whole.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InMemoryPartSource {
+    pub parts: Vec<(String, Vec<u8>)>,
+}
+
+impl InMemoryPartSource {
+    /// Decompresses the parts of a zip archive.
+    pub fn from_zip_bytes(data: &[u8]) -> io::Result<Self> {
+        let mut zipper = ZipArchive::new(Cursor::new(data))?;
+        let mut parts = Vec::with_capacity(zipper.len());
+        for idx in 0..zipper.len() {
+            let mut zip_file = zipper.by_index(idx)?;
+            let mut content = Vec::with_capacity(zip_file.size() as usize);
+            zip_file.read_to_end(&mut content)?;
+            parts.push((String::from(zip_file.name()), content));
+        }
+
+        Ok(Self { parts })
+    }
+}
+
+impl PartSource for InMemoryPartSource {
+    fn list_parts(&self) -> PartFuture<'_, Vec<PartEntry>> {
+        let entries = self
+            .parts
+            .iter()
+            .map(|(name, content)| PartEntry {
+                name: name.clone(),
+                size: content.len() as u64,
+            })
+            .collect();
+        Box::pin(async move { Ok(entries) })
+    }
+
+    fn read_part<'a>(&'a self, name: &'a str) -> PartFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            self.parts
+                .iter()
+                .find(|(part_name, _)| part_name == name)
+                .map(|(_, content)| content.clone())
+                .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("Part '{}' not found", name)))
+        })
+    }
+}
+
+/// Fetches the given parts, at most `concurrency` of them at a time. The contents are returned in the order of the
+/// names.
+pub async fn read_parts<S: PartSource + ?Sized>(
+    source: &S,
+    names: &[&str],
+    concurrency: usize,
+) -> Vec<io::Result<Vec<u8>>> {
+    let mut contents = Vec::with_capacity(names.len());
+    for chunk in names.chunks(concurrency.max(1)) {
+        let mut futures: Vec<_> = chunk.iter().map(|name| source.read_part(name)).collect();
+        let mut results: Vec<Option<io::Result<Vec<u8>>>> = futures.iter().map(|_| None).collect();
+        poll_fn(|cx| {
+            let mut is_pending = false;
+            for (future, result) in futures.iter_mut().zip(results.iter_mut()) {
+                if result.is_none() {
+                    match future.as_mut().poll(cx) {
+                        Poll::Ready(output) => *result = Some(output),
+                        Poll::Pending => is_pending = true,
+                    }
+                }
+            }
+
+            match is_pending {
+                true => Poll::Pending,
+                false => Poll::Ready(()),
+            }
+        })
+        .await;
+        contents.extend(results.into_iter().flatten());
+    }
+
+    contents
+}
@@ -1,6 +1,8 @@
+use super::partname::resolve_segments;
 use crate::error::MissingAttributeError;
 use crate::xml::XmlNode;
 use std::{io::Read, str::FromStr};
+use strum_macros::EnumString;
 use zip::read::ZipFile;
 
 pub type RelationshipId = String;
@@ -8,12 +10,44 @@ pub type RelationshipId = String;
 pub type Result<T> = ::std::result::Result<T, Box<dyn (::std::error::Error)>>;
 
 pub const THEME_RELATION_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
+pub const OFFICE_DOCUMENT_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
+pub const CORE_PROPERTIES_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties";
+pub const EXTENDED_PROPERTIES_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties";
+pub const HYPERLINK_RELATION_TYPE: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+
+const TRANSITIONAL_RELATION_TYPE_PREFIX: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/";
+const STRICT_RELATION_TYPE_PREFIX: &str = "http://purl.oclc.org/ooxml/officeDocument/relationships/";
+
+/// Returns the transitional counterpart of an ECMA-376 Strict relationship type, e.g.
+/// `http://schemas.openxmlformats.org/officeDocument/2006/relationships/image` for
+/// `http://purl.oclc.org/ooxml/officeDocument/relationships/image`. Other types are returned unchanged.
+pub fn transitional_relation_type(rel_type: &str) -> String {
+    match rel_type.strip_prefix(STRICT_RELATION_TYPE_PREFIX) {
+        Some(name) => format!("{}{}", TRANSITIONAL_RELATION_TYPE_PREFIX, name),
+        None => String::from(rel_type),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+pub enum TargetMode {
+    #[strum(serialize = "Internal")]
+    Internal,
+    #[strum(serialize = "External")]
+    External,
+}
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Relationship {
     pub id: String,
+    /// The type of the relationship. Strict types are stored as their transitional counterparts, see
+    /// [`transitional_relation_type`].
     pub rel_type: String,
     pub target: String,
+    pub target_mode: Option<TargetMode>,
 }
 
 impl Relationship {
@@ -21,12 +55,14 @@ impl Relationship {
         let mut id = None;
         let mut rel_type = None;
         let mut target = None;
+        let mut target_mode = None;
 
         for (attr, value) in &xml_node.attributes {
-            match attr.as_str() {
+            match attr.as_ref() {
                 "Id" => id = Some(value.clone()),
-                "Type" => rel_type = Some(value.clone()),
+                "Type" => rel_type = Some(transitional_relation_type(value)),
                 "Target" => target = Some(value.clone()),
+                "TargetMode" => target_mode = Some(value.parse()?),
                 _ => (),
             }
         }
@@ -35,7 +71,16 @@ impl Relationship {
         let rel_type = rel_type.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "Type"))?;
         let target = target.ok_or_else(|| MissingAttributeError::new(xml_node.name.clone(), "Target"))?;
 
-        Ok(Self { id, rel_type, target })
+        Ok(Self {
+            id,
+            rel_type,
+            target,
+            target_mode,
+        })
+    }
+
+    pub fn is_external(&self) -> bool {
+        self.target_mode == Some(TargetMode::External)
     }
 }
 
@@ -51,3 +96,224 @@ pub fn relationships_from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Vec<Rel
 
     Ok(relationships)
 }
+
+/// The relationships of a single source part, parsed from the part's relationship part
+/// (e.g. `word/_rels/document.xml.rels` for `word/document.xml`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Relationships {
+    /// The name of the part the relationships belong to, without a leading slash. Empty for the package
+    /// relationships (`_rels/.rels`).
+    pub source_part: String,
+    pub relationships: Vec<Relationship>,
+}
+
+impl Relationships {
+    pub fn new<T: Into<String>>(source_part: T, relationships: Vec<Relationship>) -> Self {
+        Self {
+            source_part: source_part.into(),
+            relationships,
+        }
+    }
+
+    pub fn from_xml_element<T: Into<String>>(source_part: T, xml_node: &XmlNode) -> Result<Self> {
+        let relationships = xml_node
+            .child_nodes
+            .iter()
+            .filter(|child_node| child_node.local_name() == "Relationship")
+            .map(Relationship::from_xml_element)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(source_part, relationships))
+    }
+
+    /// Parses a relationship part from a zip file. The source part is derived from the name of the zip file.
+    pub fn from_zip_file(zip_file: &mut ZipFile<'_>) -> Result<Self> {
+        let source_part = source_part_of_rels_path(zip_file.name()).unwrap_or_default();
+
+        let mut xml_string = String::new();
+        zip_file.read_to_string(&mut xml_string)?;
+        let xml_node = XmlNode::from_str(xml_string.as_str())?;
+
+        Self::from_xml_element(source_part, &xml_node)
+    }
+
+    pub fn get<T: AsRef<str>>(&self, id: T) -> Option<&Relationship> {
+        self.relationships.iter().find(|rel| rel.id == id.as_ref())
+    }
+
+    /// Returns the raw target of the relationship with the given id as it's written in the relationship part.
+    pub fn target_for<T: AsRef<str>>(&self, id: T) -> Option<&str> {
+        self.get(id).map(|rel| rel.target.as_str())
+    }
+
+    /// Returns the package part name that the relationship with the given id points to, resolved relative to the
+    /// source part. Returns None for external targets.
+    pub fn resolve_target<T: AsRef<str>>(&self, id: T) -> Option<String> {
+        self.get(id)
+            .filter(|rel| !rel.is_external())
+            .map(|rel| join_part_path(&self.source_part, &rel.target))
+    }
+
+    pub fn relationships_of_type<'a>(&'a self, type_uri: &'a str) -> impl Iterator<Item = &'a Relationship> + 'a {
+        self.relationships.iter().filter(move |rel| rel.rel_type == type_uri)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Relationship> {
+        self.relationships.iter()
+    }
+}
+
+/// Returns the name of the relationship part that belongs to the given source part.
+///
+/// ```
+/// use oox::shared::relationship::rels_path_for_part;
+///
+/// assert_eq!(rels_path_for_part("word/document.xml"), "word/_rels/document.xml.rels");
+/// assert_eq!(rels_path_for_part(""), "_rels/.rels");
+/// ```
+pub fn rels_path_for_part(part_name: &str) -> String {
+    let part_name = part_name.trim_start_matches('/');
+    match part_name.rfind('/') {
+        Some(idx) => format!("{}/_rels/{}.rels", &part_name[..idx], &part_name[idx + 1..]),
+        None => format!("_rels/{}.rels", part_name),
+    }
+}
+
+/// Returns the name of the source part that the given relationship part belongs to, or None if the path is not a
+/// relationship part.
+///
+/// ```
+/// use oox::shared::relationship::source_part_of_rels_path;
+///
+/// assert_eq!(source_part_of_rels_path("ppt/slides/_rels/slide1.xml.rels"), Some(String::from("ppt/slides/slide1.xml")));
+/// assert_eq!(source_part_of_rels_path("_rels/.rels"), Some(String::new()));
+/// assert_eq!(source_part_of_rels_path("word/document.xml"), None);
+/// ```
+pub fn source_part_of_rels_path(rels_path: &str) -> Option<String> {
+    let rels_path = rels_path.trim_start_matches('/');
+    let (directory, file_name) = match rels_path.rfind('/') {
+        Some(idx) => (&rels_path[..idx], &rels_path[idx + 1..]),
+        None => return None,
+    };
+
+    let file_name = file_name.strip_suffix(".rels")?;
+    let directory = if directory == "_rels" {
+        ""
+    } else {
+        directory.strip_suffix("/_rels")?
+    };
+
+    if directory.is_empty() {
+        Some(String::from(file_name))
+    } else {
+        Some(format!("{}/{}", directory, file_name))
+    }
+}
+
+/// Resolves a relationship target relative to the directory of the source part and normalizes the result. Targets
+/// starting with a slash are relative to the package root.
+///
+/// ```
+/// use oox::shared::relationship::join_part_path;
+///
+/// assert_eq!(join_part_path("word/document.xml", "media/image1.png"), "word/media/image1.png");
+/// assert_eq!(join_part_path("ppt/slides/slide1.xml", "../slideLayouts/slideLayout1.xml"), "ppt/slideLayouts/slideLayout1.xml");
+/// assert_eq!(join_part_path("word/document.xml", "/customXml/item1.xml"), "customXml/item1.xml");
+/// assert_eq!(join_part_path("", "word/document.xml"), "word/document.xml");
+/// ```
+pub fn join_part_path(source_part: &str, target: &str) -> String {
+    if target.starts_with('/') {
+        return normalize_part_path(target);
+    }
+
+    let source_part = source_part.trim_start_matches('/');
+    match source_part.rfind('/') {
+        Some(idx) => normalize_part_path(&format!("{}/{}", &source_part[..idx], target)),
+        None => normalize_part_path(target),
+    }
+}
+
+/// Normalizes a part path by removing `.` and empty segments and resolving `..` segments. The result never starts
+/// with a slash. `..` segments that would leave the package root are dropped. Use
+/// [`PartName::normalize`](super::partname::PartName::normalize) to reject such paths instead.
+///
+/// ```
+/// use oox::shared::relationship::normalize_part_path;
+///
+/// assert_eq!(normalize_part_path("/word/./theme/../media//image1.png"), "word/media/image1.png");
+/// assert_eq!(normalize_part_path("../word/document.xml"), "word/document.xml");
+/// ```
+pub fn normalize_part_path(path: &str) -> String {
+    let (segments, _) = resolve_segments(path);
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relationships_for_test() -> Relationships {
+        let xml = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme"
+                Target="theme/theme1.xml"/>
+            <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image"
+                Target="media/image1.png"/>
+            <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image"
+                Target="../media/image2.png"/>
+            <Relationship Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"
+                Target="https://example.com" TargetMode="External"/>
+        </Relationships>"#;
+
+        Relationships::from_xml_element("word/document.xml", &XmlNode::from_str(xml).unwrap()).unwrap()
+    }
+
+    #[test]
+    pub fn test_relationships_from_xml() {
+        let relationships = relationships_for_test();
+        assert_eq!(relationships.relationships.len(), 4);
+        assert_eq!(
+            relationships.get("rId4"),
+            Some(&Relationship {
+                id: String::from("rId4"),
+                rel_type: String::from("http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink"),
+                target: String::from("https://example.com"),
+                target_mode: Some(TargetMode::External),
+            })
+        );
+    }
+
+    #[test]
+    pub fn test_relationships_resolution() {
+        let relationships = relationships_for_test();
+        assert_eq!(relationships.target_for("rId1"), Some("theme/theme1.xml"));
+        assert_eq!(relationships.target_for("rId9"), None);
+        assert_eq!(
+            relationships.resolve_target("rId1"),
+            Some(String::from("word/theme/theme1.xml"))
+        );
+        assert_eq!(
+            relationships.resolve_target("rId3"),
+            Some(String::from("media/image2.png"))
+        );
+        assert_eq!(relationships.resolve_target("rId4"), None);
+
+        let images: Vec<&str> = relationships
+            .relationships_of_type("http://schemas.openxmlformats.org/officeDocument/2006/relationships/image")
+            .map(|rel| rel.id.as_str())
+            .collect();
+        assert_eq!(images, vec!["rId2", "rId3"]);
+        assert_eq!(relationships.relationships_of_type(THEME_RELATION_TYPE).count(), 1);
+    }
+
+    #[test]
+    pub fn test_strict_relation_type() {
+        let xml = r#"<Relationship Id="rId1" Target="word/document.xml"
+            Type="http://purl.oclc.org/ooxml/officeDocument/relationships/officeDocument"></Relationship>"#;
+        let relationship = Relationship::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(relationship.rel_type, OFFICE_DOCUMENT_RELATION_TYPE);
+        assert_eq!(
+            transitional_relation_type(CORE_PROPERTIES_RELATION_TYPE),
+            CORE_PROPERTIES_RELATION_TYPE
+        );
+    }
+}
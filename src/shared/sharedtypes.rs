@@ -1,12 +1,26 @@
 use crate::error::PatternRestrictionError;
 use regex::Regex;
-use std::{marker::PhantomData, str::FromStr};
+use std::{
+    iter::Sum,
+    marker::PhantomData,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
 
 pub type OnOff = bool;
 pub type Lang = String;
 pub type XmlName = String; // 1 <= length <= 255
 pub type PositiveUniversalMeasure = UniversalMeasure<Unsigned>;
 
+/// The number of EMUs (English Metric Units) in an inch.
+pub const EMUS_PER_INCH: f64 = 914_400.0;
+/// The number of EMUs in a millimeter.
+pub const EMUS_PER_MM: f64 = 36_000.0;
+/// The number of EMUs in a point.
+pub const EMUS_PER_POINT: f64 = 12_700.0;
+/// The number of EMUs in a twip (a twentieth of a point).
+pub const EMUS_PER_TWIP: f64 = 635.0;
+
 /// Trait indicating that a data type is restricted by a string pattern. A pattern is basically a regular expression.
 pub trait PatternRestricted {
     fn restriction_pattern() -> &'static str;
@@ -93,7 +107,164 @@ pub enum YAlign {
     Outside,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, EnumString)]
+/// A physical length, which makes it possible to do arithmetic on the different measure types of the specification.
+/// The length is stored in EMUs, which is the smallest unit used by Office Open XML, so converting from any other unit
+/// is lossless.
+///
+/// ```
+/// use oox::shared::sharedtypes::{Length, Measure, Signed, TwipsMeasure, UniversalMeasure, UniversalMeasureUnit};
+///
+/// let margin = TwipsMeasure::Decimal(1440).to_length();
+/// let gutter = "0.5in".parse::<UniversalMeasure>().unwrap().to_length();
+/// assert_eq!((margin + gutter).to_twips(), 2160.0);
+/// assert_eq!(Length::from_points(72.0), Length::from_inches(1.0));
+/// assert_eq!(UniversalMeasure::<Signed>::new(2.0, UniversalMeasureUnit::Centimeter).to_mm(), 20.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Length {
+    emus: f64,
+}
+
+impl Length {
+    pub fn from_emu(emus: f64) -> Self {
+        Self { emus }
+    }
+
+    pub fn from_twips(twips: f64) -> Self {
+        Self::from_emu(twips * EMUS_PER_TWIP)
+    }
+
+    pub fn from_points(points: f64) -> Self {
+        Self::from_emu(points * EMUS_PER_POINT)
+    }
+
+    pub fn from_mm(mm: f64) -> Self {
+        Self::from_emu(mm * EMUS_PER_MM)
+    }
+
+    pub fn from_inches(inches: f64) -> Self {
+        Self::from_emu(inches * EMUS_PER_INCH)
+    }
+
+    pub fn to_emu(self) -> f64 {
+        self.emus
+    }
+
+    pub fn to_twips(self) -> f64 {
+        self.emus / EMUS_PER_TWIP
+    }
+
+    pub fn to_points(self) -> f64 {
+        self.emus / EMUS_PER_POINT
+    }
+
+    pub fn to_mm(self) -> f64 {
+        self.emus / EMUS_PER_MM
+    }
+
+    pub fn to_inches(self) -> f64 {
+        self.emus / EMUS_PER_INCH
+    }
+
+    /// Converts the length to pixels at the given resolution in dots per inch.
+    pub fn to_pixels(self, dpi: f64) -> f64 {
+        self.to_inches() * dpi
+    }
+
+    pub fn abs(self) -> Self {
+        Self::from_emu(self.emus.abs())
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self::from_emu(self.emus.max(other.emus))
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self::from_emu(self.emus.min(other.emus))
+    }
+}
+
+impl Add for Length {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::from_emu(self.emus + rhs.emus)
+    }
+}
+
+impl AddAssign for Length {
+    fn add_assign(&mut self, rhs: Self) {
+        self.emus += rhs.emus;
+    }
+}
+
+impl Sub for Length {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_emu(self.emus - rhs.emus)
+    }
+}
+
+impl SubAssign for Length {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.emus -= rhs.emus;
+    }
+}
+
+impl Neg for Length {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_emu(-self.emus)
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self::from_emu(self.emus * rhs)
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Self::from_emu(self.emus / rhs)
+    }
+}
+
+impl Sum for Length {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Trait implemented by the measure types of the specification to convert them to a [`Length`]. DrawingML coordinates
+/// are plain integers of EMUs and can be converted with [`Length::from_emu`].
+pub trait Measure {
+    fn to_length(&self) -> Length;
+
+    fn to_emu(&self) -> f64 {
+        self.to_length().to_emu()
+    }
+
+    fn to_twips(&self) -> f64 {
+        self.to_length().to_twips()
+    }
+
+    fn to_points(&self) -> f64 {
+        self.to_length().to_points()
+    }
+
+    fn to_mm(&self) -> f64 {
+        self.to_length().to_mm()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, AsRefStr)]
 pub enum UniversalMeasureUnit {
     #[strum(serialize = "mm")]
     Millimeter,
@@ -126,6 +297,18 @@ impl<T> UniversalMeasure<T> {
     }
 }
 
+impl<T> Measure for UniversalMeasure<T> {
+    fn to_length(&self) -> Length {
+        match self.unit {
+            UniversalMeasureUnit::Millimeter => Length::from_mm(self.value),
+            UniversalMeasureUnit::Centimeter => Length::from_mm(self.value * 10.0),
+            UniversalMeasureUnit::Inch => Length::from_inches(self.value),
+            UniversalMeasureUnit::Point => Length::from_points(self.value),
+            UniversalMeasureUnit::Pica | UniversalMeasureUnit::Pitch => Length::from_points(self.value * 12.0),
+        }
+    }
+}
+
 impl PatternRestricted for UniversalMeasure<Signed> {
     fn restriction_pattern() -> &'static str {
         r#"^(-?[0-9]+(?:\.[0-9]+)?)(mm|cm|in|pt|pc|pi)$"#
@@ -175,6 +358,15 @@ impl FromStr for TwipsMeasure {
     }
 }
 
+impl Measure for TwipsMeasure {
+    fn to_length(&self) -> Length {
+        match self {
+            TwipsMeasure::Decimal(value) => Length::from_twips(*value as f64),
+            TwipsMeasure::UniversalMeasure(measure) => measure.to_length(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Percentage(pub f64);
 
@@ -229,6 +421,36 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_universal_measure_to_twips() {
+        assert_eq!("1in".parse::<UniversalMeasure>().unwrap().to_twips(), 1440.0);
+        assert_eq!("-12pt".parse::<UniversalMeasure>().unwrap().to_twips(), -240.0);
+        assert_eq!("2.54cm".parse::<UniversalMeasure>().unwrap().to_twips().round(), 1440.0);
+        assert_eq!("1pc".parse::<TwipsMeasure>().unwrap().to_twips(), 240.0);
+        assert_eq!("720".parse::<TwipsMeasure>().unwrap().to_twips(), 720.0);
+    }
+
+    #[test]
+    pub fn test_length_conversions() {
+        let length = Length::from_inches(1.0);
+        assert_eq!(length.to_emu(), 914_400.0);
+        assert_eq!(length.to_twips(), 1440.0);
+        assert_eq!(length.to_points(), 72.0);
+        assert_eq!(length.to_mm(), 25.4);
+
+        let total: Length = vec![
+            Length::from_twips(720.0),
+            Length::from_points(36.0),
+            -Length::from_mm(25.4),
+        ]
+        .into_iter()
+        .sum();
+        assert_eq!(total, Length::default());
+        assert_eq!((Length::from_points(10.0) * 3.0 / 2.0).to_points(), 15.0);
+        assert!(Length::from_mm(10.0) > Length::from_points(10.0));
+        assert_eq!("12.7mm".parse::<TwipsMeasure>().unwrap().to_emu(), 457_200.0);
+    }
+
     #[test]
     pub fn test_twips_measure_from_str() {
         assert_eq!("123".parse::<TwipsMeasure>().unwrap(), TwipsMeasure::Decimal(123));
@@ -0,0 +1,497 @@
+//! A schema conformance check working on the raw xml tree of a part.
+//!
+//! Parsing is lenient: unknown attributes are ignored and only a few constraints make it fail. The validator checks
+//! the occurrence constraints of child elements and the values of attributes described by a set of [`ElementRule`]s
+//! and collects every violation instead of stopping at the first one. The same rules can also be used to repair a
+//! tree, see [`Validator::repair`].
+use crate::{error::MaxOccurs, xml::XmlNode};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+/// A restriction on the value of an attribute.
+#[derive(Debug, Clone, Copy)]
+pub enum ValueRule {
+    /// An integer in the inclusive range.
+    Integer { min: i64, max: i64 },
+    /// One of the listed values.
+    Enumeration(&'static [&'static str]),
+    /// `true`, `false`, `on`, `off`, `1` or `0`.
+    OnOff,
+    /// A value accepted by the function. The string describes the expected values in reports.
+    Custom(fn(&str) -> bool, &'static str),
+}
+
+impl ValueRule {
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            ValueRule::Integer { min, max } => value
+                .parse::<i64>()
+                .map(|value| (*min..=*max).contains(&value))
+                .unwrap_or(false),
+            ValueRule::Enumeration(values) => values.contains(&value),
+            ValueRule::OnOff => matches!(value, "true" | "false" | "on" | "off" | "1" | "0"),
+            ValueRule::Custom(accepts, _) => accepts(value),
+        }
+    }
+
+    /// Describes the accepted values, e.g. `an integer in 1..=45`.
+    pub fn expected(&self) -> String {
+        match self {
+            ValueRule::Integer { min, max } => format!("an integer in {}..={}", min, max),
+            ValueRule::Enumeration(values) => format!("one of {}", values.join(", ")),
+            ValueRule::OnOff => String::from("an on/off value"),
+            ValueRule::Custom(_, description) => String::from(*description),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AttributeRule {
+    /// The qualified name of the attribute, e.g. `w:val`.
+    pub name: &'static str,
+    pub required: bool,
+    pub value: ValueRule,
+}
+
+/// The number of occurrences allowed for a group of child elements. The occurrences of every listed element are
+/// summed, so `headerReference` and `footerReference` can share a limit.
+#[derive(Debug, Clone, Copy)]
+pub struct ChildRule {
+    /// The qualified names of the child elements, e.g. `w:tblPr`.
+    pub names: &'static [&'static str],
+    pub min_occurs: u32,
+    pub max_occurs: MaxOccurs,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ElementRule {
+    /// The qualified name of the element the rule applies to, e.g. `w:cols`.
+    pub name: &'static str,
+    pub children: &'static [ChildRule],
+    pub attributes: &'static [AttributeRule],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    TooFewOccurrences {
+        names: Vec<&'static str>,
+        min_occurs: u32,
+        occurs: u32,
+    },
+    TooManyOccurrences {
+        names: Vec<&'static str>,
+        max_occurs: MaxOccurs,
+        occurs: u32,
+    },
+    MissingAttribute(&'static str),
+    InvalidAttributeValue {
+        name: &'static str,
+        value: String,
+        expected: String,
+    },
+}
+
+/// A single schema violation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The path of the violating element, e.g. `/w:document/w:body/w:p[3]/w:pPr`. Indexes are one based and only
+    /// written for elements that have siblings with the same name.
+    pub path: String,
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ViolationKind::TooFewOccurrences {
+                names,
+                min_occurs,
+                occurs,
+            } => write!(
+                f,
+                "{}: {} occurs {} times, at least {} required",
+                self.path,
+                names.join("|"),
+                occurs,
+                min_occurs
+            ),
+            ViolationKind::TooManyOccurrences {
+                names,
+                max_occurs,
+                occurs,
+            } => write!(
+                f,
+                "{}: {} occurs {} times, at most {} allowed",
+                self.path,
+                names.join("|"),
+                occurs,
+                max_occurs
+            ),
+            ViolationKind::MissingAttribute(name) => write!(f, "{}: missing attribute {}", self.path, name),
+            ViolationKind::InvalidAttributeValue { name, value, expected } => write!(
+                f,
+                "{}: attribute {} has value '{}', expected {}",
+                self.path, name, value, expected
+            ),
+        }
+    }
+}
+
+/// The result of validating one or more parts.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConformanceReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ConformanceReport {
+    pub fn is_conformant(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Appends the violations of another report, prefixing their paths with the name of the part they were found in.
+    pub fn merge_part<T: AsRef<str>>(&mut self, part_name: T, report: ConformanceReport) {
+        self.violations
+            .extend(report.violations.into_iter().map(|violation| Violation {
+                path: format!("{}:{}", part_name.as_ref(), violation.path),
+                ..violation
+            }));
+    }
+}
+
+/// How a violation was repaired by [`Validator::repair`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepairAction {
+    /// The invalid value of an optional attribute was dropped, so the parser falls back to its default.
+    RemovedAttribute(&'static str),
+    /// The element was dropped, because a required attribute was missing or invalid.
+    RemovedElement,
+    /// The occurrences of a child element group above the allowed maximum were dropped.
+    RemovedExcessChildren(u32),
+}
+
+/// A violation together with the way it was repaired.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    pub violation: Violation,
+    pub action: RepairAction,
+}
+
+/// Returns the paths of the child elements of a node, in the format used by [`Violation::path`].
+pub fn child_paths(path: &str, xml_node: &XmlNode) -> Vec<String> {
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for child_node in &xml_node.child_nodes {
+        *name_counts.entry(child_node.name.as_ref()).or_default() += 1;
+    }
+
+    let mut name_indexes: HashMap<&str, usize> = HashMap::new();
+    xml_node
+        .child_nodes
+        .iter()
+        .map(|child_node| {
+            let name = child_node.name.as_ref();
+            if name_counts[name] > 1 {
+                let index = name_indexes.entry(name).or_default();
+                *index += 1;
+                format!("{}/{}[{}]", path, name, index)
+            } else {
+                format!("{}/{}", path, name)
+            }
+        })
+        .collect()
+}
+
+/// Validates an xml tree against a set of rules. Elements without a rule are only descended into.
+pub struct Validator {
+    rules: HashMap<&'static str, &'static ElementRule>,
+}
+
+impl Validator {
+    pub fn new(rules: &'static [ElementRule]) -> Self {
+        Self {
+            rules: rules.iter().map(|rule| (rule.name, rule)).collect(),
+        }
+    }
+
+    pub fn validate(&self, root: &XmlNode) -> ConformanceReport {
+        let mut report: ConformanceReport = Default::default();
+        self.validate_node(root, format!("/{}", root.name), &mut report);
+        report
+    }
+
+    fn validate_node(&self, xml_node: &XmlNode, path: String, report: &mut ConformanceReport) {
+        if let Some(rule) = self.rules.get(xml_node.name.as_ref()) {
+            Self::check_attributes(rule, xml_node, &path, report);
+            Self::check_children(rule, xml_node, &path, report);
+        }
+
+        for (child_node, child_path) in xml_node.child_nodes.iter().zip(child_paths(&path, xml_node)) {
+            self.validate_node(child_node, child_path, report);
+        }
+    }
+
+    /// Repairs the violations of an xml tree that can be fixed by dropping content and returns the repairs made.
+    /// Invalid optional attributes are removed, elements with a missing or invalid required attribute are removed and
+    /// excess child elements are truncated. Missing child elements can't be repaired and are left as they are.
+    /// Returns an empty list and leaves the tree untouched if the root element itself would have to be removed.
+    pub fn repair(&self, root: &mut XmlNode) -> Vec<Repair> {
+        let mut repairs = Vec::new();
+        let path = format!("/{}", root.name);
+        if !self.repair_attributes(root, &path, &mut repairs) {
+            return Vec::new();
+        }
+
+        self.repair_children(root, &path, &mut repairs);
+        repairs
+    }
+
+    /// Returns false if the element has to be removed.
+    fn repair_node(&self, xml_node: &mut XmlNode, path: &str, repairs: &mut Vec<Repair>) -> bool {
+        if !self.repair_attributes(xml_node, path, repairs) {
+            return false;
+        }
+
+        self.repair_children(xml_node, path, repairs);
+        true
+    }
+
+    fn repair_attributes(&self, xml_node: &mut XmlNode, path: &str, repairs: &mut Vec<Repair>) -> bool {
+        let rule = match self.rules.get(xml_node.name.as_ref()) {
+            Some(rule) => rule,
+            None => return true,
+        };
+
+        for attribute_rule in rule.attributes {
+            let kind = match xml_node.attributes.get(attribute_rule.name) {
+                Some(value) if !attribute_rule.value.accepts(value) => ViolationKind::InvalidAttributeValue {
+                    name: attribute_rule.name,
+                    value: value.clone(),
+                    expected: attribute_rule.value.expected(),
+                },
+                None if attribute_rule.required => ViolationKind::MissingAttribute(attribute_rule.name),
+                _ => continue,
+            };
+
+            let violation = Violation {
+                path: String::from(path),
+                kind,
+            };
+            if attribute_rule.required {
+                repairs.push(Repair {
+                    violation,
+                    action: RepairAction::RemovedElement,
+                });
+                return false;
+            }
+
+            xml_node.attributes.remove(attribute_rule.name);
+            repairs.push(Repair {
+                violation,
+                action: RepairAction::RemovedAttribute(attribute_rule.name),
+            });
+        }
+
+        true
+    }
+
+    fn repair_children(&self, xml_node: &mut XmlNode, path: &str, repairs: &mut Vec<Repair>) {
+        let paths = child_paths(path, xml_node);
+        let mut keep = xml_node
+            .child_nodes
+            .iter_mut()
+            .zip(paths.iter())
+            .map(|(child_node, child_path)| self.repair_node(child_node, child_path, repairs))
+            .collect::<Vec<_>>()
+            .into_iter();
+        xml_node.child_nodes.retain(|_| keep.next().unwrap_or(true));
+
+        let rule = match self.rules.get(xml_node.name.as_ref()) {
+            Some(rule) => rule,
+            None => return,
+        };
+
+        for child_rule in rule.children {
+            let max_occurs = match child_rule.max_occurs {
+                MaxOccurs::Value(max_occurs) => max_occurs,
+                MaxOccurs::Unbounded => continue,
+            };
+
+            let is_in_group = |child_node: &XmlNode| child_rule.names.contains(&child_node.name.as_ref());
+            let occurs = xml_node
+                .child_nodes
+                .iter()
+                .filter(|child_node| is_in_group(child_node))
+                .count() as u32;
+            if occurs <= max_occurs {
+                continue;
+            }
+
+            let mut seen = 0;
+            xml_node.child_nodes.retain(|child_node| {
+                if !is_in_group(child_node) {
+                    return true;
+                }
+
+                seen += 1;
+                seen <= max_occurs
+            });
+            repairs.push(Repair {
+                violation: Violation {
+                    path: String::from(path),
+                    kind: ViolationKind::TooManyOccurrences {
+                        names: child_rule.names.to_vec(),
+                        max_occurs: child_rule.max_occurs,
+                        occurs,
+                    },
+                },
+                action: RepairAction::RemovedExcessChildren(occurs - max_occurs),
+            });
+        }
+    }
+
+    fn check_attributes(rule: &ElementRule, xml_node: &XmlNode, path: &str, report: &mut ConformanceReport) {
+        for attribute_rule in rule.attributes {
+            let kind = match xml_node.attributes.get(attribute_rule.name) {
+                Some(value) if !attribute_rule.value.accepts(value) => ViolationKind::InvalidAttributeValue {
+                    name: attribute_rule.name,
+                    value: value.clone(),
+                    expected: attribute_rule.value.expected(),
+                },
+                None if attribute_rule.required => ViolationKind::MissingAttribute(attribute_rule.name),
+                _ => continue,
+            };
+
+            report.violations.push(Violation {
+                path: String::from(path),
+                kind,
+            });
+        }
+    }
+
+    fn check_children(rule: &ElementRule, xml_node: &XmlNode, path: &str, report: &mut ConformanceReport) {
+        for child_rule in rule.children {
+            let occurs = xml_node
+                .child_nodes
+                .iter()
+                .filter(|child_node| child_rule.names.contains(&child_node.name.as_ref()))
+                .count() as u32;
+
+            let kind = if occurs < child_rule.min_occurs {
+                ViolationKind::TooFewOccurrences {
+                    names: child_rule.names.to_vec(),
+                    min_occurs: child_rule.min_occurs,
+                    occurs,
+                }
+            } else {
+                match child_rule.max_occurs {
+                    MaxOccurs::Value(max_occurs) if occurs > max_occurs => ViolationKind::TooManyOccurrences {
+                        names: child_rule.names.to_vec(),
+                        max_occurs: child_rule.max_occurs,
+                        occurs,
+                    },
+                    _ => continue,
+                }
+            };
+
+            report.violations.push(Violation {
+                path: String::from(path),
+                kind,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const RULES: &[ElementRule] = &[ElementRule {
+        name: "a:list",
+        children: &[ChildRule {
+            names: &["a:item"],
+            min_occurs: 1,
+            max_occurs: MaxOccurs::Value(2),
+        }],
+        attributes: &[
+            AttributeRule {
+                name: "a:count",
+                required: true,
+                value: ValueRule::Integer { min: 1, max: 2 },
+            },
+            AttributeRule {
+                name: "a:sorted",
+                required: false,
+                value: ValueRule::OnOff,
+            },
+        ],
+    }];
+
+    #[test]
+    pub fn test_validator() {
+        let xml = r#"<a:root>
+            <a:list a:count="2"><a:item /></a:list>
+            <a:list a:sorted="yes"><a:item /><a:item /><a:item /></a:list>
+        </a:root>"#;
+
+        let report = Validator::new(RULES).validate(&XmlNode::from_str(xml).unwrap());
+        assert!(!report.is_conformant());
+        assert_eq!(
+            report.violations,
+            vec![
+                Violation {
+                    path: String::from("/a:root/a:list[2]"),
+                    kind: ViolationKind::MissingAttribute("a:count"),
+                },
+                Violation {
+                    path: String::from("/a:root/a:list[2]"),
+                    kind: ViolationKind::InvalidAttributeValue {
+                        name: "a:sorted",
+                        value: String::from("yes"),
+                        expected: String::from("an on/off value"),
+                    },
+                },
+                Violation {
+                    path: String::from("/a:root/a:list[2]"),
+                    kind: ViolationKind::TooManyOccurrences {
+                        names: vec!["a:item"],
+                        max_occurs: MaxOccurs::Value(2),
+                        occurs: 3,
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            report.violations[2].to_string(),
+            "/a:root/a:list[2]: a:item occurs 3 times, at most 2 allowed"
+        );
+    }
+
+    #[test]
+    pub fn test_repair() {
+        let xml = r#"<a:root>
+            <a:list a:count="1" a:sorted="yes"><a:item /><a:item /><a:item /></a:list>
+            <a:list a:sorted="1"><a:item /></a:list>
+        </a:root>"#;
+
+        let mut root = XmlNode::from_str(xml).unwrap();
+        let repairs = Validator::new(RULES).repair(&mut root);
+        let actions: Vec<(&str, RepairAction)> = repairs
+            .iter()
+            .map(|repair| (repair.violation.path.as_str(), repair.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("/a:root/a:list[1]", RepairAction::RemovedAttribute("a:sorted")),
+                ("/a:root/a:list[1]", RepairAction::RemovedExcessChildren(1)),
+                ("/a:root/a:list[2]", RepairAction::RemovedElement),
+            ]
+        );
+        assert_eq!(root.child_nodes.len(), 1);
+        assert_eq!(root.child_nodes[0].child_nodes.len(), 2);
+        assert!(!root.child_nodes[0].attributes.contains_key("a:sorted"));
+        assert!(Validator::new(RULES).validate(&root).is_conformant());
+    }
+}
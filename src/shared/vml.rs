@@ -0,0 +1,230 @@
+//! A minimal model of the Vector Markup Language (VML) elements used by legacy pictures, OLE object previews and
+//! ActiveX controls. Only the structure of the shapes, their CSS style and the image data they reference are parsed.
+
+use crate::{error::NotGroupMemberError, shared::relationship::RelationshipId, xml::XmlNode};
+use log::info;
+
+pub type Result<T> = ::std::result::Result<T, Box<dyn ::std::error::Error>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeKind {
+    Shape,
+    ShapeType,
+    Group,
+    Rect,
+    RoundRect,
+    Oval,
+    Line,
+    PolyLine,
+    Arc,
+    Curve,
+    Image,
+}
+
+impl ShapeKind {
+    pub fn from_local_name<T: AsRef<str>>(local_name: T) -> Option<Self> {
+        match local_name.as_ref() {
+            "shape" => Some(ShapeKind::Shape),
+            "shapetype" => Some(ShapeKind::ShapeType),
+            "group" => Some(ShapeKind::Group),
+            "rect" => Some(ShapeKind::Rect),
+            "roundrect" => Some(ShapeKind::RoundRect),
+            "oval" => Some(ShapeKind::Oval),
+            "line" => Some(ShapeKind::Line),
+            "polyline" => Some(ShapeKind::PolyLine),
+            "arc" => Some(ShapeKind::Arc),
+            "curve" => Some(ShapeKind::Curve),
+            "image" => Some(ShapeKind::Image),
+            _ => None,
+        }
+    }
+
+    pub fn is_choice_member<T: AsRef<str>>(node_name: T) -> bool {
+        Self::from_local_name(node_name).is_some()
+    }
+}
+
+/// The image referenced by a shape (`v:imagedata`) or by an image shape (`v:image`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImageData {
+    /// The relationship id of the image part (`r:id`).
+    pub rel_id: Option<RelationshipId>,
+    /// The relationship id of the image part in legacy documents (`o:relid`).
+    pub legacy_rel_id: Option<RelationshipId>,
+    pub title: Option<String>,
+}
+
+impl ImageData {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Self {
+        let mut instance: Self = Default::default();
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "r:id" => instance.rel_id = Some(value.clone()),
+                "o:relid" => instance.legacy_rel_id = Some(value.clone()),
+                "o:title" => instance.title = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        instance
+    }
+
+    /// Returns the relationship id of the referenced image, preferring `r:id` over `o:relid`.
+    pub fn image_rel_id(&self) -> Option<&RelationshipId> {
+        self.rel_id.as_ref().or(self.legacy_rel_id.as_ref())
+    }
+}
+
+/// A VML shape element (`v:shape`, `v:rect`, `v:group`, etc.).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    pub kind: ShapeKind,
+    pub id: Option<String>,
+    /// The id of the shape type this shape is based on (`type`), e.g. `#_x0000_t75`.
+    pub shape_type: Option<String>,
+    /// The CSS declarations of the `style` attribute in order of their appearance.
+    pub style: Vec<(String, String)>,
+    pub alternate_text: Option<String>,
+    pub image_data: Option<ImageData>,
+    pub child_shapes: Vec<Shape>,
+}
+
+impl Shape {
+    pub fn from_xml_element(xml_node: &XmlNode) -> Result<Self> {
+        info!("parsing vml Shape");
+
+        let kind = ShapeKind::from_local_name(xml_node.local_name())
+            .ok_or_else(|| NotGroupMemberError::new(xml_node.name.clone(), "VmlShape"))?;
+
+        let mut instance = Self {
+            kind,
+            id: None,
+            shape_type: None,
+            style: Vec::new(),
+            alternate_text: None,
+            image_data: None,
+            child_shapes: Vec::new(),
+        };
+
+        for (attr, value) in &xml_node.attributes {
+            match attr.as_ref() {
+                "id" => instance.id = Some(value.clone()),
+                "type" => instance.shape_type = Some(value.clone()),
+                "style" => instance.style = parse_style(value),
+                "alt" => instance.alternate_text = Some(value.clone()),
+                _ => (),
+            }
+        }
+
+        if kind == ShapeKind::Image {
+            instance.image_data = Some(ImageData::from_xml_element(xml_node));
+        }
+
+        for child_node in &xml_node.child_nodes {
+            match child_node.local_name() {
+                "imagedata" => instance.image_data = Some(ImageData::from_xml_element(child_node)),
+                node_name if ShapeKind::is_choice_member(node_name) => {
+                    instance.child_shapes.push(Shape::from_xml_element(child_node)?)
+                }
+                _ => (),
+            }
+        }
+
+        Ok(instance)
+    }
+
+    /// Returns the value of a CSS property of the shape's style. Property names are compared case insensitively.
+    pub fn style_property<T: AsRef<str>>(&self, name: T) -> Option<&str> {
+        self.style
+            .iter()
+            .find(|(property, _)| property.eq_ignore_ascii_case(name.as_ref()))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Iterates over the image data of this shape and all of its descendant shapes in document order.
+    pub fn images(&self) -> Box<dyn Iterator<Item = &ImageData> + '_> {
+        Box::new(
+            self.image_data
+                .iter()
+                .chain(self.child_shapes.iter().flat_map(|child_shape| child_shape.images())),
+        )
+    }
+}
+
+/// Parses the declarations of a CSS style attribute, e.g. `width:100pt;height:50pt`.
+///
+/// ```
+/// use oox::shared::vml::parse_style;
+///
+/// assert_eq!(
+///     parse_style("width:100pt; height:50pt;;mso-position-horizontal : center"),
+///     vec![
+///         (String::from("width"), String::from("100pt")),
+///         (String::from("height"), String::from("50pt")),
+///         (String::from("mso-position-horizontal"), String::from("center")),
+///     ]
+/// );
+/// ```
+pub fn parse_style(style: &str) -> Vec<(String, String)> {
+    style
+        .split(';')
+        .filter_map(|declaration| {
+            let idx = declaration.find(':')?;
+            let property = declaration[..idx].trim();
+            if property.is_empty() {
+                return None;
+            }
+
+            Some((String::from(property), String::from(declaration[idx + 1..].trim())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    pub fn test_shape_from_xml() {
+        let xml = r##"<v:group id="Group 1" style="width:200pt;height:100pt">
+            <v:shapetype id="_x0000_t75" />
+            <v:shape id="Picture 1" type="#_x0000_t75" style="width:100pt;height:50pt" alt="Logo">
+                <v:imagedata r:id="rId5" o:title="logo" />
+            </v:shape>
+            <v:image id="Picture 2" o:relid="rId6" />
+        </v:group>"##;
+
+        let shape = Shape::from_xml_element(&XmlNode::from_str(xml).unwrap()).unwrap();
+        assert_eq!(shape.kind, ShapeKind::Group);
+        assert_eq!(shape.style_property("Height"), Some("100pt"));
+        assert_eq!(shape.child_shapes.len(), 3);
+        assert_eq!(
+            shape.child_shapes[1],
+            Shape {
+                kind: ShapeKind::Shape,
+                id: Some(String::from("Picture 1")),
+                shape_type: Some(String::from("#_x0000_t75")),
+                style: vec![
+                    (String::from("width"), String::from("100pt")),
+                    (String::from("height"), String::from("50pt")),
+                ],
+                alternate_text: Some(String::from("Logo")),
+                image_data: Some(ImageData {
+                    rel_id: Some(String::from("rId5")),
+                    legacy_rel_id: None,
+                    title: Some(String::from("logo")),
+                }),
+                child_shapes: Vec::new(),
+            }
+        );
+
+        let image_rel_ids: Vec<&str> = shape
+            .images()
+            .filter_map(|image_data| image_data.image_rel_id())
+            .map(String::as_str)
+            .collect();
+        assert_eq!(image_rel_ids, vec!["rId5", "rId6"]);
+    }
+}
@@ -1,3 +1,13 @@
+/// How toggle properties, such as bold or italic, are combined when updating formatting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToggleMode {
+    /// The value of the update replaces the current value. Used for direct formatting and document defaults.
+    Override,
+    /// An update of `true` inverts the current value, `false` leaves it unchanged. Used when a style is applied on
+    /// another level of the style hierarchy, e.g. a character style over a paragraph style.
+    Toggle,
+}
+
 pub trait Update {
     fn update_with(self, other: Self) -> Self;
 }
@@ -8,3 +18,14 @@ pub fn update_options<T: Update>(lhs: Option<T>, rhs: Option<T>) -> Option<T> {
         (lhs, rhs) => rhs.or(lhs),
     }
 }
+
+pub fn update_boxed_options<T: Update>(lhs: Option<Box<T>>, rhs: Option<Box<T>>) -> Option<Box<T>> {
+    update_options(lhs.map(|lhs| *lhs), rhs.map(|rhs| *rhs)).map(Box::new)
+}
+
+pub fn update_toggle(lhs: Option<bool>, rhs: Option<bool>, mode: ToggleMode) -> Option<bool> {
+    match (mode, lhs, rhs) {
+        (ToggleMode::Toggle, Some(lhs), Some(rhs)) => Some(lhs ^ rhs),
+        (_, lhs, rhs) => rhs.or(lhs),
+    }
+}
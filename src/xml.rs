@@ -1,22 +1,121 @@
 use crate::error::{InvalidXmlError, ParseBoolError};
 use quick_xml::{
-    events::{BytesStart, Event},
+    events::{BytesStart, BytesText, Event},
     Reader,
 };
 use std::{
-    collections::HashMap,
+    borrow::Cow,
+    collections::{HashMap, HashSet},
     fmt::{Display, Formatter},
-    io::Read,
+    io::{BufRead, Read},
     str::FromStr,
+    sync::Arc,
 };
 use zip::read::ZipFile;
 
+/// The namespaces known by the parsers along with the prefixes the parsers use to refer to them.
+///
+/// Documents may bind any prefix to these namespaces. The parser rewrites the names of elements and attributes of
+/// these namespaces to use the canonical prefix, so e.g. the `val` attribute of the WordprocessingML namespace is
+/// always stored as `w:val`, regardless of the prefix used by the document.
+pub const KNOWN_NAMESPACES: &[(&str, &str)] = &[
+    ("w", WORDPROCESSINGML_NAMESPACE),
+    ("r", RELATIONSHIPS_NAMESPACE),
+    ("a", DRAWINGML_NAMESPACE),
+    ("p", PRESENTATIONML_NAMESPACE),
+    (
+        "wp",
+        "http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing",
+    ),
+    ("pic", "http://schemas.openxmlformats.org/drawingml/2006/picture"),
+    ("c", "http://schemas.openxmlformats.org/drawingml/2006/chart"),
+    ("dgm", "http://schemas.openxmlformats.org/drawingml/2006/diagram"),
+    ("m", "http://schemas.openxmlformats.org/officeDocument/2006/math"),
+    ("mc", "http://schemas.openxmlformats.org/markup-compatibility/2006"),
+    ("v", "urn:schemas-microsoft-com:vml"),
+    ("o", "urn:schemas-microsoft-com:office:office"),
+    ("w10", "urn:schemas-microsoft-com:office:word"),
+    ("w14", "http://schemas.microsoft.com/office/word/2010/wordml"),
+    ("w15", "http://schemas.microsoft.com/office/word/2012/wordml"),
+    (
+        "wp14",
+        "http://schemas.microsoft.com/office/word/2010/wordprocessingDrawing",
+    ),
+    (
+        "wps",
+        "http://schemas.microsoft.com/office/word/2010/wordprocessingShape",
+    ),
+    (
+        "wpg",
+        "http://schemas.microsoft.com/office/word/2010/wordprocessingGroup",
+    ),
+    (
+        "wpc",
+        "http://schemas.microsoft.com/office/word/2010/wordprocessingCanvas",
+    ),
+    ("a14", "http://schemas.microsoft.com/office/drawing/2010/main"),
+    ("p14", "http://schemas.microsoft.com/office/powerpoint/2010/main"),
+    ("p15", "http://schemas.microsoft.com/office/powerpoint/2012/main"),
+];
+
+/// The namespaces of ECMA-376 Strict documents along with the canonical prefixes of their transitional counterparts.
+/// Elements and attributes of these namespaces are parsed the same way as the transitional ones, while
+/// [`XmlNode::namespace`] keeps the strict URI.
+pub const STRICT_NAMESPACES: &[(&str, &str)] = &[
+    ("w", "http://purl.oclc.org/ooxml/wordprocessingml/main"),
+    ("r", "http://purl.oclc.org/ooxml/officeDocument/relationships"),
+    ("a", "http://purl.oclc.org/ooxml/drawingml/main"),
+    ("p", "http://purl.oclc.org/ooxml/presentationml/main"),
+    ("wp", "http://purl.oclc.org/ooxml/drawingml/wordprocessingDrawing"),
+    ("pic", "http://purl.oclc.org/ooxml/drawingml/picture"),
+    ("c", "http://purl.oclc.org/ooxml/drawingml/chart"),
+    ("dgm", "http://purl.oclc.org/ooxml/drawingml/diagram"),
+    ("m", "http://purl.oclc.org/ooxml/officeDocument/math"),
+];
+
+pub const WORDPROCESSINGML_NAMESPACE: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+pub const RELATIONSHIPS_NAMESPACE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+pub const DRAWINGML_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+pub const PRESENTATIONML_NAMESPACE: &str = "http://schemas.openxmlformats.org/presentationml/2006/main";
+
+/// Returns the prefix the parsers use for a known namespace, either transitional or strict.
+pub fn canonical_prefix(namespace: &str) -> Option<&'static str> {
+    KNOWN_NAMESPACES
+        .iter()
+        .chain(STRICT_NAMESPACES.iter())
+        .find(|(_, uri)| *uri == namespace)
+        .map(|(prefix, _)| *prefix)
+}
+
+/// Returns whether the namespace is one of the [`STRICT_NAMESPACES`].
+pub fn is_strict_namespace(namespace: &str) -> bool {
+    STRICT_NAMESPACES.iter().any(|(_, uri)| *uri == namespace)
+}
+
+/// Returns the namespace of a known canonical prefix.
+pub fn known_namespace(prefix: &str) -> Option<&'static str> {
+    KNOWN_NAMESPACES
+        .iter()
+        .find(|(known_prefix, _)| *known_prefix == prefix)
+        .map(|(_, uri)| *uri)
+}
+
 /// Represents an implementation independent xml node
 #[derive(Debug, Clone, PartialEq)]
 pub struct XmlNode {
-    pub name: String,
+    /// The qualified name of the element. Elements of [`KNOWN_NAMESPACES`] use the canonical prefix. The names are
+    /// shared by all the elements with the same name in the parsed document.
+    pub name: Arc<str>,
+    /// The namespace URI of the element, if the element's prefix (or the default namespace) is declared. The URI is
+    /// shared by all the elements of the namespace.
+    pub namespace: Option<Arc<str>>,
     pub child_nodes: Vec<XmlNode>,
-    pub attributes: HashMap<String, String>,
+    /// The attributes keyed by their qualified names. Attributes of [`KNOWN_NAMESPACES`] use the canonical prefix.
+    /// The names are shared by all the attributes with the same name in the parsed document.
+    pub attributes: HashMap<Arc<str>, String>,
+    /// The namespace URIs of the attribute prefixes that aren't bound to one of the [`KNOWN_NAMESPACES`], keyed by
+    /// prefix. The namespaces of the canonical prefixes are returned by [`known_namespace`].
+    pub attribute_namespaces: HashMap<Arc<str>, Arc<str>>,
     pub text: Option<String>,
 }
 
@@ -27,11 +126,13 @@ impl Display for XmlNode {
 }
 
 impl XmlNode {
-    pub fn new<T: Into<String>>(name: T) -> Self {
+    pub fn new<T: Into<Arc<str>>>(name: T) -> Self {
         Self {
             name: name.into(),
+            namespace: None,
             child_nodes: Vec::new(),
             attributes: HashMap::new(),
+            attribute_namespaces: HashMap::new(),
             text: None,
         }
     }
@@ -39,20 +140,86 @@ impl XmlNode {
     pub fn local_name(&self) -> &str {
         match self.name.find(':') {
             Some(idx) => self.name.split_at(idx + 1).1,
-            None => self.name.as_str(),
+            None => &self.name,
+        }
+    }
+
+    /// Returns whether the node is the element with the given namespace and local name. Strict and transitional
+    /// namespaces are equivalent. Elements without a declared namespace, like the ones in most test fragments, are
+    /// matched by their canonical prefix.
+    pub fn is_element(&self, namespace: &str, local_name: &str) -> bool {
+        if self.local_name() != local_name {
+            return false;
+        }
+
+        match &self.namespace {
+            Some(node_namespace) if &**node_namespace == namespace => true,
+            Some(node_namespace) => {
+                matches!(canonical_prefix(node_namespace), Some(prefix) if canonical_prefix(namespace) == Some(prefix))
+            }
+            None => matches!(canonical_prefix(namespace), Some(prefix) if self.name.split(':').next() == Some(prefix)),
         }
     }
 
-    fn from_quick_xml_element(xml_element: &BytesStart<'_>) -> Result<Self, ::std::str::Utf8Error> {
-        let name = ::std::str::from_utf8(xml_element.name())?;
-        let mut node = Self::new(name);
+    /// Returns the value of the attribute with the given namespace and local name.
+    ///
+    /// ```
+    /// use oox::xml::{XmlNode, WORDPROCESSINGML_NAMESPACE};
+    /// use std::str::FromStr;
+    ///
+    /// let xml = r#"<word:jc word:val="center"
+    ///     xmlns:word="http://schemas.openxmlformats.org/wordprocessingml/2006/main"></word:jc>"#;
+    /// let node = XmlNode::from_str(xml).unwrap();
+    /// assert_eq!(&*node.name, "w:jc");
+    /// assert_eq!(node.namespace.as_deref(), Some(WORDPROCESSINGML_NAMESPACE));
+    /// assert_eq!(node.attribute_ns(WORDPROCESSINGML_NAMESPACE, "val").unwrap(), "center");
+    /// ```
+    pub fn attribute_ns(&self, namespace: &str, local_name: &str) -> Option<&String> {
+        let prefix = canonical_prefix(namespace)?;
+        self.attributes.get(format!("{}:{}", prefix, local_name).as_str())
+    }
+
+    /// Returns the namespace of an attribute of the node by its key, if the key uses a canonical prefix.
+    pub fn attribute_namespace(key: &str) -> Option<&'static str> {
+        let (prefix, _) = key.split_once(':')?;
+        known_namespace(prefix)
+    }
+
+    fn from_quick_xml_element(
+        xml_element: &BytesStart<'_>,
+        state: &mut ParserState<'_>,
+    ) -> Result<Self, ::std::str::Utf8Error> {
+        // Namespace declarations apply to the element declaring them, so they're collected before resolving names
+        let mut attribute_count = 0;
+        for attr in xml_element.attributes().flatten() {
+            let key = ::std::str::from_utf8(attr.key)?;
+            if key == "xmlns" {
+                state
+                    .bindings
+                    .push((String::new(), Arc::from(::std::str::from_utf8(&attr.value)?)));
+            } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                state
+                    .bindings
+                    .push((String::from(prefix), Arc::from(::std::str::from_utf8(&attr.value)?)));
+            }
+
+            attribute_count += 1;
+        }
 
-        for attr in xml_element.attributes() {
-            if let Ok(a) = attr {
-                let key_str = ::std::str::from_utf8(&a.key)?;
-                let value_str = ::std::str::from_utf8(&a.value)?;
-                node.attributes.insert(String::from(key_str), String::from(value_str));
+        let (name, namespace) = resolve_name(::std::str::from_utf8(xml_element.name())?, &state.bindings, false);
+        let mut node = Self::new(state.intern(&name));
+        node.namespace = namespace;
+        node.attributes.reserve(attribute_count);
+        for attr in xml_element.attributes().flatten() {
+            let (key, namespace) = resolve_name(::std::str::from_utf8(attr.key)?, &state.bindings, true);
+            let key = state.intern(&key);
+            if let (Some(namespace), Some((prefix, _))) = (namespace, key.split_once(':')) {
+                if canonical_prefix(&namespace).is_none() && !node.attribute_namespaces.contains_key(prefix) {
+                    node.attribute_namespaces.insert(state.intern(prefix), namespace);
+                }
             }
+            node.attributes
+                .insert(key, String::from(::std::str::from_utf8(&attr.value)?));
         }
 
         Ok(node)
@@ -60,56 +227,219 @@ impl XmlNode {
 
     fn parse_child_elements(
         xml_node: &mut Self,
-        xml_element: &BytesStart<'_>,
         xml_reader: &mut Reader<&[u8]>,
+        buffer: &mut Vec<u8>,
+        state: &mut ParserState<'_>,
     ) -> Result<Vec<Self>, ::std::str::Utf8Error> {
         let mut child_nodes = Vec::new();
-
-        let mut buffer = Vec::new();
         loop {
-            match xml_reader.read_event(&mut buffer) {
-                Ok(Event::Start(ref element)) => {
-                    let mut node = Self::from_quick_xml_element(element)?;
-                    node.child_nodes = Self::parse_child_elements(&mut node, element, xml_reader)?;
-                    child_nodes.push(node);
-                }
-                Ok(Event::Text(text)) => {
-                    xml_node.text = text.unescape_and_decode(xml_reader).ok();
-                }
-                Ok(Event::Empty(ref element)) => {
-                    let node = Self::from_quick_xml_element(element)?;
-                    child_nodes.push(node);
+            buffer.clear();
+            let scope = state.bindings.len();
+            let offset = xml_reader.buffer_position();
+            // The new node copies everything from the event, so the buffer can be reused for the child nodes
+            let (mut node, has_children) = match xml_reader.read_event(buffer) {
+                Ok(Event::Start(ref element)) => (Self::from_quick_xml_element(element, state)?, true),
+                Ok(Event::Empty(ref element)) => (Self::from_quick_xml_element(element, state)?, false),
+                Ok(Event::Text(ref text)) => {
+                    xml_node.set_text(text)?;
+                    continue;
                 }
-                Ok(Event::End(ref element)) => {
-                    if element.name() == xml_element.name() {
-                        break;
+                // The reader checks that end tags match their start tags, so this is the end tag of the parent
+                Ok(Event::End(_)) | Ok(Event::Eof) => break,
+                _ => continue,
+            };
+
+            if !state.accepts(&node, offset) {
+                if has_children {
+                    // The skipped element is scanned without building nodes. Its end tag is the first one at the
+                    // same depth, so the name doesn't have to match the qualified name used by the document.
+                    let mut depth = 0;
+                    loop {
+                        buffer.clear();
+                        match xml_reader.read_event(buffer) {
+                            Ok(Event::Start(_)) => depth += 1,
+                            Ok(Event::End(_)) if depth == 0 => break,
+                            Ok(Event::End(_)) => depth -= 1,
+                            Ok(Event::Eof) | Err(_) => break,
+                            _ => (),
+                        }
                     }
                 }
-                Ok(Event::Eof) => {
-                    break;
-                }
-                _ => (),
+                state.bindings.truncate(scope);
+                continue;
             }
 
-            buffer.clear();
+            if has_children {
+                if state.filter.is_some() {
+                    state.ancestors.push(String::from(&*node.name));
+                }
+                node.child_nodes = Self::parse_child_elements(&mut node, xml_reader, buffer, state)?;
+                if state.filter.is_some() {
+                    state.ancestors.pop();
+                }
+            }
+            state.bindings.truncate(scope);
+            child_nodes.push(node);
         }
 
         Ok(child_nodes)
     }
+
+    /// Sets the text of the node. The whitespace between child elements is reported as text too, so the allocation of
+    /// the previous text is reused.
+    fn set_text(&mut self, text: &BytesText<'_>) -> Result<(), ::std::str::Utf8Error> {
+        let unescaped = match text.unescaped() {
+            Ok(unescaped) => unescaped,
+            Err(_) => {
+                self.text = None;
+                return Ok(());
+            }
+        };
+
+        let decoded = ::std::str::from_utf8(&unescaped)?;
+        let text = self.text.get_or_insert_with(String::new);
+        text.clear();
+        text.push_str(decoded);
+        Ok(())
+    }
+}
+
+/// An element of a document being parsed by [`XmlNode::from_str_filtered`].
+pub struct ElementContext<'a> {
+    /// The names of the ancestors of the element, starting with the root element.
+    pub ancestors: &'a [String],
+    /// The element with its attributes. Its child nodes are not parsed yet.
+    pub node: &'a XmlNode,
+    /// The byte offset of the start tag of the element in the document.
+    pub offset: usize,
+}
+
+/// Decides whether an element is parsed, see [`XmlNode::from_str_filtered`].
+pub type ElementFilter<'f> = dyn FnMut(&ElementContext<'_>) -> bool + 'f;
+
+/// The state shared by the nodes of a document while parsing it.
+#[derive(Default)]
+struct ParserState<'f> {
+    /// The namespace declarations in scope, the innermost last.
+    bindings: Vec<(String, Arc<str>)>,
+    /// The element and attribute names found so far. Documents use a small set of names repeatedly, so sharing them
+    /// saves an allocation per element and attribute.
+    names: HashSet<Arc<str>>,
+    filter: Option<&'f mut ElementFilter<'f>>,
+    /// The names of the elements being parsed. Only tracked when there's a filter.
+    ancestors: Vec<String>,
+}
+
+impl ParserState<'_> {
+    /// Returns whether the child of the parent is parsed.
+    fn accepts(&mut self, node: &XmlNode, offset: usize) -> bool {
+        let filter = match &mut self.filter {
+            Some(filter) => filter,
+            None => return true,
+        };
+
+        filter(&ElementContext {
+            ancestors: &self.ancestors,
+            node,
+            offset,
+        })
+    }
+
+    fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(interned.clone());
+        interned
+    }
+}
+
+/// Resolves the namespace of a qualified name using the namespace declarations in scope and rewrites the prefix of
+/// known namespaces to the canonical one. Unprefixed attributes don't belong to any namespace, unprefixed elements
+/// belong to the default namespace.
+fn resolve_name<'a>(
+    name: &'a str,
+    bindings: &[(String, Arc<str>)],
+    is_attribute: bool,
+) -> (Cow<'a, str>, Option<Arc<str>>) {
+    let (prefix, local_name) = match name.split_once(':') {
+        Some(("xmlns", _)) | Some(("xml", _)) => return (Cow::Borrowed(name), None),
+        Some((prefix, local_name)) => (prefix, local_name),
+        None if is_attribute || name == "xmlns" => return (Cow::Borrowed(name), None),
+        None => ("", name),
+    };
+
+    let namespace = match bindings.iter().rev().find(|(bound_prefix, _)| bound_prefix == prefix) {
+        Some((_, namespace)) => namespace.clone(),
+        None => return (Cow::Borrowed(name), None),
+    };
+
+    match canonical_prefix(&namespace) {
+        Some(canonical) if canonical != prefix => {
+            (Cow::Owned(format!("{}:{}", canonical, local_name)), Some(namespace))
+        }
+        _ => (Cow::Borrowed(name), Some(namespace)),
+    }
 }
 
 impl FromStr for XmlNode {
     type Err = InvalidXmlError;
 
     fn from_str(xml_string: &str) -> Result<Self, Self::Err> {
-        let mut xml_reader = Reader::from_str(xml_string.as_ref());
+        Self::parse(xml_string.as_bytes(), ParserState::default())
+    }
+}
+
+impl XmlNode {
+    /// Parses a document skipping the elements rejected by the filter. The filter is called with every element
+    /// except the root before its child nodes are parsed. The child nodes of a rejected element are skipped without
+    /// building nodes for them, which makes the filter useful for parsing a part of a large document.
+    ///
+    /// ```
+    /// use oox::xml::XmlNode;
+    ///
+    /// let xml = "<root><item /><skipped><item /><item /></skipped><item /></root>";
+    /// let node = XmlNode::from_str_filtered(xml, &mut |context| &*context.node.name != "skipped").unwrap();
+    /// assert_eq!(node.child_nodes.len(), 2);
+    /// ```
+    pub fn from_str_filtered<'f>(xml_string: &str, filter: &'f mut ElementFilter<'f>) -> Result<Self, InvalidXmlError> {
+        let state = ParserState {
+            filter: Some(filter),
+            ..Default::default()
+        };
+        Self::parse(xml_string.as_bytes(), state)
+    }
+
+    /// Parses a document from a buffer, e.g. a memory-mapped part, without converting it to a `String` first. The
+    /// names and values are checked to be valid UTF-8 one by one while building the nodes.
+    ///
+    /// ```
+    /// use oox::xml::XmlNode;
+    ///
+    /// let node = XmlNode::from_bytes(b"<w:p><w:r /></w:p>").unwrap();
+    /// assert_eq!(&*node.child_nodes[0].name, "w:r");
+    /// ```
+    pub fn from_bytes(xml: &[u8]) -> Result<Self, InvalidXmlError> {
+        Self::parse(xml, ParserState::default())
+    }
+
+    fn parse(xml: &[u8], mut state: ParserState<'_>) -> Result<Self, InvalidXmlError> {
+        let mut xml_reader = Reader::from_reader(xml);
         let mut buffer = Vec::new();
         loop {
             match xml_reader.read_event(&mut buffer) {
                 Ok(Event::Start(ref element)) => {
-                    let mut root_node = Self::from_quick_xml_element(element).map_err(|_| InvalidXmlError {})?;
-                    root_node.child_nodes = Self::parse_child_elements(&mut root_node, element, &mut xml_reader)
-                        .map_err(|_| InvalidXmlError {})?;
+                    let mut root_node =
+                        Self::from_quick_xml_element(element, &mut state).map_err(|_| InvalidXmlError {})?;
+                    if state.filter.is_some() {
+                        state.ancestors.push(String::from(&*root_node.name));
+                    }
+                    buffer.clear();
+                    root_node.child_nodes =
+                        Self::parse_child_elements(&mut root_node, &mut xml_reader, &mut buffer, &mut state)
+                            .map_err(|_| InvalidXmlError {})?;
                     return Ok(root_node);
                 }
                 Ok(Event::Eof) => break,
@@ -123,6 +453,342 @@ impl FromStr for XmlNode {
     }
 }
 
+/// An element borrowing its names, attribute values and text from the buffer it was parsed from, see
+/// [`XmlNodeRef::parse`].
+///
+/// Parsing into an [`XmlNode`] copies every name and value of the document. When the buffer outlives the parsed tree,
+/// e.g. a memory-mapped part, the tree can borrow from it instead. Only the names rewritten to a canonical prefix and
+/// the texts containing entity references are copied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XmlNodeRef<'a> {
+    /// The qualified name of the element, using the canonical prefix like [`XmlNode::name`].
+    pub name: Cow<'a, str>,
+    pub namespace: Option<Arc<str>>,
+    /// The attributes in document order. The values are kept escaped, like the values of [`XmlNode::attributes`].
+    pub attributes: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    /// The namespace URIs of the attribute prefixes, like [`XmlNode::attribute_namespaces`].
+    pub attribute_namespaces: Vec<(Cow<'a, str>, Arc<str>)>,
+    pub child_nodes: Vec<XmlNodeRef<'a>>,
+    pub text: Option<Cow<'a, str>>,
+}
+
+impl<'a> XmlNodeRef<'a> {
+    /// Parses a document borrowing from the buffer.
+    ///
+    /// ```
+    /// use oox::xml::XmlNodeRef;
+    /// use std::borrow::Cow;
+    ///
+    /// let xml = br#"<w:p><w:r w:rsidR="00A1"><w:t>Hello</w:t></w:r></w:p>"#;
+    /// let node = XmlNodeRef::parse(xml).unwrap();
+    /// let run = &node.child_nodes[0];
+    /// assert_eq!(run.attribute("w:rsidR"), Some("00A1"));
+    /// assert!(matches!(run.child_nodes[0].text, Some(Cow::Borrowed("Hello"))));
+    /// ```
+    pub fn parse(xml: &'a [u8]) -> Result<Self, InvalidXmlError> {
+        let mut parser = BorrowingParser {
+            input: xml,
+            reader: Reader::from_reader(xml),
+            bindings: Vec::new(),
+        };
+        parser.parse_root().map_err(|_| InvalidXmlError {})
+    }
+
+    pub fn local_name(&self) -> &str {
+        match self.name.find(':') {
+            Some(idx) => &self.name[idx + 1..],
+            None => &self.name,
+        }
+    }
+
+    /// Returns the value of the attribute with the given qualified name.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_ref())
+    }
+
+    /// Copies the tree into an [`XmlNode`], e.g. to parse a part of it with the models of this library.
+    pub fn to_xml_node(&self) -> XmlNode {
+        let mut node = XmlNode::new(self.name.as_ref());
+        node.namespace = self.namespace.clone();
+        node.attributes = self
+            .attributes
+            .iter()
+            .map(|(key, value)| (Arc::from(key.as_ref()), String::from(value.as_ref())))
+            .collect();
+        node.attribute_namespaces = self
+            .attribute_namespaces
+            .iter()
+            .map(|(prefix, namespace)| (Arc::from(prefix.as_ref()), namespace.clone()))
+            .collect();
+        node.child_nodes = self.child_nodes.iter().map(XmlNodeRef::to_xml_node).collect();
+        node.text = self.text.as_deref().map(String::from);
+        node
+    }
+}
+
+/// Builds an [`XmlNodeRef`] tree. The reader copies every event into a buffer, so the slices of the events are mapped
+/// back to the input by their offsets.
+struct BorrowingParser<'a> {
+    input: &'a [u8],
+    reader: Reader<&'a [u8]>,
+    /// The namespace declarations in scope, the innermost last.
+    bindings: Vec<(String, Arc<str>)>,
+}
+
+impl<'a> BorrowingParser<'a> {
+    fn parse_root(&mut self) -> Result<XmlNodeRef<'a>, InvalidXmlError> {
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            let offset = self.reader.buffer_position();
+            match self.reader.read_event(&mut buffer) {
+                Ok(Event::Start(ref element)) => {
+                    let mut root_node = self.element(element, offset + 1)?;
+                    self.parse_child_elements(&mut root_node, &mut buffer)?;
+                    return Ok(root_node);
+                }
+                Ok(Event::Eof) | Err(_) => return Err(InvalidXmlError {}),
+                _ => (),
+            }
+        }
+    }
+
+    fn parse_child_elements(
+        &mut self,
+        xml_node: &mut XmlNodeRef<'a>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), InvalidXmlError> {
+        loop {
+            buffer.clear();
+            let scope = self.bindings.len();
+            let offset = self.reader.buffer_position();
+            match self.reader.read_event(buffer) {
+                Ok(Event::Start(ref element)) => {
+                    let mut node = self.element(element, offset + 1)?;
+                    self.parse_child_elements(&mut node, buffer)?;
+                    xml_node.child_nodes.push(node);
+                }
+                Ok(Event::Empty(ref element)) => {
+                    let node = self.element(element, offset + 1)?;
+                    xml_node.child_nodes.push(node);
+                }
+                Ok(Event::Text(ref text)) => {
+                    xml_node.text = match text.unescaped() {
+                        Ok(Cow::Borrowed(unescaped)) => Some(self.borrow_str(text, offset, unescaped)?),
+                        Ok(Cow::Owned(unescaped)) => Some(Cow::Owned(
+                            String::from_utf8(unescaped).map_err(|_| InvalidXmlError {})?,
+                        )),
+                        Err(_) => None,
+                    };
+                }
+                Ok(Event::End(_)) | Ok(Event::Eof) => break,
+                Err(_) => return Err(InvalidXmlError {}),
+                _ => (),
+            }
+            self.bindings.truncate(scope);
+        }
+
+        Ok(())
+    }
+
+    /// Builds a node from a start tag, `base` is the offset of the tag in the input.
+    fn element(&mut self, element: &BytesStart<'_>, base: usize) -> Result<XmlNodeRef<'a>, InvalidXmlError> {
+        let mut attributes = Vec::new();
+        for attr in element.attributes() {
+            let attr = attr.map_err(|_| InvalidXmlError {})?;
+            let key = self.borrow_str(element, base, attr.key)?;
+            let value = match attr.value {
+                Cow::Borrowed(value) => self.borrow_str(element, base, value)?,
+                Cow::Owned(value) => Cow::Owned(String::from_utf8(value).map_err(|_| InvalidXmlError {})?),
+            };
+
+            if key == "xmlns" {
+                self.bindings.push((String::new(), Arc::from(value.as_ref())));
+            } else if let Some(prefix) = key.strip_prefix("xmlns:") {
+                self.bindings.push((String::from(prefix), Arc::from(value.as_ref())));
+            }
+            attributes.push((key, value));
+        }
+
+        let (name, namespace) = self.resolve_name(self.borrow_str(element, base, element.name())?, false);
+        let mut resolved_attributes = Vec::with_capacity(attributes.len());
+        let mut attribute_namespaces: Vec<(Cow<'a, str>, Arc<str>)> = Vec::new();
+        for (key, value) in attributes {
+            let (key, namespace) = self.resolve_name(key, true);
+            if let (Some(namespace), Some(idx)) = (namespace, key.find(':')) {
+                let is_recorded = attribute_namespaces.iter().any(|(prefix, _)| *prefix == key[..idx]);
+                if canonical_prefix(&namespace).is_none() && !is_recorded {
+                    let prefix = match &key {
+                        Cow::Borrowed(key) => Cow::Borrowed(&key[..idx]),
+                        Cow::Owned(key) => Cow::Owned(String::from(&key[..idx])),
+                    };
+                    attribute_namespaces.push((prefix, namespace));
+                }
+            }
+            resolved_attributes.push((key, value));
+        }
+
+        Ok(XmlNodeRef {
+            name,
+            namespace,
+            attributes: resolved_attributes,
+            attribute_namespaces,
+            child_nodes: Vec::new(),
+            text: None,
+        })
+    }
+
+    fn resolve_name(&self, name: Cow<'a, str>, is_attribute: bool) -> (Cow<'a, str>, Option<Arc<str>>) {
+        match name {
+            Cow::Borrowed(name) => resolve_name(name, &self.bindings, is_attribute),
+            Cow::Owned(name) => {
+                let (resolved, namespace) = resolve_name(&name, &self.bindings, is_attribute);
+                (Cow::Owned(resolved.into_owned()), namespace)
+            }
+        }
+    }
+
+    /// Returns the part of the input matching a slice of an event, or a copy of the slice if it can't be found in the
+    /// input. `event` is the content of the event and `base` is its offset in the input.
+    fn borrow_str(&self, event: &[u8], base: usize, slice: &[u8]) -> Result<Cow<'a, str>, InvalidXmlError> {
+        let input: &'a [u8] = self.input;
+        let offset = (slice.as_ptr() as usize).wrapping_sub(event.as_ptr() as usize);
+        if offset <= event.len() && slice.len() <= event.len() - offset {
+            let start = base + offset;
+            if let Some(borrowed) = input
+                .get(start..start + slice.len())
+                .filter(|borrowed| *borrowed == slice)
+            {
+                return ::std::str::from_utf8(borrowed)
+                    .map(Cow::Borrowed)
+                    .map_err(|_| InvalidXmlError {});
+            }
+        }
+
+        ::std::str::from_utf8(slice)
+            .map(|slice| Cow::Owned(String::from(slice)))
+            .map_err(|_| InvalidXmlError {})
+    }
+}
+
+impl XmlNode {
+    /// Writes the node with its attributes, text and child nodes as xml. Attributes are written in the order of their
+    /// names, so the output doesn't depend on the order of parsing. Attribute values are kept escaped by the parser,
+    /// so they're written as they are, only quotes and `<` are escaped.
+    ///
+    /// ```
+    /// use oox::xml::XmlNode;
+    /// use std::str::FromStr;
+    ///
+    /// let node = XmlNode::from_str(r#"<a:p b="1" a="&lt;"><a:t>x &amp; y</a:t><a:br /></a:p>"#).unwrap();
+    /// assert_eq!(node.to_xml_string(), r#"<a:p a="&lt;" b="1"><a:t>x &amp; y</a:t><a:br/></a:p>"#);
+    /// ```
+    pub fn to_xml_string(&self) -> String {
+        let mut xml = String::new();
+        self.write_xml(&mut xml);
+        xml
+    }
+
+    pub fn write_xml(&self, xml: &mut String) {
+        xml.push('<');
+        xml.push_str(&self.name);
+        write_xml_attributes(&self.attributes, xml);
+
+        if self.child_nodes.is_empty() && self.text.is_none() {
+            xml.push_str("/>");
+            return;
+        }
+
+        xml.push('>');
+        if let Some(text) = &self.text {
+            xml.push_str(&escape_xml(text));
+        }
+        for child_node in &self.child_nodes {
+            child_node.write_xml(xml);
+        }
+        xml.push_str("</");
+        xml.push_str(&self.name);
+        xml.push('>');
+    }
+}
+
+fn write_xml_attributes(attributes: &HashMap<Arc<str>, String>, xml: &mut String) {
+    let mut attributes: Vec<_> = attributes.iter().collect();
+    attributes.sort();
+    for (name, value) in attributes {
+        xml.push(' ');
+        xml.push_str(name);
+        xml.push_str("=\"");
+        xml.push_str(&value.replace('"', "&quot;").replace('<', "&lt;"));
+        xml.push('"');
+    }
+}
+
+/// Escapes the markup characters of a text, so it can be written as the text or as an attribute value of an xml or
+/// html element. Apostrophes are escaped with a character reference, since html doesn't define `&apos;`.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Resolves the entity and character references of an attribute value, which [`XmlNode::attributes`] keep escaped.
+/// Values with invalid references are returned as they are.
+pub(crate) fn unescape_xml(value: &str) -> Cow<'_, str> {
+    if !value.contains('&') {
+        return Cow::Borrowed(value);
+    }
+
+    BytesText::from_escaped_str(value)
+        .unescaped()
+        .ok()
+        .and_then(|unescaped| String::from_utf8(unescaped.into_owned()).ok())
+        .map_or(Cow::Borrowed(value), Cow::Owned)
+}
+
+/// The attributes and child elements of an element that the model of the element doesn't recognize, e.g. the
+/// extensions of a newer version of Office. They are kept, so writing the element again doesn't lose them.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct XmlExtras {
+    /// The unrecognized attributes keyed by their qualified names.
+    pub attributes: HashMap<Arc<str>, String>,
+    /// The namespaces of the attribute prefixes, see [`XmlNode::attribute_namespaces`].
+    pub attribute_namespaces: HashMap<Arc<str>, Arc<str>>,
+    /// The unrecognized child elements in document order.
+    pub child_nodes: Vec<XmlNode>,
+}
+
+impl XmlExtras {
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty() && self.child_nodes.is_empty()
+    }
+
+    /// Writes the attributes as xml, each of them preceded by a space.
+    pub fn write_attributes(&self, xml: &mut String) {
+        write_xml_attributes(&self.attributes, xml);
+    }
+
+    /// Writes the child elements as xml.
+    pub fn write_child_nodes(&self, xml: &mut String) {
+        for child_node in &self.child_nodes {
+            child_node.write_xml(xml);
+        }
+    }
+}
+
 pub fn parse_xml_bool<T: AsRef<str>>(value: T) -> Result<bool, ParseBoolError> {
     match value.as_ref() {
         "true" | "1" => Ok(true),
@@ -132,15 +798,39 @@ pub fn parse_xml_bool<T: AsRef<str>>(value: T) -> Result<bool, ParseBoolError> {
 }
 
 pub fn zip_file_to_xml_node(zip_file: &mut ZipFile) -> Result<XmlNode, Box<dyn std::error::Error>> {
-    let mut xml_string = String::new();
-    zip_file.read_to_string(&mut xml_string)?;
-    XmlNode::from_str(xml_string.as_str()).map_err(Into::into)
+    let size = zip_file.size();
+    read_xml_node(zip_file, size)
+}
+
+/// Reads an xml document of the given size in bytes. The size is only used to preallocate the buffer.
+pub fn read_xml_node<R: Read>(reader: &mut R, size: u64) -> Result<XmlNode, Box<dyn std::error::Error>> {
+    let mut xml = Vec::with_capacity(size as usize);
+    reader.read_to_end(&mut xml)?;
+    XmlNode::from_bytes(&xml).map_err(Into::into)
+}
+
+/// Reads only the root element of an xml document with its attributes, e.g. to inspect the namespace declarations of
+/// a large part without parsing all of it. The returned node has no child nodes.
+pub fn read_root_element<R: BufRead>(reader: R) -> Result<XmlNode, Box<dyn std::error::Error>> {
+    let mut xml_reader = Reader::from_reader(reader);
+    let mut buffer = Vec::new();
+    loop {
+        match xml_reader.read_event(&mut buffer)? {
+            Event::Start(ref element) | Event::Empty(ref element) => {
+                return Ok(XmlNode::from_quick_xml_element(element, &mut ParserState::default())?);
+            }
+            Event::Eof => return Err(Box::new(InvalidXmlError {})),
+            _ => (),
+        }
+
+        buffer.clear();
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::XmlNode;
-    use std::str::FromStr;
+    use super::{escape_xml, unescape_xml, XmlNode};
+    use std::{borrow::Cow, str::FromStr};
 
     #[test]
     fn test_xml_parser() {
@@ -157,29 +847,163 @@ mod tests {
             .expect("Failed to read sample xml file to string");
 
         let root_node = XmlNode::from_str(file_content.as_str()).expect("Couldn't create XmlNode from string");
-        assert_eq!(root_node.name, "p:presentation");
+        assert_eq!(&*root_node.name, "p:presentation");
         assert_eq!(
             root_node.attributes.get("xmlns:a").unwrap(),
             "http://schemas.openxmlformats.org/drawingml/2006/main"
         );
 
-        assert_eq!(root_node.child_nodes[0].name, "p:sldMasterIdLst");
-        assert_eq!(root_node.child_nodes[1].name, "p:sldIdLst");
-        assert_eq!(root_node.child_nodes[2].name, "p:sldSz");
-        assert_eq!(root_node.child_nodes[3].name, "p:notesSz");
-        assert_eq!(root_node.child_nodes[4].name, "p:custDataLst");
-        assert_eq!(root_node.child_nodes[5].name, "p:defaultTextStyle");
-        assert_eq!(root_node.child_nodes[0].child_nodes[0].name, "p:sldMasterId");
+        assert_eq!(&*root_node.child_nodes[0].name, "p:sldMasterIdLst");
+        assert_eq!(&*root_node.child_nodes[1].name, "p:sldIdLst");
+        assert_eq!(&*root_node.child_nodes[2].name, "p:sldSz");
+        assert_eq!(&*root_node.child_nodes[3].name, "p:notesSz");
+        assert_eq!(&*root_node.child_nodes[4].name, "p:custDataLst");
+        assert_eq!(&*root_node.child_nodes[5].name, "p:defaultTextStyle");
+        assert_eq!(&*root_node.child_nodes[0].child_nodes[0].name, "p:sldMasterId");
 
         let slide_id_0_node = &root_node.child_nodes[1].child_nodes[0];
-        assert_eq!(slide_id_0_node.name, "p:sldId");
+        assert_eq!(&*slide_id_0_node.name, "p:sldId");
         assert_eq!(slide_id_0_node.attributes.get("id").unwrap(), "256");
         assert_eq!(slide_id_0_node.attributes.get("r:id").unwrap(), "rId2");
 
-        assert_eq!(root_node.child_nodes[1].child_nodes[1].name, "p:sldId");
+        assert_eq!(&*root_node.child_nodes[1].child_nodes[1].name, "p:sldId");
 
         let lvl1_ppr_defrpr_node = &root_node.child_nodes[5].child_nodes[1].child_nodes[0];
         assert_eq!(lvl1_ppr_defrpr_node.attributes.get("sz").unwrap(), "1800");
         assert_eq!(lvl1_ppr_defrpr_node.attributes.get("kern").unwrap(), "1200");
     }
+
+    #[test]
+    fn test_read_root_element() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body /></w:document>"#;
+
+        let root_node = super::read_root_element(xml.as_bytes()).unwrap();
+        assert_eq!(&*root_node.name, "w:document");
+        assert_eq!(
+            root_node.attributes.get("xmlns:w").unwrap(),
+            "http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+        );
+        assert!(root_node.child_nodes.is_empty());
+        assert!(super::read_root_element("".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_namespace_aware_names() {
+        let xml = r#"<doc:document xmlns:doc="http://schemas.openxmlformats.org/wordprocessingml/2006/main"
+                xmlns:rel="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+            <doc:hyperlink rel:id="rId1" doc:history="1" />
+            <item xmlns="http://schemas.openxmlformats.org/wordprocessingml/2006/main" val="1" />
+            <w:p xmlns:w="urn:example:custom"><w:r /></w:p>
+            <undeclared:p />
+        </doc:document>"#;
+
+        let root_node = XmlNode::from_str(xml).unwrap();
+        assert_eq!(&*root_node.name, "w:document");
+        assert_eq!(root_node.namespace.as_deref(), Some(super::WORDPROCESSINGML_NAMESPACE));
+        assert!(root_node.is_element(super::WORDPROCESSINGML_NAMESPACE, "document"));
+
+        let hyperlink_node = &root_node.child_nodes[0];
+        assert_eq!(&*hyperlink_node.name, "w:hyperlink");
+        assert_eq!(hyperlink_node.attributes.get("r:id").unwrap(), "rId1");
+        assert_eq!(
+            hyperlink_node
+                .attribute_ns(super::RELATIONSHIPS_NAMESPACE, "id")
+                .unwrap(),
+            "rId1"
+        );
+        assert_eq!(
+            XmlNode::attribute_namespace("w:history"),
+            Some(super::WORDPROCESSINGML_NAMESPACE)
+        );
+
+        // Unprefixed attributes don't belong to the default namespace
+        let item_node = &root_node.child_nodes[1];
+        assert_eq!(&*item_node.name, "w:item");
+        assert_eq!(item_node.attributes.get("val").unwrap(), "1");
+
+        // A prefix bound to an unknown namespace is kept, even if it's a canonical one
+        let custom_node = &root_node.child_nodes[2];
+        assert_eq!(&*custom_node.name, "w:p");
+        assert_eq!(custom_node.namespace.as_deref(), Some("urn:example:custom"));
+        assert!(!custom_node.is_element(super::WORDPROCESSINGML_NAMESPACE, "p"));
+        assert_eq!(
+            custom_node.child_nodes[0].namespace.as_deref(),
+            Some("urn:example:custom")
+        );
+
+        let undeclared_node = &root_node.child_nodes[3];
+        assert_eq!(&*undeclared_node.name, "undeclared:p");
+        assert_eq!(undeclared_node.namespace, None);
+    }
+
+    #[test]
+    fn test_strict_namespaces() {
+        let xml = r#"<w:document xmlns:w="http://purl.oclc.org/ooxml/wordprocessingml/main"
+                xmlns:rel="http://purl.oclc.org/ooxml/officeDocument/relationships">
+            <w:hyperlink rel:id="rId1" />
+        </w:document>"#;
+
+        let root_node = XmlNode::from_str(xml).unwrap();
+        assert!(super::is_strict_namespace(root_node.namespace.as_deref().unwrap()));
+        assert!(root_node.is_element(super::WORDPROCESSINGML_NAMESPACE, "document"));
+
+        let hyperlink_node = &root_node.child_nodes[0];
+        assert_eq!(hyperlink_node.attributes.get("r:id").unwrap(), "rId1");
+        assert_eq!(
+            hyperlink_node
+                .attribute_ns(super::RELATIONSHIPS_NAMESPACE, "id")
+                .unwrap(),
+            "rId1"
+        );
+    }
+
+    #[test]
+    fn test_xml_node_ref() {
+        let xml = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+            <doc:document xmlns:doc="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <doc:body>
+                    <doc:p doc:rsidR="00A1"><doc:r><doc:t>Fish &amp; chips</doc:t></doc:r></doc:p>
+                    <w:p xmlns:w="urn:example:custom" w:val="&lt;1"><w:t>Plain</w:t></w:p>
+                </doc:body>
+            </doc:document>"#;
+
+        let root_node = super::XmlNodeRef::parse(xml).unwrap();
+        assert_eq!(root_node.to_xml_node(), XmlNode::from_bytes(xml).unwrap());
+
+        let body_node = &root_node.child_nodes[0];
+        assert_eq!(&*body_node.name, "w:body");
+        assert_eq!(body_node.local_name(), "body");
+
+        // Names using a canonical prefix and unescaped texts are copied, everything else is borrowed
+        let paragraph_node = &body_node.child_nodes[0];
+        assert!(matches!(paragraph_node.name, Cow::Owned(_)));
+        assert!(matches!(paragraph_node.attributes[0].1, Cow::Borrowed("00A1")));
+        assert_eq!(paragraph_node.attribute("w:rsidR"), Some("00A1"));
+        let text = paragraph_node.child_nodes[0].child_nodes[0].text.as_ref().unwrap();
+        assert!(matches!(text, Cow::Owned(text) if text == "Fish & chips"));
+
+        let custom_node = &body_node.child_nodes[1];
+        assert!(matches!(custom_node.name, Cow::Borrowed("w:p")));
+        assert_eq!(custom_node.attribute("w:val"), Some("&lt;1"));
+        assert!(matches!(custom_node.child_nodes[0].text, Some(Cow::Borrowed("Plain"))));
+
+        assert!(super::XmlNodeRef::parse(b"<a><b></a>").is_err());
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_xml("plain"), "plain");
+    }
+
+    #[test]
+    fn test_unescape_xml() {
+        assert_eq!(unescape_xml("a=1&amp;b=&lt;2&gt;&#39;&#x41;"), "a=1&b=<2>'A");
+        assert_eq!(unescape_xml("plain"), "plain");
+        assert_eq!(unescape_xml("broken &unknown;"), "broken &unknown;");
+    }
 }
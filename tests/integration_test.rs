@@ -2,11 +2,29 @@
 extern crate oox;
 
 use oox::{
-    docx::package::Package as DocxPackage,
+    docx::{
+        package::Package as DocxPackage,
+        text::{RevisionView, TextOptions},
+        validation::{ParseDiagnostic, ParseOptions},
+        wml::document::{BlockLevelElts, ContentBlockContent, ContentRunContent, PContent, RunInnerContent},
+    },
+    error::UnsupportedFormat,
     pptx::package::Package as PptxPackage,
-    shared::drawingml::coordsys::{Point2D, PositiveSize2D},
+    shared::{
+        drawingml::coordsys::{Point2D, PositiveSize2D},
+        filekind::FileKind,
+        package::OoxPackage,
+        partkind::{PackageParts, PartKind},
+        sharedtypes::ConformanceClass,
+    },
 };
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
 
 #[test]
 fn test_docx_package_load() {
@@ -28,6 +46,152 @@ fn test_docx_package_load() {
     assert_eq!(package.medias.len(), 4);
     assert_eq!(package.themes.len(), 1);
     package.themes.get("theme1").unwrap();
+    assert_eq!(package.unsupported_parts().len(), 8);
+    let numbering_relationships = package
+        .unsupported_parts()
+        .iter()
+        .find(|part| part.part_name == "word/_rels/numbering.xml.rels")
+        .unwrap();
+    assert_eq!(numbering_relationships.kind, PartKind::Relationships);
+    assert_eq!(numbering_relationships.size, 289);
+    assert_eq!(package.web_settings.as_ref().unwrap().optimize_for_browser, Some(true));
+
+    assert!(package.font_table.is_some());
+    let embedded_fonts = package.embedded_fonts().unwrap();
+    assert_eq!(embedded_fonts.len(), 6);
+    for embedded_font in &embedded_fonts {
+        let version = &embedded_font.bytes[..4];
+        assert!(
+            version == [0, 1, 0, 0] || version == b"OTTO" || version == b"true",
+            "{} is not a font file",
+            embedded_font.part_name
+        );
+    }
+
+    let report = package.validate().unwrap();
+    assert!(report.is_conformant(), "{:?}", report.violations);
+    assert!(package.producer_info().unwrap().producer.is_word());
+
+    let text_map = package.main_document.as_ref().unwrap().text_map(RevisionView::Accepted);
+    assert_eq!(text_map.text, package.extract_text(&TextOptions::default()));
+    let options = ParseOptions {
+        strict: true,
+        conformance_class: Some(ConformanceClass::Transitional),
+        ..Default::default()
+    };
+    DocxPackage::from_file_with_options(&sample_docx_file, options).unwrap();
+    let options = ParseOptions {
+        conformance_class: Some(ConformanceClass::Strict),
+        ..Default::default()
+    };
+    assert!(DocxPackage::from_file_with_options(&sample_docx_file, options).is_err());
+
+    let parts = PackageParts::from_file(&sample_docx_file).unwrap();
+    assert_eq!(
+        parts.first_of_kind(PartKind::MainDocument).unwrap().name,
+        "word/document.xml"
+    );
+    assert_eq!(parts.parts_of_kind(PartKind::Image).count(), 4);
+    assert_eq!(parts.parts_of_kind(PartKind::Theme).count(), 1);
+
+    let metadata = OoxPackage::open_metadata_only(&sample_docx_file).unwrap();
+    assert_eq!(metadata.main_part_name().as_deref(), Some("word/document.xml"));
+    assert_eq!(metadata.main_part_kind(), Some(PartKind::MainDocument));
+    assert_eq!(metadata.app_info, package.app_info);
+    assert_eq!(metadata.core, package.core);
+}
+
+#[test]
+fn test_docx_package_parse_report() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_docx_file = manifest_dir.join("tests/sample.docx");
+
+    let report = DocxPackage::from_file_with_report(&sample_docx_file).unwrap();
+    assert!(!report.has_errors());
+    assert!(report.parsed_parts.iter().any(|part| part == "word/footnotes.xml"));
+
+    // Copy the sample with a malformed footnotes part
+    let broken_docx_file = std::env::temp_dir().join("oox_broken_footnotes.docx");
+    {
+        let mut zipper = ZipArchive::new(File::open(&sample_docx_file).unwrap()).unwrap();
+        let mut zip_writer = ZipWriter::new(File::create(&broken_docx_file).unwrap());
+        for idx in 0..zipper.len() {
+            let mut zip_file = zipper.by_index(idx).unwrap();
+            let mut contents = Vec::new();
+            zip_file.read_to_end(&mut contents).unwrap();
+            if zip_file.name() == "word/footnotes.xml" {
+                contents = b"<w:footnotes><w:footnote w:type=\"invalid\"/></w:footnotes>".to_vec();
+            }
+
+            zip_writer.start_file(zip_file.name(), FileOptions::default()).unwrap();
+            zip_writer.write_all(&contents).unwrap();
+        }
+        zip_writer.finish().unwrap();
+    }
+
+    assert!(DocxPackage::from_file(&broken_docx_file).is_err());
+
+    let report = DocxPackage::from_file_with_report(&broken_docx_file).unwrap();
+    assert_eq!(report.errors.len(), 1);
+    assert!(report.error_for("word/footnotes.xml").is_some());
+    assert!(report.package.footnotes.is_none());
+    assert!(report.package.main_document.is_some());
+    assert!(report.package.styles.is_some());
+
+    let report = DocxPackage::from_file_lenient(&broken_docx_file).unwrap();
+    std::fs::remove_file(&broken_docx_file).unwrap();
+    assert!(!report.has_errors());
+    assert_eq!(report.diagnostics.len(), 1);
+    assert!(matches!(
+        &report.diagnostics[0],
+        ParseDiagnostic::SkippedElement { part_name, path, .. }
+            if part_name == "word/footnotes.xml" && path == "/w:footnotes/w:footnote"
+    ));
+    assert!(report.package.footnotes.unwrap().0.is_empty());
+}
+
+#[test]
+fn test_docx_media() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_docx_file = manifest_dir.join("tests/sample.docx");
+
+    let package = DocxPackage::from_file(&sample_docx_file).unwrap();
+
+    let media = package.media_for("rId10").unwrap().unwrap();
+    assert_eq!(media.part_name, "word/media/image2.png");
+    assert_eq!(media.content_type.as_deref(), Some("image/png"));
+    assert!(media.bytes.starts_with(b"\x89PNG"));
+    assert!(package.media_for("rId9").unwrap().is_none());
+
+    let html = package.to_html(&Default::default());
+    assert!(html.contains(r#"<img src="data:image/png;base64,iVBORw0KGgo"#));
+
+    let body = package.main_document.as_ref().unwrap().body.as_ref().unwrap();
+    let drawing = body
+        .block_level_elements
+        .iter()
+        .filter_map(|element| match element {
+            BlockLevelElts::Chunk(ContentBlockContent::Paragraph(paragraph)) => Some(paragraph),
+            _ => None,
+        })
+        .flat_map(|paragraph| paragraph.contents.iter())
+        .filter_map(|content| match content {
+            PContent::ContentRunContent(content) => match content.as_ref() {
+                ContentRunContent::Run(run) => Some(run),
+                _ => None,
+            },
+            _ => None,
+        })
+        .flat_map(|run| run.run_inner_contents.iter())
+        .find_map(|inner_content| match inner_content {
+            RunInnerContent::Drawing(drawing) => drawing.0.first(),
+            _ => None,
+        })
+        .unwrap();
+
+    let medias = package.drawing_media(drawing).unwrap();
+    assert_eq!(medias.len(), 1);
+    assert_eq!(medias[0].size_in_pixels(96.0), Some((14, 14)));
 }
 
 #[test]
@@ -39,7 +203,7 @@ fn test_pptx_package_load() {
     let document = PptxPackage::from_file(&sample_pptx_file).unwrap();
     let mut slides = document.slides();
     {
-        let first_slide = slides.next().unwrap();
+        let first_slide = slides.next().unwrap().unwrap();
         let sptree = &first_slide.common_slide_data.shape_tree;
         assert_eq!(sptree.non_visual_props.drawing_props.id, 1);
         let transform = sptree.group_shape_props.transform.as_ref().unwrap();
@@ -51,7 +215,7 @@ fn test_pptx_package_load() {
     }
 
     {
-        let second_slide = slides.next().unwrap();
+        let second_slide = slides.next().unwrap().unwrap();
         let sptree = &second_slide.common_slide_data.shape_tree;
         assert_eq!(sptree.non_visual_props.drawing_props.id, 1);
         let transform = sptree.group_shape_props.transform.as_ref().unwrap();
@@ -64,3 +228,60 @@ fn test_pptx_package_load() {
 
     assert_eq!(slides.next().is_none(), true);
 }
+
+#[test]
+fn test_pptx_lazy_slides() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let sample_pptx_file = manifest_dir.join("tests/sample.pptx");
+
+    let document = PptxPackage::from_file(&sample_pptx_file).unwrap();
+    assert_eq!(document.slide_count(), 2);
+    assert!(document.slide_cache.is_empty());
+
+    let second_slide = document.slide(1).unwrap();
+    assert_eq!(document.slide_cache.len(), 1);
+    assert!(Arc::ptr_eq(&second_slide, &document.slide(1).unwrap()));
+    assert_eq!(document.slide_cache.len(), 1);
+
+    assert!(document.slide(2).is_err());
+    assert_eq!(document.slides().filter(Result::is_ok).count(), 2);
+    assert_eq!(document.slide_cache.len(), 2);
+
+    assert!(document.slide_inheritance(0).is_some());
+    assert!(document.slide_inheritance(2).is_none());
+
+    // The cache doesn't take part in comparison
+    let fresh_document = PptxPackage::from_file(&sample_pptx_file).unwrap();
+    assert!(fresh_document.slide_cache.is_empty());
+    assert_eq!(document, fresh_document);
+
+    std::thread::scope(|scope| {
+        for index in 0..fresh_document.slide_count() {
+            let document = &fresh_document;
+            scope.spawn(move || assert!(document.slide(index).is_ok()));
+        }
+    });
+    assert_eq!(fresh_document.slide_cache.len(), 2);
+}
+
+#[test]
+fn test_unsupported_format() {
+    let legacy_doc_file = std::env::temp_dir().join("oox_legacy.doc");
+    let mut contents = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1".to_vec();
+    contents.resize(512, 0);
+    std::fs::write(&legacy_doc_file, &contents).unwrap();
+
+    let docx_error = DocxPackage::from_file(&legacy_doc_file).unwrap_err();
+    let pptx_error = PptxPackage::from_file(&legacy_doc_file).unwrap_err();
+    let metadata_error = OoxPackage::open_metadata_only(&legacy_doc_file).unwrap_err();
+    std::fs::remove_file(&legacy_doc_file).unwrap();
+
+    for error in [docx_error, pptx_error, metadata_error] {
+        assert_eq!(
+            error.downcast_ref::<UnsupportedFormat>(),
+            Some(&UnsupportedFormat {
+                detected: FileKind::CompoundFile
+            })
+        );
+    }
+}